@@ -0,0 +1,522 @@
+/*
+============================================================================
+操作モード状態機械モジュール (app_mode.rs)
+============================================================================
+
+【ファイル概要】
+エリア選択・キャプチャ・PDF変換のどれが有効かを表す状態機械 `AppMode` と、
+各遷移で実行する副作用（フックのインストール・オーバーレイの表示など）を `ModeEffect` として返すライブラリモジュールです。
+副作用そのものはバイナリ側（`mode_effects.rs` の `apply_mode_effects`）が実行するため、Win32 APIには依存しません。
+
+【主要機能】
+1.  **`AppMode`**: 操作モードの列挙型と遷移メソッド（許可されない遷移は `None` を返して無視）
+2.  **`ModeTransition`**: 遷移の種類（テストで全モード×全遷移を列挙するため）
+3.  **`ModeEffect`**: 遷移ごとに実行する副作用の一覧
+
+【技術仕様】
+-   **副作用の順序**: 開始時は「フック → オーバーレイ → コントロール」、終了時はオーバーレイ・フックを外してから
+    コントロールを更新する（エリア選択はオーバーレイを先に隠し、キャプチャはフックを先に外す）
+-   **排他**: 各モードは1つの列挙型で表すため、エリア選択モードとキャプチャモードが同時に有効になることはない
+
+【AI解析用：依存関係】
+-   `app_state.rs`（バイナリ側）: `AppState.mode` として保持し、`is_area_select_mode` などの判定メソッドから参照
+-   `mode_effects.rs`（バイナリ側）: `apply_mode_effects` で `ModeEffect` をWin32の処理に対応付けて実行
+-   `area_select.rs` / `screen_capture.rs` / `hook/mouse.rs` / `ui/pdf_export_button_handler.rs`（バイナリ側）: 遷移の呼び出し元
+*/
+
+/// 【操作モード】エリア選択・キャプチャ・PDF変換のどれが有効かを表す状態機械
+///
+/// 各モードは同時に有効にならないため、独立したフラグではなく1つの列挙型で表し、
+/// 「エリア選択中のキャプチャモード」のような不正な組み合わせを型で防ぎます。
+/// モードの変更は遷移メソッドでのみ行い、現在のモードから許可されない遷移は `None` を返して無視します。
+/// 許可された遷移は、呼び出し側が実行する副作用（`ModeEffect`）の一覧を返します。
+///
+/// # 遷移
+/// ```text
+/// Idle ──enter_area_select──▶ AreaSelecting { dragging: false } ◀──end_drag── { dragging: true }
+///   ▲                               │ begin_drag ───────────────────────────────────▲
+///   └───────exit_area_select────────┘（ドラッグ中からも可）
+/// Idle ──enter_capture──▶ Capturing { processing } ──exit_capture──▶ Idle
+///                          （set_capture_processing で処理中表示を切り替え）
+/// Idle ──enter_pdf_export──▶ ExportingPdf ──exit_pdf_export──▶ Idle
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AppMode {
+    /// 通常モード
+    #[default]
+    Idle,
+    /// エリア選択モード（`dragging`: マウス左ボタンを押してドラッグ中）
+    AreaSelecting { dragging: bool },
+    /// キャプチャモード（`processing`: キャプチャ処理中。オーバーレイに処理中アイコンを表示）
+    Capturing { processing: bool },
+    /// PDF変換中（UIスレッドで同期実行するため、全コントロールを無効化）
+    ExportingPdf,
+}
+
+/// 【遷移】`AppMode` に対する操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeTransition {
+    EnterAreaSelect,
+    BeginDrag,
+    EndDrag,
+    ExitAreaSelect,
+    EnterCapture,
+    SetCaptureProcessing(bool),
+    ExitCapture,
+    EnterPdfExport,
+    ExitPdfExport,
+}
+
+/// 【副作用】遷移に伴ってバイナリ側で実行する処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeEffect {
+    /// マウス・キーボードの低レベルフックをインストールする
+    InstallHooks,
+    /// マウス・キーボードの低レベルフックをアンインストールする
+    UninstallHooks,
+    /// エリア選択オーバーレイを表示する
+    ShowAreaSelectOverlay,
+    /// エリア選択オーバーレイを非表示にし、画面の固定で保存した固定フレームを解放する
+    HideAreaSelectOverlay,
+    /// エリア選択オーバーレイを再描画する（ドラッグ範囲・マスク領域の更新）
+    RefreshAreaSelectOverlay,
+    /// キャプチャモードオーバーレイを表示する
+    ShowCapturingOverlay,
+    /// キャプチャモードオーバーレイを非表示にする
+    HideCapturingOverlay,
+    /// キャプチャモードオーバーレイを再描画する（待機中・処理中アイコンの切り替え）
+    RefreshCapturingOverlay,
+    /// 入力コントロールの有効・無効をモードに合わせて更新する
+    UpdateInputControls,
+}
+
+use ModeEffect::*;
+
+const ENTER_AREA_SELECT_EFFECTS: &[ModeEffect] =
+    &[InstallHooks, ShowAreaSelectOverlay, UpdateInputControls];
+const EXIT_AREA_SELECT_EFFECTS: &[ModeEffect] =
+    &[HideAreaSelectOverlay, UninstallHooks, UpdateInputControls];
+const ENTER_CAPTURE_EFFECTS: &[ModeEffect] =
+    &[InstallHooks, ShowCapturingOverlay, UpdateInputControls];
+const EXIT_CAPTURE_EFFECTS: &[ModeEffect] =
+    &[UninstallHooks, HideCapturingOverlay, UpdateInputControls];
+
+impl AppMode {
+    /// 通常モードからエリア選択モードを開始する
+    #[must_use]
+    pub fn enter_area_select(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::EnterAreaSelect)
+    }
+
+    /// エリア選択モード中にドラッグを開始する（ドラッグ中に押し直した場合も開始し直す）
+    #[must_use]
+    pub fn begin_drag(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::BeginDrag)
+    }
+
+    /// ドラッグを終了し、エリア選択モードを継続する（マスク領域の追加後など）
+    #[must_use]
+    pub fn end_drag(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::EndDrag)
+    }
+
+    /// エリア選択モードを終了する（ドラッグ中の場合もドラッグごと終了）
+    #[must_use]
+    pub fn exit_area_select(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::ExitAreaSelect)
+    }
+
+    /// 通常モードからキャプチャモードを開始する（待機中表示から始める）
+    #[must_use]
+    pub fn enter_capture(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::EnterCapture)
+    }
+
+    /// キャプチャモード中の処理中表示を切り替える
+    #[must_use]
+    pub fn set_capture_processing(&mut self, processing: bool) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::SetCaptureProcessing(processing))
+    }
+
+    /// キャプチャモードを終了する
+    #[must_use]
+    pub fn exit_capture(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::ExitCapture)
+    }
+
+    /// 通常モードからPDF変換を開始する
+    #[must_use]
+    pub fn enter_pdf_export(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::EnterPdfExport)
+    }
+
+    /// PDF変換を終了する
+    #[must_use]
+    pub fn exit_pdf_export(&mut self) -> Option<&'static [ModeEffect]> {
+        self.apply(ModeTransition::ExitPdfExport)
+    }
+
+    /// 現在のモードから遷移先を求め、許可された遷移の場合のみ適用して副作用の一覧を返す
+    ///
+    /// # 戻り値
+    /// * `Some(effects)` - 遷移を適用した。`effects` を先頭から順に実行する
+    /// * `None` - 現在のモードからは許可されない遷移のため、モードを変更していない
+    #[must_use]
+    pub fn apply(&mut self, transition: ModeTransition) -> Option<&'static [ModeEffect]> {
+        let next = self.next(transition)?;
+        let effects = effects_between(*self, next);
+        *self = next;
+        Some(effects)
+    }
+
+    /// 遷移先のモードを求める（許可されない遷移は `None`）
+    pub fn next(self, transition: ModeTransition) -> Option<Self> {
+        use ModeTransition as T;
+        match (self, transition) {
+            (Self::Idle, T::EnterAreaSelect) => Some(Self::AreaSelecting { dragging: false }),
+            (Self::AreaSelecting { .. }, T::BeginDrag) => {
+                Some(Self::AreaSelecting { dragging: true })
+            }
+            (Self::AreaSelecting { .. }, T::EndDrag) => {
+                Some(Self::AreaSelecting { dragging: false })
+            }
+            (Self::AreaSelecting { .. }, T::ExitAreaSelect) => Some(Self::Idle),
+            (Self::Idle, T::EnterCapture) => Some(Self::Capturing { processing: false }),
+            (Self::Capturing { .. }, T::SetCaptureProcessing(processing)) => {
+                Some(Self::Capturing { processing })
+            }
+            (Self::Capturing { .. }, T::ExitCapture) => Some(Self::Idle),
+            (Self::Idle, T::EnterPdfExport) => Some(Self::ExportingPdf),
+            (Self::ExportingPdf, T::ExitPdfExport) => Some(Self::Idle),
+            _ => None,
+        }
+    }
+
+    /// エリア選択モード中か（ドラッグ中を含む）
+    pub fn is_area_selecting(self) -> bool {
+        matches!(self, Self::AreaSelecting { .. })
+    }
+
+    /// エリア選択モードでドラッグ中か
+    pub fn is_dragging(self) -> bool {
+        matches!(self, Self::AreaSelecting { dragging: true })
+    }
+
+    /// キャプチャモード中か（処理中を含む）
+    pub fn is_capturing(self) -> bool {
+        matches!(self, Self::Capturing { .. })
+    }
+
+    /// キャプチャオーバーレイに処理中アイコンを表示するか
+    pub fn is_capture_processing(self) -> bool {
+        matches!(self, Self::Capturing { processing: true })
+    }
+
+    /// PDF変換中か
+    pub fn is_exporting_pdf(self) -> bool {
+        self == Self::ExportingPdf
+    }
+
+    /// このモードの間、マウス・キーボードフックがインストールされているべきか
+    pub fn needs_hooks(self) -> bool {
+        self.is_area_selecting() || self.is_capturing()
+    }
+}
+
+/// 遷移前後のモードから、実行する副作用の一覧を求める
+fn effects_between(from: AppMode, to: AppMode) -> &'static [ModeEffect] {
+    use AppMode::*;
+    match (from, to) {
+        (Idle, AreaSelecting { .. }) => ENTER_AREA_SELECT_EFFECTS,
+        (AreaSelecting { .. }, AreaSelecting { .. }) => &[RefreshAreaSelectOverlay],
+        (AreaSelecting { .. }, Idle) => EXIT_AREA_SELECT_EFFECTS,
+        (Idle, Capturing { .. }) => ENTER_CAPTURE_EFFECTS,
+        (Capturing { .. }, Capturing { .. }) => &[RefreshCapturingOverlay],
+        (Capturing { .. }, Idle) => EXIT_CAPTURE_EFFECTS,
+        (Idle, ExportingPdf) | (ExportingPdf, Idle) => &[UpdateInputControls],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_MODES: [AppMode; 6] = [
+        AppMode::Idle,
+        AppMode::AreaSelecting { dragging: false },
+        AppMode::AreaSelecting { dragging: true },
+        AppMode::Capturing { processing: false },
+        AppMode::Capturing { processing: true },
+        AppMode::ExportingPdf,
+    ];
+
+    const ALL_TRANSITIONS: [ModeTransition; 10] = [
+        ModeTransition::EnterAreaSelect,
+        ModeTransition::BeginDrag,
+        ModeTransition::EndDrag,
+        ModeTransition::ExitAreaSelect,
+        ModeTransition::EnterCapture,
+        ModeTransition::SetCaptureProcessing(false),
+        ModeTransition::SetCaptureProcessing(true),
+        ModeTransition::ExitCapture,
+        ModeTransition::EnterPdfExport,
+        ModeTransition::ExitPdfExport,
+    ];
+
+    /// 遷移表（期待値）: 許可される (遷移前, 遷移, 遷移後, 副作用) の組み合わせ。ここにない組み合わせはすべて拒否される
+    fn expected_transition(
+        from: AppMode,
+        transition: ModeTransition,
+    ) -> Option<(AppMode, &'static [ModeEffect])> {
+        use AppMode::*;
+        use ModeTransition as T;
+        const ENTER_SEL: &[ModeEffect] = ENTER_AREA_SELECT_EFFECTS;
+        const EXIT_SEL: &[ModeEffect] = EXIT_AREA_SELECT_EFFECTS;
+        const ENTER_CAP: &[ModeEffect] = ENTER_CAPTURE_EFFECTS;
+        const EXIT_CAP: &[ModeEffect] = EXIT_CAPTURE_EFFECTS;
+        const REFRESH_SEL: &[ModeEffect] = &[RefreshAreaSelectOverlay];
+        const REFRESH_CAP: &[ModeEffect] = &[RefreshCapturingOverlay];
+        const CONTROLS: &[ModeEffect] = &[UpdateInputControls];
+        // S0/S1: エリア選択（ドラッグなし/ドラッグ中）、C0/C1: キャプチャ（待機中/処理中）
+        let (s0, s1) = (
+            AreaSelecting { dragging: false },
+            AreaSelecting { dragging: true },
+        );
+        let (c0, c1) = (
+            Capturing { processing: false },
+            Capturing { processing: true },
+        );
+        let (p0, p1) = (
+            T::SetCaptureProcessing(false),
+            T::SetCaptureProcessing(true),
+        );
+        let table: [(AppMode, ModeTransition, AppMode, &[ModeEffect]); 16] = [
+            (Idle, T::EnterAreaSelect, s0, ENTER_SEL),
+            (Idle, T::EnterCapture, c0, ENTER_CAP),
+            (Idle, T::EnterPdfExport, ExportingPdf, CONTROLS),
+            (s0, T::BeginDrag, s1, REFRESH_SEL),
+            (s1, T::BeginDrag, s1, REFRESH_SEL),
+            (s0, T::EndDrag, s0, REFRESH_SEL),
+            (s1, T::EndDrag, s0, REFRESH_SEL),
+            (s0, T::ExitAreaSelect, Idle, EXIT_SEL),
+            (s1, T::ExitAreaSelect, Idle, EXIT_SEL),
+            (c0, p0, c0, REFRESH_CAP),
+            (c0, p1, c1, REFRESH_CAP),
+            (c1, p0, c0, REFRESH_CAP),
+            (c1, p1, c1, REFRESH_CAP),
+            (c0, T::ExitCapture, Idle, EXIT_CAP),
+            (c1, T::ExitCapture, Idle, EXIT_CAP),
+            (ExportingPdf, T::ExitPdfExport, Idle, CONTROLS),
+        ];
+        table
+            .iter()
+            .find(|(mode, t, _, _)| *mode == from && *t == transition)
+            .map(|&(_, _, to, effects)| (to, effects))
+    }
+
+    #[test]
+    fn transition_table_is_exhaustive() {
+        let mut allowed = 0;
+        for from in ALL_MODES {
+            for transition in ALL_TRANSITIONS {
+                let mut mode = from;
+                let effects = mode.apply(transition);
+                match expected_transition(from, transition) {
+                    Some((to, expected_effects)) => {
+                        allowed += 1;
+                        assert_eq!(effects, Some(expected_effects), "{from:?} {transition:?}");
+                        assert_eq!(mode, to, "{from:?} {transition:?}");
+                    }
+                    None => {
+                        assert_eq!(effects, None, "{from:?} {transition:?}");
+                        assert_eq!(
+                            mode, from,
+                            "拒否した遷移でモードが変わった: {from:?} {transition:?}"
+                        );
+                    }
+                }
+            }
+        }
+        // 6モード×10遷移のうち、表にある16通りのみ許可される
+        assert_eq!(allowed, 16);
+    }
+
+    #[test]
+    fn named_transitions_match_apply() {
+        type Named = fn(&mut AppMode) -> Option<&'static [ModeEffect]>;
+        let named: [(Named, ModeTransition); 10] = [
+            (AppMode::enter_area_select, ModeTransition::EnterAreaSelect),
+            (AppMode::begin_drag, ModeTransition::BeginDrag),
+            (AppMode::end_drag, ModeTransition::EndDrag),
+            (AppMode::exit_area_select, ModeTransition::ExitAreaSelect),
+            (AppMode::enter_capture, ModeTransition::EnterCapture),
+            (
+                |mode| mode.set_capture_processing(false),
+                ModeTransition::SetCaptureProcessing(false),
+            ),
+            (
+                |mode| mode.set_capture_processing(true),
+                ModeTransition::SetCaptureProcessing(true),
+            ),
+            (AppMode::exit_capture, ModeTransition::ExitCapture),
+            (AppMode::enter_pdf_export, ModeTransition::EnterPdfExport),
+            (AppMode::exit_pdf_export, ModeTransition::ExitPdfExport),
+        ];
+        for from in ALL_MODES {
+            for (method, transition) in named {
+                let (mut by_name, mut by_apply) = (from, from);
+                assert_eq!(method(&mut by_name), by_apply.apply(transition));
+                assert_eq!(by_name, by_apply, "{from:?} {transition:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn mode_predicates() {
+        // (モード, エリア選択中, ドラッグ中, キャプチャ中, 処理中, PDF変換中, フックが必要)
+        let cases = [
+            (AppMode::Idle, false, false, false, false, false, false),
+            (ALL_MODES[1], true, false, false, false, false, true),
+            (ALL_MODES[2], true, true, false, false, false, true),
+            (ALL_MODES[3], false, false, true, false, false, true),
+            (ALL_MODES[4], false, false, true, true, false, true),
+            (
+                AppMode::ExportingPdf,
+                false,
+                false,
+                false,
+                false,
+                true,
+                false,
+            ),
+        ];
+        for (mode, selecting, dragging, capturing, processing, exporting, hooks) in cases {
+            assert_eq!(mode.is_area_selecting(), selecting, "{mode:?}");
+            assert_eq!(mode.is_dragging(), dragging, "{mode:?}");
+            assert_eq!(mode.is_capturing(), capturing, "{mode:?}");
+            assert_eq!(mode.is_capture_processing(), processing, "{mode:?}");
+            assert_eq!(mode.is_exporting_pdf(), exporting, "{mode:?}");
+            assert_eq!(mode.needs_hooks(), hooks, "{mode:?}");
+        }
+    }
+
+    /// フック・オーバーレイの代わりに状態だけを記録するモック（`apply_mode_effects` の代替）
+    #[derive(Debug, Default, Clone)]
+    struct MockLayer {
+        hooks_installed: bool,
+        area_overlay_visible: bool,
+        capturing_overlay_visible: bool,
+        control_updates: usize,
+    }
+
+    impl MockLayer {
+        fn apply(&mut self, effects: &[ModeEffect]) {
+            for effect in effects {
+                match effect {
+                    InstallHooks => {
+                        assert!(!self.hooks_installed, "フックを二重にインストールした");
+                        self.hooks_installed = true;
+                    }
+                    UninstallHooks => {
+                        assert!(self.hooks_installed, "インストールしていないフックを外した");
+                        self.hooks_installed = false;
+                    }
+                    ShowAreaSelectOverlay => {
+                        assert!(
+                            !self.capturing_overlay_visible,
+                            "キャプチャ中にエリア選択を表示"
+                        );
+                        self.area_overlay_visible = true;
+                    }
+                    HideAreaSelectOverlay => self.area_overlay_visible = false,
+                    RefreshAreaSelectOverlay => {
+                        assert!(self.area_overlay_visible, "非表示のエリア選択を再描画");
+                    }
+                    ShowCapturingOverlay => {
+                        assert!(!self.area_overlay_visible, "エリア選択中にキャプチャを表示");
+                        self.capturing_overlay_visible = true;
+                    }
+                    HideCapturingOverlay => self.capturing_overlay_visible = false,
+                    RefreshCapturingOverlay => {
+                        assert!(self.capturing_overlay_visible, "非表示のキャプチャを再描画");
+                    }
+                    UpdateInputControls => self.control_updates += 1,
+                }
+            }
+        }
+
+        /// モックの状態がモードと一致しているか（フック・オーバーレイはモード中のみ、両モードは排他）
+        fn assert_matches(&self, mode: AppMode, history: &[ModeTransition]) {
+            assert_eq!(self.hooks_installed, mode.needs_hooks(), "{history:?}");
+            assert_eq!(
+                self.area_overlay_visible,
+                mode.is_area_selecting(),
+                "{history:?}"
+            );
+            assert_eq!(
+                self.capturing_overlay_visible,
+                mode.is_capturing(),
+                "{history:?}"
+            );
+            assert!(
+                !(self.area_overlay_visible && self.capturing_overlay_visible),
+                "{history:?}"
+            );
+        }
+    }
+
+    /// 通常モードから任意の遷移を `depth` 回行うすべての順序で、
+    /// 拒否された遷移はフック・オーバーレイに触れず、許可された遷移の副作用でモックがモードと一致し続けることを確認する
+    fn walk(mode: AppMode, layer: &MockLayer, history: &mut Vec<ModeTransition>, depth: usize) {
+        if depth == 0 {
+            return;
+        }
+        for transition in ALL_TRANSITIONS {
+            let mut next = mode;
+            let effects = next.apply(transition);
+            let mut next_layer = layer.clone();
+            history.push(transition);
+            match effects {
+                Some(effects) => next_layer.apply(effects),
+                None => assert_eq!(next, mode, "{history:?}"),
+            }
+            next_layer.assert_matches(next, history);
+            walk(next, &next_layer, history, depth - 1);
+            history.pop();
+        }
+    }
+
+    #[test]
+    fn mocked_hooks_and_overlays_follow_mode() {
+        let layer = MockLayer::default();
+        layer.assert_matches(AppMode::Idle, &[]);
+        // 10遷移の5回の組み合わせ（10^5通り）をすべて辿る
+        walk(AppMode::Idle, &layer, &mut Vec::new(), 5);
+    }
+
+    #[test]
+    fn capture_and_area_select_are_mutually_exclusive() {
+        // エリア選択中・キャプチャ中は、もう一方のモードを開始できない（フック・オーバーレイに触れない）
+        for mode in ALL_MODES.into_iter().filter(|mode| mode.needs_hooks()) {
+            let mut rejected = mode;
+            assert_eq!(rejected.enter_capture(), None, "{mode:?}");
+            assert_eq!(rejected.enter_area_select(), None, "{mode:?}");
+            assert_eq!(rejected, mode);
+        }
+
+        // キャプチャモードの開始時にエリア選択中だった場合（`toggle_capture_mode`）：
+        // 先にエリア選択を終了してからキャプチャモードを開始し、フックは1組だけインストールされる
+        let mut layer = MockLayer::default();
+        let mut mode = AppMode::Idle;
+        layer.apply(mode.enter_area_select().unwrap());
+        layer.apply(mode.begin_drag().unwrap());
+        assert_eq!(mode.enter_capture(), None);
+        layer.apply(mode.exit_area_select().unwrap());
+        layer.apply(mode.enter_capture().unwrap());
+        layer.assert_matches(mode, &[]);
+        assert!(layer.capturing_overlay_visible && !layer.area_overlay_visible);
+        // 開始・終了の各遷移でコントロールを1回ずつ更新する
+        assert_eq!(layer.control_updates, 3);
+    }
+}
@@ -27,30 +27,37 @@
 ┌─ 🖼️ UI状態ハンドル管理
 │  ├─ dialog_hwnd: Win32メインダイアログ（リソース管理中枢）
 │  ├─ area_select_overlay: 半透明の矩形選択オーバーレイ
-│  └─ capturing_overlay: キャプチャモード中の状態表示オーバーレイ
+│  ├─ capturing_overlay: キャプチャモード中の状態表示オーバーレイ
+│  └─ saved_toast_overlay: 保存完了トースト（フェードアウト表示）
 ├─ 🎣 システムレベルフック
 │  ├─ mouse_hook: グローバルマウス監視（<1msレスポンス）
 │  └─ keyboard_hook: ESCキー緊急停止（システム全体対応）
 ├─ 🎯 操作モード状態管理（状態機械パターン）
-│  ├─ is_area_select_mode: 領域選択アクティブ（オーバーレイ制御）
-│  ├─ is_capture_mode: キャプチャ待機（ワンクリック撮影）
-│  └─ is_dragging: ドラッグ進行中（リアルタイム描画）
+│  └─ mode: AppMode（通常 / エリア選択・ドラッグ中 / キャプチャ・処理中 / PDF変換中）
 ├─ 📍 高精度座標・領域管理（DPI完全対応）
 │  ├─ drag_start/end: ピクセル完璧矩形計算
 │  ├─ current_mouse_pos: 60fps座標更新
-│  └─ selected_area: 確定領域（キャプチャ対象）
+│  ├─ selected_area: 確定領域（キャプチャ対象）
+│  ├─ selected_area_2: 2つ目の確定領域（Shift+ドラッグ、1枚に結合して保存）
+│  └─ mask_areas: マスク領域（保存時に黒で塗りつぶし）
 ├─ 💾 インテリジェントファイル管理
 │  ├─ selected_folder_path: OneDrive/Pictures自動検出
-│  └─ capture_file_counter: 自動連番（0001-9999）
+│  ├─ capture_file_counter: 自動連番（0001-9999、以降は5桁で継続）
+│  └─ capture_history: セッション中のキャプチャ履歴（削除・並べ替え、PDFのページ順）
 ├─ 🖥️ マルチモニター・解像度管理
 │  ├─ screen_width/height: プライマリ解像度
 │  └─ DPI対応: SetProcessDPIAware統合
 ├─ 🎨 プロフェッショナル品質制御
-│  ├─ capture_scale_factor: 55%-100%（5%刻み）
+│  ├─ capture_scale_factor: 25%-100%（5%刻み）／幅指定モード（capture_target_width）
+│  ├─ capture_max_width/height: 保存サイズ上限（縦横比維持で自動縮小）
+│  ├─ capture_backend / desktop_duplication: 画面取得方式（GDI / DXGI Desktop Duplication / 自動）
 │  ├─ jpeg_quality: 70%-100%（画質・サイズ最適化）
-│  └─ pdf_max_size_mb: 20-100MB（大容量対応）
+│  ├─ pdf_max_size_mb: 20-100MB（大容量対応）
+│  ├─ gif_max_width / gif_frame_delay_ms: GIF変換の最大幅・フレーム間隔
+│  └─ contact_sheet_columns / contact_sheet_thumbnail_width: 一覧画像の列数・サムネイル幅
 ├─ 🖱️ 自動クリック機能
 │  ├─ auto_clicker: 自動クリックの状態と制御を管理
+│  ├─ scheduled_capturer: 定期キャプチャ（N分ごと）の状態と制御を管理
 └─ 🚀 高性能システム統合
    ├─ LayeredWindow: UpdateLayeredWindowによるハードウェア加速透明処理
    ├─ GDI+: 高品質な図形描画と画像処理
@@ -67,6 +74,44 @@ UI更新: 状態変更→自動UI同期→リアルタイム反映
   ↓
 終了: 自動RAII→明示的cleanup→完全リソース解放
 
+【スレッド別アクセス一覧】
+状態は「UIスレッド専用の `AppState`」と「全スレッドから参照できる共有部分」に分けて保持する。
+`get_app_state_ref` / `get_app_state_mut` / `try_get_app_state_ref` は
+デバッグビルドで呼び出し元がUIスレッドかを検査する（`debug_assert!`）。
+ワーカースレッドは `AppState` を参照せず、下記の共有部分のみを使用する。
+
+■ 共有部分（アトミック変数・`Arc`。どのスレッドからも参照可）
+│ フィールド                          │ 書き込み                    │ 読み取り
+├─ SharedState.mouse_x / mouse_y    │ UI（マウスフック・エリア選択開始）│ UI（オーバーレイ描画・キャプチャ開始位置）
+├─ SharedState.capture_mode         │ UI（apply_mode_effects）     │ 自動クリック（停止条件）
+├─ AutoClicker の stop_flag          │ UI（停止・ESC・マウスフック）  │ 自動クリック
+├─ AutoClicker の progress_count     │ 自動クリック                 │ UI（オーバーレイ・ESCの確認・完了通知）
+├─ AutoClicker の max_count          │ UI（回数の変更）             │ 自動クリック
+├─ AutoClicker の session_locked・stop_confirming・user_input │ UI（WTS通知・ESC・マウスフック）│ 自動クリック（一時停止）
+├─ AutoClicker の paused             │ 自動クリック                 │ UI（オーバーレイ）
+├─ ScheduledCapturer の stop_flag・progress_count │ UI / 定期キャプチャ │ 定期キャプチャ / UI
+├─ zip_export_cancel_flag           │ UI（中止ボタン）             │ ZIP変換
+└─ DIALOG_HWND / APP_STATE_ALIVE    │ UI（init / cleanup）         │ 全スレッド（shared_dialog_hwnd）
+
+■ スレッドごとの参照先（`AppState` の上記以外のフィールドはUIスレッド専用）
+┌─ UIスレッド（メインダイアログのメッセージループ）
+│  ├─ AppStateの全フィールド: ダイアログ・コントロールのハンドラ、WM_TIMER、オーバーレイのウィンドウプロシージャ
+│  └─ mode / drag_start・drag_end など: 低レベルマウス・キーボードフック
+│     （フックはインストールしたUIスレッドのメッセージループで呼ばれるため、UIスレッドとして扱う）
+├─ 自動クリックスレッド（auto_click.rs）
+│  ├─ stop_flag / progress_count / max_count: `Arc<Atomic*>` を開始時に複製
+│  ├─ キャプチャモード: `AppState::shared().is_capture_mode()`（終了していれば停止）
+│  ├─ キャプチャ中オーバーレイのHWND: 開始時にコピー（InvalidateRectのみ呼ぶ）
+│  └─ ダイアログHWND: `shared_dialog_hwnd`（PostMessageW・ログ転送。上限到達の警告は
+│     WM_AUTO_CLICK_COMPLETE のWPARAMで伝え、UIスレッドで表示）
+├─ 定期キャプチャスレッド（scheduled_capture.rs）
+│  ├─ stop_flag / progress_count: `Arc<Atomic*>` を開始時に複製
+│  └─ ダイアログHWND: `shared_dialog_hwnd`
+└─ GIF・ZIP・一覧画像の変換スレッド（export_gif.rs / export_zip.rs / export_contact_sheet.rs）
+   ├─ 変換設定・パス: 開始時にUIスレッドで値をコピー
+   ├─ zip_export_cancel_flag: `Arc<AtomicBool>` を開始時に複製
+   └─ ダイアログHWND: `shared_dialog_hwnd`（完了通知・ログ転送）
+
 【エンタープライズ品質保証】
 - 🛡️ メモリ安全性: Box管理・所有権追跡・ダングリングポインタ防止
 - ⚡ パフォーマンス: O(1)アクセス・キャッシュ効率・CPU最適化
@@ -77,24 +122,108 @@ UI更新: 状態変更→自動UI同期→リアルタイム反映
 ============================================================================
 */
 
-use std::{ops::Deref, sync::OnceLock};
+use std::{
+    ops::{Deref, RangeInclusive},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicI32, Ordering},
+    },
+    time::Instant,
+};
 
-use windows::Win32::{
-    Foundation::{HWND, POINT, RECT}, // 基本的なデータ型
-    UI::{
-        WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+use windows::{
+    Win32::{
+        Foundation::{HWND, POINT, RECT}, // 基本的なデータ型
+        UI::{
+            WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+        },
     },
+    core::PCWSTR,
 };
 
 // 連続自動クリック機能モジュール
 use crate::auto_click::AutoClicker;
 
+// 定期キャプチャ機能
+use crate::scheduled_capture::ScheduledCapturer;
+
 // キャプチャオーバーレイ
 use crate::overlay::capturing_overlay::*;
 
 // エリア選択オーバーレイ
 use crate::overlay::area_select_overlay::*;
 
+// 保存完了トーストオーバーレイ
+use crate::overlay::saved_toast_overlay::*;
+
+// オーバーレイ共通トレイト（ハンドルの取得）
+use crate::overlay::Overlay;
+
+// キャプチャトリガー（マウスボタン）設定
+use crate::hook::mouse::CaptureTrigger;
+
+// 表示言語の設定
+use crate::strings::LanguageSetting;
+
+// 配色テーマの設定
+use crate::theme::{ThemeSetting, detect_system_dark_mode};
+
+// 設定値の補正ログ
+use crate::{strings, system_utils::app_log};
+
+// 注入入力（SendInput等）の扱い
+use crate::hook::InjectedInputPolicy;
+
+// キャプチャ画像の保存形式・2領域キャプチャの結合方向
+use clickcapture::{CaptureFormat, capturer::DualAreaLayout};
+
+// キャプチャバックエンド（GDI / DXGI Desktop Duplication）
+use clickcapture::capture_backend::{CaptureBackendKind, DesktopDuplicationBackend};
+
+// キャプチャサムネイルストリップ
+use crate::ui::thumbnail_strip_handler::ThumbnailStrip;
+
+// アイコンボタンのアイコンキャッシュ
+use crate::ui::icon_button::IconCache;
+
+// 保存に失敗したキャプチャの保持
+use crate::screen_capture::FailedCapture;
+
+// キャプチャ所要時間の集計
+use crate::capture_timing::CaptureTimingStats;
+
+// 前回保存した画像からの変化の検出
+use clickcapture::frame_diff::ChangeDetection;
+use image::RgbImage;
+
+// 外部プロセスへのキャプチャ画像の出力（名前付きパイプ・標準出力）
+use clickcapture::capture_sink::{CaptureSink, CaptureSinkTarget};
+
+// 操作モード（状態機械）と遷移の副作用
+pub use clickcapture::app_mode::{AppMode, ModeEffect};
+
+// クイックキャプチャ・再キャプチャのグローバルホットキー
+use clickcapture::hotkey::{
+    DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY, DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY, Hotkey,
+};
+
+// 前回終了時の設定（レジストリ）
+use crate::settings::{
+    AUTO_CLICK_STEP_COUNT_VALUE_NAME, AUTO_CLICK_STEP_X_VALUE_NAME, AUTO_CLICK_STEP_Y_VALUE_NAME,
+    AUTO_CLICK_TARGET_CHECK_VALUE_NAME, AUTO_PDF_EXPORT_VALUE_NAME, BORDER_COLOR_VALUE_NAME,
+    BORDER_HALO_VALUE_NAME, BORDER_WIDTH_VALUE_NAME, CAPTURE_DEBOUNCE_VALUE_NAME,
+    CAPTURE_TRIGGER_VALUE_NAME, CAPTURE_WARM_UP_VALUE_NAME, CHANGE_DETECTION_VALUE_NAME,
+    COMPLETION_NOTIFY_VALUE_NAME, DIM_OPACITY_VALUE_NAME, ELAPSED_STAMP_VALUE_NAME,
+    ESC_CONFIRM_THRESHOLD_VALUE_NAME, FREEZE_SCREEN_VALUE_NAME, JPEG_COMMENT_VALUE_NAME,
+    OVERLAY_FONT_FAMILY_VALUE_NAME, OVERLAY_FONT_SIZE_VALUE_NAME, OVERLAY_HIDE_SETTLE_VALUE_NAME,
+    OVERLAY_VISIBILITY_VALUE_NAME, PAUSE_ON_USER_INPUT_VALUE_NAME, PDF_ARCHIVE_VALUE_NAME,
+    PDF_AUTO_VERSION_VALUE_NAME, QUICK_WINDOW_CAPTURE_HOTKEY_VALUE_NAME,
+    REPEAT_LAST_CAPTURE_HOTKEY_VALUE_NAME, SAVE_FOLDER_TEMPLATE_VALUE_NAME,
+    TIMING_DEBUG_VALUE_NAME, USER_INPUT_QUIET_MS_VALUE_NAME, load_setting_dword,
+    load_setting_string,
+};
+
 /*
 ============================================================================
 超高性能スレッドセーフWrapperシステム
@@ -189,7 +318,7 @@ impl Deref for SafeHHOOK {
 /// # 状態カテゴリ
 /// 1. **UIハンドル**: メインダイアログと各オーバーレイウィンドウのハンドルを管理します。
 /// 2. **システムフック**: マウスとキーボードのグローバルフックハンドルを保持します。
-/// 3. **操作モード**: `is_area_select_mode` や `is_capture_mode` などのフラグで、現在のアプリケーションの動作モードを制御します。
+/// 3. **操作モード**: `mode`（`AppMode` 状態機械）で、現在のアプリケーションの動作モードを制御します。
 /// 4. **座標と領域**: マウスのドラッグ操作や選択されたキャプチャ領域の座標を管理します。
 /// 5. **ファイルと設定**: 保存先フォルダ、ファイル連番、画質設定など、ユーザーが構成可能な項目を保持します。
 /// 6. **自動クリック**: `AutoClicker` 構造体を通じて、連続キャプチャ機能の状態を管理します。
@@ -206,6 +335,15 @@ pub struct AppState {
     /// - 実装: main.rs DialogBoxParamW・リソース管理
     pub dialog_hwnd: Option<SafeHWND>,
 
+    /// ダイアログコントロールのツールチップ
+    /// - 機能: 各コントロールの説明とキーボードショートカットを表示（無効状態でも表示）
+    /// - 実装: `ui/tooltip_handler.rs`（WM_INITDIALOGで作成、WM_DESTROYで破棄）
+    pub tooltip_hwnd: Option<SafeHWND>,
+
+    /// マウスカーソルが乗っているアイコンボタンのコントロールID
+    /// - 用途: オーナードローボタンのホバー表示（`ui/icon_button.rs` のサブクラスで更新）
+    pub hovered_icon_button: Option<i32>,
+
     /// エリア選択オーバーレイ: ドラッグによる領域選択UIを提供
     /// - 機能: 全画面を覆う半透明の黒背景と、ドラッグでくり抜かれる選択範囲を描画
     /// - 実装: `area_select_overlay.rs`
@@ -216,38 +354,279 @@ pub struct AppState {
     /// - 実装: `capturing_overlay.rs`
     pub capturing_overlay: Option<CapturingOverLay>,
 
+    /// 保存完了トーストオーバーレイ
+    /// - 機能: 保存成功時にキャプチャ領域の右下へ「保存しました 0042.jpg」を表示しフェードアウト
+    /// - 実装: `saved_toast_overlay.rs`（WM_TIMER駆動、キャプチャ対象外）
+    pub saved_toast_overlay: Option<SavedToastOverlay>,
+
     // ===== システムフック管理 =====
     // 低レベルマウスフック：システム全体のマウスイベント監視
     pub mouse_hook: Option<SafeHHOOK>,
     // 低レベルキーボードフック：エスケープキーによるモード終了監視
     pub keyboard_hook: Option<SafeHHOOK>,
 
-    // ===== 操作モード状態フラグ =====
-    // エリア選択モード：ドラッグによる矩形領域選択が有効
-    pub is_area_select_mode: bool,
-    // キャプチャモード：左クリックによる画面保存が有効
-    pub is_capture_mode: bool,
-    // ドラッグ操作中：マウス左ボタンが押され、ドラッグ中
-    pub is_dragging: bool,
+    // ===== 操作モード =====
+    // 通常 / エリア選択（ドラッグ中）/ キャプチャ（処理中）/ PDF変換中（変更は AppMode の遷移メソッドで行う）
+    pub mode: AppMode,
+    // マスク領域ドラッグ中：Ctrlキーを押しながらドラッグを開始した（キャプチャ領域ではなくマスク領域を選択）
+    pub is_mask_dragging: bool,
+    // 2つ目の領域ドラッグ中：Shiftキーを押しながらドラッグを開始した（selected_area_2 を選択）
+    pub is_secondary_dragging: bool,
+    // 選択領域の強調方法：外側を暗く（デフォルト）/ 内側を暗く / 境界線のみ（エリア選択中にTabキーで切り替え）
+    pub selection_style: SelectionStyle,
+    /// エリア選択の暗転の不透明度（%、50〜100、デフォルト60 = 従来の #99000000）
+    /// - UI制御: IDC_DIM_OPACITY_COMBO（レジストリ `DimOpacity` に保存）
+    /// - 使用箇所: overlay/area_select_overlay.rs の set_dim_opacity
+    pub area_select_dim_opacity: u8,
+    /// エリア選択の境界線の色（ARGB、デフォルトは赤 #FFFF0000）
+    /// - UI制御: IDC_BORDER_COLOR_COMBO（レジストリ `BorderColor` に保存）
+    /// - 使用箇所: overlay/area_select_overlay.rs の update_style（リサイズハンドルも同じ色相）
+    pub area_select_border_argb: u32,
+    /// エリア選択の境界線の太さ（ピクセル、1〜4、デフォルト2）
+    /// - UI制御: IDC_BORDER_WIDTH_COMBO（レジストリ `BorderWidth` に保存）
+    pub area_select_border_width: u8,
+    /// エリア選択の境界線に白い縁取りを付ける（境界線と同系色の画面でも見えるようにする、デフォルト無効）
+    /// - UI制御: IDC_BORDER_HALO_CHECKBOX（レジストリ `BorderHalo` に保存）
+    pub area_select_border_halo: bool,
+    /// エリア選択の開始時に画面を固定する（動画など動き続ける画面を静止画に対して選択する、デフォルト無効）
+    /// - UI制御: IDC_FREEZE_SCREEN_CHECKBOX（レジストリ `FreezeScreen` に保存）
+    /// - 使用箇所: area_select.rs の start_area_select_mode（overlay/area_select_overlay.rs の capture_frozen_frame）
+    pub freeze_screen_on_area_select: bool,
+    // 三分割グリッド表示：選択範囲を縦横3等分するガイド線を描画する（エリア選択中にGキーで切り替え、デフォルト無効）
+    pub show_selection_grid: bool,
 
     // ===== 座標・領域管理 =====
     // ドラッグ開始座標：マウス左ボタン押下時の初期位置
     pub drag_start: POINT,
     // ドラッグ終了座標：マウス左ボタン離上時の最終位置
     pub drag_end: POINT,
-    // 現在のマウス位置：リアルタイムで更新される座標（オーバーレイ表示用）
-    pub current_mouse_pos: POINT,
 
     // ===== 確定領域管理 =====
     // 選択確定済み領域：エリア選択完了後の矩形領域（キャプチャ対象）
     pub selected_area: Option<RECT>,
+    // 2つ目の選択確定済み領域：Shift+ドラッグで選択（比較用に selected_area と1枚に結合して保存）
+    // - 使用箇所: screen_capture.rs（2領域の結合）、capturing_overlay.rs（2領域選択中の表示）
+    // - 通常のエリア選択で selected_area を選び直すとクリアされる
+    pub selected_area_2: Option<RECT>,
+    // マスク領域：キャプチャ画像内で黒く塗りつぶす矩形領域（スクリーン座標、Ctrl+ドラッグで追加）
+    // - 使用箇所: screen_capture.rs（保存前に塗りつぶし）、area_select_overlay.rs（斜線で表示）
+    pub mask_areas: Vec<RECT>,
 
     // ===== ファイル管理設定 =====
     // 保存先フォルダーパス：ユーザー選択またはデフォルト（Pictures/OneDrive）
     pub selected_folder_path: Option<String>,
-    // キャプチャファイル連番：0001.jpg, 0002.jpg...
+    // 保存先フォルダーのテンプレート：`{yyyy}` `{mm}` `{dd}` を保存時の日付に置き換えたフォルダーに保存
+    // - None: selected_folder_path に保存（デフォルト、レジストリ `SaveFolderTemplate` で設定、画面に設定項目なし）
+    // - 使用箇所: screen_capture.rs の `get_save_dir_path`
+    pub save_folder_template: Option<String>,
+    // キャプチャファイル連番：0001.jpg, 0002.jpg...（9999の次は10000.jpg、更新は advance_capture_file_counter）
     pub capture_file_counter: u32,
 
+    // ===== 連続キャプチャ抑止（デバウンス） =====
+    // 最終キャプチャ時刻：GetTickCount()の値（ミリ秒、起動からの経過時間）
+    pub last_capture_tick: u32,
+    // デバウンス間隔：この時間内の手動クリックはキャプチャしない（デフォルト300ms）
+    // - ダブルクリック癖による連番違いの重複ファイル生成を防止
+    // - 自動クリック実行中は適用しない（間隔はユーザー指定で常にこれより長い）
+    // - レジストリ `CaptureDebounceMs`（画面に設定項目なし、`CAPTURE_DEBOUNCE_MS_RANGE` に丸める）
+    pub capture_debounce_ms: u32,
+
+    /// キャプチャトリガー：キャプチャを実行するマウスボタン（左 / 中 / 左+中 / 右）
+    /// - UI制御: IDC_CAPTURE_TRIGGER_COMBO（レジストリ `CaptureTrigger` に保存）
+    /// - 使用箇所: hook/mouse.rs のクリック判定
+    pub capture_trigger: CaptureTrigger,
+
+    /// 注入入力ポリシー：他のツールが注入したクリック・キー入力をトリガーとして扱うか
+    /// - AllowInjected: 注入入力もトリガーとして扱う（デフォルト）
+    /// - RealInputOnly: 実際の入力と自動クリックの合成クリックのみ
+    /// - UI制御: IDC_REAL_INPUT_ONLY_CHECKBOX
+    /// - 使用箇所: hook.rs の is_trigger_input_allowed
+    pub injected_input_policy: InjectedInputPolicy,
+
+    /// ウィンドウ単体キャプチャモード
+    /// - true: クリック位置のトップレベルウィンドウを PrintWindow で取得し、アルファ付きPNGで保存
+    /// - false: 選択エリアを BitBlt で取得し、JPEGで保存（デフォルト）
+    /// - UI制御: IDC_WINDOW_CAPTURE_CHECKBOX
+    /// - 使用箇所: hook/mouse.rs のキャプチャ分岐、window_capture.rs
+    pub window_capture_mode: bool,
+
+    /// 効果音再生：保存時のシャッター音と自動クリック完了時のチャイム（デフォルト無効）
+    /// - UI制御: IDC_SOUND_CHECKBOX
+    /// - 使用箇所: sound.rs の play_sound
+    pub sound_enabled: bool,
+
+    /// 完了通知：自動クリック・PDF変換の完了を通知領域のバルーンで知らせる（回数・経過時間を表示、デフォルト無効）
+    /// - UI制御: IDC_COMPLETION_NOTIFY_CHECKBOX（レジストリ `CompletionNotify` に保存）
+    /// - 使用箇所: notification.rs の notify_completion
+    pub completion_notify_enabled: bool,
+    /// 完了通知のアイコンを通知領域に表示中か（バルーンが閉じられたら削除）
+    pub is_notify_icon_visible: bool,
+
+    /// 高精細テキストモード：JPEGを4:4:4サンプリング・フラットな輝度量子化テーブル・プログレッシブで保存（デフォルト無効）
+    /// - false: imageクレートのJpegEncoder（4:2:0）で保存
+    /// - true: jpeg-encoderクレートで色差成分を間引かずに保存（赤文字等の滲みを防止）
+    /// - UI制御: IDC_HIGH_FIDELITY_TEXT_CHECKBOX
+    /// - 使用箇所: screen_capture.rs のJPEG保存処理
+    pub jpeg_high_fidelity_text: bool,
+
+    /// プログレッシブJPEG：通常のJPEGをプログレッシブ形式で保存（デフォルト無効、Web掲載向け）
+    /// - false: imageクレートのJpegEncoder（ベースライン）で保存
+    /// - true: jpeg-encoderクレートでプログレッシブ形式に保存（`progressive-jpeg` フィーチャー有効時のみ）
+    /// - UI制御: IDC_PROGRESSIVE_JPEG_CHECKBOX
+    /// - 使用箇所: screen_capture.rs のJPEG保存処理
+    pub jpeg_progressive: bool,
+
+    /// 撮影情報の記録（デフォルト無効）
+    /// - true: JPEGに撮影日時・撮影条件（選択領域・スケール・品質）のEXIFを埋め込み、PDF変換時に撮影日時のフッターを表示
+    /// - false: EXIFを追加せず、従来と同一のバイト列で保存
+    /// - UI制御: IDC_CAPTURE_METADATA_CHECKBOX
+    /// - 使用箇所: screen_capture.rs のJPEG保存処理、export_pdf.rs
+    pub record_capture_metadata: bool,
+
+    /// JPEGに埋め込むコメント（取得元のURLやメモなど、COMセグメント）
+    /// - `{counter}` は保存する連番、`{timestamp}` は撮影日時に置き換える
+    /// - None: 埋め込まない（デフォルト、レジストリ `JpegComment` で設定、画面に設定項目なし）
+    /// - 使用箇所: capture_pipeline.rs の `encode`
+    pub jpeg_comment: Option<String>,
+
+    /// カーソル下ウィンドウのクイックキャプチャのホットキー（RegisterHotKeyで登録）
+    /// - 既定: Ctrl+Alt+Shift+W（レジストリ `QuickWindowCaptureHotkey` で変更、画面に設定項目なし）
+    /// - 使用箇所: global_hotkey.rs の登録、strings.rs のツールチップ
+    pub quick_window_capture_hotkey: Hotkey,
+
+    /// 前回の領域の再キャプチャのホットキー（RegisterHotKeyで登録）
+    /// - 既定: Ctrl+Alt+Shift+R（レジストリ `RepeatLastCaptureHotkey` で変更、画面に設定項目なし）
+    /// - 使用箇所: global_hotkey.rs の登録、strings.rs のツールチップ
+    pub repeat_last_capture_hotkey: Hotkey,
+
+    /// 表示言語の設定：自動（OSのUI言語から判定、デフォルト） / 日本語 / English
+    /// - ログ・メッセージボックス・オーバーレイのラベルに適用（ダイアログリソースのキャプションは対象外）
+    /// - 実際の表示言語は strings.rs がワーカースレッドからも参照できるよう別途保持
+    /// - UI制御: IDC_LANGUAGE_COMBO
+    pub language_setting: LanguageSetting,
+
+    /// 配色テーマの設定：自動（OSのアプリモードに従う、デフォルト） / ライト / ダーク
+    /// - オーナードローボタン・サムネイルストリップの配色、エリア選択の暗転色に適用
+    /// - UI制御: IDC_THEME_COMBO
+    pub theme_setting: ThemeSetting,
+
+    /// OSのアプリモードがダークか（`AppsUseLightTheme` レジストリ値、`WM_SETTINGCHANGE` で更新）
+    pub system_dark_mode: bool,
+
+    /// 保存形式：JPEG（デフォルト） / WebP（可逆） / WebP（非可逆） / AVIF（`avif` フィーチャー有効時） / BMP（無圧縮）
+    /// - 非可逆形式の品質は jpeg_quality を流用
+    /// - UI制御: IDC_CAPTURE_FORMAT_COMBO
+    /// - 使用箇所: screen_capture.rs の保存処理（拡張子とエンコーダーの選択）
+    pub capture_format: CaptureFormat,
+
+    /// 2領域キャプチャの結合方向：左右（デフォルト） / 上下
+    /// - UI制御: IDC_DUAL_AREA_STACKED_CHECKBOX
+    /// - 使用箇所: screen_capture.rs の2領域結合処理（selected_area_2 選択時のみ）
+    pub dual_area_layout: DualAreaLayout,
+
+    /// キャプチャバックエンド：自動（デフォルト） / GDI / Desktop Duplication
+    ///
+    /// 自動の場合はGDI（BitBlt）で取得し、結果が真っ黒（ハードウェアアクセラレーション・DRM保護された画面）
+    /// の場合のみDesktop Duplicationで取得し直します。Desktop Duplicationが使えない環境（RDPセッションなど）では
+    /// ログを出力してGDIの結果を使用します。
+    /// - 使用箇所: screen_capture.rs
+    pub capture_backend: CaptureBackendKind,
+
+    /// Desktop Duplicationのセッション（初回使用時に作成し、連続キャプチャで再利用）
+    /// - 使用箇所: screen_capture.rs
+    pub desktop_duplication: DesktopDuplicationBackend,
+
+    // ===== サムネイルストリップ =====
+    /// 直近キャプチャのサムネイル（リングバッファ、最大`MAX_THUMBNAIL_COUNT`件）
+    /// - 更新: screen_capture.rs の保存成功時
+    /// - 描画: thumbnail_strip_handler.rs（IDC_THUMBNAIL_STRIP のオーナードロー）
+    /// - 解放: 上限超過時およびAppState破棄時にHBITMAPをDeleteObject
+    pub capture_thumbnails: ThumbnailStrip,
+
+    // ===== キャプチャ履歴 =====
+    /// 今回のセッションで保存したキャプチャのパス（保存順、最大`MAX_CAPTURE_HISTORY`件）
+    /// - 更新: screen_capture.rs / window_capture.rs の保存成功時、履歴ダイアログでの削除・並べ替え
+    /// - 使用箇所: export_pdf.rs（空でない場合はこの順でPDFに変換）
+    /// - セッション中のみ保持（レジストリには保存しない）
+    pub capture_history: Vec<PathBuf>,
+
+    /// 履歴ダイアログでリスト項目をドラッグ中かどうか（LVN_BEGINDRAG〜WM_LBUTTONUP）
+    /// - 使用箇所: ui/capture_history_handler.rs
+    pub is_dragging_history_items: bool,
+
+    // ===== 共有 =====
+    /// 最後に保存したキャプチャのパス（履歴の削除・並べ替えの影響を受けない）
+    /// - 更新: ui/capture_history_handler.rs の add_capture_history
+    /// - 使用箇所: 「共有」ボタン（IDC_SHARE_BUTTON、保存済みの場合のみ有効）
+    pub last_saved_file: Option<PathBuf>,
+    /// Windowsの共有UI（共有ウィンドウ）を使用できるか（Windows Serverなどでは使用不可）
+    /// - 更新: ui/share_button_handler.rs の initialize_share_button（ダイアログ初期化時に1回判定）
+    /// - 使用箇所: input_control_handlers.rs（使用できない場合は「共有」ボタンを常に無効）
+    pub share_ui_supported: bool,
+    /// 共有UIの `DataRequested` イベントに登録したハンドラーのトークン（次回の共有時に登録を外す）
+    /// - 使用箇所: ui/share_button_handler.rs
+    pub share_request_token: Option<i64>,
+
+    // ===== キャプチャ所要時間の計測 =====
+    /// キャプチャの段階ごとの所要時間をログに出力するか（レジストリ `TimingDebug`、画面に設定項目なし）
+    /// - 使用箇所: capture_timing.rs
+    pub timing_debug: bool,
+
+    /// キャプチャモード・定期キャプチャの実行中の所要時間の集計（終了時に出力してリセット）
+    /// - 更新: capture_timing.rs の finish_capture_timing / dump_capture_timing_stats
+    pub capture_timing_stats: CaptureTimingStats,
+
+    // ===== アイコンボタン =====
+    /// オーナードローボタンのアイコン（リソースIDごとのHICON、初回描画時に読み込み）
+    /// - 使用箇所: ui/icon_button.rs の draw_icon_button
+    /// - 解放: AppState破棄時にDestroyIcon（描画のたびに読み込み・破棄しない）
+    pub icon_cache: IconCache,
+
+    // ===== 保存失敗時の再保存 =====
+    /// 再試行しても保存できなかったキャプチャ（エンコード済みデータ、最新の1件のみ）
+    /// - 更新: screen_capture.rs の保存失敗時
+    /// - 使用箇所: 「再保存」ボタン（IDC_RESAVE_BUTTON、保持中のみ有効）
+    pub last_failed_capture: Option<FailedCapture>,
+
+    // ===== 変化の検出 =====
+    /// 前回保存した画像からの変化の検出（レジストリ `ChangeDetection`、画面に設定項目なし）
+    /// - 使用箇所: capture_pipeline.rs の detect_change（変化がなければ保存しない・変化した範囲のみ保存）
+    pub change_detection: ChangeDetection,
+    /// 前回保存した画像（マスク適用後・縮小前の原寸、変化の検出が有効な場合のみ保持）
+    /// - 更新: screen_capture.rs の保存成功時
+    /// - 破棄: キャプチャモード開始時（最初の1枚は必ず保存する）
+    pub previous_capture_frame: Option<RgbImage>,
+    /// 現在（直前）のキャプチャモード中にファイルへ保存した枚数（出力先への送信は含まない）
+    /// - 更新: キャプチャモード開始時に0に戻し、screen_capture.rs の advance_capture_file_counter で加算
+    /// - 使用箇所: キャプチャモード終了時のPDF自動変換（0枚の場合は変換しない）
+    pub capture_session_saved_count: u32,
+
+    // ===== 経過時間スタンプ =====
+    /// キャプチャ画像の左上に撮影時刻と前回からの経過時間を焼き込むか（デフォルト無効）
+    /// - true: `GetDIBits` の前にGDIで描画し、画像のピクセルの一部として保存（1つ目の領域のみ）
+    /// - Desktop Duplicationで取得し直した画像には描画されない。マスク領域と重なった場合はマスクが優先
+    /// - 変化の検出と併用すると、スタンプが毎回変わるため常に変化ありと判定される
+    /// - UI制御: IDC_ELAPSED_STAMP_CHECKBOX（レジストリ `ElapsedStamp` に保存）
+    /// - 使用箇所: capture_pipeline.rs の grab
+    pub burn_in_elapsed_stamp: bool,
+    /// 前回保存したキャプチャの取得開始時刻（経過時間スタンプの基準）
+    /// - 更新: screen_capture.rs の保存成功時
+    /// - 破棄: キャプチャモード・定期キャプチャの開始時（最初の1枚は経過時間を表示しない）
+    pub last_capture_started_at: Option<Instant>,
+
+    // ===== 暖機運転 =====
+    /// キャプチャモードの開始時に、保存しない1回分のキャプチャを実行するか（レジストリ `CaptureWarmUp`、既定は有効）
+    /// - 使用箇所: screen_capture.rs の warm_up_capture（DCの作成・メモリ確保・エンコーダーの初期化を済ませる）
+    pub capture_warm_up: bool,
+
+    // ===== 外部プロセスへの出力 =====
+    /// キャプチャ画像の出力先（起動引数 `--sink=stdout` / `--sink=pipe:名前`、`None` はファイルに保存）
+    /// - 設定: main.rs の `record_capture_sink`（起動時のみ）
+    /// - 使用箇所: capture_pipeline.rs の persist（ファイルの代わりに送信）
+    pub capture_sink_target: Option<CaptureSinkTarget>,
+    /// 接続中の出力先（名前付きパイプは初回のキャプチャで接続し、送信に失敗したら破棄して次回再接続）
+    pub capture_sink: Option<CaptureSink>,
+
     // ===== 画面解像度情報 =====
     // プライマリモニタ幅：GetSystemMetrics(SM_CXSCREEN)
     pub screen_width: i32,
@@ -255,21 +634,64 @@ pub struct AppState {
     pub screen_height: i32,
 
     // ===== オーバーレイ表示状態 =====
-    /// キャプチャオーバーレイの状態フラグ
-    /// - true: 処理中状態（処理中アイコンを表示）
-    /// - false: 待機中状態（待機中アイコンを表示）
-    /// - 制御方法：switch_capture_processing(bool) -> capturing_overlay.refresh_overlay()
-    pub capture_overlay_is_processing: bool,
+    /// キャプチャオーバーレイに経過時間とキャプチャ枚数を表示するモードのフラグ
+    /// - true: 定期キャプチャ中（「00:12:30 · 42枚」形式のラベルを選択エリア左上に表示）
+    /// - false: 通常表示（自動クリック中は N/M 形式の進行状況ラベル）
+    /// - 使用箇所: ui/schedule_handler.rs（開始/終了時に切り替え）、overlay/capturing_overlay.rs
+    pub capture_overlay_shows_elapsed: bool,
+
+    /// キャプチャオーバーレイの表示方法：表示（デフォルト） / 半透明 / 非表示
+    /// - UI制御: IDC_OVERLAY_VISIBILITY_COMBO（レジストリ `OverlayVisibility` に保存）
+    /// - 使用箇所: overlay/capturing_overlay.rs の overlay_window_alpha
+    pub capturing_overlay_visibility: CapturingOverlayVisibility,
+
+    /// キャプチャの直前にオーバーレイを隠してから画面を取得するまでの待ち時間（ミリ秒）
+    /// - 非表示をDWMが合成し終える前に取得すると、低速な環境でアイコンが写り込むため
+    /// - レジストリ `OverlayHideSettleMs`（画面に設定項目なし、`OVERLAY_HIDE_SETTLE_MS_RANGE` に丸める）
+    /// - 使用箇所: capture_pipeline.rs の hide_capturing_overlay_for_grab
+    pub overlay_hide_settle_ms: u32,
+
+    /// キャプチャオーバーレイのラベルのフォント名（`None` はシステムのメッセージフォント）
+    /// - レジストリ `OverlayFontFamily`（画面に設定項目なし、見つからない場合は候補へフォールバック）
+    /// - 使用箇所: init_app_state での CapturingOverLay::new
+    pub overlay_font_family: Option<String>,
+
+    /// キャプチャオーバーレイのラベルの文字サイズ（ポイント、96DPI基準）
+    /// - レジストリ `OverlayFontSize`（画面に設定項目なし、`OVERLAY_FONT_POINTS_RANGE` に丸める）
+    pub overlay_font_points: u32,
 
     // ===== キャプチャ設定 =====
-    // キャプチャ画質設定：画像のスケールファクター（55%〜100%、5%刻み）
+    // キャプチャ画質設定：画像のスケールファクター（25%〜100%、5%刻み）
     // - 100: 最高画質（元の解像度のまま保存）
     // - 65: 標準画質（画質とファイルサイズのバランス良好）※デフォルト
-    // - 55: 軽量画質（ファイルサイズ重視、SNS共有に適している）
+    // - 25〜55: 軽量画質（ファイルサイズ重視、高DPI画面のドキュメント用途）
     // - UI制御: ドロップダウンコンボボックスでユーザー選択
     // - 使用箇所: screen_capture.rs内でキャプチャ処理時に参照
     pub capture_scale_factor: u8,
 
+    /// 幅指定スケールモード
+    ///
+    /// - true: `capture_target_width` の幅に合わせて縦横比を保ったまま縮小
+    /// - false: `capture_scale_factor` の割合で縮小（デフォルト）
+    /// - UI制御: スケールコンボボックスの「幅指定」項目
+    /// - 使用箇所: screen_capture.rs, window_capture.rs
+    pub capture_fit_to_width: bool,
+
+    /// 幅指定モード時の出力画像幅（ピクセル）
+    ///
+    /// 元画像より大きい値の場合は拡大せず、元の幅のまま保存します。
+    /// - UI制御: 出力幅エディットボックス（幅指定モード時のみ有効）
+    /// - 使用箇所: screen_capture.rs, window_capture.rs
+    pub capture_target_width: u32,
+
+    /// 保存画像の最大幅・最大高さ（ピクセル、0で無制限）
+    ///
+    /// スケール設定（%または幅指定）適用後のサイズがこれを超える場合、
+    /// 縦横比を保ったまま上限内に収まるよう更に縮小します（4K領域を100%で保存した際の巨大ファイル対策）。
+    /// - 使用箇所: screen_capture.rs, window_capture.rs
+    pub capture_max_width: u32,
+    pub capture_max_height: u32,
+
     /// JPEG画像保存品質設定（70%〜100%、5%刻み）
     ///
     /// キャプチャした画像をJPEG形式で保存する際の圧縮品質を制御します。
@@ -300,10 +722,80 @@ pub struct AppState {
     /// - 使用箇所: export_pdf.rs内でPDFサイズ制限判定時に参照
     pub pdf_max_size_mb: u16,
 
-    pub is_exporting_to_pdf: bool, // PDFエクスポート中フラグ
+    /// PDF変換元フォルダー（None: `selected_folder_path` を使用）
+    /// - 用途: セッションごとのサブフォルダーなど、保存先以外のフォルダーを変換対象にする
+    /// - UI制御: PDF変換ボタン押下時のフォルダー選択ステップで設定
+    pub pdf_source_dir: Option<String>,
+
+    /// `pdf_source_dir` の後ろに連結する追加のPDF変換元フォルダー（選択した順）
+    /// - 用途: 複数のセッションサブフォルダーを1つのPDF（ページ通し）にまとめる
+    /// - UI制御: PDF変換ボタン押下時のフォルダー選択ステップで「フォルダーを追加」を選ぶと設定
+    pub pdf_additional_source_dirs: Vec<String>,
+
+    /// PDF出力先フォルダー（None: `selected_folder_path` を使用）
+    /// - 出力先でも `0001.pdf` からの連番命名は維持される
+    /// - UI制御: PDF変換ボタン押下時のフォルダー選択ステップで設定
+    pub pdf_output_dir: Option<String>,
+
+    /// 出力先に以前の変換の連番PDF（`0001.pdf` 形式）がある場合に、確認せず枝番を付けて保存するか
+    /// - false: 上書きするか・枝番を付けて残すかを変換開始時に確認（デフォルト）
+    /// - true: 確認せずに `0001_2.pdf` 形式で保存（レジストリ `PdfAutoVersion`、画面に設定項目なし）
+    /// - 使用箇所: export_pdf.rs
+    pub pdf_auto_version: bool,
+
+    /// PDF変換をアーカイブ用のPDF/A-1b形式で出力するか（デフォルト無効）
+    /// - true: sRGBの出力インテント・XMPメタデータを追加し、撮影日時フッターは省略
+    /// - UI制御: IDC_PDF_ARCHIVE_CHECKBOX（レジストリ `PdfArchive` に保存）
+    /// - 使用箇所: export_pdf.rs
+    pub pdf_archive: bool,
+
+    /// キャプチャモード終了時に、保存先フォルダーを自動でPDFに変換するか（デフォルト無効）
+    /// - true: モード中に1枚以上保存した場合、終了後に `WM_AUTO_PDF_EXPORT` で変換を開始
+    /// - UI制御: IDC_AUTO_PDF_EXPORT_CHECKBOX（レジストリ `AutoPdfExport` に保存）
+    /// - 使用箇所: screen_capture.rs, ui/pdf_export_button_handler.rs
+    pub auto_pdf_export: bool,
+
+    /// GIF変換時のフレーム最大幅（ピクセル、0で縮小なし）
+    ///
+    /// キャプチャ画像をそのままGIFにすると巨大になるため、縦横比を保ったままこの幅まで縮小します。
+    /// - 使用箇所: export_gif.rs
+    pub gif_max_width: u32,
+
+    /// GIF変換時のフレーム間隔（ミリ秒、0で自動クリック間隔を使用）
+    /// - 使用箇所: export_gif.rs
+    pub gif_frame_delay_ms: u32,
+
+    pub is_exporting_to_gif: bool, // GIF変換中フラグ（ワーカースレッド実行中）
+
+    /// 一覧画像（コンタクトシート）の1行あたりのサムネイル数
+    /// - 使用箇所: export_contact_sheet.rs
+    pub contact_sheet_columns: u32,
+
+    /// 一覧画像（コンタクトシート）のサムネイル幅（ピクセル）
+    /// - 使用箇所: export_contact_sheet.rs
+    pub contact_sheet_thumbnail_width: u32,
+
+    pub is_exporting_contact_sheet: bool, // 一覧画像作成中フラグ（ワーカースレッド実行中）
+
+    /// ZIP圧縮中フラグ（ワーカースレッド実行中）
+    /// - ZIP圧縮中もキャプチャは継続でき、「ZIPに圧縮」ボタンは「ZIP中止」として機能する
+    pub is_exporting_to_zip: bool,
+
+    /// ZIP圧縮のキャンセルフラグ（ワーカースレッドと共有、「ZIP中止」ボタンで `true` に設定）
+    pub zip_export_cancel_flag: Arc<AtomicBool>,
+
+    /// 作成中（または直前に作成した）ZIPファイルのパス
+    /// - 用途: 完了通知（`WM_ZIP_EXPORT_COMPLETE`）受信時に「フォルダーを開く」で選択表示する
+    pub zip_export_output_path: Option<PathBuf>,
 
     // ===== 自動連続クリック機能 =====
     pub auto_clicker: AutoClicker, // 自動クリック機能管理
+
+    // ===== 定期キャプチャ機能 =====
+    /// 一定間隔（分）ごとに選択エリアをキャプチャする機能の状態と制御
+    /// - UI制御: 定期開始/停止ボタン、間隔コンボボックス、回数エディットボックス
+    /// - 使用箇所: ui/schedule_handler.rs, ui/dialog_handler.rs（WM_SCHEDULED_CAPTURE受信時）
+    pub scheduled_capturer: ScheduledCapturer,
 }
 
 /*
@@ -333,6 +825,199 @@ impl AppState {
         self.keyboard_hook.map(|hook| *hook)
     }
 
+    /// 【モード判定】エリア選択モード中か（ドラッグ中を含む）
+    pub fn is_area_select_mode(&self) -> bool {
+        self.mode.is_area_selecting()
+    }
+
+    /// 【モード判定】エリア選択モードでドラッグ中か
+    pub fn is_dragging(&self) -> bool {
+        self.mode.is_dragging()
+    }
+
+    /// 【モード判定】キャプチャモード中か（処理中を含む）
+    pub fn is_capture_mode(&self) -> bool {
+        self.mode.is_capturing()
+    }
+
+    /// 【モード判定】キャプチャオーバーレイに処理中アイコンを表示するか
+    pub fn capture_overlay_is_processing(&self) -> bool {
+        self.mode.is_capture_processing()
+    }
+
+    /// 【モード判定】PDF変換中か
+    pub fn is_exporting_to_pdf(&self) -> bool {
+        self.mode.is_exporting_pdf()
+    }
+
+    /// 【オーバーレイHWND】キャプチャ中オーバーレイのハンドル（ワーカースレッドへ渡す用）
+    //
+    // 自動クリックスレッドはAppStateを参照せず、開始時にコピーしたこのHWNDで再描画を要求する
+    pub fn capturing_overlay_hwnd(&self) -> Option<SafeHWND> {
+        self.capturing_overlay
+            .as_ref()
+            .and_then(|overlay| overlay.get_hwnd())
+    }
+
+    /// 【スケール設定】画像のスケール（%）を設定する
+    //
+    // 範囲外の値（0%や100%超など）は `CAPTURE_SCALE_RANGE` に丸め、補正した旨をログに記録する
+    pub fn set_capture_scale_factor(&mut self, scale_percent: u8) {
+        self.capture_scale_factor = clamp_setting(
+            strings::capture_scale_setting_name(),
+            scale_percent,
+            CAPTURE_SCALE_RANGE,
+        );
+    }
+
+    /// 【品質設定】JPEG品質（%）を設定する
+    //
+    // 範囲外の値は `JPEG_QUALITY_RANGE` に丸め、補正した旨をログに記録する
+    pub fn set_jpeg_quality(&mut self, quality: u8) {
+        self.jpeg_quality = clamp_setting(
+            strings::jpeg_quality_setting_name(),
+            quality,
+            JPEG_QUALITY_RANGE,
+        );
+    }
+
+    /// 【設定の検証】読み込んだスケール・JPEG品質を有効範囲に補正する
+    //
+    // 手動で編集された設定などで 0% のスケールが読み込まれると、保存サイズが0になり
+    // キャプチャが失敗するため、起動時（init_app_state）に一度だけ検証する
+    pub fn sanitize_capture_settings(&mut self) {
+        self.set_capture_scale_factor(self.capture_scale_factor);
+        self.set_jpeg_quality(self.jpeg_quality);
+    }
+
+    /// 【起動時の初期化失敗】GDI+の初期化・オーバーレイの描画リソースの作成に失敗した項目を列挙する
+    //
+    // 概要：
+    //   失敗してもキャプチャ・保存は継続できるため終了はせず、機能が制限されることを
+    //   WM_INITDIALOGで1回だけまとめて通知する（コンソールのないリリースビルドでも気付けるように）
+    //
+    // 戻り値：
+    //   ユーザー向けの説明（1項目1行）。失敗がなければ空
+    pub fn startup_failures(&self) -> Vec<String> {
+        let mut failures = Vec::new();
+        if let Some(&status) = GDIPLUS_STARTUP_STATUS.get().filter(|&&status| status != 0) {
+            failures.push(strings::gdiplus_startup_failed(status));
+        }
+
+        // GDI+自体が失敗している場合は、全オーバーレイが使用できないことが上の1行で伝わる
+        if failures.is_empty() {
+            let overlays = [
+                self.area_select_overlay
+                    .as_ref()
+                    .map(|o| (o.is_usable(), o.get_description())),
+                self.capturing_overlay
+                    .as_ref()
+                    .map(|o| (o.is_usable(), o.get_description())),
+                self.saved_toast_overlay
+                    .as_ref()
+                    .map(|o| (o.is_usable(), o.get_description())),
+            ];
+            failures.extend(
+                overlays
+                    .into_iter()
+                    .flatten()
+                    .filter(|(usable, _)| !usable)
+                    .map(|(_, description)| strings::overlay_resources_failed(description)),
+            );
+        }
+        failures
+    }
+
+    /// 【状態ダンプ】診断用に主要な状態を人が読める形式で整形する
+    //
+    // 概要：
+    //   動作モード・選択領域・連番・スケール/画質・自動クリック設定・画面解像度・GDI+初期化結果を
+    //   1項目1行の文字列にまとめる（`#[derive(Debug)]` の出力はHBITMAP等を含み長すぎるため）
+    //
+    // 使用場面：
+    //   - 診断モード（Alt+D、ui/keyboard_navigation_handler.rs）で app_log に出力
+    //   - 不具合報告時にログをそのまま貼り付けてもらう
+    pub fn dump(&self) -> String {
+        let format_rect = |rect: Option<RECT>| match rect {
+            Some(r) => format!(
+                "({}, {}) - ({}, {}) {}x{}",
+                r.left,
+                r.top,
+                r.right,
+                r.bottom,
+                r.right - r.left,
+                r.bottom - r.top
+            ),
+            None => "なし".to_string(),
+        };
+        let scale = if self.capture_fit_to_width {
+            format!("幅指定 {}px", self.capture_target_width)
+        } else {
+            format!("{}%", self.capture_scale_factor)
+        };
+        let gdiplus_status = match GDIPLUS_STARTUP_STATUS.get() {
+            Some(0) => "Ok".to_string(),
+            Some(status) => format!("失敗 (Status {})", status),
+            None => "未初期化".to_string(),
+        };
+
+        [
+            format!("モード: {:?}", self.mode),
+            format!("選択領域: {}", format_rect(self.selected_area)),
+            format!("選択領域2: {}", format_rect(self.selected_area_2)),
+            format!("マスク領域: {}件", self.mask_areas.len()),
+            format!(
+                "保存先: {} (テンプレート: {}, 次の連番: {:04})",
+                self.selected_folder_path.as_deref().unwrap_or("未設定"),
+                self.save_folder_template.as_deref().unwrap_or("なし"),
+                self.capture_file_counter
+            ),
+            format!(
+                "スケール: {} / 最大サイズ: {}x{} / 品質: {}% / 形式: {:?} / バックエンド: {:?}",
+                scale,
+                self.capture_max_width,
+                self.capture_max_height,
+                self.jpeg_quality,
+                self.capture_format,
+                self.capture_backend
+            ),
+            format!(
+                "自動クリック: 有効={} 実行中={} モード={:?} 間隔={}ms 回数={}/{}",
+                self.auto_clicker.is_enabled(),
+                self.auto_clicker.is_running(),
+                self.auto_clicker.get_mode(),
+                self.auto_clicker.get_interval(),
+                self.auto_clicker.get_progress_count(),
+                self.auto_clicker.get_max_count()
+            ),
+            format!(
+                "定期キャプチャ: 実行中={} 間隔={}分 回数={}/{}",
+                self.scheduled_capturer.is_running(),
+                self.scheduled_capturer.get_interval_minutes(),
+                self.scheduled_capturer.get_progress_count(),
+                self.scheduled_capturer.get_max_count()
+            ),
+            format!(
+                "変換中: PDF={} GIF={} ZIP={} 一覧画像={}",
+                self.is_exporting_to_pdf(),
+                self.is_exporting_to_gif,
+                self.is_exporting_to_zip,
+                self.is_exporting_contact_sheet
+            ),
+            format!("画面解像度: {}x{}", self.screen_width, self.screen_height),
+            format!(
+                "言語: {:?} / テーマ: {:?} (OSダーク={})",
+                self.language_setting, self.theme_setting, self.system_dark_mode
+            ),
+            format!(
+                "ホットキー: クイックキャプチャ={} 再キャプチャ={}",
+                self.quick_window_capture_hotkey, self.repeat_last_capture_hotkey
+            ),
+            format!("GDI+: {}", gdiplus_status),
+        ]
+        .join("\n")
+    }
+
     /// 【状態初期化】アプリケーション開始時の状態セットアップ
     //
     // 概要：
@@ -360,8 +1045,18 @@ impl AppState {
         app_state.dialog_hwnd = Some(SafeHWND(hwnd));
 
         // オーバーレイ構造体の初期化
-        app_state.area_select_overlay = Some(AreaSelectOverLay::new());
-        app_state.capturing_overlay = Some(CapturingOverLay::new());
+        let area_select_overlay = AreaSelectOverLay::new();
+        area_select_overlay.set_dim_opacity(app_state.area_select_dim_opacity);
+        area_select_overlay.update_style(
+            app_state.area_select_border_argb,
+            app_state.area_select_border_width,
+        );
+        app_state.area_select_overlay = Some(area_select_overlay);
+        app_state.capturing_overlay = Some(CapturingOverLay::new(
+            app_state.overlay_font_family.as_deref(),
+            app_state.overlay_font_points,
+        ));
+        app_state.saved_toast_overlay = Some(SavedToastOverlay::new());
 
         // グローバル状態変数にデフォルト値をセット
         let app_state_box = Box::new(app_state);
@@ -370,9 +1065,14 @@ impl AppState {
             SetWindowLongPtrW(hwnd, GWLP_USERDATA, app_state_ptr as isize);
         }
 
+        let _ = DIALOG_THREAD_ID.set(std::thread::current().id());
         DIALOG_HWND
             .set(SafeHWND(hwnd))
             .expect("グローバルダイアログハンドルの設定に失敗しました。");
+        APP_STATE_ALIVE.store(true, Ordering::Release);
+
+        // 読み込んだ設定に範囲外の値があれば補正（ログ出力のためグローバル状態の設定後に行う）
+        AppState::get_app_state_mut().sanitize_capture_settings();
 
         println!("アプリケーション状態が初期化されました");
     }
@@ -388,17 +1088,82 @@ impl AppState {
             // ダイアログのユーザーデータからAppStateへのポインタを取得
             let app_state_ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *mut AppState;
             if !app_state_ptr.is_null() {
+                // ワーカースレッドからのPostMessageW・MessageBoxWの宛先を無効化
+                APP_STATE_ALIVE.store(false, Ordering::Release);
+                // 先にポインタをクリアしてダングリングポインタを防止
+                // （解放中のDrop処理から `app_log` が呼ばれても、解放済みの状態を参照しない）
+                SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
                 // Box::from_rawでポインタの所有権をBoxに戻す。
                 // このBoxはこの関数のスコープを抜ける際に自動的にdropされ、
                 // AppStateとそれが持つ全てのリソース（オーバーレイなど）が解放される。
                 let _ = Box::from_raw(app_state_ptr);
                 println!("🗑️ AppStateリソースを解放しました。");
-                // ポインタをクリアしてダングリングポインタを防止
-                SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
             }
         }
     }
 
+    /// 【状態参照取得（初期化前対応）】AppStateが存在する場合のみ不変参照を取得
+    //
+    // 概要：
+    //   `init_app_state` の前（main()の初期化エラー、GDI+起動失敗など）や
+    //   `cleanup_app_state` の後は `None` を返す。`get_app_state_ref` と異なりパニックしない。
+    //
+    // 使用場面：
+    //   - app_log / show_message_box（ダイアログが無い場合はコンソールのみに出力）
+    pub fn try_get_app_state_ref() -> Option<&'static AppState> {
+        let hwnd = DIALOG_HWND.get()?;
+        debug_assert!(
+            Self::is_dialog_thread(),
+            "AppStateはUIスレッド以外から参照できません（shared_dialog_hwndを使用）"
+        );
+        unsafe {
+            let ptr = GetWindowLongPtrW(**hwnd, GWLP_USERDATA) as *const AppState;
+            ptr.as_ref()
+        }
+    }
+
+    /// 【ダイアログハンドル取得（全スレッド対応）】ワーカースレッドから通知を送る宛先を取得
+    //
+    // 概要：
+    //   AppStateを参照せず、グローバルHWNDのみを返す。`init_app_state` の前と
+    //   `cleanup_app_state` の後は `None` を返す。
+    //
+    // 使用場面：
+    //   - ワーカースレッドからのPostMessageW（完了通知・キャプチャ要求・ログ転送）
+    //   - show_message_box（初期化前・破棄後はコンソールに出力）
+    pub fn shared_dialog_hwnd() -> Option<SafeHWND> {
+        if !APP_STATE_ALIVE.load(Ordering::Acquire) {
+            return None;
+        }
+        DIALOG_HWND.get().copied()
+    }
+
+    /// 【共有状態取得（全スレッド対応）】ワーカースレッドからも参照する状態を取得
+    //
+    // 概要：
+    //   AppStateを参照せず、アトミック変数のみの `SharedState` を返す。
+    //   `init_app_state` の前後も有効（初期値はマウス位置 (0, 0)・通常モード）。
+    //
+    // 使用場面：
+    //   - マウスフック・オーバーレイ描画でのマウス位置の更新・参照
+    //   - 自動クリックのスレッドでのキャプチャモード終了の検出
+    pub fn shared() -> &'static SharedState {
+        &SHARED_STATE
+    }
+
+    /// 【マウス位置取得】現在のマウス位置（`SharedState` の値）
+    pub fn current_mouse_pos(&self) -> POINT {
+        SHARED_STATE.mouse_pos()
+    }
+
+    /// 【スレッド判定】現在のスレッドがメインダイアログのスレッド（UIスレッド）かを判定
+    //
+    // 使用場面：
+    //   - app_log（ワーカースレッドからはPostMessageWでUIスレッドに転送）
+    pub fn is_dialog_thread() -> bool {
+        DIALOG_THREAD_ID.get() == Some(&std::thread::current().id())
+    }
+
     /// 【状態参照取得】HWNDからAppStateへの不変参照を取得
     //
     // 概要：
@@ -420,6 +1185,10 @@ impl AppState {
         let hwnd = DIALOG_HWND
             .get()
             .expect("グローバルダイアログハンドルの取得に失敗しました。");
+        debug_assert!(
+            Self::is_dialog_thread(),
+            "AppStateはUIスレッド以外から参照できません（shared_dialog_hwndを使用）"
+        );
         unsafe {
             let ptr = GetWindowLongPtrW(**hwnd, GWLP_USERDATA) as *const AppState;
             &*ptr
@@ -449,6 +1218,10 @@ impl AppState {
         let hwnd = DIALOG_HWND
             .get()
             .expect("グローバルダイアログハンドルの取得に失敗しました。");
+        debug_assert!(
+            Self::is_dialog_thread(),
+            "AppStateはUIスレッド以外から変更できません（shared_dialog_hwndを使用）"
+        );
 
         unsafe {
             let ptr = GetWindowLongPtrW(**hwnd, GWLP_USERDATA) as *mut AppState;
@@ -470,27 +1243,177 @@ impl Default for AppState {
 
         Self {
             dialog_hwnd: None,
+            tooltip_hwnd: None,
+            hovered_icon_button: None,
             area_select_overlay: None,
             capturing_overlay: None,
+            saved_toast_overlay: None,
             mouse_hook: None,
             keyboard_hook: None,
-            is_area_select_mode: false,
-            is_capture_mode: false,
-            is_dragging: false,
+            mode: AppMode::Idle,
+            is_mask_dragging: false,
+            is_secondary_dragging: false,
+            selection_style: SelectionStyle::DimOutside,
+            area_select_dim_opacity: load_setting_dword(DIM_OPACITY_VALUE_NAME)
+                .map(|percent| percent.clamp(50, 100) as u8)
+                .unwrap_or(DEFAULT_DIM_OPACITY_PERCENT),
+            area_select_border_argb: load_setting_dword(BORDER_COLOR_VALUE_NAME)
+                .unwrap_or(DEFAULT_BORDER_ARGB),
+            area_select_border_width: load_setting_dword(BORDER_WIDTH_VALUE_NAME)
+                .map(|width| width.clamp(1, 4) as u8)
+                .unwrap_or(DEFAULT_BORDER_WIDTH),
+            area_select_border_halo: load_setting_dword(BORDER_HALO_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            freeze_screen_on_area_select: load_setting_dword(FREEZE_SCREEN_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            show_selection_grid: false,
             drag_start: POINT { x: 0, y: 0 },
             drag_end: POINT { x: 0, y: 0 },
-            current_mouse_pos: POINT { x: 0, y: 0 },
             selected_area: None,
+            selected_area_2: None,
+            mask_areas: Vec::new(),
             selected_folder_path: None,
+            save_folder_template: load_setting_string(SAVE_FOLDER_TEMPLATE_VALUE_NAME),
             capture_file_counter: 1,
+            last_capture_tick: 0,
+            capture_debounce_ms: load_setting_dword(CAPTURE_DEBOUNCE_VALUE_NAME)
+                .map(|ms| {
+                    ms.clamp(
+                        *CAPTURE_DEBOUNCE_MS_RANGE.start(),
+                        *CAPTURE_DEBOUNCE_MS_RANGE.end(),
+                    )
+                })
+                .unwrap_or(DEFAULT_CAPTURE_DEBOUNCE_MS),
+            capture_trigger: CaptureTrigger::from_u8(
+                load_setting_dword(CAPTURE_TRIGGER_VALUE_NAME).unwrap_or(0) as u8,
+            ),
+            injected_input_policy: InjectedInputPolicy::AllowInjected,
+            window_capture_mode: false,
+            sound_enabled: false,
+            completion_notify_enabled: load_setting_dword(COMPLETION_NOTIFY_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            is_notify_icon_visible: false,
+            jpeg_high_fidelity_text: false,
+            jpeg_progressive: false,
+            record_capture_metadata: false,
+            jpeg_comment: load_setting_string(JPEG_COMMENT_VALUE_NAME),
+            quick_window_capture_hotkey: load_hotkey_setting(
+                QUICK_WINDOW_CAPTURE_HOTKEY_VALUE_NAME,
+                DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY,
+            ),
+            repeat_last_capture_hotkey: load_hotkey_setting(
+                REPEAT_LAST_CAPTURE_HOTKEY_VALUE_NAME,
+                DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY,
+            ),
+            language_setting: LanguageSetting::Auto,
+            theme_setting: ThemeSetting::Auto,
+            system_dark_mode: detect_system_dark_mode(),
+            capture_format: CaptureFormat::Jpeg,
+            dual_area_layout: DualAreaLayout::SideBySide,
+            capture_backend: CaptureBackendKind::Auto,
+            desktop_duplication: DesktopDuplicationBackend::new(),
+            capture_thumbnails: ThumbnailStrip::new(),
+            capture_history: Vec::new(),
+            is_dragging_history_items: false,
+            last_saved_file: None,
+            share_ui_supported: false,
+            share_request_token: None,
+            timing_debug: load_setting_dword(TIMING_DEBUG_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            capture_timing_stats: CaptureTimingStats::default(),
+            icon_cache: IconCache::new(),
+            last_failed_capture: None,
+            change_detection: load_setting_dword(CHANGE_DETECTION_VALUE_NAME)
+                .map(ChangeDetection::from_u32)
+                .unwrap_or_default(),
+            previous_capture_frame: None,
+            capture_session_saved_count: 0,
+            burn_in_elapsed_stamp: load_setting_dword(ELAPSED_STAMP_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            last_capture_started_at: None,
+            capture_warm_up: load_setting_dword(CAPTURE_WARM_UP_VALUE_NAME)
+                .is_none_or(|value| value != 0),
+            capture_sink_target: CAPTURE_SINK_TARGET.get().cloned(),
+            capture_sink: STARTUP_CAPTURE_SINK
+                .lock()
+                .ok()
+                .and_then(|mut sink| sink.take()),
             screen_width,
             screen_height,
-            capture_overlay_is_processing: false,
+            capture_overlay_shows_elapsed: false,
+            capturing_overlay_visibility: load_setting_dword(OVERLAY_VISIBILITY_VALUE_NAME)
+                .map(|value| CapturingOverlayVisibility::from_u8(value as u8))
+                .unwrap_or_default(),
+            overlay_hide_settle_ms: load_setting_dword(OVERLAY_HIDE_SETTLE_VALUE_NAME)
+                .map(|ms| {
+                    ms.clamp(
+                        *OVERLAY_HIDE_SETTLE_MS_RANGE.start(),
+                        *OVERLAY_HIDE_SETTLE_MS_RANGE.end(),
+                    )
+                })
+                .unwrap_or(DEFAULT_OVERLAY_HIDE_SETTLE_MS),
+            overlay_font_family: load_setting_string(OVERLAY_FONT_FAMILY_VALUE_NAME),
+            overlay_font_points: load_setting_dword(OVERLAY_FONT_SIZE_VALUE_NAME)
+                .map(|points| {
+                    points.clamp(
+                        *OVERLAY_FONT_POINTS_RANGE.start(),
+                        *OVERLAY_FONT_POINTS_RANGE.end(),
+                    )
+                })
+                .unwrap_or(DEFAULT_OVERLAY_FONT_POINTS),
             capture_scale_factor: 65, // デフォルト65%（バランス良好）
-            jpeg_quality: 95,         // デフォルト95%（高画質）
-            pdf_max_size_mb: 20,      // デフォルト20MB
-            is_exporting_to_pdf: false,
-            auto_clicker: AutoClicker::new(),
+            capture_fit_to_width: false,
+            capture_target_width: 1200,
+            capture_max_width: 2560,
+            capture_max_height: 2560,
+            jpeg_quality: 95,    // デフォルト95%（高画質）
+            pdf_max_size_mb: 20, // デフォルト20MB
+            pdf_source_dir: None,
+            pdf_additional_source_dirs: Vec::new(),
+            pdf_output_dir: None,
+            pdf_auto_version: load_setting_dword(PDF_AUTO_VERSION_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            pdf_archive: load_setting_dword(PDF_ARCHIVE_VALUE_NAME).is_some_and(|value| value != 0),
+            auto_pdf_export: load_setting_dword(AUTO_PDF_EXPORT_VALUE_NAME)
+                .is_some_and(|value| value != 0),
+            gif_max_width: 640,
+            gif_frame_delay_ms: 0,
+            is_exporting_to_gif: false,
+            contact_sheet_columns: 4,
+            contact_sheet_thumbnail_width: 320,
+            is_exporting_contact_sheet: false,
+            is_exporting_to_zip: false,
+            zip_export_cancel_flag: Arc::new(AtomicBool::new(false)),
+            zip_export_output_path: None,
+            auto_clicker: {
+                // クリック位置の進行（レジストリの符号付きの値、画面に設定項目なし）
+                let mut auto_clicker = AutoClicker::new();
+                auto_clicker.set_position_step(
+                    POINT {
+                        x: load_setting_dword(AUTO_CLICK_STEP_X_VALUE_NAME).unwrap_or(0) as i32,
+                        y: load_setting_dword(AUTO_CLICK_STEP_Y_VALUE_NAME).unwrap_or(0) as i32,
+                    },
+                    load_setting_dword(AUTO_CLICK_STEP_COUNT_VALUE_NAME).unwrap_or(0),
+                );
+                // 対象ウィンドウの確認（既定は有効、0で無効）
+                auto_clicker.set_verify_target_window(
+                    load_setting_dword(AUTO_CLICK_TARGET_CHECK_VALUE_NAME) != Some(0),
+                );
+                // ESCキーで停止する前に確認する残り回数（0は確認しない）
+                if let Some(threshold) = load_setting_dword(ESC_CONFIRM_THRESHOLD_VALUE_NAME) {
+                    auto_clicker.set_esc_confirm_threshold(threshold);
+                }
+                // ユーザー操作中の一時停止（既定は無効）と静止時間
+                auto_clicker.set_pause_on_user_input(
+                    load_setting_dword(PAUSE_ON_USER_INPUT_VALUE_NAME)
+                        .is_some_and(|value| value != 0),
+                );
+                if let Some(quiet_ms) = load_setting_dword(USER_INPUT_QUIET_MS_VALUE_NAME) {
+                    auto_clicker.set_user_input_quiet_ms(quiet_ms);
+                }
+                auto_clicker
+            },
+            scheduled_capturer: ScheduledCapturer::new(),
         }
     }
 }
@@ -501,5 +1424,133 @@ impl Default for AppState {
 ============================================================================
 */
 
+// 【スケール設定の有効範囲】スケールコンボボックスの選択肢（25%〜100%）と同じ
+pub const CAPTURE_SCALE_RANGE: RangeInclusive<u8> = 25..=100;
+
+// 【JPEG品質の有効範囲】品質コンボボックスの選択肢（70%〜100%）と同じ
+pub const JPEG_QUALITY_RANGE: RangeInclusive<u8> = 70..=100;
+
+// 【連続キャプチャ抑止の間隔】手動クリックのダブルクリック癖による重複保存を防ぐ（0で抑止しない）
+pub const DEFAULT_CAPTURE_DEBOUNCE_MS: u32 = 300;
+pub const CAPTURE_DEBOUNCE_MS_RANGE: RangeInclusive<u32> = 0..=2000;
+
+// 【オーバーレイ非表示の待ち時間】DwmFlushの後に待つ時間（既定は60Hzの約1フレーム）
+pub const DEFAULT_OVERLAY_HIDE_SETTLE_MS: u32 = 16;
+pub const OVERLAY_HIDE_SETTLE_MS_RANGE: RangeInclusive<u32> = 0..=200;
+
+// 【オーバーレイの文字サイズ】ポイント（96DPI基準、12pt = 16px）
+pub const DEFAULT_OVERLAY_FONT_POINTS: u32 = 12;
+pub const OVERLAY_FONT_POINTS_RANGE: RangeInclusive<u32> = 8..=36;
+
+/*
+============================================================================
+スレッド間共有状態
+============================================================================
+*/
+
+/// 【SharedState】UIスレッド以外からも読み書きする状態（アトミック変数のみ）
+///
+/// # 設計目的
+/// `AppState` はUIスレッド専用のため、ワーカースレッドが参照する値はここに分けて保持し、
+/// `AppState::shared()` でどのスレッドからでも参照できるようにします。
+/// 値の更新はUIスレッド（フック・モード遷移）のみが行い、ワーカースレッドは読み取りのみです。
+///
+/// # フィールド
+/// - `mouse_x` / `mouse_y`: マウスフックが更新する現在のマウス位置。x・yは別々に読み書きするため、
+///   更新中に読むと新旧の値が混ざることがある（オーバーレイの表示位置が1イベント分ずれるだけ）
+/// - `capture_mode`: キャプチャモード中か（`apply_mode_effects` がモード遷移ごとに公開）。
+///   自動クリックのスレッドは、停止フラグに加えてこの値でもキャプチャモードの終了を検出する
+pub struct SharedState {
+    mouse_x: AtomicI32,
+    mouse_y: AtomicI32,
+    capture_mode: AtomicBool,
+}
+
+impl SharedState {
+    const fn new() -> Self {
+        Self {
+            mouse_x: AtomicI32::new(0),
+            mouse_y: AtomicI32::new(0),
+            capture_mode: AtomicBool::new(false),
+        }
+    }
+
+    /// 現在のマウス位置（マウスフック・エリア選択の開始時に更新）
+    pub fn mouse_pos(&self) -> POINT {
+        POINT {
+            x: self.mouse_x.load(Ordering::Relaxed),
+            y: self.mouse_y.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 現在のマウス位置を更新する（UIスレッドのみ）
+    pub fn set_mouse_pos(&self, pos: POINT) {
+        self.mouse_x.store(pos.x, Ordering::Relaxed);
+        self.mouse_y.store(pos.y, Ordering::Relaxed);
+    }
+
+    /// キャプチャモード中か
+    pub fn is_capture_mode(&self) -> bool {
+        self.capture_mode.load(Ordering::Acquire)
+    }
+
+    /// モード遷移後のモードを公開する（UIスレッドのみ、`apply_mode_effects` から呼び出す）
+    pub fn publish_mode(&self, mode: AppMode) {
+        self.capture_mode
+            .store(mode.is_capturing(), Ordering::Release);
+    }
+}
+
+/// 【設定値の補正】設定値を有効範囲に丸める（補正した場合はログに記録）
+fn clamp_setting(name: &str, value: u8, range: RangeInclusive<u8>) -> u8 {
+    let clamped = value.clamp(*range.start(), *range.end());
+    if clamped != value {
+        app_log(&strings::setting_clamped(name, value, clamped));
+    }
+    clamped
+}
+
+/// 【ホットキー設定の読み込み】設定文字列を解析する（解析できない場合はログに記録して既定値を使う）
+fn load_hotkey_setting(value_name: PCWSTR, default: Hotkey) -> Hotkey {
+    let Some(text) = load_setting_string(value_name) else {
+        return default;
+    };
+    Hotkey::parse(&text).unwrap_or_else(|| {
+        app_log(&strings::hotkey_setting_invalid(&text, default));
+        default
+    })
+}
+
 // 【グローバルダイアログハンドル】フック処理用の高速アクセス
 static DIALOG_HWND: OnceLock<SafeHWND> = OnceLock::new();
+
+// 【UIスレッドID】メインダイアログを作成したスレッド（ワーカースレッドからのUI操作の判定用）
+static DIALOG_THREAD_ID: OnceLock<std::thread::ThreadId> = OnceLock::new();
+
+// 【AppState生存フラグ】init_app_stateからcleanup_app_stateまでtrue（shared_dialog_hwnd用）
+static APP_STATE_ALIVE: AtomicBool = AtomicBool::new(false);
+
+// 【スレッド間共有状態】マウス位置・キャプチャモード（AppState::shared() で全スレッドから参照）
+static SHARED_STATE: SharedState = SharedState::new();
+
+// 【GDI+初期化結果】main()のGdiplusStartupの戻り値（診断ダンプ・起動時の通知用、0: Ok）
+static GDIPLUS_STARTUP_STATUS: OnceLock<i32> = OnceLock::new();
+
+// 【キャプチャ出力先】main()で起動引数 `--sink=` から設定（AppState作成時に取り込む）
+static CAPTURE_SINK_TARGET: OnceLock<CaptureSinkTarget> = OnceLock::new();
+
+// 【起動時に引き取った出力先】標準出力はログより先に引き取る必要があるため、main()で接続済みのものを受け渡す
+static STARTUP_CAPTURE_SINK: Mutex<Option<CaptureSink>> = Mutex::new(None);
+
+/// 【キャプチャ出力先の記録】main()から起動引数の出力先と、起動時に接続した出力先（標準出力）を記録する
+pub fn record_capture_sink(target: CaptureSinkTarget, sink: Option<CaptureSink>) {
+    let _ = CAPTURE_SINK_TARGET.set(target);
+    if let Ok(mut startup_sink) = STARTUP_CAPTURE_SINK.lock() {
+        *startup_sink = sink;
+    }
+}
+
+/// 【GDI+初期化結果の記録】main()からGdiplusStartupの戻り値を記録する（診断ダンプで表示）
+pub fn record_gdiplus_startup_status(status: i32) {
+    let _ = GDIPLUS_STARTUP_STATUS.set(status);
+}
@@ -14,48 +14,56 @@
     -   モードの開始/終了を管理し、関連リソース（フック、オーバーレイ）を制御します。
 2.  **領域確定処理 (`end_area_select_mode`)**:
     -   ドラッグ操作で選択された矩形領域を `AppState` に保存します。
+    -   Ctrlキーを押しながらのドラッグはマスク領域として `mask_areas` に追加し、モードを継続します
+        （Ctrl+クリックでマスク領域をすべて解除）。
+    -   Shiftキーを押しながらのドラッグは2つ目の領域として `selected_area_2` に保存します
+        （Shift+クリックで2つ目の領域のみ解除、通常のエリア選択では両方を選び直し）。
 3.  **オーバーレイ連携**:
     -   `area_select_overlay` を表示/非表示にし、ユーザーに視覚的なフィードバックを提供します。
 
 【処理フロー】
 1.  **[UI]** 「エリア選択」ボタンクリック
 2.  **`start_area_select_mode()`**:
-    -   `AppState` の `is_area_select_mode` を `true` に設定。
-    -   マウスとキーボードのフックをインストール (`install_hooks`)。
-    -   `area_select_overlay` を表示。
-3.  **[マウスフック]** `WM_LBUTTONDOWN` でドラッグ開始 (`is_dragging = true`)。
+    -   `AppState` のモードを `AppMode::AreaSelecting` に遷移。
+    -   画面の固定が有効な場合は、ダイアログを最小化してから画面全体を固定フレームとして保存。
+    -   遷移が返した副作用を実行し、フックのインストールと `area_select_overlay` の表示を行う (`apply_mode_effects`)。
+3.  **[マウスフック]** `WM_LBUTTONDOWN` でドラッグ開始 (`AppMode::AreaSelecting { dragging: true }`)。
 4.  **[マウスフック]** `WM_MOUSEMOVE` でドラッグ中の矩形をオーバーレイに再描画。
 5.  **[マウスフック]** `WM_LBUTTONUP` で `end_area_select_mode()` を呼び出し。
 6.  **`end_area_select_mode()`**:
     -   選択された `RECT` を `AppState` に保存。
     -   `cancel_area_select_mode()` を呼び出してモードを終了。
 7.  **`cancel_area_select_mode()`** (完了またはESCキーでのキャンセル時):
-    -   フックをアンインストールし、オーバーレイを非表示にする。
+    -   フックをアンインストールし、オーバーレイを非表示にする（固定フレームも解放）。
 
 【技術仕様】
 -   **オーバーレイ**: `area_select_overlay` が `LayeredWindow` を使用して半透明描画。
 -   **イベント監視**: `WH_MOUSE_LL` と `WH_KEYBOARD_LL` フックを利用してシステム全体のマウス・キーボードイベントを監視。
 -   **状態管理**: `AppState` を介してモードフラグや選択領域を安全に共有。
+-   **画面の固定**: `AppState.freeze_screen_on_area_select` が有効な場合、開始時の画面をオーバーレイの背景に描画。
+    選択した座標は実際の画面の座標のため、以降のキャプチャは通常どおり実際の画面から取得します。
 
 ============================================================================
 */
 
 use windows::Win32::{
     Foundation::{POINT, RECT},
-    UI::WindowsAndMessaging::{GetCursorPos, MB_ICONERROR, MB_OK},
+    UI::WindowsAndMessaging::{GetCursorPos, MB_ICONERROR, MB_ICONWARNING, MB_OK},
 };
 
+use clickcapture::drag_rect::DragRect;
+
 use crate::{
     app_state::*,
-    hook::*,
-    overlay::*,
+    mode_effects::apply_mode_effects,
+    strings,
     system_utils::*,
-    ui::{
-        dialog_handler::{bring_dialog_to_back, bring_dialog_to_front},
-        input_control_handlers::update_input_control_states,
-    },
+    ui::dialog_handler::{bring_dialog_to_back, bring_dialog_to_front},
 };
 
+/// Ctrl/Shift+クリックとドラッグを区別するための最小サイズ（ピクセル）
+const MIN_DRAG_SIZE: i32 = 4;
+
 /**
  * エリア選択モードを開始する
  *
@@ -63,28 +71,30 @@ use crate::{
  * システムフック（マウス・キーボード）を初期化します。
  *
  * # 処理フロー
- * 1. 重複起動をチェックし、モード中でなければ続行します。
- * 2. `AppState` の `is_area_select_mode` フラグを `true` に設定します。
- * 3. マウスとキーボードのグローバルフックをインストールします (`install_hooks`)。
- * 4. `area_select_overlay` を表示し、全画面を半透明に覆います。
- * 5. UIコントロールの状態を「エリア選択モード」に合わせて更新します。
- * 6. メインダイアログを最小化し、画面操作の邪魔にならないようにします。
+ * 1. 重複起動とキャプチャモード中でないことをチェックし、どちらでもなければ続行します。
+ * 2. `AppState` のモードを `AppMode::AreaSelecting` に遷移します。
+ * 3. 画面の固定が有効な場合は、ダイアログを最小化してから画面全体を固定フレームとして保存します。
+ * 4. 遷移が返した副作用を `apply_mode_effects` で実行します
+ *    （フックのインストール、`area_select_overlay` の表示、UIコントロールの更新）。
+ * 5. メインダイアログを最小化し、画面操作の邪魔にならないようにします。
  *
  * # エラーハンドリング
  * - 既にエリア選択モードの場合は、メッセージボックスを表示して処理を中断します。
+ * - キャプチャモード中の場合も、メッセージボックスを表示して処理を中断します
+ *   （実行中の自動クリックを中断しないよう、キャプチャモードは自動で終了しません）。
  * - オーバーレイの表示に失敗した場合は、モードを即座にキャンセルしてクリーンアップします。
  *
  * # 副作用
  * - システム全体のマウス・キーボードフックが有効になります。
  * - 全画面を覆うオーバーレイウィンドウが表示されます。
- * - `AppState` の `is_area_select_mode` フラグが `true` になります。
+ * - `AppState` のモードが `AppMode::AreaSelecting { dragging: false }` になります。
  *
  */
 pub fn start_area_select_mode() {
     unsafe {
         // 重複起動を防止
         let app_state = AppState::get_app_state_mut();
-        if app_state.is_area_select_mode {
+        if app_state.is_area_select_mode() {
             show_message_box(
                 "既にエリア選択モード中です",
                 "エリア選択エラー",
@@ -93,31 +103,59 @@ pub fn start_area_select_mode() {
             return;
         }
 
+        // キャプチャモード中は開始しない（フックとオーバーレイを両モードで取り合わないようにする）
+        // 実行中の自動クリックを中断しないよう、キャプチャモードを自動では終了しない
+        if app_state.is_capture_mode() {
+            app_log(strings::area_select_blocked_by_capture_log());
+            show_message_box(
+                strings::area_select_blocked_by_capture_message(),
+                strings::area_select_blocked_by_capture_title(),
+                MB_OK | MB_ICONWARNING,
+            );
+            return;
+        }
+
         app_log("エリア選択モードを開始しました (エスケープキーでキャンセル可能)");
+        app_log("Ctrl+ドラッグでマスク領域を追加、Ctrl+クリックでマスク領域を解除できます");
+        app_log("Shift+ドラッグで比較用の2つ目の領域を選択、Shift+クリックで解除できます");
 
         // 現在のマウス位置を取得して状態を初期化
         let mut current_pos = POINT { x: 0, y: 0 };
         if GetCursorPos(&mut current_pos).is_ok() {
             println!("現在のマウス位置: ({}, {})", current_pos.x, current_pos.y);
-
-            // AppState状態更新
-            app_state.is_area_select_mode = true;
-            app_state.current_mouse_pos = current_pos; // 初期位置設定
-
-            // システムフックを開始（ESCキーでのキャンセルとマウス操作の監視）
-            install_hooks();
+            AppState::shared().set_mouse_pos(current_pos); // 初期位置設定
         }
 
-        // エリア選択用のオーバーレイを表示
-        if let Some(overlay) = app_state.area_select_overlay.as_mut() {
-            if let Err(e) = overlay.show_overlay() {
-                eprintln!("❌ エリア選択オーバーレイの表示に失敗: {:?}", e);
-                cancel_area_select_mode(); // エラー時はモードをキャンセル
+        // AppState状態更新（フック・オーバーレイは画面の固定の後に開始する）
+        let effects = app_state.mode.enter_area_select();
+        debug_assert!(
+            effects.is_some(),
+            "通常モード以外からエリア選択モードを開始しようとしました: {:?}",
+            app_state.mode
+        );
+        let Some(effects) = effects else {
+            return;
+        };
+
+        // 画面の固定：ダイアログが写り込まないよう先に最小化してから、画面全体を保存する
+        if app_state.freeze_screen_on_area_select {
+            bring_dialog_to_back();
+            let (screen_width, screen_height) = (app_state.screen_width, app_state.screen_height);
+            if let Some(overlay) = app_state.area_select_overlay.as_mut() {
+                if overlay.capture_frozen_frame(screen_width, screen_height) {
+                    app_log(strings::freeze_screen_succeeded());
+                } else {
+                    app_log(strings::freeze_screen_failed());
+                }
             }
         }
 
-        // UIコントロールの状態を更新
-        update_input_control_states();
+        // システムフックを開始し（ESCキーでのキャンセルとマウス操作の監視）、
+        // エリア選択用のオーバーレイを表示してUIコントロールの状態を更新
+        if let Err(e) = apply_mode_effects(effects) {
+            eprintln!("❌ エリア選択オーバーレイの表示に失敗: {:?}", e);
+            cancel_area_select_mode(); // エラー時はモードをキャンセル
+        }
 
         // メインダイアログを最小化
         bring_dialog_to_back();
@@ -138,38 +176,94 @@ pub fn start_area_select_mode() {
  *
  * # 保存される状態
  * - `app_state.selected_area`: 後続のキャプチャ処理でこの領域が使用されます。
+ * - `app_state.mask_areas`: Ctrl+ドラッグ時はマスク領域として追加され、エリア選択モードは継続します。
+ * - `app_state.selected_area_2`: Shift+ドラッグ時は2つ目の領域として保存されます。
+ *   通常のドラッグで `selected_area` を選び直した場合はクリアされます。
  */
 pub fn end_area_select_mode() {
     let app_state = AppState::get_app_state_mut();
 
+    // Ctrl+ドラッグ：マスク領域の追加（またはCtrl+クリックで全解除）
+    if app_state.is_mask_dragging {
+        end_mask_drag();
+        return;
+    }
+
     // 選択矩形の座標を取得
-    let (left, top, right, bottom) = {
-        let left = app_state.drag_start.x.min(app_state.drag_end.x);
-        let top = app_state.drag_start.y.min(app_state.drag_end.y);
-        let right = app_state.drag_start.x.max(app_state.drag_end.x);
-        let bottom = app_state.drag_start.y.max(app_state.drag_end.y);
-        (left, top, right, bottom)
-    };
-
-    let rect = RECT {
-        left,
-        top,
-        right,
-        bottom,
-    };
-
-    app_log(&format!(
-        "✅ エリア選択完了: ({}, {}) - ({}, {})",
-        rect.left, rect.top, rect.right, rect.bottom
-    ));
-
-    // 選択領域をAppStateに保存
-    app_state.selected_area = Some(rect);
+    let is_secondary = app_state.is_secondary_dragging;
+    app_state.is_secondary_dragging = false;
+
+    let drag_rect = current_drag_rect(app_state);
+    let rect = to_win32_rect(drag_rect);
+
+    if is_secondary {
+        // Shift+ドラッグ：2つ目の領域（Shift+クリックで解除）
+        if drag_rect.is_click(MIN_DRAG_SIZE) {
+            app_state.selected_area_2 = None;
+            app_log("🧹 2つ目の領域を解除しました");
+        } else {
+            app_state.selected_area_2 = Some(rect);
+            app_log(&format!(
+                "✅ 2つ目の領域選択完了: ({}, {}) - ({}, {})",
+                rect.left, rect.top, rect.right, rect.bottom
+            ));
+            if app_state.selected_area.is_none() {
+                app_log("⚠️ 1つ目の領域が未選択です。Shiftを押さずにドラッグして選択してください");
+            }
+        }
+    } else {
+        app_log(&format!(
+            "✅ エリア選択完了: ({}, {}) - ({}, {})",
+            rect.left, rect.top, rect.right, rect.bottom
+        ));
+
+        // 選択領域をAppStateに保存（選び直した場合は2つ目の領域もクリア）
+        app_state.selected_area = Some(rect);
+        if app_state.selected_area_2.take().is_some() {
+            app_log("🧹 2つ目の領域を解除しました");
+        }
+    }
 
     // 共通の終了処理を呼び出す
     cancel_area_select_mode();
 }
 
+/**
+ * マスク領域のドラッグを完了する
+ *
+ * ドラッグ範囲をマスク領域として `AppState.mask_areas` に追加します。
+ * ドラッグせずにクリックした場合（幅または高さが数ピクセル未満）は、マスク領域をすべて解除します。
+ * 続けて複数のマスク領域を追加できるよう、エリア選択モードは終了しません。
+ */
+fn end_mask_drag() {
+    let app_state = AppState::get_app_state_mut();
+    let effects = app_state.mode.end_drag();
+    app_state.is_mask_dragging = false;
+
+    let drag_rect = current_drag_rect(app_state);
+    let rect = to_win32_rect(drag_rect);
+
+    if drag_rect.is_click(MIN_DRAG_SIZE) {
+        app_state.mask_areas.clear();
+        app_log("🧹 マスク領域をすべて解除しました");
+    } else {
+        app_state.mask_areas.push(rect);
+        app_log(&format!(
+            "⬛ マスク領域を追加: ({}, {}) - ({}, {}) (計{}件)",
+            rect.left,
+            rect.top,
+            rect.right,
+            rect.bottom,
+            app_state.mask_areas.len()
+        ));
+    }
+
+    // マスク領域の表示を更新（モードは継続）
+    if let Some(effects) = effects {
+        let _ = apply_mode_effects(effects);
+    }
+}
+
 /**
  * エリア選択モードを終了（キャンセル）する
  *
@@ -178,35 +272,45 @@ pub fn end_area_select_mode() {
  * ESCキーでキャンセルされたとき (`hook/keyboard.rs` から) に呼び出されます。
  *
  * # クリーンアップ処理
- * 1. `AppState` の `is_area_select_mode` と `is_dragging` フラグを `false` にリセットします。
- * 2. `area_select_overlay` を非表示にします。
- * 3. マウスとキーボードのフックをアンインストールします (`uninstall_hooks`)。
- * 4. UIコントロールの状態を通常モードに戻します。
- * 5. メインダイアログを復元し、最前面に表示します。
+ * 1. `AppState` のモードを `AppMode::Idle` に戻します（ドラッグ中の場合もドラッグごと終了）。
+ * 2. 遷移が返した副作用を `apply_mode_effects` で実行します
+ *    （`area_select_overlay` の非表示と固定フレームの解放、フックのアンインストール、UIコントロールの更新）。
+ *    エリア選択モード中でなかった場合は、他のモードのフック・オーバーレイに触れないよう何もしません。
+ * 3. メインダイアログを復元し、最前面に表示します。
  */
 pub fn cancel_area_select_mode() {
     let app_state = AppState::get_app_state_mut();
 
-    // 【Step 1】AppState フラグの安全な初期化
-    app_state.is_area_select_mode = false; // エリア選択モード終了
+    // 【Step 1】AppState のモードを通常に戻す（ドラッグ中だった場合もドラッグごと終了）
+    let effects = app_state.mode.exit_area_select();
+    app_state.is_mask_dragging = false;
+    app_state.is_secondary_dragging = false;
 
-    // ドラッグ中だった場合もフラグをリセット
-    if app_state.is_dragging {
-        app_state.is_dragging = false;
+    // 【Step 2】オーバーレイを非表示にし、システムフックを停止してUIコントロールの状態を更新
+    if let Some(effects) = effects {
+        let _ = apply_mode_effects(effects);
     }
 
-    // オーバーレイを非表示にする
-    if let Some(overlay) = app_state.area_select_overlay.as_mut() {
-        overlay.hide_overlay();
-    }
-
-    // システムフックを停止
-    uninstall_hooks();
-    // UIコントロールの状態を更新
-    update_input_control_states();
-
     // メインダイアログを復元して最前面に表示
     bring_dialog_to_front();
 
     println!("エリア選択モードを終了します");
 }
+
+/// ドラッグの開始点・終了点から、正規化した矩形を求める
+pub fn current_drag_rect(app_state: &AppState) -> DragRect {
+    DragRect::from_corners(
+        (app_state.drag_start.x, app_state.drag_start.y),
+        (app_state.drag_end.x, app_state.drag_end.y),
+    )
+}
+
+/// 正規化した矩形を、保存・キャプチャに使用する `RECT` に変換する
+pub fn to_win32_rect(rect: DragRect) -> RECT {
+    RECT {
+        left: rect.left,
+        top: rect.top,
+        right: rect.right,
+        bottom: rect.bottom,
+    }
+}
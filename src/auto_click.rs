@@ -9,7 +9,9 @@
 メインUIスレッドをブロックしないように、バックグラウンドスレッドで実行されます。
 
 【主要機能】
-1.  **`AutoClicker` 構造体**: 自動クリック機能の状態（有効/無効、間隔、回数など）を管理します。
+1.  **`AutoClicker` 構造体**: 自動クリック機能の状態（有効/無効、間隔、回数、動作モードなど）を管理します。
+    -   `AutoClickMode::ClickAndCapture`: クリックを合成し、そのクリックをトリガーにキャプチャ（従来動作）
+    -   `AutoClickMode::CaptureOnly`: クリックを合成せず、一定間隔でキャプチャのみ実行（進捗バーの監視など）
 2.  **バックグラウンド実行**: `std::thread` を使用して、クリック処理を別スレッドで実行し、UIの応答性を維持します。
 3.  **安全なスレッド制御**:
     -   `Arc<AtomicBool>` を使用した停止フラグにより、外部から安全にスレッドを停止させることができます。
@@ -18,7 +20,17 @@
 
 【技術仕様】
 -   **クリックシミュレーション**: `SendInput` API を使用して、物理的なマウスクリックイベントを生成します。
--   **スレッド同期**: `Arc` と `Atomic*` 型（`AtomicBool`, `AtomicU32`）を使用して、スレッド間で安全に状態を共有・変更します。
+    `dwExtraInfo` に `AUTO_CLICK_EXTRA_INFO` を設定し、フック側で自アプリの合成クリックと判別できるようにします。
+-   **スレッド同期**: `Arc` と `Atomic*` 型（`AtomicBool`, `AtomicU8`, `AtomicU32`, `AtomicU64`）を使用して、スレッド間で安全に状態を共有・変更します。
+-   **一時停止**: UACの確認画面（セキュアデスクトップ）やロック画面は取得できず真っ黒な画像になるため、クリックの前に
+    入力デスクトップが通常のデスクトップ（`Default`）かを `OpenInputDesktop` で確認し、そうでない間、
+    またはセッションがロックされている間（`WM_WTSSESSION_CHANGE`、`set_session_locked`）は待機します。
+    戻った後は間隔分待ってから再開します（一時停止中も最大実行時間の経過時間に含める）。
+-   **ユーザー操作中の一時停止**: 有効時（`set_pause_on_user_input`）は、マウスフックが実際のマウス移動の時刻を
+    `note_user_input` で記録し、最後の操作から静止時間（既定2秒、レジストリ `UserInputQuietMs`）が経つまで
+    次のクリックを遅らせます。回数は飛ばさず、その分だけ実行が後ろに延びます。
+-   **ESCでの停止の確認**: 残り回数が `esc_confirm_threshold` 以上の実行中は、誤ってESCを押して長い実行を
+    やり直さないよう、すぐに停止せずに確認中（`set_stop_confirming`）として一時停止し、確認ダイアログで停止を選んだ場合のみ停止します。
 
 【処理フロー】
 1.  **[UI]** ユーザーが自動クリックを有効にし、キャプチャモードを開始します。
@@ -26,47 +38,99 @@
 3.  **`AutoClicker::start()`**:
     -   停止フラグをリセットし、新しいバックグラウンドスレッドを生成します。
     -   スレッド内で `auto_click_loop` 関数が実行されます。
-4.  **`auto_click_loop()`**:
+4.  **`auto_click_loop()`**（判断は `clickcapture::auto_click_runner::run_auto_click`、Win32 APIの呼び出しは `Win32AutoClickDriver`）:
     -   指定された間隔で待機します。
     -   `perform_mouse_click()` を呼び出して、`start`時に指定された座標でクリックをシミュレートします。
+    -   クリック位置を進める設定（`set_position_step`）の場合は、開始位置 + 移動量 × 回数の位置へ
+        カーソルを移動してクリックします（スクロールするリストの「次へ」など）。画面外に出たら終了します。
     -   このシミュレートされたクリックは `hook/mouse.rs` に捕捉され、`capture_screen_area_with_counter()` が実行されます。
-    -   指定回数に達するか、停止フラグが立てられるまで上記を繰り返します。
+    -   `CaptureOnly` モードではクリックせず、`WM_AUTO_CAPTURE_REQUEST` をメインダイアログに送信し、
+        メインスレッドでキャプチャを実行します（ワーカースレッドからGDIを呼び出さない）。
+    -   指定回数または最大実行時間（`set_max_duration`）に達するか、停止フラグが立てられるまで上記を繰り返します。
+    -   セキュアデスクトップ・ロック画面の表示中はクリック・キャプチャせずに一時停止し、戻ったら自動で再開します。
+    -   開始時にクリック位置にあったウィンドウが閉じられた・クリック位置から外れた場合は、デスクトップの壁紙を
+        撮り続けないよう終了します（`set_verify_target_window`、レジストリ `AutoClickTargetCheck` で無効化）。
 5.  **[ループ終了後]**:
     -   `PostMessageW` でメインダイアログに `WM_AUTO_CLICK_COMPLETE` メッセージを送信します。
 6.  **[main.rs]**: `WM_AUTO_CLICK_COMPLETE` を受信し、キャプチャモードを終了するなどの後処理を実行します。
 
 【AI解析用：依存関係】
 - `hook/mouse.rs`: ユーザーの最初のクリックをトリガーとして `AutoClicker::start` を呼び出す。
+- `clickcapture::auto_click_runner`（ライブラリ）: 実行ループ本体・`AutoClickMode` / `AutoClickPause`（Win32 APIなし、単体テストあり）。
 - `main.rs`: `WM_AUTO_CLICK_COMPLETE` メッセージを受信して後処理を行う。
+- `ui/dialog_handler.rs`: `WM_WTSSESSION_CHANGE`（ロック・ロック解除）で `set_session_locked` を呼び出す。
+  `WM_ESC_STOP_CONFIRM` で停止の確認ダイアログを表示し、`set_stop_confirming(false)` で確認中を解除する。
+- `hook/keyboard.rs`: ESCキーで `needs_stop_confirmation` を確認し、確認中にする。
+- `hook/mouse.rs`: 実行中、自動クリック由来でないマウス移動で `note_user_input` を呼び出す。
 - `app_state.rs`: `AppState` に `AutoClicker` インスタンスを保持する。
+  ワーカースレッドからは `AppState` を参照せず、`shared_dialog_hwnd` で通知先を取得する。
+- `strings.rs`: ログ・警告メッセージの表示文字列（表示言語に応じて日本語・英語を切り替え）。
 */
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicU64, Ordering};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use windows::Win32::UI::WindowsAndMessaging::{MB_ICONWARNING, MB_OK, PostMessageW};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GA_ROOTOWNER, GetAncestor, GetCursorPos, IsWindow, PostMessageW, SetCursorPos, WindowFromPoint,
+};
 use windows::Win32::{
-    Foundation::{LPARAM, POINT, WPARAM},
+    Foundation::{HANDLE, HWND, LPARAM, POINT, WPARAM},
+    Graphics::Gdi::{InvalidateRect, MONITOR_DEFAULTTONULL, MonitorFromPoint},
+    System::StationsAndDesktops::{
+        CloseDesktop, DESKTOP_CONTROL_FLAGS, DESKTOP_READOBJECTS, GetUserObjectInformationW,
+        OpenInputDesktop, UOI_NAME,
+    },
+    System::SystemInformation::GetTickCount64,
     UI::Input::KeyboardAndMouse::*,
 };
 
-use crate::app_state::AppState;
-use crate::constants::WM_AUTO_CLICK_COMPLETE;
-use crate::overlay::Overlay;
-use crate::system_utils::{app_log, show_message_box};
+use crate::app_state::{AppState, SafeHWND};
+use crate::constants::{WM_AUTO_CAPTURE_REQUEST, WM_AUTO_CLICK_COMPLETE};
+use crate::strings;
+use crate::system_utils::app_log;
+use clickcapture::auto_click_runner::{
+    AutoClickDriver, AutoClickEnd, AutoClickEvent, AutoClickPlan, ScreenPoint, is_stepping,
+    run_auto_click, step_position,
+};
+pub use clickcapture::auto_click_runner::{AutoClickMode, AutoClickPause};
+use clickcapture::input_tag::AUTO_CLICK_EXTRA_INFO;
+
+/// クリック後の描画待ち時間（ミリ秒）＝設定できるクリック間隔の下限
+///
+/// 合成クリックでページ送りした画面が再描画される前に次のクリック（キャプチャ）が実行されると、
+/// 描画途中や送り前の画面を撮影してしまうため、これより短い間隔は切り上げる。
+pub const POST_CLICK_RENDER_DELAY_MS: u64 = 200;
+
+/// ESCキーで停止する前に確認する残り回数の既定値（レジストリ `EscConfirmThreshold` で変更、0は確認しない）
+pub const DEFAULT_ESC_CONFIRM_THRESHOLD: u32 = 50;
 
-const MAX_CAPTURE_COUNT: u32 = 999; // 最大連続クリック数制限
+/// ユーザー操作中の一時停止で、最後の操作から次のクリックまで空ける静止時間の既定値（ミリ秒）
+pub const DEFAULT_USER_INPUT_QUIET_MS: u32 = 2000;
 
 /// 自動連続クリック機能の状態と制御を管理する
 #[derive(Debug)]
 pub struct AutoClicker {
     enabled: bool,                                 // 機能がUI上で有効かどうかのフラグ
+    mode: AutoClickMode, // 動作モード（クリック＋キャプチャ / キャプチャのみ）
     stop_flag: Arc<AtomicBool>, // バックグラウンドスレッドを停止させるためのフラグ
-    interval_ms: u64,           // クリック実行間隔（ミリ秒）
+    interval_ms: u64,    // クリック実行間隔（ミリ秒）
     progress_count: Arc<AtomicU32>, // 現在の実行回数
-    max_count: Arc<AtomicU32>,  // 設定された最大実行回数
+    max_count: Arc<AtomicU32>, // 設定された最大実行回数
+    max_duration: Option<Duration>, // 最大実行時間（None は時間で停止しない、回数と先に達した方で停止）
+    session_locked: Arc<AtomicBool>, // セッションがロックされているか（UIスレッドが WM_WTSSESSION_CHANGE で更新）
+    paused: Arc<AtomicU8>, // 一時停止中の理由（0は停止していない、スレッドが更新、オーバーレイの表示用）
+    stop_confirming: Arc<AtomicBool>, // ESCキーによる停止の確認中か（UIスレッドが更新）
+    esc_confirm_threshold: u32, // ESCキーで停止する前に確認する残り回数（0は確認しない）
+    pause_on_user_input: bool, // ユーザーがマウスを操作している間は次のクリックを遅らせるか
+    user_input_quiet_ms: u32, // 最後のユーザー操作から次のクリックまで空ける静止時間（ミリ秒）
+    user_input: Arc<UserInputMonitor>, // ユーザーのマウス操作の検出（マウスフックが更新）
+    started_at: Option<Instant>, // 開始時刻（完了通知の経過時間用）
+    verify_target_window: bool, // クリックの前に、開始時のウィンドウがクリック位置にあるかを確認するか
+    position_step: POINT,       // 1回ごとにクリック位置を進める量（(0, 0) は同じ位置でクリック）
+    position_steps: u32, // クリック位置を進める回数の上限（0は無制限、以降は最後の位置でクリック）
+    start_position: Option<POINT>, // 開始時のクリック位置（クリック位置の進行の基準）
     thread_handle: Option<thread::JoinHandle<()>>, // バックグラウンドスレッドのハンドル
 }
 
@@ -75,10 +139,24 @@ impl AutoClicker {
     pub fn new() -> Self {
         Self {
             enabled: false,
+            mode: AutoClickMode::ClickAndCapture,
             stop_flag: Arc::new(AtomicBool::new(true)),
             interval_ms: 1000, // デフォルト1秒
             progress_count: Arc::new(AtomicU32::new(0)),
             max_count: Arc::new(AtomicU32::new(0)),
+            max_duration: None,
+            session_locked: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicU8::new(0)),
+            stop_confirming: Arc::new(AtomicBool::new(false)),
+            esc_confirm_threshold: DEFAULT_ESC_CONFIRM_THRESHOLD,
+            pause_on_user_input: false,
+            user_input_quiet_ms: DEFAULT_USER_INPUT_QUIET_MS,
+            user_input: Arc::new(UserInputMonitor::new()),
+            started_at: None,
+            verify_target_window: true,
+            position_step: POINT { x: 0, y: 0 },
+            position_steps: 0,
+            start_position: None,
             thread_handle: None,
         }
     }
@@ -93,14 +171,38 @@ impl AutoClicker {
         self.enabled = enabled;
     }
 
+    /// 動作モードを取得する
+    pub fn get_mode(&self) -> AutoClickMode {
+        self.mode
+    }
+
+    /// 動作モードを設定する（次回の開始から適用）
+    pub fn set_mode(&mut self, mode: AutoClickMode) {
+        self.mode = mode;
+    }
+
     /// バックグラウンドスレッドが実行中かを確認する
     pub fn is_running(&self) -> bool {
         self.thread_handle.is_some()
     }
 
     /// クリック間隔（ミリ秒）を設定する
+    ///
+    /// 1秒未満（250ms / 500ms / 750ms）も設定できます。
+    /// `POST_CLICK_RENDER_DELAY_MS` より短い値は、ログを出力したうえで下限に切り上げます。
     pub fn set_interval(&mut self, interval_ms: u64) {
-        self.interval_ms = interval_ms;
+        if interval_ms < POST_CLICK_RENDER_DELAY_MS {
+            app_log(&strings::auto_click_interval_clamped(
+                interval_ms,
+                POST_CLICK_RENDER_DELAY_MS,
+            ));
+        }
+        self.interval_ms = interval_ms.max(POST_CLICK_RENDER_DELAY_MS);
+    }
+
+    /// 設定されたクリック間隔（ミリ秒）を取得する
+    pub fn get_interval(&self) -> u64 {
+        self.interval_ms
     }
 
     /// 現在の実行回数を取得する
@@ -108,6 +210,13 @@ impl AutoClicker {
         self.progress_count.load(Ordering::Relaxed)
     }
 
+    /// 開始からの経過時間を取得する（未開始の場合は0）
+    pub fn get_elapsed(&self) -> Duration {
+        self.started_at
+            .map(|started_at| started_at.elapsed())
+            .unwrap_or_default()
+    }
+
     /// 最大実行回数を設定する
     pub fn set_max_count(&mut self, count: u32) {
         self.max_count.store(count, Ordering::Relaxed);
@@ -118,44 +227,211 @@ impl AutoClicker {
         self.max_count.load(Ordering::Relaxed)
     }
 
+    /// 最大実行時間を設定する（次回の開始から適用、None は時間で停止しない）
+    pub fn set_max_duration(&mut self, max_duration: Option<Duration>) {
+        self.max_duration = max_duration;
+    }
+
+    /// 設定された最大実行時間を取得する
+    pub fn get_max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// 最大実行時間までの残り時間を取得する（最大実行時間が未設定の場合は None）
+    ///
+    /// キャプチャ中オーバーレイの進行状況ラベルに表示します。
+    pub fn get_remaining_duration(&self) -> Option<Duration> {
+        self.max_duration
+            .map(|max_duration| max_duration.saturating_sub(self.get_elapsed()))
+    }
+
+    /// セッションのロック状態を設定する（`WM_WTSSESSION_CHANGE` で呼び出す、実行中にも反映される）
+    ///
+    /// ロック中は実行中の自動クリックが一時停止し、ロック解除後に再開します。
+    pub fn set_session_locked(&self, locked: bool) {
+        self.session_locked.store(locked, Ordering::Relaxed);
+    }
+
+    /// ESCキーで停止する前に確認する残り回数を設定する（0は確認せずに停止する）
+    pub fn set_esc_confirm_threshold(&mut self, threshold: u32) {
+        self.esc_confirm_threshold = threshold;
+    }
+
+    /// ESCキーで停止する前に確認が必要かを取得する（実行中で、残り回数が閾値以上の場合）
+    ///
+    /// 短い実行は従来どおりすぐに停止します。
+    pub fn needs_stop_confirmation(&self) -> bool {
+        let remaining = self
+            .get_max_count()
+            .saturating_sub(self.get_progress_count());
+        self.is_running()
+            && self.esc_confirm_threshold > 0
+            && remaining >= self.esc_confirm_threshold
+    }
+
+    /// ESCキーによる停止の確認中かを設定する（確認中は実行中の自動クリックが一時停止する）
+    pub fn set_stop_confirming(&self, confirming: bool) {
+        self.stop_confirming.store(confirming, Ordering::Relaxed);
+    }
+
+    /// ESCキーによる停止の確認中かを取得する
+    pub fn is_stop_confirming(&self) -> bool {
+        self.stop_confirming.load(Ordering::Relaxed)
+    }
+
+    /// ユーザーがマウスを操作している間は次のクリックを遅らせるかを設定する（次回の開始から適用）
+    pub fn set_pause_on_user_input(&mut self, enabled: bool) {
+        self.pause_on_user_input = enabled;
+    }
+
+    /// ユーザーがマウスを操作している間は次のクリックを遅らせるかを取得する
+    pub fn is_pause_on_user_input(&self) -> bool {
+        self.pause_on_user_input
+    }
+
+    /// 最後のユーザー操作から次のクリックまで空ける静止時間（ミリ秒）を設定する（次回の開始から適用）
+    pub fn set_user_input_quiet_ms(&mut self, quiet_ms: u32) {
+        self.user_input_quiet_ms = quiet_ms;
+    }
+
+    /// 最後のユーザー操作から次のクリックまで空ける静止時間（ミリ秒）を取得する
+    pub fn get_user_input_quiet_ms(&self) -> u32 {
+        self.user_input_quiet_ms
+    }
+
+    /// ユーザーがマウスを動かした時刻を記録する（マウスフックから、自動クリックのタグのない移動で呼び出す）
+    ///
+    /// クリック位置を進める設定で自動クリックが `SetCursorPos` で移動した位置への移動は無視します。
+    pub fn note_user_input(&self, position: POINT) {
+        self.user_input.note_move(position);
+    }
+
+    /// セキュアデスクトップ・ロック画面・停止の確認・ユーザー操作のために一時停止している理由を取得する
+    ///
+    /// キャプチャ中オーバーレイの進行状況ラベルに表示します。
+    pub fn pause_reason(&self) -> Option<AutoClickPause> {
+        AutoClickPause::from_u8(self.paused.load(Ordering::Relaxed))
+    }
+
+    /// クリックの前に、開始時にクリック位置にあったウィンドウが残っているかを確認するかを設定する（次回の開始から適用）
+    ///
+    /// 実行中に意図的にウィンドウを切り替える手順では無効にします（レジストリ `AutoClickTargetCheck` が0）。
+    pub fn set_verify_target_window(&mut self, verify: bool) {
+        self.verify_target_window = verify;
+    }
+
+    /// 1回ごとにクリック位置を進める量と回数の上限を設定する（次回の開始から適用）
+    ///
+    /// スクロールするリストの「次へ」が一定の間隔で並んでいる場合などに、
+    /// `n` 回目の自動クリックを開始位置 + `step` × `n` の位置で実行します。
+    /// `steps` 回進めた後は最後の位置でクリックを続けます（0は無制限）。
+    pub fn set_position_step(&mut self, step: POINT, steps: u32) {
+        self.position_step = step;
+        self.position_steps = steps;
+    }
+
+    /// 直近の自動クリックの位置を取得する（クリック位置を進める設定で実行中の場合のみ）
+    ///
+    /// キャプチャ中オーバーレイの進行状況ラベルに表示します。
+    pub fn current_target(&self) -> Option<POINT> {
+        let step = (self.position_step.x, self.position_step.y);
+        if self.mode != AutoClickMode::ClickAndCapture || !is_stepping(step) {
+            return None;
+        }
+        self.start_position.map(|start| {
+            to_point(step_position(
+                (start.x, start.y),
+                step,
+                self.position_steps,
+                self.get_progress_count(),
+            ))
+        })
+    }
+
     /// 自動連続クリック処理をバックグラウンドスレッドで開始する
     ///
     /// # 引数
-    /// * `position` - クリックをシミュレートする画面上の座標。
-    pub fn start(&mut self, position: POINT) -> Result<(), String> {
+    /// * `position` - クリックをシミュレートする画面上の座標（`CaptureOnly` モードでは未使用）。
+    /// * `overlay_hwnd` - 再描画するキャプチャ中オーバーレイのハンドル（`AppState::capturing_overlay_hwnd`）。
+    pub fn start(&mut self, position: POINT, overlay_hwnd: Option<SafeHWND>) -> Result<(), String> {
         if self.thread_handle.is_some() {
-            return Err("連続クリックは既に開始されています".to_string());
+            return Err(strings::auto_click_already_running().to_string());
         }
 
         // スレッドを開始する前に停止フラグをリセット
         self.stop_flag.store(false, Ordering::Relaxed);
-        let stop_flag = Arc::clone(&self.stop_flag);
 
         let interval = self.interval_ms;
-
-        let max_count = Arc::clone(&self.max_count);
+        let mode = self.mode;
+        let position_step = self.position_step;
+        let position_steps = self.position_steps;
+        let max_duration = self.max_duration;
+        let plan = AutoClickPlan {
+            mode,
+            interval_ms: interval,
+            max_count: self.max_count.load(Ordering::Relaxed),
+            max_duration,
+            position: (position.x, position.y),
+            position_step: (position_step.x, position_step.y),
+            position_steps,
+        };
+        // クリックする場合のみ、開始位置のウィンドウ（所有者をたどった最上位）を記録する
+        let target_window = (mode == AutoClickMode::ClickAndCapture && self.verify_target_window)
+            .then(|| root_window_at(position))
+            .flatten();
 
         self.progress_count.store(0, Ordering::Relaxed);
-        let progress_count = Arc::clone(&self.progress_count);
+
+        self.paused.store(0, Ordering::Relaxed);
+        self.stop_confirming.store(false, Ordering::Relaxed);
+        // 開始のためのクリックまでのマウス移動は、実行中の操作として扱わない
+        self.user_input.reset();
+        let pause_state = PauseState {
+            session_locked: Arc::clone(&self.session_locked),
+            stop_confirming: Arc::clone(&self.stop_confirming),
+            user_input: self
+                .pause_on_user_input
+                .then(|| Arc::clone(&self.user_input)),
+            user_input_quiet_ms: u64::from(self.user_input_quiet_ms),
+            paused: Arc::clone(&self.paused),
+        };
+
+        let driver = Win32AutoClickDriver {
+            stop_flag: Arc::clone(&self.stop_flag),
+            started_at: Instant::now(),
+            progress_count: Arc::clone(&self.progress_count),
+            overlay_hwnd,
+            pause_state,
+            target_window,
+        };
 
         // バックグラウンドスレッドで連続クリック実行
-        let handle = thread::spawn(move || {
-            auto_click_loop(stop_flag, interval, progress_count, max_count, position);
-        });
+        let handle = thread::spawn(move || auto_click_loop(plan, driver));
 
         self.thread_handle = Some(handle);
-        app_log(&format!(
-            "🖱️ 連続クリックを開始しました（{}ms間隔, {}回クリック）",
-            interval,
-            self.max_count.load(Ordering::Relaxed)
-        ));
+        self.started_at = Some(Instant::now());
+        self.start_position = Some(position);
+        app_log(&strings::auto_click_started(mode, interval, plan.max_count));
+        if let Some(max_duration) = max_duration {
+            app_log(&strings::auto_click_max_duration(
+                max_duration.as_secs() / 60,
+            ));
+        }
+        if mode == AutoClickMode::ClickAndCapture && is_stepping(plan.position_step) {
+            app_log(&strings::auto_click_position_step(
+                position_step.x,
+                position_step.y,
+                position_steps,
+            ));
+        }
 
         Ok(())
     }
 
     /// 実行中の自動連続クリック処理を安全に停止する
     pub fn stop(&mut self) {
-        if self.stop_flag.load(Ordering::Relaxed) {
+        // `request_stop` で停止フラグのみ立てられている場合も、スレッドの終了を待機して後始末する
+        if self.thread_handle.is_none() {
             return; // 既に停止している場合は何もしない
         }
 
@@ -166,10 +442,109 @@ impl AutoClicker {
         if let Some(handle) = self.thread_handle.take() {
             let _ = handle.join();
         }
-        app_log("🛑 自動連続クリック処理（スレッド）を停止しました");
+        app_log(strings::auto_click_stopped());
+    }
+
+    /// スレッドの終了を待たずに停止を要求する
+    ///
+    /// マウスフック内など、自動クリックスレッドの `SendInput` が完了を待っている箇所から
+    /// `stop` を呼ぶとデッドロックするため、停止フラグのみを立てます。
+    /// スレッドはループを抜けて `WM_AUTO_CLICK_COMPLETE` を送信し、メインスレッドで後処理されます。
+    pub fn request_stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
     }
 }
 
+/// 自動クリックのスレッドと共有する一時停止の状態
+struct PauseState {
+    /// セッションがロックされているか（UIスレッドが更新）
+    session_locked: Arc<AtomicBool>,
+    /// ESCキーによる停止の確認中か（UIスレッドが更新）
+    stop_confirming: Arc<AtomicBool>,
+    /// ユーザーのマウス操作の検出（マウスフックが更新、`None` はユーザー操作で一時停止しない）
+    user_input: Option<Arc<UserInputMonitor>>,
+    /// 最後のユーザー操作から次のクリックまで空ける静止時間（ミリ秒）
+    user_input_quiet_ms: u64,
+    /// 一時停止中の理由（0は停止していない、スレッドが更新）
+    paused: Arc<AtomicU8>,
+}
+
+impl PauseState {
+    /// 現在一時停止すべき理由を確認する（停止の確認・ロックを優先、いずれでもなければ `None`）
+    fn reason(&self) -> Option<AutoClickPause> {
+        if self.stop_confirming.load(Ordering::Relaxed) {
+            Some(AutoClickPause::ConfirmingStop)
+        } else if self.session_locked.load(Ordering::Relaxed) {
+            Some(AutoClickPause::SessionLocked)
+        } else if !is_input_desktop_default() {
+            Some(AutoClickPause::SecureDesktop)
+        } else if self.is_user_active() {
+            Some(AutoClickPause::UserActive)
+        } else {
+            None
+        }
+    }
+
+    /// 最後のユーザー操作から静止時間が経っていないかを確認する
+    fn is_user_active(&self) -> bool {
+        self.user_input.as_ref().is_some_and(|user_input| {
+            user_input
+                .idle_ms()
+                .is_some_and(|idle_ms| idle_ms < self.user_input_quiet_ms)
+        })
+    }
+}
+
+/// マウスフックと自動クリックのスレッドで共有する、ユーザーのマウス操作の検出状態
+#[derive(Debug)]
+struct UserInputMonitor {
+    /// 最後にユーザーがマウスを動かした時刻（`GetTickCount64`、0は未検出）
+    last_move_tick: AtomicU64,
+    /// 自動クリックが最後に `SetCursorPos` で移動した位置（`pack_point`、フックがユーザー操作と区別する）
+    synthetic_position: AtomicU64,
+}
+
+impl UserInputMonitor {
+    fn new() -> Self {
+        Self {
+            last_move_tick: AtomicU64::new(0),
+            synthetic_position: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// 検出した操作を消去する（自動クリックの開始時）
+    fn reset(&self) {
+        self.last_move_tick.store(0, Ordering::Relaxed);
+        self.synthetic_position.store(u64::MAX, Ordering::Relaxed);
+    }
+
+    /// マウスの移動を記録する（自動クリックが移動した位置への移動は除く）
+    fn note_move(&self, position: POINT) {
+        if self.synthetic_position.load(Ordering::Relaxed) == pack_point(position) {
+            return;
+        }
+        self.last_move_tick
+            .store(unsafe { GetTickCount64() }, Ordering::Relaxed);
+    }
+
+    /// 自動クリックがカーソルを移動する位置を記録する（移動の前に呼び出す）
+    fn note_synthetic_move(&self, position: POINT) {
+        self.synthetic_position
+            .store(pack_point(position), Ordering::Relaxed);
+    }
+
+    /// 最後のユーザー操作からの経過時間（ミリ秒、未検出は `None`）
+    fn idle_ms(&self) -> Option<u64> {
+        let last = self.last_move_tick.load(Ordering::Relaxed);
+        (last != 0).then(|| unsafe { GetTickCount64() }.saturating_sub(last))
+    }
+}
+
+/// 座標を1つの `AtomicU64` に格納できるようにまとめる
+fn pack_point(position: POINT) -> u64 {
+    (u64::from(position.x as u32) << 32) | u64::from(position.y as u32)
+}
+
 impl Drop for AutoClicker {
     /// `AutoClicker` インスタンスが破棄される際に、実行中のスレッドを確実に停止させる
     fn drop(&mut self) {
@@ -177,104 +552,217 @@ impl Drop for AutoClicker {
     }
 }
 
-/// 自動クリックをバックグラウンドで実行するループ処理
+/// 自動クリックをバックグラウンドで実行する
 ///
-/// # 引数
-/// * `stop_flag` - ループを外部から停止させるためのフラグ。
-/// * `interval_ms` - クリックを実行する間隔（ミリ秒）。
-/// * `progress_count_boxed` - 実行回数をカウントするためのアトミックなカウンタ。
-/// * `max_count_boxed` - 実行回数の上限。
-/// * `position` - クリックをシミュレートする座標。
-fn auto_click_loop(
+/// 実行ループ本体（間隔・回数・一時停止・停止条件の判断）は `clickcapture::auto_click_runner::run_auto_click` で、
+/// クリックの送信などのWin32 APIの呼び出しは `Win32AutoClickDriver` が行います。
+/// このスレッドからは `AppState` を参照しません（UIスレッド専用のため）。
+fn auto_click_loop(plan: AutoClickPlan, mut driver: Win32AutoClickDriver) {
+    let end = run_auto_click(&plan, &mut driver);
+
+    // ループ終了後、メインスレッドに処理完了を非同期で通知する
+    // カスタムメッセージ（WM_AUTO_CLICK_COMPLETE）をダイアログのメッセージキューに送信
+    // 上限到達の警告はワーカースレッドで表示せず、WPARAMで伝えてメインスレッドで表示する
+    let limit_reached = end == AutoClickEnd::LimitReached;
+    if let Err(e) = post_dialog_message(WM_AUTO_CLICK_COMPLETE, limit_reached as usize) {
+        app_log(&strings::post_message_failed(&e));
+    }
+}
+
+/// 自動クリックのスレッドで使用する、Win32 APIによる `AutoClickDriver` の実装
+struct Win32AutoClickDriver {
+    /// 停止フラグ（UIスレッド・マウスフックが立てる）
     stop_flag: Arc<AtomicBool>,
-    interval_ms: u64,
-    progress_count_boxed: Arc<AtomicU32>,
-    max_count_boxed: Arc<AtomicU32>,
-    position: POINT,
-) {
-    let max_count = max_count_boxed.load(Ordering::Relaxed);
-    let mut progress_count = progress_count_boxed.load(Ordering::Relaxed);
-
-    let app_state = AppState::get_app_state_ref();
-
-    while !stop_flag.load(Ordering::Relaxed) {
-        // オーバーレイを最新状態に更新
-        let overlay = app_state
-            .capturing_overlay
-            .as_ref()
-            .expect("キャプチャーオーバーレイが存在しません。");
-        overlay.refresh_overlay();
-
-        // 指定された間隔で待機する。
-        // ただし、長い待機時間中に停止要求があった場合に即座に応答できるよう、
-        // 100ミリ秒ごとに短いスリープを繰り返し、その都度停止フラグを確認する。
-        let sleep_duration = Duration::from_millis(interval_ms);
-        let check_interval = Duration::from_millis(100);
-        let mut remaining = sleep_duration;
-
-        // `check_interval` ごとに停止フラグを確認しつつ、指定された `sleep_duration` に達するまで待機
-        while remaining > Duration::from_millis(0) && !stop_flag.load(Ordering::Relaxed) {
-            let sleep_time = remaining.min(check_interval);
-            // 指定時間スリープ
-            thread::sleep(sleep_time);
-            remaining = remaining.saturating_sub(sleep_time);
-        }
+    /// 開始時刻（最大実行時間の判定用）
+    started_at: Instant,
+    /// 実行回数（UIスレッドがオーバーレイ・ESCの確認に使用）
+    progress_count: Arc<AtomicU32>,
+    /// 毎回再描画を要求するキャプチャ中オーバーレイのハンドル
+    overlay_hwnd: Option<SafeHWND>,
+    /// セッションのロック状態と、一時停止中かどうか
+    pause_state: PauseState,
+    /// 開始時にクリック位置にあったウィンドウ（`None` は確認しない）
+    target_window: Option<SafeHWND>,
+}
 
-        // スリープ中に停止要求（例: ESCキー押下）があった場合、クリックを実行せずにループを抜ける
-        if stop_flag.load(Ordering::Relaxed) {
-            break;
-        }
+impl AutoClickDriver for Win32AutoClickDriver {
+    fn is_stop_requested(&self) -> bool {
+        // 停止フラグに加え、キャプチャモードが終了していれば停止する（`SharedState` はどのスレッドからも参照可）
+        self.stop_flag.load(Ordering::Relaxed) || !AppState::shared().is_capture_mode()
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
 
-        // 最大クリック数に到達したかチェック
-        // `MAX_CAPTURE_COUNT` は暴走を防ぐための安全装置
-        if progress_count >= MAX_CAPTURE_COUNT || progress_count >= max_count {
-            if progress_count >= MAX_CAPTURE_COUNT {
-                show_message_box(
-                    &format!(
-                        "⚠️ 連続クリックが最大クリック数({})に達しました。連続クリックを停止します。",
-                        MAX_CAPTURE_COUNT
-                    ),
-                    "自動クリック警告",
-                    MB_OK | MB_ICONWARNING,
-                );
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+
+    fn pause_reason(&self) -> Option<AutoClickPause> {
+        self.pause_state.reason()
+    }
+
+    fn set_paused(&mut self, reason: Option<AutoClickPause>) {
+        self.pause_state
+            .paused
+            .store(reason.map_or(0, |reason| reason as u8), Ordering::Relaxed);
+    }
+
+    fn refresh_overlay(&mut self) {
+        // UpdateWindowはUIスレッドに同期で描画させるため、`stop` の join と待ち合うと
+        // デッドロックする。再描画の要求のみ行い、描画はUIスレッドのメッセージループに任せる
+        if let Some(hwnd) = self.overlay_hwnd {
+            unsafe {
+                let _ = InvalidateRect(Some(*hwnd), None, true);
             }
-            break;
         }
+    }
+
+    fn is_on_screen(&self, point: ScreenPoint) -> bool {
+        is_on_screen(to_point(point))
+    }
 
-        // 実行回数をインクリメントし、クリックを実行
-        progress_count += 1;
-        app_log(&format!(
-            "🖱️ 自動クリック実行: マウス位置({}, {}) {}/{}回目",
-            position.x, position.y, progress_count, max_count
-        ));
-
-        // マウスクリックを実行
-        if let Err(e) = perform_mouse_click(position) {
-            app_log(&format!("❌ クリック実行エラー: {}", e));
-            break;
+    fn cursor_position(&self) -> Option<ScreenPoint> {
+        cursor_position().map(|position| (position.x, position.y))
+    }
+
+    fn is_target_window_at(&self, point: ScreenPoint) -> bool {
+        self.target_window
+            .is_none_or(|window| is_window_at(*window, to_point(point)))
+    }
+
+    fn click(&mut self, point: ScreenPoint, move_cursor: bool) -> Result<(), String> {
+        let position = to_point(point);
+        // クリック位置へのカーソル移動を、ユーザーのマウス操作として検出しないよう記録する
+        if move_cursor && let Some(user_input) = &self.pause_state.user_input {
+            user_input.note_synthetic_move(position);
         }
-        progress_count_boxed.store(progress_count, Ordering::Relaxed);
+        perform_mouse_click(position, move_cursor)
     }
 
-    // ループ終了後、メインスレッドに処理完了を非同期で通知する
-    let app_state = AppState::get_app_state_ref();
-    if let Some(hwnd) = app_state.dialog_hwnd {
-        unsafe {
-            // カスタムメッセージ（WM_AUTO_CLICK_COMPLETE）をダイアログのメッセージキューに送信
-            if let Err(e) = PostMessageW(Some(*hwnd), WM_AUTO_CLICK_COMPLETE, WPARAM(0), LPARAM(0))
-            {
-                app_log(&format!("❌ メッセージ送信エラー: {}", e));
+    fn request_capture(&mut self) -> Result<(), String> {
+        // キャプチャ自体はメインスレッドで実行する（GDI呼び出しをワーカースレッドで行わない）
+        post_dialog_message(WM_AUTO_CAPTURE_REQUEST, 0).map_err(|e| e.to_string())
+    }
+
+    fn report_progress(&mut self, count: u32) {
+        self.progress_count.store(count, Ordering::Relaxed);
+    }
+
+    fn notify(&mut self, event: AutoClickEvent) {
+        match event {
+            AutoClickEvent::DurationReached { count } => {
+                app_log(&strings::auto_click_duration_reached(count))
+            }
+            AutoClickEvent::Paused { reason, count } => {
+                app_log(&strings::auto_click_paused(reason, count))
+            }
+            AutoClickEvent::Resumed { reason } => app_log(strings::auto_click_resumed(reason)),
+            AutoClickEvent::TargetOffScreen { target } => {
+                app_log(&strings::auto_click_target_off_screen(target.0, target.1))
+            }
+            AutoClickEvent::TargetWindowLost => app_log(strings::auto_click_target_window_lost()),
+            AutoClickEvent::Clicking {
+                target,
+                count,
+                max_count,
+            } => app_log(&strings::auto_click_executed(
+                target.0, target.1, count, max_count,
+            )),
+            AutoClickEvent::ClickFailed(e) => app_log(&strings::click_failed(&e)),
+            AutoClickEvent::CaptureRequesting { count, max_count } => {
+                app_log(&strings::auto_capture_executed(count, max_count))
+            }
+            AutoClickEvent::CaptureRequestFailed(e) => {
+                app_log(&strings::capture_request_failed(&e))
             }
         }
     }
 }
 
+/// ライブラリの座標（`(x, y)`）を `POINT` に変換する
+fn to_point(point: ScreenPoint) -> POINT {
+    POINT {
+        x: point.0,
+        y: point.1,
+    }
+}
+
+/// メインダイアログのメッセージキューにカスタムメッセージを送信する
+fn post_dialog_message(message: u32, wparam: usize) -> windows::core::Result<()> {
+    match AppState::shared_dialog_hwnd() {
+        Some(hwnd) => unsafe { PostMessageW(Some(*hwnd), message, WPARAM(wparam), LPARAM(0)) },
+        None => Ok(()),
+    }
+}
+
+/// 指定した位置にあるウィンドウの最上位のウィンドウ（所有者をたどったもの）を取得する
+///
+/// 対象のアプリが表示したダイアログ・ポップアップも同じウィンドウとして扱うため、`GA_ROOTOWNER` を使用します。
+fn root_window_at(position: POINT) -> Option<SafeHWND> {
+    unsafe {
+        let hwnd = WindowFromPoint(position);
+        (!hwnd.is_invalid()).then(|| SafeHWND(GetAncestor(hwnd, GA_ROOTOWNER)))
+    }
+}
+
+/// 現在のカーソル位置を取得する（取得できない場合は `None`）
+fn cursor_position() -> Option<POINT> {
+    let mut position = POINT::default();
+    unsafe { GetCursorPos(&mut position) }
+        .is_ok()
+        .then_some(position)
+}
+
+/// 開始時のウィンドウが残っていて、まだ指定した位置にあるかを確認する
+fn is_window_at(window: HWND, position: POINT) -> bool {
+    let exists = unsafe { IsWindow(Some(window)).as_bool() };
+    exists && root_window_at(position).is_some_and(|current| *current == window)
+}
+
+/// 入力デスクトップが通常のデスクトップ（`Default`）かを確認する
+///
+/// UACの確認画面・ロック画面（`Winlogon` デスクトップ）の表示中は、`OpenInputDesktop` が
+/// アクセス拒否で失敗するか、`Default` 以外の名前が返されます。
+fn is_input_desktop_default() -> bool {
+    unsafe {
+        let Ok(desktop) = OpenInputDesktop(DESKTOP_CONTROL_FLAGS(0), false, DESKTOP_READOBJECTS)
+        else {
+            return false;
+        };
+        let mut name = [0u16; 64];
+        let result = GetUserObjectInformationW(
+            HANDLE(desktop.0),
+            UOI_NAME,
+            Some(name.as_mut_ptr() as *mut _),
+            (name.len() * std::mem::size_of::<u16>()) as u32,
+            None,
+        );
+        let _ = CloseDesktop(desktop);
+
+        let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+        result.is_ok() && String::from_utf16_lossy(&name[..len]).eq_ignore_ascii_case("Default")
+    }
+}
+
+/// 座標がいずれかのモニター上にあるか（仮想スクリーン内でもモニターの隙間は画面外とする）
+fn is_on_screen(position: POINT) -> bool {
+    unsafe { !MonitorFromPoint(position, MONITOR_DEFAULTTONULL).is_invalid() }
+}
+
 /// `SendInput` APIを使用してマウスクリックをシミュレートする
 ///
 /// 指定されたスクリーン座標で、マウスの左ボタンダウンと左ボタンアップの
 /// イベントを連続して発生させる。
-fn perform_mouse_click(position: POINT) -> Result<(), String> {
+/// `move_cursor` が `true` の場合（クリック位置を進める設定）は、先に `SetCursorPos` でカーソルを移動する。
+fn perform_mouse_click(position: POINT, move_cursor: bool) -> Result<(), String> {
     unsafe {
+        if move_cursor {
+            SetCursorPos(position.x, position.y)
+                .map_err(|e| format!("SetCursorPos failed: {}", e))?;
+        }
+
         // マウス入力構造体を作成
         let mut inputs = [
             INPUT {
@@ -286,7 +774,7 @@ fn perform_mouse_click(position: POINT) -> Result<(), String> {
                         mouseData: 0,
                         dwFlags: MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_LEFTDOWN,
                         time: 0,
-                        dwExtraInfo: 0,
+                        dwExtraInfo: AUTO_CLICK_EXTRA_INFO, // 自動クリック由来であることをフックに伝える
                     },
                 },
             },
@@ -299,7 +787,7 @@ fn perform_mouse_click(position: POINT) -> Result<(), String> {
                         mouseData: 0,
                         dwFlags: MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_LEFTUP,
                         time: 0,
-                        dwExtraInfo: 0,
+                        dwExtraInfo: AUTO_CLICK_EXTRA_INFO, // 自動クリック由来であることをフックに伝える
                     },
                 },
             },
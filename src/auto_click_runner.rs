@@ -0,0 +1,728 @@
+/*
+============================================================================
+自動クリック実行ループモジュール (auto_click_runner.rs)
+============================================================================
+
+【ファイル概要】
+自動連続クリックの間隔・回数・最大実行時間・一時停止・クリック位置の進行・停止条件を判断する
+実行ループ本体のライブラリモジュールです。クリックの送信・待機・画面やウィンドウの確認などの副作用は
+`AutoClickDriver` トレイト経由で呼び出すため、Win32 APIに依存せず、仮想の時計で単体テストできます。
+
+【主要機能】
+1.  **`run_auto_click`**: `AutoClickPlan` に従ってクリック（またはキャプチャ要求）を繰り返し、終了理由を返す
+2.  **`AutoClickDriver`**: 停止要求・時計・待機・一時停止の理由・クリック・キャプチャ要求などの注入点
+3.  **`step_position`**: クリック位置を進める設定での `n` 回目のクリック位置
+
+【技術仕様】
+-   **待機**: 間隔を100ミリ秒ごとに分けて待機し、その都度停止要求と最大実行時間を確認（1秒未満の間隔も端数分だけ待つ）
+-   **一時停止**: 理由がある間は500ミリ秒ごとに確認し、戻った後は間隔分待ってから次のクリックを実行（回数は飛ばさない）
+-   **上限**: 設定回数に加え、暴走を防ぐための `MAX_CAPTURE_COUNT` で終了（`AutoClickEnd::LimitReached`）
+-   **対象ウィンドウ**: クリック位置を進めない場合はカーソルを移動しないため、現在のカーソル位置で確認する
+
+【AI解析用：依存関係】
+-   `auto_click.rs`（バイナリ側）: `SendInput`・`OpenInputDesktop`・`WindowFromPoint` などで `AutoClickDriver` を実装し、
+    ワーカースレッドから `run_auto_click` を呼び出す。`AutoClickMode` / `AutoClickPause` を再公開する
+-   `strings.rs`（バイナリ側）: `AutoClickEvent` に対応するログメッセージ
+*/
+
+use std::time::Duration;
+
+/// 最大連続クリック数（暴走を防ぐための安全装置、設定回数より優先）
+pub const MAX_CAPTURE_COUNT: u32 = 999;
+
+/// 間隔の待機中に停止要求・最大実行時間を確認する間隔（ミリ秒）
+const STOP_CHECK_INTERVAL_MS: u64 = 100;
+
+/// 一時停止中に理由が解消したかを確認する間隔（ミリ秒）
+const PAUSE_CHECK_INTERVAL_MS: u64 = 500;
+
+/// 画面上の座標（仮想スクリーン座標、`POINT` の `(x, y)`）
+pub type ScreenPoint = (i32, i32);
+
+/// 自動クリックを一時停止している理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AutoClickPause {
+    /// UACの確認画面などのセキュアデスクトップが表示されている（取得できず真っ黒になる）
+    SecureDesktop = 1,
+    /// セッションがロックされている（ロック画面が表示されている）
+    SessionLocked = 2,
+    /// ESCキーによる停止の確認ダイアログを表示している
+    ConfirmingStop = 3,
+    /// ユーザーがマウスを操作している（静止時間が経つまで次のクリックを遅らせる）
+    UserActive = 4,
+}
+
+impl AutoClickPause {
+    /// スレッド間で共有する値（0は一時停止していない）から変換する
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::SecureDesktop),
+            2 => Some(Self::SessionLocked),
+            3 => Some(Self::ConfirmingStop),
+            4 => Some(Self::UserActive),
+            _ => None,
+        }
+    }
+}
+
+/// 自動連続クリックの動作モード
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AutoClickMode {
+    /// クリックを合成し、そのクリックをトリガーにキャプチャする（開始は最初のクリック）
+    #[default]
+    ClickAndCapture,
+    /// クリックを合成せず、一定間隔でキャプチャのみ実行する（開始はキャプチャモード開始直後）
+    CaptureOnly,
+}
+
+/// 1回の自動クリックの実行内容（開始時に確定し、実行中は変更しない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoClickPlan {
+    /// 動作モード（`CaptureOnly` ではクリックせずにキャプチャ要求のみ送信）
+    pub mode: AutoClickMode,
+    /// クリックを実行する間隔（ミリ秒）
+    pub interval_ms: u64,
+    /// 実行回数の上限
+    pub max_count: u32,
+    /// 実行時間の上限（None は時間で停止しない）。回数の上限と先に達した方で停止する
+    pub max_duration: Option<Duration>,
+    /// クリックする座標（クリック位置を進める場合は開始位置）
+    pub position: ScreenPoint,
+    /// 1回ごとにクリック位置を進める量（(0, 0) は同じ位置でクリック）
+    pub position_step: ScreenPoint,
+    /// クリック位置を進める回数の上限（0は無制限）
+    pub position_steps: u32,
+}
+
+/// 実行中の出来事（バイナリ側でログに出力する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoClickEvent {
+    /// 最大実行時間に達した（`count` はそれまでの実行回数）
+    DurationReached { count: u32 },
+    /// 一時停止した
+    Paused { reason: AutoClickPause, count: u32 },
+    /// 一時停止から再開した
+    Resumed { reason: AutoClickPause },
+    /// 進めたクリック位置が画面外に出た
+    TargetOffScreen { target: ScreenPoint },
+    /// 開始時のウィンドウがクリック位置に無くなった
+    TargetWindowLost,
+    /// クリックを実行する
+    Clicking {
+        target: ScreenPoint,
+        count: u32,
+        max_count: u32,
+    },
+    /// クリックの送信に失敗した
+    ClickFailed(String),
+    /// キャプチャを要求する（`CaptureOnly`）
+    CaptureRequesting { count: u32, max_count: u32 },
+    /// キャプチャの要求に失敗した
+    CaptureRequestFailed(String),
+}
+
+/// 自動クリックが終了した理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoClickEnd {
+    /// 停止要求（ESCキー、キャプチャモードの終了など）
+    Stopped,
+    /// 設定回数に達した
+    Completed,
+    /// 最大実行時間に達した
+    DurationReached,
+    /// `MAX_CAPTURE_COUNT` に達した（UIスレッドで警告を表示する）
+    LimitReached,
+    /// 進めたクリック位置が画面外に出た
+    TargetOffScreen,
+    /// 開始時のウィンドウがクリック位置に無くなった
+    TargetWindowLost,
+    /// クリックの送信に失敗した
+    ClickFailed,
+    /// キャプチャの要求に失敗した
+    CaptureRequestFailed,
+}
+
+/// 自動クリックの実行ループが呼び出す副作用（バイナリ側はWin32 API、テストは仮想の時計で実装する）
+pub trait AutoClickDriver {
+    /// 停止が要求されているか
+    fn is_stop_requested(&self) -> bool;
+    /// 開始からの経過時間（一時停止中も含む）
+    fn elapsed(&self) -> Duration;
+    /// 指定した時間だけ待機する
+    fn sleep(&mut self, duration: Duration);
+    /// 現在一時停止すべき理由（無ければ `None`）
+    fn pause_reason(&self) -> Option<AutoClickPause>;
+    /// 一時停止中の理由を公開する（キャプチャ中オーバーレイの表示用、`None` は再開）
+    fn set_paused(&mut self, reason: Option<AutoClickPause>);
+    /// キャプチャ中オーバーレイの再描画を要求する
+    fn refresh_overlay(&mut self);
+    /// 座標がいずれかのモニター上にあるか
+    fn is_on_screen(&self, point: ScreenPoint) -> bool;
+    /// 現在のカーソル位置（取得できない場合は `None`）
+    fn cursor_position(&self) -> Option<ScreenPoint>;
+    /// 開始時のウィンドウが指定した位置に残っているか（確認しない設定では常に `true`）
+    fn is_target_window_at(&self, point: ScreenPoint) -> bool;
+    /// クリックを送信する（`move_cursor` が `true` の場合は先にカーソルを移動する）
+    fn click(&mut self, point: ScreenPoint, move_cursor: bool) -> Result<(), String>;
+    /// キャプチャをUIスレッドに要求する（`CaptureOnly`）
+    fn request_capture(&mut self) -> Result<(), String>;
+    /// 実行回数を公開する（1回実行するごと）
+    fn report_progress(&mut self, count: u32);
+    /// 実行中の出来事を通知する（ログ出力用）
+    fn notify(&mut self, event: AutoClickEvent);
+}
+
+/// 自動クリックを実行し、終了した理由を返す
+///
+/// 間隔分待ってから1回ずつクリック（またはキャプチャ要求）を実行し、停止要求・設定回数・
+/// 最大実行時間・`MAX_CAPTURE_COUNT`・画面外・対象ウィンドウの消失・送信の失敗のいずれかで終了します。
+/// 一時停止中は回数を進めずに待ち、戻った後は間隔分待ってから次のクリックを実行します。
+pub fn run_auto_click(plan: &AutoClickPlan, driver: &mut impl AutoClickDriver) -> AutoClickEnd {
+    let stepping = is_stepping(plan.position_step);
+    let mut progress_count = 0;
+    // 最大実行時間に達する時刻（待機中に達した場合は、次のクリックを待たずに終了する）
+    let deadline = plan
+        .max_duration
+        .map(|max_duration| driver.elapsed() + max_duration);
+
+    loop {
+        if driver.is_stop_requested() {
+            return AutoClickEnd::Stopped;
+        }
+
+        // オーバーレイを最新状態に更新
+        driver.refresh_overlay();
+
+        // 指定された間隔で待機する。
+        // ただし、長い待機時間中に停止要求があった場合に即座に応答できるよう、
+        // 100ミリ秒ごとに短いスリープを繰り返し、その都度停止要求を確認する。
+        // （1秒未満の間隔では最後の端数分だけスリープするため、間隔の精度は保たれる）
+        let check_interval = Duration::from_millis(STOP_CHECK_INTERVAL_MS);
+        let mut remaining = Duration::from_millis(plan.interval_ms);
+        while !remaining.is_zero()
+            && !driver.is_stop_requested()
+            && !is_deadline_passed(deadline, driver)
+        {
+            let sleep_time = remaining.min(check_interval);
+            driver.sleep(sleep_time);
+            remaining = remaining.saturating_sub(sleep_time);
+        }
+
+        // スリープ中に停止要求（例: ESCキー押下）があった場合、クリックを実行せずに終了する
+        if driver.is_stop_requested() {
+            return AutoClickEnd::Stopped;
+        }
+
+        // 最大実行時間に達した場合も、クリックを実行せずに終了する
+        if is_deadline_passed(deadline, driver) {
+            driver.notify(AutoClickEvent::DurationReached {
+                count: progress_count,
+            });
+            return AutoClickEnd::DurationReached;
+        }
+
+        // セキュアデスクトップ・ロック画面の表示中は真っ黒な画像になるため、戻るまでクリック・キャプチャしない
+        // （ESCキーによる停止の確認中・ユーザーがマウスを操作している間も、回数を飛ばさずに待ってからクリックする）
+        if let Some(reason) = driver.pause_reason() {
+            driver.notify(AutoClickEvent::Paused {
+                reason,
+                count: progress_count,
+            });
+            while let Some(current) = driver.pause_reason()
+                && !driver.is_stop_requested()
+                && !is_deadline_passed(deadline, driver)
+            {
+                driver.set_paused(Some(current));
+                driver.sleep(Duration::from_millis(PAUSE_CHECK_INTERVAL_MS));
+            }
+            driver.set_paused(None);
+            if !driver.is_stop_requested() && !is_deadline_passed(deadline, driver) {
+                driver.notify(AutoClickEvent::Resumed { reason });
+            }
+            // 戻った直後の画面は再描画中のことがあるため、間隔分待ってから次のクリックを実行する
+            continue;
+        }
+
+        // 最大クリック数に到達したかチェック
+        // `MAX_CAPTURE_COUNT` は暴走を防ぐための安全装置
+        if progress_count >= MAX_CAPTURE_COUNT {
+            return AutoClickEnd::LimitReached;
+        }
+        if progress_count >= plan.max_count {
+            return AutoClickEnd::Completed;
+        }
+
+        // 実行回数をインクリメント
+        progress_count += 1;
+
+        match plan.mode {
+            AutoClickMode::ClickAndCapture => {
+                // クリック位置を進める場合は、開始位置 + 移動量 × 回数（画面外に出たら終了）
+                let target = step_position(
+                    plan.position,
+                    plan.position_step,
+                    plan.position_steps,
+                    progress_count,
+                );
+                if stepping && !driver.is_on_screen(target) {
+                    driver.notify(AutoClickEvent::TargetOffScreen { target });
+                    return AutoClickEnd::TargetOffScreen;
+                }
+                // 対象のアプリが終了・クラッシュした場合は、デスクトップをクリックし続けないよう終了する
+                // （クリック位置を進めない場合はカーソルを移動しないため、クリックは現在のカーソル位置に届く）
+                let click_point = if stepping {
+                    target
+                } else {
+                    driver.cursor_position().unwrap_or(target)
+                };
+                if !driver.is_target_window_at(click_point) {
+                    driver.notify(AutoClickEvent::TargetWindowLost);
+                    return AutoClickEnd::TargetWindowLost;
+                }
+
+                driver.notify(AutoClickEvent::Clicking {
+                    target,
+                    count: progress_count,
+                    max_count: plan.max_count,
+                });
+                if let Err(e) = driver.click(target, stepping) {
+                    driver.notify(AutoClickEvent::ClickFailed(e));
+                    return AutoClickEnd::ClickFailed;
+                }
+            }
+            AutoClickMode::CaptureOnly => {
+                driver.notify(AutoClickEvent::CaptureRequesting {
+                    count: progress_count,
+                    max_count: plan.max_count,
+                });
+                if let Err(e) = driver.request_capture() {
+                    driver.notify(AutoClickEvent::CaptureRequestFailed(e));
+                    return AutoClickEnd::CaptureRequestFailed;
+                }
+            }
+        }
+        driver.report_progress(progress_count);
+    }
+}
+
+/// 最大実行時間に達したか（最大実行時間が未設定の場合は常に `false`）
+fn is_deadline_passed(deadline: Option<Duration>, driver: &impl AutoClickDriver) -> bool {
+    deadline.is_some_and(|deadline| driver.elapsed() >= deadline)
+}
+
+/// クリック位置を進める設定か（移動量が (0, 0) 以外）
+pub fn is_stepping(step: ScreenPoint) -> bool {
+    step != (0, 0)
+}
+
+/// `iteration` 回目の自動クリックの位置（開始位置 + 移動量 × 回数、回数は `steps` で頭打ち）
+///
+/// # 引数
+/// * `start` - 開始時のクリック位置（ユーザーの最初のクリック）
+/// * `step` - 1回ごとにクリック位置を進める量
+/// * `steps` - クリック位置を進める回数の上限（0は無制限）
+/// * `iteration` - 自動クリックの回数（1回目は開始位置から1回分進めた位置）
+pub fn step_position(
+    start: ScreenPoint,
+    step: ScreenPoint,
+    steps: u32,
+    iteration: u32,
+) -> ScreenPoint {
+    let count = if steps == 0 {
+        iteration
+    } else {
+        iteration.min(steps)
+    };
+    let count = count.min(i32::MAX as u32) as i32;
+    (
+        start.0.saturating_add(step.0.saturating_mul(count)),
+        start.1.saturating_add(step.1.saturating_mul(count)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    fn plan(interval_ms: u64, max_count: u32) -> AutoClickPlan {
+        AutoClickPlan {
+            mode: AutoClickMode::ClickAndCapture,
+            interval_ms,
+            max_count,
+            max_duration: None,
+            position: (100, 200),
+            position_step: (0, 0),
+            position_steps: 0,
+        }
+    }
+
+    /// 仮想の時計で動く `AutoClickDriver`（クリック・キャプチャ要求・通知を記録する）
+    #[derive(Default)]
+    struct FakeDriver {
+        now: Duration,
+        /// この時刻以降は停止要求ありとする
+        stop_at: Option<Duration>,
+        /// (開始, 終了, 理由) の間は一時停止する
+        pauses: Vec<(Duration, Duration, AutoClickPause)>,
+        /// x座標がこの値以上の位置は画面外とする
+        screen_right: Option<i32>,
+        cursor: Option<ScreenPoint>,
+        /// 開始時のウィンドウがある位置（`None` は確認しない）
+        target_window_at: Option<ScreenPoint>,
+        /// この回数目のクリックの送信に失敗する
+        fail_click: Option<usize>,
+        clicks: Vec<(Duration, ScreenPoint, bool)>,
+        captures: Vec<Duration>,
+        progress: Vec<u32>,
+        paused: Vec<Option<AutoClickPause>>,
+        events: Vec<AutoClickEvent>,
+        sleeps: Vec<Duration>,
+        refreshes: usize,
+    }
+
+    impl AutoClickDriver for FakeDriver {
+        fn is_stop_requested(&self) -> bool {
+            self.stop_at.is_some_and(|stop_at| self.now >= stop_at)
+        }
+        fn elapsed(&self) -> Duration {
+            self.now
+        }
+        fn sleep(&mut self, duration: Duration) {
+            self.sleeps.push(duration);
+            self.now += duration;
+        }
+        fn pause_reason(&self) -> Option<AutoClickPause> {
+            self.pauses
+                .iter()
+                .find(|(start, end, _)| (*start..*end).contains(&self.now))
+                .map(|(_, _, reason)| *reason)
+        }
+        fn set_paused(&mut self, reason: Option<AutoClickPause>) {
+            self.paused.push(reason);
+        }
+        fn refresh_overlay(&mut self) {
+            self.refreshes += 1;
+        }
+        fn is_on_screen(&self, point: ScreenPoint) -> bool {
+            self.screen_right.is_none_or(|right| point.0 < right)
+        }
+        fn cursor_position(&self) -> Option<ScreenPoint> {
+            self.cursor
+        }
+        fn is_target_window_at(&self, point: ScreenPoint) -> bool {
+            self.target_window_at.is_none_or(|window| window == point)
+        }
+        fn click(&mut self, point: ScreenPoint, move_cursor: bool) -> Result<(), String> {
+            if self.fail_click == Some(self.clicks.len() + 1) {
+                return Err("SendInput failed".to_string());
+            }
+            self.clicks.push((self.now, point, move_cursor));
+            Ok(())
+        }
+        fn request_capture(&mut self) -> Result<(), String> {
+            self.captures.push(self.now);
+            Ok(())
+        }
+        fn report_progress(&mut self, count: u32) {
+            self.progress.push(count);
+        }
+        fn notify(&mut self, event: AutoClickEvent) {
+            self.events.push(event);
+        }
+    }
+
+    fn click_times(driver: &FakeDriver) -> Vec<Duration> {
+        driver.clicks.iter().map(|(time, _, _)| *time).collect()
+    }
+
+    #[test]
+    fn clicks_once_per_interval_until_max_count() {
+        let mut driver = FakeDriver::default();
+        let end = run_auto_click(&plan(1000, 3), &mut driver);
+        assert_eq!(end, AutoClickEnd::Completed);
+        assert_eq!(click_times(&driver), [ms(1000), ms(2000), ms(3000)]);
+        assert!(
+            driver
+                .clicks
+                .iter()
+                .all(|(_, point, move_cursor)| { *point == (100, 200) && !move_cursor })
+        );
+        assert_eq!(driver.progress, [1, 2, 3]);
+        // 最後のクリックの後も間隔分待ってから終了を判定する
+        assert_eq!(driver.now, ms(4000));
+        assert_eq!(driver.refreshes, 4);
+    }
+
+    #[test]
+    fn waits_in_short_sleeps_including_remainder() {
+        let mut driver = FakeDriver::default();
+        run_auto_click(&plan(250, 1), &mut driver);
+        assert_eq!(driver.sleeps[..3], [ms(100), ms(100), ms(50)]);
+        assert_eq!(click_times(&driver), [ms(250)]);
+    }
+
+    #[test]
+    fn stop_request_during_wait_ends_without_clicking() {
+        let mut driver = FakeDriver {
+            stop_at: Some(ms(2550)),
+            ..Default::default()
+        };
+        let end = run_auto_click(&plan(1000, 10), &mut driver);
+        assert_eq!(end, AutoClickEnd::Stopped);
+        assert_eq!(click_times(&driver), [ms(1000), ms(2000)]);
+        // 停止要求から100ミリ秒以内に応答する
+        assert_eq!(driver.now, ms(2600));
+    }
+
+    #[test]
+    fn stop_requested_before_start_never_clicks() {
+        let mut driver = FakeDriver {
+            stop_at: Some(ms(0)),
+            ..Default::default()
+        };
+        assert_eq!(
+            run_auto_click(&plan(1000, 10), &mut driver),
+            AutoClickEnd::Stopped
+        );
+        assert!(driver.clicks.is_empty());
+        assert!(driver.sleeps.is_empty());
+    }
+
+    #[test]
+    fn max_duration_ends_during_wait() {
+        let mut driver = FakeDriver::default();
+        let plan = AutoClickPlan {
+            max_duration: Some(ms(2500)),
+            ..plan(1000, 10)
+        };
+        let end = run_auto_click(&plan, &mut driver);
+        assert_eq!(end, AutoClickEnd::DurationReached);
+        assert_eq!(click_times(&driver), [ms(1000), ms(2000)]);
+        // 次のクリックの時刻（3000ms）を待たずに終了する
+        assert_eq!(driver.now, ms(2500));
+        assert_eq!(
+            driver.events.last(),
+            Some(&AutoClickEvent::DurationReached { count: 2 })
+        );
+    }
+
+    #[test]
+    fn max_capture_count_limits_long_runs() {
+        let mut driver = FakeDriver::default();
+        let end = run_auto_click(&plan(200, 5000), &mut driver);
+        assert_eq!(end, AutoClickEnd::LimitReached);
+        assert_eq!(driver.clicks.len(), MAX_CAPTURE_COUNT as usize);
+        assert_eq!(driver.progress.last(), Some(&MAX_CAPTURE_COUNT));
+    }
+
+    #[test]
+    fn pause_delays_clicks_without_skipping_count() {
+        let mut driver = FakeDriver {
+            pauses: vec![(ms(1500), ms(4200), AutoClickPause::SessionLocked)],
+            ..Default::default()
+        };
+        let end = run_auto_click(&plan(1000, 3), &mut driver);
+        assert_eq!(end, AutoClickEnd::Completed);
+        // 2000msで一時停止を検出 → 500msごとに確認し4500msで再開 → 間隔分待って5500msにクリック
+        assert_eq!(click_times(&driver), [ms(1000), ms(5500), ms(6500)]);
+        assert_eq!(driver.progress, [1, 2, 3]);
+        assert_eq!(
+            driver.paused,
+            [
+                Some(AutoClickPause::SessionLocked),
+                Some(AutoClickPause::SessionLocked),
+                Some(AutoClickPause::SessionLocked),
+                Some(AutoClickPause::SessionLocked),
+                Some(AutoClickPause::SessionLocked),
+                None,
+            ]
+        );
+        assert!(driver.events.contains(&AutoClickEvent::Paused {
+            reason: AutoClickPause::SessionLocked,
+            count: 1,
+        }));
+        assert!(driver.events.contains(&AutoClickEvent::Resumed {
+            reason: AutoClickPause::SessionLocked,
+        }));
+    }
+
+    #[test]
+    fn stop_during_pause_does_not_report_resume() {
+        let mut driver = FakeDriver {
+            pauses: vec![(ms(0), ms(60_000), AutoClickPause::ConfirmingStop)],
+            stop_at: Some(ms(3000)),
+            ..Default::default()
+        };
+        let end = run_auto_click(&plan(1000, 10), &mut driver);
+        assert_eq!(end, AutoClickEnd::Stopped);
+        assert!(driver.clicks.is_empty());
+        assert_eq!(driver.paused.last(), Some(&None));
+        assert!(
+            !driver
+                .events
+                .iter()
+                .any(|event| matches!(event, AutoClickEvent::Resumed { .. }))
+        );
+    }
+
+    #[test]
+    fn capture_only_requests_capture_instead_of_clicking() {
+        let mut driver = FakeDriver::default();
+        let plan = AutoClickPlan {
+            mode: AutoClickMode::CaptureOnly,
+            ..plan(500, 3)
+        };
+        let end = run_auto_click(&plan, &mut driver);
+        assert_eq!(end, AutoClickEnd::Completed);
+        assert!(driver.clicks.is_empty());
+        assert_eq!(driver.captures, [ms(500), ms(1000), ms(1500)]);
+        assert_eq!(driver.progress, [1, 2, 3]);
+    }
+
+    #[test]
+    fn stepping_moves_cursor_to_each_position() {
+        let mut driver = FakeDriver::default();
+        let plan = AutoClickPlan {
+            position_step: (10, -5),
+            position_steps: 2,
+            ..plan(1000, 4)
+        };
+        run_auto_click(&plan, &mut driver);
+        let points: Vec<_> = driver
+            .clicks
+            .iter()
+            .map(|(_, point, move_cursor)| (*point, *move_cursor))
+            .collect();
+        assert_eq!(
+            points,
+            [
+                ((110, 195), true),
+                ((120, 190), true),
+                ((120, 190), true),
+                ((120, 190), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn stepping_off_screen_ends_before_clicking() {
+        let mut driver = FakeDriver {
+            screen_right: Some(125),
+            ..Default::default()
+        };
+        let plan = AutoClickPlan {
+            position_step: (10, 0),
+            ..plan(1000, 10)
+        };
+        let end = run_auto_click(&plan, &mut driver);
+        assert_eq!(end, AutoClickEnd::TargetOffScreen);
+        assert_eq!(driver.clicks.len(), 2);
+        assert_eq!(
+            driver.events.last(),
+            Some(&AutoClickEvent::TargetOffScreen { target: (130, 200) })
+        );
+    }
+
+    #[test]
+    fn target_window_is_checked_at_actual_click_point() {
+        // (クリック位置を進める量, カーソル位置, ウィンドウの位置, 期待する終了理由)
+        let cases = [
+            // 進めない場合は、カーソルがウィンドウから外れていれば終了（クリックは現在のカーソル位置に届く）
+            (
+                (0, 0),
+                Some((500, 500)),
+                (100, 200),
+                AutoClickEnd::TargetWindowLost,
+            ),
+            (
+                (0, 0),
+                Some((500, 500)),
+                (500, 500),
+                AutoClickEnd::Completed,
+            ),
+            // カーソル位置を取得できない場合はクリック位置で確認
+            ((0, 0), None, (100, 200), AutoClickEnd::Completed),
+            // 進める場合はカーソルを移動するため、進めた位置で確認
+            (
+                (10, 0),
+                Some((100, 200)),
+                (100, 200),
+                AutoClickEnd::TargetWindowLost,
+            ),
+        ];
+        for (step, cursor, window, expected) in cases {
+            let mut driver = FakeDriver {
+                cursor,
+                target_window_at: Some(window),
+                ..Default::default()
+            };
+            let plan = AutoClickPlan {
+                position_step: step,
+                ..plan(1000, 2)
+            };
+            assert_eq!(
+                run_auto_click(&plan, &mut driver),
+                expected,
+                "step {step:?} cursor {cursor:?} window {window:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn click_failure_ends_run() {
+        let mut driver = FakeDriver {
+            fail_click: Some(2),
+            ..Default::default()
+        };
+        let end = run_auto_click(&plan(1000, 5), &mut driver);
+        assert_eq!(end, AutoClickEnd::ClickFailed);
+        assert_eq!(driver.clicks.len(), 1);
+        assert_eq!(driver.progress, [1]);
+        assert_eq!(
+            driver.events.last(),
+            Some(&AutoClickEvent::ClickFailed("SendInput failed".to_string()))
+        );
+    }
+
+    #[test]
+    fn step_position_table() {
+        // (開始位置, 移動量, 回数の上限, 回数, 期待する位置)
+        let cases = [
+            ((100, 200), (0, 0), 0, 5, (100, 200)),
+            ((100, 200), (0, 30), 0, 1, (100, 230)),
+            ((100, 200), (0, 30), 0, 3, (100, 290)),
+            ((100, 200), (0, 30), 2, 3, (100, 260)),
+            ((100, 200), (-10, 0), 0, 4, (60, 200)),
+            ((0, 0), (1, 1), 0, u32::MAX, (i32::MAX, i32::MAX)),
+            ((i32::MAX - 1, 0), (5, 0), 0, 1, (i32::MAX, 0)),
+        ];
+        for (start, step, steps, iteration, expected) in cases {
+            assert_eq!(
+                step_position(start, step, steps, iteration),
+                expected,
+                "{start:?} + {step:?} x {iteration} (max {steps})"
+            );
+        }
+        assert!(!is_stepping((0, 0)));
+        assert!(is_stepping((0, -1)));
+    }
+
+    #[test]
+    fn pause_reason_round_trips_through_u8() {
+        for reason in [
+            AutoClickPause::SecureDesktop,
+            AutoClickPause::SessionLocked,
+            AutoClickPause::ConfirmingStop,
+            AutoClickPause::UserActive,
+        ] {
+            assert_eq!(AutoClickPause::from_u8(reason as u8), Some(reason));
+        }
+        assert_eq!(AutoClickPause::from_u8(0), None);
+        assert_eq!(AutoClickPause::from_u8(5), None);
+    }
+}
@@ -0,0 +1,532 @@
+/*
+============================================================================
+キャプチャバックエンドモジュール (capture_backend.rs)
+============================================================================
+
+【ファイル概要】
+画面の指定領域からピクセルデータを取得する処理（キャプチャバックエンド）を
+`CaptureBackend` トレイトとして抽象化し、GDIとDXGI Desktop Duplicationの2つの実装を提供します。
+取得した `RgbImage` は、呼び出し側で既存のマスク・縮小・エンコード処理にそのまま渡します。
+
+【主要機能】
+1.  **`CaptureBackend` トレイト**: `capture_rect(area, output_size)` で領域の画像を取得
+    -   テストや他ツールからの組み込み時に、合成画像を返すバックエンドを差し込める
+2.  **`GdiBackend`**: 従来の `BitBlt` + `GetDIBits`（`gdi-stretchblt` 有効時は `StretchBlt` で縮小済み）
+    -   `capture_rect_with` と `GdiCaptureHooks` で、`BitBlt` の前後（オーバーレイの非表示・再表示）と
+        `GetDIBits` の直前（経過時間スタンプの描画）に呼び出し側の処理を差し込める
+3.  **`DesktopDuplicationBackend`**: DXGI Output Duplication（`AcquireNextFrame`）
+    -   ハードウェアアクセラレーション・DRM保護でBitBltが真っ黒になる画面にも対応
+    -   デバイスと複製セッションを保持し、連続キャプチャ（自動クリック）時の取得を高速化
+4.  **`CaptureBackendKind`**: バックエンドの選択（GDI / Desktop Duplication / 自動）
+5.  **`is_blank_frame`**: BitBltの結果が真っ黒か判定（自動モードでの切り替え判定）
+
+【技術仕様】
+-   **Desktop Duplication**: 選択範囲を含むモニター（`DXGI_OUTPUT_DESC.DesktopCoordinates`）を複製し、
+    取得したテクスチャをCPU読み取り用のステージングテクスチャへコピー → `Map` → 範囲を切り出し（BGRA→RGB）
+-   **画面変化なし**: `DXGI_ERROR_WAIT_TIMEOUT` の場合はステージングテクスチャに残る前回フレームを使用
+-   **非対応環境**: RDPセッション・複数モニターにまたがる範囲・回転したモニターでは `Err` を返す
+    （呼び出し側でGDIにフォールバック）
+-   **セッション喪失**: `DXGI_ERROR_ACCESS_LOST`（解像度変更・UAC画面など）時は次回取得時に再作成
+
+【AI解析用：依存関係】
+-   `capturer.rs`: `Capturer::capture_area` / `capture_area_with` からバックエンドを使用
+-   `screen_capture.rs`（バイナリ側）: 自動切り替え・Desktop Duplication指定時、2つ目の領域の取得
+-   `capture_pipeline.rs`（バイナリ側）: `GdiBackend::capture_rect_with` にオーバーレイの非表示と経過時間スタンプのフックを渡す
+-   `app_state.rs`（バイナリ側）: `capture_backend` 設定と `desktop_duplication` インスタンスを保持
+ */
+
+use image::RgbImage;
+#[cfg(windows)]
+use windows::{
+    Win32::{
+        Foundation::{HMODULE, RECT},
+        Graphics::{
+            Direct3D::D3D_DRIVER_TYPE_UNKNOWN,
+            Direct3D11::{
+                D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAP_READ,
+                D3D11_MAPPED_SUBRESOURCE, D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC,
+                D3D11_USAGE_STAGING, D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext,
+                ID3D11Texture2D,
+            },
+            Dxgi::{
+                Common::{DXGI_MODE_ROTATION_IDENTITY, DXGI_MODE_ROTATION_UNSPECIFIED},
+                CreateDXGIFactory1, DXGI_ERROR_ACCESS_LOST, DXGI_ERROR_WAIT_TIMEOUT,
+                DXGI_OUTDUPL_FRAME_INFO, IDXGIFactory1, IDXGIOutput1, IDXGIOutputDuplication,
+                IDXGIResource,
+            },
+            Gdi::*,
+        },
+    },
+    core::Interface,
+};
+
+#[cfg(windows)]
+use crate::dib::{bgr_dib_to_rgb_image, bgra_rows_to_rgb_image, dib_row_size};
+
+/// Desktop Duplicationで新しいフレームを待つ最大時間（ミリ秒）
+#[cfg(windows)]
+const ACQUIRE_TIMEOUT_MS: u32 = 100;
+
+/// キャプチャバックエンドの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureBackendKind {
+    /// GDI（`BitBlt`）のみを使用
+    Gdi,
+    /// DXGI Desktop Duplicationを使用（取得できない場合はGDIにフォールバック）
+    DesktopDuplication,
+    /// GDIで取得し、結果が真っ黒な場合のみDesktop Duplicationで取得し直す（デフォルト）
+    #[default]
+    Auto,
+}
+
+/// 画面の指定領域からピクセルデータを取得するキャプチャバックエンド
+#[cfg(windows)]
+pub trait CaptureBackend {
+    /// ログ表示用のバックエンド名
+    fn name(&self) -> &'static str;
+
+    /// 画面の指定領域を取得する
+    ///
+    /// # 引数
+    /// * `area` - キャプチャするスクリーン座標の矩形
+    /// * `output_size` - 保存サイズ `(幅, 高さ)`。バックエンドが縮小に対応する場合のみ使用
+    ///
+    /// # 戻り値
+    /// 原寸、または `output_size` に縮小済みの画像。
+    /// 呼び出し側は `downscale_image` で保存サイズに揃える（縮小済みの場合はそのまま返る）。
+    fn capture_rect(
+        &mut self,
+        area: RECT,
+        output_size: (i32, i32),
+    ) -> Result<RgbImage, Box<dyn std::error::Error>>;
+}
+
+/// `GdiBackend::capture_rect_with` で、取得の段階の間に呼び出し側の処理を差し込むフック
+///
+/// 既定の実装は何もしません（フックが不要な場合は `()` を渡します）。
+#[cfg(windows)]
+pub trait GdiCaptureHooks {
+    /// `BitBlt` の直前に呼び出す（オーバーレイの非表示など）
+    fn before_blt(&mut self) {}
+
+    /// `BitBlt` の直後に呼び出す（オーバーレイの再表示、同じ瞬間の別の領域の取得など）
+    fn after_blt(&mut self) {}
+
+    /// `GetDIBits` の直前に、取得元のビットマップへ描画する（経過時間スタンプなど）
+    ///
+    /// # 引数
+    /// * `dc` - 取得元のビットマップを選択したメモリDC
+    /// * `size` - 取得元のビットマップのサイズ（`gdi-stretchblt` 有効時は縮小後）
+    fn before_read(&mut self, _dc: HDC, _size: (i32, i32)) {}
+}
+
+#[cfg(windows)]
+impl GdiCaptureHooks for () {}
+
+/// GDI（`BitBlt` + `GetDIBits`）によるキャプチャバックエンド
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GdiBackend;
+
+#[cfg(windows)]
+impl CaptureBackend for GdiBackend {
+    fn name(&self) -> &'static str {
+        "GDI"
+    }
+
+    fn capture_rect(
+        &mut self,
+        area: RECT,
+        output_size: (i32, i32),
+    ) -> Result<RgbImage, Box<dyn std::error::Error>> {
+        self.capture_rect_with(area, output_size, &mut ())
+    }
+}
+
+#[cfg(windows)]
+impl GdiBackend {
+    /// `capture_rect` と同じ処理で、各段階の間に `hooks` を呼び出しながら画面の指定領域を取得する
+    ///
+    /// # 引数
+    /// * `area` / `output_size` - `CaptureBackend::capture_rect` と同じ
+    /// * `hooks` - `BitBlt` の前後と `GetDIBits` の直前に呼び出す処理
+    pub fn capture_rect_with(
+        &self,
+        area: RECT,
+        output_size: (i32, i32),
+        hooks: &mut dyn GdiCaptureHooks,
+    ) -> Result<RgbImage, Box<dyn std::error::Error>> {
+        let width = (area.right - area.left).abs();
+        let height = (area.bottom - area.top).abs();
+        if width == 0 || height == 0 {
+            return Err(format!("無効なキャプチャ領域: {}x{}", width, height).into());
+        }
+
+        // GDI縮小時は縮小ビットマップ、Rust縮小時は原寸ビットマップからピクセルを取得する
+        let (source_width, source_height) = if cfg!(feature = "gdi-stretchblt") {
+            (output_size.0.max(1), output_size.1.max(1))
+        } else {
+            (width, height)
+        };
+        let row_size = dib_row_size(source_width as u32); // Windows 4バイト境界調整
+        let mut pixel_data = vec![0u8; row_size * source_height as usize];
+
+        let result;
+        unsafe {
+            let screen_dc = GetDC(None);
+            let memory_dc = CreateCompatibleDC(Some(screen_dc));
+
+            // 原寸ビットマップへ画面の指定領域をコピー
+            let hbitmap = CreateCompatibleBitmap(screen_dc, width, height);
+            let old_bitmap = SelectObject(memory_dc, hbitmap.into());
+            hooks.before_blt();
+            let _ = BitBlt(
+                memory_dc,
+                0,
+                0,
+                width,
+                height,
+                Some(screen_dc),
+                area.left.min(area.right),
+                area.top.min(area.bottom),
+                SRCCOPY,
+            );
+            hooks.after_blt();
+
+            // gdi-stretchblt：HALFTONEモードで縮小ビットマップへコピー
+            #[cfg(feature = "gdi-stretchblt")]
+            let (source_dc, source_bitmap, scaled_resources) = {
+                let scaled_dc = CreateCompatibleDC(Some(screen_dc));
+                let hbitmap_scaled = CreateCompatibleBitmap(screen_dc, source_width, source_height);
+                let old_bitmap_scaled = SelectObject(scaled_dc, hbitmap_scaled.into());
+                let _ = SetStretchBltMode(scaled_dc, HALFTONE);
+                let _ = SetBrushOrgEx(scaled_dc, 0, 0, None);
+                let _ = StretchBlt(
+                    scaled_dc,
+                    0,
+                    0,
+                    source_width,
+                    source_height,
+                    Some(memory_dc),
+                    0,
+                    0,
+                    width,
+                    height,
+                    SRCCOPY,
+                );
+                (
+                    scaled_dc,
+                    hbitmap_scaled,
+                    (scaled_dc, hbitmap_scaled, old_bitmap_scaled),
+                )
+            };
+            #[cfg(not(feature = "gdi-stretchblt"))]
+            let (source_dc, source_bitmap) = (memory_dc, hbitmap);
+            hooks.before_read(source_dc, (source_width, source_height));
+
+            // 24bpp・トップダウンでピクセルデータを抽出
+            let mut bitmap_info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: source_width,
+                    biHeight: -source_height,
+                    biPlanes: 1,
+                    biBitCount: 24,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            result = GetDIBits(
+                source_dc,
+                source_bitmap,
+                0,
+                source_height as u32,
+                Some(pixel_data.as_mut_ptr() as *mut _),
+                &mut bitmap_info,
+                DIB_RGB_COLORS,
+            );
+
+            // Windows GDIリソースを解放
+            #[cfg(feature = "gdi-stretchblt")]
+            {
+                let (scaled_dc, hbitmap_scaled, old_bitmap_scaled) = scaled_resources;
+                let _ = SelectObject(scaled_dc, old_bitmap_scaled);
+                let _ = DeleteObject(hbitmap_scaled.into());
+                let _ = DeleteDC(scaled_dc);
+            }
+            let _ = SelectObject(memory_dc, old_bitmap);
+            let _ = DeleteObject(hbitmap.into());
+            let _ = DeleteDC(memory_dc);
+            let _ = ReleaseDC(None, screen_dc);
+        }
+
+        if result == 0 {
+            return Err("ビットマップデータの取得に失敗".into());
+        }
+
+        Ok(bgr_dib_to_rgb_image(
+            &pixel_data,
+            source_width,
+            source_height,
+        ))
+    }
+}
+
+/// DXGI Desktop Duplicationによるキャプチャバックエンド
+///
+/// 作成時点ではGPUリソースを確保せず、初回の `capture_rect` で選択範囲を含むモニターの
+/// 複製セッションを作成します。以降は同じモニター内の範囲であればセッションを再利用します。
+#[cfg(windows)]
+#[derive(Debug, Default)]
+pub struct DesktopDuplicationBackend {
+    session: Option<DuplicationSession>,
+}
+
+/// 1つのモニターに対する複製セッション
+#[cfg(windows)]
+#[derive(Debug)]
+struct DuplicationSession {
+    device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    duplication: IDXGIOutputDuplication,
+    /// 複製しているモニターのデスクトップ座標
+    output_rect: RECT,
+    /// 最新フレームのCPU読み取り用コピー（画面に変化がない場合はこの内容を再利用）
+    staging: Option<ID3D11Texture2D>,
+}
+
+#[cfg(windows)]
+impl DesktopDuplicationBackend {
+    /// 新しいインスタンスを作成する（GPUリソースは初回取得時に確保）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 複製セッションを破棄する（次回の取得時に再作成）
+    pub fn reset(&mut self) {
+        self.session = None;
+    }
+}
+
+#[cfg(windows)]
+impl CaptureBackend for DesktopDuplicationBackend {
+    fn name(&self) -> &'static str {
+        "Desktop Duplication"
+    }
+
+    fn capture_rect(
+        &mut self,
+        area: RECT,
+        _output_size: (i32, i32),
+    ) -> Result<RgbImage, Box<dyn std::error::Error>> {
+        let area = RECT {
+            left: area.left.min(area.right),
+            top: area.top.min(area.bottom),
+            right: area.left.max(area.right),
+            bottom: area.top.max(area.bottom),
+        };
+        if area.right == area.left || area.bottom == area.top {
+            return Err("無効なキャプチャ領域".into());
+        }
+
+        // 別のモニター上の範囲が選択された場合はセッションを作り直す
+        if self
+            .session
+            .as_ref()
+            .is_some_and(|session| !rect_contains(&session.output_rect, &area))
+        {
+            self.session = None;
+        }
+        if self.session.is_none() {
+            self.session = Some(DuplicationSession::open(&area)?);
+        }
+
+        let session = self.session.as_mut().ok_or("複製セッションがありません")?;
+        let result = session.capture(&area);
+
+        // 解像度変更やデスクトップ切り替え（UAC画面など）で複製が無効になった場合は、次回作り直す
+        let is_access_lost = result
+            .as_ref()
+            .err()
+            .and_then(|e| e.downcast_ref::<windows::core::Error>())
+            .is_some_and(|e| e.code() == DXGI_ERROR_ACCESS_LOST);
+        if is_access_lost {
+            self.session = None;
+        }
+        result
+    }
+}
+
+#[cfg(windows)]
+impl DuplicationSession {
+    /// 指定範囲を含むモニターを探し、複製セッションを作成する
+    fn open(area: &RECT) -> Result<Self, Box<dyn std::error::Error>> {
+        unsafe {
+            let factory: IDXGIFactory1 = CreateDXGIFactory1()?;
+
+            let mut adapter_index = 0;
+            while let Ok(adapter) = factory.EnumAdapters1(adapter_index) {
+                let mut output_index = 0;
+                while let Ok(output) = adapter.EnumOutputs(output_index) {
+                    output_index += 1;
+
+                    let desc = output.GetDesc()?;
+                    if !rect_contains(&desc.DesktopCoordinates, area) {
+                        continue;
+                    }
+                    if desc.Rotation != DXGI_MODE_ROTATION_IDENTITY
+                        && desc.Rotation != DXGI_MODE_ROTATION_UNSPECIFIED
+                    {
+                        return Err("回転表示のモニターには対応していません".into());
+                    }
+
+                    // モニターが接続されているアダプター上にデバイスを作成
+                    let mut device = None;
+                    let mut context = None;
+                    D3D11CreateDevice(
+                        &adapter,
+                        D3D_DRIVER_TYPE_UNKNOWN,
+                        HMODULE::default(),
+                        D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                        None,
+                        D3D11_SDK_VERSION,
+                        Some(&mut device),
+                        None,
+                        Some(&mut context),
+                    )?;
+                    let device: ID3D11Device = device.ok_or("D3D11デバイスの作成に失敗")?;
+                    let context = context.ok_or("D3D11デバイスコンテキストの作成に失敗")?;
+
+                    // RDPセッションなど複製できない環境ではここでエラーになる
+                    let output1: IDXGIOutput1 = output.cast()?;
+                    let duplication = output1.DuplicateOutput(&device)?;
+
+                    return Ok(Self {
+                        device,
+                        context,
+                        duplication,
+                        output_rect: desc.DesktopCoordinates,
+                        staging: None,
+                    });
+                }
+                adapter_index += 1;
+            }
+
+            Err("選択範囲を含むモニターが見つかりません（複数モニターにまたがる範囲には対応していません）".into())
+        }
+    }
+
+    /// 最新フレームを取得し、指定範囲を切り出す
+    fn capture(&mut self, area: &RECT) -> Result<RgbImage, Box<dyn std::error::Error>> {
+        unsafe {
+            self.acquire_latest_frame()?;
+            let staging = self
+                .staging
+                .as_ref()
+                .ok_or("デスクトップのフレームを取得できませんでした")?;
+
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            staging.GetDesc(&mut desc);
+
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context
+                .Map(staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))?;
+
+            // モニター内の座標に変換してBGRAからRGBへ切り出し
+            let offset_x = (area.left - self.output_rect.left) as usize;
+            let offset_y = (area.top - self.output_rect.top) as usize;
+            let width = (area.right - area.left) as u32;
+            let height = (area.bottom - area.top) as u32;
+            let data = std::slice::from_raw_parts(
+                mapped.pData as *const u8,
+                mapped.RowPitch as usize * desc.Height as usize,
+            );
+
+            let image = bgra_rows_to_rgb_image(
+                data,
+                mapped.RowPitch as usize,
+                (offset_x, offset_y),
+                width,
+                height,
+            );
+
+            self.context.Unmap(staging, 0);
+            Ok(image)
+        }
+    }
+
+    /// 新しいフレームがあればステージングテクスチャへコピーする
+    ///
+    /// タイムアウト（画面に変化なし）の場合は、前回コピーしたフレームをそのまま使用します。
+    fn acquire_latest_frame(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        unsafe {
+            let mut frame_info = DXGI_OUTDUPL_FRAME_INFO::default();
+            let mut resource: Option<IDXGIResource> = None;
+
+            match self.duplication.AcquireNextFrame(
+                ACQUIRE_TIMEOUT_MS,
+                &mut frame_info,
+                &mut resource,
+            ) {
+                Ok(()) => {
+                    let copy_result = self.copy_to_staging(resource);
+                    let _ = self.duplication.ReleaseFrame();
+                    copy_result
+                }
+                Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT => Ok(()),
+                Err(e) => Err(e.into()),
+            }
+        }
+    }
+
+    /// 取得したデスクトップテクスチャをCPU読み取り用のステージングテクスチャへコピーする
+    fn copy_to_staging(
+        &mut self,
+        resource: Option<IDXGIResource>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        unsafe {
+            let texture: ID3D11Texture2D = resource.ok_or("フレームが空です")?.cast()?;
+
+            if self.staging.is_none() {
+                let mut desc = D3D11_TEXTURE2D_DESC::default();
+                texture.GetDesc(&mut desc);
+                desc.Usage = D3D11_USAGE_STAGING;
+                desc.BindFlags = 0;
+                desc.CPUAccessFlags = D3D11_CPU_ACCESS_READ.0 as u32;
+                desc.MiscFlags = 0;
+                desc.MipLevels = 1;
+                desc.ArraySize = 1;
+
+                let mut staging = None;
+                self.device
+                    .CreateTexture2D(&desc, None, Some(&mut staging))?;
+                self.staging = staging;
+            }
+
+            let staging = self
+                .staging
+                .as_ref()
+                .ok_or("ステージングテクスチャの作成に失敗")?;
+            self.context.CopyResource(staging, &texture);
+            Ok(())
+        }
+    }
+}
+
+/// `outer` が `inner` を完全に含むかを判定する
+#[cfg(windows)]
+fn rect_contains(outer: &RECT, inner: &RECT) -> bool {
+    inner.left >= outer.left
+        && inner.top >= outer.top
+        && inner.right <= outer.right
+        && inner.bottom <= outer.bottom
+}
+
+/// 画像が真っ黒（全ピクセルが黒）かを判定する
+///
+/// ハードウェアアクセラレーション・DRM保護された画面を `BitBlt` で取得すると
+/// 真っ黒になるため、自動モードでDesktop Duplicationへ切り替える判定に使用します。
+pub fn is_blank_frame(image: &RgbImage) -> bool {
+    image.pixels().all(|pixel| pixel.0 == [0, 0, 0])
+}
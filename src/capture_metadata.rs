@@ -0,0 +1,451 @@
+/*
+============================================================================
+撮影情報メタデータモジュール (capture_metadata.rs)
+============================================================================
+
+【ファイル概要】
+保存したJPEGに撮影日時と撮影条件（選択領域・スケール・品質）をEXIFとして埋め込み、
+後から読み出すためのライブラリモジュールです。
+外部のEXIFクレートは使用せず、エンコード済みJPEGにAPP1（Exif）セグメントを挿入します。
+
+【主要機能】
+1.  **`insert_exif_segment`**:
+    -   `DateTime`（IFD0）、`DateTimeOriginal` と `UserComment`（Exif IFD）を含むAPP1セグメントを作成
+    -   SOI直後（JFIFのAPP0がある場合はその直後）に挿入。画像データ本体は変更しません
+2.  **`read_exif_date_time_original`**:
+    -   JPEGのAPP1（Exif）から `DateTimeOriginal`（無い場合は `DateTime`）を読み出し
+    -   PDF変換時の撮影日時フッター（`pdf_builder.rs`）で使用
+3.  **`read_exif_user_comment` / `effective_dpi_from_user_comment`**:
+    -   `UserComment` の撮影条件（選択領域・出力サイズ・スケール・撮影時の画面DPI）から、画像の実効DPIを算出
+    -   PDF変換時のページサイズ（`pdf_builder.rs`、画面上で等倍表示できる大きさ）で使用
+4.  **`format_exif_date_time`**: EXIF形式の日時文字列（`YYYY:MM:DD HH:MM:SS`）を作成
+5.  **`insert_comment_segment` / `read_jpeg_comment` / `expand_comment_template`**:
+    -   ユーザーが設定した文字列（取得元のURLやメモ）をCOMセグメントとしてJPEGに埋め込み・読み出し
+    -   `{counter}`（4桁の連番）・`{timestamp}`（撮影日時）のトークンを展開
+
+【技術仕様】
+-   **TIFF構造**: ビッグエンディアン（`MM`）、IFD0 → Exif IFD（`ExifIFDPointer` 0x8769）
+-   **UserComment**: 文字コード識別子 `ASCII\0\0\0` + ASCII文字列
+    （例: `rect=100,200 800x600; output=520x390; scale=65%; quality=95%; dpi=96`）
+-   **読み出し**: リトルエンディアン（`II`）のEXIF（カメラ・他ツールで作成したJPEG）にも対応
+-   **COMセグメント**: UTF-8の文字列を最大 `MAX_COMMENT_BYTES` バイトまで格納（超える分は文字の境界で切り捨て）。
+    APP0（JFIF）・APP1（Exif）の後に挿入し、デコーダーが先頭のAPPnを期待する並びを保つ
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`（バイナリ側）: 撮影情報を記録する設定の場合、JPEGエンコード後に `insert_exif_segment` を適用
+-   `capture_pipeline.rs`（バイナリ側）: コメント（レジストリ `JpegComment`）の設定がある場合、`insert_comment_segment` を適用
+-   `pdf_builder.rs`: 撮影日時フッター用に `read_exif_date_time_original`、ページサイズ用に `effective_dpi_from_user_comment` を使用
+*/
+
+/// JPEGマーカー：画像開始（SOI）
+const MARKER_SOI: u8 = 0xD8;
+/// JPEGマーカー：JFIF（APP0）
+const MARKER_APP0: u8 = 0xE0;
+/// JPEGマーカー：Exif（APP1）
+const MARKER_APP1: u8 = 0xE1;
+/// JPEGマーカー：スキャン開始（SOS、以降は圧縮データのためセグメント走査を終了）
+const MARKER_SOS: u8 = 0xDA;
+/// JPEGマーカー：コメント（COM）
+const MARKER_COM: u8 = 0xFE;
+
+/// COMセグメントに格納するコメントの最大バイト数（ファイルサイズへの影響を抑えるため小さく制限）
+pub const MAX_COMMENT_BYTES: usize = 1024;
+
+/// APP1セグメントの識別子
+const EXIF_HEADER: &[u8; 6] = b"Exif\0\0";
+
+/// TIFFタグ：ファイル変更日時（IFD0）
+const TAG_DATE_TIME: u16 = 0x0132;
+/// TIFFタグ：Exif IFDへのポインタ（IFD0）
+const TAG_EXIF_IFD_POINTER: u16 = 0x8769;
+/// Exifタグ：撮影日時
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+/// Exifタグ：ユーザーコメント
+const TAG_USER_COMMENT: u16 = 0x9286;
+
+/// TIFFデータ型：ASCII（NUL終端）
+const TYPE_ASCII: u16 = 2;
+/// TIFFデータ型：LONG（32bit符号なし）
+const TYPE_LONG: u16 = 4;
+/// TIFFデータ型：UNDEFINED（バイト列）
+const TYPE_UNDEFINED: u16 = 7;
+
+/// UserCommentの文字コード識別子（ASCII）
+const USER_COMMENT_ASCII_PREFIX: &[u8; 8] = b"ASCII\0\0\0";
+
+/// EXIF日時文字列の長さ（`YYYY:MM:DD HH:MM:SS` + NUL）
+const EXIF_DATE_TIME_LEN: usize = 20;
+
+/// JPEGに埋め込む撮影情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureMetadata {
+    /// 撮影日時（`format_exif_date_time` で作成した `YYYY:MM:DD HH:MM:SS` 形式）
+    pub date_time: String,
+    /// 撮影条件（選択領域・スケール・品質など、ASCIIのみ）
+    pub user_comment: String,
+}
+
+/// EXIF形式の日時文字列（`YYYY:MM:DD HH:MM:SS`）を作成する
+pub fn format_exif_date_time(
+    year: u16,
+    month: u16,
+    day: u16,
+    hour: u16,
+    minute: u16,
+    second: u16,
+) -> String {
+    format!(
+        "{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+        year, month, day, hour, minute, second
+    )
+}
+
+/// エンコード済みJPEGに撮影情報のAPP1（Exif）セグメントを挿入する
+///
+/// SOIで始まらないデータ（JPEG以外）はそのまま返します。
+///
+/// # 引数
+/// * `jpeg` - エンコード済みのJPEGデータ
+/// * `metadata` - 埋め込む撮影情報
+pub fn insert_exif_segment(jpeg: &[u8], metadata: &CaptureMetadata) -> Vec<u8> {
+    if jpeg.len() < 4 || jpeg[0] != 0xFF || jpeg[1] != MARKER_SOI {
+        return jpeg.to_vec();
+    }
+
+    let tiff = build_tiff(metadata);
+    let segment_length = 2 + EXIF_HEADER.len() + tiff.len();
+    if segment_length > u16::MAX as usize {
+        // コメントが長すぎる場合はセグメントに収まらないため埋め込まない
+        return jpeg.to_vec();
+    }
+
+    // JFIF（APP0）がある場合は、その直後に挿入してAPP0を先頭に保つ
+    let mut insert_at = 2;
+    if jpeg[2] == 0xFF && jpeg[3] == MARKER_APP0 && jpeg.len() >= 6 {
+        let app0_length = u16::from_be_bytes([jpeg[4], jpeg[5]]) as usize;
+        if 4 + app0_length <= jpeg.len() {
+            insert_at = 2 + 2 + app0_length;
+        }
+    }
+
+    let mut output = Vec::with_capacity(jpeg.len() + 2 + segment_length);
+    output.extend_from_slice(&jpeg[..insert_at]);
+    output.extend_from_slice(&[0xFF, MARKER_APP1]);
+    output.extend_from_slice(&(segment_length as u16).to_be_bytes());
+    output.extend_from_slice(EXIF_HEADER);
+    output.extend_from_slice(&tiff);
+    output.extend_from_slice(&jpeg[insert_at..]);
+    output
+}
+
+/// コメントのテンプレートのトークンを展開する
+///
+/// * `{counter}` - 保存する連番（4桁ゼロパディング、`0001` など）
+/// * `{timestamp}` - 撮影日時（呼び出し元が作成した文字列）
+///
+/// # 引数
+/// * `template` - ユーザーが設定したコメント（取得元のURLやメモなど）
+/// * `counter` - 保存する連番
+/// * `timestamp` - 撮影日時の文字列
+pub fn expand_comment_template(template: &str, counter: u32, timestamp: &str) -> String {
+    template
+        .replace("{counter}", &format!("{:04}", counter))
+        .replace("{timestamp}", timestamp)
+}
+
+/// エンコード済みJPEGにコメント（COM）セグメントを挿入する
+///
+/// 先頭のAPPnセグメント（JFIF・Exif）の直後に挿入します。画像データ本体は変更しません。
+/// `MAX_COMMENT_BYTES` を超えるコメントは文字の境界で切り捨てます。
+/// SOIで始まらないデータ（JPEG以外）や空のコメントの場合はそのまま返します。
+///
+/// # 引数
+/// * `jpeg` - エンコード済みのJPEGデータ
+/// * `comment` - 埋め込む文字列（UTF-8）
+pub fn insert_comment_segment(jpeg: &[u8], comment: &str) -> Vec<u8> {
+    if jpeg.len() < 4 || jpeg[0] != 0xFF || jpeg[1] != MARKER_SOI || comment.is_empty() {
+        return jpeg.to_vec();
+    }
+
+    let mut end = comment.len().min(MAX_COMMENT_BYTES);
+    while !comment.is_char_boundary(end) {
+        end -= 1;
+    }
+    let comment = &comment.as_bytes()[..end];
+
+    // 先頭のAPPn（0xE0〜0xEF）を読み飛ばし、その直後に挿入する
+    let mut insert_at = 2;
+    while insert_at + 4 <= jpeg.len()
+        && jpeg[insert_at] == 0xFF
+        && (MARKER_APP0..=0xEF).contains(&jpeg[insert_at + 1])
+    {
+        let length = u16::from_be_bytes([jpeg[insert_at + 2], jpeg[insert_at + 3]]) as usize;
+        if length < 2 || insert_at + 2 + length > jpeg.len() {
+            break;
+        }
+        insert_at += 2 + length;
+    }
+
+    let segment_length = 2 + comment.len();
+    let mut output = Vec::with_capacity(jpeg.len() + 2 + segment_length);
+    output.extend_from_slice(&jpeg[..insert_at]);
+    output.extend_from_slice(&[0xFF, MARKER_COM]);
+    output.extend_from_slice(&(segment_length as u16).to_be_bytes());
+    output.extend_from_slice(comment);
+    output.extend_from_slice(&jpeg[insert_at..]);
+    output
+}
+
+/// JPEGの最初のコメント（COM）セグメントを読み出す
+///
+/// # 戻り値
+/// コメントの文字列（UTF-8として解釈できないバイトは置き換え文字）。COMセグメントが無い場合は `None`
+pub fn read_jpeg_comment(jpeg: &[u8]) -> Option<String> {
+    find_segment(jpeg, |marker, _| marker == MARKER_COM)
+        .map(|segment| String::from_utf8_lossy(segment).into_owned())
+}
+
+/// JPEGのEXIFから撮影日時（`DateTimeOriginal`、無い場合は `DateTime`）を読み出す
+///
+/// # 戻り値
+/// `YYYY:MM:DD HH:MM:SS` 形式の文字列。EXIFが無い場合や解析できない場合は `None`
+pub fn read_exif_date_time_original(jpeg: &[u8]) -> Option<String> {
+    let tiff = find_exif_tiff(jpeg)?;
+    let reader = TiffReader::new(tiff)?;
+    let ifd0 = reader.u32_at(4)? as usize;
+
+    let exif_ifd = reader
+        .find_entry(ifd0, TAG_EXIF_IFD_POINTER)
+        .and_then(|entry| reader.u32_at(entry + 8));
+    exif_ifd
+        .and_then(|offset| reader.find_entry(offset as usize, TAG_DATE_TIME_ORIGINAL))
+        .or_else(|| reader.find_entry(ifd0, TAG_DATE_TIME))
+        .and_then(|entry| reader.ascii_value(entry))
+}
+
+/// JPEGのEXIFから撮影条件（`UserComment`）を読み出す
+///
+/// # 戻り値
+/// 文字コード識別子を除いた文字列。EXIFやUserCommentが無い場合は `None`
+pub fn read_exif_user_comment(jpeg: &[u8]) -> Option<String> {
+    let tiff = find_exif_tiff(jpeg)?;
+    let reader = TiffReader::new(tiff)?;
+    let ifd0 = reader.u32_at(4)? as usize;
+    let exif_ifd = reader
+        .find_entry(ifd0, TAG_EXIF_IFD_POINTER)
+        .and_then(|entry| reader.u32_at(entry + 8))?;
+    let entry = reader.find_entry(exif_ifd as usize, TAG_USER_COMMENT)?;
+    let bytes = reader.value_bytes(entry)?;
+    let text = bytes.strip_prefix(USER_COMMENT_ASCII_PREFIX.as_slice())?;
+    let text = String::from_utf8_lossy(text)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// 撮影条件（`UserComment`）から、保存した画像の実効DPI（画面上の1インチに相当する画像のピクセル数）を求める
+///
+/// 撮影時の画面DPI（`dpi=`、記録の無い画像は `screen_dpi`）に縮小率を掛けた値です。
+/// 縮小率は選択領域が1つの場合は出力幅と選択領域の幅（`output=` / `rect=`）の比、
+/// 2領域の合成画像の場合は `scale=` の値から求めます（例: 96DPIの画面を65%に縮小 → 62.4DPI）。
+///
+/// # 戻り値
+/// 縮小率を判定できない場合（幅指定の2領域合成など）は `None`
+pub fn effective_dpi_from_user_comment(comment: &str, screen_dpi: f64) -> Option<f64> {
+    let field = |key: &str| {
+        comment
+            .split(';')
+            .filter_map(|item| item.trim().split_once('='))
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value.trim())
+    };
+    // `800x600` 形式のサイズから幅を取得（`rect=` は `x,y WxH` 形式のため末尾を使用）
+    let width_of = |value: &str| -> Option<f64> {
+        let size = value.rsplit(' ').next()?;
+        let width: f64 = size.split_once('x')?.0.parse().ok()?;
+        (width > 0.0).then_some(width)
+    };
+
+    let capture_dpi = field("dpi")
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|&dpi| dpi > 0.0)
+        .unwrap_or(screen_dpi);
+    let ratio = if field("rect2").is_none() {
+        width_of(field("output")?)? / width_of(field("rect")?)?
+    } else {
+        let percent: f64 = field("scale")?.strip_suffix('%')?.parse().ok()?;
+        percent / 100.0
+    };
+    (ratio > 0.0).then_some(capture_dpi * ratio)
+}
+
+/// EXIFのTIFF部分（ビッグエンディアン）を作成する
+///
+/// 構成: TIFFヘッダー(8) → IFD0（DateTime, ExifIFDPointer） → Exif IFD（DateTimeOriginal, UserComment） → 値領域
+fn build_tiff(metadata: &CaptureMetadata) -> Vec<u8> {
+    const IFD_ENTRY_COUNT: usize = 2;
+    const IFD_SIZE: usize = 2 + IFD_ENTRY_COUNT * 12 + 4;
+    const IFD0_OFFSET: usize = 8;
+    const EXIF_IFD_OFFSET: usize = IFD0_OFFSET + IFD_SIZE;
+    const DATE_TIME_OFFSET: usize = EXIF_IFD_OFFSET + IFD_SIZE;
+    const USER_COMMENT_OFFSET: usize = DATE_TIME_OFFSET + EXIF_DATE_TIME_LEN;
+
+    let mut date_time = metadata.date_time.clone().into_bytes();
+    date_time.resize(EXIF_DATE_TIME_LEN - 1, b' ');
+    date_time.push(0);
+
+    let mut user_comment = USER_COMMENT_ASCII_PREFIX.to_vec();
+    user_comment.extend(metadata.user_comment.bytes().filter(u8::is_ascii));
+
+    let mut tiff = Vec::with_capacity(USER_COMMENT_OFFSET + user_comment.len());
+    tiff.extend_from_slice(b"MM");
+    tiff.extend_from_slice(&42u16.to_be_bytes());
+    tiff.extend_from_slice(&(IFD0_OFFSET as u32).to_be_bytes());
+
+    // IFD0（タグ番号順）：DateTime と両IFDで共有する日時文字列
+    tiff.extend_from_slice(&(IFD_ENTRY_COUNT as u16).to_be_bytes());
+    push_ifd_entry(
+        &mut tiff,
+        TAG_DATE_TIME,
+        TYPE_ASCII,
+        EXIF_DATE_TIME_LEN as u32,
+        DATE_TIME_OFFSET as u32,
+    );
+    push_ifd_entry(
+        &mut tiff,
+        TAG_EXIF_IFD_POINTER,
+        TYPE_LONG,
+        1,
+        EXIF_IFD_OFFSET as u32,
+    );
+    tiff.extend_from_slice(&0u32.to_be_bytes());
+
+    // Exif IFD
+    tiff.extend_from_slice(&(IFD_ENTRY_COUNT as u16).to_be_bytes());
+    push_ifd_entry(
+        &mut tiff,
+        TAG_DATE_TIME_ORIGINAL,
+        TYPE_ASCII,
+        EXIF_DATE_TIME_LEN as u32,
+        DATE_TIME_OFFSET as u32,
+    );
+    push_ifd_entry(
+        &mut tiff,
+        TAG_USER_COMMENT,
+        TYPE_UNDEFINED,
+        user_comment.len() as u32,
+        USER_COMMENT_OFFSET as u32,
+    );
+    tiff.extend_from_slice(&0u32.to_be_bytes());
+
+    // 値領域（4バイトを超える値はここに格納し、エントリからオフセットで参照）
+    tiff.extend_from_slice(&date_time);
+    tiff.extend_from_slice(&user_comment);
+    tiff
+}
+
+/// IFDエントリ（タグ・型・個数・値オフセットの12バイト）を追加する
+fn push_ifd_entry(tiff: &mut Vec<u8>, tag: u16, value_type: u16, count: u32, value: u32) {
+    tiff.extend_from_slice(&tag.to_be_bytes());
+    tiff.extend_from_slice(&value_type.to_be_bytes());
+    tiff.extend_from_slice(&count.to_be_bytes());
+    tiff.extend_from_slice(&value.to_be_bytes());
+}
+
+/// JPEGのセグメントを走査し、APP1（Exif）のTIFF部分を返す
+fn find_exif_tiff(jpeg: &[u8]) -> Option<&[u8]> {
+    find_segment(jpeg, |marker, segment| {
+        marker == MARKER_APP1 && segment.starts_with(EXIF_HEADER)
+    })
+    .map(|segment| &segment[EXIF_HEADER.len()..])
+}
+
+/// JPEGのセグメント（SOSより前）を走査し、条件に一致した最初のセグメントの内容（長さの後ろ）を返す
+fn find_segment(jpeg: &[u8], matches: impl Fn(u8, &[u8]) -> bool) -> Option<&[u8]> {
+    if jpeg.len() < 4 || jpeg[0] != 0xFF || jpeg[1] != MARKER_SOI {
+        return None;
+    }
+
+    let mut position = 2;
+    while position + 4 <= jpeg.len() && jpeg[position] == 0xFF {
+        let marker = jpeg[position + 1];
+        if marker == MARKER_SOS {
+            break;
+        }
+        let length = u16::from_be_bytes([jpeg[position + 2], jpeg[position + 3]]) as usize;
+        let segment = jpeg.get(position + 4..position + 2 + length)?;
+        if matches(marker, segment) {
+            return Some(segment);
+        }
+        position += 2 + length;
+    }
+    None
+}
+
+/// TIFF構造の読み出し（バイトオーダーに対応）
+struct TiffReader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    /// TIFFヘッダーからバイトオーダーを判定する
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(0..2)? {
+            b"MM" => true,
+            b"II" => false,
+            _ => return None,
+        };
+        Some(Self { data, big_endian })
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    /// IFD内で指定タグのエントリを探し、エントリの先頭オフセットを返す
+    fn find_entry(&self, ifd_offset: usize, tag: u16) -> Option<usize> {
+        let count = self.u16_at(ifd_offset)? as usize;
+        (0..count)
+            .map(|index| ifd_offset + 2 + index * 12)
+            .find(|&entry| self.u16_at(entry) == Some(tag))
+    }
+
+    /// ASCII型エントリの値（NUL終端まで）を文字列として返す
+    fn ascii_value(&self, entry: usize) -> Option<String> {
+        if self.u16_at(entry + 2)? != TYPE_ASCII {
+            return None;
+        }
+        let bytes = self.value_bytes(entry)?;
+        let text = bytes.split(|&b| b == 0).next().unwrap_or_default();
+        let text = String::from_utf8_lossy(text).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// 1バイト単位の型（ASCII・UNDEFINED）のエントリの値をバイト列として返す
+    fn value_bytes(&self, entry: usize) -> Option<&'a [u8]> {
+        let count = self.u32_at(entry + 4)? as usize;
+        // 4バイト以下の値はエントリ内に直接格納される
+        let start = if count <= 4 {
+            entry + 8
+        } else {
+            self.u32_at(entry + 8)? as usize
+        };
+        self.data.get(start..start + count)
+    }
+}
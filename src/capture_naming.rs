@@ -0,0 +1,307 @@
+/*
+============================================================================
+連番ファイル名モジュール (capture_naming.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャ画像の連番ファイル名（`0001.jpg` など）の作成・解析と、連番カウンタの計算を
+まとめたライブラリモジュールです。ファイルの作成・名前の変更などの入出力は行わず、
+Win32 APIにも依存しないため、実際のデスクトップがなくても動作を確認できます。
+
+【主要機能】
+1.  **`capture_file_name` / `capture_file_number`**: 連番とファイル名の相互変換（4桁未満はゼロ埋め）
+2.  **`first_free_capture_counter`**: 既存ファイルの最大の番号から、次に保存する連番の候補を求める
+3.  **`capture_counter_milestone`**: 保存で連番が上限の警告・5桁への切り替えを越えたかを判定
+4.  **`plan_capture_file_renumbering`**: 連番ファイルを `0001` から欠番なく振り直す場合の名前の変更内容を求める
+5.  **`expand_save_folder_template`**: 保存先フォルダーのテンプレート（`D:\caps\{yyyy}\{mm}\{dd}`）の日付を展開し、
+    Windowsで使用できるパスかを検証する
+
+【技術仕様】
+-   **連番の形式**: `{:04}` の数字のみのファイル名。`9999` の次は `10000`（5桁）で継続
+-   **判定**: ファイル名（拡張子を除く）が数字のみの場合のみ連番として扱う（別名で保存したファイルは対象外）
+
+【AI解析用：依存関係】
+-   `system_utils.rs`（バイナリ側）: `create_next_capture_file` / `max_capture_file_number` での連番の計算
+-   `screen_capture.rs`（バイナリ側）: 保存後の `advance_capture_file_counter` でのログ判定
+-   `ui/renumber_button_handler.rs`（バイナリ側）: 「連番整理」ボタンでの振り直し内容の計算
+-   `screen_capture.rs`（バイナリ側）: `get_save_dir_path` での保存先フォルダーのテンプレートの展開
+*/
+
+use std::path::{Path, PathBuf};
+
+/// 連番が残り少なくなったことを警告する番号（以降は4桁の残り枚数をログに出力）
+pub const CAPTURE_COUNTER_WARNING_THRESHOLD: u32 = 9900;
+
+/// 4桁で表せる最大の連番（これを超えると `10000.jpg` のように5桁で継続する）
+pub const CAPTURE_COUNTER_FOUR_DIGIT_MAX: u32 = 9999;
+
+/// 保存で連番が越えた節目（ログ出力用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureCounterMilestone {
+    /// `CAPTURE_COUNTER_WARNING_THRESHOLD` に達した（`remaining` は4桁で保存できる残り枚数）
+    NearLimit { remaining: u32 },
+    /// 4桁を超えて5桁のファイル名になった
+    Widened,
+}
+
+/// 連番と拡張子からファイル名を作成する（`1, "jpg"` → `0001.jpg`）
+pub fn capture_file_name(counter: u32, extension: &str) -> String {
+    format!("{:04}.{}", counter, extension)
+}
+
+/// ファイル名（拡張子を除く）が数字のみの場合に、その連番を返す（`0001.jpg` → `1`）
+pub fn capture_file_number(path: &Path) -> Option<u32> {
+    let stem = path.file_stem()?.to_str()?;
+    if stem.is_empty() || !stem.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    stem.parse::<u32>().ok()
+}
+
+/// 次に保存する連番の候補を求める
+///
+/// 保存先フォルダーの既存ファイルを上書きしないよう、`start_counter` と
+/// 既存の最大の番号の次のうち大きい方を返します。
+///
+/// # 引数
+/// * `start_counter` - このインスタンスが次に使用する予定の連番（`capture_file_counter`）。
+/// * `max_existing` - 保存先フォルダーの連番ファイルの最大の番号（ない場合は `None`）。
+pub fn first_free_capture_counter(start_counter: u32, max_existing: Option<u32>) -> u32 {
+    start_counter.max(max_existing.unwrap_or(0).saturating_add(1))
+}
+
+/// `previous_counter` の次から `saved_counter` で保存するまでに越えた節目を返す
+///
+/// 他インスタンスの保存で番号が飛んだ場合も、範囲内に節目があれば検出します。
+/// 5桁への切り替えと残り枚数の警告の両方を越えた場合は、5桁への切り替えを優先します。
+pub fn capture_counter_milestone(
+    previous_counter: u32,
+    saved_counter: u32,
+) -> Option<CaptureCounterMilestone> {
+    let crossed = |boundary: u32| (previous_counter..=saved_counter).contains(&boundary);
+
+    if crossed(CAPTURE_COUNTER_FOUR_DIGIT_MAX + 1) {
+        Some(CaptureCounterMilestone::Widened)
+    } else if crossed(CAPTURE_COUNTER_WARNING_THRESHOLD) {
+        Some(CaptureCounterMilestone::NearLimit {
+            remaining: CAPTURE_COUNTER_FOUR_DIGIT_MAX.saturating_sub(saved_counter),
+        })
+    } else {
+        None
+    }
+}
+
+/**
+ * 連番ファイルを `0001` から欠番なく振り直す場合の名前の変更内容を求める
+ *
+ * # 引数
+ * * `files` - 連番順に並べたファイル（`collect_numbered_capture_files` で収集）
+ *
+ * # 戻り値
+ * 名前が変わるファイルの（変更前, 変更後）のパス。既に正しい番号のファイルは含みません。
+ */
+pub fn plan_capture_file_renumbering(files: &[PathBuf]) -> Vec<(PathBuf, PathBuf)> {
+    files
+        .iter()
+        .zip(1u32..)
+        .filter_map(|(path, counter)| {
+            let extension = path.extension()?.to_string_lossy();
+            let renamed = path.with_file_name(capture_file_name(counter, &extension));
+            (renamed != *path).then(|| (path.clone(), renamed))
+        })
+        .collect()
+}
+
+/// Windowsのファイル名に使用できない文字（`\` `/` はパスの区切りとして別に扱う）
+const INVALID_PATH_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// Windowsでファイル・フォルダー名に使用できない予約名（拡張子付きも不可）
+const RESERVED_PATH_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/**
+ * 保存先フォルダーのテンプレートの日付トークンを展開する
+ *
+ * * `{yyyy}` - 年（4桁）
+ * * `{mm}` - 月（2桁）
+ * * `{dd}` - 日（2桁）
+ *
+ * 展開後のパスがWindowsで使用できるかも確認します。ドライブ（`D:\`）またはUNC（`\\server\share`）から
+ * 始まる絶対パスで、各フォルダー名に使用できない文字・予約名・末尾の空白やピリオドがない場合のみ有効です。
+ *
+ * # 引数
+ * * `template` - 保存先フォルダーのテンプレート（例: `D:\caps\{yyyy}\{mm}\{dd}`）
+ * * `year` / `month` / `day` - 展開する日付（呼び出し元のローカル時刻）
+ *
+ * # 戻り値
+ * 展開したパス。未知のトークンが残る場合や、Windowsで使用できないパスの場合は `None`
+ */
+pub fn expand_save_folder_template(
+    template: &str,
+    year: u16,
+    month: u16,
+    day: u16,
+) -> Option<String> {
+    let expanded = template
+        .trim()
+        .replace("{yyyy}", &format!("{:04}", year))
+        .replace("{mm}", &format!("{:02}", month))
+        .replace("{dd}", &format!("{:02}", day))
+        .replace('/', "\\");
+
+    // ドライブ（`D:\`）またはUNC（`\\server\share\`）の部分を除いたフォルダー名を検証する
+    let folders = if let Some(unc) = expanded.strip_prefix("\\\\") {
+        let mut parts = unc.splitn(3, '\\');
+        let (server, share) = (parts.next()?, parts.next()?);
+        if !is_valid_path_component(server) || !is_valid_path_component(share) {
+            return None;
+        }
+        parts.next().unwrap_or("")
+    } else {
+        let mut chars = expanded.chars();
+        let drive = chars.next()?;
+        if !drive.is_ascii_alphabetic() || chars.next()? != ':' || chars.next()? != '\\' {
+            return None;
+        }
+        &expanded[3..]
+    };
+
+    folders
+        .split('\\')
+        .filter(|folder| !folder.is_empty())
+        .all(is_valid_path_component)
+        .then_some(expanded)
+}
+
+/// フォルダー名がWindowsで使用できるかを判定する（未展開のトークンの `{` `}` も不可とする）
+fn is_valid_path_component(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or("");
+    !name.is_empty()
+        && !name.ends_with([' ', '.'])
+        && !name
+            .chars()
+            .any(|c| c.is_control() || INVALID_PATH_CHARS.contains(&c) || c == '{' || c == '}')
+        && !RESERVED_PATH_NAMES
+            .iter()
+            .any(|reserved| stem.trim_end().eq_ignore_ascii_case(reserved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf_builder::sort_capture_files;
+
+    #[test]
+    fn capture_file_name_pads_to_four_digits_then_widens() {
+        for (counter, expected) in [
+            (1, "0001.jpg"),
+            (9899, "9899.jpg"),
+            (9900, "9900.jpg"),
+            (9999, "9999.jpg"),
+            (10000, "10000.jpg"),
+            (10001, "10001.jpg"),
+        ] {
+            assert_eq!(capture_file_name(counter, "jpg"), expected);
+            assert_eq!(capture_file_number(Path::new(expected)), Some(counter));
+        }
+    }
+
+    #[test]
+    fn capture_file_number_ignores_non_numeric_names() {
+        for name in [
+            "",
+            "memo.jpg",
+            "9999a.jpg",
+            "-1.jpg",
+            "99999999999.jpg",
+            ".jpg",
+        ] {
+            assert_eq!(capture_file_number(Path::new(name)), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn capture_counter_milestone_at_counter_boundaries() {
+        // (保存前に予定していた連番, 保存した連番, 期待する節目)。通常は両者が等しい
+        let cases = [
+            (9899, 9899, None),
+            (
+                9900,
+                9900,
+                Some(CaptureCounterMilestone::NearLimit { remaining: 99 }),
+            ),
+            (9901, 9901, None),
+            (9999, 9999, None),
+            (10000, 10000, Some(CaptureCounterMilestone::Widened)),
+            (10001, 10001, None),
+            // 既存ファイル・他インスタンスの保存で番号が飛んだ場合も、範囲内の節目を検出する
+            (
+                9899,
+                9900,
+                Some(CaptureCounterMilestone::NearLimit { remaining: 99 }),
+            ),
+            (
+                9850,
+                9950,
+                Some(CaptureCounterMilestone::NearLimit { remaining: 49 }),
+            ),
+            (9999, 10000, Some(CaptureCounterMilestone::Widened)),
+            (9800, 10005, Some(CaptureCounterMilestone::Widened)),
+            (10001, 10100, None),
+        ];
+        for (previous, saved, expected) in cases {
+            assert_eq!(
+                capture_counter_milestone(previous, saved),
+                expected,
+                "{previous} -> {saved}"
+            );
+        }
+    }
+
+    #[test]
+    fn first_free_capture_counter_skips_existing_files_past_9999() {
+        assert_eq!(first_free_capture_counter(9999, None), 9999);
+        assert_eq!(first_free_capture_counter(9999, Some(9999)), 10000);
+        assert_eq!(first_free_capture_counter(1, Some(10000)), 10001);
+        assert_eq!(first_free_capture_counter(10002, Some(10000)), 10002);
+        assert_eq!(first_free_capture_counter(1, Some(u32::MAX)), u32::MAX);
+    }
+
+    #[test]
+    fn five_digit_files_sort_after_9999_for_export() {
+        let mut files: Vec<PathBuf> =
+            ["10000.jpg", "9999.jpg", "memo.jpg", "10001.jpg", "0001.jpg"]
+                .iter()
+                .map(PathBuf::from)
+                .collect();
+        sort_capture_files(&mut files);
+        let names: Vec<_> = files.iter().map(|p| p.to_str().unwrap()).collect();
+        assert_eq!(
+            names,
+            ["0001.jpg", "9999.jpg", "10000.jpg", "10001.jpg", "memo.jpg"]
+        );
+    }
+
+    #[test]
+    fn plan_capture_file_renumbering_narrows_five_digit_names() {
+        let files: Vec<PathBuf> = ["9999.jpg", "10000.jpg", "10001.png"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let plan = plan_capture_file_renumbering(&files);
+        let plan: Vec<_> = plan
+            .iter()
+            .map(|(from, to)| (from.to_str().unwrap(), to.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            plan,
+            [
+                ("9999.jpg", "0001.jpg"),
+                ("10000.jpg", "0002.jpg"),
+                ("10001.png", "0003.png")
+            ]
+        );
+    }
+}
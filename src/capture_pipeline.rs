@@ -0,0 +1,804 @@
+/*
+============================================================================
+キャプチャパイプラインモジュール (capture_pipeline.rs)
+============================================================================
+
+【ファイル概要】
+`capture_screen_area_with_counter` の1回分の処理（画面取得 → マスク → 縮小・結合 → エンコード → 保存）を、
+段階ごとのメソッドに分けた `CapturePipeline` として提供するモジュールです。
+各段階は `CaptureError` を返し、呼び出し元（`screen_capture.rs`）は段階を順に呼び出すだけにします。
+
+【主要機能】
+1.  **設定の取得 (`CapturePipeline::from_app_state`)**:
+    -   選択領域・保存サイズ（スケール・幅指定・最大サイズ）・マスク・保存形式などを1回分の設定として取得
+    -   クイックキャプチャは `CapturePipeline::for_area` で、選択領域の代わりにウィンドウの領域を1回だけ使う
+2.  **画面取得 (`grab`)**: `GdiBackend`（`BitBlt` + `GetDIBits`）で原寸の画像を取得し `RawFrame` を返す
+    -   バックエンド設定に応じてDesktop Duplication（DXGI）で取得し直す
+    -   2つ目の領域も同時に取得（サムネイルは保存後に、保存した画像から作成する）
+    -   経過時間スタンプ（`burn_in_elapsed_stamp`）が有効な場合、`GetDIBits` の前に撮影時刻と前回からの経過時間を
+        GDIで描画し、画像のピクセルの一部にする（`draw_elapsed_stamp`）
+3.  **後処理 (`postprocess`)**: マスク領域を黒で塗りつぶす（縮小前の原寸座標で適用）
+    -   変化の検出（`detect_change`）: 前回保存した画像から変化がなければ保存を省略し、
+        `CropToChange` の場合は縮小後に変化した範囲のみ切り出す（`crop_to_change`）
+4.  **縮小・結合 (`scale`)**: 保存サイズへ縮小し、2つ目の領域を同じ縮小率で縮小して1枚に結合
+    （本体はライブラリの `capturer::scale_capture`。合成画像の出力ハッシュをテストで固定）
+5.  **エンコード (`encode`)**: 保存形式・品質でエンコードし、撮影情報のEXIF・コメント（COMセグメント）を埋め込む
+6.  **保存 (`persist`)**: 空き容量を確認し、連番ファイルを排他的に作成して書き込む
+    -   起動引数 `--sink=` で出力先を指定した場合は、ファイルの代わりに名前付きパイプ・標準出力へ送信（`CaptureDestination::Sink`）
+7.  **暖機運転 (`warm_up`)**: キャプチャモード開始時に取得〜エンコードまでを実行して結果を破棄し、初回の遅延を解消
+
+【技術仕様】
+-   **機能の追加箇所**:
+    -   画面取得の方式（新しいバックエンドなど）は `grab`、画像への加工（マスク・透かしなど）は `postprocess`
+    -   保存サイズ・レイアウトは `scale`、保存形式・メタデータは `encode`、保存先・命名・クリップボードへのコピーは `persist`
+-   **GDIリソース**: 取得・解放は `GdiBackend::capture_rect_with` が行い、`grab` はフック（`GrabHooks`）で
+    オーバーレイの非表示・2つ目の領域の取得・経過時間スタンプの描画だけを差し込む
+-   **サムネイル**: 保存に成功した後、`scale` 以降の画像（保存した内容そのもの）から作成してストリップに追加
+    （`screen_capture.rs` の `run_capture_pipeline`、ファイルを読み直さない）
+-   **オーバーレイ**: 画面取得の瞬間だけ非表示にし、`DwmFlush` と待ち時間（`OverlayHideSettleMs`）で写り込みを防止
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`: `capture_screen_area_with_counter` で各段階を順に呼び出す
+-   `clickcapture::capturer`: 縮小・マスク・結合・エンコード
+-   `clickcapture::capture_backend`: GDI / Desktop Duplicationによる画面取得
+-   `system_utils.rs`: 連番ファイルの作成（`write_capture_file_with_retry`）
+-   `capture_timing.rs`: 段階ごとの所要時間の計測（`CaptureTimer::lap`）
+-   `clickcapture::frame_diff`: 前回保存した画像との比較・変化した範囲の切り出し
+-   `clickcapture::capture_sink`: 名前付きパイプ・標準出力への長さ付きフレームの送信
+*/
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use clickcapture::CaptureFormat;
+use clickcapture::capture_backend::{
+    CaptureBackend, CaptureBackendKind, GdiBackend, GdiCaptureHooks, is_blank_frame,
+};
+use clickcapture::capture_metadata::{
+    CaptureMetadata, expand_comment_template, format_exif_date_time, insert_comment_segment,
+    insert_exif_segment,
+};
+use clickcapture::capture_sink::{CaptureSink, CaptureSinkTarget};
+use clickcapture::capturer::{
+    DualAreaLayout, apply_mask_areas, clamp_to_max_size, compute_scaled_size, encode_capture,
+    scale_capture,
+};
+use clickcapture::frame_diff::{
+    CHANGE_TOLERANCE, ChangeDetection, ChangedRegion, changed_region, crop_to_region,
+};
+use image::RgbImage;
+use windows::{
+    Win32::{
+        Foundation::{COLORREF, RECT, SIZE, SYSTEMTIME},
+        Graphics::{Dwm::DwmFlush, Gdi::*},
+        System::SystemInformation::GetLocalTime,
+    },
+    core::{PCWSTR, w},
+};
+
+use crate::{
+    app_state::*,
+    capture_timing::{CapturePhase, CaptureTimer},
+    error::CaptureError,
+    overlay::Overlay,
+    screen_capture::{FailedCapture, ensure_disk_space, get_save_dir_path},
+    strings,
+    system_utils::{app_log, get_system_dpi, write_capture_file_with_retry},
+};
+
+/// 暖機運転を行う選択領域の最大画素数（4K相当。超える場合はオーバーレイの表示が遅れるため省略）
+pub const WARM_UP_MAX_PIXELS: i64 = 3840 * 2160;
+
+/// 経過時間スタンプの文字の高さ（保存する画像でのピクセル数）
+const ELAPSED_STAMP_FONT_HEIGHT: i32 = 16;
+
+/// 1回のキャプチャの設定（開始時に `AppState` から取得）
+pub struct CapturePipeline {
+    /// 1つ目の領域（スクリーン座標）
+    area: RECT,
+    /// 1つ目の領域の原寸サイズ
+    full_size: (i32, i32),
+    /// 保存サイズ（スケール・幅指定・最大サイズを適用済み）
+    output_size: (i32, i32),
+    /// 2つ目の領域（Shift+ドラッグで選択した場合）
+    secondary_area: Option<RECT>,
+    /// マスク領域（黒で塗りつぶす）
+    mask_areas: Vec<RECT>,
+    dual_area_layout: DualAreaLayout,
+    format: CaptureFormat,
+    quality: u8,
+    high_fidelity_text: bool,
+    progressive: bool,
+    /// 撮影情報のEXIFを埋め込むか（JPEGのみ）
+    record_metadata: bool,
+    /// COMセグメントに埋め込むコメント（トークン展開済み、JPEGのみ）
+    comment: Option<String>,
+    /// 前回保存した画像からの変化の検出
+    change_detection: ChangeDetection,
+    /// 画像の左上に焼き込む撮影時刻と前回からの経過時間（無効時は `None`）
+    elapsed_stamp: Option<String>,
+    /// このキャプチャの取得開始時刻（次回の経過時間の基準）
+    started_at: Instant,
+}
+
+/// `CapturePipeline::detect_change` の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameChange {
+    /// 画像全体を保存する（変化の検出が無効、または最初の1枚）
+    Save,
+    /// 前回保存した画像から変化がないため保存しない
+    Unchanged,
+    /// 変化した範囲（原寸画像の座標）のみ保存する
+    Crop(ChangedRegion),
+}
+
+/// 画面から取得した縮小前の画像
+pub struct RawFrame {
+    /// 1つ目の領域の原寸画像
+    pub image: RgbImage,
+    /// 2つ目の領域の原寸画像（未選択・取得失敗時は `None`）
+    pub secondary: Option<RgbImage>,
+}
+
+impl CapturePipeline {
+    /// 現在の設定から1回分のキャプチャ設定を作成する
+    ///
+    /// 保存サイズが最大幅・最大高さで制限された場合はログに出力します。
+    pub fn from_app_state(app_state: &AppState) -> Result<Self, CaptureError> {
+        let area = app_state
+            .selected_area
+            .ok_or(CaptureError::AreaNotSelected)?;
+        Ok(Self::with_areas(app_state, area, app_state.selected_area_2))
+    }
+
+    /// 選択領域の代わりに指定した領域で1回分のキャプチャ設定を作成する（クイックキャプチャ用）
+    ///
+    /// `AppState` の選択領域（`selected_area` / `selected_area_2`）は参照も変更もしないため、
+    /// ドラッグで選択した領域はそのまま次のキャプチャ・再キャプチャで使えます。
+    pub fn for_area(app_state: &AppState, area: RECT) -> Self {
+        Self::with_areas(app_state, area, None)
+    }
+
+    /// 1つ目・2つ目の領域と現在の設定から1回分のキャプチャ設定を作成する
+    fn with_areas(app_state: &AppState, area: RECT, secondary_area: Option<RECT>) -> Self {
+        let width = (area.right - area.left).abs();
+        let height = (area.bottom - area.top).abs();
+        let started_at = Instant::now();
+
+        // ユーザー設定のスケール値（または幅指定）に基づいて、リサイズ後のサイズを計算
+        // 縦横比を維持し、元画像より大きくはしない（高さ0にもならない）
+        let scaled_size = compute_scaled_size(
+            width,
+            height,
+            app_state.capture_scale_factor,
+            app_state
+                .capture_fit_to_width
+                .then_some(app_state.capture_target_width),
+        );
+
+        // 保存サイズ上限（最大幅・最大高さ）を超える場合は縦横比を保って更に縮小
+        let output_size = clamp_to_max_size(
+            scaled_size.0,
+            scaled_size.1,
+            Some(app_state.capture_max_width),
+            Some(app_state.capture_max_height),
+        );
+        if output_size != scaled_size {
+            app_log(&strings::size_cap_applied(
+                scaled_size,
+                output_size,
+                (app_state.capture_max_width, app_state.capture_max_height),
+            ));
+        }
+
+        Self {
+            area,
+            full_size: (width, height),
+            output_size,
+            secondary_area,
+            mask_areas: app_state.mask_areas.clone(),
+            dual_area_layout: app_state.dual_area_layout,
+            format: app_state.capture_format,
+            quality: app_state.jpeg_quality,
+            high_fidelity_text: app_state.jpeg_high_fidelity_text,
+            progressive: app_state.jpeg_progressive,
+            record_metadata: app_state.record_capture_metadata,
+            comment: app_state.jpeg_comment.as_deref().map(|template| {
+                expand_comment_template(
+                    template,
+                    app_state.capture_file_counter,
+                    &comment_timestamp(),
+                )
+            }),
+            change_detection: app_state.change_detection,
+            elapsed_stamp: app_state.burn_in_elapsed_stamp.then(|| {
+                let elapsed = app_state
+                    .last_capture_started_at
+                    .map(|previous| started_at.duration_since(previous));
+                format_elapsed_stamp(&unsafe { GetLocalTime() }, elapsed)
+            }),
+            started_at,
+        }
+    }
+
+    /// 1つ目の領域（スクリーン座標、保存完了トーストの表示位置にも使用）
+    pub fn area(&self) -> RECT {
+        self.area
+    }
+
+    /// 保存形式（ファイルの拡張子）
+    pub fn format(&self) -> CaptureFormat {
+        self.format
+    }
+
+    /// 1つ目の領域の原寸サイズ
+    pub fn full_size(&self) -> (i32, i32) {
+        self.full_size
+    }
+
+    /// このキャプチャの取得開始時刻（保存に成功した場合、次回の経過時間スタンプの基準になる）
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /**
+     * 保存しない1回分のキャプチャを実行する（暖機運転）
+     *
+     * `grab` → `postprocess` → `scale` → `encode` を実行して結果を破棄します。
+     * 保存・連番・サムネイル・変化の検出に使う前回の画像は更新せず、所要時間の集計にも含めません。
+     * 選択領域が大きいと時間がかかるため、呼び出し側で `WARM_UP_MAX_PIXELS` と比較して省略してください。
+     */
+    pub fn warm_up(&self) -> Result<(), CaptureError> {
+        let mut frame = self.grab(&mut CaptureTimer::start())?;
+        self.postprocess(&mut frame);
+        let image = self.scale(frame.image, frame.secondary);
+        self.encode(&image).map(|_| ())
+    }
+
+    /**
+     * 画面の選択領域を原寸で取得する
+     *
+     * # 処理内容
+     * 1. `GdiBackend::capture_rect_with` で取得し、`GrabHooks` で次の処理を差し込む
+     *    -   `BitBlt` の前後: オーバーレイを取得の瞬間だけ非表示にし、2つ目の領域も同じ瞬間に取得
+     *    -   `GetDIBits` の直前: 経過時間スタンプをビットマップの左上へ描画
+     *
+     *    `gdi-stretchblt` フィーチャー有効時は `StretchBlt` で縮小したビットマップから取得する
+     * 2. バックエンド設定に応じて（自動選択時は結果が真っ黒な場合のみ）Desktop Duplicationで取得し直す
+     *
+     * `timer` には画面取得（`Grab`）とピクセル変換（`Convert`）の時間を記録します。
+     */
+    pub fn grab(&self, timer: &mut CaptureTimer) -> Result<RawFrame, CaptureError> {
+        let mut hooks = GrabHooks {
+            pipeline: self,
+            timer,
+            is_overlay_visible: is_capturing_overlay_visible(),
+            secondary_gdi_image: None,
+            show_result: Ok(()),
+        };
+        let gdi_image = GdiBackend
+            .capture_rect_with(self.area, self.output_size, &mut hooks)
+            .ok();
+        let GrabHooks {
+            timer,
+            secondary_gdi_image,
+            show_result,
+            ..
+        } = hooks;
+
+        // 設定に応じてDesktop Duplicationで取得し直す
+        // （自動選択時は、GDIの結果が真っ黒な場合＝DRM保護コンテンツやGPU描画ウィンドウのみ）
+        let duplicated_image = if should_use_desktop_duplication(gdi_image.as_ref()) {
+            capture_with_desktop_duplication(self.area, self.output_size)
+        } else {
+            None
+        };
+        let captured_image = duplicated_image.or(gdi_image);
+
+        // 2つ目の領域も1つ目と同じ方針でバックエンドを選択
+        let secondary = secondary_gdi_image.and_then(|(area_2, full_size, gdi_image)| {
+            let duplicated_image = if should_use_desktop_duplication(gdi_image.as_ref()) {
+                capture_with_desktop_duplication(area_2, full_size)
+            } else {
+                None
+            };
+            let image = duplicated_image.or(gdi_image);
+            if image.is_none() {
+                app_log(strings::second_area_failed());
+            }
+            image
+        });
+        timer.lap(CapturePhase::Convert);
+
+        show_result.map_err(CaptureError::Overlay)?;
+        let image = captured_image.ok_or(CaptureError::GdiFailure("GetDIBits"))?;
+        Ok(RawFrame { image, secondary })
+    }
+
+    /// 原寸の画像にマスク領域を適用する（縮小前に行うため、マスク領域の座標は原寸のまま）
+    pub fn postprocess(&self, frame: &mut RawFrame) {
+        apply_mask_areas(&mut frame.image, self.area, &self.mask_areas);
+        if let (Some(secondary), Some(area_2)) = (frame.secondary.as_mut(), self.secondary_area) {
+            apply_mask_areas(secondary, area_2, &self.mask_areas);
+        }
+    }
+
+    /// 変化の検出が有効か（保存に成功した画像を次回の比較用に保持する）
+    pub fn detects_changes(&self) -> bool {
+        self.change_detection.is_enabled()
+    }
+
+    /**
+     * 前回保存した画像と比較し、保存するか・変化した範囲のみ保存するかを判定する
+     *
+     * マスク適用後の原寸画像で比較するため、マスク領域内の変化は無視されます。
+     * 2つ目の領域がある場合は1つ目の領域のみで判定し、切り出しは行いません（結合後の配置が崩れるため）。
+     *
+     * # 引数
+     * * `image` - 今回取得した1つ目の領域の画像（`postprocess` 適用後）
+     * * `previous` - 前回保存した画像（`None` の場合は必ず保存）
+     */
+    pub fn detect_change(&self, image: &RgbImage, previous: Option<&RgbImage>) -> FrameChange {
+        let Some(previous) = previous.filter(|_| self.change_detection.is_enabled()) else {
+            return FrameChange::Save;
+        };
+        match changed_region(previous, image, CHANGE_TOLERANCE) {
+            None => FrameChange::Unchanged,
+            Some(region)
+                if self.change_detection == ChangeDetection::CropToChange
+                    && self.secondary_area.is_none()
+                    && !region.covers(image.width(), image.height()) =>
+            {
+                FrameChange::Crop(region)
+            }
+            Some(_) => FrameChange::Save,
+        }
+    }
+
+    /// 縮小後の画像から、原寸画像で求めた変化した範囲を切り出す
+    ///
+    /// # 引数
+    /// * `image` - `scale` で縮小した画像
+    /// * `raw_size` - 変化した範囲を求めた原寸画像のサイズ
+    /// * `region` - 変化した範囲（原寸画像の座標）
+    pub fn crop_to_change(
+        &self,
+        image: RgbImage,
+        raw_size: (u32, u32),
+        region: ChangedRegion,
+    ) -> RgbImage {
+        let region = region.scale_to(raw_size, image.dimensions());
+        crop_to_region(&image, region)
+    }
+
+    /// 保存サイズへ縮小し、2つ目の領域がある場合は同じ縮小率で縮小して1枚に結合する
+    pub fn scale(&self, image: RgbImage, secondary: Option<RgbImage>) -> RgbImage {
+        scale_capture(
+            image,
+            secondary,
+            self.output_size,
+            self.full_size.0,
+            self.dual_area_layout,
+        )
+    }
+
+    /// 保存形式に応じてエンコードする
+    ///
+    /// JPEGの高精細テキストモード時は4:4:4・プログレッシブ、プログレッシブJPEG有効時は4:2:0・プログレッシブ。
+    /// 撮影情報の記録が有効な場合は、JPEGに撮影日時・撮影条件のEXIFを埋め込みます
+    /// （無効時・JPEG以外はエンコード結果をそのまま返し、従来と同じバイト列になる）。
+    /// コメント（`JpegComment`）が設定されている場合は、JPEGにCOMセグメントとして埋め込みます。
+    pub fn encode(&self, image: &RgbImage) -> Result<Vec<u8>, CaptureError> {
+        let mut encoded = Vec::new();
+        encode_capture(
+            &mut encoded,
+            image,
+            self.format,
+            self.quality,
+            self.high_fidelity_text,
+            self.progressive,
+        )
+        .map_err(CaptureError::Encode)?;
+
+        if self.record_metadata && self.format == CaptureFormat::Jpeg {
+            let metadata = build_capture_metadata(
+                self.area,
+                self.secondary_area,
+                image.width(),
+                image.height(),
+            );
+            encoded = insert_exif_segment(&encoded, &metadata);
+        }
+        if let Some(comment) = (self.format == CaptureFormat::Jpeg)
+            .then_some(self.comment.as_deref())
+            .flatten()
+        {
+            encoded = insert_comment_segment(&encoded, comment);
+        }
+        Ok(encoded)
+    }
+
+    /**
+     * エンコード済みの画像を保存する（出力先の指定がある場合はファイルの代わりに送信する）
+     *
+     * 起動引数 `--sink=` で出力先を指定した場合は、ディスクに書き込まずに `send_to_sink` で送信します。
+     *
+     * 空き容量が不足している場合は書き込まず（書きかけのファイルを残さない）、
+     * 連番ファイル（4桁ゼロパディング）を排他的に作成して書き込みます（一時的なロックは再試行）。
+     * 書き込めなかった画像は、空きを作った後などに「再保存」ボタンで書き込めるよう `last_failed_capture` に保持します。
+     *
+     * # 戻り値
+     * * `Ok(CaptureDestination)` - 保存した連番とファイルパス、または送信した出力先。
+     */
+    pub fn persist(&self, encoded: Vec<u8>) -> Result<CaptureDestination, CaptureError> {
+        if let Some(target) = AppState::get_app_state_ref().capture_sink_target.clone() {
+            return self.send_to_sink(&target, &encoded);
+        }
+
+        // 保存先フォルダが存在しない場合は作成（親ディレクトリも含めて再帰作成）
+        let save_dir_path = get_save_dir_path();
+        let save_dir = std::path::Path::new(&save_dir_path);
+        if !save_dir.exists() {
+            fs::create_dir_all(save_dir)?;
+        }
+
+        let app_state = AppState::get_app_state_mut();
+        ensure_disk_space(save_dir, encoded.len() as u64)
+            .and_then(|()| {
+                // 別インスタンスが同じフォルダーに保存していても、既存ファイルを上書きしない
+                write_capture_file_with_retry(
+                    save_dir,
+                    self.format.extension(),
+                    app_state.capture_file_counter,
+                    &encoded,
+                )
+                .map_err(CaptureError::Io)
+            })
+            .map(|(counter, path)| CaptureDestination::File { counter, path })
+            .inspect_err(|_| {
+                app_state.last_failed_capture = Some(FailedCapture {
+                    encoded,
+                    extension: self.format.extension(),
+                });
+                app_log(strings::failed_capture_kept());
+            })
+    }
+
+    /// エンコード済みの画像を出力先へ長さ付きフレームとして送信する
+    ///
+    /// 名前付きパイプは未接続なら接続し、送信に失敗した場合は接続を破棄して次回のキャプチャで再接続します。
+    fn send_to_sink(
+        &self,
+        target: &CaptureSinkTarget,
+        encoded: &[u8],
+    ) -> Result<CaptureDestination, CaptureError> {
+        let app_state = AppState::get_app_state_mut();
+        let sent = match app_state.capture_sink.take() {
+            Some(sink) => Ok(sink),
+            None => CaptureSink::connect(target),
+        }
+        .and_then(|mut sink| sink.send_frame(encoded).map(|()| sink));
+
+        // 送信できた場合のみ接続を保持する（失敗した接続は破棄し、次回のキャプチャで再接続）
+        let sink = sent.inspect_err(|e| {
+            app_log(&strings::capture_sink_unavailable(
+                &target.label(),
+                &e.to_string(),
+            ));
+        })?;
+        app_state.capture_sink = Some(sink);
+        Ok(CaptureDestination::Sink {
+            label: target.label(),
+            bytes: encoded.len(),
+        })
+    }
+}
+
+/// `CapturePipeline::persist` の保存先
+pub enum CaptureDestination {
+    /// 連番ファイルとして保存した（保存した連番とファイルパス）
+    File { counter: u32, path: PathBuf },
+    /// 出力先（名前付きパイプ・標準出力）へ送信した（出力先のラベルと送信したバイト数）
+    Sink { label: String, bytes: usize },
+}
+
+/// `grab` で `GdiBackend` の取得の段階の間に行う処理
+struct GrabHooks<'a> {
+    pipeline: &'a CapturePipeline,
+    timer: &'a mut CaptureTimer,
+    /// 取得の瞬間だけ非表示にするオーバーレイが表示中か
+    is_overlay_visible: bool,
+    /// オーバーレイ非表示のまま取得した2つ目の領域（領域、原寸サイズ、GDIの結果）
+    secondary_gdi_image: Option<(RECT, (i32, i32), Option<RgbImage>)>,
+    /// オーバーレイの再表示の結果（GDIリソースを解放してからエラーを返す）
+    show_result: windows::core::Result<()>,
+}
+
+impl GdiCaptureHooks for GrabHooks<'_> {
+    fn before_blt(&mut self) {
+        // オーバーレイ表示中（キャプチャモード中、定期キャプチャの経過時間表示中）は、
+        // キャプチャの瞬間だけオーバーレイを非表示にし、BitBltを実行後、再表示する
+        // （通常は表示アフィニティでウィンドウ全体が除外済み。これは WDA_EXCLUDEFROMCAPTURE 非対応環境向けの回避策）
+        if self.is_overlay_visible {
+            hide_capturing_overlay_for_grab();
+        }
+    }
+
+    fn after_blt(&mut self) {
+        // デュアル選択時は、オーバーレイ非表示のまま2つ目の領域も原寸で取得
+        self.secondary_gdi_image = self.pipeline.secondary_area.map(|area_2| {
+            let full_size = (
+                (area_2.right - area_2.left).abs().max(1),
+                (area_2.bottom - area_2.top).abs().max(1),
+            );
+            (
+                area_2,
+                full_size,
+                GdiBackend.capture_rect(area_2, full_size).ok(),
+            )
+        });
+
+        self.show_result = match AppState::get_app_state_mut().capturing_overlay.as_mut() {
+            Some(overlay) if self.is_overlay_visible => overlay.show_overlay(),
+            _ => Ok(()),
+        };
+        self.timer.lap(CapturePhase::Grab);
+    }
+
+    fn before_read(&mut self, dc: HDC, (source_width, _): (i32, i32)) {
+        // 経過時間スタンプを描画（縮小後も同じ大きさになるよう、縮小率に合わせて文字を大きくする）
+        if let Some(stamp) = self.pipeline.elapsed_stamp.as_deref() {
+            let font_height = ELAPSED_STAMP_FONT_HEIGHT * source_width
+                / self.pipeline.output_size.0.clamp(1, source_width.max(1));
+            draw_elapsed_stamp(dc, stamp, font_height);
+        }
+    }
+}
+
+/// 撮影時刻と前回のキャプチャからの経過時間を、ビットマップの左上に黒地に白文字で描画する
+///
+/// # 引数
+/// * `dc` - 描画先のビットマップを選択したメモリDC
+/// * `text` - 描画する文字列（`format_elapsed_stamp` で作成）
+/// * `font_height` - 文字の高さ（ピクセル）
+fn draw_elapsed_stamp(dc: HDC, text: &str, font_height: i32) {
+    let text_wide: Vec<u16> = text.encode_utf16().collect();
+    let padding = (font_height / 4).max(2);
+
+    unsafe {
+        let font = CreateFontW(
+            -font_height,
+            0,
+            0,
+            0,
+            FW_BOLD.0 as i32,
+            0,
+            0,
+            0,
+            DEFAULT_CHARSET,
+            OUT_DEFAULT_PRECIS,
+            CLIP_DEFAULT_PRECIS,
+            ANTIALIASED_QUALITY,
+            (FIXED_PITCH.0 | FF_MODERN.0) as u32,
+            w!("Consolas"),
+        );
+        let old_font = SelectObject(dc, font.into());
+
+        // 文字の大きさに余白を加えた範囲を黒で塗りつぶし、その上に白で描画する（どの画面でも読める）
+        let mut text_size = SIZE::default();
+        let _ = GetTextExtentPoint32W(dc, &text_wide, &mut text_size);
+        let background = RECT {
+            left: 0,
+            top: 0,
+            right: text_size.cx + padding * 2,
+            bottom: text_size.cy + padding * 2,
+        };
+        let _ = SetBkColor(dc, COLORREF(0x000000));
+        let _ = SetTextColor(dc, COLORREF(0xFFFFFF));
+        let _ = ExtTextOutW(
+            dc,
+            padding,
+            padding,
+            ETO_OPAQUE,
+            Some(&background),
+            PCWSTR(text_wide.as_ptr()),
+            text_wide.len() as u32,
+            None,
+        );
+
+        let _ = SelectObject(dc, old_font);
+        let _ = DeleteObject(font.into());
+    }
+}
+
+/// 経過時間スタンプの文字列を作成する
+///
+/// 例: `14:03:27.512 +12.4s`、`14:05:02.130 +1:34.6`、`15:10:00.000 +1:04:58`。
+/// 前回のキャプチャがない場合（モード・定期キャプチャの最初の1枚）は `14:03:27.512 first` とします。
+fn format_elapsed_stamp(now: &SYSTEMTIME, elapsed: Option<Duration>) -> String {
+    let time = format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        now.wHour, now.wMinute, now.wSecond, now.wMilliseconds
+    );
+    let Some(elapsed) = elapsed else {
+        return format!("{} first", time);
+    };
+
+    let tenths = elapsed.as_millis() / 100;
+    let (minutes, seconds) = (tenths / 600, tenths % 600);
+    let delta = if minutes == 0 {
+        format!("+{}.{}s", seconds / 10, seconds % 10)
+    } else if minutes < 60 {
+        format!("+{}:{:02}.{}", minutes, seconds / 10, seconds % 10)
+    } else {
+        format!("+{}:{:02}:{:02}", minutes / 60, minutes % 60, seconds / 10)
+    };
+    format!("{} {}", time, delta)
+}
+
+/// コメントの `{timestamp}` に使う撮影日時（ローカル時刻、`YYYY-MM-DD HH:MM:SS` 形式）
+fn comment_timestamp() -> String {
+    let now = unsafe { GetLocalTime() };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        now.wYear, now.wMonth, now.wDay, now.wHour, now.wMinute, now.wSecond
+    )
+}
+
+/// EXIFに埋め込む撮影情報（撮影日時、選択領域・出力サイズ・スケール・品質）を作成する
+///
+/// UserCommentはASCIIのみのため、例えば `rect=100,200 800x600; output=520x390; scale=65%; quality=95%; dpi=96` の形式にします。
+/// `dpi` は撮影時のシステムDPIで、PDF変換時に画面上で等倍表示できるページサイズの計算に使用します。
+fn build_capture_metadata(
+    area_rect: RECT,
+    secondary_rect: Option<RECT>,
+    output_width: u32,
+    output_height: u32,
+) -> CaptureMetadata {
+    let app_state = AppState::get_app_state_ref();
+    let now = unsafe { GetLocalTime() };
+
+    let scale_label = if app_state.capture_fit_to_width {
+        format!("width {}px", app_state.capture_target_width)
+    } else {
+        format!("{}%", app_state.capture_scale_factor)
+    };
+    let mut user_comment = format!(
+        "rect={},{} {}x{}",
+        area_rect.left,
+        area_rect.top,
+        area_rect.right - area_rect.left,
+        area_rect.bottom - area_rect.top
+    );
+    if let Some(area_2) = secondary_rect {
+        user_comment.push_str(&format!(
+            "; rect2={},{} {}x{}",
+            area_2.left,
+            area_2.top,
+            area_2.right - area_2.left,
+            area_2.bottom - area_2.top
+        ));
+    }
+    user_comment.push_str(&format!(
+        "; output={}x{}; scale={}; quality={}%; dpi={}",
+        output_width,
+        output_height,
+        scale_label,
+        app_state.jpeg_quality,
+        get_system_dpi()
+    ));
+
+    CaptureMetadata {
+        date_time: format_exif_date_time(
+            now.wYear,
+            now.wMonth,
+            now.wDay,
+            now.wHour,
+            now.wMinute,
+            now.wSecond,
+        ),
+        user_comment,
+    }
+}
+
+/**
+ * キャプチャオーバーレイが画面に表示されているかを判定する
+ *
+ * キャプチャモード中と、定期キャプチャの経過時間表示中（`capture_overlay_shows_elapsed`）に表示されます。
+ * 表示中はキャプチャ結果に写り込まないよう、取得の瞬間だけ非表示にする必要があります。
+ */
+fn is_capturing_overlay_visible() -> bool {
+    let app_state = AppState::get_app_state_ref();
+    app_state.is_capture_mode() || app_state.capture_overlay_shows_elapsed
+}
+
+/**
+ * 画面を取得する直前にキャプチャオーバーレイを非表示にし、非表示が画面に反映されるまで待つ
+ *
+ * `ShowWindow(SW_HIDE)` は即座に戻りますが、DWMが非表示を合成し終える前に `BitBlt` すると、
+ * 低速な環境ではアイコンが写り込みます。`DwmFlush` で次の合成を待ち、さらに
+ * `overlay_hide_settle_ms`（レジストリ `OverlayHideSettleMs`、既定16ms）だけ待機します。
+ */
+fn hide_capturing_overlay_for_grab() {
+    let app_state = AppState::get_app_state_ref();
+    let Some(overlay) = app_state.capturing_overlay.as_ref() else {
+        return;
+    };
+    overlay.hide_overlay();
+
+    unsafe {
+        // デスクトップコンポジションの完了を待つ（失敗時は待ち時間のみで対応）
+        let _ = DwmFlush();
+    }
+    if app_state.overlay_hide_settle_ms > 0 {
+        std::thread::sleep(std::time::Duration::from_millis(
+            app_state.overlay_hide_settle_ms as u64,
+        ));
+    }
+}
+
+/**
+ * 設定されたキャプチャバックエンドに応じて、Desktop Duplicationを使用するか判定する
+ *
+ * # 引数
+ * * `gdi_image` - GDI（`BitBlt`）で取得した画像（取得失敗時は `None`）
+ *
+ * # 戻り値
+ * * `Gdi` - 常に `false`
+ * * `DesktopDuplication` - 常に `true`
+ * * `Auto` - GDIの取得に失敗したか、結果が真っ黒（`is_blank_frame`）な場合のみ `true`
+ */
+fn should_use_desktop_duplication(gdi_image: Option<&RgbImage>) -> bool {
+    match AppState::get_app_state_ref().capture_backend {
+        CaptureBackendKind::Gdi => false,
+        CaptureBackendKind::DesktopDuplication => true,
+        CaptureBackendKind::Auto => gdi_image.is_none_or(is_blank_frame),
+    }
+}
+
+/**
+ * Desktop Duplication（DXGI）で画面の指定領域をキャプチャする
+ *
+ * Desktop Duplicationはレイヤードウィンドウも合成後の画面として取得するため、
+ * オーバーレイ表示中は取得の瞬間だけ `capturing_overlay` を非表示にします。
+ *
+ * # 引数
+ * * `area` - キャプチャ領域（スクリーン座標）
+ * * `output_size` - 保存サイズ（Desktop Duplicationでは原寸で取得し、縮小は呼び出し元で行う）
+ *
+ * # 戻り値
+ * 取得に失敗した場合はログを出力して `None`（呼び出し元でGDIの結果を使用）。
+ */
+fn capture_with_desktop_duplication(area: RECT, output_size: (i32, i32)) -> Option<RgbImage> {
+    let app_state = AppState::get_app_state_mut();
+    let is_overlay_visible = is_capturing_overlay_visible();
+    if is_overlay_visible {
+        hide_capturing_overlay_for_grab();
+    }
+
+    let result = app_state
+        .desktop_duplication
+        .capture_rect(area, output_size);
+
+    if let Some(overlay) = app_state
+        .capturing_overlay
+        .as_mut()
+        .filter(|_| is_overlay_visible)
+    {
+        let _ = overlay.show_overlay();
+    }
+
+    match result {
+        Ok(image) => {
+            app_log(strings::desktop_duplication_used());
+            Some(image)
+        }
+        Err(e) => {
+            app_log(&strings::desktop_duplication_fallback(&e));
+            None
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/*
+============================================================================
+キャプチャ出力先モジュール (capture_sink.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャした画像をファイルに保存せず、名前付きパイプまたは標準出力へ送るための
+ライブラリモジュールです。ClickCaptureを大きな自動化処理のキャプチャ部分として使い、
+ディスクに書き込まずに別プロセスへ画像を渡す用途を想定しています。
+
+【主要機能】
+1.  **`CaptureSinkTarget`**: 出力先の指定（`stdout` / `pipe:名前`）の解析と表示用ラベル
+2.  **`CaptureSink`**: 出力先への接続と、1枚ずつの画像の送信（`send_frame`）
+3.  **`write_frame`**: 長さ付きフレーム（4バイトの長さ + 画像のバイト列）の書き出し
+
+【技術仕様】
+-   **フレーム形式**: `u32`（リトルエンディアン）の画像のバイト数に続けて、エンコード済みの画像（`CaptureConfig::format` の形式）。
+    区切り文字を使わないため、受信側は長さを読んでからその分だけ読み取る
+-   **名前付きパイプ**: クライアントとして接続（`\\.\pipe\名前`。パイプのサーバーは受信側のプロセスが作成しておく）
+-   **標準出力**: `CaptureSink::take_stdout` で標準出力のハンドルを引き取り、以降の `println!` は標準エラー出力へ
+    切り替える（ログがフレームに混ざらないようにする）。GUIサブシステムのリリースビルドでは、
+    親プロセスが標準出力をリダイレクトして起動した場合のみ使用可能
+
+【AI解析用：依存関係】
+-   `capturer.rs`: `Capturer::capture_area_to_sink` で取得した画像をそのまま送信
+-   `main.rs`（バイナリ側）: 起動引数 `--sink=` の解析と標準出力の引き取り
+-   `capture_pipeline.rs`（バイナリ側）: `persist` でファイルの代わりに出力先へ送信
+*/
+
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+};
+#[cfg(windows)]
+use std::{
+    fs::File,
+    os::windows::io::{FromRawHandle, RawHandle},
+};
+
+#[cfg(windows)]
+use windows::Win32::System::Console::{
+    GetStdHandle, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE, SetStdHandle,
+};
+
+/// 名前付きパイプのパスの接頭辞
+pub const PIPE_PATH_PREFIX: &str = r"\\.\pipe\";
+
+/// キャプチャ画像の出力先の指定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureSinkTarget {
+    /// 標準出力
+    Stdout,
+    /// 名前付きパイプ（`\\.\pipe\` を除いた名前）
+    NamedPipe(String),
+}
+
+impl CaptureSinkTarget {
+    /// 出力先の指定（`stdout` / `pipe:名前`）を解析する（形式が正しくない場合は `None`）
+    ///
+    /// パイプ名は `\\.\pipe\名前` の形式でも指定できます。
+    pub fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("stdout") {
+            return Some(Self::Stdout);
+        }
+        let name = value.strip_prefix("pipe:")?;
+        let name = name.strip_prefix(PIPE_PATH_PREFIX).unwrap_or(name);
+        (!name.is_empty()).then(|| Self::NamedPipe(name.to_string()))
+    }
+
+    /// ログ・トースト表示用のラベル（`stdout` / `pipe:名前`）
+    pub fn label(&self) -> String {
+        match self {
+            Self::Stdout => "stdout".to_string(),
+            Self::NamedPipe(name) => format!("pipe:{}", name),
+        }
+    }
+}
+
+/// キャプチャ画像を長さ付きフレームとして送る出力先
+pub struct CaptureSink {
+    writer: Box<dyn Write + Send>,
+}
+
+impl fmt::Debug for CaptureSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureSink").finish_non_exhaustive()
+    }
+}
+
+impl CaptureSink {
+    /// 任意の書き込み先を出力先として使用する
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+
+    /// 名前付きパイプにクライアントとして接続する
+    ///
+    /// パイプのサーバーが作成されていない場合や、他のクライアントが接続中の場合は失敗します。
+    pub fn connect_named_pipe(name: &str) -> io::Result<Self> {
+        let pipe = OpenOptions::new()
+            .write(true)
+            .open(format!("{}{}", PIPE_PATH_PREFIX, name))?;
+        Ok(Self::new(pipe))
+    }
+
+    /// 標準出力のハンドルを引き取り、出力先として使用する
+    ///
+    /// 以降の `println!` などの標準出力への書き込みは標準エラー出力へ切り替わります。
+    /// 何か出力する前（`main` の先頭）に1回だけ呼び出してください。
+    #[cfg(windows)]
+    pub fn take_stdout() -> io::Result<Self> {
+        let stdout = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) }.map_err(io::Error::from)?;
+        if stdout.is_invalid() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "standard output is not available",
+            ));
+        }
+        unsafe {
+            let stderr = GetStdHandle(STD_ERROR_HANDLE).map_err(io::Error::from)?;
+            SetStdHandle(STD_OUTPUT_HANDLE, stderr).map_err(io::Error::from)?;
+        }
+        // 標準出力のハンドルは以降このFileのみが使用する（破棄時に閉じ、受信側にEOFを通知）
+        let file = unsafe { File::from_raw_handle(stdout.0 as RawHandle) };
+        Ok(Self::new(file))
+    }
+
+    /// 出力先の指定に従って接続する（標準出力は `take_stdout` で引き取ったものを使用するため対象外）
+    pub fn connect(target: &CaptureSinkTarget) -> io::Result<Self> {
+        match target {
+            CaptureSinkTarget::NamedPipe(name) => Self::connect_named_pipe(name),
+            CaptureSinkTarget::Stdout => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "standard output must be taken at startup",
+            )),
+        }
+    }
+
+    /// 1枚の画像を長さ付きフレームとして送信する
+    pub fn send_frame(&mut self, bytes: &[u8]) -> io::Result<()> {
+        write_frame(&mut self.writer, bytes)
+    }
+}
+
+/// 長さ付きフレーム（`u32` リトルエンディアンの長さ + バイト列）を書き出す
+///
+/// 4GiB以上のデータは長さを表せないため `InvalidInput` を返します。
+pub fn write_frame<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(bytes.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "frame is too large for a u32 length prefix",
+        )
+    })?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(bytes)?;
+    writer.flush()
+}
@@ -0,0 +1,149 @@
+/*
+============================================================================
+キャプチャ所要時間計測モジュール (capture_timing.rs)
+============================================================================
+
+【ファイル概要】
+高速な自動クリック中に1回のキャプチャが実際に何ミリ秒かかっているかを確認するため、
+`capture_screen_area_with_counter` の処理を段階ごとに計測し、ログに出力するモジュールです。
+自動クリックの間隔の調整や、保存先ディスクが遅い場合の原因調査に使用します。
+
+【主要機能】
+1.  **段階ごとの計測 (`CaptureTimer`)**:
+    -   `lap` を呼ぶたびに、前回の `lap` からの経過時間をその段階の時間として記録
+    -   段階：画面取得（BitBlt）/ ピクセル変換（GetDIBits・DXGI・StretchBlt）/ 縮小・結合 / エンコード / 書き込み
+2.  **1回ごとのログ (`finish_capture_timing`)**:
+    -   保存成功時に段階ごとの内訳をログに出力し、セッションの集計に加える
+3.  **セッションの集計 (`dump_capture_timing_stats`)**:
+    -   キャプチャモード・定期キャプチャの終了時に、合計時間の最小・平均・最大と段階ごとの平均を出力してリセット
+
+【技術仕様】
+-   **有効化**: レジストリ `HKCU\Software\ClickCapture\TimingDebug`（REG_DWORD）が1の場合のみ計測結果を出力
+    （画面には設定項目を設けない開発・調査用の設定。`AppState.timing_debug` に起動時に読み込む）
+-   **計測**: `std::time::Instant`（単調増加クロック）。無効時も `Instant::now()` の呼び出しのみで、ログ出力・集計はしない
+-   **集計対象**: 保存まで成功したキャプチャのみ（途中で失敗した回は含めない）
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`: `capture_screen_area_with_counter` で段階ごとに `lap` を呼び出す
+-   `app_state.rs`: `timing_debug`（有効フラグ）と `capture_timing_stats`（セッションの集計）の保持
+-   `ui/schedule_handler.rs`: 定期キャプチャ終了時に `dump_capture_timing_stats` を呼び出す
+ */
+
+use std::time::{Duration, Instant};
+
+use crate::{app_state::AppState, strings, system_utils::app_log};
+
+/// 計測する処理の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapturePhase {
+    /// 画面からメモリDCへのコピー（BitBlt、2つ目の領域の取得を含む）
+    Grab,
+    /// ピクセルデータの取得（StretchBlt・GetDIBits・Desktop Duplication・サムネイル作成）
+    Convert,
+    /// マスクの塗りつぶし・保存サイズへの縮小・2領域の結合
+    Scale,
+    /// 保存形式へのエンコード（EXIFの埋め込みを含む）
+    Encode,
+    /// 空き容量の確認とファイルへの書き込み
+    Write,
+}
+
+/// 段階の数（`CapturePhase` のバリアント数）
+const PHASE_COUNT: usize = 5;
+
+/// 1回のキャプチャの段階ごとの所要時間を計測する
+pub struct CaptureTimer {
+    started: Instant,
+    last_lap: Instant,
+    phases: [Duration; PHASE_COUNT],
+}
+
+impl CaptureTimer {
+    /// 計測を開始する
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last_lap: now,
+            phases: [Duration::ZERO; PHASE_COUNT],
+        }
+    }
+
+    /// 前回の `lap`（初回は計測開始）からの経過時間を、指定した段階の時間として加算する
+    pub fn lap(&mut self, phase: CapturePhase) {
+        let now = Instant::now();
+        self.phases[phase as usize] += now - self.last_lap;
+        self.last_lap = now;
+    }
+
+    /// 計測開始からの合計時間
+    fn total(&self) -> Duration {
+        self.last_lap - self.started
+    }
+}
+
+/// セッション（キャプチャモード・定期キャプチャの1回の実行）の所要時間の集計
+#[derive(Debug, Default)]
+pub struct CaptureTimingStats {
+    count: u32,
+    total_min: Duration,
+    total_max: Duration,
+    total_sum: Duration,
+    phase_sums: [Duration; PHASE_COUNT],
+}
+
+impl CaptureTimingStats {
+    /// 1回分の計測結果を集計に加える
+    fn record(&mut self, timer: &CaptureTimer) {
+        let total = timer.total();
+        self.total_min = if self.count == 0 {
+            total
+        } else {
+            self.total_min.min(total)
+        };
+        self.total_max = self.total_max.max(total);
+        self.total_sum += total;
+        for (sum, phase) in self.phase_sums.iter_mut().zip(timer.phases) {
+            *sum += phase;
+        }
+        self.count += 1;
+    }
+}
+
+/// 保存に成功したキャプチャの計測を終了し、内訳をログに出力してセッションの集計に加える
+///
+/// `timing_debug` が無効な場合は何もしません。
+pub fn finish_capture_timing(timer: &CaptureTimer) {
+    let app_state = AppState::get_app_state_mut();
+    if !app_state.timing_debug {
+        return;
+    }
+    app_log(&strings::capture_timing_breakdown(
+        as_millis(timer.total()),
+        timer.phases.map(as_millis),
+    ));
+    app_state.capture_timing_stats.record(timer);
+}
+
+/// セッションの集計（最小・平均・最大、段階ごとの平均）をログに出力してリセットする
+///
+/// 計測したキャプチャがない場合は何も出力しません。
+pub fn dump_capture_timing_stats() {
+    let app_state = AppState::get_app_state_mut();
+    let stats = std::mem::take(&mut app_state.capture_timing_stats);
+    if !app_state.timing_debug || stats.count == 0 {
+        return;
+    }
+    app_log(&strings::capture_timing_summary(
+        stats.count,
+        as_millis(stats.total_min),
+        as_millis(stats.total_sum / stats.count),
+        as_millis(stats.total_max),
+        stats.phase_sums.map(|sum| as_millis(sum / stats.count)),
+    ));
+}
+
+/// ログ表示用のミリ秒（小数第1位まで表示するため `f64`）
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,1087 @@
+/*
+============================================================================
+キャプチャライブラリAPIモジュール (capturer.rs)
+============================================================================
+
+【ファイル概要】
+ダイアログ・フック・オーバーレイ・グローバル状態（`AppState`）に依存せずに、
+画面領域のキャプチャ（JPEG/WebP/AVIF/BMP）とフォルダ一括PDF変換を行うライブラリAPIです。
+設定は型付きの `CaptureConfig` / `PdfConfig` で受け取り、結果は戻り値で返します。
+他のツールからの組み込みや、UIを介さない動作確認に使用できます。
+
+【主要機能】
+1.  **`Capturer::capture_area`**:
+    -   キャプチャバックエンド（`capture_backend.rs`：GDI / Desktop Duplication）で画面の指定領域を取得し、
+        `downscale_image`（Lanczos3）でスケール設定に従って縮小
+    -   設定された保存形式のバイト列として返却
+    -   `capture_area_with` で任意のバックエンド（テスト用の合成画像など）を指定可能
+    -   `capture_area_to_sink` で、取得した画像を `capture_sink.rs` の出力先へ長さ付きフレームとして送信
+2.  **`Capturer::export_pdf`**:
+    -   `pdf_builder::export_jpeg_folders_to_pdf` を呼び出し、フォルダ内のJPEGをPDFへ変換
+3.  **共通処理（バイナリ側の `screen_capture.rs` と共有）**:
+    -   `compute_scaled_size`: 縮小率（%）または出力幅（px）から保存サイズを計算（拡大なし、最小1px）
+    -   `clamp_to_max_size`: 最大幅・最大高さを超える場合に縦横比を保って縮小
+    -   `apply_mask_areas`: マスク領域（スクリーン座標）を画像内の座標に変換して黒で塗りつぶし
+    -   `downscale_image`: 原寸画像を `image::imageops::resize`（`DOWNSCALE_FILTER`）で縮小
+    -   `compose_dual_images`: デュアル選択の2画像を左右または上下に余白付きで結合
+    -   `scale_capture`: 保存サイズへの縮小と、2つ目の領域の同じ縮小率での縮小・結合（`CapturePipeline::scale` の本体）
+    -   `encode_capture`: 保存形式（JPEG / WebP可逆 / WebP非可逆 / AVIF / BMP）に応じてエンコード
+    -   `estimate_encoded_size`: 保存形式・品質から1枚あたりのファイルサイズを見積もり（自動クリック前の空き容量確認用）
+    -   `encode_bmp`: 24bit BMP（無圧縮）をヘッダーから直接書き出し（`image` クレート不使用）
+    -   `encode_jpeg`: 品質・高精細テキストモード（4:4:4・フラットな輝度量子化テーブル）・プログレッシブを指定してJPEGエンコード
+
+【技術仕様】
+-   **画面取得**: `GetDC` + `BitBlt` + `GetDIBits`（24bpp、トップダウン）、
+    または DXGI Desktop Duplication（`CaptureConfig::backend`、自動モードではGDIの結果が真っ黒な場合のみ）
+-   **縮小処理**: 原寸バッファをRust側で縮小（`HALFTONE` の `StretchBlt` より文字のにじみ・リンギングが少ない）。
+    `gdi-stretchblt` フィーチャー有効時は従来の `StretchBlt`（`HALFTONE`）で縮小（低スペック環境向けフォールバック）
+-   **エンコード**: 通常は `image` クレートの `JpegEncoder`、高精細テキストモード時は `jpeg-encoder`
+    （標準の量子化テーブルは高周波成分を粗く量子化するため、品質を95→100に上げても文字のリンギングが
+    ほとんど減らない。高精細テキストモードでは輝度にフラットなテーブルを使い、文字の輪郭を保持する）。
+    `progressive-jpeg` フィーチャー有効時は、`CaptureConfig::progressive_jpeg` で通常のJPEGも
+    `jpeg-encoder` のプログレッシブ形式（4:2:0・標準の量子化テーブル）で保存できる（Web掲載向け）
+-   **WebP / AVIF**: `image` クレートの `WebPEncoder`（非可逆はlibwebp）、AVIFは `avif` フィーチャー有効時のみ
+-   **エラー処理**: `Box<dyn std::error::Error>` で呼び出し元へ返却（ログ出力・UI通知は行わない）
+
+【AI解析用：依存関係】
+-   `lib.rs`: ライブラリの公開モジュールとして宣言
+-   `pdf_builder.rs`: PDF変換処理の本体
+-   `capture_backend.rs`: `CaptureBackend` トレイトとGDI / Desktop Duplication実装
+-   `dib.rs`: BGR/BGRAのピクセル変換（`bgr_dib_to_rgb_image`）、BMPのサイズ見積もり（`dib_row_size`）
+-   `screen_capture.rs`（バイナリ側）: `encode_capture` を使用
+ */
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use image::{
+    ColorType, ImageEncoder, RgbImage,
+    codecs::{
+        jpeg::JpegEncoder,
+        webp::{WebPEncoder, WebPQuality},
+    },
+    imageops::FilterType,
+};
+#[cfg(windows)]
+use windows::Win32::Foundation::RECT;
+
+use crate::{
+    capture_backend::CaptureBackendKind,
+    dib::dib_row_size,
+    pdf_builder::{
+        DEFAULT_SCREEN_DPI, PdfConformance, PdfExportOptions, PdfOverwritePolicy,
+        export_jpeg_folders_to_pdf,
+    },
+};
+#[cfg(windows)]
+use crate::{
+    capture_backend::{CaptureBackend, DesktopDuplicationBackend, GdiBackend, is_blank_frame},
+    capture_sink::CaptureSink,
+};
+
+/// Rust側の縮小処理に使用するフィルタ
+///
+/// Lanczos3は文字の輪郭を保ちやすく、4K画面を55%程度に縮小してもにじみが少ない。
+/// 速度を優先する場合は `FilterType::Triangle` に変更する。
+pub const DOWNSCALE_FILTER: FilterType = FilterType::Lanczos3;
+
+/// キャプチャ画像の保存形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    /// JPEG（従来動作、デフォルト）。PDF変換にそのまま埋め込める
+    Jpeg = 0,
+    /// 可逆WebP。単色の多いUI画面ではJPEGより大幅に小さくなる
+    WebpLossless = 1,
+    /// 非可逆WebP。品質はJPEG品質設定を流用
+    WebpLossy = 2,
+    /// AVIF（`avif` フィーチャー有効時のみ）。品質はJPEG品質設定を流用
+    #[cfg(feature = "avif")]
+    Avif = 3,
+    /// 24bit BMP（無圧縮）。コマ単位の作業用に画質劣化ゼロで保存。PDF変換時はJPEGに変換して埋め込む
+    Bmp = 4,
+}
+
+impl CaptureFormat {
+    /// 選択可能な保存形式の一覧（ビルド時のフィーチャーに応じて変化）
+    pub fn all() -> &'static [CaptureFormat] {
+        &[
+            CaptureFormat::Jpeg,
+            CaptureFormat::WebpLossless,
+            CaptureFormat::WebpLossy,
+            #[cfg(feature = "avif")]
+            CaptureFormat::Avif,
+            CaptureFormat::Bmp,
+        ]
+    }
+
+    /// コンボボックス項目データから復元（不明な値はJPEG扱い）
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CaptureFormat::WebpLossless,
+            2 => CaptureFormat::WebpLossy,
+            #[cfg(feature = "avif")]
+            3 => CaptureFormat::Avif,
+            4 => CaptureFormat::Bmp,
+            _ => CaptureFormat::Jpeg,
+        }
+    }
+
+    /// コンボボックス表示用ラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaptureFormat::Jpeg => "JPEG",
+            CaptureFormat::WebpLossless => "WebP(可逆)",
+            CaptureFormat::WebpLossy => "WebP",
+            #[cfg(feature = "avif")]
+            CaptureFormat::Avif => "AVIF",
+            CaptureFormat::Bmp => "BMP(無圧縮)",
+        }
+    }
+
+    /// 保存ファイルの拡張子
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CaptureFormat::Jpeg => "jpg",
+            CaptureFormat::WebpLossless | CaptureFormat::WebpLossy => "webp",
+            #[cfg(feature = "avif")]
+            CaptureFormat::Avif => "avif",
+            CaptureFormat::Bmp => "bmp",
+        }
+    }
+}
+
+/// 2領域キャプチャ（デュアル選択）時の結合方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DualAreaLayout {
+    /// 左右に並べる（デフォルト）。左が1つ目の領域
+    #[default]
+    SideBySide,
+    /// 上下に並べる。上が1つ目の領域
+    Stacked,
+}
+
+/// 2領域を結合する際の領域間の余白（ピクセル）
+pub const DUAL_AREA_GAP: u32 = 8;
+
+/// 画面キャプチャの設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureConfig {
+    /// 保存形式
+    pub format: CaptureFormat,
+    /// 画像スケール（%）。100で原寸、それ未満で縮小
+    pub scale_percent: u8,
+    /// 出力幅（px）。指定時は `scale_percent` の代わりに、縦横比を保ってこの幅に縮小
+    pub target_width: Option<u32>,
+    /// 最大幅（px）。縮小後のサイズがこれを超える場合は縦横比を保って更に縮小
+    pub max_width: Option<u32>,
+    /// 最大高さ（px）。縮小後のサイズがこれを超える場合は縦横比を保って更に縮小
+    pub max_height: Option<u32>,
+    /// JPEG品質（1〜100）
+    pub jpeg_quality: u8,
+    /// 高精細テキストモード（4:4:4サンプリング・フラットな輝度量子化テーブル・プログレッシブで保存）
+    pub high_fidelity_text: bool,
+    /// プログレッシブJPEGで保存（`progressive-jpeg` フィーチャー有効時のみ。高精細テキストモードは常にプログレッシブ）
+    pub progressive_jpeg: bool,
+    /// 黒で塗りつぶすマスク領域（スクリーン座標）。個人情報などの伏せ字に使用
+    #[cfg(windows)]
+    pub mask_areas: Vec<RECT>,
+    /// キャプチャバックエンド（GDI / Desktop Duplication / 自動）
+    pub backend: CaptureBackendKind,
+}
+
+impl Default for CaptureConfig {
+    /// アプリケーションの初期設定と同じ値（スケール65%、品質95%）
+    fn default() -> Self {
+        Self {
+            format: CaptureFormat::Jpeg,
+            scale_percent: 65,
+            target_width: None,
+            max_width: None,
+            max_height: None,
+            jpeg_quality: 95,
+            high_fidelity_text: false,
+            progressive_jpeg: false,
+            #[cfg(windows)]
+            mask_areas: Vec::new(),
+            backend: CaptureBackendKind::Auto,
+        }
+    }
+}
+
+/// PDF変換の設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfConfig {
+    /// 1ファイルあたりのPDFサイズ上限（MB）。超えた場合は複数ファイルに分割
+    pub max_size_mb: u16,
+    /// PDFの出力先フォルダ。`None` の場合は変換元フォルダに出力
+    pub output_dir: Option<PathBuf>,
+    /// 各ページの下にEXIFの撮影日時をフッターとして表示する
+    pub capture_time_footer: bool,
+    /// 撮影時の画面DPI。EXIFに画面DPIの記録が無い画像のページサイズに使用
+    pub screen_dpi: u32,
+    /// 出力先に同じ連番のPDFが既にある場合に上書きするか、枝番を付けて残すか
+    pub overwrite_policy: PdfOverwritePolicy,
+    /// 通常のPDFか、アーカイブ用のPDF/A-1b（撮影日時フッターは省略）か
+    pub conformance: PdfConformance,
+}
+
+impl Default for PdfConfig {
+    /// アプリケーションの初期設定と同じ値（上限20MB、変換元フォルダに出力、フッターなし、96DPI、上書き、通常のPDF）
+    fn default() -> Self {
+        Self {
+            max_size_mb: 20,
+            output_dir: None,
+            capture_time_footer: false,
+            screen_dpi: DEFAULT_SCREEN_DPI,
+            overwrite_policy: PdfOverwritePolicy::Overwrite,
+            conformance: PdfConformance::Standard,
+        }
+    }
+}
+
+/// UIやグローバル状態に依存しない画面キャプチャ・PDF変換の実行者
+#[derive(Debug, Clone, Default)]
+pub struct Capturer {
+    config: CaptureConfig,
+}
+
+impl Capturer {
+    /// 指定した設定で `Capturer` を作成する
+    pub fn new(config: CaptureConfig) -> Self {
+        Self { config }
+    }
+
+    /// 現在のキャプチャ設定
+    pub fn config(&self) -> &CaptureConfig {
+        &self.config
+    }
+
+    /// 画面の指定領域をキャプチャし、設定された保存形式のバイト列として返す
+    ///
+    /// `CaptureConfig::backend` に従ってキャプチャバックエンドを選択します。
+    /// Desktop Duplicationが使用できない環境（RDPセッションなど）ではGDIで取得します。
+    /// Desktop Duplicationのセッションは呼び出しごとに作成するため、連続して取得する場合は
+    /// `DesktopDuplicationBackend` を保持して `capture_area_with` を使用してください。
+    ///
+    /// # 引数
+    /// * `area` - キャプチャするスクリーン座標の矩形
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<u8>)` - エンコード済みの画像データ（`CaptureConfig::format` の形式）
+    /// * `Err(Box<dyn std::error::Error>)` - 領域が空、ビットマップ取得失敗、エンコード失敗など
+    #[cfg(windows)]
+    pub fn capture_area(&self, area: RECT) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let img_buffer = match self.config.backend {
+            CaptureBackendKind::Gdi => self.capture_image_with(&mut GdiBackend, area)?,
+            CaptureBackendKind::DesktopDuplication => self
+                .capture_image_with(&mut DesktopDuplicationBackend::new(), area)
+                .or_else(|_| self.capture_image_with(&mut GdiBackend, area))?,
+            CaptureBackendKind::Auto => {
+                // GDIの結果が真っ黒（保護された画面など）の場合のみDesktop Duplicationで取得し直す
+                let img_buffer = self.capture_image_with(&mut GdiBackend, area)?;
+                if is_blank_frame(&img_buffer) {
+                    self.capture_image_with(&mut DesktopDuplicationBackend::new(), area)
+                        .unwrap_or(img_buffer)
+                } else {
+                    img_buffer
+                }
+            }
+        };
+        self.encode_image(&img_buffer)
+    }
+
+    /// 画面の指定領域をキャプチャし、ファイルに保存せずに出力先へ1フレームとして送信する
+    ///
+    /// エンコードは `capture_area` と同じです。送信したフレームの画像のバイト数を返します。
+    ///
+    /// # 引数
+    /// * `area` - キャプチャするスクリーン座標の矩形
+    /// * `sink` - 送信先（名前付きパイプ・標準出力など）
+    #[cfg(windows)]
+    pub fn capture_area_to_sink(
+        &self,
+        area: RECT,
+        sink: &mut CaptureSink,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let encoded = self.capture_area(area)?;
+        sink.send_frame(&encoded)?;
+        Ok(encoded.len())
+    }
+
+    /// 指定したキャプチャバックエンドで画面の指定領域をキャプチャし、保存形式のバイト列として返す
+    ///
+    /// `CaptureConfig::backend` は無視し、フォールバックも行いません。
+    /// テスト用の合成画像バックエンドや、保持している `DesktopDuplicationBackend` の利用に使用します。
+    ///
+    /// # 引数
+    /// * `backend` - 使用するキャプチャバックエンド
+    /// * `area` - キャプチャするスクリーン座標の矩形
+    #[cfg(windows)]
+    pub fn capture_area_with(
+        &self,
+        backend: &mut dyn CaptureBackend,
+        area: RECT,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let img_buffer = self.capture_image_with(backend, area)?;
+        self.encode_image(&img_buffer)
+    }
+
+    /// バックエンドで取得した画像にマスクを適用し、保存サイズへ縮小する
+    #[cfg(windows)]
+    fn capture_image_with(
+        &self,
+        backend: &mut dyn CaptureBackend,
+        area: RECT,
+    ) -> Result<RgbImage, Box<dyn std::error::Error>> {
+        let width = (area.right - area.left).abs();
+        let height = (area.bottom - area.top).abs();
+        if width == 0 || height == 0 {
+            return Err(format!("無効なキャプチャ領域: {}x{}", width, height).into());
+        }
+
+        let (scaled_width, scaled_height) = compute_scaled_size(
+            width,
+            height,
+            self.config.scale_percent,
+            self.config.target_width,
+        );
+        let (scaled_width, scaled_height) = clamp_to_max_size(
+            scaled_width,
+            scaled_height,
+            self.config.max_width,
+            self.config.max_height,
+        );
+
+        let mut img_buffer = backend.capture_rect(area, (scaled_width, scaled_height))?;
+        apply_mask_areas(&mut img_buffer, area, &self.config.mask_areas);
+        Ok(downscale_image(img_buffer, scaled_width, scaled_height))
+    }
+
+    /// 設定された保存形式・品質で画像をエンコードする
+    #[cfg(windows)]
+    fn encode_image(&self, img_buffer: &RgbImage) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut encoded_bytes = Vec::new();
+        encode_capture(
+            &mut encoded_bytes,
+            img_buffer,
+            self.config.format,
+            self.config.jpeg_quality,
+            self.config.high_fidelity_text,
+            self.config.progressive_jpeg,
+        )?;
+        Ok(encoded_bytes)
+    }
+
+    /// フォルダ内のJPEG画像をPDFファイルに変換する
+    ///
+    /// # 引数
+    /// * `dir` - 変換元のJPEGが格納されたフォルダ
+    /// * `config` - PDFサイズ上限・出力先フォルダ・画面DPI・既存PDFの扱い
+    pub fn export_pdf(
+        &self,
+        dir: impl AsRef<Path>,
+        config: &PdfConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let source_dir = dir.as_ref();
+        let output_dir = config.output_dir.as_deref().unwrap_or(source_dir);
+        export_jpeg_folders_to_pdf(
+            &[source_dir.to_path_buf()],
+            output_dir,
+            &PdfExportOptions {
+                max_size_mb: config.max_size_mb,
+                screen_dpi: config.screen_dpi,
+                capture_time_footer: config.capture_time_footer,
+                overwrite_policy: config.overwrite_policy,
+                conformance: config.conformance,
+            },
+            &|event| println!("{:?}", event),
+        )
+    }
+}
+
+/// キャプチャ領域のサイズと縮小設定から、保存する画像のサイズを計算する
+///
+/// 拡大は行わず（原寸が上限）、極端な縦横比でも幅・高さが0にならないよう最小1pxに丸めます。
+///
+/// # 引数
+/// * `width` - キャプチャ領域の幅（ピクセル）
+/// * `height` - キャプチャ領域の高さ（ピクセル）
+/// * `scale_percent` - 縮小率（%）。`target_width` が `None` の場合に使用
+/// * `target_width` - 出力幅（px）。指定時は縦横比を保ってこの幅に縮小
+///
+/// # 戻り値
+/// `(scaled_width, scaled_height)`
+pub fn compute_scaled_size(
+    width: i32,
+    height: i32,
+    scale_percent: u8,
+    target_width: Option<u32>,
+) -> (i32, i32) {
+    let (scaled_width, scaled_height) = match target_width {
+        Some(target_width) if target_width > 0 => {
+            // 幅指定：原寸より大きい幅は原寸に制限し、高さは縦横比から算出
+            let scaled_width = (target_width as i64).min(width as i64);
+            let scaled_height = height as i64 * scaled_width / (width as i64).max(1);
+            (scaled_width as i32, scaled_height as i32)
+        }
+        _ => {
+            let scale_factor = (scale_percent.min(100) as f32) / 100.0;
+            (
+                ((width as f32) * scale_factor) as i32,
+                ((height as f32) * scale_factor) as i32,
+            )
+        }
+    };
+    (scaled_width.max(1), scaled_height.max(1))
+}
+
+/// 画像サイズが最大幅・最大高さを超える場合に、縦横比を保ったまま上限内に収まるサイズを計算する
+///
+/// 上限を超えていない場合や上限が `None`（または0）の場合は、そのままのサイズを返します。
+///
+/// # 引数
+/// * `width` - 縮小設定適用後の幅（ピクセル）
+/// * `height` - 縮小設定適用後の高さ（ピクセル）
+/// * `max_width` - 最大幅（px）
+/// * `max_height` - 最大高さ（px）
+///
+/// # 戻り値
+/// `(width, height)`（いずれも最小1px）
+pub fn clamp_to_max_size(
+    width: i32,
+    height: i32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> (i32, i32) {
+    let (mut width, mut height) = (width as i64, height as i64);
+
+    if let Some(max_width) = max_width.map(|w| w as i64).filter(|&w| w > 0 && width > w) {
+        height = height * max_width / width;
+        width = max_width;
+    }
+    if let Some(max_height) = max_height
+        .map(|h| h as i64)
+        .filter(|&h| h > 0 && height > h)
+    {
+        width = width * max_height / height;
+        height = max_height;
+    }
+    ((width as i32).max(1), (height as i32).max(1))
+}
+
+/// マスク領域を黒で塗りつぶす
+///
+/// スクリーン座標のマスク領域をキャプチャ領域内の座標へ変換し、画像の範囲に切り詰めて塗りつぶします。
+/// 画像がキャプチャ領域から縮小済みの場合（`gdi-stretchblt`）も、縮小率に合わせて座標を変換します。
+/// 縮小時の端数は外側へ丸め、伏せた内容が境界からはみ出さないようにします。
+///
+/// # 引数
+/// * `image` - キャプチャ画像（キャプチャ領域全体に対応）
+/// * `capture_area` - キャプチャ領域（スクリーン座標）
+/// * `mask_areas` - 塗りつぶす領域（スクリーン座標）
+#[cfg(windows)]
+pub fn apply_mask_areas(image: &mut RgbImage, capture_area: RECT, mask_areas: &[RECT]) {
+    let area_left = capture_area.left.min(capture_area.right) as f64;
+    let area_top = capture_area.top.min(capture_area.bottom) as f64;
+    let area_width = (capture_area.right - capture_area.left).abs().max(1) as f64;
+    let area_height = (capture_area.bottom - capture_area.top).abs().max(1) as f64;
+    let (image_width, image_height) = image.dimensions();
+    let scale_x = image_width as f64 / area_width;
+    let scale_y = image_height as f64 / area_height;
+
+    for mask in mask_areas {
+        // キャプチャ領域のローカル座標へ変換し、画像の範囲に切り詰める
+        let to_x = |x: i32| ((x as f64 - area_left) * scale_x).clamp(0.0, image_width as f64);
+        let to_y = |y: i32| ((y as f64 - area_top) * scale_y).clamp(0.0, image_height as f64);
+        let left = to_x(mask.left.min(mask.right)).floor() as u32;
+        let right = to_x(mask.left.max(mask.right)).ceil() as u32;
+        let top = to_y(mask.top.min(mask.bottom)).floor() as u32;
+        let bottom = to_y(mask.top.max(mask.bottom)).ceil() as u32;
+
+        for y in top..bottom {
+            for x in left..right {
+                image.put_pixel(x, y, image::Rgb([0, 0, 0]));
+            }
+        }
+    }
+}
+
+/// 原寸画像を指定サイズへ縮小する（`DOWNSCALE_FILTER` を使用）
+///
+/// 既に指定サイズの場合（縮小不要、または `gdi-stretchblt` で縮小済み）は、そのまま返します。
+///
+/// # 引数
+/// * `image` - 縮小元の画像
+/// * `width` - 縮小後の幅（ピクセル）
+/// * `height` - 縮小後の高さ（ピクセル）
+pub fn downscale_image(image: RgbImage, width: i32, height: i32) -> RgbImage {
+    let (width, height) = (width.max(1) as u32, height.max(1) as u32);
+    if image.dimensions() == (width, height) {
+        return image;
+    }
+    image::imageops::resize(&image, width, height, DOWNSCALE_FILTER)
+}
+
+/// 2つの画像を余白を挟んで1枚に結合する（比較用のデュアル選択キャプチャ）
+///
+/// 余白と、サイズ差によって生じる空き領域は白で塗りつぶします。
+///
+/// # 引数
+/// * `first` - 1つ目の画像（左または上に配置）
+/// * `second` - 2つ目の画像（右または下に配置）
+/// * `layout` - 結合方向
+/// * `gap` - 画像間の余白（ピクセル）
+pub fn compose_dual_images(
+    first: &RgbImage,
+    second: &RgbImage,
+    layout: DualAreaLayout,
+    gap: u32,
+) -> RgbImage {
+    let (width, height, second_x, second_y) = match layout {
+        DualAreaLayout::SideBySide => (
+            first.width() + gap + second.width(),
+            first.height().max(second.height()),
+            first.width() + gap,
+            0,
+        ),
+        DualAreaLayout::Stacked => (
+            first.width().max(second.width()),
+            first.height() + gap + second.height(),
+            0,
+            first.height() + gap,
+        ),
+    };
+
+    let mut composed = RgbImage::from_pixel(width, height, image::Rgb([255, 255, 255]));
+    image::imageops::replace(&mut composed, first, 0, 0);
+    image::imageops::replace(&mut composed, second, second_x as i64, second_y as i64);
+    composed
+}
+
+/// 保存サイズへ縮小し、2つ目の領域がある場合は同じ縮小率で縮小して1枚に結合する
+///
+/// 2つ目の領域の縮小率は1つ目の領域の原寸の幅を基準にします
+/// （`gdi-stretchblt` では取得時に縮小済みのため、`image` の幅ではなく `full_width` を使う）。
+///
+/// # 引数
+/// * `image` - 1つ目の領域の画像
+/// * `secondary` - 2つ目の領域の画像（デュアル選択時のみ）
+/// * `output_size` - 1つ目の領域の保存サイズ（ピクセル）
+/// * `full_width` - 1つ目の領域の原寸の幅（ピクセル）
+/// * `layout` - 2つの画像の結合方向
+pub fn scale_capture(
+    image: RgbImage,
+    secondary: Option<RgbImage>,
+    output_size: (i32, i32),
+    full_width: i32,
+    layout: DualAreaLayout,
+) -> RgbImage {
+    let (scaled_width, scaled_height) = output_size;
+    let image = downscale_image(image, scaled_width, scaled_height);
+
+    match secondary {
+        Some(secondary) => {
+            let ratio = scaled_width as f64 / full_width.max(1) as f64;
+            let secondary_width = (secondary.width() as f64 * ratio).round() as i32;
+            let secondary_height = (secondary.height() as f64 * ratio).round() as i32;
+            let secondary = downscale_image(secondary, secondary_width, secondary_height);
+            compose_dual_images(&image, &secondary, layout, DUAL_AREA_GAP)
+        }
+        None => image,
+    }
+}
+
+/// RGB画像を指定した保存形式でエンコードして書き込む
+///
+/// # 引数
+/// * `writer` - 出力先（ファイル、メモリバッファなど）
+/// * `img_buffer` - エンコードするRGB画像
+/// * `format` - 保存形式
+/// * `quality` - 非可逆形式の品質（1〜100）。可逆WebPでは無視
+/// * `high_fidelity_text` - JPEG時のみ有効。4:4:4・フラットな輝度量子化テーブル・プログレッシブで保存
+/// * `progressive` - JPEG時のみ有効。プログレッシブ形式で保存（`progressive-jpeg` フィーチャー無効時は無視）
+pub fn encode_capture<W: Write>(
+    writer: W,
+    img_buffer: &RgbImage,
+    format: CaptureFormat,
+    quality: u8,
+    high_fidelity_text: bool,
+    progressive: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        CaptureFormat::Jpeg => {
+            encode_jpeg(writer, img_buffer, quality, high_fidelity_text, progressive)?
+        }
+        CaptureFormat::WebpLossless => {
+            WebPEncoder::new_lossless(writer).write_image(
+                img_buffer.as_raw(),
+                img_buffer.width(),
+                img_buffer.height(),
+                ColorType::Rgb8,
+            )?;
+        }
+        CaptureFormat::WebpLossy => {
+            // 非可逆WebPは image 0.24 で非推奨（将来削除予定）だが、libwebp経由で現在も利用可能
+            #[allow(deprecated)]
+            WebPEncoder::new_with_quality(writer, WebPQuality::lossy(quality)).write_image(
+                img_buffer.as_raw(),
+                img_buffer.width(),
+                img_buffer.height(),
+                ColorType::Rgb8,
+            )?;
+        }
+        #[cfg(feature = "avif")]
+        CaptureFormat::Avif => {
+            // 速度8：キャプチャ操作を待たせない程度のエンコード速度を優先
+            image::codecs::avif::AvifEncoder::new_with_speed_quality(writer, 8, quality)
+                .write_image(
+                    img_buffer.as_raw(),
+                    img_buffer.width(),
+                    img_buffer.height(),
+                    ColorType::Rgb8,
+                )?;
+        }
+        CaptureFormat::Bmp => encode_bmp(writer, img_buffer)?,
+    }
+    Ok(())
+}
+
+/// 保存形式・品質から、1枚あたりのおおよそのファイルサイズ（バイト）を見積もる
+///
+/// 画面キャプチャ（文字・単色の多いUI画面）を想定した1ピクセルあたりのバイト数の経験値から
+/// 計算します。写真や動画の画面では実際のサイズがこれより大きくなる場合があります。
+/// BMPは無圧縮のため、ヘッダーと行パディングを含めた正確なサイズを返します。
+///
+/// # 引数
+/// * `width`, `height` - 保存する画像のサイズ（縮小後、ピクセル）
+/// * `format` - 保存形式
+/// * `quality` - 非可逆形式の品質（1〜100）
+/// * `high_fidelity_text` - JPEGの高精細テキストモード（4:4:4で色差を間引かないため大きくなる）
+pub fn estimate_encoded_size(
+    width: u32,
+    height: u32,
+    format: CaptureFormat,
+    quality: u8,
+    high_fidelity_text: bool,
+) -> u64 {
+    let pixels = width as u64 * height as u64;
+
+    // 品質ごとのJPEGの1ピクセルあたりのバイト数（UI画面の実測に基づく目安）
+    let jpeg_bytes_per_pixel = match quality {
+        100.. => 1.0,
+        95..=99 => 0.6,
+        90..=94 => 0.45,
+        85..=89 => 0.35,
+        80..=84 => 0.3,
+        _ => 0.25,
+    };
+
+    let bytes_per_pixel = match format {
+        CaptureFormat::Jpeg if high_fidelity_text => jpeg_bytes_per_pixel * 1.5,
+        CaptureFormat::Jpeg => jpeg_bytes_per_pixel,
+        CaptureFormat::WebpLossless => 0.5,
+        CaptureFormat::WebpLossy => jpeg_bytes_per_pixel * 0.7,
+        #[cfg(feature = "avif")]
+        CaptureFormat::Avif => jpeg_bytes_per_pixel * 0.5,
+        CaptureFormat::Bmp => return 54 + dib_row_size(width) as u64 * height as u64,
+    };
+    (pixels as f64 * bytes_per_pixel) as u64
+}
+
+/// RGB画像を24bit BMP（無圧縮、ボトムアップ）として書き込む
+///
+/// `BITMAPFILEHEADER`（14バイト）+ `BITMAPINFOHEADER`（40バイト）+ BGR画素データ（各行4バイト境界）を
+/// 直接書き出します。速度を優先し、`image` クレートのエンコーダーは使用しません。
+///
+/// # 引数
+/// * `writer` - 出力先（ファイル、メモリバッファなど）
+/// * `img_buffer` - 書き込むRGB画像
+pub fn encode_bmp<W: Write>(mut writer: W, img_buffer: &RgbImage) -> std::io::Result<()> {
+    const FILE_HEADER_SIZE: u32 = 14;
+    const INFO_HEADER_SIZE: u32 = 40;
+
+    let (width, height) = img_buffer.dimensions();
+    let row_size = (width * 3 + 3) & !3; // 各行は4バイト境界にパディング
+    let pixel_data_size = row_size * height;
+    let pixel_offset = FILE_HEADER_SIZE + INFO_HEADER_SIZE;
+
+    let mut header = Vec::with_capacity(pixel_offset as usize);
+    // BITMAPFILEHEADER
+    header.extend_from_slice(b"BM");
+    header.extend_from_slice(&(pixel_offset + pixel_data_size).to_le_bytes()); // bfSize
+    header.extend_from_slice(&0u32.to_le_bytes()); // bfReserved1, bfReserved2
+    header.extend_from_slice(&pixel_offset.to_le_bytes()); // bfOffBits
+    // BITMAPINFOHEADER
+    header.extend_from_slice(&INFO_HEADER_SIZE.to_le_bytes()); // biSize
+    header.extend_from_slice(&(width as i32).to_le_bytes()); // biWidth
+    header.extend_from_slice(&(height as i32).to_le_bytes()); // biHeight（正：ボトムアップ）
+    header.extend_from_slice(&1u16.to_le_bytes()); // biPlanes
+    header.extend_from_slice(&24u16.to_le_bytes()); // biBitCount
+    header.extend_from_slice(&0u32.to_le_bytes()); // biCompression = BI_RGB
+    header.extend_from_slice(&pixel_data_size.to_le_bytes()); // biSizeImage
+    header.extend_from_slice(&0i32.to_le_bytes()); // biXPelsPerMeter
+    header.extend_from_slice(&0i32.to_le_bytes()); // biYPelsPerMeter
+    header.extend_from_slice(&0u32.to_le_bytes()); // biClrUsed
+    header.extend_from_slice(&0u32.to_le_bytes()); // biClrImportant
+    writer.write_all(&header)?;
+
+    // 画素データ（下の行から、RGB → BGR）
+    let mut row = vec![0u8; row_size as usize];
+    for y in (0..height).rev() {
+        for x in 0..width {
+            let image::Rgb([r, g, b]) = *img_buffer.get_pixel(x, y);
+            let idx = (x * 3) as usize;
+            row[idx] = b;
+            row[idx + 1] = g;
+            row[idx + 2] = r;
+        }
+        writer.write_all(&row)?;
+    }
+    writer.flush()
+}
+
+/// RGB画像をJPEGにエンコードして書き込む
+///
+/// # 引数
+/// * `writer` - 出力先（ファイル、メモリバッファなど）
+/// * `img_buffer` - エンコードするRGB画像
+/// * `quality` - JPEG品質（1〜100）
+/// * `high_fidelity_text` - `true` の場合、色差を間引かない4:4:4・フラットな輝度量子化テーブル・
+///   プログレッシブで保存
+/// * `progressive` - `true` の場合、通常の設定（4:2:0・標準の量子化テーブル）のまま
+///   `jpeg-encoder` でプログレッシブ形式に保存（`progressive-jpeg` フィーチャー無効時は無視し、
+///   `image` クレートのベースライン形式で保存）
+///
+/// # 高精細テキストモードの量子化テーブル
+/// 標準（Annex K）の輝度テーブルは高周波成分ほど値が大きく（最大121）、文字の輪郭が持つ高周波成分が
+/// 削られてリンギングが生じます。フラットなテーブル（全係数16を品質で拡縮）では、品質95で全係数が2前後、
+/// 品質100で1（実質無損失の量子化）となり、品質を上げた分だけ文字が鮮明になります。
+/// 色差は文字の鮮明さへの影響が小さいため標準テーブルのままとし、ファイルサイズの増加を抑えます。
+pub fn encode_jpeg<W: Write>(
+    mut writer: W,
+    img_buffer: &RgbImage,
+    quality: u8,
+    high_fidelity_text: bool,
+    progressive: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if high_fidelity_text {
+        // 高精細テキストモード：色差を間引かない4:4:4で文字の色滲みを防止
+        let mut encoder = jpeg_encoder::Encoder::new(&mut writer, quality);
+        encoder.set_sampling_factor(jpeg_encoder::SamplingFactor::R_4_4_4);
+        // 輝度はフラットな量子化テーブルで高周波成分（文字の輪郭）を保持
+        encoder.set_quantization_tables(
+            jpeg_encoder::QuantizationTableType::Flat,
+            jpeg_encoder::QuantizationTableType::Default,
+        );
+        encoder.set_progressive(true);
+        encoder.encode(
+            img_buffer.as_raw(),
+            u16::try_from(img_buffer.width())?,
+            u16::try_from(img_buffer.height())?,
+            jpeg_encoder::ColorType::Rgb,
+        )?;
+    } else if cfg!(feature = "progressive-jpeg") && progressive {
+        // プログレッシブ：粗い画像から段階的に表示されるため、Web掲載時の読み込み中の見え方が良い
+        // （サンプリング・量子化テーブルは `jpeg-encoder` の標準設定＝ベースラインと同等）
+        let mut encoder = jpeg_encoder::Encoder::new(&mut writer, quality);
+        encoder.set_progressive(true);
+        encoder.encode(
+            img_buffer.as_raw(),
+            u16::try_from(img_buffer.width())?,
+            u16::try_from(img_buffer.height())?,
+            jpeg_encoder::ColorType::Rgb,
+        )?;
+    } else {
+        let encoder = JpegEncoder::new_with_quality(&mut writer, quality);
+        img_buffer.write_with_encoder(encoder)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 左半分が赤・右半分が白の画像（文字の色滲みが出やすい境界を含む）
+    fn make_two_tone(width: u32, height: u32) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, _| {
+            if x < width / 2 {
+                image::Rgb([200, 30, 30])
+            } else {
+                image::Rgb([255, 255, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn encode_jpeg_output_decodes_with_image_crate() {
+        // (高精細テキストモード, プログレッシブ)：jpeg-encoder の4:4:4・プログレッシブ出力も image で開けること
+        let modes = [(false, false), (false, true), (true, false), (true, true)];
+        for (high_fidelity_text, progressive) in modes {
+            for quality in [60, 95, 100] {
+                for (width, height) in [(1, 1), (17, 9), (64, 48)] {
+                    let original = make_two_tone(width, height);
+                    let mut encoded = Vec::new();
+                    encode_jpeg(
+                        &mut encoded,
+                        &original,
+                        quality,
+                        high_fidelity_text,
+                        progressive,
+                    )
+                    .unwrap();
+                    let label = format!(
+                        "hifi={high_fidelity_text} progressive={progressive} q={quality} {width}x{height}"
+                    );
+                    assert_eq!(&encoded[..2], &[0xFF, 0xD8], "{label}");
+
+                    let decoded =
+                        image::load_from_memory_with_format(&encoded, image::ImageFormat::Jpeg)
+                            .unwrap_or_else(|e| panic!("{label}: {e}"))
+                            .to_rgb8();
+                    assert_eq!(decoded.dimensions(), (width, height), "{label}");
+                    // 境界から離れた画素は元の色に近いこと
+                    for x in [0, width - 1] {
+                        let (expected, actual) =
+                            (original.get_pixel(x, 0).0, decoded.get_pixel(x, 0).0);
+                        for (e, a) in expected.iter().zip(actual) {
+                            assert!(
+                                e.abs_diff(a) <= 40,
+                                "{label} at x={x}: {expected:?} vs {actual:?}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// FNV-1a（64bit）。`DefaultHasher` はRustのバージョンで値が変わるため、固定値の比較に使う
+    fn fnv1a64(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// 文字の輪郭に近い細線とグラデーションを含む合成画像（縮小・色差の間引きの差が出やすい）
+    fn make_synthetic_frame(width: u32, height: u32, seed: u32) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, y| {
+            if (x + seed) % 23 < 2 || (y + seed).is_multiple_of(17) {
+                image::Rgb([20, 20, 20])
+            } else {
+                image::Rgb([
+                    (x * 255 / width) as u8,
+                    (y * 255 / height) as u8,
+                    ((x ^ y).wrapping_add(seed) % 256) as u8,
+                ])
+            }
+        })
+    }
+
+    /// `CapturePipeline` 導入前の `capture_screen_area_with_counter` の縮小・結合処理（比較の基準）
+    fn scale_before_pipeline(
+        image: RgbImage,
+        secondary: Option<RgbImage>,
+        (scaled_width, scaled_height): (i32, i32),
+        width: i32,
+        layout: DualAreaLayout,
+    ) -> RgbImage {
+        let image = downscale_image(image, scaled_width, scaled_height);
+        match secondary {
+            Some(secondary) => {
+                let ratio = scaled_width as f64 / width.max(1) as f64;
+                let secondary_width = (secondary.width() as f64 * ratio).round() as i32;
+                let secondary_height = (secondary.height() as f64 * ratio).round() as i32;
+                let secondary = downscale_image(secondary, secondary_width, secondary_height);
+                compose_dual_images(&image, &secondary, layout, DUAL_AREA_GAP)
+            }
+            None => image,
+        }
+    }
+
+    #[test]
+    fn scale_capture_output_hashes_match_before_pipeline() {
+        // (保存形式, 品質, 高精細テキストモード, 縮小率%, 2つ目の領域の結合方向, 出力のハッシュ)
+        let cases = [
+            (CaptureFormat::Jpeg, 95, false, 65, None, 0x1968d3d29ab270df),
+            (CaptureFormat::Jpeg, 100, true, 65, None, 0xe97770d423b13103),
+            (
+                CaptureFormat::Jpeg,
+                95,
+                false,
+                100,
+                None,
+                0x052ab894b2a16bdc,
+            ),
+            (CaptureFormat::Bmp, 95, false, 65, None, 0xb83449c0d1a6efb1),
+            (
+                CaptureFormat::WebpLossless,
+                95,
+                false,
+                65,
+                None,
+                0xefb18d4f4e83becc,
+            ),
+            (
+                CaptureFormat::Jpeg,
+                95,
+                false,
+                65,
+                Some(DualAreaLayout::SideBySide),
+                0x1c46ff315e64ae5e,
+            ),
+            (
+                CaptureFormat::Bmp,
+                95,
+                false,
+                50,
+                Some(DualAreaLayout::Stacked),
+                0x8cdde356a9a880a9,
+            ),
+        ];
+        let (width, height) = (320, 200);
+        for (format, quality, high_fidelity_text, scale, layout, expected) in cases {
+            let case =
+                format!("{format:?} q{quality} hifi={high_fidelity_text} {scale}% {layout:?}");
+            let output_size = compute_scaled_size(width, height, scale, None);
+            let frame = || make_synthetic_frame(width as u32, height as u32, 0);
+            let secondary = || layout.map(|_| make_synthetic_frame(150, 110, 7));
+            let layout = layout.unwrap_or(DualAreaLayout::SideBySide);
+
+            let encode = |image: &RgbImage| {
+                let mut encoded = Vec::new();
+                encode_capture(
+                    &mut encoded,
+                    image,
+                    format,
+                    quality,
+                    high_fidelity_text,
+                    false,
+                )
+                .unwrap();
+                encoded
+            };
+            let after = encode(&scale_capture(
+                frame(),
+                secondary(),
+                output_size,
+                width,
+                layout,
+            ));
+            let before = encode(&scale_before_pipeline(
+                frame(),
+                secondary(),
+                output_size,
+                width,
+                layout,
+            ));
+            assert_eq!(fnv1a64(&after), fnv1a64(&before), "{case}");
+            assert_eq!(fnv1a64(&after), expected, "{case}");
+        }
+    }
+
+    #[test]
+    fn encode_capture_bmp_round_trips_exactly() {
+        for (width, height) in [(1, 1), (3, 2), (7, 5)] {
+            let original = RgbImage::from_fn(width, height, |x, y| {
+                image::Rgb([(x * 40) as u8, (y * 50) as u8, ((x + y) * 20) as u8])
+            });
+            let mut encoded = Vec::new();
+            encode_capture(
+                &mut encoded,
+                &original,
+                CaptureFormat::Bmp,
+                95,
+                false,
+                false,
+            )
+            .unwrap();
+            assert_eq!(
+                encoded.len() as u64,
+                estimate_encoded_size(width, height, CaptureFormat::Bmp, 95, false)
+            );
+            let decoded = image::load_from_memory_with_format(&encoded, image::ImageFormat::Bmp)
+                .unwrap()
+                .to_rgb8();
+            assert_eq!(decoded, original, "{width}x{height}");
+        }
+    }
+
+    /// 白一色の原寸画像を返すテスト用バックエンド（要求された領域と保存サイズを記録する）
+    #[cfg(windows)]
+    #[derive(Default)]
+    struct WhiteBackend {
+        requests: Vec<(RECT, (i32, i32))>,
+    }
+
+    #[cfg(windows)]
+    impl CaptureBackend for WhiteBackend {
+        fn name(&self) -> &'static str {
+            "White"
+        }
+
+        fn capture_rect(
+            &mut self,
+            area: RECT,
+            output_size: (i32, i32),
+        ) -> Result<RgbImage, Box<dyn std::error::Error>> {
+            self.requests.push((area, output_size));
+            let width = (area.right - area.left).unsigned_abs();
+            let height = (area.bottom - area.top).unsigned_abs();
+            Ok(RgbImage::from_pixel(
+                width,
+                height,
+                image::Rgb([255, 255, 255]),
+            ))
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn capture_area_with_masks_then_downscales_backend_image() {
+        // 200x100の領域の左半分をマスクし、50%（100x50）で保存する（BMPは劣化しない）
+        let area = RECT {
+            left: 100,
+            top: 50,
+            right: 300,
+            bottom: 150,
+        };
+        let capturer = Capturer::new(CaptureConfig {
+            format: CaptureFormat::Bmp,
+            scale_percent: 50,
+            mask_areas: vec![RECT {
+                left: 100,
+                top: 50,
+                right: 200,
+                bottom: 150,
+            }],
+            ..CaptureConfig::default()
+        });
+        let mut backend = WhiteBackend::default();
+        let encoded = capturer.capture_area_with(&mut backend, area).unwrap();
+
+        assert_eq!(backend.requests, vec![(area, (100, 50))]);
+        let image = image::load_from_memory_with_format(&encoded, image::ImageFormat::Bmp)
+            .unwrap()
+            .to_rgb8();
+        assert_eq!(image.dimensions(), (100, 50));
+        // マスクは縮小前の原寸座標で適用されるため、縮小後も左半分が黒・右半分が白になる
+        // （境界付近はLanczos3のにじみがあるため、境界から離れた列で確認）
+        for y in [0, 25, 49] {
+            for x in [0, 10, 40] {
+                assert!(
+                    image.get_pixel(x, y).0.iter().all(|&v| v <= 2),
+                    "({x}, {y})"
+                );
+            }
+            for x in [60, 90, 99] {
+                assert!(
+                    image.get_pixel(x, y).0.iter().all(|&v| v >= 253),
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+/*
+============================================================================
+キャプチャ中オーバーレイの配置モジュール (capturing_overlay_layout.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャモード中に表示するオーバーレイ（アイコン + 自動クリックの進行状況ラベル）の
+ウィンドウサイズ・表示位置・ラベルの範囲と配色、キャプチャからの除外方法（表示アフィニティ）を
+まとめたライブラリモジュールです。描画とウィンドウの操作はバイナリ側（`overlay/capturing_overlay.rs`）が行うため、
+Win32 APIには依存しません。
+
+【主要機能】
+1.  **`window_rect`**: 基準点（カーソル位置）とDPI倍率から、オーバーレイウィンドウのスクリーン座標の範囲を求める
+2.  **`label_rect`**: ウィンドウ内のオレンジのラベル（進行状況の背景）のスクリーン座標の範囲を求める
+3.  **`DISPLAY_AFFINITY`**: ウィンドウ作成時に `SetWindowDisplayAffinity` に渡す値（キャプチャから除外）
+
+【技術仕様】
+-   **サイズ**: 230x90（96DPI基準）。アイコン32x32の右下にラベル（左端20px、アイコンの1px下から下端まで）
+-   **表示位置**: 基準点がアイコンの右下の角に来るよう、ウィンドウの左上をアイコンの大きさだけ左上にずらす
+-   **キャプチャからの除外**: 選択エリアの内側のカーソルに追従するため、ウィンドウ全体を
+    `WDA_EXCLUDEFROMCAPTURE` で除外する（キャプチャ直前の非表示はDWMの合成と競合するため回避策のみ）
+
+【AI解析用：依存関係】
+-   `overlay/capturing_overlay.rs`（バイナリ側）: ウィンドウサイズ・表示位置・ラベルの描画範囲と配色、表示アフィニティの設定
+*/
+
+use crate::{auto_click_runner::ScreenPoint, drag_rect::DragRect};
+
+/// 表示アフィニティなし（`WDA_NONE`、通常どおりキャプチャに写る）
+pub const WDA_NONE: u32 = 0x00;
+/// キャプチャから除外する表示アフィニティ（`WDA_EXCLUDEFROMCAPTURE`、Windows 10 2004以降）
+pub const WDA_EXCLUDEFROMCAPTURE: u32 = 0x11;
+
+/// キャプチャ中オーバーレイのウィンドウに設定する表示アフィニティ
+pub const DISPLAY_AFFINITY: u32 = WDA_EXCLUDEFROMCAPTURE;
+
+/// ウィンドウサイズ（96DPI基準）
+/// 幅230px: アイコン32px + テキスト領域198px（自動クリック進行表示用）
+/// 高90px: アイコン32px + テキスト行高58px（マージン込み）
+pub const WINDOW_SIZE: (i32, i32) = (230, 90);
+
+/// アイコンの描画サイズ（96DPI基準、32x32ピクセル）
+pub const ICON_SIZE: i32 = 32;
+
+/// ラベルの左端のオフセット（96DPI基準、ウィンドウの左端から）
+pub const LABEL_OFFSET_X: i32 = 20;
+
+/// ラベルの背景色（ARGB、Burlywood `#DEB887`。ツールチップ背景に近いオレンジ）
+pub const LABEL_BACKGROUND_ARGB: u32 = 0xFFDEB887;
+
+/// 96DPI基準の長さをDPI倍率に合わせたピクセル数に換算する
+pub fn scaled(length: i32, scale: f32) -> i32 {
+    (length as f32 * scale).round() as i32
+}
+
+/// オーバーレイウィンドウのスクリーン座標の範囲
+///
+/// # 引数
+/// * `anchor` - 基準点（キャプチャモード中はカーソル位置。アイコンの右下の角に来る）
+/// * `scale` - DPI倍率（96DPIで1.0）
+pub fn window_rect(anchor: ScreenPoint, scale: f32) -> DragRect {
+    let offset = scaled(ICON_SIZE, scale);
+    let (left, top) = (anchor.0 - offset, anchor.1 - offset);
+    DragRect {
+        left,
+        top,
+        right: left + scaled(WINDOW_SIZE.0, scale),
+        bottom: top + scaled(WINDOW_SIZE.1, scale),
+    }
+}
+
+/// 進行状況ラベル（オレンジの背景）のスクリーン座標の範囲
+///
+/// # 引数
+/// * `anchor` / `scale` - `window_rect` と同じ
+pub fn label_rect(anchor: ScreenPoint, scale: f32) -> DragRect {
+    let window = window_rect(anchor, scale);
+    DragRect {
+        left: window.left + scaled(LABEL_OFFSET_X, scale),
+        top: window.top + scaled(ICON_SIZE + 1, scale),
+        ..window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_follows_cursor_inside_selection() {
+        // (基準点, DPI倍率, ウィンドウ, ラベル)
+        let cases = [
+            ((300, 200), 1.0, (268, 168, 498, 258), (288, 201, 498, 258)),
+            ((300, 200), 1.5, (252, 152, 597, 287), (282, 202, 597, 287)),
+        ];
+        for (anchor, scale, window, label) in cases {
+            let w = window_rect(anchor, scale);
+            let l = label_rect(anchor, scale);
+            assert_eq!((w.left, w.top, w.right, w.bottom), window, "{scale}");
+            assert_eq!((l.left, l.top, l.right, l.bottom), label, "{scale}");
+            // ラベルはカーソルの直下を含むため、カーソルが選択エリア内なら必ず重なる
+            let below = (anchor.0, anchor.1 + 2);
+            assert!((l.left..l.right).contains(&below.0) && (l.top..l.bottom).contains(&below.1));
+        }
+    }
+
+    #[test]
+    fn overlay_window_is_excluded_from_capture() {
+        // ラベルは選択エリアの内側に重なるため、ウィンドウ全体をキャプチャから除外する
+        assert_eq!(DISPLAY_AFFINITY, WDA_EXCLUDEFROMCAPTURE);
+        assert_ne!(DISPLAY_AFFINITY, WDA_NONE);
+        // 除外はウィンドウ単位のため、ラベルがウィンドウからはみ出さないこと
+        for scale in [1.0, 1.25, 1.5, 2.0] {
+            let w = window_rect((300, 200), scale);
+            let l = label_rect((300, 200), scale);
+            assert!(w.left <= l.left && w.top <= l.top, "{scale}");
+            assert!(l.right <= w.right && l.bottom <= w.bottom, "{scale}");
+            assert!(l.width() > 0 && l.height() > 0, "{scale}");
+        }
+    }
+}
@@ -67,6 +67,8 @@ constants.rs ↔ capture_overlay.rc (オーバーレイレイアウト)
 // メインアプリケーションダイアログの識別子
 // DialogBoxParamW()でリソースを読み込む際に使用
 pub const IDD_DIALOG1: u16 = 101;
+// キャプチャ履歴ダイアログ（「履歴」ボタンからモーダル表示）の識別子
+pub const IDD_HISTORY_DIALOG: u16 = 102;
 
 // ===== UIコントロール識別子 =====
 // GetDlgItem()、WM_COMMANDメッセージ処理で使用される一意ID
@@ -97,6 +99,86 @@ pub const IDC_AUTO_CLICK_CHECKBOX: i32 = 1013;
 pub const IDC_AUTO_CLICK_INTERVAL_COMBO: i32 = 1014;
 // 連続クリック回数エディットボックス：自動クリックの回数を指定
 pub const IDC_AUTO_CLICK_COUNT_EDIT: i32 = 1015;
+// サムネイルストリップ：今回のセッションで保存した直近キャプチャのサムネイル一覧（クリックでファイルを開く）
+pub const IDC_THUMBNAIL_STRIP: i32 = 1016;
+// キャプチャトリガーコンボボックス：キャプチャを実行するマウスボタンを選択（左 / 中 / 左+中 / 右）
+pub const IDC_CAPTURE_TRIGGER_COMBO: i32 = 1017;
+// ウィンドウ単体キャプチャチェックボックス：クリック位置のウィンドウをアルファ付きPNGで保存する
+pub const IDC_WINDOW_CAPTURE_CHECKBOX: i32 = 1018;
+// サウンド再生チェックボックス：保存時のシャッター音と自動クリック完了時のチャイムを有効にする
+pub const IDC_SOUND_CHECKBOX: i32 = 1019;
+// 高精細テキストモードチェックボックス：JPEGを4:4:4（色差間引きなし）・フラットな輝度量子化テーブル・プログレッシブで保存する
+pub const IDC_HIGH_FIDELITY_TEXT_CHECKBOX: i32 = 1020;
+// 実入力限定チェックボックス：他ツールが注入したクリック・キー入力をキャプチャトリガーとして扱わない
+pub const IDC_REAL_INPUT_ONLY_CHECKBOX: i32 = 1021;
+// 保存形式コンボボックス：キャプチャ画像の保存形式を選択（JPEG / WebP(可逆) / WebP / AVIF）
+pub const IDC_CAPTURE_FORMAT_COMBO: i32 = 1022;
+// 出力幅エディットボックス：スケール「幅指定」選択時の出力画像幅（ピクセル）を入力する
+pub const IDC_TARGET_WIDTH_EDIT: i32 = 1023;
+// 定期キャプチャ開始/停止ボタン：選択エリアを一定間隔で自動キャプチャする
+pub const IDC_SCHEDULE_BUTTON: i32 = 1024;
+// 定期キャプチャ間隔コンボボックス：キャプチャ間隔（分）を選択
+pub const IDC_SCHEDULE_INTERVAL_COMBO: i32 = 1025;
+// 定期キャプチャ回数エディットボックス：最大キャプチャ回数（0で無制限）を入力
+pub const IDC_SCHEDULE_COUNT_EDIT: i32 = 1026;
+// 2領域結合方向チェックボックス：デュアル選択時に2領域を上下に結合する（OFFで左右）
+pub const IDC_DUAL_AREA_STACKED_CHECKBOX: i32 = 1027;
+// 自動クリック動作モードチェックボックス：クリックを合成せずにキャプチャのみ繰り返す
+pub const IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX: i32 = 1028;
+// GIF変換ボタン：保存フォルダーのキャプチャ画像をアニメーションGIFに変換
+pub const IDC_EXPORT_GIF_BUTTON: i32 = 1029;
+// ZIP圧縮ボタン：保存フォルダーの画像をZIPにまとめる（実行中は中止ボタンとして機能）
+pub const IDC_EXPORT_ZIP_BUTTON: i32 = 1030;
+// 再保存ボタン：保存に失敗したキャプチャ（エンコード済み）を再度保存する
+pub const IDC_RESAVE_BUTTON: i32 = 1031;
+// 撮影情報記録チェックボックス：JPEGに撮影日時・撮影条件のEXIFを埋め込み、PDFに撮影日時のフッターを表示する
+pub const IDC_CAPTURE_METADATA_CHECKBOX: i32 = 1032;
+// 言語コンボボックス：ログ・メッセージ・オーバーレイの表示言語（自動 / 日本語 / English）を切り替える
+pub const IDC_LANGUAGE_COMBO: i32 = 1033;
+// テーマコンボボックス：ボタン・エリア選択オーバーレイの配色（自動 / ライト / ダーク）を切り替える
+pub const IDC_THEME_COMBO: i32 = 1034;
+// 一覧画像ボタン：保存フォルダーのキャプチャ画像をサムネイルの一覧（contactsheet.png）にまとめる
+pub const IDC_EXPORT_CONTACT_SHEET_BUTTON: i32 = 1035;
+// 表示方法コンボボックス：キャプチャオーバーレイ（カーソル追従アイコン）を表示 / 半透明 / 非表示にする
+pub const IDC_OVERLAY_VISIBILITY_COMBO: i32 = 1036;
+// 暗転の濃さコンボボックス：エリア選択中の暗転の不透明度（50〜100%）
+pub const IDC_DIM_OPACITY_COMBO: i32 = 1037;
+// 境界線の色コンボボックス：エリア選択の境界線（とリサイズハンドル）の色（赤 / 緑 / 青 / 黄 / 黒）
+pub const IDC_BORDER_COLOR_COMBO: i32 = 1038;
+// 境界線の太さコンボボックス：エリア選択の境界線の太さ（1〜4px）
+pub const IDC_BORDER_WIDTH_COMBO: i32 = 1039;
+// 縁取りチェックボックス：エリア選択の境界線の両側に白い縁取りを付ける
+pub const IDC_BORDER_HALO_CHECKBOX: i32 = 1040;
+// 画面固定チェックボックス：エリア選択の開始時の画面を静止画として表示し、その上で範囲を選択する
+pub const IDC_FREEZE_SCREEN_CHECKBOX: i32 = 1041;
+// 完了通知チェックボックス：自動クリック・PDF変換の完了を通知領域のバルーンで知らせる
+pub const IDC_COMPLETION_NOTIFY_CHECKBOX: i32 = 1042;
+// 履歴ボタン：今回のセッションで保存したキャプチャの一覧（削除・並べ替え）を表示する
+pub const IDC_HISTORY_BUTTON: i32 = 1043;
+// 履歴リストビュー：キャプチャ履歴ダイアログのファイル一覧（複数選択・ドラッグで並べ替え）
+pub const IDC_HISTORY_LIST: i32 = 1044;
+// 履歴削除ボタン：キャプチャ履歴ダイアログで選択したファイルを削除する
+pub const IDC_HISTORY_DELETE_BUTTON: i32 = 1045;
+// 作業領域ボタン：プライマリモニターのタスクバーを除いた範囲をキャプチャエリアに設定する
+pub const IDC_WORK_AREA_BUTTON: i32 = 1046;
+// 連番整理ボタン：保存フォルダーの連番ファイルを0001から欠番なく振り直す
+pub const IDC_RENUMBER_BUTTON: i32 = 1047;
+// 連番リセットボタン：次のキャプチャを0001から保存する（保存フォルダーが空の場合のみ）
+pub const IDC_RESET_COUNTER_BUTTON: i32 = 1048;
+// プログレッシブJPEGチェックボックス：通常のJPEGをプログレッシブ形式で保存する（progressive-jpeg フィーチャー）
+pub const IDC_PROGRESSIVE_JPEG_CHECKBOX: i32 = 1049;
+// PDF/Aチェックボックス：PDF変換をアーカイブ用のPDF/A-1b形式で出力する
+pub const IDC_PDF_ARCHIVE_CHECKBOX: i32 = 1050;
+// PDF自動変換チェックボックス：キャプチャモード終了時に、保存先フォルダーを自動でPDFに変換する
+pub const IDC_AUTO_PDF_EXPORT_CHECKBOX: i32 = 1051;
+// 共有ボタン：最後に保存したキャプチャをWindowsの共有UI（Teams・メールなど）で送る
+pub const IDC_SHARE_BUTTON: i32 = 1052;
+// 自動クリック最大実行時間コンボボックス：回数の上限に加えて、N分経過したら自動クリックを停止する
+pub const IDC_AUTO_CLICK_DURATION_COMBO: i32 = 1053;
+// 経過時間スタンプチェックボックス：キャプチャ画像の左上に撮影時刻と前回からの経過時間を焼き込む
+pub const IDC_ELAPSED_STAMP_CHECKBOX: i32 = 1054;
+// ユーザー操作中の一時停止チェックボックス：マウスを操作している間は、自動クリックの次のクリックを遅らせる
+pub const IDC_PAUSE_ON_USER_INPUT_CHECKBOX: i32 = 1055;
 
 // ===== アイコンリソース識別子 =====
 // LoadIconW()で.icoファイルを読み込む際の識別子
@@ -123,10 +205,52 @@ pub const IDI_APP_ICON: i32 = 2008;
 pub const IDP_CAPTURE_PROCESSING: i32 = 2009;
 pub const IDP_CAPTURE_WAITING: i32 = 2010;
 
+// 効果音用WAVリソース識別子（RCDATAとして埋め込み）
+// 画像保存時のシャッター音
+pub const IDW_SHUTTER_SOUND: i32 = 2011;
+// 自動クリック完了時のチャイム
+pub const IDW_COMPLETE_SOUND: i32 = 2012;
+
 // ===== カスタムウィンドウメッセージ =====
 // WM_APP (0x8000) 以降はアプリケーション定義メッセージとして使用可能
-// 自動クリック処理完了をメインスレッドに通知する
+// 自動クリック処理完了をメインスレッドに通知する（wparam: 1=安全上限の回数に到達して停止、0=それ以外）
 pub const WM_AUTO_CLICK_COMPLETE: u32 = 0x8000 + 1;
+// 定期キャプチャのキャプチャ要求をメインスレッドに通知する（wparam: 実行回数）
+pub const WM_SCHEDULED_CAPTURE: u32 = 0x8000 + 2;
+// 定期キャプチャの停止条件（最大回数・最大継続時間）到達をメインスレッドに通知する
+pub const WM_SCHEDULED_CAPTURE_COMPLETE: u32 = 0x8000 + 3;
+// 自動クリックの「キャプチャのみ」モードのキャプチャ要求をメインスレッドに通知する
+pub const WM_AUTO_CAPTURE_REQUEST: u32 = 0x8000 + 4;
+// GIF変換（ワーカースレッド）の完了をメインスレッドに通知する（wparam: 1=成功, 0=失敗）
+pub const WM_GIF_EXPORT_COMPLETE: u32 = 0x8000 + 5;
+// ZIP圧縮（ワーカースレッド）の終了をメインスレッドに通知する（wparam: 1=成功, 0=失敗・中止）
+pub const WM_ZIP_EXPORT_COMPLETE: u32 = 0x8000 + 6;
+// ワーカースレッドからのログ出力をメインスレッドに転送する（lparam: Box<String>のポインタ、受信側で解放）
+pub const WM_APP_LOG: u32 = 0x8000 + 7;
+// 一覧画像作成（ワーカースレッド）の完了をメインスレッドに通知する（wparam: 1=成功, 0=失敗）
+pub const WM_CONTACT_SHEET_EXPORT_COMPLETE: u32 = 0x8000 + 8;
+// 完了通知の通知領域アイコンからのコールバック（lparam: NIN_BALLOON* またはマウスメッセージ）
+pub const WM_NOTIFY_ICON: u32 = 0x8000 + 9;
+// キャプチャモード終了時のPDF自動変換を要求する（フックの処理中に変換しないよう、メッセージで後から実行）
+pub const WM_AUTO_PDF_EXPORT: u32 = 0x8000 + 10;
+// ESCキーによる自動クリックの停止の確認ダイアログの表示を要求する（フックの処理中にダイアログを表示しないよう、メッセージで後から実行）
+pub const WM_ESC_STOP_CONFIRM: u32 = 0x8000 + 11;
+
+// ===== タイマー識別子 =====
+// SetTimer()/KillTimer()でメインダイアログに設定するタイマーID（WM_TIMERのwparam）
+//
+// 保存完了トーストの表示時間管理とフェードアウトアニメーション
+pub const TIMER_ID_SAVED_TOAST: usize = 1;
+// 定期キャプチャ中のオーバーレイ経過時間表示の更新（1秒ごと）
+pub const TIMER_ID_CAPTURE_OVERLAY_ELAPSED: usize = 2;
+
+// ===== ホットキー =====
+// RegisterHotKeyでメインダイアログに登録するホットキーのID（WM_HOTKEYのwparam）
+//
+// カーソル下ウィンドウのクイックキャプチャ（既定：Ctrl+Alt+Shift+W、レジストリ QuickWindowCaptureHotkey で変更可）
+pub const HOTKEY_ID_QUICK_WINDOW_CAPTURE: i32 = 1;
+// 前回の領域の再キャプチャ（既定：Ctrl+Alt+Shift+R、レジストリ RepeatLastCaptureHotkey で変更可）
+pub const HOTKEY_ID_REPEAT_LAST_CAPTURE: i32 = 2;
 
 
 /*
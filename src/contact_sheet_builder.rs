@@ -0,0 +1,214 @@
+/*
+============================================================================
+コンタクトシート（一覧画像）構築モジュール (contact_sheet_builder.rs)
+============================================================================
+
+【ファイル概要】
+フォルダ内のキャプチャ画像（JPEG / WebP / BMP）を、PDF変換と同じ収集・並び順で読み込み、
+サムネイルを格子状に並べた1枚のPNG画像（`contactsheet.png`）にまとめるライブラリモジュールです。
+連続キャプチャした画面の全体像を、1枚の画像で素早く確認・共有できるようにします。
+`AppState` やダイアログには依存せず、進捗・警告は `ContactSheetEvent` として呼び出し元のコールバックに通知します。
+
+【主要機能】
+1.  **`export_image_folder_to_contact_sheet`**:
+    -   `pdf_builder::collect_capture_images` で画像をファイル名順に収集します。
+    -   各画像をサムネイル幅（`thumbnail_width`）に縮小し、左上から行優先で `columns` 列に並べます。
+    -   読み込み・デコードに失敗した画像とAVIFはスキップし、1枚も配置できなかった場合のみエラーを返します。
+
+【技術仕様】
+-   **縮小処理**: `image::imageops::resize`（Lanczos3）、縦横比を維持（拡大なし）
+-   **セルの大きさ**: 幅は `thumbnail_width`、高さは縮小後のサムネイルの最大の高さ
+    （縦横比の異なる画像はセル内で中央揃え）
+-   **余白**: セル間と外周に `padding` ピクセルの白い余白
+-   **列数**: 画像が `columns` 枚未満の場合は画像の枚数に合わせて詰める
+-   **出力形式**: PNG（サムネイルの文字が滲まないよう可逆圧縮）
+
+【AI解析用：依存関係】
+-   `pdf_builder.rs`: `collect_capture_images`（画像の収集・並び順をPDF変換と共通化）、`ImageSkipReason`
+-   `export_contact_sheet.rs`（バイナリ側）: `AppState` の設定と、`ContactSheetEvent` を `app_log` に出力するコールバックを渡してワーカースレッドで呼び出し
+-   `image`: デコード・縮小・PNGエンコード
+*/
+
+use image::imageops::FilterType;
+use image::io::Reader as ImageReader;
+use image::{Rgb, RgbImage};
+use std::path::Path;
+
+use crate::pdf_builder::{ImageSkipReason, collect_capture_images};
+
+/// 出力するコンタクトシートのファイル名（変換元フォルダ直下に作成）
+pub const CONTACT_SHEET_FILE_NAME: &str = "contactsheet.png";
+
+/// 背景（余白）の色
+const BACKGROUND_COLOR: Rgb<u8> = Rgb([255, 255, 255]);
+
+/// コンタクトシートの配置設定
+#[derive(Debug, Clone, Copy)]
+pub struct ContactSheetOptions {
+    /// 1行に並べるサムネイルの数（0は1として扱う）
+    pub columns: u32,
+    /// サムネイルの幅（ピクセル、0は1として扱う）
+    pub thumbnail_width: u32,
+    /// セル間と外周の余白（ピクセル）
+    pub padding: u32,
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            columns: 4,
+            thumbnail_width: 320,
+            padding: 8,
+        }
+    }
+}
+
+/// コンタクトシート作成中の出来事（バイナリ側で表示言語に合わせてログに出力する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContactSheetEvent {
+    /// 画像をサムネイルに縮小する（`index` は1から）
+    Thumbnailing {
+        file: String,
+        index: usize,
+        total: usize,
+    },
+    /// 画像をスキップした
+    Skipped {
+        file: String,
+        reason: ImageSkipReason,
+    },
+    /// 一覧画像を保存する
+    Saving {
+        width: u32,
+        height: u32,
+        columns: u32,
+        rows: u32,
+    },
+}
+
+/// フォルダ内のキャプチャ画像をコンタクトシート（格子状の一覧画像）に変換する
+///
+/// `source_dir` 内の画像をファイル名順に読み込み、サムネイルに縮小して
+/// `output_path` にPNGとして保存します。
+///
+/// # 引数
+/// * `source_dir` - 変換元の画像が格納されたフォルダ
+/// * `output_path` - 出力するPNGファイルのパス
+/// * `options` - 列数・サムネイル幅・余白
+/// * `notify` - 進捗・警告の通知先（`ContactSheetEvent`）
+///
+/// # 戻り値
+/// 配置したサムネイルの枚数。
+pub fn export_image_folder_to_contact_sheet(
+    source_dir: &Path,
+    output_path: &Path,
+    options: &ContactSheetOptions,
+    notify: &dyn Fn(ContactSheetEvent),
+) -> Result<usize, Box<dyn std::error::Error>> {
+    if !source_dir.exists() {
+        return Err(format!(
+            "❌ 指定されたフォルダーが存在しません: {}",
+            source_dir.display()
+        )
+        .into());
+    }
+
+    let entries = collect_capture_images(source_dir)?;
+    if entries.is_empty() {
+        return Err("対象の画像ファイルが見つかりませんでした".into());
+    }
+
+    let thumbnail_width = options.thumbnail_width.max(1);
+    let total_files = entries.len();
+
+    // 先に全サムネイルを作成する（セルの高さは最も縦長のサムネイルに合わせるため）
+    let mut thumbnails: Vec<RgbImage> = Vec::with_capacity(total_files);
+    for (index, path) in entries.iter().enumerate() {
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        notify(ContactSheetEvent::Thumbnailing {
+            file: filename.clone(),
+            index: index + 1,
+            total: total_files,
+        });
+
+        // AVIFはデコーダーを同梱していないためスキップ
+        let is_avif = path
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("avif"));
+        let loaded = if is_avif {
+            Err(ImageSkipReason::Avif)
+        } else {
+            match ImageReader::open(path).and_then(|reader| reader.with_guessed_format()) {
+                Ok(reader) => reader
+                    .decode()
+                    .map_err(|e| ImageSkipReason::Decode(e.to_string())),
+                Err(e) => Err(ImageSkipReason::Read(e.to_string())),
+            }
+        };
+        let img = match loaded {
+            Ok(img) => img,
+            Err(reason) => {
+                notify(ContactSheetEvent::Skipped {
+                    file: filename,
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        let (width, height) = fit_to_width(img.width(), img.height(), thumbnail_width);
+        let rgb = img.to_rgb8();
+        thumbnails.push(if (rgb.width(), rgb.height()) == (width, height) {
+            rgb
+        } else {
+            image::imageops::resize(&rgb, width, height, FilterType::Lanczos3)
+        });
+    }
+
+    if thumbnails.is_empty() {
+        return Err("一覧画像に配置できる画像がありませんでした".into());
+    }
+
+    // 格子のサイズを決定（画像が列数未満の場合は列を詰める）
+    let count = thumbnails.len() as u32;
+    let columns = options.columns.max(1).min(count);
+    let rows = count.div_ceil(columns);
+    let padding = options.padding;
+    let cell_height = thumbnails.iter().map(|t| t.height()).max().unwrap_or(1);
+    let sheet_width = columns * thumbnail_width + (columns + 1) * padding;
+    let sheet_height = rows * cell_height + (rows + 1) * padding;
+
+    let mut sheet = RgbImage::from_pixel(sheet_width, sheet_height, BACKGROUND_COLOR);
+    for (index, thumbnail) in thumbnails.iter().enumerate() {
+        let column = index as u32 % columns;
+        let row = index as u32 / columns;
+        // セル内で中央揃え
+        let x = padding
+            + column * (thumbnail_width + padding)
+            + (thumbnail_width - thumbnail.width()) / 2;
+        let y = padding + row * (cell_height + padding) + (cell_height - thumbnail.height()) / 2;
+        image::imageops::overlay(&mut sheet, thumbnail, x as i64, y as i64);
+    }
+
+    notify(ContactSheetEvent::Saving {
+        width: sheet_width,
+        height: sheet_height,
+        columns,
+        rows,
+    });
+    sheet.save_with_format(output_path, image::ImageFormat::Png)?;
+
+    Ok(thumbnails.len())
+}
+
+/// 縦横比を保ったまま、幅が `thumbnail_width` 以下になるサイズを計算する（拡大なし、最小1px）
+fn fit_to_width(width: u32, height: u32, thumbnail_width: u32) -> (u32, u32) {
+    if width <= thumbnail_width {
+        return (width.max(1), height.max(1));
+    }
+    let scaled_height = (height as u64 * thumbnail_width as u64 / width.max(1) as u64) as u32;
+    (thumbnail_width, scaled_height.max(1))
+}
@@ -0,0 +1,265 @@
+/*
+============================================================================
+DIBピクセル変換モジュール (dib.rs)
+============================================================================
+
+【ファイル概要】
+GDI（`GetDIBits`）・DXGI Desktop Duplication・`PrintWindow` で取得したBGR/BGRAのピクセルデータを、
+`image` クレートの `RgbImage` / `RgbaImage` に変換するライブラリモジュールです。
+バイト列の並べ替えのみを行い、Win32 APIには依存しません（取得はバイナリ側・`capture_backend.rs` が行う）。
+
+【主要機能】
+1.  **`bgr_dib_to_rgb_image`**: 24bpp DIB（BGR、4バイト境界パディング付き）を `RgbImage` に変換（行単位でパディングを除去）
+2.  **`dib_row_size`**: 24bpp DIBの1行あたりのバイト数（4バイト境界）
+3.  **`bgra_rows_to_rgb_image`**: 行ピッチ付きのBGRAバッファ（Desktop Duplicationのステージングテクスチャ）から範囲を切り出して `RgbImage` に変換
+4.  **`premultiplied_bgra_to_rgba_image`**: 乗算済みアルファのBGRA（`PrintWindow` の32bpp DIB）をストレートアルファの `RgbaImage` に変換
+
+【技術仕様】
+-   **行の向き**: いずれもトップダウン（1行目が画像の上端）
+-   **不足データ**: 24bpp DIBのデータが `height` 行分に満たない場合、不足した行は黒のまま（パニックしない）
+
+【AI解析用：依存関係】
+-   `capture_backend.rs`: GDI / Desktop Duplicationで取得したピクセルの変換
+-   `capture_pipeline.rs`, `thumbnail_strip_handler.rs`, `window_capture.rs`（バイナリ側）: DIBの変換・行サイズの計算
+*/
+
+use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+/// `GetDIBits` で取得した24bpp（BGR、4バイト境界パディング付き）のピクセルデータを `RgbImage` に変換する
+///
+/// 各行は `width * 3` バイトのピクセルと、4バイト境界までのパディング（0〜3バイト）で構成されます。
+/// 行単位でパディングを除いてから変換するため、幅が4の倍数でない場合も最終列まで変換されます。
+/// データが `height` 行分に満たない場合、不足した行は黒のままになります。
+///
+/// # 引数
+/// * `pixel_data` - トップダウン形式のDIBピクセルデータ
+/// * `width` - 画像の幅（ピクセル）
+/// * `height` - 画像の高さ（ピクセル）
+pub fn bgr_dib_to_rgb_image(pixel_data: &[u8], width: i32, height: i32) -> RgbImage {
+    let mut img_buffer = RgbImage::new(width.max(0) as u32, height.max(0) as u32);
+    let pixel_bytes = img_buffer.width() as usize * 3;
+    if pixel_bytes == 0 {
+        return img_buffer;
+    }
+    let row_size = dib_row_size(img_buffer.width());
+
+    // Windows GDI はBGR順なのでRGB順に変換（出力バッファはパディングなしのRGB）
+    for (src_row, dst_row) in pixel_data
+        .chunks(row_size)
+        .zip(img_buffer.chunks_exact_mut(pixel_bytes))
+    {
+        let Some(src_pixels) = src_row.get(..pixel_bytes) else {
+            break; // 最終行のデータが不足している場合
+        };
+        for (src, dst) in src_pixels.chunks_exact(3).zip(dst_row.chunks_exact_mut(3)) {
+            dst[0] = src[2];
+            dst[1] = src[1];
+            dst[2] = src[0];
+        }
+    }
+    img_buffer
+}
+
+/// 24bpp DIBの1行あたりのバイト数（`width * 3` を4バイト境界に切り上げ）
+pub fn dib_row_size(width: u32) -> usize {
+    (width as usize * 3).div_ceil(4) * 4
+}
+
+/// 行ピッチ付きのBGRA（32bpp）バッファから、指定した範囲を切り出して `RgbImage` に変換する
+///
+/// DXGI Desktop Duplicationのステージングテクスチャ（`D3D11_MAPPED_SUBRESOURCE`）のように、
+/// 1行のバイト数（`row_pitch`）が `幅 * 4` より大きいバッファを想定しています。
+///
+/// # 引数
+/// * `data` - トップダウン形式のBGRAピクセルデータ（`row_pitch * 行数` バイト）
+/// * `row_pitch` - 1行あたりのバイト数
+/// * `offset` - 切り出す範囲の左上（バッファ内のピクセル座標）
+/// * `width` / `height` - 切り出す範囲の大きさ（ピクセル）
+///
+/// # パニック
+/// 切り出す範囲が `data` の外にはみ出す場合（呼び出し側でモニター内に収めてから呼び出す）
+pub fn bgra_rows_to_rgb_image(
+    data: &[u8],
+    row_pitch: usize,
+    offset: (usize, usize),
+    width: u32,
+    height: u32,
+) -> RgbImage {
+    let (offset_x, offset_y) = offset;
+    let mut image = RgbImage::new(width, height);
+    for (y, row) in image.rows_mut().enumerate() {
+        let start = (offset_y + y) * row_pitch + offset_x * 4;
+        let source_row = &data[start..start + width as usize * 4];
+        for (pixel, bgra) in row.zip(source_row.chunks_exact(4)) {
+            *pixel = Rgb([bgra[2], bgra[1], bgra[0]]);
+        }
+    }
+    image
+}
+
+/// `PrintWindow` で取得した乗算済みBGRAピクセルを、ストレートアルファのRGBA画像に変換する
+///
+/// アルファ非対応の描画経路（従来のGDI描画など）ではアルファが全て0になるため、
+/// その場合は全画素を不透明（255）として扱います。
+pub fn premultiplied_bgra_to_rgba_image(bgra_pixels: &[u8], width: u32, height: u32) -> RgbaImage {
+    let has_alpha = bgra_pixels.chunks_exact(4).any(|pixel| pixel[3] != 0);
+
+    let mut img_buffer = RgbaImage::new(width, height);
+    for (pixel, bgra) in img_buffer.pixels_mut().zip(bgra_pixels.chunks_exact(4)) {
+        let (b, g, r) = (bgra[0], bgra[1], bgra[2]);
+
+        if !has_alpha {
+            *pixel = Rgba([r, g, b, 255]);
+            continue;
+        }
+
+        let a = bgra[3];
+        *pixel = match a {
+            0 => Rgba([0, 0, 0, 0]),
+            255 => Rgba([r, g, b, 255]),
+            // 乗算済みアルファを元に戻す（色成分 = 色成分 × 255 / アルファ）
+            _ => {
+                let unpremultiply =
+                    |c: u8| ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8;
+                Rgba([unpremultiply(r), unpremultiply(g), unpremultiply(b), a])
+            }
+        };
+    }
+    img_buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用の24bpp DIB（トップダウン、BGR）を作成する（パディングは変換に混ざらないよう 0xEE で埋める）
+    fn make_dib(width: u32, height: u32) -> Vec<u8> {
+        let row_size = dib_row_size(width);
+        let mut data = vec![0xEE; row_size * height as usize];
+        for y in 0..height {
+            for x in 0..width {
+                let offset = y as usize * row_size + x as usize * 3;
+                let [r, g, b] = expected_pixel(x, y);
+                data[offset..offset + 3].copy_from_slice(&[b, g, r]);
+            }
+        }
+        data
+    }
+
+    /// `make_dib` が (x, y) に書き込むRGB値
+    fn expected_pixel(x: u32, y: u32) -> [u8; 3] {
+        [(x % 251) as u8, (y % 241) as u8, ((x + y) % 239) as u8]
+    }
+
+    #[test]
+    fn dib_row_size_pads_to_four_bytes() {
+        for (width, expected) in [
+            (0, 0),
+            (1, 4),
+            (2, 8),
+            (3, 12),
+            (4, 12),
+            (7, 24),
+            (1920, 5760),
+        ] {
+            assert_eq!(dib_row_size(width), expected, "width {width}");
+        }
+    }
+
+    #[test]
+    fn bgr_dib_to_rgb_image_skips_row_padding() {
+        for (width, height) in [(1, 3), (3, 2), (7, 5), (1920, 2)] {
+            let image = bgr_dib_to_rgb_image(&make_dib(width, height), width as i32, height as i32);
+            assert_eq!(image.dimensions(), (width, height));
+            for (x, y, pixel) in image.enumerate_pixels() {
+                assert_eq!(pixel.0, expected_pixel(x, y), "width {width} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn bgr_dib_to_rgb_image_leaves_missing_rows_black() {
+        for (width, height, rows) in [(1, 4, 2), (3, 3, 1), (7, 2, 0), (1920, 3, 2)] {
+            let mut data = make_dib(width, height);
+            data.truncate(dib_row_size(width) * rows);
+            let image = bgr_dib_to_rgb_image(&data, width as i32, height as i32);
+            assert_eq!(image.dimensions(), (width, height));
+            for (x, y, pixel) in image.enumerate_pixels() {
+                let expected = if y < rows as u32 {
+                    expected_pixel(x, y)
+                } else {
+                    [0, 0, 0]
+                };
+                assert_eq!(pixel.0, expected, "width {width} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn bgr_dib_to_rgb_image_converts_partial_last_row_as_black() {
+        // 最終行のパディングが欠けていてもピクセル分があれば変換し、ピクセルの途中で切れた行は黒のまま
+        let width = 7;
+        let data = make_dib(width, 2);
+        let row_size = dib_row_size(width);
+        let image = bgr_dib_to_rgb_image(&data[..row_size + width as usize * 3], 7, 2);
+        assert_eq!(image.get_pixel(6, 1).0, expected_pixel(6, 1));
+        let image = bgr_dib_to_rgb_image(&data[..row_size + 4], 7, 2);
+        assert_eq!(image.get_pixel(0, 1).0, [0, 0, 0]);
+        assert_eq!(image.get_pixel(0, 0).0, expected_pixel(0, 0));
+    }
+
+    #[test]
+    fn bgr_dib_to_rgb_image_handles_empty_sizes() {
+        for (width, height) in [(0, 0), (0, 5), (5, 0), (-1, 3), (3, -1)] {
+            let image = bgr_dib_to_rgb_image(&[], width, height);
+            assert_eq!(
+                image.dimensions(),
+                (width.max(0) as u32, height.max(0) as u32)
+            );
+        }
+    }
+
+    #[test]
+    fn bgra_rows_to_rgb_image_crops_with_row_pitch() {
+        // 4x3のBGRA（行ピッチ20バイト＝1ピクセル分の余白付き）から (1, 1) を左上に 2x2 を切り出す
+        let row_pitch = 20;
+        let mut data = vec![0xEE; row_pitch * 3];
+        for y in 0..3 {
+            for x in 0..4 {
+                let offset = y * row_pitch + x * 4;
+                data[offset..offset + 4].copy_from_slice(&[x as u8, y as u8, 100, 0]);
+            }
+        }
+        let image = bgra_rows_to_rgb_image(&data, row_pitch, (1, 1), 2, 2);
+        assert_eq!(image.dimensions(), (2, 2));
+        for (x, y, pixel) in image.enumerate_pixels() {
+            assert_eq!(pixel.0, [100, y as u8 + 1, x as u8 + 1], "({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn premultiplied_bgra_to_rgba_image_table() {
+        // (BGRA, 期待するRGBA)
+        let cases = [
+            ([10, 20, 30, 255], [30, 20, 10, 255]),
+            ([0, 0, 0, 0], [0, 0, 0, 0]),
+            ([64, 64, 128, 128], [255, 128, 128, 128]),
+            ([1, 2, 3, 4], [191, 128, 64, 4]),
+            // 不正な乗算済みの値（色成分 > アルファ）は255で頭打ち
+            ([200, 200, 200, 100], [255, 255, 255, 100]),
+        ];
+        let data: Vec<u8> = cases.iter().flat_map(|(bgra, _)| *bgra).collect();
+        let image = premultiplied_bgra_to_rgba_image(&data, cases.len() as u32, 1);
+        for (x, (_, expected)) in cases.iter().enumerate() {
+            assert_eq!(image.get_pixel(x as u32, 0).0, *expected, "pixel {x}");
+        }
+    }
+
+    #[test]
+    fn premultiplied_bgra_without_alpha_is_opaque() {
+        // アルファが全て0（アルファ非対応の描画経路）の場合は不透明として色をそのまま使う
+        let image = premultiplied_bgra_to_rgba_image(&[10, 20, 30, 0, 40, 50, 60, 0], 2, 1);
+        assert_eq!(image.get_pixel(0, 0).0, [30, 20, 10, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, [60, 50, 40, 255]);
+    }
+}
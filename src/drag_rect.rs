@@ -0,0 +1,122 @@
+/*
+============================================================================
+ドラッグ矩形モジュール (drag_rect.rs)
+============================================================================
+
+【ファイル概要】
+エリア選択・マスク領域・2つ目の領域のドラッグで、開始点と終了点から矩形を求める
+ライブラリモジュールです。Win32 APIに依存しない整数座標の計算のみを行い、
+呼び出し側（バイナリの `area_select.rs`）で `RECT` に変換して使用します。
+
+【主要機能】
+1.  **`DragRect::from_corners`**: ドラッグの向き（右下→左上など）に関わらず、左上・右下に正規化
+2.  **`DragRect::is_click`**: 幅または高さが最小サイズ未満か（ドラッグせずにクリックしたか）を判定
+
+【技術仕様】
+-   **座標系**: 仮想スクリーン座標（マルチモニターで負の値もそのまま扱う）
+-   **右端・下端**: `right` / `bottom` は `RECT` と同じく幅・高さの計算に使用（`right - left`）
+
+【AI解析用：依存関係】
+-   `area_select.rs`（バイナリ側）: `end_area_select_mode` / `end_mask_drag` で選択領域・マスク領域を確定
+*/
+
+/// ドラッグの開始点と終了点から求めた、正規化済みの矩形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DragRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl DragRect {
+    /// 2つの頂点（ドラッグの開始点・終了点）から、左上・右下に正規化した矩形を作成する
+    pub fn from_corners(start: (i32, i32), end: (i32, i32)) -> Self {
+        Self {
+            left: start.0.min(end.0),
+            top: start.1.min(end.1),
+            right: start.0.max(end.0),
+            bottom: start.1.max(end.1),
+        }
+    }
+
+    /// 矩形の幅（ピクセル）
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    /// 矩形の高さ（ピクセル）
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+
+    /// 幅または高さが `min_size` 未満か（ドラッグせずにクリックした場合）
+    ///
+    /// Ctrl/Shift+クリックによる解除と、ドラッグによる追加の区別に使用します。
+    pub fn is_click(&self, min_size: i32) -> bool {
+        self.width() < min_size || self.height() < min_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_corners_normalizes_any_drag_direction() {
+        let expected = DragRect {
+            left: 10,
+            top: 20,
+            right: 110,
+            bottom: 70,
+        };
+        // 右下・左上・右上・左下へのドラッグ
+        for (start, end) in [
+            ((10, 20), (110, 70)),
+            ((110, 70), (10, 20)),
+            ((10, 70), (110, 20)),
+            ((110, 20), (10, 70)),
+        ] {
+            let rect = DragRect::from_corners(start, end);
+            assert_eq!(rect, expected, "{start:?} -> {end:?}");
+            assert_eq!((rect.width(), rect.height()), (100, 50));
+        }
+    }
+
+    #[test]
+    fn from_corners_keeps_negative_virtual_screen_coordinates() {
+        // プライマリモニターの左・上にあるモニター上のドラッグ
+        let rect = DragRect::from_corners((-1920, 300), (-100, -200));
+        assert_eq!(
+            rect,
+            DragRect {
+                left: -1920,
+                top: -200,
+                right: -100,
+                bottom: 300,
+            }
+        );
+        assert_eq!((rect.width(), rect.height()), (1820, 500));
+    }
+
+    #[test]
+    fn is_click_table() {
+        // (開始点, 終了点, 最小サイズ, 期待値)
+        let cases = [
+            ((0, 0), (0, 0), 5, true),
+            ((0, 0), (4, 100), 5, true),
+            ((0, 0), (100, 4), 5, true),
+            ((0, 0), (5, 5), 5, false),
+            ((5, 5), (0, 0), 5, false),
+            ((0, 0), (1, 1), 1, false),
+            ((0, 0), (100, 100), 0, false),
+        ];
+        for (start, end, min_size, expected) in cases {
+            assert_eq!(
+                DragRect::from_corners(start, end).is_click(min_size),
+                expected,
+                "{start:?} -> {end:?} (min {min_size})"
+            );
+        }
+    }
+}
@@ -0,0 +1,182 @@
+/*
+============================================================================
+エラー型定義モジュール (error.rs)
+============================================================================
+
+【ファイル概要】
+画面キャプチャ処理と埋め込みリソース読み込み処理のエラーを、
+`Box<dyn Error>` や文字列ではなく列挙型で表現するモジュール。
+呼び出し元はバリアントで分岐し、処理を中断するか警告のみに留めるかを判断できます。
+
+【主要機能】
+1.  **`CaptureError`**: `capture_screen_area_with_counter` のエラー
+    -   `AreaNotSelected`: キャプチャエリア未選択
+    -   `GdiFailure`: GDI API（`GetDIBits` など）の失敗
+    -   `Overlay`: キャプチャアイコンの再表示失敗
+    -   `Io`: 保存先フォルダ・ファイルの作成失敗（ディスク容量不足、アクセス拒否など）
+    -   `Encode`: 画像エンコードの失敗
+    -   `DiskFull`: 保存先の空き容量不足（書き込み前に検出し、書きかけのファイルを残さない）
+    -   `should_stop_auto_click`: 自動クリックを継続しても回復しないエラーかを判定
+2.  **`ResourceError`**: `load_png_from_resource` のエラー（失敗したWin32 / GDI+ APIごとのバリアント）
+
+【技術仕様】
+-   `std::fmt::Display` でログ表示用の日本語メッセージを提供
+-   `std::error::Error::source` で元のエラー（`std::io::Error`, `windows::core::Error` など）を参照可能
+-   `From<std::io::Error>` を実装し、`?` 演算子でI/Oエラーを `CaptureError::Io` に変換
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`: `CaptureError` を返却
+-   `hook/mouse.rs`: `CaptureError::should_stop_auto_click` で自動クリックの停止を判断
+-   `overlay/capturing_overlay.rs`: `ResourceError` を返却
+-   `strings.rs`: `DiskFull` の容量表示（`byte_size_label`）
+ */
+
+use std::{error::Error, fmt};
+
+use windows::Win32::Graphics::GdiPlus::Status;
+
+use crate::strings;
+
+/// 画面キャプチャ処理（`capture_screen_area_with_counter`）のエラー
+#[derive(Debug)]
+pub enum CaptureError {
+    /// キャプチャエリアが選択されていない
+    AreaNotSelected,
+    /// GDI APIの呼び出しに失敗した（失敗したAPI名）
+    GdiFailure(&'static str),
+    /// キャプチャアイコン（オーバーレイ）の再表示に失敗した
+    Overlay(windows::core::Error),
+    /// 保存先フォルダ・ファイルの作成や書き込みに失敗した
+    Io(std::io::Error),
+    /// 画像のエンコードに失敗した
+    Encode(Box<dyn Error>),
+    /// 保存先の空き容量が不足している（保存に必要なバイト数、空き容量）
+    DiskFull { required: u64, available: u64 },
+}
+
+impl CaptureError {
+    /// 自動クリックを継続しても回復しないエラーかを判定する
+    ///
+    /// エリア未選択・保存先の書き込み失敗・エンコード失敗は次のクリックでも同じ結果になるため、
+    /// 自動クリックを停止すべきと判断します。GDI・オーバーレイの失敗は一時的なものとして扱います。
+    pub fn should_stop_auto_click(&self) -> bool {
+        match self {
+            CaptureError::AreaNotSelected
+            | CaptureError::Io(_)
+            | CaptureError::Encode(_)
+            | CaptureError::DiskFull { .. } => true,
+            CaptureError::GdiFailure(_) | CaptureError::Overlay(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::AreaNotSelected => write!(f, "キャプチャエリアが選択されていません"),
+            CaptureError::GdiFailure(api) => {
+                write!(f, "ビットマップデータの取得に失敗 ({})", api)
+            }
+            CaptureError::Overlay(e) => write!(f, "キャプチャアイコンの再表示に失敗: {}", e),
+            CaptureError::Io(e) => write!(f, "ファイルの保存に失敗: {}", e),
+            CaptureError::Encode(e) => write!(f, "画像のエンコードに失敗: {}", e),
+            CaptureError::DiskFull {
+                required,
+                available,
+            } => write!(
+                f,
+                "保存先の空き容量が不足しています（必要 {} / 空き {}）",
+                strings::byte_size_label(*required),
+                strings::byte_size_label(*available)
+            ),
+        }
+    }
+}
+
+impl Error for CaptureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CaptureError::Overlay(e) => Some(e),
+            CaptureError::Io(e) => Some(e),
+            CaptureError::Encode(e) => Some(e.as_ref()),
+            CaptureError::AreaNotSelected
+            | CaptureError::GdiFailure(_)
+            | CaptureError::DiskFull { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CaptureError {
+    fn from(e: std::io::Error) -> Self {
+        CaptureError::Io(e)
+    }
+}
+
+/// 埋め込みリソース読み込み処理（`load_png_from_resource`）のエラー
+#[derive(Debug)]
+pub enum ResourceError {
+    /// モジュールハンドルの取得に失敗した (GetModuleHandleW)
+    ModuleHandle(windows::core::Error),
+    /// リソースが見つからない (FindResourceW)
+    NotFound,
+    /// リソースのロードに失敗した (LoadResource)
+    Load(windows::core::Error),
+    /// リソースデータへのポインタを取得できない (LockResource)
+    Lock,
+    /// リソースサイズが0 (SizeofResource)
+    Empty,
+    /// メモリストリームの作成に失敗した (SHCreateMemStream)
+    StreamCreation,
+    /// ストリームからのビットマップ作成に失敗した (GdipCreateBitmapFromStream)
+    BitmapCreation(Status),
+    /// ビットマップ作成は成功したが、ポインタがnull
+    NullBitmap,
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceError::ModuleHandle(e) => {
+                write!(
+                    f,
+                    "モジュールハンドルの取得に失敗しました (GetModuleHandleW): {}",
+                    e
+                )
+            }
+            ResourceError::NotFound => write!(f, "リソースの検索に失敗しました (FindResourceW)"),
+            ResourceError::Load(e) => {
+                write!(f, "リソースのロードに失敗しました (LoadResource): {}", e)
+            }
+            ResourceError::Lock => {
+                write!(f, "リソースポインタの取得に失敗しました (LockResource)")
+            }
+            ResourceError::Empty => write!(f, "リソースサイズが0です (SizeofResource)"),
+            ResourceError::StreamCreation => {
+                write!(
+                    f,
+                    "メモリストリームの作成に失敗しました (SHCreateMemStream)"
+                )
+            }
+            ResourceError::BitmapCreation(status) => write!(
+                f,
+                "ストリームからのビットマップ作成に失敗しました (GdipCreateBitmapFromStream): {:?}",
+                status
+            ),
+            ResourceError::NullBitmap => {
+                write!(
+                    f,
+                    "ビットマップは正常に作成されましたが、ポインタがnullです"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ResourceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ResourceError::ModuleHandle(e) | ResourceError::Load(e) => Some(e),
+            _ => None,
+        }
+    }
+}
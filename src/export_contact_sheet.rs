@@ -0,0 +1,123 @@
+/*
+============================================================================
+キャプチャ画像から一覧画像（コンタクトシート）への変換モジュール (export_contact_sheet.rs)
+============================================================================
+
+【ファイル概要】
+「一覧PNG」ボタンから呼び出され、`AppState` に設定された変換元フォルダ・列数・サムネイル幅を解決して、
+ライブラリ側の `export_image_folder_to_contact_sheet` をワーカースレッドで実行します。
+全画像のデコード・縮小には時間がかかるため、GIF変換と同様にUIスレッドをブロックしません。
+
+【主要機能】
+1.  **設定の解決**:
+    -   変換元は `pdf_source_dir`（未設定の場合は `selected_folder_path`）、出力先は変換元フォルダの `contactsheet.png` です。
+    -   列数は `contact_sheet_columns`、サムネイル幅は `contact_sheet_thumbnail_width` を使用します。
+2.  **ワーカースレッドでの変換**:
+    -   進捗・警告は `app_log` でログに出力します。
+    -   完了時に `WM_CONTACT_SHEET_EXPORT_COMPLETE`（wparam: 1=成功, 0=失敗）をメインダイアログに送信します。
+
+【AI解析用：依存関係】
+- `app_state.rs`: 保存先フォルダパス、一覧画像の設定を取得。
+- `export_gif.rs`: `get_gif_source_dir`（変換元フォルダの解決をGIF変換と共通化）。
+- `system_utils.rs`: `app_log` を使用して処理の進捗をログに出力。
+- `contact_sheet_builder.rs`（ライブラリ）: 一覧画像作成処理の本体。
+- `ui/contact_sheet_button_handler.rs`: 呼び出し元、`WM_CONTACT_SHEET_EXPORT_COMPLETE` の受信処理。
+*/
+
+use crate::app_state::*;
+use crate::constants::WM_CONTACT_SHEET_EXPORT_COMPLETE;
+use crate::export_gif::get_gif_source_dir;
+use crate::strings;
+use crate::system_utils::app_log;
+use clickcapture::contact_sheet_builder::{
+    CONTACT_SHEET_FILE_NAME, ContactSheetEvent, ContactSheetOptions,
+    export_image_folder_to_contact_sheet,
+};
+use std::path::PathBuf;
+use std::thread;
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+
+/// 選択されたフォルダ内のキャプチャ画像を一覧画像にまとめる（ワーカースレッド）
+///
+/// # 戻り値
+/// * `true` - ワーカースレッドを起動した場合（完了は `WM_CONTACT_SHEET_EXPORT_COMPLETE` で通知）。
+/// * `false` - 変換元フォルダが未選択のため起動しなかった場合。
+pub fn start_contact_sheet_export() -> bool {
+    let Some(folder) = get_gif_source_dir() else {
+        app_log(strings::contact_sheet_no_folder());
+        return false;
+    };
+
+    let app_state = AppState::get_app_state_ref();
+    let options = ContactSheetOptions {
+        columns: app_state.contact_sheet_columns,
+        thumbnail_width: app_state.contact_sheet_thumbnail_width,
+        ..Default::default()
+    };
+
+    let source_dir = PathBuf::from(&folder);
+    let output_path = source_dir.join(CONTACT_SHEET_FILE_NAME);
+
+    println!(
+        "一覧画像作成開始: 変換元フォルダー = {}, 列数 = {}, サムネイル幅 = {}px",
+        folder, options.columns, options.thumbnail_width
+    );
+
+    thread::spawn(move || {
+        let result = export_image_folder_to_contact_sheet(
+            &source_dir,
+            &output_path,
+            &options,
+            &log_contact_sheet_event,
+        );
+
+        let success = match result {
+            Ok(image_count) => {
+                app_log(&strings::contact_sheet_export_completed(
+                    &output_path,
+                    image_count,
+                ));
+                true
+            }
+            Err(e) => {
+                app_log(&strings::contact_sheet_export_failed(&e));
+                false
+            }
+        };
+
+        // メインスレッドに完了を非同期で通知する
+        if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+            unsafe {
+                if let Err(e) = PostMessageW(
+                    Some(*hwnd),
+                    WM_CONTACT_SHEET_EXPORT_COMPLETE,
+                    WPARAM(success as usize),
+                    LPARAM(0),
+                ) {
+                    app_log(&strings::post_message_failed(&e));
+                }
+            }
+        }
+    });
+
+    true
+}
+
+/// ライブラリからの一覧画像作成の進捗・警告を、表示言語に合わせてログに出力する
+fn log_contact_sheet_event(event: ContactSheetEvent) {
+    match event {
+        ContactSheetEvent::Thumbnailing { file, index, total } => {
+            app_log(&strings::contact_sheet_thumbnailing(&file, index, total))
+        }
+        ContactSheetEvent::Skipped { file, reason } => {
+            app_log(&strings::contact_sheet_image_skipped(&file, &reason))
+        }
+        ContactSheetEvent::Saving {
+            width,
+            height,
+            columns,
+            rows,
+        } => app_log(&strings::contact_sheet_saving(width, height, columns, rows)),
+    }
+}
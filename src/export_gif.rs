@@ -0,0 +1,148 @@
+/*
+============================================================================
+キャプチャ画像からアニメーションGIFへの変換モジュール (export_gif.rs)
+============================================================================
+
+【ファイル概要】
+「GIFに変換」ボタンから呼び出され、`AppState` に設定された変換元フォルダ・最大幅・
+フレーム間隔を解決して、ライブラリ側の `export_image_folder_to_gif` をワーカースレッドで実行します。
+GIFの減色処理は時間がかかるため、PDF変換と異なりUIスレッドをブロックしません。
+
+【主要機能】
+1.  **設定の解決**:
+    -   変換元は `pdf_source_dir`（未設定の場合は `selected_folder_path`）、出力先は変換元フォルダの `animation.gif` です。
+    -   フレーム間隔は `gif_frame_delay_ms`、0の場合は自動クリックの間隔を使用します。
+2.  **ワーカースレッドでの変換**:
+    -   進捗・警告は `app_log` でログに出力します。
+    -   完了時に `WM_GIF_EXPORT_COMPLETE`（wparam: 1=成功, 0=失敗）をメインダイアログに送信します。
+
+【処理フロー】
+1.  `start_gif_export` が呼び出されます（`is_exporting_to_gif` は呼び出し元で設定済み）。
+2.  `AppState` から変換元フォルダと設定値を取得し、ワーカースレッドを起動します。
+3.  ワーカースレッドで変換を実行し、結果をログに出力してメインスレッドに完了を通知します。
+
+【AI解析用：依存関係】
+- `app_state.rs`: 保存先フォルダパス、GIF変換設定、自動クリック間隔を取得。
+- `system_utils.rs`: `app_log` を使用して処理の進捗をログに出力。
+- `gif_builder.rs`（ライブラリ）: GIF変換処理の本体。
+- `ui/gif_export_button_handler.rs`: 呼び出し元、`WM_GIF_EXPORT_COMPLETE` の受信処理。
+*/
+
+use crate::app_state::*;
+use crate::constants::WM_GIF_EXPORT_COMPLETE;
+use crate::strings;
+use crate::system_utils::app_log;
+use clickcapture::gif_builder::{GifExportEvent, export_image_folder_to_gif};
+use std::path::PathBuf;
+use std::thread;
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+
+/// 出力するGIFファイル名（変換元フォルダ直下に作成）
+const GIF_OUTPUT_FILE_NAME: &str = "animation.gif";
+
+/// GIF変換の変換元フォルダを取得する
+///
+/// `pdf_source_dir` が設定されていればそれを、未設定の場合は `selected_folder_path` を返します。
+pub fn get_gif_source_dir() -> Option<String> {
+    let app_state = AppState::get_app_state_ref();
+    app_state
+        .pdf_source_dir
+        .as_ref()
+        .or(app_state.selected_folder_path.as_ref())
+        .cloned()
+}
+
+/// 選択されたフォルダ内のキャプチャ画像をアニメーションGIFに変換する（ワーカースレッド）
+///
+/// # 戻り値
+/// * `true` - ワーカースレッドを起動した場合（完了は `WM_GIF_EXPORT_COMPLETE` で通知）。
+/// * `false` - 変換元フォルダが未選択のため起動しなかった場合。
+pub fn start_gif_export() -> bool {
+    let Some(folder) = get_gif_source_dir() else {
+        app_log(strings::gif_no_folder());
+        return false;
+    };
+
+    let app_state = AppState::get_app_state_ref();
+    let max_width = app_state.gif_max_width;
+    let frame_delay_ms = match app_state.gif_frame_delay_ms {
+        0 => app_state.auto_clicker.get_interval() as u32,
+        delay_ms => delay_ms,
+    };
+
+    let source_dir = PathBuf::from(&folder);
+    let output_path = source_dir.join(GIF_OUTPUT_FILE_NAME);
+
+    println!(
+        "GIF変換開始: 変換元フォルダー = {}, 最大幅 = {}px, フレーム間隔 = {}ms",
+        folder, max_width, frame_delay_ms
+    );
+
+    thread::spawn(move || {
+        let result = export_image_folder_to_gif(
+            &source_dir,
+            &output_path,
+            max_width,
+            frame_delay_ms,
+            &log_gif_export_event,
+        );
+
+        let success = match result {
+            Ok(frame_count) => {
+                let file_size = std::fs::metadata(&output_path)
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                app_log(&strings::gif_export_completed(
+                    &output_path,
+                    frame_count,
+                    file_size,
+                ));
+                true
+            }
+            Err(e) => {
+                app_log(&strings::gif_export_failed(&e));
+                false
+            }
+        };
+
+        // メインスレッドに完了を非同期で通知する
+        if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+            unsafe {
+                if let Err(e) = PostMessageW(
+                    Some(*hwnd),
+                    WM_GIF_EXPORT_COMPLETE,
+                    WPARAM(success as usize),
+                    LPARAM(0),
+                ) {
+                    app_log(&strings::post_message_failed(&e));
+                }
+            }
+        }
+    });
+
+    true
+}
+
+/// ライブラリからのGIF変換の進捗・警告を、表示言語に合わせてログに出力する
+fn log_gif_export_event(event: GifExportEvent) {
+    match event {
+        GifExportEvent::Converting { file, index, total } => {
+            app_log(&strings::gif_converting_image(&file, index, total))
+        }
+        GifExportEvent::Skipped { file, reason } => {
+            app_log(&strings::gif_image_skipped(&file, &reason))
+        }
+        GifExportEvent::LargeOutput {
+            estimated_bytes,
+            width,
+            height,
+            frames,
+        } => app_log(&strings::gif_large_output(
+            estimated_bytes,
+            width,
+            height,
+            frames,
+        )),
+    }
+}
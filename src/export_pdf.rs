@@ -4,221 +4,98 @@ JPEGからPDFへの変換モジュール (export_pdf.rs)
 ============================================================================
 
 【ファイル概要】
-指定されたフォルダ内のJPEGファイルを読み込み、1つまたは複数のPDFファイルに変換して
-保存する機能を提供します。
-ユーザーが設定したファイルサイズの上限を超えた場合、自動的に新しいPDFファイルを作成して
-分割保存する機能を持ちます。
+「PDF変換」ボタンから呼び出され、`AppState` に設定された変換元・出力先フォルダと
+PDFサイズ上限を解決して、ライブラリ側の `export_jpeg_folders_to_pdf` に変換処理を委譲します。
+PDFの構築・分割保存のロジック本体は `pdf_builder.rs`（ライブラリ）にあります。
 
 【主要機能】
-1.  **JPEGファイルの収集とソート**:
-    -   `AppState` から指定されたフォルダを読み取り、`jpg`または`jpeg`拡張子のファイルを収集します。
-    -   ファイル名を昇順にソートして、ページ順序を保証します。
-2.  **高品質なPDF変換 (`PdfBuilder`)**:
-    -   `lopdf` クレートを利用してPDFドキュメントを構築します。
-    -   JPEGデータを再圧縮せずに `DCTDecode` フィルタを使用してそのまま埋め込むことで、画質の劣化を防ぎます。
-3.  **ファイルサイズの自動分割**:
-    -   `AppState` で設定された最大ファイルサイズ (`pdf_max_size_mb`) を超えないように、PDFの推定サイズを監視します。
-    -   上限を超えた場合、現在のPDFを保存し、新しいPDFファイルを作成して処理を継続します。
-4.  **連番ファイル名**:
-    -   生成されるPDFファイルには `0001.pdf`, `0002.pdf` のような4桁の連番が付与されます。
+1.  **フォルダの解決**:
+    -   変換元は `pdf_source_dir`、出力先は `pdf_output_dir` を優先し、未設定の場合は `selected_folder_path` を使用します。
+    -   `pdf_additional_source_dirs` があれば変換元の後ろに連結し、複数フォルダーを1つのPDFにまとめます。
+2.  **キャプチャ履歴の並び順での変換**:
+    -   変換元フォルダーを選び直していない状態でキャプチャ履歴（`capture_history`）が空でない場合は、
+        フォルダーのファイル名順ではなく履歴の並び順（履歴ダイアログで削除・並べ替えた結果）で変換します。
+    -   PDFに埋め込めない形式（ウィンドウ単体のPNG）と、既に存在しないファイルは除外します。
+3.  **既存のPDFへの追記 (`choose_pdf_export_action` / `run_pdf_export`)**:
+    -   出力先の最新の連番PDFより後に保存した画像がある場合、変換開始前に「既存PDFに追記」するかを確認します。
+    -   追記する場合は、その画像だけを `append_image_files_to_pdf` で既存のPDFの末尾に追加します（分割なし）。
+    -   他のツールで作成・編集されたPDF（暗号化・タグ付き・PDF/A等）は、ライブラリ側で追記を拒否します。
+4.  **既存のPDFの確認 (`confirm_pdf_overwrite`)**:
+    -   出力先に以前の変換の連番PDF（`0001.pdf` 形式）がある場合、変換開始前に1回だけ
+        上書きするか・枝番を付けて残すか（`0001_2.pdf`）・中止するかを確認します。
+    -   レジストリ `PdfAutoVersion` が有効な場合は確認せず、枝番を付けて残します。
+5.  **キャプチャモード終了時の自動変換 (`export_save_folder_to_pdf`)**:
+    -   「キャプチャ終了時に自動でPDF変換」が有効な場合に、保存先フォルダーをそのまま変換します（確認なし）。
+    -   既存の連番PDFは、`PdfAutoVersion` が有効なら枝番を付けて残し、それ以外は上書きします。
+6.  **PDF変換の委譲**:
+    -   `AppState` の最大ファイルサイズ (`pdf_max_size_mb`)、現在のシステムDPIと `app_log` を渡して変換を実行します。
+    -   システムDPIは、撮影時のDPIが記録されていない画像のページサイズ（画面上で等倍になる大きさ）に使用されます。
+    -   `pdf_archive` が有効な場合は `PdfConformance::PdfA1b`（アーカイブ用のPDF/A-1b）を指定します。
 
 【処理フロー】
-1.  `export_selected_folder_to_pdf` が呼び出されます。
-2.  指定フォルダからJPEGファイルを収集・ソートします。
-3.  `PdfBuilder` の新しいインスタンスを作成します。
-4.  ファイルリストをループ処理:
-    a. JPEGファイルを読み込み、`PdfBuilder::add_jpeg_page` でPDFページとして追加します。
-    b. 一定数のファイルを追加するごとに `PdfBuilder::estimate_size` で現在のPDFサイズを推定します。
-    c. 推定サイズが上限を超えた場合:
-        i.  現在の `PdfBuilder` を（最後に追加したページを除いて）ファイルに保存します。
-        ii. 新しい `PdfBuilder` を作成し、最後に追加したページを最初のページとして新しいPDFの構築を開始します。
-5.  ループ終了後、最後の `PdfBuilder` をファイルに保存します。
-
-【技術仕様】
--   **PDFライブラリ**: `lopdf` を使用して、低レベルなPDFオブジェクトを直接操作。
--   **画像ライブラリ**: `image` を使用して、JPEGの寸法（幅・高さ）を取得。
--   **ファイルI/O**: `std::fs` を使用してファイルとディレクトリを操作。
+1.  呼び出し元が `choose_pdf_export_action` で追記か新しく変換するかを決め、`run_pdf_export` に渡します。
+    新しく変換する場合は `export_selected_folder_to_pdf` が呼び出されます。
+2.  `AppState` から変換元・出力先フォルダを決定します（未選択の場合はログ出力のみで終了）。
+    既存のPDFの扱い（`PdfOverwritePolicy`）は、`choose_pdf_export_action` 内の `confirm_pdf_overwrite` で決まります。
+3.  キャプチャ履歴が使える場合は `export_image_files_to_pdf` に履歴の順でファイルを渡します。
+4.  それ以外は `export_jpeg_folders_to_pdf` を呼び出し、画像の収集・ページ追加（WebPはJPEGへ変換）・サイズ分割・保存を行います。
 
 【AI解析用：依存関係】
 - `app_state.rs`: 保存先フォルダパスやPDF最大サイズ設定を取得。
-- `system_utils.rs`: `app_log` を使用して処理の進捗をログに出力。
-- `lopdf`, `image`: PDF生成と画像解析のための外部クレート。
+- `screen_capture.rs`: 自動変換の変換元・出力先となる保存先フォルダーを取得（`get_save_dir_path`）。
+- `system_utils.rs`: `app_log` を使用して処理の進捗をログに出力。`get_system_dpi` でページサイズの基準DPIを取得。
+- `pdf_builder.rs`（ライブラリ）: `PdfBuilder` とフォルダ一括変換・ファイル指定変換の本体。
+- `ui/capture_history_handler.rs`: キャプチャ履歴の記録・削除・並べ替え。
 */
 
 use crate::app_state::*;
-use crate::system_utils::app_log;
-use image::GenericImageView;
-use image::io::Reader as ImageReader;
-use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
-use num_format::{Locale, ToFormattedString};
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::Path;
-
-/// PDFドキュメントの構築を管理するヘルパー構造体
-///
-/// `lopdf` を使用して、JPEG画像からPDFページを作成し、
-/// ドキュメント全体の構造（Pagesツリー、Catalogなど）を管理します。
-struct PdfBuilder {
-    /// `lopdf` のドキュメントオブジェクト。全てのPDFオブジェクト（ディクショナリ、ストリーム等）を保持します。
-    doc: Document,
-    /// 作成された各ページの `ObjectId` を保持するベクター。最終的に `Pages` ツリーの構築に使用されます。
-    pages: Vec<ObjectId>,
-    /// PDF内で画像リソース（XObject）にユニークな名前を付けるためのカウンター。
-    current_image_counter: u32,
+use crate::screen_capture::get_save_dir_path;
+use crate::strings;
+use crate::system_utils::{app_log, get_system_dpi, show_message_box};
+use clickcapture::pdf_builder::{
+    PdfConformance, PdfExportEvent, PdfExportOptions, PdfOverwritePolicy,
+    append_image_files_to_pdf, collect_capture_images, export_image_files_to_pdf,
+    export_jpeg_folders_to_pdf, files_modified_after_pdf, find_existing_pdf_outputs,
+    is_capture_image,
+};
+use std::path::{Path, PathBuf};
+use windows::Win32::UI::WindowsAndMessaging::{
+    IDNO, IDYES, MB_ICONQUESTION, MB_ICONWARNING, MB_YESNOCANCEL,
+};
+
+/// 「PDF変換」ボタンで実行する処理（変換開始前の確認で決まる）
+pub enum PdfExportAction {
+    /// すべての画像から新しくPDFを作成する（既存の連番PDFの扱いを含む）
+    Export(PdfOverwritePolicy),
+    /// 既存のPDFの末尾に、そのPDFより後に保存した画像を追記する
+    Append {
+        /// 追記先のPDF
+        pdf_path: PathBuf,
+        /// 追記する画像（ページの順）
+        files: Vec<PathBuf>,
+    },
 }
 
-impl PdfBuilder {
-    /// 新しい `PdfBuilder` インスタンスを作成します。
-    fn new() -> Self {
-        Self {
-            doc: Document::with_version("1.5"),
-            pages: Vec::new(),
-            current_image_counter: 1,
-        }
-    }
-
-    /// JPEG画像を新しいページとしてPDFドキュメントに追加する
-    ///
-    /// JPEGデータを再圧縮せずに `DCTDecode` フィルタを用いてそのまま埋め込むことで、
-    /// 画質の劣化を防ぎます。
-    ///
-    /// # 引数
-    /// * `jpeg_bytes` - JPEGファイルの生データ。
-    /// * `width` - 画像の幅（ピクセル）。
-    /// * `height` - 画像の高さ（ピクセル）。
-    fn add_jpeg_page(
-        &mut self,
-        jpeg_bytes: Vec<u8>,
-        width: u32,
-        height: u32,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // JPEGサイズの事前検証
-        if jpeg_bytes.is_empty() {
-            return Err("空のJPEGデータが渡されました".into());
-        }
-
-        if width == 0 || height == 0 {
-            return Err(format!("無効な画像サイズ: {}x{}", width, height).into());
-        }
-
-        // 画像XObject（PDF内で画像を表現するオブジェクト）を作成します。
-        let mut xobject = Dictionary::new();
-        xobject.set("Type", "XObject");
-        xobject.set("Subtype", "Image");
-        xobject.set("Width", Object::Integer(width as i64));
-        xobject.set("Height", Object::Integer(height as i64));
-        xobject.set("ColorSpace", "DeviceRGB");
-        xobject.set("BitsPerComponent", Object::Integer(8));
-        xobject.set("Filter", "DCTDecode");
-
-        // 元のJPEGデータをストリームとしてラップします。`DCTDecode`フィルタが指定されているため、
-        // PDFビューアはこれをJPEGとして直接デコードします。
-        let stream = Stream::new(xobject, jpeg_bytes);
-        let image_id = self.doc.add_object(stream);
-
-        // ページ内で画像を参照するためのユニークなリソース名を生成します。
-        let resource_name = format!("Image{}", self.current_image_counter);
-        self.current_image_counter += 1;
-
-        // ページサイズをポイント単位で計算します。ここでは300 DPIを基準としています。
-        // これにより、印刷時や表示時に適切な解像度が維持されます。
-        let dpi = 300.0;
-        let px_to_pt = |px: u32| -> f64 { (px as f64) * 72.0 / dpi };
-        let page_width = px_to_pt(width);
-        let page_height = px_to_pt(height);
-
-        // ページコンテンツストリーム（画像をページ全体に配置）
-        let contents = format!(
-            "q\n{0} 0 0 {1} 0 0 cm\n/{2} Do\nQ\n",
-            page_width, page_height, resource_name
-        );
-
-        let contents_stream = Stream::new(Dictionary::new(), contents.into_bytes());
-        let contents_id = self.doc.add_object(contents_stream);
-
-        // ページが使用するリソース（この場合は画像XObject）を定義するリソースディクショナリを作成します。
-        let mut resources = Dictionary::new();
-        let mut xobj_map = Dictionary::new();
-        xobj_map.set(resource_name, image_id);
-        resources.set("XObject", xobj_map);
-
-        // ページ辞書の作成
-        let mut page = Dictionary::new();
-        page.set("Type", "Page");
-        page.set(
-            "MediaBox",
-            vec![
-                Object::Integer(0),
-                Object::Integer(0),
-                Object::Real(page_width),
-                Object::Real(page_height),
-            ],
-        );
-        page.set("Resources", resources);
-        page.set("Contents", contents_id);
-
-        let page_id = self.doc.add_object(page);
-        self.pages.push(page_id);
-
-        Ok(())
-    }
-
-    /// ドキュメントの最終処理を行い、保存可能な状態にする
-    ///
-    /// `Pages` ツリーと `Catalog` ディクショナリを構築し、ドキュメントのルートを設定します。
-    /// この処理は、ドキュメントを保存する直前、またはサイズを推定する前に呼び出す必要があります。
-    fn finalize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if self.pages.is_empty() {
-            return Ok(()); // 空文書は何もしない
+/// 変換開始前の確認で決まった処理を実行する
+///
+/// # 引数
+/// * `action` - `choose_pdf_export_action` で決まった処理
+pub fn run_pdf_export(action: PdfExportAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        PdfExportAction::Export(overwrite_policy) => {
+            export_selected_folder_to_pdf(overwrite_policy)
         }
-
-        let pages_kids: Vec<Object> = self.pages.iter().map(|id| Object::Reference(*id)).collect();
-        let mut pages_dict = Dictionary::new();
-        pages_dict.set("Type", "Pages");
-        pages_dict.set("Kids", Object::Array(pages_kids));
-        pages_dict.set("Count", Object::Integer(self.pages.len() as i64));
-
-        // 各ページのParent参照を設定
-        let pages_id = self.doc.add_object(pages_dict);
-        for &page_id in &self.pages {
-            if let Ok(page_obj) = self.doc.get_object_mut(page_id) {
-                if let Object::Dictionary(page_dict) = page_obj {
-                    page_dict.set("Parent", pages_id);
-                }
-            }
+        PdfExportAction::Append { pdf_path, files } => {
+            let app_state = AppState::get_app_state_ref();
+            append_image_files_to_pdf(
+                &pdf_path,
+                &files,
+                get_system_dpi() as u32,
+                app_state.record_capture_metadata,
+                &log_pdf_export_event,
+            )
+            .map(|_| ())
         }
-
-        // カタログの作成
-        let mut catalog = Dictionary::new();
-        catalog.set("Type", "Catalog");
-        catalog.set("Pages", pages_id);
-        let catalog_id = self.doc.add_object(catalog);
-
-        // ドキュメントのルートオブジェクトとしてカタログを設定
-        self.doc.trailer.set("Root", catalog_id);
-
-        Ok(())
-    }
-
-    /// 現在構築中のPDFの推定ファイルサイズをバイト単位で計算する
-    ///
-    /// 内部的にドキュメントをメモリ上のバッファに保存してみて、そのサイズを返します。
-    /// ファイル分割の判定に使用されます。
-    fn estimate_size(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
-        self.finalize()?;
-        let mut buffer = Vec::new();
-        self.doc.save_to(&mut buffer)?;
-        Ok(buffer.len())
-    }
-
-    /// 構築したPDFドキュメントを指定されたパスに保存する
-    fn save_to_file(&mut self, path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
-        self.finalize()?;
-        let mut buffer = Vec::new();
-        self.doc.save_to(&mut buffer)?;
-        File::create(path)?.write_all(&buffer)?;
-        Ok(buffer.len())
     }
 }
 
@@ -226,208 +103,298 @@ impl PdfBuilder {
 ///
 /// フォルダ内のJPEGファイルをファイル名順に読み込み、`AppState` で設定された
 /// 最大ファイルサイズに基づいて、1つまたは複数のPDFファイルに分割して保存します。
-pub fn export_selected_folder_to_pdf() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// 変換元は `pdf_source_dir`、出力先は `pdf_output_dir` が設定されていればそれを使用し、
+/// 未設定の場合はどちらも `selected_folder_path` を使用します。
+/// `pdf_additional_source_dirs` が設定されている場合は、変換元の後ろに選択順で連結して1つのPDFストリームにします。
+/// 変換元フォルダーを選び直していない状態でキャプチャ履歴が空でない場合は、
+/// フォルダーの代わりに履歴の並び順でファイルを変換します（`capture_history_pdf_files`）。
+/// `record_capture_metadata` が有効な場合は、EXIFの撮影日時を各ページの下にフッターとして表示します。
+/// `pdf_archive` が有効な場合は、アーカイブ用のPDF/A-1b形式で出力します（フッターは省略）。
+///
+/// # 引数
+/// * `overwrite_policy` - 出力先に同じ連番のPDFがある場合の扱い（`confirm_pdf_overwrite` の結果）
+pub fn export_selected_folder_to_pdf(
+    overwrite_policy: PdfOverwritePolicy,
+) -> Result<(), Box<dyn std::error::Error>> {
     let app_state = AppState::get_app_state_ref();
-    let folder = match &app_state.selected_folder_path {
+    let folder = match app_state
+        .pdf_source_dir
+        .as_ref()
+        .or(app_state.selected_folder_path.as_ref())
+    {
         Some(p) => p.clone(),
         None => {
-            app_log("⚠️ PDF変換エラー: 保存フォルダーが選択されていません");
+            app_log(strings::pdf_no_folder());
             return Ok(());
         }
     };
-
-    println!("PDF変換開始: フォルダー = {}", folder);
-
-    // フォルダの存在を確認
-    let folder_path = Path::new(&folder);
-    if !folder_path.exists() {
-        return Err(format!("❌ 指定されたフォルダーが存在しません: {}", folder).into());
+    let output_folder = resolve_pdf_output_folder().unwrap_or_else(|| folder.clone());
+    let options = pdf_export_options(overwrite_policy, pdf_conformance());
+
+    // キャプチャ履歴の並び順（削除・並べ替え済み）を、フォルダーのファイル名順より優先する
+    if let Some(history_files) = capture_history_pdf_files() {
+        app_log(&strings::pdf_using_capture_history(history_files.len()));
+        return export_image_files_to_pdf(
+            &history_files,
+            Path::new(&output_folder),
+            &options,
+            &log_pdf_export_event,
+        );
     }
 
-    // フォルダ内のJPEGファイル（.jpg, .jpeg）を収集してファイル名でソート
-    let mut entries: Vec<_> = fs::read_dir(&folder)?
-        .filter_map(|r| r.ok())
-        .filter(|e| {
-            if let Some(ext) = e.path().extension() {
-                let s = ext.to_string_lossy().to_lowercase();
-                s == "jpg" || s == "jpeg"
-            } else {
-                false
-            }
-        })
+    // 追加の変換元フォルダーは、変換元フォルダーの後ろに選択順で連結する
+    let source_dirs: Vec<PathBuf> = std::iter::once(&folder)
+        .chain(app_state.pdf_additional_source_dirs.iter())
+        .map(PathBuf::from)
         .collect();
 
-    entries.sort_by_key(|e| e.path());
-
-    if entries.is_empty() {
-        app_log("⚠️ PDF変換: 対象のJPEGファイルが見つかりませんでした。");
-        return Ok(());
-    }
-
-    println!("処理対象ファイル数: {}", entries.len());
-
-    let mut pdf_index = 1;
-    let mut current_builder = PdfBuilder::new();
-    let mut files_in_current_pdf = 0;
-    let mut total_processed = 0;
-    let total_files = entries.len();
-
-    // AppStateからPDFの最大ファイルサイズ（MB単位）を取得し、バイトに変換
-    let app_state = AppState::get_app_state_ref();
-    let max_pdf_size_bytes = (app_state.pdf_max_size_mb as u64) * 1024 * 1024;
     println!(
-        "PDFサイズ上限: {} Byte",
-        max_pdf_size_bytes.to_formatted_string(&Locale::ja)
+        "PDF変換開始: 変換元フォルダー = {}, 追加フォルダー数 = {}, 出力先フォルダー = {}",
+        folder,
+        app_state.pdf_additional_source_dirs.len(),
+        output_folder
     );
 
-    for entry in entries {
-        let path = entry.path();
-        let filename = path
-            .file_name()
-            .expect("ファイル名の取得に失敗しました")
-            .to_string_lossy()
-            .to_string();
-
-        total_processed += 1;
-        app_log(&format!(
-            "⏳ 処理中のJPEG: {} ({}/{})",
-            filename, total_processed, total_files
-        ));
-
-        // `image` クレートを使って画像のデコードと寸法取得を試みる
-        let img = match ImageReader::open(&path) {
-            Ok(reader) => match reader.decode() {
-                Ok(img) => img,
-                Err(e) => {
-                    eprintln!("❌ 画像デコードエラー ({}): {}", filename, e);
-                    return Err(e.into());
-                }
-            },
-            Err(e) => {
-                eprintln!("❌ 画像読み込みエラー ({}): {}", filename, e);
-                return Err(e.into());
-            }
-        };
+    export_jpeg_folders_to_pdf(
+        &source_dirs,
+        Path::new(&output_folder),
+        &options,
+        &log_pdf_export_event,
+    )
+}
 
-        let (width, height) = img.dimensions();
+/// キャプチャモード終了時の自動変換として、保存先フォルダーをPDFに変換する
+///
+/// 変換元・出力先はどちらも今回のキャプチャの保存先フォルダー（`get_save_dir_path`）です。
+/// 「PDF変換」ボタンで選び直した変換元やキャプチャ履歴の並び順は使いません。
+/// 無人で実行するため既存のPDFの扱いは確認せず、`pdf_auto_version` が有効な場合は枝番を付けて残し、
+/// それ以外は上書きします。サイズの上限による分割・PDF/Aの設定はボタンからの変換と同じです。
+pub fn export_save_folder_to_pdf() -> Result<(), Box<dyn std::error::Error>> {
+    let app_state = AppState::get_app_state_ref();
+    let save_dir = PathBuf::from(get_save_dir_path());
+    let overwrite_policy = if app_state.pdf_auto_version {
+        PdfOverwritePolicy::KeepExisting
+    } else {
+        PdfOverwritePolicy::Overwrite
+    };
 
-        // JPEGファイルの生データを読み込む
-        let jpeg_bytes = match fs::read(&path) {
-            Ok(bytes) => {
-                let file_size_mb = bytes.len() as f64 / 1024.0 / 1024.0;
-                let bytes_per_pixel = bytes.len() as f64 / (width * height) as f64;
+    export_jpeg_folders_to_pdf(
+        std::slice::from_ref(&save_dir),
+        &save_dir,
+        &pdf_export_options(overwrite_policy, pdf_conformance()),
+        &log_pdf_export_event,
+    )
+}
 
-                println!(
-                    "  {} x {} px, {:.1}MB, {:.3}バイト/ピクセル",
-                    width, height, file_size_mb, bytes_per_pixel
-                );
+/// ライブラリからのPDF変換・追記の進捗・警告を、表示言語に合わせてログに出力する
+fn log_pdf_export_event(event: PdfExportEvent) {
+    match event {
+        PdfExportEvent::NoImages => app_log(strings::pdf_no_images()),
+        PdfExportEvent::ArchiveFooterOmitted => app_log(strings::pdf_archive_footer_omitted()),
+        PdfExportEvent::Processing { file, index, total } => {
+            app_log(&strings::pdf_processing_image(&file, index, total))
+        }
+        PdfExportEvent::Skipped { file, reason } => {
+            app_log(&strings::pdf_image_skipped(&file, &reason))
+        }
+        PdfExportEvent::SizeLimitReached { estimated_bytes } => {
+            app_log(&strings::pdf_size_limit_reached(estimated_bytes))
+        }
+        PdfExportEvent::Overwriting { path } => app_log(&strings::pdf_overwriting(&path)),
+        PdfExportEvent::KeepingExisting { path, versioned } => {
+            app_log(&strings::pdf_keeping_existing(&path, &versioned))
+        }
+        PdfExportEvent::Saved { path, bytes } => app_log(&strings::pdf_saved(&path, bytes)),
+        PdfExportEvent::Completed { converted, skipped } => {
+            app_log(&strings::pdf_export_completed(converted, skipped))
+        }
+        PdfExportEvent::Appending { file, index, total } => {
+            app_log(&strings::pdf_appending_image(&file, index, total))
+        }
+        PdfExportEvent::Appended {
+            path,
+            added,
+            total_pages,
+            bytes,
+        } => app_log(&strings::pdf_appended(&path, added, total_pages, bytes)),
+        PdfExportEvent::AppendSkipped { count } => app_log(&strings::pdf_append_skipped(count)),
+    }
+}
 
-                if bytes.len() > 50 * 1024 * 1024 {
-                    // 50MB以上の画像は警告
-                    println!("⚠️ 警告: 大きな画像ファイル ({:.1}MB)", file_size_mb);
-                }
+/// サイズ上限・画面DPI・撮影日時フッターの設定から、PDF変換の出力設定を作成する
+fn pdf_export_options(
+    overwrite_policy: PdfOverwritePolicy,
+    conformance: PdfConformance,
+) -> PdfExportOptions {
+    let app_state = AppState::get_app_state_ref();
+    PdfExportOptions {
+        max_size_mb: app_state.pdf_max_size_mb,
+        screen_dpi: get_system_dpi() as u32,
+        capture_time_footer: app_state.record_capture_metadata,
+        overwrite_policy,
+        conformance,
+    }
+}
 
-                if bytes_per_pixel < 0.1 {
-                    println!(
-                        "⚠️ 警告: 低品質JPEG ({:.3}バイト/ピクセル)",
-                        bytes_per_pixel
-                    );
-                } else if bytes_per_pixel > 1.0 {
-                    println!("✅ 高品質JPEG ({:.3}バイト/ピクセル)", bytes_per_pixel);
-                }
+/// `pdf_archive` の設定から、PDFの出力形式を決める
+fn pdf_conformance() -> PdfConformance {
+    if AppState::get_app_state_ref().pdf_archive {
+        PdfConformance::PdfA1b
+    } else {
+        PdfConformance::Standard
+    }
+}
 
-                bytes
-            }
-            Err(e) => {
-                eprintln!("ファイル読み込みエラー ({}): {}", filename, e);
-                return Err(e.into());
-            }
-        };
+/// PDFの出力先フォルダーを取得する
+///
+/// `pdf_output_dir`、`selected_folder_path`、`pdf_source_dir` の順に、最初に設定されているものを返します。
+fn resolve_pdf_output_folder() -> Option<String> {
+    let app_state = AppState::get_app_state_ref();
+    app_state
+        .pdf_output_dir
+        .as_ref()
+        .or(app_state.selected_folder_path.as_ref())
+        .or(app_state.pdf_source_dir.as_ref())
+        .cloned()
+}
 
-        // 読み込んだJPEGデータを現在の `PdfBuilder` にページとして追加
-        if let Err(e) = current_builder.add_jpeg_page(jpeg_bytes.clone(), width, height) {
-            eprintln!("❌ PDF追加エラー ({}): {}", filename, e);
-            return Err(e.into());
+/// 変換開始前に、既存のPDFへの追記か新しく変換するか（既存のPDFの扱い）を決める
+///
+/// 出力先の最新のPDFより後に保存した画像がある場合は、先に追記するかを確認します。
+/// 追記しない場合や追記できる画像がない場合は、`confirm_pdf_overwrite` で既存のPDFの扱いを確認します。
+/// アーカイブ用（PDF/A）の設定では、追記したPDFがPDF/Aにならないため追記は提案しません。
+///
+/// # 戻り値
+/// * `Some(action)` - 実行する処理
+/// * `None` - ユーザーがキャンセルした
+pub fn choose_pdf_export_action() -> Option<PdfExportAction> {
+    if let Some((pdf_path, files)) = find_pdf_append_target() {
+        let pdf_name = pdf_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let result = show_message_box(
+            &strings::pdf_append_confirm(&pdf_name, files.len()),
+            strings::pdf_append_title(),
+            MB_YESNOCANCEL | MB_ICONQUESTION,
+        );
+        if result.0 == IDYES.0 {
+            app_log(&strings::pdf_append_start(&pdf_name, files.len()));
+            return Some(PdfExportAction::Append { pdf_path, files });
         }
+        if result.0 != IDNO.0 {
+            return None;
+        }
+    }
 
-        files_in_current_pdf += 1;
+    confirm_pdf_overwrite().map(PdfExportAction::Export)
+}
 
-        // ファイルサイズをチェックして、必要であればPDFを分割する。
-        // 毎回チェックするとパフォーマンスが落ちるため、10ファイルごと、または最初の1ファイル以降にチェック。
-        if files_in_current_pdf % 10 == 0 || files_in_current_pdf > 1 {
-            let estimated_size = match current_builder.estimate_size() {
-                Ok(size) => size,
-                Err(e) => {
-                    eprintln!("❌ PDFサイズ推定エラー: {}", e);
-                    return Err(e);
-                }
-            };
+/// 追記先のPDF（出力先で最も新しい連番PDF）と、それより後に保存した変換元の画像を探す
+///
+/// 変換元は通常の変換と同じく、キャプチャ履歴が使える場合は履歴の並び順、
+/// それ以外は変換元フォルダー（追加フォルダーを含む）の連番順です。
+fn find_pdf_append_target() -> Option<(PathBuf, Vec<PathBuf>)> {
+    let app_state = AppState::get_app_state_ref();
+    if app_state.pdf_archive {
+        return None;
+    }
 
-            println!(
-                "推定PDFサイズ: {} Byte",
-                estimated_size.to_formatted_string(&Locale::ja)
-            );
+    let output_folder = resolve_pdf_output_folder()?;
+    let pdf_path = find_existing_pdf_outputs(Path::new(&output_folder))
+        .ok()?
+        .into_iter()
+        .max_by_key(|path| {
+            path.metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })?;
+
+    let source_files = match capture_history_pdf_files() {
+        Some(history_files) => history_files,
+        None => {
+            let folder = app_state
+                .pdf_source_dir
+                .as_ref()
+                .or(app_state.selected_folder_path.as_ref())?;
+            std::iter::once(folder)
+                .chain(app_state.pdf_additional_source_dirs.iter())
+                .filter_map(|dir| collect_capture_images(Path::new(dir)).ok())
+                .flatten()
+                .collect()
+        }
+    };
 
-            if estimated_size > max_pdf_size_bytes as usize && files_in_current_pdf > 1 {
-                app_log(&format!(
-                    "➡️ PDFサイズ制限到達 ({:.1}MB)。現在のPDFを保存して新しいPDFを開始します。",
-                    estimated_size as f64 / 1024.0 / 1024.0
-                ));
+    let files = files_modified_after_pdf(&source_files, &pdf_path).ok()?;
+    (!files.is_empty()).then_some((pdf_path, files))
+}
 
-                // 現在のPDFを保存する。ただし、サイズオーバーの原因となった最後の画像は含めない。
-                // その画像は次の新しいPDFの最初のページになる。
-                current_builder.pages.pop();
+/// 出力先に以前の変換の連番PDFが残っている場合に、上書きするか枝番を付けて残すかを決める
+///
+/// 既存のPDFがある場合は、変換開始前に1回だけ確認ダイアログを表示します
+/// （分割保存の途中で何度も確認しないため）。`pdf_auto_version` が有効な場合は確認せずに残します。
+/// 既存のPDFを確認できなかった場合は、手動で編集したPDFを失わないよう枝番を付けて残します。
+///
+/// # 戻り値
+/// * `Some(policy)` - 変換を続行する（既存のPDFがない場合は `Overwrite`）
+/// * `None` - ユーザーがキャンセルした
+pub fn confirm_pdf_overwrite() -> Option<PdfOverwritePolicy> {
+    let Some(output_folder) = resolve_pdf_output_folder() else {
+        return Some(PdfOverwritePolicy::Overwrite);
+    };
+    let existing = match find_existing_pdf_outputs(Path::new(&output_folder)) {
+        Ok(existing) => existing,
+        Err(e) => {
+            app_log(&strings::pdf_existing_check_failed(&e));
+            return Some(PdfOverwritePolicy::KeepExisting);
+        }
+    };
+    let Some(first) = existing.first() else {
+        return Some(PdfOverwritePolicy::Overwrite);
+    };
 
-                if !current_builder.pages.is_empty() {
-                    let output_path = Path::new(&folder).join(format!("{:04}.pdf", pdf_index));
-                    match current_builder.save_to_file(&output_path) {
-                        Ok(file_size) => {
-                            app_log(&format!(
-                                "✅ PDF完了: {} ({:.1}MB)",
-                                output_path.display(),
-                                file_size as f64 / 1024.0 / 1024.0
-                            ));
-                            pdf_index += 1;
-                        }
-                        Err(e) => {
-                            eprintln!("❌ PDF保存エラー: {}", e);
-                            return Err(e);
-                        }
-                    }
-                }
+    if AppState::get_app_state_ref().pdf_auto_version {
+        app_log(&strings::pdf_keep_existing(existing.len()));
+        return Some(PdfOverwritePolicy::KeepExisting);
+    }
 
-                // 新しい `PdfBuilder` を作成し、先ほど除外した画像から新しいPDFを開始する
-                current_builder = PdfBuilder::new();
-                if let Err(e) = current_builder.add_jpeg_page(jpeg_bytes, width, height) {
-                    eprintln!("❌ 新PDF開始エラー ({}): {}", filename, e);
-                    return Err(e);
-                }
-                files_in_current_pdf = 1;
-            }
-        }
+    let first_name = first
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let result = show_message_box(
+        &strings::pdf_overwrite_confirm(existing.len(), &first_name),
+        strings::pdf_overwrite_title(),
+        MB_YESNOCANCEL | MB_ICONWARNING,
+    );
+    if result.0 == IDYES.0 {
+        app_log(&strings::pdf_overwrite_existing(existing.len()));
+        Some(PdfOverwritePolicy::Overwrite)
+    } else if result.0 == IDNO.0 {
+        app_log(&strings::pdf_keep_existing(existing.len()));
+        Some(PdfOverwritePolicy::KeepExisting)
+    } else {
+        None
     }
+}
 
-    // ループ終了後、残っているページがあれば最後のPDFファイルとして保存
-    if !current_builder.pages.is_empty() {
-        let output_path = Path::new(&folder).join(format!("{:04}.pdf", pdf_index));
-        match current_builder.save_to_file(&output_path) {
-            Ok(file_size) => {
-                app_log(&format!(
-                    "✅ PDF完了: {} ({:.1}MB)",
-                    output_path.display(),
-                    file_size as f64 / 1024.0 / 1024.0
-                ));
-            }
-            Err(e) => {
-                eprintln!("❌ 最終PDF保存エラー: {}", e);
-                return Err(e);
-            }
-        }
+/// キャプチャ履歴の並び順で変換する場合の対象ファイルを取得する
+///
+/// 変換元フォルダーを選び直している場合（`pdf_source_dir` / `pdf_additional_source_dirs`）は
+/// そのフォルダーを優先するため `None` を返します。履歴のうち、PDFに埋め込める形式で
+/// 現在も存在するファイルが1つもない場合も `None` を返します。
+pub fn capture_history_pdf_files() -> Option<Vec<PathBuf>> {
+    let app_state = AppState::get_app_state_ref();
+    if app_state.pdf_source_dir.is_some() || !app_state.pdf_additional_source_dirs.is_empty() {
+        return None;
     }
 
-    app_log(&format!(
-        "✅ 全JPEGからのPDF変換処理が完了しました。処理ファイル数: {}",
-        total_processed
-    ));
-    Ok(())
+    let files: Vec<PathBuf> = app_state
+        .capture_history
+        .iter()
+        .filter(|path| is_capture_image(path) && path.exists())
+        .cloned()
+        .collect();
+    (!files.is_empty()).then_some(files)
 }
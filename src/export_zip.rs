@@ -0,0 +1,146 @@
+/*
+============================================================================
+キャプチャフォルダのZIP圧縮モジュール (export_zip.rs)
+============================================================================
+
+【ファイル概要】
+「ZIPに圧縮」ボタンから呼び出され、`selected_folder_path` 内の画像を
+`captures_<日付>.zip` にまとめる処理を、ライブラリ側の `export_image_folder_to_zip` に委譲して
+ワーカースレッドで実行します。ZIP圧縮中もキャプチャは継続でき、中止ボタンでキャンセルできます。
+
+【主要機能】
+1.  **出力パスの決定**:
+    -   `captures_YYYYMMDD.zip`（ローカル日付）を保存フォルダ直下に作成します。
+    -   同名のファイルが既にある場合は `captures_YYYYMMDD_2.zip` のように連番を付けます。
+2.  **ワーカースレッドでの圧縮**:
+    -   進捗・警告は `app_log` でログに出力します。
+    -   終了時に `WM_ZIP_EXPORT_COMPLETE`（wparam: 1=成功, 0=失敗・中止）をメインダイアログに送信します。
+3.  **中止要求**: `request_zip_export_cancel` でキャンセルフラグを設定します（次のファイルの処理前に中断）。
+
+【AI解析用：依存関係】
+- `app_state.rs`: 保存先フォルダパス、キャンセルフラグ、出力パスを保持。
+- `system_utils.rs`: `app_log` を使用して処理の進捗をログに出力。
+- `zip_builder.rs`（ライブラリ）: ZIP圧縮処理の本体。
+- `ui/zip_export_button_handler.rs`: 呼び出し元、`WM_ZIP_EXPORT_COMPLETE` の受信処理。
+*/
+
+use crate::app_state::*;
+use crate::constants::WM_ZIP_EXPORT_COMPLETE;
+use crate::strings;
+use crate::system_utils::app_log;
+use clickcapture::zip_builder::{ZipExportEvent, ZipExportResult, export_image_folder_to_zip};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::thread;
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+
+/// 保存フォルダ内の画像をZIPファイルにまとめる（ワーカースレッド）
+///
+/// # 戻り値
+/// * `true` - ワーカースレッドを起動した場合（終了は `WM_ZIP_EXPORT_COMPLETE` で通知）。
+/// * `false` - 保存フォルダが未選択のため起動しなかった場合。
+pub fn start_zip_export() -> bool {
+    let app_state = AppState::get_app_state_mut();
+    let Some(folder) = app_state.selected_folder_path.clone() else {
+        app_log(strings::zip_no_folder());
+        return false;
+    };
+
+    let source_dir = PathBuf::from(&folder);
+    let output_path = build_zip_output_path(&source_dir);
+    app_state.zip_export_output_path = Some(output_path.clone());
+
+    let cancel_flag = app_state.zip_export_cancel_flag.clone();
+    cancel_flag.store(false, Ordering::Relaxed);
+
+    println!(
+        "ZIP圧縮開始: 保存フォルダー = {}, 出力先 = {}",
+        folder,
+        output_path.display()
+    );
+
+    thread::spawn(move || {
+        let result = export_image_folder_to_zip(
+            &source_dir,
+            &output_path,
+            &cancel_flag,
+            &log_zip_export_event,
+        );
+
+        let success = match result {
+            Ok(ZipExportResult::Completed { archived, skipped }) => {
+                let file_size = std::fs::metadata(&output_path)
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                app_log(&strings::zip_export_completed(
+                    &output_path,
+                    archived,
+                    skipped,
+                    file_size,
+                ));
+                true
+            }
+            Ok(ZipExportResult::Cancelled) => {
+                app_log(strings::zip_export_cancelled());
+                false
+            }
+            Err(e) => {
+                app_log(&strings::zip_export_failed(&e));
+                false
+            }
+        };
+
+        // メインスレッドに終了を非同期で通知する
+        if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+            unsafe {
+                if let Err(e) = PostMessageW(
+                    Some(*hwnd),
+                    WM_ZIP_EXPORT_COMPLETE,
+                    WPARAM(success as usize),
+                    LPARAM(0),
+                ) {
+                    app_log(&strings::post_message_failed(&e));
+                }
+            }
+        }
+    });
+
+    true
+}
+
+/// 実行中のZIP圧縮に中止を要求する
+///
+/// ワーカースレッドは次のファイルの処理前にフラグを確認して中断し、`WM_ZIP_EXPORT_COMPLETE` を送信します。
+pub fn request_zip_export_cancel() {
+    AppState::get_app_state_ref()
+        .zip_export_cancel_flag
+        .store(true, Ordering::Relaxed);
+}
+
+/// 出力するZIPファイルのパスを決定する（`captures_YYYYMMDD.zip`、既存の場合は `_2`, `_3`...）
+fn build_zip_output_path(source_dir: &Path) -> PathBuf {
+    let now = unsafe { GetLocalTime() };
+    let base_name = format!("captures_{:04}{:02}{:02}", now.wYear, now.wMonth, now.wDay);
+
+    let mut output_path = source_dir.join(format!("{}.zip", base_name));
+    let mut suffix = 2;
+    while output_path.exists() {
+        output_path = source_dir.join(format!("{}_{}.zip", base_name, suffix));
+        suffix += 1;
+    }
+    output_path
+}
+
+/// ライブラリからのZIP圧縮の進捗・警告を、表示言語に合わせてログに出力する
+fn log_zip_export_event(event: ZipExportEvent) {
+    match event {
+        ZipExportEvent::Archiving { file, index, total } => {
+            app_log(&strings::zip_archiving_image(&file, index, total))
+        }
+        ZipExportEvent::Skipped { file, error } => {
+            app_log(&strings::zip_file_skipped(&file, &error))
+        }
+    }
+}
@@ -0,0 +1,160 @@
+/*
+============================================================================
+変化検出モジュール (frame_diff.rs)
+============================================================================
+
+【ファイル概要】
+前回保存した画像と今回取得した画像を比較し、変化した範囲（バウンディングボックス）を求める
+ライブラリモジュールです。ほとんど変化しない画面を監視する場合に、変化がなければ保存を省略し、
+変化した部分だけを切り出して保存するために使用します。Win32 APIには依存しません。
+
+【主要機能】
+1.  **`ChangeDetection`**: 変化検出の動作（無効 / 変化がなければ保存しない / 変化した範囲のみ保存）
+2.  **`changed_region`**: 2つの画像の変化した範囲を求める（変化がなければ `None`）
+3.  **`ChangedRegion::scale_to` / `crop_to_region`**: 原寸で求めた範囲を縮小後の画像に合わせて切り出す
+
+【技術仕様】
+-   **比較**: RGBの各チャンネルの差が `CHANGE_TOLERANCE` を超えた画素を変化とみなす（縮小前の原寸画像で比較）
+-   **サイズの違い**: 画像のサイズが異なる場合（選択領域の変更など）は画像全体を変化した範囲とする
+-   **切り出し**: 縮小後の座標に換算する際は外側に丸め、変化した画素が切れないようにする
+
+【AI解析用：依存関係】
+-   `capture_pipeline.rs`（バイナリ側）: `detect_change` / `crop_to_change` で保存の省略・切り出し
+-   `app_state.rs`（バイナリ側）: 前回保存した画像（`previous_capture_frame`）と設定（`change_detection`）の保持
+*/
+
+use image::{RgbImage, imageops};
+
+/// 変化とみなすRGBの各チャンネルの差（これ以下の差は無視する）
+pub const CHANGE_TOLERANCE: u8 = 8;
+
+/// 変化検出の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeDetection {
+    /// 毎回保存する（デフォルト）
+    #[default]
+    Off = 0,
+    /// 前回保存した画像から変化がなければ保存しない
+    SkipUnchanged = 1,
+    /// 変化がなければ保存せず、変化した場合は変化した範囲のみ保存する
+    CropToChange = 2,
+}
+
+impl ChangeDetection {
+    /// レジストリ値から復元（不明な値は無効扱い）
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => ChangeDetection::SkipUnchanged,
+            2 => ChangeDetection::CropToChange,
+            _ => ChangeDetection::Off,
+        }
+    }
+
+    /// 前回の画像との比較を行うか
+    pub fn is_enabled(&self) -> bool {
+        *self != ChangeDetection::Off
+    }
+}
+
+/// 画像内の変化した範囲（ピクセル座標）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangedRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ChangedRegion {
+    /// 画像全体を表す範囲
+    pub fn whole(width: u32, height: u32) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    /// 範囲が `width` x `height` の画像全体か
+    pub fn covers(&self, width: u32, height: u32) -> bool {
+        *self == Self::whole(width, height)
+    }
+
+    /// `from` のサイズの画像で求めた範囲を、`to` のサイズに縮小した画像の座標に換算する
+    ///
+    /// 左上は切り捨て、右下は切り上げて外側に丸めます（最小1px、画像の外にははみ出さない）。
+    pub fn scale_to(&self, from: (u32, u32), to: (u32, u32)) -> Self {
+        let scale = |value: u32, from: u32, to: u32, round_up: bool| {
+            let scaled = value as u64 * to as u64;
+            let from = from.max(1) as u64;
+            let scaled = if round_up {
+                scaled.div_ceil(from)
+            } else {
+                scaled / from
+            };
+            (scaled as u32).min(to)
+        };
+        let left = scale(self.x, from.0, to.0, false);
+        let top = scale(self.y, from.1, to.1, false);
+        let right = scale(self.x + self.width, from.0, to.0, true);
+        let bottom = scale(self.y + self.height, from.1, to.1, true);
+
+        let left = left.min(to.0.saturating_sub(1));
+        let top = top.min(to.1.saturating_sub(1));
+        Self {
+            x: left,
+            y: top,
+            width: right.saturating_sub(left).max(1),
+            height: bottom.saturating_sub(top).max(1),
+        }
+    }
+}
+
+/// 前回の画像と今回の画像を比較し、変化した範囲を返す（変化がなければ `None`）
+///
+/// 画像のサイズが異なる場合は、今回の画像全体を変化した範囲として返します。
+///
+/// # 引数
+/// * `previous` - 前回保存した画像（縮小前の原寸）
+/// * `current` - 今回取得した画像（縮小前の原寸）
+/// * `tolerance` - 変化とみなさないRGBの各チャンネルの差
+pub fn changed_region(
+    previous: &RgbImage,
+    current: &RgbImage,
+    tolerance: u8,
+) -> Option<ChangedRegion> {
+    let (width, height) = current.dimensions();
+    if previous.dimensions() != (width, height) {
+        return Some(ChangedRegion::whole(width, height));
+    }
+
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for ((x, y, before), after) in previous.enumerate_pixels().zip(current.pixels()) {
+        let changed = before
+            .0
+            .iter()
+            .zip(after.0.iter())
+            .any(|(b, a)| b.abs_diff(*a) > tolerance);
+        if changed {
+            bounds = Some(match bounds {
+                Some((left, top, right, bottom)) => {
+                    (left.min(x), top.min(y), right.max(x), bottom.max(y))
+                }
+                None => (x, y, x, y),
+            });
+        }
+    }
+
+    bounds.map(|(left, top, right, bottom)| ChangedRegion {
+        x: left,
+        y: top,
+        width: right - left + 1,
+        height: bottom - top + 1,
+    })
+}
+
+/// 画像から指定した範囲を切り出す
+pub fn crop_to_region(image: &RgbImage, region: ChangedRegion) -> RgbImage {
+    imageops::crop_imm(image, region.x, region.y, region.width, region.height).to_image()
+}
@@ -0,0 +1,193 @@
+/*
+============================================================================
+アニメーションGIF構築モジュール (gif_builder.rs)
+============================================================================
+
+【ファイル概要】
+フォルダ内のキャプチャ画像（JPEG / WebP / BMP）を、PDF変換と同じ収集・並び順で読み込み、
+1つのアニメーションGIF（`animation.gif`）にまとめるライブラリモジュールです。
+自動クリックで連続キャプチャした画面を、手軽に共有できる形にすることを想定しています。
+`AppState` やダイアログには依存せず、進捗・警告は `GifExportEvent` として呼び出し元のコールバックに通知します。
+
+【主要機能】
+1.  **`export_image_folder_to_gif`**:
+    -   `pdf_builder::collect_capture_images` で画像をファイル名順に収集します。
+    -   各フレームを最大幅（`max_width`）以下に縮小し、1枚目と同じサイズに揃えます。
+    -   `image` クレートの `GifEncoder`（NeuQuantによる256色への減色）でエンコードし、無限ループで保存します。
+    -   推定サイズが `SIZE_WARNING_BYTES`（約50MB）を超える場合は警告を出力します。
+    -   読み込み・デコードに失敗した画像とAVIFはスキップし、1フレームも作成できなかった場合のみエラーを返します。
+
+【技術仕様】
+-   **縮小処理**: `image::imageops::resize`（Lanczos3）、縦横比を維持
+-   **減色**: `GifEncoder::new_with_speed`（`QUANTIZE_SPEED`、1が最高品質・30が最速）
+-   **フレーム間隔**: 呼び出し元が指定（GIFの仕様上10ms単位）
+-   **サイズ推定**: 1ピクセル1バイト（インデックスカラー・圧縮前）×フレーム数
+
+【AI解析用：依存関係】
+-   `pdf_builder.rs`: `collect_capture_images`（画像の収集・並び順をPDF変換と共通化）、`ImageSkipReason`
+-   `export_gif.rs`（バイナリ側）: `AppState` の設定と、`GifExportEvent` を `app_log` に出力するコールバックを渡してワーカースレッドで呼び出し
+-   `image`: デコード・縮小・GIFエンコード
+*/
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::io::Reader as ImageReader;
+use image::{Delay, Frame};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::pdf_builder::{ImageSkipReason, collect_capture_images};
+
+/// GIFの減色速度（1〜30、値が小さいほど高品質・低速）
+const QUANTIZE_SPEED: i32 = 10;
+
+/// 推定出力サイズがこの値を超える場合に警告する（約50MB）
+pub const SIZE_WARNING_BYTES: u64 = 50 * 1024 * 1024;
+
+/// GIF変換中の出来事（バイナリ側で表示言語に合わせてログに出力する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GifExportEvent {
+    /// 画像をフレームとして追加する（`index` は1から）
+    Converting {
+        file: String,
+        index: usize,
+        total: usize,
+    },
+    /// 画像をスキップした
+    Skipped {
+        file: String,
+        reason: ImageSkipReason,
+    },
+    /// 推定サイズが `SIZE_WARNING_BYTES` を超える
+    LargeOutput {
+        estimated_bytes: u64,
+        width: u32,
+        height: u32,
+        frames: usize,
+    },
+}
+
+/// フォルダ内のキャプチャ画像をアニメーションGIFに変換する
+///
+/// `source_dir` 内の画像をファイル名順に読み込み、最大幅 `max_width` に縮小して
+/// `output_path` にアニメーションGIFとして保存します。
+///
+/// # 引数
+/// * `source_dir` - 変換元の画像が格納されたフォルダ
+/// * `output_path` - 出力するGIFファイルのパス
+/// * `max_width` - フレームの最大幅（px、超える場合は縦横比を保って縮小）
+/// * `frame_delay_ms` - フレーム間隔（ミリ秒）
+/// * `notify` - 進捗・警告の通知先（`GifExportEvent`）
+///
+/// # 戻り値
+/// 作成したフレーム数。
+pub fn export_image_folder_to_gif(
+    source_dir: &Path,
+    output_path: &Path,
+    max_width: u32,
+    frame_delay_ms: u32,
+    notify: &dyn Fn(GifExportEvent),
+) -> Result<usize, Box<dyn std::error::Error>> {
+    if !source_dir.exists() {
+        return Err(format!(
+            "❌ 指定されたフォルダーが存在しません: {}",
+            source_dir.display()
+        )
+        .into());
+    }
+
+    let entries = collect_capture_images(source_dir)?;
+    if entries.is_empty() {
+        return Err("対象の画像ファイルが見つかりませんでした".into());
+    }
+
+    let total_files = entries.len();
+    let mut encoder =
+        GifEncoder::new_with_speed(BufWriter::new(File::create(output_path)?), QUANTIZE_SPEED);
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    // 全フレームを1枚目のサイズに揃える（GIFの論理画面サイズは1枚目で決まるため）
+    let mut frame_size: Option<(u32, u32)> = None;
+    let mut frame_count = 0;
+
+    for (index, path) in entries.iter().enumerate() {
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        notify(GifExportEvent::Converting {
+            file: filename.clone(),
+            index: index + 1,
+            total: total_files,
+        });
+
+        // AVIFはデコーダーを同梱していないためスキップ
+        let is_avif = path
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("avif"));
+        let loaded = if is_avif {
+            Err(ImageSkipReason::Avif)
+        } else {
+            match ImageReader::open(path).and_then(|reader| reader.with_guessed_format()) {
+                Ok(reader) => reader
+                    .decode()
+                    .map_err(|e| ImageSkipReason::Decode(e.to_string())),
+                Err(e) => Err(ImageSkipReason::Read(e.to_string())),
+            }
+        };
+        let img = match loaded {
+            Ok(img) => img,
+            Err(reason) => {
+                notify(GifExportEvent::Skipped {
+                    file: filename,
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        let (width, height) = *frame_size.get_or_insert_with(|| {
+            let size = fit_to_max_width(img.width(), img.height(), max_width);
+            let estimated_bytes = size.0 as u64 * size.1 as u64 * total_files as u64;
+            if estimated_bytes > SIZE_WARNING_BYTES {
+                notify(GifExportEvent::LargeOutput {
+                    estimated_bytes,
+                    width: size.0,
+                    height: size.1,
+                    frames: total_files,
+                });
+            }
+            size
+        });
+
+        let rgba = if (img.width(), img.height()) == (width, height) {
+            img.to_rgba8()
+        } else {
+            image::imageops::resize(&img.to_rgba8(), width, height, FilterType::Lanczos3)
+        };
+
+        encoder.encode_frame(Frame::from_parts(
+            rgba,
+            0,
+            0,
+            Delay::from_numer_denom_ms(frame_delay_ms, 1),
+        ))?;
+        frame_count += 1;
+    }
+
+    if frame_count == 0 {
+        return Err("GIFに変換できる画像がありませんでした".into());
+    }
+
+    Ok(frame_count)
+}
+
+/// 縦横比を保ったまま、幅が `max_width` 以下になるサイズを計算する（拡大なし、最小1px）
+fn fit_to_max_width(width: u32, height: u32, max_width: u32) -> (u32, u32) {
+    if max_width == 0 || width <= max_width {
+        return (width.max(1), height.max(1));
+    }
+    let scaled_height = (height as u64 * max_width as u64 / width.max(1) as u64) as u32;
+    (max_width, scaled_height.max(1))
+}
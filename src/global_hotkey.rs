@@ -0,0 +1,111 @@
+/*
+============================================================================
+グローバルホットキー登録モジュール (global_hotkey.rs)
+============================================================================
+
+【ファイル概要】
+クイックキャプチャ・前回の領域の再キャプチャのホットキーを `RegisterHotKey` でメインダイアログに登録し、
+`WM_HOTKEY` を受け取ってキャプチャを実行するモジュールです。
+キーボードフックでキー入力を横取りする方式と異なり、OSが登録済みの組み合わせだけを通知するため、
+他のアプリが使っているショートカットを奪わず、使用中の組み合わせは登録の失敗として検出できます。
+
+【主要機能】
+1.  **`register_global_hotkeys`**: `WM_INITDIALOG` で設定（`AppState.quick_window_capture_hotkey` /
+    `AppState.repeat_last_capture_hotkey`）のホットキーを登録
+    -   登録できない場合（他のアプリが使用中など）はログに記録して続行（そのホットキーなしで起動）
+2.  **`unregister_global_hotkeys`**: `WM_DESTROY` で登録を解除
+3.  **`handle_hotkey`**: `WM_HOTKEY` のIDに応じてキャプチャを実行
+
+【技術仕様】
+-   **ID**: `HOTKEY_ID_QUICK_WINDOW_CAPTURE` / `HOTKEY_ID_REPEAT_LAST_CAPTURE`（constants.rs）
+-   **キーリピート**: `MOD_NOREPEAT` を付けて登録し、押し続けても1回だけ実行する
+-   **実行条件**: エリア選択中はオーバーレイが画面を覆っているため実行しない。
+    再キャプチャはキャプチャモード中も実行しない（キャプチャモードのクリックと連番が競合するため）
+-   **既定値**: Ctrl+Alt+Shift+W / Ctrl+Alt+Shift+R（`clickcapture::hotkey`、
+    レジストリ `QuickWindowCaptureHotkey` / `RepeatLastCaptureHotkey` で変更可）
+
+【AI解析用：依存関係】
+-   `clickcapture::hotkey`（ライブラリ）: `Hotkey` の定義・設定文字列の解析
+-   `app_state.rs`: 設定から読み込んだホットキーとモードの参照
+-   `screen_capture.rs`: `capture_window_under_cursor` / `repeat_last_capture`
+-   `ui/dialog_handler.rs`: `WM_INITDIALOG` / `WM_DESTROY` / `WM_HOTKEY` から呼び出し
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Input::KeyboardAndMouse::{
+        HOT_KEY_MODIFIERS, MOD_NOREPEAT, RegisterHotKey, UnregisterHotKey,
+    },
+};
+
+use crate::{
+    app_state::AppState,
+    constants::{HOTKEY_ID_QUICK_WINDOW_CAPTURE, HOTKEY_ID_REPEAT_LAST_CAPTURE},
+    screen_capture::{capture_window_under_cursor, repeat_last_capture},
+    strings,
+    system_utils::app_log,
+};
+
+/// 登録するホットキーのID（解除もこの順に行う）
+const HOTKEY_IDS: [i32; 2] = [
+    HOTKEY_ID_QUICK_WINDOW_CAPTURE,
+    HOTKEY_ID_REPEAT_LAST_CAPTURE,
+];
+
+/// 【ホットキー登録】設定のホットキーをメインダイアログに登録する
+///
+/// # 引数
+/// * `hwnd` - `WM_HOTKEY` を受け取るメインダイアログのハンドル
+pub fn register_global_hotkeys(hwnd: HWND) {
+    let app_state = AppState::get_app_state_ref();
+    for id in HOTKEY_IDS {
+        let hotkey = match id {
+            HOTKEY_ID_REPEAT_LAST_CAPTURE => app_state.repeat_last_capture_hotkey,
+            _ => app_state.quick_window_capture_hotkey,
+        };
+        let result = unsafe {
+            RegisterHotKey(
+                Some(hwnd),
+                id,
+                HOT_KEY_MODIFIERS(hotkey.modifiers) | MOD_NOREPEAT,
+                hotkey.vk,
+            )
+        };
+        match result {
+            Ok(()) => app_log(&strings::hotkey_registered(id, hotkey)),
+            Err(e) => app_log(&strings::hotkey_register_failed(id, hotkey, &e)),
+        }
+    }
+}
+
+/// 【ホットキー解除】登録したホットキーを解除する（未登録の場合は何もしない）
+///
+/// # 引数
+/// * `hwnd` - 登録したメインダイアログのハンドル
+pub fn unregister_global_hotkeys(hwnd: HWND) {
+    for id in HOTKEY_IDS {
+        let _ = unsafe { UnregisterHotKey(Some(hwnd), id) };
+    }
+}
+
+/// 【WM_HOTKEY処理】押されたホットキーに応じてキャプチャを実行する
+///
+/// # 引数
+/// * `id` - `WM_HOTKEY` の `wparam`（登録時のID）
+pub fn handle_hotkey(id: i32) {
+    let app_state = AppState::get_app_state_ref();
+    match id {
+        // カーソル下のウィンドウのクライアント領域をドラッグなしでキャプチャ
+        // エリア選択中はオーバーレイが画面を覆っているため対象外
+        HOTKEY_ID_QUICK_WINDOW_CAPTURE if !app_state.is_area_select_mode() => {
+            capture_window_under_cursor();
+        }
+        // 前回の領域をオーバーレイを出さずに即座に保存（キャプチャモード・エリア選択モード以外のみ）
+        HOTKEY_ID_REPEAT_LAST_CAPTURE
+            if !app_state.is_capture_mode() && !app_state.is_area_select_mode() =>
+        {
+            repeat_last_capture();
+        }
+        _ => {}
+    }
+}
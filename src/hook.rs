@@ -13,15 +13,24 @@
 1.  **統合フックインストール (`install_hooks`)**:
     -   `keyboard::install_keyboard_hook()` と `mouse::install_mouse_hook()` を一度に呼び出します。
 2.  **統合フックアンインストール (`uninstall_hooks`)**:
-    -   `keyboard::uninstall_keyboard_hook()` と `mouse::uninstall_mouse_hook()` を一度に呼び出し、リソースを解放します。
+    -   `keyboard::uninstall_keyboard_hook()` と `mouse::uninstall_mouse_hook()` を呼び出し、両方のフックを解放します。
+    -   モード外のホットキー（クイックキャプチャ・再キャプチャ）はフックではなく `RegisterHotKey`（`global_hotkey.rs`）で受け取るため、
+        モード外ではキー入力を監視しません。
 
 【設計意図】
 -   **関心の分離**: フックのインストール/アンインストールの呼び出しをこのモジュールに集約することで、呼び出し元のコード（例: `area_select.rs`）をシンプルに保ちます。
 -   **保守性の向上**: 将来的に新しい種類のフックが追加された場合でも、変更はこのモジュール内に限定され、呼び出し元への影響を最小限に抑えることができます。
 
+3.  **注入入力ポリシー (`InjectedInputPolicy`, `is_trigger_input_allowed`)**:
+    -   `LLMHF_INJECTED` / `LLKHF_INJECTED` フラグが立った入力（`SendInput` 等で合成された入力）を
+        キャプチャトリガーやESCキーとして扱うかどうかを判定します。
+    -   自動クリックの合成クリック（`dwExtraInfo == AUTO_CLICK_EXTRA_INFO`）はポリシーに関係なく常に許可します。
+    -   `is_auto_click_input` により、自動クリック実行中は自アプリの合成クリックのみをキャプチャトリガーとします。
+
 【AI解析用：依存関係】
 -   `hook/keyboard.rs`: キーボードフックの実装。
 -   `hook/mouse.rs`: マウスフックの実装。
+-   `clickcapture::input_tag`（ライブラリ）: 識別タグ・注入入力ポリシーの判定本体（Win32 APIなし、単体テストあり）。
 -   `area_select.rs`, `screen_capture.rs`: モードの開始/終了時にこのモジュールの関数を呼び出す。
 
 */
@@ -29,10 +38,26 @@
 pub mod keyboard;
 pub mod mouse;
 
+use crate::app_state::AppState;
+pub use clickcapture::input_tag::{InjectedInputPolicy, is_auto_click_input};
+use clickcapture::input_tag::is_trigger_input_allowed_by;
+
+/// フックで受け取った入力を、現在の `AppState::injected_input_policy` でキャプチャトリガーとして扱ってよいか判定する
+///
+/// 判定内容は `clickcapture::input_tag::is_trigger_input_allowed_by` を参照してください。
+pub fn is_trigger_input_allowed(injected: bool, extra_info: usize) -> bool {
+    is_trigger_input_allowed_by(
+        AppState::get_app_state_ref().injected_input_policy,
+        injected,
+        extra_info,
+    )
+}
+
 /// マウスフックとキーボードフックの両方をインストールする
 ///
 /// システム全体のマウスイベントとキーボードイベントの監視を開始します。
-/// エリア選択モードやキャプチャモードの開始時に呼び出されます。
+/// エリア選択モードやキャプチャモードの開始時に、`ModeEffect::InstallHooks` として
+/// `mode_effects.rs` の `apply_mode_effects` から呼び出されます。
 pub fn install_hooks() {
     keyboard::install_keyboard_hook();
     mouse::install_mouse_hook();
@@ -40,9 +65,8 @@ pub fn install_hooks() {
 
 /// マウスフックとキーボードフックの両方をアンインストールする
 ///
-/// システム全体のマウスイベントとキーボードイベントの監視を停止し、
-/// 関連するシステムリソースを解放します。
-/// モードの終了時やアプリケーションのクリーンアップ時に呼び出されます。
+/// システム全体のマウスイベントとキーボードイベントの監視を停止し、関連するシステムリソースを解放します。
+/// モードの終了時に、`ModeEffect::UninstallHooks` として `apply_mode_effects` から呼び出されます。
 pub fn uninstall_hooks() {
     keyboard::uninstall_keyboard_hook();
     mouse::uninstall_mouse_hook();
@@ -11,8 +11,12 @@ Windows低レベルキーボードフックAPIを使用して、システム全
 【主要機能】
 1. キーボードフックのインストール/アンインストール（install/uninstall_keyboard_hook）
 2. エスケープキー検出による自動モード終了（low_level_keyboard_proc）
-3. キャプチャモード終了処理（is_capture_mode = false）
+3. キャプチャモード終了処理（AppMode::Capturing → Idle）
 4. エリア選択モード終了処理（cancel_area_select_mode呼び出し）
+5. エリア選択中のTabキーによる選択範囲の強調方法切り替え（selection_style）
+   Gキーによる三分割グリッドの表示切り替え（show_selection_grid）
+6. 長い自動クリックの実行中のESCは、すぐに終了せずに停止の確認を要求（WM_ESC_STOP_CONFIRM）
+   確認ダイアログの表示中にもう一度ESCを押すと「はい」を選んだことにして停止
 
 【アーキテクチャパターン】
 - システムレベルフック：SetWindowsHookExW(WH_KEYBOARD_LL)使用
@@ -21,13 +25,15 @@ Windows低レベルキーボードフックAPIを使用して、システム全
 - リソース管理：フック設定/解除の確実な実行
 
 【状態フロー図】
-初期状態 → install_keyboard_hook() → フック監視中
+初期状態 → install_keyboard_hook()（モード開始時） → フック監視中
                                       ↓ (ESCキー検出)
                                  low_level_keyboard_proc()
                                       ↓
-                    ┌─ is_capture_mode=true → キャプチャモード終了
-                    └─ is_area_select_mode=true → エリア選択モード終了
+                    ┌─ AppMode::Capturing → キャプチャモード終了（残り回数が多い自動クリック中は確認）
+                    └─ AppMode::AreaSelecting → エリア選択モード終了
                                       ↓
+                              フック監視中
+                                      ↓ (モード終了時)
                               uninstall_keyboard_hook()
                                       ↓
                                    初期状態
@@ -35,7 +41,10 @@ Windows低レベルキーボードフックAPIを使用して、システム全
 【技術仕様】
 - Windows API: SetWindowsHookExW, UnhookWindowsHookEx, CallNextHookEx
 - フックタイプ: WH_KEYBOARD_LL（低レベルキーボードフック）
-- 監視対象: VK_ESCAPE（仮想キーコード27）
+- 監視対象: VK_ESCAPE（仮想キーコード27）、VK_TAB（9、エリア選択中の強調方法切り替え）、
+            Gキー（0x47、エリア選択中の三分割グリッド切り替え）
+            （クイックキャプチャ・再キャプチャのホットキーは RegisterHotKey で登録、global_hotkey.rs）
+- 注入入力: 「実入力のみ」有効時は LLKHF_INJECTED の立ったキー入力を無視
 - スレッド対応: 全スレッド監視（dwThreadId = 0）
 - メモリ管理: SafeHHOOK wrapperによる安全なハンドル管理
 
@@ -83,6 +92,17 @@ use crate::screen_capture::*;
 // システムユーティリティ（ログ出力など）
 use crate::system_utils::app_log;
 
+// 表示文字列（ログ）
+use crate::strings;
+
+// 注入入力ポリシー判定
+use crate::hook::is_trigger_input_allowed;
+
+// 停止の確認の要求メッセージ
+use crate::constants::WM_ESC_STOP_CONFIRM;
+
+// オーバーレイ共通トレイト（再描画）
+use crate::overlay::*;
 
 /*
 ============================================================================
@@ -103,8 +123,7 @@ use crate::system_utils::app_log;
 //   - 既存フックが存在する場合は重複インストールを回避
 //
 // 呼び出しタイミング：
-//   - キャプチャモード開始時（toggle_capture_mode内）
-//   - エリア選択モード開始時（必要に応じて）
+//   - キャプチャモード・エリア選択モード開始時（install_hooks内）
 //
 // エラーハンドリング：
 //   - フック設定失敗時はunwrap()でパニック（システム機能として必須）
@@ -148,8 +167,7 @@ pub fn install_keyboard_hook() {
 //   - フックが存在しない場合は何もしない（冪等性保証）
 //
 // 呼び出しタイミング：
-//   - キャプチャモード終了時（toggle_capture_mode内）
-//   - アプリケーション終了時（cleanup処理）
+//   - キャプチャモード・エリア選択モード終了時（uninstall_hooks内）
 //   - エラー時の緊急クリーンアップ
 //
 // エラーハンドリング：
@@ -235,27 +253,76 @@ unsafe extern "system" fn low_level_keyboard_proc(
                     // === フェーズ4: 仮想キーコード抽出 ===
                     let vk_code = (*keyboard_struct).vkCode;
 
+                    // 「実入力のみ」有効時は、他ツールが注入したキー入力でモードを終了させない
+                    let trigger_allowed = is_trigger_input_allowed(
+                        (*keyboard_struct).flags.contains(LLKHF_INJECTED),
+                        (*keyboard_struct).dwExtraInfo,
+                    );
+
                     // === フェーズ5: エスケープキー処理判定 ===
                     let mut escape_key_handled = false; // イベント消費フラグ
 
                     // エスケープキー（VK_ESCAPE = 27）検出時の処理分岐
                     // === キャプチャモード終了処理 ===
-                    let is_capture_mode = app_state.is_capture_mode;
-                    if vk_code == 27 && is_capture_mode {
-                        println!("エスケープキーによるキャプチャモード終了検出");
-                        toggle_capture_mode(); // モード切替処理を呼び出し
+                    let is_capture_mode = app_state.is_capture_mode();
+                    if vk_code == 27 && is_capture_mode && trigger_allowed {
+                        if app_state.auto_clicker.is_stop_confirming() {
+                            // 確認ダイアログの表示中にもう一度ESC：「はい」を選んだことにして停止
+                            if !answer_esc_stop_confirm() {
+                                // ダイアログが見つからない場合は、確認中のまま止まらないようここで終了する
+                                app_state.auto_clicker.set_stop_confirming(false);
+                                toggle_capture_mode();
+                            }
+                        } else if app_state.auto_clicker.needs_stop_confirmation() {
+                            // 残り回数が多い自動クリック中は、誤操作でやり直さないよう確認する
+                            // （フック内でダイアログを表示するとフックがタイムアウトするため、メッセージで後から表示）
+                            app_state.auto_clicker.set_stop_confirming(true);
+                            if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+                                let _ = PostMessageW(
+                                    Some(*hwnd),
+                                    WM_ESC_STOP_CONFIRM,
+                                    WPARAM(0),
+                                    LPARAM(0),
+                                );
+                            }
+                        } else {
+                            println!("エスケープキーによるキャプチャモード終了検出");
+                            toggle_capture_mode(); // モード切替処理を呼び出し
+                        }
                         escape_key_handled = true; // イベント消費フラグを立てる
                     }
 
                     // === エリア選択モード終了処理 ===
-                    let is_area_select_mode = app_state.is_area_select_mode;
-                    if vk_code == 27 && is_area_select_mode {
+                    let is_area_select_mode = app_state.is_area_select_mode();
+                    if vk_code == 27 && is_area_select_mode && trigger_allowed {
                         // エリア選択モード終了（オーバーレイ削除も含む）
                         cancel_area_select_mode();
-                        app_log("エリア選択モードを終了しました (エスケープキー)");
+                        app_log(strings::area_select_cancelled_by_escape());
                         escape_key_handled = true; // イベント消費フラグを立てる
                     }
 
+                    // === 選択領域の強調方法切り替え（VK_TAB = 9） ===
+                    if vk_code == 9 && app_state.is_area_select_mode() && trigger_allowed {
+                        app_state.selection_style = app_state.selection_style.next();
+                        app_log(&strings::selection_style_changed(
+                            app_state.selection_style.label(),
+                        ));
+                        if let Some(overlay) = app_state.area_select_overlay.as_ref() {
+                            overlay.refresh_overlay();
+                        }
+                        escape_key_handled = true; // Tabキーも他のアプリケーションに渡さない
+                    }
+
+                    // === 三分割グリッドの表示切り替え（Gキー = 0x47） ===
+                    if vk_code == 0x47 && app_state.is_area_select_mode() && trigger_allowed {
+                        app_state.show_selection_grid = !app_state.show_selection_grid;
+                        app_log(strings::selection_grid_toggled(app_state.show_selection_grid));
+                        if let Some(overlay) = app_state.area_select_overlay.as_ref() {
+                            overlay.refresh_overlay();
+                        }
+                        escape_key_handled = true; // Gキーも他のアプリケーションに渡さない
+                    }
+
                     // === フェーズ6: イベント消費判定 ===
                     if escape_key_handled {
                         // エスケープキーを他のアプリケーションに渡さない
@@ -277,6 +344,42 @@ unsafe extern "system" fn low_level_keyboard_proc(
     }
 }
 
+// 【停止の確認への回答】表示中の停止の確認ダイアログで「はい」を選ぶ
+//
+// 概要：
+//   確認ダイアログ（メインダイアログが所有するMessageBox）が前面にある場合、
+//   「はい」ボタンのWM_COMMANDを送ってダイアログを閉じる
+//   停止の処理はダイアログを表示した WM_ESC_STOP_CONFIRM の処理側で行う
+//
+// 戻り値：
+//   true  - 確認ダイアログに「はい」を送った
+//   false - 確認ダイアログが前面にない（表示前・別のウィンドウが前面）
+fn answer_esc_stop_confirm() -> bool {
+    let Some(dialog_hwnd) = AppState::shared_dialog_hwnd() else {
+        return false;
+    };
+
+    unsafe {
+        let foreground = GetForegroundWindow();
+        // 所有者がメインダイアログのダイアログクラス（#32770）のウィンドウのみを対象にする
+        let owned_by_dialog =
+            GetWindow(foreground, GW_OWNER).is_ok_and(|owner| owner == *dialog_hwnd);
+        let mut class_name = [0u16; 16];
+        let length = GetClassNameW(foreground, &mut class_name) as usize;
+        if !owned_by_dialog || String::from_utf16_lossy(&class_name[..length]) != "#32770" {
+            return false;
+        }
+
+        PostMessageW(
+            Some(foreground),
+            WM_COMMAND,
+            WPARAM(IDYES.0 as usize),
+            LPARAM(0),
+        )
+        .is_ok()
+    }
+}
+
 /*
 ============================================================================
 モジュール設計まとめ（AI解析用）
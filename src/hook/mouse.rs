@@ -18,20 +18,30 @@
 【技術仕様】
 - フックタイプ：WH_MOUSE_LL（低レベルマウスフック）
 - 監視範囲：システム全体（全アプリケーション）
-- イベント：WM_MOUSEMOVE, WM_LBUTTONDOWN, WM_LBUTTONUP
+- イベント：WM_MOUSEMOVE, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
+            WM_RBUTTONDOWN, WM_RBUTTONUP
 - パフォーマンス：unsafe最適化による高速処理
 - スレッドセーフ：AppState経由の安全な状態共有
 
 【処理フロー】
 SetWindowsHookExW → low_level_mouse_proc コールバック → イベント種別判定
                          ├─ WM_MOUSEMOVE → カーソル位置更新 + オーバーレイ位置/描画更新
-                         │   ├─ is_capture_mode: capturing_overlay の位置を更新
-                         │   └─ is_dragging: area_select_overlay を再描画
+                         │   ├─ 自動クリック実行中: ユーザー操作の時刻を記録（note_user_input）
+                         │   ├─ Capturing: capturing_overlay の位置を更新
+                         │   ├─ AreaSelecting { dragging: true }: area_select_overlay を再描画
+                         │   └─ AreaSelecting { dragging: false }: 十字ガイド線のため area_select_overlay を再描画
                          ├─ WM_LBUTTONDOWN → ドラッグ開始 or キャプチャ実行
-                         │   ├─ is_area_select_mode: ドラッグ開始状態に移行
-                         │   └─ is_capture_mode: 自動クリック開始 or 単発キャプチャ実行
-                         └─ WM_LBUTTONUP → ドラッグ終了
-                             └─ is_dragging: エリア選択を完了し、イベントを消費
+                         │   ├─ AreaSelecting: ドラッグ開始状態に移行（Ctrl押下時はマスク領域の選択）
+                         │   └─ Capturing: 自動クリック開始 or 単発キャプチャ実行
+                         ├─ WM_LBUTTONUP → ドラッグ終了 or キャプチャ実行
+                         │   ├─ AreaSelecting { dragging: true }: エリア選択を完了し、イベントを消費
+                         │   └─ Capturing: トリガーが「左」を含む場合にキャプチャ実行
+                         ├─ WM_MBUTTONDOWN/UP → トリガーが「中」を含む場合にキャプチャ実行
+                         │   └─ キャプチャ・自動クリック開始時のみ消費（押下も同じ判定で消費し、オートスクロール防止）
+                         │      自動クリック実行中は透過（合成クリックのみがトリガー）
+                         └─ WM_RBUTTONDOWN/UP → トリガーが「右」の場合にキャプチャ実行
+                             └─ キャプチャ・自動クリック開始時のみ消費（押下も同じ判定で消費し、コンテキストメニュー防止）
+                                自動クリック実行中は透過（合成クリックのみがトリガー）
                          ↓
                    CallNextHookEx → 他のアプリへイベントを継続（キャプチャモードのクリックは透過）
 
@@ -49,9 +59,11 @@ use windows::Win32::{
     Foundation::{LPARAM, LRESULT, POINT, WPARAM}, // 基本的なデータ型
     System::{
         LibraryLoader::GetModuleHandleW, // プログラムのハンドル取得
+        SystemInformation::GetTickCount, // デバウンス判定用の経過時間取得
     },
 
     UI::{
+        Input::KeyboardAndMouse::{GetAsyncKeyState, VK_CONTROL, VK_SHIFT}, // マスク領域（Ctrl）・2つ目の領域（Shift）判定
         WindowsAndMessaging::*, // ウィンドウとメッセージ処理
     },
 };
@@ -65,9 +77,80 @@ use crate::area_select::*;
 // オーバーレイ管理関数
 use crate::overlay::*;
 
+// モード遷移の副作用（オーバーレイの再描画）
+use crate::mode_effects::apply_mode_effects;
+
 // 画面キャプチャ管理関数
 use crate::screen_capture::*;
 
+// ウィンドウ単体キャプチャ機能
+use crate::window_capture::capture_window_at_point_with_counter;
+
+// 注入入力ポリシー判定・自動クリック合成クリックの識別
+use crate::hook::{is_auto_click_input, is_trigger_input_allowed};
+
+// 自動クリックの動作モード
+use crate::auto_click::AutoClickMode;
+
+// 表示文字列（日本語/英語）
+use crate::strings;
+
+// ログ出力
+use crate::system_utils::app_log;
+
+/// キャプチャモード中にキャプチャを実行するマウスボタンの設定
+///
+/// 自動クリック実行中の合成クリック（`perform_mouse_click`による左クリック）は
+/// この設定に関係なく常にキャプチャ対象となる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureTrigger {
+    /// 左クリックでキャプチャ（従来動作、デフォルト）
+    Left = 0,
+    /// 中クリックでキャプチャ（左クリックは対象アプリへそのまま透過）
+    Middle = 1,
+    /// 左クリック・中クリックのどちらでもキャプチャ
+    LeftAndMiddle = 2,
+    /// 右クリックでキャプチャ（左クリックは対象アプリへそのまま透過、右クリックのメニューは開かない）
+    Right = 3,
+}
+
+impl CaptureTrigger {
+    /// コンボボックス項目データから復元（不明な値は左クリック扱い）
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CaptureTrigger::Middle,
+            2 => CaptureTrigger::LeftAndMiddle,
+            3 => CaptureTrigger::Right,
+            _ => CaptureTrigger::Left,
+        }
+    }
+
+    /// コンボボックス表示用ラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaptureTrigger::Left => strings::capture_trigger_left(),
+            CaptureTrigger::Middle => strings::capture_trigger_middle(),
+            CaptureTrigger::LeftAndMiddle => strings::capture_trigger_left_and_middle(),
+            CaptureTrigger::Right => strings::capture_trigger_right(),
+        }
+    }
+
+    /// 左クリックがキャプチャトリガーか
+    pub fn accepts_left(&self) -> bool {
+        matches!(self, CaptureTrigger::Left | CaptureTrigger::LeftAndMiddle)
+    }
+
+    /// 中クリックがキャプチャトリガーか
+    pub fn accepts_middle(&self) -> bool {
+        matches!(self, CaptureTrigger::Middle | CaptureTrigger::LeftAndMiddle)
+    }
+
+    /// 右クリックがキャプチャトリガーか
+    pub fn accepts_right(&self) -> bool {
+        matches!(self, CaptureTrigger::Right)
+    }
+}
+
 // マウスフックを開始する関数
 pub fn install_mouse_hook() {
     unsafe {
@@ -117,12 +200,14 @@ pub fn uninstall_mouse_hook() {
 
  【AI解析用：イベント処理フロー】
  WM_MOUSEMOVE: 常時 → 座標更新 + 各オーバーレイの更新
- WM_LBUTTONDOWN: AppState.is_area_select_mode時 → ドラッグ開始 / AppState.is_capture_mode時 → キャプチャ実行
- WM_LBUTTONUP: AppState.is_dragging時 → ドラッグ終了、エリア選択完了
+ WM_LBUTTONDOWN: AppMode::AreaSelecting時 → ドラッグ開始 / AppMode::Capturing時 → キャプチャ実行
+ WM_LBUTTONUP: AppMode::AreaSelecting { dragging: true }時 → ドラッグ終了、エリア選択完了
 
  【重要な条件分岐】
- 1. AppState.is_area_select_mode: エリア選択ボタンで制御される状態
- 2. AppState.is_dragging: WM_LBUTTONDOWN～WM_LBUTTONUP間の状態
+ 1. AppState.mode == AreaSelecting: エリア選択ボタンで制御される状態
+ 2. AreaSelecting { dragging: true }: WM_LBUTTONDOWN～WM_LBUTTONUP間の状態
+ 3. trigger_allowed: 「実入力のみ」有効時、他ツールが注入したクリックはキャプチャせず透過
+ 4. is_auto_click_event: 自動クリック実行中は dwExtraInfo のタグが一致するクリックのみキャプチャ
 
  【座標系の一貫性】
  - 全ての座標はスクリーン絶対座標（画面左上が0,0）
@@ -147,8 +232,26 @@ unsafe extern "system" fn low_level_mouse_proc(
                 POINT { x: 0, y: 0 } // エラー時はゼロ座標
             };
 
-            // グローバルAppState構造体に現在のマウス位置を保存
-            app_state.current_mouse_pos = current_pos;
+            // 注入された入力（SendInput等）をキャプチャトリガーとして扱ってよいか
+            // 自動クリックの合成クリックは dwExtraInfo のタグで判別し、常に許可する
+            let trigger_allowed = if !mouse_struct.is_null() {
+                is_trigger_input_allowed(
+                    (*mouse_struct).flags & LLMHF_INJECTED != 0,
+                    (*mouse_struct).dwExtraInfo,
+                )
+            } else {
+                true
+            };
+
+            // 自動クリック（perform_mouse_click）が送信した合成クリックか
+            let is_auto_click_event = !mouse_struct.is_null()
+                && is_auto_click_input(
+                    (*mouse_struct).flags & LLMHF_INJECTED != 0,
+                    (*mouse_struct).dwExtraInfo,
+                );
+
+            // 現在のマウス位置を共有状態に保存（オーバーレイの描画で参照）
+            AppState::shared().set_mouse_pos(current_pos);
 
             // マウスイベントの種類によって処理を分岐
             match wparam.0 as u32 {
@@ -156,18 +259,25 @@ unsafe extern "system" fn low_level_mouse_proc(
                     // ===== マウス移動イベント =====
                     // マウスが移動するたびに呼び出される
 
+                    // 自動クリックの実行中のユーザー操作を記録（「操作中は一時停止」有効時に次のクリックを遅らせる）
+                    if !is_auto_click_event && app_state.auto_clicker.is_running() {
+                        app_state.auto_clicker.note_user_input(current_pos);
+                    }
+
                     // 🔧 キャプチャモードオーバーレイの位置更新
-                    if app_state.is_capture_mode {
+                    if app_state.is_capture_mode() {
                         if let Some(overlay) = app_state.capturing_overlay.as_mut() {
                             overlay.set_window_pos();
                         }
                     }
 
-                    // エリア選択オーバーレイ表示中かつドラッグ中の場合
-                    let is_dragging = app_state.is_area_select_mode && app_state.is_dragging;
-
-                    if is_dragging {
-                        app_state.drag_end = current_pos;
+                    // エリア選択オーバーレイ表示中の場合
+                    if let AppMode::AreaSelecting { dragging } = app_state.mode {
+                        // ドラッグ中：選択範囲の終点を更新
+                        // ドラッグ開始前：カーソルに追従する十字ガイド線・座標ラベルのみ（ドラッグ中と同じ経路で再描画）
+                        if dragging {
+                            app_state.drag_end = current_pos;
+                        }
 
                         // エリア選択オーバーレイを再描画
                         if let Some(overlay) = app_state.area_select_overlay.as_mut() {
@@ -179,13 +289,21 @@ unsafe extern "system" fn low_level_mouse_proc(
                     let mut block_mouse_propagation = false; // 今回はfalseに設定（下のウィンドウにも渡す）
 
                     // エリア選択モードの時のみオーバーレイを表示
-                    let is_area_select_mode = app_state.is_area_select_mode;
+                    let is_area_select_mode = app_state.is_area_select_mode();
 
                     if is_area_select_mode {
                         // 左クリック押下時：正確な座標を記録してオーバーレイを表示
                         app_state.drag_start = current_pos;
                         app_state.drag_end = current_pos;
-                        app_state.is_dragging = true;
+                        if let Some(effects) = app_state.mode.begin_drag() {
+                            let _ = apply_mode_effects(effects);
+                        }
+
+                        // Ctrlキー押下中はマスク領域の選択として扱う
+                        app_state.is_mask_dragging = GetAsyncKeyState(VK_CONTROL.0 as i32) < 0;
+                        // Shiftキー押下中は2つ目のキャプチャ領域の選択として扱う（Ctrlが優先）
+                        app_state.is_secondary_dragging =
+                            !app_state.is_mask_dragging && GetAsyncKeyState(VK_SHIFT.0 as i32) < 0;
 
                         // マウスイベントを捕獲（下のウィンドウに渡さない）
                         block_mouse_propagation = true;
@@ -197,46 +315,78 @@ unsafe extern "system" fn low_level_mouse_proc(
                 }
                 WM_LBUTTONUP => {
                     // エリア選択モード中のドラッグ終了時の処理
-                    let (is_area_select_mode, is_dragging) =
-                        (app_state.is_area_select_mode, app_state.is_dragging);
-
-                    if is_area_select_mode && is_dragging {
+                    if app_state.mode == (AppMode::AreaSelecting { dragging: true }) {
                         // 【変更】即座にキャプチャせず、選択エリアを保存
                         end_area_select_mode();
                     }
                     // 画面キャプチャモード中の左クリック処理
-                    else {
-                        if app_state.is_capture_mode {
-                            // 連続クリックが有効な場合のみ機能を初期化＆開始
-                            if app_state.auto_clicker.is_enabled()
-                                && !app_state.auto_clicker.is_running()
-                            {
-                                let _ = app_state.auto_clicker.start(current_pos);
-                                return LRESULT(1); // イベントを消費
-                            }
-
-                            // ファイル名に連番を使用してキャプチャ実行
-                            let _ = capture_screen_area_with_counter();
-
-                            println!(
-                                "画面キャプチャ実行: ファイル {}.jpg",
-                                app_state.capture_file_counter - 1
-                            );
-
-                            // 【重要】左クリック後もキャプチャモードは継続するが、
-                            // 他のアプリケーションにも左クリックイベントを渡す
-                            // return LRESULT(1); // 削除：イベント消費しない
-                        }
+                    // 【重要】左クリック後もキャプチャモードは継続するが、
+                    // 自動クリックを開始した場合を除き、他のアプリケーションにも左クリックイベントを渡す
+                    else if is_capture_trigger_click(
+                        app_state.capture_trigger.accepts_left(),
+                        trigger_allowed,
+                        is_auto_click_event,
+                    ) && handle_capture_trigger(current_pos)
+                        == CaptureTriggerAction::StartAutoClick
+                    {
+                        return LRESULT(1); // イベントを消費
                     }
                 }
-
-                // 【削除】右クリック処理は不要（エスケープキーに変更）
+                // 中クリックがトリガーの場合、離上でキャプチャする押下も消費して
+                // 下のウィンドウでオートスクロールが始まらないようにする
+                WM_MBUTTONDOWN
+                    if is_capture_trigger_click(
+                        app_state.capture_trigger.accepts_middle(),
+                        trigger_allowed,
+                        is_auto_click_event,
+                    ) && capture_trigger_action(GetTickCount())
+                        != CaptureTriggerAction::PassThrough =>
+                {
+                    return LRESULT(1); // イベントを消費
+                }
+                // 画面キャプチャモード中の中クリック処理
+                // 自動クリック待機中は左クリックと同様にクリック位置で自動クリックを開始する
+                // （キャプチャのみモード・デバウンスでキャプチャしなかったクリックは対象アプリへ透過）
+                WM_MBUTTONUP
+                    if is_capture_trigger_click(
+                        app_state.capture_trigger.accepts_middle(),
+                        trigger_allowed,
+                        is_auto_click_event,
+                    ) && handle_capture_trigger(current_pos)
+                        != CaptureTriggerAction::PassThrough =>
+                {
+                    return LRESULT(1); // イベントを消費
+                }
+                // 右クリックがトリガーの場合、離上でキャプチャする押下も消費して
+                // 下のウィンドウが押下だけを受け取った状態にならないようにする
+                WM_RBUTTONDOWN
+                    if is_capture_trigger_click(
+                        app_state.capture_trigger.accepts_right(),
+                        trigger_allowed,
+                        is_auto_click_event,
+                    ) && capture_trigger_action(GetTickCount())
+                        != CaptureTriggerAction::PassThrough =>
+                {
+                    return LRESULT(1); // イベントを消費
+                }
+                // 画面キャプチャモード中の右クリック処理（中クリックと同様）
+                // 消費するため、キャプチャしたクリックでは下のウィンドウのコンテキストメニューは開かない
+                WM_RBUTTONUP
+                    if is_capture_trigger_click(
+                        app_state.capture_trigger.accepts_right(),
+                        trigger_allowed,
+                        is_auto_click_event,
+                    ) && handle_capture_trigger(current_pos)
+                        != CaptureTriggerAction::PassThrough =>
+                {
+                    return LRESULT(1); // イベントを消費
+                }
                 _ => {}
             }
         }
 
         // エリア選択中（オーバーレイ表示中）は、マウスイベントを下のウィンドウに渡さない
-        let is_area_select_mode = app_state.is_area_select_mode;
+        let is_area_select_mode = app_state.is_area_select_mode();
 
         if is_area_select_mode
             && (wparam.0 as u32 == WM_LBUTTONDOWN || wparam.0 as u32 == WM_LBUTTONUP)
@@ -249,3 +399,127 @@ unsafe extern "system" fn low_level_mouse_proc(
         CallNextHookEx(mouse_hook, ncode, wparam, lparam)
     }
 }
+
+/// キャプチャトリガーとなるクリックで行う処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureTriggerAction {
+    /// クリック位置で自動クリックを開始する
+    StartAutoClick,
+    /// 単発キャプチャを実行する
+    Capture,
+    /// 何もせず、クリックを対象アプリへ透過させる（キャプチャのみモード・デバウンス）
+    PassThrough,
+}
+
+/// キャプチャモード中のクリックをキャプチャトリガーとして扱うか
+///
+/// 自動クリック実行中はタグ付きの合成クリックのみをトリガー設定に関係なくキャプチャし、
+/// 他ツールの注入クリックや手動クリックで余分なキャプチャが発生しないようにする。
+///
+/// # 引数
+/// * `accepts_button` - クリックしたボタンがトリガー設定に含まれるか
+/// * `trigger_allowed` - 注入入力ポリシーでトリガーとして許可されるか
+/// * `is_auto_click_event` - 自動クリックの合成クリックか
+fn is_capture_trigger_click(
+    accepts_button: bool,
+    trigger_allowed: bool,
+    is_auto_click_event: bool,
+) -> bool {
+    let app_state = AppState::get_app_state_ref();
+    app_state.is_capture_mode()
+        && if app_state.auto_clicker.is_running() {
+            is_auto_click_event
+        } else {
+            trigger_allowed && accepts_button
+        }
+}
+
+/// キャプチャトリガーのクリックで行う処理を、状態を変更せずに判定する
+///
+/// 押下（DOWN）を消費するかを離上（UP）の `handle_capture_trigger` と一致させるために使用します。
+///
+/// # 引数
+/// * `now` - 現在の `GetTickCount` の値（デバウンス判定用）
+fn capture_trigger_action(now: u32) -> CaptureTriggerAction {
+    let app_state = AppState::get_app_state_ref();
+    let auto_clicker = &app_state.auto_clicker;
+
+    // キャプチャのみモードはタイマーでキャプチャするため、
+    // ユーザーのクリックはキャプチャせずに対象アプリへ透過させる
+    if auto_clicker.is_enabled() && auto_clicker.get_mode() == AutoClickMode::CaptureOnly {
+        return CaptureTriggerAction::PassThrough;
+    }
+
+    // 連続クリックが有効な場合のみ機能を初期化＆開始
+    if auto_clicker.is_enabled() && !auto_clicker.is_running() {
+        return CaptureTriggerAction::StartAutoClick;
+    }
+
+    // デバウンス判定：前回キャプチャから一定時間内の手動クリックは
+    // キャプチャせず、クリックのみ対象アプリへ透過させる
+    // （自動クリック実行中は間隔がユーザー制御のため判定しない）
+    let elapsed = now.wrapping_sub(app_state.last_capture_tick);
+    if !auto_clicker.is_running() && elapsed < app_state.capture_debounce_ms {
+        return CaptureTriggerAction::PassThrough;
+    }
+    CaptureTriggerAction::Capture
+}
+
+/// キャプチャトリガーとなるクリック（左/中/右）を処理する
+///
+/// - 自動クリックが有効かつ未実行: クリック位置で自動クリックを開始
+/// - それ以外: デバウンス判定のうえ単発キャプチャを実行
+///
+/// # 戻り値
+/// 実際に行った処理（自動クリックを開始できなかった場合は `PassThrough`）。
+/// 呼び出し元は、何か処理した場合のみイベントを消費する。
+fn handle_capture_trigger(current_pos: POINT) -> CaptureTriggerAction {
+    let app_state = AppState::get_app_state_mut();
+    let now = unsafe { GetTickCount() };
+
+    match capture_trigger_action(now) {
+        CaptureTriggerAction::StartAutoClick => {
+            let overlay_hwnd = app_state.capturing_overlay_hwnd();
+            return match app_state.auto_clicker.start(current_pos, overlay_hwnd) {
+                Ok(()) => CaptureTriggerAction::StartAutoClick,
+                Err(e) => {
+                    app_log(&e);
+                    CaptureTriggerAction::PassThrough
+                }
+            };
+        }
+        CaptureTriggerAction::PassThrough => {
+            // キャプチャのみモード以外での透過は、デバウンスによるスキップ
+            if !app_state.auto_clicker.is_enabled() {
+                println!(
+                    "⌛ 前回キャプチャから{}msのためスキップしました（デバウンス{}ms）",
+                    now.wrapping_sub(app_state.last_capture_tick),
+                    app_state.capture_debounce_ms
+                );
+            }
+            return CaptureTriggerAction::PassThrough;
+        }
+        CaptureTriggerAction::Capture => {}
+    }
+    app_state.last_capture_tick = now;
+
+    // ファイル名に連番を使用してキャプチャ実行
+    // （ウィンドウ単体キャプチャ時はクリック位置のウィンドウをPNGで保存）
+    if app_state.window_capture_mode {
+        let _ = capture_window_at_point_with_counter(current_pos);
+    } else if let Err(e) = capture_screen_area_with_counter() {
+        // 保存先への書き込み失敗など、次のクリックでも回復しないエラーは自動クリックを停止する
+        report_capture_error(&e);
+    }
+
+    println!(
+        "画面キャプチャ実行: ファイル {}.{}",
+        app_state.capture_file_counter - 1,
+        if app_state.window_capture_mode {
+            "png"
+        } else {
+            app_state.capture_format.extension()
+        }
+    );
+    CaptureTriggerAction::Capture
+}
@@ -0,0 +1,222 @@
+/*
+============================================================================
+グローバルホットキー定義モジュール (hotkey.rs)
+============================================================================
+
+【ファイル概要】
+クイックキャプチャ・前回の領域の再キャプチャに割り当てるグローバルホットキー（修飾キー＋キー）を表し、
+設定文字列（例: `Ctrl+Alt+Shift+W`）との相互変換を行うライブラリモジュールです。
+`RegisterHotKey` による登録はバイナリ側（`global_hotkey.rs`）が行うため、Win32 APIには依存しません。
+
+【主要機能】
+1.  **`Hotkey`**: 修飾キー（`MOD_*`、`RegisterHotKey` と同じ値）と仮想キーコードの組
+2.  **`Hotkey::parse`**: 設定文字列を解析（大文字・小文字、`+` 前後の空白は区別しない）
+3.  **`Display`**: ログ・ツールチップ用の表示（`Ctrl+Alt+Shift+W` の順に正規化）
+
+【技術仕様】
+-   **既定値**: Ctrl+Alt+Shift+W（クイックキャプチャ）/ Ctrl+Alt+Shift+R（再キャプチャ）。
+    ブラウザ・エディタで使われるCtrl+Shift+W（タブを閉じる）・Ctrl+Shift+R（再読み込み）と重ならないようにする
+-   **使用できるキー**: A〜Z・0〜9・F1〜F24。Shiftのみ・修飾キーなしは通常の文字入力を奪うため不可
+
+【AI解析用：依存関係】
+-   `app_state.rs`（バイナリ側）: レジストリの設定（`QuickWindowCaptureHotkey` / `RepeatLastCaptureHotkey`）を `parse` して保持
+-   `global_hotkey.rs`（バイナリ側）: `modifiers` / `vk` を `RegisterHotKey` に渡して登録
+-   `strings.rs`（バイナリ側）: ツールチップ・ログにホットキーを表示
+*/
+
+use std::fmt;
+
+/// Altキー（`MOD_ALT`）
+pub const MOD_ALT: u32 = 0x0001;
+/// Ctrlキー（`MOD_CONTROL`）
+pub const MOD_CONTROL: u32 = 0x0002;
+/// Shiftキー（`MOD_SHIFT`）
+pub const MOD_SHIFT: u32 = 0x0004;
+/// Windowsキー（`MOD_WIN`）
+pub const MOD_WIN: u32 = 0x0008;
+
+/// 表示・解析に使う修飾キーの名前（表示順）
+const MODIFIER_NAMES: [(u32, &str); 4] = [
+    (MOD_CONTROL, "Ctrl"),
+    (MOD_ALT, "Alt"),
+    (MOD_SHIFT, "Shift"),
+    (MOD_WIN, "Win"),
+];
+
+/// 仮想キーコード F1（F1〜F24は連続した値）
+const VK_F1: u32 = 0x70;
+
+/// 【ホットキー】修飾キーとキーの組
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    /// 修飾キー（`MOD_ALT` / `MOD_CONTROL` / `MOD_SHIFT` / `MOD_WIN` の組み合わせ）
+    pub modifiers: u32,
+    /// 仮想キーコード（`'A'`〜`'Z'`・`'0'`〜`'9'` は文字コードと同じ値）
+    pub vk: u32,
+}
+
+/// クイックキャプチャ（カーソル下のウィンドウ）の既定のホットキー: Ctrl+Alt+Shift+W
+pub const DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY: Hotkey = Hotkey {
+    modifiers: MOD_CONTROL | MOD_ALT | MOD_SHIFT,
+    vk: b'W' as u32,
+};
+
+/// 前回の領域の再キャプチャの既定のホットキー: Ctrl+Alt+Shift+R
+pub const DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY: Hotkey = Hotkey {
+    modifiers: MOD_CONTROL | MOD_ALT | MOD_SHIFT,
+    vk: b'R' as u32,
+};
+
+impl Hotkey {
+    /// 設定文字列（例: `Ctrl+Alt+Shift+W`、`ctrl + f9`）を解析する
+    ///
+    /// # 戻り値
+    /// 修飾キーとキーが1つずつ以上あり、Ctrl・Alt・Winのいずれかを含む場合のみ `Some`。
+    /// 不明な名前・キーが2つ以上・修飾キーの重複・Shiftのみの場合は `None`。
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = 0;
+        let mut vk = None;
+        for token in text.split('+').map(str::trim) {
+            if let Some(&(modifier, _)) = MODIFIER_NAMES
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(token))
+            {
+                if modifiers & modifier != 0 {
+                    return None;
+                }
+                modifiers |= modifier;
+            } else if vk.replace(parse_key(token)?).is_some() {
+                return None;
+            }
+        }
+        let hotkey = Self { modifiers, vk: vk? };
+        hotkey.is_valid().then_some(hotkey)
+    }
+
+    /// 通常の文字入力を奪わない組み合わせか（Ctrl・Alt・Winのいずれかを含み、キーが有効）
+    pub fn is_valid(&self) -> bool {
+        self.modifiers & (MOD_CONTROL | MOD_ALT | MOD_WIN) != 0
+            && self.modifiers & !(MOD_CONTROL | MOD_ALT | MOD_SHIFT | MOD_WIN) == 0
+            && key_name(self.vk).is_some()
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.modifiers & modifier != 0 {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match key_name(self.vk) {
+            Some(name) => f.write_str(&name),
+            None => write!(f, "0x{:02X}", self.vk),
+        }
+    }
+}
+
+/// キーの名前（`W`・`7`・`F9`）を仮想キーコードに変換する
+fn parse_key(token: &str) -> Option<u32> {
+    let upper = token.to_ascii_uppercase();
+    match upper.as_bytes() {
+        [c] if c.is_ascii_uppercase() || c.is_ascii_digit() => Some(*c as u32),
+        [b'F', digits @ ..] if !digits.is_empty() => {
+            let number: u32 = std::str::from_utf8(digits).ok()?.parse().ok()?;
+            (1..=24).contains(&number).then(|| VK_F1 + number - 1)
+        }
+        _ => None,
+    }
+}
+
+/// 仮想キーコードの表示名（対応していないキーは `None`）
+fn key_name(vk: u32) -> Option<String> {
+    match vk {
+        0x30..=0x39 | 0x41..=0x5A => Some(char::from(vk as u8).to_string()),
+        _ if (VK_F1..VK_F1 + 24).contains(&vk) => Some(format!("F{}", vk - VK_F1 + 1)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_table() {
+        const CAS: u32 = MOD_CONTROL | MOD_ALT | MOD_SHIFT;
+        // (設定文字列, 期待値)
+        let cases = [
+            ("Ctrl+Alt+Shift+W", Some((CAS, 'W' as u32))),
+            ("shift + alt + ctrl + w", Some((CAS, 'W' as u32))),
+            ("Ctrl+F9", Some((MOD_CONTROL, 0x78))),
+            ("Win+Shift+F24", Some((MOD_WIN | MOD_SHIFT, 0x87))),
+            ("Alt+7", Some((MOD_ALT, '7' as u32))),
+            ("W", None),
+            ("Shift+W", None),
+            ("Ctrl+Shift", None),
+            ("Ctrl+W+R", None),
+            ("Ctrl+Ctrl+W", None),
+            ("Ctrl+F25", None),
+            ("Ctrl+F0", None),
+            ("Ctrl+Esc", None),
+            ("Ctrl++W", None),
+            ("", None),
+        ];
+        for (text, expected) in cases {
+            let parsed = Hotkey::parse(text).map(|hotkey| (hotkey.modifiers, hotkey.vk));
+            assert_eq!(parsed, expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for text in [
+            "Ctrl+Alt+Shift+W",
+            "Ctrl+F9",
+            "Alt+Shift+Win+0",
+            "Ctrl+Win+F24",
+        ] {
+            let hotkey = Hotkey::parse(text).unwrap();
+            assert_eq!(Hotkey::parse(&hotkey.to_string()), Some(hotkey), "{text}");
+        }
+        // 表示は Ctrl → Alt → Shift → Win の順に正規化する
+        assert_eq!(
+            Hotkey::parse("win+shift+alt+ctrl+q").unwrap().to_string(),
+            "Ctrl+Alt+Shift+Win+Q"
+        );
+    }
+
+    #[test]
+    fn defaults_avoid_common_shortcuts() {
+        assert_eq!(
+            DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY.to_string(),
+            "Ctrl+Alt+Shift+W"
+        );
+        assert_eq!(
+            DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY.to_string(),
+            "Ctrl+Alt+Shift+R"
+        );
+        assert!(DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY.is_valid());
+        assert!(DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY.is_valid());
+        assert_ne!(
+            DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY,
+            DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY
+        );
+        // ブラウザ・エディタ・Windowsでよく使われるショートカットとは重ならない
+        for common in [
+            "Ctrl+Shift+W",
+            "Ctrl+Shift+R",
+            "Ctrl+W",
+            "Ctrl+R",
+            "Ctrl+Shift+T",
+            "Ctrl+Alt+W",
+            "Ctrl+Alt+R",
+            "Win+Shift+S",
+            "Alt+F4",
+        ] {
+            let common = Hotkey::parse(common).unwrap();
+            assert_ne!(DEFAULT_QUICK_WINDOW_CAPTURE_HOTKEY, common);
+            assert_ne!(DEFAULT_REPEAT_LAST_CAPTURE_HOTKEY, common);
+        }
+    }
+}
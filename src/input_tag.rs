@@ -0,0 +1,121 @@
+/*
+============================================================================
+入力イベント識別タグモジュール (input_tag.rs)
+============================================================================
+
+【ファイル概要】
+自動クリックが `SendInput` で送信するクリックに付けるタグ（`MOUSEINPUT.dwExtraInfo`）と、
+低レベルフックで受け取った入力をキャプチャトリガーとして扱うかの判定をまとめたライブラリモジュールです。
+判定はフック構造体から取り出した値（注入フラグ・`dwExtraInfo`）のみで行い、Win32 APIに依存しません。
+
+【主要機能】
+1.  **`AUTO_CLICK_EXTRA_INFO`**: 自動クリックの合成クリックに設定する識別子
+2.  **`is_auto_click_input`**: 注入フラグとタグから、自アプリの合成クリックかを判定
+3.  **`InjectedInputPolicy` / `is_trigger_input_allowed_by`**: 他のツールが注入した入力をトリガーとして扱うかを判定
+
+【技術仕様】
+-   **タグ**: ASCII "CLKC"（`0x434C4B43`）。注入フラグ（`LLMHF_INJECTED`）が立っている場合のみ有効とし、
+    実際の入力に偶然同じ値が入っていても合成クリックとはみなさない
+
+【AI解析用：依存関係】
+-   `auto_click.rs`（バイナリ側）: `perform_mouse_click` で `dwExtraInfo` に `AUTO_CLICK_EXTRA_INFO` を設定
+-   `hook.rs`（バイナリ側）: `AppState::injected_input_policy` を渡して `is_trigger_input_allowed_by` を呼び出す
+-   `hook/mouse.rs`（バイナリ側）: 自動クリック実行中は `is_auto_click_input` の合成クリックのみをトリガーにする
+*/
+
+/// 自動クリック（`perform_mouse_click`）が送信したクリックの識別子（ASCII "CLKC"）
+///
+/// `SendInput` で送信する `MOUSEINPUT.dwExtraInfo` に設定し、フック側で自アプリの合成入力を判別します。
+pub const AUTO_CLICK_EXTRA_INFO: usize = 0x434C_4B43;
+
+/// 他のツールが注入した入力（`SendInput` 等）をトリガーとして扱うかどうかのポリシー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InjectedInputPolicy {
+    /// 注入された入力もトリガーとして扱う（従来動作、デフォルト）
+    AllowInjected,
+    /// 実際の入力と自動クリックの合成クリックのみをトリガーとして扱う
+    RealInputOnly,
+}
+
+/// フックで受け取った入力が自動クリック（`perform_mouse_click`）の合成クリックか判定する
+///
+/// 注入フラグが立ち、かつ `dwExtraInfo` に `AUTO_CLICK_EXTRA_INFO` が設定されている場合のみ
+/// 自アプリの合成クリックとみなします。他の自動化ツールが注入したクリックは対象外です。
+///
+/// # 引数
+/// * `injected` - `LLMHF_INJECTED` フラグが立っているか
+/// * `extra_info` - フック構造体の `dwExtraInfo`
+pub fn is_auto_click_input(injected: bool, extra_info: usize) -> bool {
+    injected && extra_info == AUTO_CLICK_EXTRA_INFO
+}
+
+/// フックで受け取った入力を、指定したポリシーでキャプチャトリガー（クリック・ESCキー）として扱ってよいか判定する
+///
+/// # 引数
+/// * `policy` - 他のツールが注入した入力の扱い
+/// * `injected` - `LLMHF_INJECTED` / `LLKHF_INJECTED` フラグが立っているか
+/// * `extra_info` - フック構造体の `dwExtraInfo`
+///
+/// # 戻り値
+/// * `true` - 実際の入力、自動クリックの合成クリック、またはポリシーが注入入力を許可している
+/// * `false` - 他のツールが注入した入力で、ポリシーが実入力のみを要求している
+pub fn is_trigger_input_allowed_by(
+    policy: InjectedInputPolicy,
+    injected: bool,
+    extra_info: usize,
+) -> bool {
+    !injected
+        || is_auto_click_input(injected, extra_info)
+        || policy == InjectedInputPolicy::AllowInjected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_click_sentinel_round_trips() {
+        // 自動クリックが設定したタグは、注入フラグ付きで受け取った場合のみ合成クリックと判定される
+        assert!(is_auto_click_input(true, AUTO_CLICK_EXTRA_INFO));
+        assert!(!is_auto_click_input(false, AUTO_CLICK_EXTRA_INFO));
+        assert_eq!(&AUTO_CLICK_EXTRA_INFO.to_be_bytes()[4..], b"CLKC");
+    }
+
+    #[test]
+    fn foreign_extra_info_is_not_auto_click() {
+        for extra_info in [
+            0,
+            1,
+            AUTO_CLICK_EXTRA_INFO - 1,
+            AUTO_CLICK_EXTRA_INFO + 1,
+            AUTO_CLICK_EXTRA_INFO << 32,
+            usize::MAX,
+        ] {
+            assert!(!is_auto_click_input(true, extra_info), "{extra_info:#x}");
+        }
+    }
+
+    #[test]
+    fn trigger_policy_table() {
+        use InjectedInputPolicy::*;
+        const OTHER: usize = 0x1234;
+        // (ポリシー, 注入フラグ, dwExtraInfo, 期待値)
+        let cases = [
+            (AllowInjected, false, 0, true),
+            (AllowInjected, true, OTHER, true),
+            (AllowInjected, true, AUTO_CLICK_EXTRA_INFO, true),
+            (RealInputOnly, false, 0, true),
+            (RealInputOnly, false, OTHER, true),
+            (RealInputOnly, true, OTHER, false),
+            (RealInputOnly, true, 0, false),
+            (RealInputOnly, true, AUTO_CLICK_EXTRA_INFO, true),
+        ];
+        for (policy, injected, extra_info, expected) in cases {
+            assert_eq!(
+                is_trigger_input_allowed_by(policy, injected, extra_info),
+                expected,
+                "{policy:?} injected={injected} extra_info={extra_info:#x}"
+            );
+        }
+    }
+}
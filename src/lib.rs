@@ -0,0 +1,97 @@
+/*
+============================================================================
+ClickCapture ライブラリ (lib.rs)
+============================================================================
+
+【ライブラリ概要】
+ClickCaptureの中核処理（画面領域のキャプチャ、JPEG/WebPフォルダのPDF一括変換）を、
+ダイアログ・マウス/キーボードフック・グローバル状態（`AppState`）から切り離して
+公開するライブラリクレートです。バイナリ（main.rs）もこのライブラリを利用します。
+
+【公開API】
+- `Capturer`：`new(CaptureConfig)` で作成し、`capture_area(RECT)` で画像のバイト列を取得、
+  `export_pdf(dir, &PdfConfig)` でPDF変換を実行
+- `CaptureConfig`：保存形式（`CaptureFormat`）、スケール（%）または出力幅（px）、最大幅・最大高さ、JPEG品質、高精細テキストモード、
+  キャプチャバックエンド（`CaptureBackendKind`）
+- `CaptureBackend`：画面取得処理のトレイト（GDI / DXGI Desktop Duplication、`Capturer::capture_area_with` で差し替え可能）
+- `PdfConfig`：PDFサイズ上限（MB）、出力先フォルダ、撮影日時フッターの有無、準拠規格（通常 / PDF/A-1b）
+- `pdf_builder::PdfBuilder`：JPEGを再圧縮せずにPDFページとして埋め込むビルダー（`with_conformance` でPDF/A-1b）
+- `gif_builder::export_image_folder_to_gif`：キャプチャフォルダの画像をアニメーションGIFに変換
+- `contact_sheet_builder::export_image_folder_to_contact_sheet`：キャプチャフォルダの画像をサムネイル一覧のPNGにまとめる
+- `zip_builder::export_image_folder_to_zip`：キャプチャフォルダの画像を1つのZIPにまとめる（キャンセル可能）
+- `capture_metadata::insert_exif_segment`：JPEGに撮影日時・撮影条件のEXIFを埋め込む
+- `capture_metadata::insert_comment_segment`：JPEGにコメント（取得元のURLやメモ）をCOMセグメントとして埋め込む
+- `frame_diff::changed_region`：前回の画像と比較して変化した範囲を求める（変化がない場合の保存の省略・変化した範囲の切り出し）
+- `capture_sink::CaptureSink`：キャプチャ画像を長さ付きフレームで名前付きパイプ・標準出力へ送る（`Capturer::capture_area_to_sink`）
+
+【使用例】
+```rust,no_run
+use clickcapture::{CaptureConfig, Capturer, PdfConfig};
+use windows::Win32::Foundation::RECT;
+
+let capturer = Capturer::new(CaptureConfig {
+    scale_percent: 100,
+    ..Default::default()
+});
+let jpeg_bytes = capturer
+    .capture_area(RECT { left: 0, top: 0, right: 800, bottom: 600 })
+    .unwrap();
+std::fs::write("C:\\captures\\0001.jpg", jpeg_bytes).unwrap();
+capturer.export_pdf("C:\\captures", &PdfConfig::default()).unwrap();
+```
+
+【ファイル責任・API境界】
+- capturer.rs：`Capturer` / 設定構造体、縮小・マスク、JPEGエンコード
+- dib.rs：GDI・Desktop Duplication・`PrintWindow` のBGR/BGRAピクセルの `RgbImage` / `RgbaImage` への変換（Win32 APIなし）
+- capture_backend.rs：`CaptureBackend` トレイト、GDI / Desktop Duplicationによる画面取得
+- pdf_builder.rs：`PdfBuilder`、フォルダ一括PDF変換（サイズ上限による分割、複数フォルダの連結）、既存PDFへの追記、キャプチャ画像の収集
+- pdf_archive.rs：PDF/A-1b用のsRGB ICCプロファイル・XMPメタデータ・文書ID、PDF/A-1の書式での書き出し（lopdfの保存処理の代替）
+- gif_builder.rs：フォルダ一括GIF変換（縮小・減色・フレーム間隔）
+- contact_sheet_builder.rs：フォルダ一括のコンタクトシート作成（列数・サムネイル幅・余白）
+- zip_builder.rs：フォルダ一括ZIP圧縮（無圧縮格納、書き込み中ファイルの再試行・スキップ）
+- capture_metadata.rs：EXIF（APP1）・コメント（COM）の作成・挿入と撮影日時・コメントの読み出し
+- capture_sink.rs：名前付きパイプ・標準出力への長さ付きフレームの送信、出力先指定（`--sink=`）の解析
+- capture_naming.rs：連番ファイル名の作成・解析、次の連番・振り直し内容の計算、保存先フォルダーのテンプレートの展開（入出力・Win32 APIなし）
+- frame_diff.rs：前回の画像との変化した範囲の検出・縮小後の座標への換算・切り出し（Win32 APIなし）
+- drag_rect.rs：ドラッグの開始点・終了点からの矩形の正規化（Win32 APIなし）
+- save_folder.rs：既定の保存先フォルダーの候補の優先順位と、書き込める候補の選択（Win32 APIなし）
+- auto_click_runner.rs：自動クリックの実行ループ（間隔・回数・最大実行時間・一時停止・クリック位置の進行・停止条件）、クリック等の副作用は `AutoClickDriver` で注入（Win32 APIなし）
+- input_tag.rs：自動クリックの合成クリックの識別タグ、注入入力をトリガーとして扱うかの判定（Win32 APIなし）
+- hotkey.rs：クイックキャプチャ・再キャプチャのグローバルホットキー（修飾キー＋キー）と設定文字列の解析・表示（Win32 APIなし）
+- capturing_overlay_layout.rs：キャプチャ中オーバーレイのウィンドウ・進行状況ラベルの配置と配色、キャプチャから除外する表示アフィニティ（Win32 APIなし）
+- app_mode.rs：操作モード（エリア選択・キャプチャ・PDF変換）の状態機械と、遷移ごとのフック・オーバーレイ・コントロールの副作用の一覧（Win32 APIなし）
+
+【Windows以外でのビルド・テスト】
+Win32 APIを使用する部分（`CaptureBackend` の実装、`Capturer::capture_area*`、マスク領域、
+`CaptureSink::take_stdout`、ZIP変換時の共有モード）は `#[cfg(windows)]` で区切っています。
+`windows` クレートはWindowsのみの依存関係のため、Linux・macOSのCIでも
+`cargo test --lib` で縮小・エンコード・PDF/ZIP/GIF変換・連番などの単体テストを実行できます。
+バイナリ（main.rs）はWindows専用で、それ以外ではエラーメッセージを表示して終了するだけです。
+
+============================================================================
+*/
+
+pub mod app_mode;
+pub mod auto_click_runner;
+pub mod capture_backend;
+pub mod capture_metadata;
+pub mod capture_naming;
+pub mod capture_sink;
+pub mod capturer;
+pub mod capturing_overlay_layout;
+pub mod contact_sheet_builder;
+pub mod dib;
+pub mod drag_rect;
+pub mod frame_diff;
+pub mod gif_builder;
+pub mod hotkey;
+pub mod input_tag;
+pub mod pdf_archive;
+pub mod pdf_builder;
+pub mod save_folder;
+pub mod zip_builder;
+
+#[cfg(windows)]
+pub use capture_backend::CaptureBackend;
+pub use capture_backend::CaptureBackendKind;
+pub use capturer::{CaptureConfig, CaptureFormat, Capturer, PdfConfig};
@@ -19,6 +19,7 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
 6. 🔄 自動ファイル管理：0001.jpg〜9999.jpg連番管理
 7. ⚙️ 高度品質制御：画像スケール（55%〜100%）+ JPEG品質（70%〜100%）
 8. 📄 PDF統合機能：画像一括変換 + サイズ上限制御（20MB〜100MB）
+9. 🔌 外部出力：起動引数 --sink=stdout / --sink=pipe:名前 で、画像をファイルに保存せず長さ付きフレームで送信
 
 【技術仕様・アーキテクチャ】
 ┌─ 言語：Rust 2021 Edition（メモリ安全性保証 + ネイティブパフォーマンス）
@@ -48,7 +49,12 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
 
    (その他主要モジュール)
    - export_pdf.rs: PDF変換
+   - export_gif.rs: GIF変換（ワーカースレッド）
+   - export_zip.rs: ZIP圧縮（ワーカースレッド、中止可能）
+   - export_contact_sheet.rs: 一覧画像（コンタクトシート）作成（ワーカースレッド）
    - system_utils.rs: OS連携
+   - strings.rs: 表示文字列テーブル（日本語・英語）
+   - theme.rs: 配色テーマ（ライト / ダーク）
    - folder_manager.rs: フォルダー管理
    - constants.rs: 定数管理
    - ui_utils.rs: UI描画ユーティリティ
@@ -75,6 +81,7 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
                       [コンボボックス操作] → リアルタイム設定更新
                                               ↓
                       [PDF変換ボタン] → 確認ダイアログ → 一括変換実行
+                      [GIF変換ボタン] → 確認ダイアログ → ワーカースレッドで変換 → 完了通知
 
 【パフォーマンス・品質指標】
 - マウスレスポンス：<1ms（システムレベル最適化）
@@ -98,6 +105,12 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
 - embed-resource = "2.4"（Windowsリソース統合）
 
 【ファイル責任・API境界】
+- lib.rs：ライブラリクレート（`Capturer`、`CaptureConfig`、`PdfConfig`）。UI・フック非依存の中核処理
+- capturer.rs：GDIキャプチャ、JPEGエンコード（ライブラリ側、screen_capture.rs と共有）
+- pdf_builder.rs：PdfBuilder、フォルダ一括PDF変換（ライブラリ側、export_pdf.rs から呼び出し）
+- gif_builder.rs：フォルダ一括GIF変換（ライブラリ側、export_gif.rs から呼び出し）
+- zip_builder.rs：フォルダ一括ZIP圧縮（ライブラリ側、export_zip.rs から呼び出し）
+- contact_sheet_builder.rs：フォルダ一括の一覧画像作成（ライブラリ側、export_contact_sheet.rs から呼び出し）
 - main.rs：エントリー、ダイアログ管理、メッセージループ、UI制御
 - app_state.rs：グローバル状態、スレッドセーフWrapper、ライフタイム管理
 - hook.rs: マウスとキーボードフックの統合管理
@@ -105,12 +118,19 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
 - hook/keyboard.rs：キーボードフック、ショートカット、緊急停止
 - area_select.rs：領域選択ロジック、ドラッグ処理、座標計算
 - auto_click.rs: 自動クリック機能、スレッド管理
+- scheduled_capture.rs: 定期キャプチャ機能（N分ごと）、スレッド管理
 - screen_capture.rs：画面キャプチャ、JPEG圧縮、ファイル保存
+- window_capture.rs：ウィンドウ単体キャプチャ（PrintWindow、アルファ付きPNG保存）
+- sound.rs：効果音再生（シャッター音、自動クリック完了チャイム）
 - overlay.rs：オーバーレイウィンドウ、透明度制御、リージョン管理
-- export_pdf.rs：PDF生成、メモリ管理、進捗表示
+- export_pdf.rs：PDF変換の設定解決（AppState）とライブラリへの委譲
+- export_gif.rs：GIF変換の設定解決（AppState）とワーカースレッドでの実行
+- export_zip.rs：ZIP圧縮の出力パス決定とワーカースレッドでの実行
+- export_contact_sheet.rs：一覧画像の設定解決（AppState）とワーカースレッドでの実行
 - system_utils.rs：OS連携、フォルダー操作、アイコン管理
 - folder_manager.rs：保存先管理、パス解決
 - constants.rs：定数定義、リソースID、設定値
+- settings.rs：設定値の永続化（HKCU\Software\ClickCapture）
 - ui.rs: UI関連モジュールの集約
 
 【開発・保守・品質ガイドライン】
@@ -124,25 +144,34 @@ Windows専用プロフェッショナルスクリーンキャプチャアプリ
 */
 
 // 必要なライブラリ（外部機能）をインポート
+#[cfg(windows)]
 use windows::{
     Win32::{
         Foundation::LPARAM, // 基本的なデータ型
         Graphics::GdiPlus::{
             GdiplusShutdown, GdiplusStartup, GdiplusStartupInput, GdiplusStartupOutput, Status,
         }, // グラフィック描画機能
+        System::Ole::{OleInitialize, OleUninitialize}, // ドラッグ＆ドロップ（OLE）
         UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
     },
     core::PCWSTR, // Windows API用の文字列操作
 };
 
+#[cfg(windows)]
 use color_eyre::Result;
 
+// キャプチャ画像の外部出力（名前付きパイプ・標準出力）
+#[cfg(windows)]
+use clickcapture::capture_sink::{CaptureSink, CaptureSinkTarget};
+
 /*
 ============================================================================
 定数
 ============================================================================
 */
+#[cfg(windows)]
 mod constants;
+#[cfg(windows)]
 use constants::*;
 
 /*
@@ -150,13 +179,39 @@ use constants::*;
 アプリケーション状態管理構造体
 ============================================================================
 */
+#[cfg(windows)]
 mod app_state;
 
+/*
+============================================================================
+モード遷移の副作用（フック・オーバーレイ・コントロール）
+============================================================================
+*/
+#[cfg(windows)]
+mod mode_effects;
+
+/*
+============================================================================
+エラー型定義
+============================================================================
+*/
+#[cfg(windows)]
+mod error;
+
+/*
+============================================================================
+キャプチャ所要時間の計測（TimingDebug）
+============================================================================
+*/
+#[cfg(windows)]
+mod capture_timing;
+
 /*
 ============================================================================
 オーバーレイ処理
 ============================================================================
 */
+#[cfg(windows)]
 mod overlay;
 
 /*
@@ -164,6 +219,7 @@ mod overlay;
 エリア選択処理
 ============================================================================
 */
+#[cfg(windows)]
 mod area_select;
 
 /*
@@ -171,50 +227,161 @@ mod area_select;
 画面キャプチャ処理
 ============================================================================
 */
+#[cfg(windows)]
 mod screen_capture;
 
+/*
+============================================================================
+キャプチャパイプライン（取得・マスク・縮小・エンコード・保存の各段階）
+============================================================================
+*/
+#[cfg(windows)]
+mod capture_pipeline;
+
+/*
+============================================================================
+ウィンドウ単体キャプチャ処理
+============================================================================
+*/
+#[cfg(windows)]
+mod window_capture;
+
+/*
+============================================================================
+効果音再生処理
+============================================================================
+*/
+#[cfg(windows)]
+mod sound;
+
+/*
+============================================================================
+完了通知（通知領域のバルーン）
+============================================================================
+*/
+#[cfg(windows)]
+mod notification;
+
 /*
 ============================================================================
 PDFエクスポート処理
 ============================================================================
 */
+#[cfg(windows)]
 mod export_pdf;
 
+/*
+============================================================================
+GIFエクスポート処理
+============================================================================
+*/
+#[cfg(windows)]
+mod export_gif;
+
+/*
+============================================================================
+ZIPエクスポート処理
+============================================================================
+*/
+#[cfg(windows)]
+mod export_zip;
+
+/*
+============================================================================
+一覧画像（コンタクトシート）エクスポート処理
+============================================================================
+*/
+#[cfg(windows)]
+mod export_contact_sheet;
+
 /*
 ============================================================================
 ユーティリティ関数
 ============================================================================
 */
+#[cfg(windows)]
 mod system_utils;
 
+/*
+============================================================================
+表示文字列テーブル（日本語・英語）
+============================================================================
+*/
+#[cfg(windows)]
+mod strings;
+
+/*
+============================================================================
+配色テーマ（ライト / ダーク）
+============================================================================
+*/
+#[cfg(windows)]
+mod theme;
+
+/*
+============================================================================
+設定の永続化（レジストリ）
+============================================================================
+*/
+#[cfg(windows)]
+mod settings;
+
 /*
 ============================================================================
 フック管理関数
 ============================================================================
  */
+#[cfg(windows)]
 mod hook;
 
+/*
+============================================================================
+グローバルホットキー登録
+============================================================================
+ */
+#[cfg(windows)]
+mod global_hotkey;
+
 /*
 ============================================================================
 自動クリック管理関数
 ============================================================================
  */
+#[cfg(windows)]
 mod auto_click;
 
+/*
+============================================================================
+定期キャプチャ管理関数
+============================================================================
+ */
+#[cfg(windows)]
+mod scheduled_capture;
+
 /*
 ============================================================================
 ダイアログ、UI部品描画、管理関数
 ============================================================================
  */
+#[cfg(windows)]
 mod ui;
+#[cfg(windows)]
 use ui::dialog_handler::dialog_proc;
 /*
 ============================================================================
 アプリケーションエントリーポイント
 ============================================================================
 */
+#[cfg(windows)]
 fn main() -> Result<()> {
+    // キャプチャ画像の出力先（--sink=stdout / --sink=pipe:名前）
+    // 標準出力へ送る場合は、ログがフレームに混ざらないよう最初の出力より前に引き取る
+    let sink_error = configure_capture_sink(std::env::args().skip(1));
+
     println!("アプリケーションを開始します...");
+    if let Some(message) = sink_error {
+        eprintln!("{}", message);
+    }
 
     // color-eyre エラーハンドリングの初期化
     color_eyre::install()?;    
@@ -237,18 +404,32 @@ fn main() -> Result<()> {
     };
     let mut gdiplus_startup_output = GdiplusStartupOutput::default();
 
-    unsafe {
+    let gdiplus_started = unsafe {
         let status = GdiplusStartup(
             &mut gdiplus_token,
             &gdiplus_startup_input,
             &mut gdiplus_startup_output,
         );
+        // 診断ダンプ（Alt+D）と起動時の通知（WM_INITDIALOG）で使用するため、初期化結果を記録しておく
+        app_state::record_gdiplus_startup_status(status.0);
 
         if status != Status(0) {
+            // GDI+はオーバーレイ描画のみに使用するため、終了せずにオーバーレイなしで起動する
+            // （ダイアログ表示後に report_startup_failures でメッセージボックスを表示）
             eprintln!("GdiplusStartup failed with status: {:?}", status);
-            return Ok(());
+            false
+        } else {
+            println!("✅ GDI+ を初期化しました。");
+            true
         }
-        println!("✅ GDI+ を初期化しました。");
+    };
+
+    // OLE の初期化
+    // サムネイルからのファイルのドラッグ（SHDoDragDrop）に必要。ダイアログのスレッド（このスレッド）で初期化する。
+    // 失敗してもドラッグ以外の機能には影響しないため、起動は続ける。
+    let ole_initialized = unsafe { OleInitialize(None) }.is_ok();
+    if !ole_initialized {
+        eprintln!("OleInitialize failed: サムネイルのドラッグは使用できません");
     }
 
     // メインダイアログの表示
@@ -262,10 +443,59 @@ fn main() -> Result<()> {
         }
     }
 
-    // GDI+ のシャットダウン
-    unsafe {
-        GdiplusShutdown(gdiplus_token);
+    // OLE の終了処理（初期化に成功した場合のみ）
+    if ole_initialized {
+        unsafe {
+            OleUninitialize();
+        }
+    }
+
+    // GDI+ のシャットダウン（初期化に成功した場合のみ）
+    if gdiplus_started {
+        unsafe {
+            GdiplusShutdown(gdiplus_token);
+        }
     }
     println!("アプリケーションを終了します。");
     Ok(())
 }
+
+#[cfg(windows)]
+/// 起動引数の `--sink=` を解析して出力先を記録する（エラー時はログ用のメッセージを返す）
+///
+/// 出力先を指定した場合、キャプチャ画像は連番ファイルに保存せず、長さ付きフレームとして送信する。
+/// 名前付きパイプは初回のキャプチャで接続する（受信側が先にパイプを作成していればよい）。
+fn configure_capture_sink(args: impl Iterator<Item = String>) -> Option<String> {
+    let value = args
+        .filter_map(|arg| arg.strip_prefix("--sink=").map(str::to_string))
+        .last()?;
+    let Some(target) = CaptureSinkTarget::parse(&value) else {
+        return Some(strings::capture_sink_invalid(&value));
+    };
+
+    // 標準出力を引き取れない場合（リダイレクトせずに起動したなど）は出力先を記録せず、ファイルに保存する
+    let sink = match target {
+        CaptureSinkTarget::Stdout => match CaptureSink::take_stdout() {
+            Ok(sink) => Some(sink),
+            Err(e) => {
+                return Some(strings::capture_sink_unavailable(
+                    &target.label(),
+                    &e.to_string(),
+                ));
+            }
+        },
+        CaptureSinkTarget::NamedPipe(_) => None,
+    };
+    app_state::record_capture_sink(target, sink);
+    None
+}
+
+/// Windows以外のエントリーポイント
+///
+/// アプリケーションはWindows専用です。ライブラリの単体テストをWindows以外のCIで
+/// 実行する際に、`cargo test --workspace` がバイナリもビルドできるようにするためのものです。
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("ClickCapture は Windows 専用です / ClickCapture runs on Windows only");
+    std::process::exit(1);
+}
@@ -0,0 +1,91 @@
+/*
+============================================================================
+モード遷移の副作用実行モジュール (mode_effects.rs)
+============================================================================
+
+【ファイル概要】
+`AppMode` の遷移メソッド（ライブラリの `app_mode.rs`）が返す `ModeEffect` の一覧を、
+フックのインストール・オーバーレイの表示・入力コントロールの更新といったWin32の処理として実行するモジュールです。
+遷移ごとに「何をするか」はライブラリ側の遷移表が決め、このモジュールは「どう行うか」だけを担当します。
+
+【主要機能】
+1.  **`apply_mode_effects`**: 遷移後のモードを `SharedState` に公開してから副作用を先頭から順に実行し、
+    オーバーレイの表示に失敗した場合はそこで中断してエラーを返す
+
+【技術仕様】
+-   **失敗時の扱い**: 表示に失敗したオーバーレイ以降の副作用は実行しない。
+    呼び出し側はモードを終了する遷移（`exit_area_select` / `exit_capture`）の副作用を実行して元に戻す
+-   **オーバーレイ未作成時**: `AppState` にオーバーレイがない場合は表示・非表示・再描画を行わない（従来どおり）
+
+【AI解析用：依存関係】
+-   `clickcapture::app_mode`（ライブラリ）: `ModeEffect` の定義と遷移表
+-   `hook.rs`: `install_hooks` / `uninstall_hooks`
+-   `overlay/area_select_overlay.rs` / `overlay/capturing_overlay.rs`: 表示・非表示・再描画（固定フレームの解放を含む）
+-   `ui/input_control_handlers.rs`: `update_input_control_states`
+-   呼び出し元: `area_select.rs` / `screen_capture.rs` / `hook/mouse.rs` / `ui/pdf_export_button_handler.rs`
+ */
+
+use windows::core::Error;
+
+use crate::{
+    app_state::{AppState, ModeEffect},
+    hook::{install_hooks, uninstall_hooks},
+    overlay::Overlay,
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 【副作用の実行】モード遷移が返した副作用を先頭から順に実行する
+///
+/// # 引数
+/// * `effects` - `AppMode` の遷移メソッドが返した副作用の一覧
+///
+/// # 戻り値
+/// * `Ok(())` - すべての副作用を実行した
+/// * `Err(e)` - オーバーレイを表示できなかった（以降の副作用は実行していない）
+pub fn apply_mode_effects(effects: &[ModeEffect]) -> Result<(), Error> {
+    let app_state = AppState::get_app_state_mut();
+
+    // 遷移後のモードをワーカースレッドへ公開する（自動クリックのスレッドがキャプチャモードの終了を検出）
+    AppState::shared().publish_mode(app_state.mode);
+
+    for effect in effects {
+        match effect {
+            ModeEffect::InstallHooks => install_hooks(),
+            ModeEffect::UninstallHooks => uninstall_hooks(),
+            ModeEffect::ShowAreaSelectOverlay => {
+                if let Some(overlay) = app_state.area_select_overlay.as_mut() {
+                    overlay.show_overlay()?;
+                }
+            }
+            ModeEffect::HideAreaSelectOverlay => {
+                if let Some(overlay) = app_state.area_select_overlay.as_mut() {
+                    overlay.hide_overlay();
+                    // 画面全体の固定フレームはメモリが大きいため、エリア選択の終了ごとに解放する
+                    overlay.release_frozen_frame();
+                }
+            }
+            ModeEffect::RefreshAreaSelectOverlay => {
+                if let Some(overlay) = app_state.area_select_overlay.as_ref() {
+                    overlay.refresh_overlay();
+                }
+            }
+            ModeEffect::ShowCapturingOverlay => {
+                if let Some(overlay) = app_state.capturing_overlay.as_mut() {
+                    overlay.show_overlay()?;
+                }
+            }
+            ModeEffect::HideCapturingOverlay => {
+                if let Some(overlay) = app_state.capturing_overlay.as_ref() {
+                    overlay.hide_overlay();
+                }
+            }
+            ModeEffect::RefreshCapturingOverlay => {
+                if let Some(overlay) = app_state.capturing_overlay.as_ref() {
+                    overlay.refresh_overlay();
+                }
+            }
+            ModeEffect::UpdateInputControls => update_input_control_states(),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,174 @@
+/*
+============================================================================
+完了通知モジュール (notification.rs)
+============================================================================
+
+【ファイル概要】
+自動クリックやPDF変換などの長時間の処理が終わったことを、画面から離れていても気付けるよう
+通知領域（タスクトレイ）のバルーンで知らせるモジュールです。「完了を通知」チェックボックスが
+有効な場合のみ通知します。
+
+【主要機能】
+1.  **完了通知 (`notify_completion`)**:
+    -   通知領域にアプリのアイコンを追加（`NIM_ADD`）し、`NIM_MODIFY` + `NIF_INFO` でバルーンを表示
+    -   通知領域に追加できない場合は `MessageBeep` にフォールバック
+2.  **通知アイコンのイベント処理 (`handle_notify_icon_message`)**:
+    -   バルーンが閉じられた・タイムアウトした場合はアイコンを削除
+    -   バルーンまたはアイコンがクリックされた場合は、メインダイアログを復元してからアイコンを削除
+3.  **通知アイコンの削除 (`remove_notify_icon`)**:
+    -   `WM_DESTROY` で呼び出し、終了後に通知領域にアイコンが残らないようにする
+
+【技術仕様】
+-   **常駐しないアイコン**: 通知のたびに追加し、バルーンが閉じられたら削除する（通知中のみ表示）
+-   **コールバック**: `uCallbackMessage` に `WM_NOTIFY_ICON` を指定し、`lParam` でイベント（`NIN_BALLOON*`）を受け取る
+-   **文字列長**: タイトルは63文字、本文は255文字を超える部分を切り捨てる（`szInfoTitle` / `szInfo` の長さ）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `completion_notify_enabled` フラグ、`is_notify_icon_visible` の保持
+-   `constants.rs`: `WM_NOTIFY_ICON` メッセージ、`IDI_APP_ICON` リソースID
+-   `strings.rs`: バルーンを表示できない場合のログ文字列
+-   `ui/dialog_handler.rs`: `WM_AUTO_CLICK_COMPLETE` 受信時の通知、`WM_NOTIFY_ICON` / `WM_DESTROY` の処理
+-   `ui/pdf_export_button_handler.rs`: PDF変換完了時の通知
+ */
+
+use windows::{
+    Win32::{
+        Foundation::{HINSTANCE, HWND, LPARAM},
+        System::{Diagnostics::Debug::MessageBeep, LibraryLoader::GetModuleHandleW},
+        UI::{
+            Shell::{
+                NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIM_ADD, NIM_DELETE,
+                NIM_MODIFY, NIN_BALLOONHIDE, NIN_BALLOONTIMEOUT, NIN_BALLOONUSERCLICK,
+                NOTIFYICONDATAW, Shell_NotifyIconW,
+            },
+            WindowsAndMessaging::{LoadIconW, MB_ICONASTERISK, WM_LBUTTONUP},
+        },
+    },
+    core::PCWSTR,
+};
+
+use crate::{
+    app_state::AppState,
+    constants::{IDI_APP_ICON, WM_NOTIFY_ICON},
+    strings,
+    system_utils::app_log,
+    ui::dialog_handler::bring_dialog_to_front,
+};
+
+/// 通知領域アイコンのID（このアプリでは1つのみ）
+const NOTIFY_ICON_ID: u32 = 1;
+
+/// 長時間の処理の完了を通知する
+///
+/// 「完了を通知」が無効な場合は何もしません。バルーンを表示できない場合は
+/// `MessageBeep` で代替し、それにも失敗した場合はログ出力のみ行います。
+///
+/// # 引数
+/// * `title` - バルーンのタイトル（例：「自動クリック完了」）
+/// * `message` - バルーンの本文（回数・経過時間など）
+pub fn notify_completion(title: &str, message: &str) {
+    let app_state = AppState::get_app_state_ref();
+    if !app_state.completion_notify_enabled {
+        return;
+    }
+    let Some(dialog_hwnd) = app_state.dialog_hwnd else {
+        return;
+    };
+
+    if show_balloon(*dialog_hwnd, title, message) {
+        return;
+    }
+
+    // アイコンだけ追加できた場合も、バルーンが閉じられる通知は届かないため削除しておく
+    remove_notify_icon();
+    app_log(strings::notification_balloon_fallback());
+    if let Err(e) = unsafe { MessageBeep(MB_ICONASTERISK) } {
+        eprintln!("⚠️ 完了通知のシステム音の再生に失敗しました: {}", e);
+    }
+}
+
+/// 通知領域アイコンからのコールバック（`WM_NOTIFY_ICON`）を処理する
+///
+/// # 引数
+/// * `lparam` - 発生したイベント（`NIN_BALLOON*` またはマウスメッセージ）
+pub fn handle_notify_icon_message(lparam: LPARAM) {
+    match lparam.0 as u32 {
+        NIN_BALLOONUSERCLICK | WM_LBUTTONUP => {
+            // 通知から作業に戻れるよう、ダイアログを前面に表示
+            bring_dialog_to_front();
+            remove_notify_icon();
+        }
+        NIN_BALLOONTIMEOUT | NIN_BALLOONHIDE => remove_notify_icon(),
+        _ => {}
+    }
+}
+
+/// 通知領域アイコンを削除する（表示していない場合は何もしない）
+pub fn remove_notify_icon() {
+    let app_state = AppState::get_app_state_mut();
+    if !app_state.is_notify_icon_visible {
+        return;
+    }
+    app_state.is_notify_icon_visible = false;
+    let Some(dialog_hwnd) = app_state.dialog_hwnd else {
+        return;
+    };
+    let data = notify_icon_data(*dialog_hwnd);
+    unsafe {
+        let _ = Shell_NotifyIconW(NIM_DELETE, &data);
+    }
+}
+
+/// 通知領域にアイコンを追加し（追加済みの場合はそのまま）、バルーンを表示する
+///
+/// # 戻り値
+/// バルーンを表示できた場合は `true`
+fn show_balloon(hwnd: HWND, title: &str, message: &str) -> bool {
+    unsafe {
+        if !AppState::get_app_state_ref().is_notify_icon_visible {
+            let hinstance = GetModuleHandleW(None).unwrap_or_default();
+            let mut data = notify_icon_data(hwnd);
+            data.uFlags = NIF_ICON | NIF_TIP | NIF_MESSAGE;
+            data.uCallbackMessage = WM_NOTIFY_ICON;
+            data.hIcon = LoadIconW(
+                Some(HINSTANCE(hinstance.0)),
+                PCWSTR(IDI_APP_ICON as *const u16),
+            )
+            .unwrap_or_default();
+            data.szTip = to_wide_array("ClickCapture");
+            if !Shell_NotifyIconW(NIM_ADD, &data).as_bool() {
+                return false;
+            }
+            AppState::get_app_state_mut().is_notify_icon_visible = true;
+        }
+
+        let mut data = notify_icon_data(hwnd);
+        data.uFlags = NIF_INFO;
+        data.dwInfoFlags = NIIF_INFO;
+        data.szInfoTitle = to_wide_array(title);
+        data.szInfo = to_wide_array(message);
+        Shell_NotifyIconW(NIM_MODIFY, &data).as_bool()
+    }
+}
+
+/// 通知領域アイコンを識別する共通部分（ウィンドウとアイコンID）を設定した構造体を作成する
+fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: NOTIFY_ICON_ID,
+        ..Default::default()
+    }
+}
+
+/// 文字列をNull終端のUTF-16固定長配列に変換する（収まらない部分は切り捨て）
+fn to_wide_array<const N: usize>(text: &str) -> [u16; N] {
+    let mut buffer = [0u16; N];
+    for (slot, unit) in buffer
+        .iter_mut()
+        .zip(text.encode_utf16().take(N.saturating_sub(1)))
+    {
+        *slot = unit;
+    }
+    buffer
+}
@@ -31,7 +31,7 @@
 -   **ウィンドウタイプ**: `WS_EX_LAYERED` を使用したレイヤードウィンドウ。
 
 【AI解析用：依存関係】
-- `area_select_overlay.rs`, `capturing_overlay.rs`: このモジュールの `Overlay` トレイトを実装する具体的なオーバーレイ。
+- `area_select_overlay.rs`, `capturing_overlay.rs`, `saved_toast_overlay.rs`: このモジュールの `Overlay` トレイトを実装する具体的なオーバーレイ。
 - `app_state.rs`: 各オーバーレイのインスタンスを保持する。
 
 ============================================================================
@@ -44,6 +44,7 @@
 */
 pub mod area_select_overlay;
 pub mod capturing_overlay;
+pub mod saved_toast_overlay;
 
 /*
 ============================================================================
@@ -74,6 +75,9 @@ use windows::{
 // アプリケーション状態管理構造体
 use crate::app_state::*;
 
+// ログ出力・メッセージ文字列
+use crate::{strings, system_utils::app_log};
+
 /// 各オーバーレイに固有のウィンドウプロシージャ処理を保持する構造体
 ///
 /// `overlay_dispatch_proc` から、具体的な処理を委譲するために使用される関数ポインタの集まり。
@@ -84,6 +88,10 @@ pub struct OverlayWindowProc {
     pub paint: Option<fn(hwnd: HWND, graphics: *mut GpGraphics)>,
     /// `WM_DESTROY` メッセージで呼び出されるクリーンアップ処理
     pub destroy: Option<fn(hwnd: HWND)>,
+    /// `UpdateLayeredWindow` に渡す `SourceConstantAlpha` を返す処理（None: 255で不透明）
+    ///
+    /// フェードアウトなど、ウィンドウ全体の透明度をアニメーションさせる場合に使用する。
+    pub alpha: Option<fn(hwnd: HWND) -> u8>,
 }
 
 /// オーバーレイウィンドウ作成パラメータ構造体
@@ -176,13 +184,28 @@ pub trait Overlay {
     /// オーバーレイクラスパラメータ取得
     fn get_class_params(&self) -> OverlayWindowClassParams;
 
+    /// 描画に必要なGDI+リソースが揃っているか
+    ///
+    /// GDI+の初期化やリソースの作成に失敗した場合、描画が何も行われず透明なウィンドウになるため、
+    /// `show_overlay` はウィンドウを作成・表示せずにログへ出力します（起動時の通知は `AppState::startup_failures`）。
+    fn is_usable(&self) -> bool {
+        true
+    }
+
     /// オーバーレイウィンドウを表示する
     ///
     /// # 処理内容
-    /// 1. ウィンドウがまだ作成されていなければ `create_overlay` を呼び出して作成します。
-    /// 2. `ShowWindow` でウィンドウを表示状態にします。
-    /// 3. `refresh_overlay` と `set_window_pos` で、表示内容とZオーダーを最新の状態に更新します。
+    /// 1. 描画リソースが揃っていなければ（`is_usable`）、表示せずにログ出力して終了します。
+    /// 2. ウィンドウがまだ作成されていなければ `create_overlay` を呼び出して作成します。
+    /// 3. `ShowWindow` でウィンドウを表示状態にします。
+    /// 4. `refresh_overlay` と `set_window_pos` で、表示内容とZオーダーを最新の状態に更新します。
     fn show_overlay(&mut self) -> Result<(), Error> {
+        // 描画できないオーバーレイは表示しない（透明なウィンドウが入力を遮るのを防ぎ、縮退動作をログで明示）
+        if !self.is_usable() {
+            app_log(&strings::overlay_unusable(self.get_description()));
+            return Ok(());
+        }
+
         let overlay_exists = self.get_hwnd().is_some();
 
         // オーバーレイウィンドウが存在しない場合は作成
@@ -430,12 +453,18 @@ extern "system" fn overlay_dispatch_proc(
                 overlay_window_proc = &*boxed_overlay_window_proc_ptr;
             }
 
+            // ウィンドウ全体の透明度（未指定時は不透明）
+            let source_constant_alpha = overlay_window_proc
+                .alpha
+                .as_ref()
+                .map_or(255, |alpha| alpha(hwnd));
+
             let mut ps = PAINTSTRUCT::default();
             if let Some(paint) = overlay_window_proc.paint.as_ref() {
                 // `UpdateLayeredWindow` を使った描画処理を呼び出す
                 unsafe {
                     let hdc = BeginPaint(hwnd, &mut ps);
-                    paint_by_update_layered_window(hwnd, hdc, paint);
+                    paint_by_update_layered_window(hwnd, hdc, paint, source_constant_alpha);
                     let _ = EndPaint(hwnd, &ps);
                 }
             }
@@ -478,6 +507,7 @@ extern "system" fn overlay_dispatch_proc(
 /// - hwnd: オーバーレイウィンドウのHWND   
 /// - hdc: オーバーレイウィンドウのHDC
 /// - paint: 描画関数ポインタ (Graphicsオブジェクトを受け取る)
+/// - source_constant_alpha: ウィンドウ全体に掛ける透明度（255で不透明）
 /// # 処理フロー    
 /// 1. クライアント領域サイズ取得
 /// 2. メモリDCと32bpp DIBセクション作成
@@ -507,13 +537,14 @@ extern "system" fn overlay_dispatch_proc(
 /// /// fn my_paint_function(hwnd: HWND, graphics: *mut GpGraphics) {
 /// /// ///     // GDI+を使用した描画処理
 /// /// /// }
-/// /// /// paint_by_update_layered_window(hwnd, hdc, &my_paint_function);
+/// /// /// paint_by_update_layered_window(hwnd, hdc, &my_paint_function, 255);
 /// /// ```
 ///
 fn paint_by_update_layered_window(
     hwnd: HWND,
     hdc: HDC,
     paint: &fn(hwnd: HWND, graphics: *mut GpGraphics),
+    source_constant_alpha: u8,
 ) {
     // クライアント領域サイズ取得
     let mut client_rect = RECT::default();
@@ -592,7 +623,7 @@ fn paint_by_update_layered_window(
     let blend_function = BLENDFUNCTION {
         BlendOp: AC_SRC_OVER as u8,
         BlendFlags: 0,
-        SourceConstantAlpha: 255, // ビットマップのアルファ値を使用
+        SourceConstantAlpha: source_constant_alpha, // ビットマップのアルファ値に掛け合わせる
         AlphaFormat: AC_SRC_ALPHA as u8,
     };
 
@@ -12,7 +12,7 @@ ClickCaptureアプリケーションのエリア選択モード時に表示さ
 1.  **全画面半透明オーバーレイ**: `AreaSelectOverLay`構造体
     -   画面全体を覆う半透明黒背景（Alpha=60%）
     -   選択領域の透明くり抜き表示
-    -   赤色境界線による選択範囲の明確な視覚化
+    -   境界線（既定は赤色、色・太さ・縁取りは設定可能）による選択範囲の明確な視覚化
 
 2.  **リアルタイム選択領域描画**: `area_select_overlay_paint`
     -   マウスドラッグに追従する動的矩形描画
@@ -24,18 +24,46 @@ ClickCaptureアプリケーションのエリア選択モード時に表示さ
     -   透明くり抜き：選択領域の鮮明な表示
     -   境界線：正確な選択範囲の把握支援
 
+4.  **マスク領域表示**: `draw_mask_areas`
+    -   `AppState.mask_areas` とCtrl+ドラッグ中の矩形を青色の斜線で表示（キャプチャ領域と区別）
+
+5.  **選択範囲の強調方法**: `SelectionStyle`（`AppState.selection_style`、Tabキーで切り替え）
+    -   `DimOutside`: 外側を暗くする（デフォルト） / `DimInside`: 内側を暗くする / `BorderOnly`: 境界線のみ
+
+6.  **暗転の濃さ**: `set_dim_opacity`（`AppState.area_select_dim_opacity`、50〜100%、既定60%）
+    -   暗転ブラシの色を差し替えるため、エリア選択中に変更しても次の描画から反映
+
+7.  **三分割グリッド**: `draw_thirds_grid`（`AppState.show_selection_grid`、Gキーで切り替え）
+    -   選択範囲を縦横3等分する細い半透明のガイド線を描画（UI要素の位置合わせ用、キャプチャには影響しない）
+
+8.  **境界線のスタイル**: `update_style`（`AppState.area_select_border_argb` / `area_select_border_width` / `area_select_border_halo`）
+    -   境界線の色（赤/緑/青/黄/黒、既定は赤）と太さ（1〜4px、既定2px）。リサイズハンドルも同じ色相で描画
+    -   白い縁取り（境界線の両側に1pxの白線）を有効にすると、どんな背景色の上でも境界線が見える
+    -   ペン・ブラシの色と太さを差し替えるため、エリア選択中に変更しても次の描画から反映
+
+9.  **十字ガイド線**: `draw_crosshair`（ドラッグ開始前のみ）
+    -   カーソル位置を通る画面全体の縦横1pxの線（境界線と同じ色）と、カーソル座標のラベルを描画
+    -   ドラッグの始点を合わせやすくする。ドラッグを開始すると消え、選択範囲の表示を邪魔しない
+
+10. **画面の固定**: `capture_frozen_frame` / `release_frozen_frame`（`AppState.freeze_screen_on_area_select`）
+    -   エリア選択の開始時に画面全体をGDI+ビットマップに保存し、暗転・くり抜きの下に背景として描画
+    -   動画やアニメーションが動き続ける画面でも、静止画に対して範囲を選択できる
+    -   選択した座標は実際の画面の座標のまま（以降のキャプチャは実際の画面から取得）
+
 【技術仕様】
 -   **レイアウト**: 全画面フルスクリーンオーバーレイ（プライマリモニター対応）
 -   **描画エンジン**: GDI+ による高品質レンダリング
 -   **透明処理**: LayeredWindow + UpdateLayeredWindow（ハードウェア加速）
 -   **合成モード**: SourceCopy/SourceOver の動的切り替え
--   **色彩設計**: 半透明黒背景（#99000000）+ 赤色境界線（#FFFF0000）
+-   **色彩設計**: 半透明黒背景（#99000000、ダークテーマでは半透明白 #66FFFFFF）+ 境界線（既定は赤 #FFFF0000）、マスク領域は青色斜線（#FF0078D7）
 
 【描画アルゴリズム】
+0. **固定フレーム描画**: 画面の固定が有効な場合、保存した画面全体を不透明で描画
 1. **背景マスク描画**: 画面全体を半透明黒で覆う
-2. **選択領域くり抜き**: CompositingModeSourceCopy による透明化
-3. **境界線描画**: 赤色ペンによる矩形境界の描画
-4. **状態別制御**: ドラッグ中/確定後の適切な表示切り替え
+2. **選択領域くり抜き**: CompositingModeSourceCopy による透明化（強調方法に応じて暗転・省略）
+   （固定フレームがある場合は透明にせず、選択範囲に固定フレームを描き直す）
+3. **境界線描画**: 境界線ペン（縁取り有効時は先に白い太線）による矩形境界の描画
+4. **状態別制御**: ドラッグ中/確定後の適切な表示切り替え（ドラッグ前は十字ガイド線と座標ラベル）
 
 【ユーザー体験設計】
 -   **直感的操作**: マウスドラッグによる自然な領域選択
@@ -53,26 +81,101 @@ ClickCaptureアプリケーションのエリア選択モード時に表示さ
 -   `windows`クレート: Win32 API（LayeredWindow、GDI+、全画面制御）
 -   `app_state.rs`: ドラッグ状態と選択領域座標の監視
 -   `overlay/mod.rs`: Overlayトレイトとオーバーレイ基盤機能
--   `area_select.rs`: エリア選択モード制御との連携
+-   `area_select.rs`: エリア選択モード制御との連携（ドラッグ中の矩形は `current_drag_rect` で正規化）
 -   `hook/mouse.rs`: マウスイベントによる描画トリガー
 -   `screen_capture.rs`: 選択領域の最終的なキャプチャ実行
  */
 
 // GDI+関連のライブラリ（外部機能）をインポート
 use windows::Win32::Graphics::GdiPlus::{
-    Color, CompositingModeSourceCopy, CompositingModeSourceOver, GdipCreatePen1,
-    GdipCreateSolidFill, GdipDeleteBrush, GdipDeletePen, GdipDrawRectangleI, GdipFillRectangleI,
-    GdipSetCompositingMode, GpGraphics, GpPen, GpSolidFill, Rect as GpRect, Status, UnitPixel,
+    Color, CompositingModeSourceCopy, CompositingModeSourceOver, GdipCreateBitmapFromHBITMAP,
+    GdipCreateFont, GdipCreateFontFamilyFromName, GdipCreateHatchBrush, GdipCreatePen1,
+    GdipCreateSolidFill, GdipDeleteBrush, GdipDeleteFont, GdipDeleteFontFamily, GdipDeletePen,
+    GdipDisposeImage, GdipDrawImageRectI, GdipDrawImageRectRectI, GdipDrawLineI,
+    GdipDrawRectangleI, GdipDrawString, GdipFillRectangleI, GdipGetGenericFontFamilySansSerif,
+    GdipMeasureString, GdipSetCompositingMode, GdipSetPenColor, GdipSetPenWidth,
+    GdipSetSolidFillColor, GpBitmap, GpFont, GpFontFamily, GpGraphics, GpHatch, GpPen, GpSolidFill,
+    HatchStyleWideDownwardDiagonal, Rect as GpRect, RectF, Status, UnitPixel,
 };
 
 // 必要なライブラリ（外部機能）をインポート
 use windows::Win32::{
-    Foundation::HWND,
+    Foundation::{HWND, POINT, RECT},
+    Graphics::Gdi::{
+        BitBlt, CAPTUREBLT, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject,
+        GetDC, HPALETTE, ReleaseDC, SRCCOPY, SelectObject,
+    },
     UI::WindowsAndMessaging::*, // グラフィック描画機能
 };
+use windows::core::{PCWSTR, w};
 
 use crate::app_state::*;
+use crate::area_select::{current_drag_rect, to_win32_rect};
 use crate::overlay::*;
+use crate::strings;
+use crate::system_utils::get_system_dpi;
+use crate::theme::is_dark_mode;
+
+/// 暗転の不透明度の既定値（%、従来の半透明黒 #99000000 に相当）
+pub const DEFAULT_DIM_OPACITY_PERCENT: u8 = 60;
+
+/// 境界線の色の既定値（不透明の赤）
+pub const DEFAULT_BORDER_ARGB: u32 = 0xFFFF0000;
+
+/// 境界線の太さの既定値（ピクセル）
+pub const DEFAULT_BORDER_WIDTH: u8 = 2;
+
+/// 境界線の太さの範囲（ピクセル）
+pub const BORDER_WIDTH_RANGE: std::ops::RangeInclusive<u8> = 1..=4;
+
+/// 縁取り（白線）の色
+const BORDER_HALO_ARGB: u32 = 0xFFFFFFFF;
+
+/// 十字ガイド線の不透明度（境界線の色にこのアルファを付けて描画）
+const CROSSHAIR_ALPHA: u32 = 0xC0;
+
+/// 座標ラベルの文字サイズ（96DPI基準のピクセル数、システムDPIに応じて拡大）
+const COORDINATE_FONT_SIZE: f32 = 12.0;
+
+/// 座標ラベルのカーソルからのずれ（ピクセル、カーソルの右下に表示）
+const COORDINATE_LABEL_OFFSET: i32 = 16;
+
+/// 座標ラベルの内側の余白（ピクセル）
+const COORDINATE_LABEL_PADDING: i32 = 4;
+
+/// エリア選択中の選択領域の強調方法
+///
+/// 選択中にTabキーで順に切り替えられます（`hook/keyboard.rs`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionStyle {
+    /// 選択領域の外側を暗くする（従来動作、デフォルト）
+    #[default]
+    DimOutside,
+    /// 選択領域の内側を暗くする（キャプチャから除外される範囲の確認用）
+    DimInside,
+    /// 暗くせず、境界線のみ表示する
+    BorderOnly,
+}
+
+impl SelectionStyle {
+    /// 次の強調方法（Tabキーでの切り替え順）
+    pub fn next(self) -> Self {
+        match self {
+            SelectionStyle::DimOutside => SelectionStyle::DimInside,
+            SelectionStyle::DimInside => SelectionStyle::BorderOnly,
+            SelectionStyle::BorderOnly => SelectionStyle::DimOutside,
+        }
+    }
+
+    /// ログ表示用ラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            SelectionStyle::DimOutside => strings::selection_style_dim_outside(),
+            SelectionStyle::DimInside => strings::selection_style_dim_inside(),
+            SelectionStyle::BorderOnly => strings::selection_style_border_only(),
+        }
+    }
+}
 
 /// エリア選択オーバーレイ構造体
 /// 
@@ -83,10 +186,19 @@ use crate::overlay::*;
 /// # 構造体フィールド詳細
 /// - `hwnd`: オーバーレイウィンドウハンドル（SafeHWNDでラップ）
 /// - `semi_transparent_black_brush`: 半透明黒背景ブラシ（Alpha=60%）
+/// - `semi_transparent_white_brush`: ダークテーマ用の半透明白背景ブラシ（Alpha=40%）
 /// - `transparent_brush`: 選択領域くり抜き用透明ブラシ（Alpha=0%）
-/// - `red_pen`: 境界線描画用赤色ペン（1ピクセル幅）
-/// - `resize_handles_brush`: リサイズハンドル描画用ブラシ（将来拡張用）
-/// - `resize_handles_pen`: リサイズハンドル境界用ペン（将来拡張用）
+/// - `hit_test_brush`: 背景を暗くしない場合の背景ブラシ（Alpha=1、見た目は透明のままマウス判定を維持）
+/// - `border_pen`: 境界線描画用ペン（既定は赤色2ピクセル幅、`update_style` で変更）
+/// - `border_halo_pen`: 境界線の縁取り用の白いペン（境界線より2ピクセル太い）
+/// - `resize_handles_brush`: リサイズハンドル描画用ブラシ（境界線と同じ色相の半透明）
+/// - `resize_handles_pen`: リサイズハンドル境界用ペン（境界線と同じ色）
+/// - `mask_hatch_brush`: マスク領域描画用の青色斜線ブラシ
+/// - `mask_pen`: マスク領域境界線用の青色ペン
+/// - `grid_pen`: 三分割グリッド用の半透明グレーのペン（1ピクセル幅）
+/// - `crosshair_pen`: ドラッグ前の十字ガイド線用ペン（境界線と同じ色の半透明、1ピクセル幅）
+/// - `coordinate_font` / `coordinate_text_brush` / `coordinate_back_brush`: 座標ラベルのフォント・白文字・半透明黒背景
+/// - `frozen_frame`: 画面の固定が有効な場合の、エリア選択開始時の画面全体（エリア選択中のみ保持）
 /// 
 /// # 描画リソース設計
 /// 全てのGDI+オブジェクトは初期化時に作成され、描画処理で再利用されます。
@@ -100,10 +212,21 @@ use crate::overlay::*;
 pub struct AreaSelectOverLay {
     hwnd: Option<SafeHWND>,
     semi_transparent_black_brush: *mut GpSolidFill, // 半透明黒背景ブラシ
+    semi_transparent_white_brush: *mut GpSolidFill, // ダークテーマ用の半透明白背景ブラシ
     transparent_brush: *mut GpSolidFill,            // くり抜き用の透明ブラシ
-    red_pen: *mut GpPen,                            // 赤色境界線ペン
+    hit_test_brush: *mut GpSolidFill,               // 暗くしない背景用のほぼ透明なブラシ
+    border_pen: *mut GpPen,                         // 境界線ペン
+    border_halo_pen: *mut GpPen,                    // 境界線の縁取り（白）ペン
     resize_handles_brush: *mut GpSolidFill,         // リサイズハンドル用のブラシ
     resize_handles_pen: *mut GpPen,                 // リサイズハンドル用ペン
+    mask_hatch_brush: *mut GpHatch,                 // マスク領域用の斜線ブラシ
+    mask_pen: *mut GpPen,                           // マスク領域境界線ペン
+    grid_pen: *mut GpPen,                           // 三分割グリッドのペン
+    crosshair_pen: *mut GpPen,                      // 十字ガイド線のペン
+    coordinate_font: *mut GpFont,                   // 座標ラベルのフォント
+    coordinate_text_brush: *mut GpSolidFill,        // 座標ラベルの文字ブラシ
+    coordinate_back_brush: *mut GpSolidFill,        // 座標ラベルの背景ブラシ
+    frozen_frame: *mut GpBitmap,                    // 固定した画面（エリア選択中のみ）
 }
 
 /// エリア選択オーバーレイ構造体実装
@@ -118,7 +241,7 @@ impl AreaSelectOverLay {
     /// # 初期化処理内容
     /// 1. **半透明背景ブラシ**: Alpha=60%の黒背景（視覚的抑制効果）
     /// 2. **透明ブラシ**: Alpha=0%（選択領域くり抜き用）
-    /// 3. **境界線ペン**: 赤色1ピクセル（明確な視覚的境界）
+    /// 3. **境界線ペン**: 赤色2ピクセル＋白い縁取り用ペン（明確な視覚的境界、`update_style` で変更）
     /// 4. **拡張ハンドル**: リサイズ機能用リソース（将来対応）
     ///
     /// # 色彩設計の根拠
@@ -142,10 +265,21 @@ impl AreaSelectOverLay {
         let mut overlay = AreaSelectOverLay {
             hwnd: None,
             semi_transparent_black_brush: std::ptr::null_mut(),
+            semi_transparent_white_brush: std::ptr::null_mut(),
             transparent_brush: std::ptr::null_mut(),
-            red_pen: std::ptr::null_mut(),
+            hit_test_brush: std::ptr::null_mut(),
+            border_pen: std::ptr::null_mut(),
+            border_halo_pen: std::ptr::null_mut(),
             resize_handles_brush: std::ptr::null_mut(),
             resize_handles_pen: std::ptr::null_mut(),
+            mask_hatch_brush: std::ptr::null_mut(),
+            mask_pen: std::ptr::null_mut(),
+            grid_pen: std::ptr::null_mut(),
+            crosshair_pen: std::ptr::null_mut(),
+            coordinate_font: std::ptr::null_mut(),
+            coordinate_text_brush: std::ptr::null_mut(),
+            coordinate_back_brush: std::ptr::null_mut(),
+            frozen_frame: std::ptr::null_mut(),
         };
 
         // === GDI+描画リソースの段階的初期化 ===
@@ -164,6 +298,20 @@ impl AreaSelectOverLay {
                 );
             }
 
+            // 1.1 ダークテーマ用の半透明白背景ブラシ作成
+            // Alpha=102(0x66): 暗い壁紙・ダークテーマのアプリ上では、黒で暗くするより明るく覆う方が選択範囲が見やすい
+            let semi_transparent_white_color = Color { Argb: 0x66FFFFFF };
+            let status = GdipCreateSolidFill(
+                semi_transparent_white_color.Argb,
+                &mut overlay.semi_transparent_white_brush,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for semi_transparent_white_brush failed with status {:?}",
+                    status
+                );
+            }
+
             // 2. 透明ブラシ作成（選択領域くり抜き用）
             // Alpha=0: 完全透明で選択領域を鮮明に表示
             let transparent_color = Color { Argb: 0x00000000 };
@@ -176,14 +324,44 @@ impl AreaSelectOverLay {
                 );
             }
 
-            // 3. 赤色境界線ペン作成
+            // 2.1 ほぼ透明ブラシ作成（DimInside / BorderOnly の背景用）
+            // Alpha=1: 見た目は透明だが、レイヤードウィンドウのマウス判定（カーソル表示）を維持
+            let hit_test_color = Color { Argb: 0x01000000 };
+            let status = GdipCreateSolidFill(hit_test_color.Argb, &mut overlay.hit_test_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for hit_test_brush failed with status {:?}",
+                    status
+                );
+            }
+
+            // 3. 境界線ペン作成（色と太さは update_style で設定値に差し替え）
             // 赤色（#FF0000）: 高い視認性で選択範囲を明確に表示
             // 2.0px幅: 高DPI環境でも視認可能な適切な太さ
-            let red_color = Color { Argb: 0xFFFF0000 };
-            let status = GdipCreatePen1(red_color.Argb, 2.0, UnitPixel, &mut overlay.red_pen);
+            let border_color = Color { Argb: DEFAULT_BORDER_ARGB };
+            let status = GdipCreatePen1(
+                border_color.Argb,
+                DEFAULT_BORDER_WIDTH as f32,
+                UnitPixel,
+                &mut overlay.border_pen,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreatePen1 for border_pen failed with status {:?}",
+                    status
+                );
+            }
+
+            // 3.1 境界線の縁取りペン作成（白色、境界線の両側に1pxずつはみ出す太さ）
+            let status = GdipCreatePen1(
+                BORDER_HALO_ARGB,
+                DEFAULT_BORDER_WIDTH as f32 + 2.0,
+                UnitPixel,
+                &mut overlay.border_halo_pen,
+            );
             if status != Status(0) {
                 eprintln!(
-                    "❌ GdipCreatePen1 for red_pen failed with status {:?}",
+                    "❌ GdipCreatePen1 for border_halo_pen failed with status {:?}",
                     status
                 );
             }
@@ -215,12 +393,215 @@ impl AreaSelectOverLay {
                     status
                 );
             }
+
+            // 6. マスク領域用の斜線ブラシ作成
+            // 青色斜線＋半透明黒背景: キャプチャ領域（赤）と混同しない配色
+            let mask_fore_color = Color { Argb: 0xFF0078D7 };
+            let mask_back_color = Color { Argb: 0x99000000 };
+            let status = GdipCreateHatchBrush(
+                HatchStyleWideDownwardDiagonal,
+                mask_fore_color.Argb,
+                mask_back_color.Argb,
+                &mut overlay.mask_hatch_brush,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateHatchBrush for mask_hatch_brush failed with status {:?}",
+                    status
+                );
+            }
+
+            // 7. マスク領域境界線ペン作成（青色2px）
+            let status = GdipCreatePen1(mask_fore_color.Argb, 2.0, UnitPixel, &mut overlay.mask_pen);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreatePen1 for mask_pen failed with status {:?}",
+                    status
+                );
+            }
+
+            // 8. 三分割グリッド用ペン作成（半透明グレー1px）
+            // 明るい画面・暗い画面のどちらでも見え、選択範囲の内容を邪魔しない控えめな線
+            let grid_color = Color { Argb: 0x99808080 };
+            let status = GdipCreatePen1(grid_color.Argb, 1.0, UnitPixel, &mut overlay.grid_pen);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreatePen1 for grid_pen failed with status {:?}",
+                    status
+                );
+            }
+
+            // 9. 十字ガイド線用ペン作成（境界線と同じ色の半透明1px、色は update_style で連動）
+            let status = GdipCreatePen1(
+                (CROSSHAIR_ALPHA << 24) | (DEFAULT_BORDER_ARGB & 0x00FFFFFF),
+                1.0,
+                UnitPixel,
+                &mut overlay.crosshair_pen,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreatePen1 for crosshair_pen failed with status {:?}",
+                    status
+                );
+            }
+
+            // 10. 座標ラベル用ブラシ作成（白文字＋半透明黒背景: どの背景でも読める配色）
+            let status = GdipCreateSolidFill(0xFFFFFFFF, &mut overlay.coordinate_text_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for coordinate_text_brush failed with status {:?}",
+                    status
+                );
+            }
+            let status = GdipCreateSolidFill(0xCC000000, &mut overlay.coordinate_back_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for coordinate_back_brush failed with status {:?}",
+                    status
+                );
+            }
         }
 
+        // 11. 座標ラベル用フォント作成
+        overlay.coordinate_font = create_coordinate_font();
+
         // 初期化完了したオーバーレイインスタンスを返却
         // 一部リソース作成に失敗していても、利用可能な機能で動作継続
         overlay
     }
+
+    /// 暗転ブラシの不透明度を変更する
+    ///
+    /// 半透明黒（ライトテーマ）のアルファを `percent`（50〜100%）に設定し、
+    /// ダークテーマ用の半透明白は既定の比率（黒60%に対して白40%）を保って連動させます。
+    /// ブラシを作り直さずに色だけを差し替えるため、エリア選択中でも次の描画から反映されます。
+    ///
+    /// # 引数
+    /// * `percent` - 暗転の不透明度（%、50〜100の範囲に丸める）
+    pub fn set_dim_opacity(&self, percent: u8) {
+        let black_alpha = percent.clamp(50, 100) as u32 * 255 / 100;
+        let white_alpha = black_alpha * 2 / 3;
+        unsafe {
+            if !self.semi_transparent_black_brush.is_null() {
+                GdipSetSolidFillColor(self.semi_transparent_black_brush, black_alpha << 24);
+            }
+            if !self.semi_transparent_white_brush.is_null() {
+                GdipSetSolidFillColor(
+                    self.semi_transparent_white_brush,
+                    (white_alpha << 24) | 0x00FFFFFF,
+                );
+            }
+        }
+    }
+
+    /// 境界線の色と太さを変更する
+    ///
+    /// 境界線ペンを指定の色・太さに、リサイズハンドルを同じ色相（塗りは半透明）に、
+    /// 縁取りペンを境界線より2ピクセル太く、十字ガイド線を同じ色の半透明に設定します。
+    /// `set_dim_opacity` と同様にペン・ブラシを作り直さずに差し替えるため、エリア選択中でも次の描画から反映されます。
+    ///
+    /// # 引数
+    /// * `argb` - 境界線の色（アルファは無視し、常に不透明で描画）
+    /// * `width` - 境界線の太さ（ピクセル、1〜4の範囲に丸める）
+    pub fn update_style(&self, argb: u32, width: u8) {
+        let rgb = argb & 0x00FFFFFF;
+        let width = width.clamp(*BORDER_WIDTH_RANGE.start(), *BORDER_WIDTH_RANGE.end()) as f32;
+        unsafe {
+            if !self.border_pen.is_null() {
+                GdipSetPenColor(self.border_pen, 0xFF000000 | rgb);
+                GdipSetPenWidth(self.border_pen, width);
+            }
+            if !self.border_halo_pen.is_null() {
+                GdipSetPenWidth(self.border_halo_pen, width + 2.0);
+            }
+            if !self.resize_handles_brush.is_null() {
+                GdipSetSolidFillColor(self.resize_handles_brush, 0x80000000 | rgb);
+            }
+            if !self.resize_handles_pen.is_null() {
+                GdipSetPenColor(self.resize_handles_pen, 0xFF000000 | rgb);
+            }
+            if !self.crosshair_pen.is_null() {
+                GdipSetPenColor(self.crosshair_pen, (CROSSHAIR_ALPHA << 24) | rgb);
+            }
+        }
+    }
+
+    /// 現在の画面全体を固定フレームとして保存する（画面の固定）
+    ///
+    /// `BitBlt` で画面全体をビットマップにコピーし、GDI+ビットマップに変換して保持します。
+    /// オーバーレイを表示する前に呼び出すことで、選択中に画面が変化しても保存した静止画を背景に描画します。
+    /// 既に保持している固定フレームは解放してから作り直します。
+    ///
+    /// # 引数
+    /// * `width` - 画面の幅（ピクセル）
+    /// * `height` - 画面の高さ（ピクセル）
+    ///
+    /// # 戻り値
+    /// 固定フレームを保存できた場合は `true`。失敗した場合は `false`（通常の透過表示で選択を続行）。
+    pub fn capture_frozen_frame(&mut self, width: i32, height: i32) -> bool {
+        self.release_frozen_frame();
+        unsafe {
+            let screen_dc = GetDC(None);
+            if screen_dc.is_invalid() {
+                return false;
+            }
+            let memory_dc = CreateCompatibleDC(Some(screen_dc));
+            let hbitmap = CreateCompatibleBitmap(screen_dc, width, height);
+            let old_bitmap = SelectObject(memory_dc, hbitmap.into());
+
+            // CAPTUREBLT: 他のレイヤードウィンドウ（ツールチップ等）も含めて見たままを保存
+            let copied = BitBlt(
+                memory_dc,
+                0,
+                0,
+                width,
+                height,
+                Some(screen_dc),
+                0,
+                0,
+                SRCCOPY | CAPTUREBLT,
+            )
+            .is_ok();
+
+            // GDI+ビットマップへ変換する前にメモリDCから外す（選択中のビットマップは変換できない）
+            let _ = SelectObject(memory_dc, old_bitmap);
+            let _ = DeleteDC(memory_dc);
+            let _ = ReleaseDC(None, screen_dc);
+
+            let mut frozen_frame: *mut GpBitmap = std::ptr::null_mut();
+            let status = if copied {
+                GdipCreateBitmapFromHBITMAP(hbitmap, HPALETTE::default(), &mut frozen_frame)
+            } else {
+                Status(1)
+            };
+            // GDI+ビットマップはピクセルをコピーして保持するため、元のビットマップはすぐに削除できる
+            let _ = DeleteObject(hbitmap.into());
+
+            if status != Status(0) || frozen_frame.is_null() {
+                eprintln!(
+                    "❌ 固定フレームの作成に失敗しました (BitBlt: {}, status: {:?})",
+                    copied, status
+                );
+                return false;
+            }
+            self.frozen_frame = frozen_frame;
+            true
+        }
+    }
+
+    /// 固定フレームを解放する（エリア選択の終了時）
+    ///
+    /// 画面全体のビットマップは数十MBになるため、エリア選択中のみ保持します。
+    /// 固定フレームを保持していない場合は何もしません。
+    pub fn release_frozen_frame(&mut self) {
+        if self.frozen_frame.is_null() {
+            return;
+        }
+        unsafe {
+            GdipDisposeImage(self.frozen_frame as *mut _);
+        }
+        self.frozen_frame = std::ptr::null_mut();
+    }
 }
 
 /// AreaSelectOverLay用RAII自動リソース解放実装
@@ -233,6 +614,7 @@ impl AreaSelectOverLay {
 /// - オーバーレイウィンドウ（destroy_overlay()経由）
 /// - GDI+ブラシオブジェクト群（半透明黒、透明、リサイズハンドル）
 /// - GDI+ペンオブジェクト群（境界線、リサイズハンドル境界）
+/// - 固定フレーム（エリア選択中に破棄された場合）
 /// 
 /// # 解放順序の安全性
 /// GDI+オブジェクトは相互依存がないため、任意の順序で安全に解放可能。
@@ -246,13 +628,28 @@ impl Drop for AreaSelectOverLay {
         unsafe {
             // ブラシオブジェクト解放
             GdipDeleteBrush(self.semi_transparent_black_brush as *mut _);
+            GdipDeleteBrush(self.semi_transparent_white_brush as *mut _);
             GdipDeleteBrush(self.transparent_brush as *mut _);
+            GdipDeleteBrush(self.hit_test_brush as *mut _);
             GdipDeleteBrush(self.resize_handles_brush as *mut _);
+            GdipDeleteBrush(self.mask_hatch_brush as *mut _);
+            GdipDeleteBrush(self.coordinate_text_brush as *mut _);
+            GdipDeleteBrush(self.coordinate_back_brush as *mut _);
             
             // ペンオブジェクト解放
-            GdipDeletePen(self.red_pen);
+            GdipDeletePen(self.border_pen);
+            GdipDeletePen(self.border_halo_pen);
             GdipDeletePen(self.resize_handles_pen);
+            GdipDeletePen(self.mask_pen);
+            GdipDeletePen(self.grid_pen);
+            GdipDeletePen(self.crosshair_pen);
+
+            // フォント解放
+            GdipDeleteFont(self.coordinate_font);
         }
+
+        // 3. 固定フレーム解放（エリア選択中に終了した場合）
+        self.release_frozen_frame();
     }
 }
 
@@ -270,11 +667,18 @@ impl Overlay for AreaSelectOverLay {
     fn get_description(&self) -> &str {
         "エリア選択オーバーレイ"
     }
+    /// 暗転・くり抜き用のブラシと境界線ペンがあれば表示可能（座標ラベル等は個別にスキップ）
+    fn is_usable(&self) -> bool {
+        !self.semi_transparent_black_brush.is_null()
+            && !self.transparent_brush.is_null()
+            && !self.border_pen.is_null()
+    }
     fn get_window_proc(&self) -> OverlayWindowProc {
         OverlayWindowProc {
             create: None,
             paint: Some(overlay_window_paint),
             destroy: None,
+            alpha: None,
         }
     }
 
@@ -339,10 +743,12 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
     
     // === AppState から描画に必要な状態情報を取得 ===
     let app_state = AppState::get_app_state_ref();
-    let (is_dragging, screen_width, screen_height) = (
-        app_state.is_dragging,         // ユーザーがドラッグ操作中かを判定
+    let (is_dragging, is_mask_dragging, screen_width, screen_height, selection_style) = (
+        app_state.is_dragging(),       // ユーザーがドラッグ操作中かを判定
+        app_state.is_mask_dragging,    // Ctrl+ドラッグ（マスク領域の選択）中かを判定
         app_state.screen_width,        // プライマリスクリーンの幅（ピクセル）
         app_state.screen_height,       // プライマリスクリーンの高さ（ピクセル）
+        app_state.selection_style,     // 選択領域の強調方法
     );
 
     // 描画対象オーバーレイインスタンスを取得（GDI+リソースアクセス用）
@@ -351,13 +757,40 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
         .as_ref()
         .expect("エリア選択オーバーレイが存在しません。");
 
+    // 暗転に使うブラシ（ダークテーマでは半透明白で明るく覆う）
+    let dim_brush = if is_dark_mode() {
+        overlay.semi_transparent_white_brush
+    } else {
+        overlay.semi_transparent_black_brush
+    };
+
+    // === 0. 固定フレーム描画（画面の固定が有効な場合のみ） ===
+    // エリア選択開始時の画面を不透明で描画し、以降の暗転・くり抜き・境界線はこの静止画の上に重ねる
+    let frozen_frame = overlay.frozen_frame;
+    if !frozen_frame.is_null() {
+        unsafe {
+            GdipDrawImageRectI(
+                graphics,
+                frozen_frame as *mut _,
+                0,
+                0,
+                screen_width,
+                screen_height,
+            );
+        }
+    }
+
     // === 1. 全画面背景マスク描画 ===
-    // 半透明黒（Alpha=60%）で画面全体を覆い、非選択領域の視覚的重要度を下げる
-    // この処理により、ユーザーの注意を選択領域に集中させることができる
+    // DimOutside: 半透明黒（Alpha=60%、ダークテーマでは半透明白）で画面全体を覆い、非選択領域の視覚的重要度を下げる
+    // DimInside / BorderOnly: 背景は暗くしない（ほぼ透明で覆い、マウス判定のみ維持）
+    let background_brush = match selection_style {
+        SelectionStyle::DimOutside => dim_brush,
+        SelectionStyle::DimInside | SelectionStyle::BorderOnly => overlay.hit_test_brush,
+    };
     unsafe {
         GdipFillRectangleI(
             graphics,
-            overlay.semi_transparent_black_brush as *mut _,
+            background_brush as *mut _,
             0,                          // X座標：左端から
             0,                          // Y座標：上端から
             screen_width,               // 幅：画面全幅
@@ -365,44 +798,73 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
         );
     }
 
+    // === 1.1 確定済みマスク領域の描画 ===
+    draw_mask_areas(overlay, graphics, &app_state.mask_areas);
+
     // === 2. ドラッグ中の動的選択領域処理 ===
-    if is_dragging {
+    if is_dragging && is_mask_dragging {
+        // Ctrl+ドラッグ中：マスク領域として斜線で表示（くり抜き・ハンドルは描画しない）
+        let drag_rect = to_win32_rect(current_drag_rect(app_state));
+        draw_mask_areas(overlay, graphics, &[drag_rect]);
+    } else if is_dragging {
         // === 2.1 ドラッグ開始点と終了点から正規化された矩形領域を計算 ===
-        // min/max関数により、任意方向のドラッグ（右下・左上・右上・左下）に対応
-        let (left, top, right, bottom) = {
-            let left = app_state.drag_start.x.min(app_state.drag_end.x);
-            let top = app_state.drag_start.y.min(app_state.drag_end.y);
-            let right = app_state.drag_start.x.max(app_state.drag_end.x);
-            let bottom = app_state.drag_start.y.max(app_state.drag_end.y);
-            (left, top, right, bottom)
-        };
-        let width = right - left;      // 選択領域の幅（ピクセル）
-        let height = bottom - top;     // 選択領域の高さ（ピクセル）
+        // `DragRect::from_corners` により、任意方向のドラッグ（右下・左上・右上・左下）に対応
+        // （エリア選択の確定時と同じ正規化を使用し、表示と保存される範囲を一致させる）
+        let drag_rect = current_drag_rect(app_state);
+        let (left, top) = (drag_rect.left, drag_rect.top);
+        let width = drag_rect.width();      // 選択領域の幅（ピクセル）
+        let height = drag_rect.height();    // 選択領域の高さ（ピクセル）
 
-        // === 2.2 選択領域の透明くり抜き処理 ===
+        // === 2.2 選択領域の塗りつぶし処理 ===
         // CompositingModeSourceCopy: アルファブレンド無視で完全上書き
-        // 背景マスクの上に透明領域を描画し、選択範囲を鮮明に表示
-        unsafe {
-            GdipSetCompositingMode(graphics, CompositingModeSourceCopy);
-            GdipFillRectangleI(
+        // DimOutside: 背景マスクの上に透明領域を描画し、選択範囲を鮮明に表示
+        // DimInside: 選択範囲を半透明黒で塗り、キャプチャから除外される外側を確認しやすくする
+        // BorderOnly: 塗りつぶさない（境界線のみ）
+        let selection_brush = match selection_style {
+            SelectionStyle::DimOutside => Some(overlay.transparent_brush),
+            SelectionStyle::DimInside => Some(dim_brush),
+            SelectionStyle::BorderOnly => None,
+        };
+        // 固定フレームがある場合：透明にすると実際の画面が見えてしまうため、固定フレームで描き直す
+        if !frozen_frame.is_null() {
+            draw_frozen_selection(
+                overlay,
                 graphics,
-                overlay.transparent_brush as *mut _,
-                left,                       // 選択領域の左端X座標
-                top,                        // 選択領域の上端Y座標
-                width,                      // 選択領域の幅
-                height,                     // 選択領域の高さ
+                selection_style,
+                dim_brush,
+                GpRect {
+                    X: left,
+                    Y: top,
+                    Width: width,
+                    Height: height,
+                },
             );
-            // CompositingModeSourceOver: 通常の透過描画モードに復帰
-            GdipSetCompositingMode(graphics, CompositingModeSourceOver);
+        } else if let Some(selection_brush) = selection_brush {
+            unsafe {
+                GdipSetCompositingMode(graphics, CompositingModeSourceCopy);
+                GdipFillRectangleI(
+                    graphics,
+                    selection_brush as *mut _,
+                    left,                       // 選択領域の左端X座標
+                    top,                        // 選択領域の上端Y座標
+                    width,                      // 選択領域の幅
+                    height,                     // 選択領域の高さ
+                );
+                // CompositingModeSourceOver: 通常の透過描画モードに復帰
+                GdipSetCompositingMode(graphics, CompositingModeSourceOver);
+            }
         }
 
         // === 2.3 選択領域境界線の描画 ===
-        // 赤色2ピクセル境界線で選択範囲を明確に表示
-        // 高い視認性により、ユーザーが選択範囲を正確に把握可能
+        // 設定の色・太さ（既定は赤色2ピクセル）の境界線で選択範囲を明確に表示
+        // 縁取り有効時は、先に白い太線を描いて境界線の両側に1pxの白線を残す（同系色の背景でも見える）
         unsafe {
+            if app_state.area_select_border_halo && !overlay.border_halo_pen.is_null() {
+                GdipDrawRectangleI(graphics, overlay.border_halo_pen, left, top, width, height);
+            }
             GdipDrawRectangleI(
                 graphics, 
-                overlay.red_pen,            // 赤色ペン（#FFFF0000, 2px幅）
+                overlay.border_pen,         // 境界線ペン（既定 #FFFF0000, 2px幅）
                 left,                       // 矩形左端X座標
                 top,                        // 矩形上端Y座標  
                 width,                      // 矩形幅
@@ -410,7 +872,12 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
             );
         }
 
-        // === 2.4 リサイズハンドル描画 ===
+        // === 2.4 三分割グリッド描画（有効時のみ） ===
+        if app_state.show_selection_grid {
+            draw_thirds_grid(overlay, graphics, left, top, width, height);
+        }
+
+        // === 2.5 リサイズハンドル描画 ===
         // 選択範囲の四隅にリサイズハンドルを配置し、将来的なサイズ調整機能を提供
         let border_rect = GpRect {
             X: left,                        // 選択領域の左端座標
@@ -419,6 +886,255 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
             Height: height,                 // 選択領域の高さ
         };
         draw_resize_handles(overlay, graphics, border_rect);
+    } else if app_state.is_area_select_mode() {
+        // === 3. ドラッグ開始前：十字ガイド線と座標ラベル ===
+        // 始点を合わせやすくするため、カーソルを通る画面全体の縦横線を描画（ドラッグ開始で消える）
+        draw_crosshair(
+            overlay,
+            graphics,
+            app_state.current_mouse_pos(),
+            screen_width,
+            screen_height,
+        );
+    }
+}
+
+/// 画面の固定中に、選択範囲を強調方法に応じて描画する
+///
+/// 固定フレームは不透明のため、くり抜き（透明化）の代わりに選択範囲へ固定フレームを描き直します。
+///
+/// # 引数
+/// * `overlay` - エリア選択オーバーレイの参照（固定フレームアクセス用）
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+/// * `selection_style` - 選択範囲の強調方法
+/// * `dim_brush` - 暗転に使うブラシ
+/// * `rect` - 選択範囲（スクリーン座標）
+fn draw_frozen_selection(
+    overlay: &AreaSelectOverLay,
+    graphics: *mut GpGraphics,
+    selection_style: SelectionStyle,
+    dim_brush: *mut GpSolidFill,
+    rect: GpRect,
+) {
+    unsafe {
+        match selection_style {
+            // 外側を暗くする：暗転した選択範囲に、同じ位置の固定フレームを原寸で重ねる
+            SelectionStyle::DimOutside => {
+                GdipDrawImageRectRectI(
+                    graphics,
+                    overlay.frozen_frame as *mut _,
+                    rect.X,
+                    rect.Y,
+                    rect.Width,
+                    rect.Height,
+                    rect.X,
+                    rect.Y,
+                    rect.Width,
+                    rect.Height,
+                    UnitPixel,
+                    std::ptr::null(),
+                    0,
+                    std::ptr::null_mut(),
+                );
+            }
+            // 内側を暗くする：背景は暗転していないため、選択範囲に暗転を重ねる
+            SelectionStyle::DimInside => {
+                GdipFillRectangleI(
+                    graphics,
+                    dim_brush as *mut _,
+                    rect.X,
+                    rect.Y,
+                    rect.Width,
+                    rect.Height,
+                );
+            }
+            SelectionStyle::BorderOnly => {}
+        }
+    }
+}
+
+/// マスク領域を青色の斜線と境界線で描画する
+///
+/// キャプチャ領域（透明くり抜き＋赤枠）と区別できるよう、斜線ブラシで塗りつぶします。
+///
+/// # 引数
+/// * `overlay` - エリア選択オーバーレイの参照（描画リソースアクセス用）
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+/// * `mask_areas` - 描画するマスク領域（スクリーン座標）
+fn draw_mask_areas(overlay: &AreaSelectOverLay, graphics: *mut GpGraphics, mask_areas: &[RECT]) {
+    for mask in mask_areas {
+        let width = mask.right - mask.left;
+        let height = mask.bottom - mask.top;
+        unsafe {
+            GdipFillRectangleI(
+                graphics,
+                overlay.mask_hatch_brush as *mut _,
+                mask.left,
+                mask.top,
+                width,
+                height,
+            );
+            GdipDrawRectangleI(graphics, overlay.mask_pen, mask.left, mask.top, width, height);
+        }
+    }
+}
+
+/// 選択範囲を縦横それぞれ3等分するガイド線（三分割グリッド）を描画する
+///
+/// # 引数
+/// * `overlay` - エリア選択オーバーレイの参照（描画リソースアクセス用）
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+/// * `left`, `top`, `width`, `height` - 選択範囲（スクリーン座標）
+fn draw_thirds_grid(
+    overlay: &AreaSelectOverLay,
+    graphics: *mut GpGraphics,
+    left: i32,
+    top: i32,
+    width: i32,
+    height: i32,
+) {
+    // 線が重なって見分けられない小さな選択範囲では描画しない
+    if width < 3 || height < 3 || overlay.grid_pen.is_null() {
+        return;
+    }
+    for step in 1..=2 {
+        let x = left + width * step / 3;
+        let y = top + height * step / 3;
+        unsafe {
+            GdipDrawLineI(graphics, overlay.grid_pen, x, top, x, top + height); // 縦線
+            GdipDrawLineI(graphics, overlay.grid_pen, left, y, left + width, y); // 横線
+        }
+    }
+}
+
+/// カーソル位置を通る十字ガイド線と、カーソル座標のラベルを描画する
+///
+/// ラベルはカーソルの右下に表示し、画面の右端・下端からはみ出す場合はカーソルの反対側に移します。
+///
+/// # 引数
+/// * `overlay` - エリア選択オーバーレイの参照（描画リソースアクセス用）
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+/// * `cursor` - カーソル位置（スクリーン座標）
+/// * `screen_width`, `screen_height` - オーバーレイ（画面）のサイズ
+fn draw_crosshair(
+    overlay: &AreaSelectOverLay,
+    graphics: *mut GpGraphics,
+    cursor: POINT,
+    screen_width: i32,
+    screen_height: i32,
+) {
+    if !overlay.crosshair_pen.is_null() {
+        unsafe {
+            let pen = overlay.crosshair_pen;
+            GdipDrawLineI(graphics, pen, cursor.x, 0, cursor.x, screen_height); // 縦線
+            GdipDrawLineI(graphics, pen, 0, cursor.y, screen_width, cursor.y); // 横線
+        }
+    }
+
+    if overlay.coordinate_font.is_null() {
+        return;
+    }
+    let text = format!("{}, {}", cursor.x, cursor.y);
+    let text_utf16: Vec<u16> = text.encode_utf16().collect();
+    unsafe {
+        // 文字列の大きさを測ってラベルの背景矩形を決める
+        let layout_rect = RectF {
+            X: 0.0,
+            Y: 0.0,
+            Width: screen_width as f32,
+            Height: screen_height as f32,
+        };
+        let mut text_bounds = RectF::default();
+        GdipMeasureString(
+            graphics,
+            PCWSTR(text_utf16.as_ptr()),
+            text_utf16.len() as i32,
+            overlay.coordinate_font,
+            &layout_rect,
+            std::ptr::null(),
+            &mut text_bounds,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        );
+        let label_width = text_bounds.Width.ceil() as i32 + COORDINATE_LABEL_PADDING * 2;
+        let label_height = text_bounds.Height.ceil() as i32 + COORDINATE_LABEL_PADDING * 2;
+
+        let mut label_x = cursor.x + COORDINATE_LABEL_OFFSET;
+        if label_x + label_width > screen_width {
+            label_x = cursor.x - COORDINATE_LABEL_OFFSET - label_width;
+        }
+        let mut label_y = cursor.y + COORDINATE_LABEL_OFFSET;
+        if label_y + label_height > screen_height {
+            label_y = cursor.y - COORDINATE_LABEL_OFFSET - label_height;
+        }
+
+        GdipFillRectangleI(
+            graphics,
+            overlay.coordinate_back_brush as *mut _,
+            label_x,
+            label_y,
+            label_width,
+            label_height,
+        );
+        let text_rect = RectF {
+            X: (label_x + COORDINATE_LABEL_PADDING) as f32,
+            Y: (label_y + COORDINATE_LABEL_PADDING) as f32,
+            Width: text_bounds.Width.ceil(),
+            Height: text_bounds.Height.ceil(),
+        };
+        GdipDrawString(
+            graphics,
+            PCWSTR(text_utf16.as_ptr()),
+            text_utf16.len() as i32,
+            overlay.coordinate_font,
+            &text_rect,
+            std::ptr::null(),
+            overlay.coordinate_text_brush as *mut _,
+        );
+    }
+}
+
+/// 座標ラベル用のフォントを作成する
+///
+/// Segoe UI、GDI+の汎用サンセリフの順に試します。
+/// サイズは `COORDINATE_FONT_SIZE`（96DPI基準）をシステムDPIで拡大したピクセル数です。
+///
+/// # 戻り値
+/// 作成したフォント。失敗した場合はnull（座標ラベルは表示されず、十字ガイド線のみ描画）。
+fn create_coordinate_font() -> *mut GpFont {
+    let font_size = COORDINATE_FONT_SIZE * get_system_dpi() as f32 / 96.0;
+    unsafe {
+        let mut font_family: *mut GpFontFamily = std::ptr::null_mut();
+        let status =
+            GdipCreateFontFamilyFromName(w!("Segoe UI"), std::ptr::null_mut(), &mut font_family);
+        let owns_family = if status == Status(0) {
+            true
+        } else if GdipGetGenericFontFamilySansSerif(&mut font_family) == Status(0) {
+            false // GDI+が管理する汎用ファミリーのため解放しない
+        } else {
+            eprintln!("❌ 座標ラベルのフォントファミリーを取得できませんでした");
+            return std::ptr::null_mut();
+        };
+
+        let mut font: *mut GpFont = std::ptr::null_mut();
+        let status = GdipCreateFont(
+            font_family,
+            font_size,
+            Default::default(), // FontStyleRegular（標準）
+            UnitPixel,
+            &mut font,
+        );
+        if owns_family {
+            GdipDeleteFontFamily(font_family);
+        }
+        if status != Status(0) {
+            eprintln!(
+                "❌ GdipCreateFont for coordinate_font failed with status {:?}",
+                status
+            );
+            return std::ptr::null_mut();
+        }
+        font
     }
 }
 
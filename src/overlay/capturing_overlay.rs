@@ -13,6 +13,8 @@ ClickCaptureアプリケーションのキャプチャモード中に表示さ
     -   キャプチャ待機中：待機アイコン表示
     -   キャプチャ処理中：処理中アイコン表示
     -   自動クリック中：進行状況付きツールチップ表示
+    -   定期キャプチャ中：経過時間とキャプチャ枚数のラベル表示（選択エリア左上に固定表示）
+    -   2領域選択中（デュアル選択）：アイコン右側に「2領域」バッジ表示
 
 2.  **リアルタイム視覚フィードバック**: `overlay_window_paint`
     -   GDI+による高品質アイコン描画
@@ -25,11 +27,18 @@ ClickCaptureアプリケーションのキャプチャモード中に表示さ
     -   RAII パターンによる自動リソース解放
 
 【技術仕様】
--   **オーバーレイサイズ**: 230x90ピクセル（アイコン32x32 + テキスト領域）
+-   **オーバーレイサイズ**: 230x90ピクセル（96DPI基準、アイコン32x32 + テキスト領域）
 -   **描画エンジン**: GDI+ による高品質レンダリング
 -   **透明処理**: LayeredWindow + UpdateLayeredWindow（ハードウェア加速）
 -   **位置制御**: WS_EX_TRANSPARENT による背景オブジェクトとの非干渉
--   **フォント**: Yu Gothic UI 16pt（日本語対応、高DPI対応）
+-   **表示方法**: `CapturingOverlayVisibility`（表示 / 半透明 / 非表示）に応じて `SourceConstantAlpha` を 255 / 128 / 0 に設定
+-   **キャプチャからの除外**: `SetWindowDisplayAffinity(WDA_EXCLUDEFROMCAPTURE)` でウィンドウ全体（アイコン + ラベル）を除外
+    （値・配置はライブラリの `capturing_overlay_layout.rs`。自動クリック中の保存画像にラベルが写らないことを単体テストで確認）
+    （選択エリア内のカーソルに追従するため、自動クリック中のオレンジのラベルが保存画像に写り込まないようにする）
+-   **フォント**: 設定のフォント（`OverlayFontFamily`）→ システムのメッセージフォント（`SPI_GETNONCLIENTMETRICS`）→
+    Segoe UI → Yu Gothic UI → MS UI Gothic → 汎用サンセリフの順に使用。サイズは `OverlayFontSize`（既定12pt）
+-   **高DPI**: ウィンドウサイズ（230x90）・アイコン・ラベルの座標は96DPI基準で、ウィンドウをシステムDPI倍に拡大し、
+    描画は `GdipScaleWorldTransform` で同じ倍率に拡大する（4Kディスプレイでもラベルが小さくならない）
 
 【状態別表示仕様】
 -   **待機状態**: 
@@ -41,6 +50,9 @@ ClickCaptureアプリケーションのキャプチャモード中に表示さ
 -   **自動クリック状態**:
     - 進行状況ラベル「自動クリック中 ...(N/M)」
     - オレンジ背景 + 黒文字による高視認性表示
+-   **経過時間表示状態**（`AppState.capture_overlay_shows_elapsed`）:
+    - 経過時間ラベル「定期キャプチャ中 / 00:12:30 · 42枚」（自動クリックと同じオレンジラベル）
+    - メインダイアログの `TIMER_ID_CAPTURE_OVERLAY_ELAPSED` タイマーで1秒ごとに再描画
 
 【UI/UX設計思想】
 -   **非侵襲性**: 作業画面を遮らない最小限サイズ
@@ -62,6 +74,7 @@ ClickCaptureアプリケーションのキャプチャモード中に表示さ
 -   `screen_capture.rs`: キャプチャモード制御との連携
 -   `auto_click.rs`: 自動クリック進行状況の表示連携
 -   `ui/ui_utils.rs`: PNGリソース読み込み機能（load_png_from_resource）
+-   `error.rs`: `load_png_from_resource` のエラー型（`ResourceError`）
  */
 
 // GDI+関連のライブラリ（外部機能）をインポート
@@ -70,8 +83,10 @@ use windows::Win32::Graphics::GdiPlus::{
     GdipCreateFont, GdipCreateFontFamilyFromName, GdipCreateSolidFill, GdipCreateStringFormat,
     GdipDeleteBrush, GdipDeleteFont, GdipDeleteFontFamily, GdipDeleteStringFormat,
     GdipDisposeImage, GdipDrawImageRectI, GdipDrawString, GdipFillRectangleI,
-    GdipSetCompositingMode, GdipSetStringFormatAlign, GdipSetStringFormatLineAlign, GpBitmap,
-    GpFont, GpGraphics, GpSolidFill, GpStringFormat, RectF, Status, StringAlignmentCenter,
+    GdipGetGenericFontFamilySansSerif, GdipScaleWorldTransform, GdipSetCompositingMode,
+    GdipSetStringFormatAlign, GdipSetStringFormatLineAlign, GpBitmap, GpFont, GpFontFamily,
+    GpGraphics, GpSolidFill, GpStringFormat, MatrixOrderPrepend, RectF, Status,
+    StringAlignmentCenter, UnitPixel,
 };
 use windows::Win32::System::Com::IStream;
 use windows::Win32::System::LibraryLoader::{
@@ -96,17 +111,77 @@ use crate::app_state::*;
 // リソースID定数をインポート
 use crate::constants::*;
 
+// リソース読み込みエラー型
+use crate::error::ResourceError;
+
 // オーバーレイ共通機能モジュール
 use crate::overlay::*;
 
-// オーバーレイウィンドウサイズ定数
-// 幅230px: アイコン32px + テキスト領域198px（自動クリック進行表示用）
-// 高90px: アイコン32px + テキスト行高58px（マージン込み）
-const WIN_SIZE: (i32, i32) = (230, 90);
+// ログ出力（選択したフォントの記録）
+use crate::system_utils::{app_log, get_system_dpi};
+
+// 表示文字列テーブル（ラベル・ログの多言語対応）
+use crate::strings;
+
+/// キャプチャオーバーレイの表示方法
+///
+/// カーソルに追従するアイコンが気になる場合に、半透明または非表示にできます
+/// （`AppState.capturing_overlay_visibility`、設定はレジストリに保存）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapturingOverlayVisibility {
+    /// 不透明で表示する（デフォルト）
+    #[default]
+    Visible = 0,
+    /// 半透明で表示する
+    SemiTransparent = 1,
+    /// 表示しない（ウィンドウは残し、透明度0で描画）
+    Hidden = 2,
+}
+
+impl CapturingOverlayVisibility {
+    /// コンボボックス項目データ・レジストリ値から復元（不明な値は表示扱い）
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CapturingOverlayVisibility::SemiTransparent,
+            2 => CapturingOverlayVisibility::Hidden,
+            _ => CapturingOverlayVisibility::Visible,
+        }
+    }
+
+    /// コンボボックス表示用ラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            CapturingOverlayVisibility::Visible => strings::overlay_visibility_visible(),
+            CapturingOverlayVisibility::SemiTransparent => {
+                strings::overlay_visibility_semi_transparent()
+            }
+            CapturingOverlayVisibility::Hidden => strings::overlay_visibility_hidden(),
+        }
+    }
+
+    /// `UpdateLayeredWindow` に渡す `SourceConstantAlpha`
+    pub fn alpha(&self) -> u8 {
+        match self {
+            CapturingOverlayVisibility::Visible => 255,
+            CapturingOverlayVisibility::SemiTransparent => 128,
+            CapturingOverlayVisibility::Hidden => 0,
+        }
+    }
+}
+
+// キャプチャ中オーバーレイの配置・配色・表示アフィニティ（ライブラリ側で定義）
+use clickcapture::capturing_overlay_layout as layout;
+
+// オーバーレイウィンドウサイズ定数（96DPI基準、実際のウィンドウは `CapturingOverLay::scale` 倍）
+const WIN_SIZE: (i32, i32) = layout::WINDOW_SIZE;
+
+// ラベルフォントのフォールバック先（設定のフォント・システムのメッセージフォントが使用できない場合に順に試す）
+// 日本語版Windowsの旧既定フォント（MS UI Gothic）も候補に含め、英語環境・日本語環境のどちらでも表示できるようにする
+const FALLBACK_FONT_FAMILIES: [&str; 3] = ["Segoe UI", "Yu Gothic UI", "MS UI Gothic"];
 
 // アイコン描画サイズ定数（32x32ピクセル）
 // 高DPI環境での視認性とパフォーマンスの最適バランス
-const ICON_DRAW_SIZE: i32 = 32;
+const ICON_DRAW_SIZE: i32 = layout::ICON_SIZE;
 
 /// キャプチャモードオーバーレイ構造体
 /// 
@@ -116,7 +191,7 @@ const ICON_DRAW_SIZE: i32 = 32;
 /// 
 /// # 構造体フィールド詳細
 /// - `hwnd`: オーバーレイウィンドウハンドル（SafeHWNDでラップ）
-/// - `font`: テキスト描画用GDI+フォント（Yu Gothic UI 16pt）
+/// - `font`: テキスト描画用GDI+フォント（システムのメッセージフォント、`create_label_font`）
 /// - `transparent_brush`: 背景透明化用ブラシ（Alpha=0）
 /// - `string_format`: 文字列描画制御（中央揃え設定）
 /// - `back_ground_brush`: 文字描画用黒ブラシ（文字色）
@@ -137,6 +212,8 @@ pub struct CapturingOverLay {
     back_orange_brush: *mut GpSolidFill,
     wait_bitmap: *mut GpBitmap,
     processing_bitmap: *mut GpBitmap,
+    /// システムDPIの拡大率（96DPIで1.0）。ウィンドウサイズと描画の座標変換に使用
+    scale: f32,
 }
 
 /// キャプチャモードオーバーレイ構造体実装
@@ -150,7 +227,7 @@ impl CapturingOverLay {
     ///
     /// # 初期化処理内容
     /// 1. **透明ブラシ作成**: 背景クリア用（Alpha=0）
-    /// 2. **フォント作成**: Yu Gothic UI 16ptフォント
+    /// 2. **フォント作成**: システムのメッセージフォント（`create_label_font`、16pt相当をDPIで拡大）
     /// 3. **描画ブラシ作成**: 文字用黒ブラシ、ラベル背景用オレンジブラシ
     /// 4. **文字列フォーマット作成**: 中央揃え設定
     /// 5. **アイコンビットマップ読み込み**: 待機・処理中アイコンのPNG→GDI+変換
@@ -161,13 +238,18 @@ impl CapturingOverLay {
     /// この設計により、部分的な機能低下でもアプリケーションは動作継続可能。
     ///
     /// # フォント選択理由
-    /// Yu Gothic UI: Windows 10/11標準、日本語・英語混在テキストの高い可読性、
-    /// ClearType最適化による高DPI環境での鮮明表示を実現。
+    /// 特定のフォント（Yu Gothic UI等）を決め打ちすると、Windows Serverや機能を削った環境で
+    /// フォントファミリーの取得に失敗し、ラベルが表示されなくなるため、
+    /// システムのメッセージフォントを使用し、取得できない場合は順にフォールバックします。
+    ///
+    /// # 引数
+    /// * `font_family` - ラベルのフォント名（レジストリ `OverlayFontFamily`、`None` はシステムのメッセージフォント）
+    /// * `font_points` - ラベルの文字サイズ（ポイント、96DPI基準）
     ///
     /// # 戻り値
     /// 初期化されたCapturingOverLayインスタンス。一部リソース作成に失敗しても
     /// 有効なインスタンスを返し、利用可能な機能のみで動作します。
-    pub fn new() -> Self {
+    pub fn new(font_family: Option<&str>, font_points: u32) -> Self {
         // 構造体の初期状態（全ポインタをnullで初期化）
         let mut overlay = CapturingOverLay {
             hwnd: None,
@@ -178,6 +260,7 @@ impl CapturingOverLay {
             string_format: std::ptr::null_mut(),
             wait_bitmap: std::ptr::null_mut(),
             processing_bitmap: std::ptr::null_mut(),
+            scale: get_system_dpi() as f32 / 96.0,
         };
 
         // === GDI+リソースの段階的初期化 ===
@@ -195,54 +278,13 @@ impl CapturingOverLay {
             }
         }
 
-        // 2. フォント作成（Yu Gothic UI 16pt）
-        // UTF-16エンコード + Null終端でWindows API互換文字列作成
-        let font_family_name: Vec<u16> = "Yu Gothic UI"
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
-
-        unsafe {
-            // フォントファミリーオブジェクト作成
-            let mut font_family: *mut _ = std::ptr::null_mut();
-            let status = GdipCreateFontFamilyFromName(
-                PCWSTR(font_family_name.as_ptr()),
-                std::ptr::null_mut(), // システム標準フォントコレクション使用
-                &mut font_family,
-            );
-
-            if status != Status(0) {
-                eprintln!(
-                    "❌ GdipCreateFontFamilyFromName failed in CapturingOverLay::new() with status: {:?}",
-                    status
-                );
-            }
-
-            // フォントインスタンス作成（16pt、標準スタイル）
-            // 16pt: 高DPI環境での視認性とレイアウト最適化の調和点
-            let status = GdipCreateFont(
-                font_family,
-                16.0,                    // フォントサイズ16pt
-                Default::default(),      // FontStyleRegular（標準）
-                Default::default(),      // UnitPoint（ポイント単位）
-                &mut overlay.font,
-            );
-            if status != Status(0) {
-                eprintln!(
-                    "❌ GdipCreateFont failed in CapturingOverLay::new() with status: {:?}",
-                    status
-                );
-            }
-            
-            // フォントファミリーオブジェクトのクリーンアップ
-            // 作成したフォントファミリーはフォント作成後に即座に解放
-            GdipDeleteFontFamily(font_family);
-        }
+        // 2. フォント作成（設定のフォント → システムのメッセージフォント → フォールバック候補 → 汎用サンセリフ）
+        overlay.font = create_label_font(font_family, font_points, overlay.scale);
 
         // 3. 描画ブラシ作成
         unsafe {
             // ラベル背景用オレンジブラシ作成
-            let orange_color = Color { Argb: 0xFFDEB887 }; // Burlywood色（#DEB887）
+            let orange_color = Color { Argb: layout::LABEL_BACKGROUND_ARGB }; // Burlywood色（#DEB887）
             let status = GdipCreateSolidFill(orange_color.Argb, &mut overlay.back_orange_brush);
             if status != Status(0) {
                 eprintln!(
@@ -274,27 +316,37 @@ impl CapturingOverLay {
 
         // 5. アイコンビットマップリソース読み込み
         // 待機状態アイコン（マウスクリック待機中の表示用）
-        if let Ok(bitmap) =
-            load_png_from_resource(PCWSTR(IDP_CAPTURE_WAITING as usize as *const u16))
-        {
-            overlay.wait_bitmap = bitmap;
-        } else {
-            eprintln!("❌ Failed to load PNG resource: IDP_CAPTURE_WAITING");
+        match load_png_from_resource(PCWSTR(IDP_CAPTURE_WAITING as usize as *const u16)) {
+            Ok(bitmap) => overlay.wait_bitmap = bitmap,
+            Err(e) => eprintln!(
+                "❌ Failed to load PNG resource: IDP_CAPTURE_WAITING ({})",
+                e
+            ),
         }
 
         // 処理中状態アイコン（キャプチャ実行中の表示用）
-        if let Ok(bitmap) =
-            load_png_from_resource(PCWSTR(IDP_CAPTURE_PROCESSING as usize as *const u16))
-        {
-            overlay.processing_bitmap = bitmap;
-        } else {
-            eprintln!("❌ Failed to load PNG resource: IDP_CAPTURE_PROCESSING");
+        match load_png_from_resource(PCWSTR(IDP_CAPTURE_PROCESSING as usize as *const u16)) {
+            Ok(bitmap) => overlay.processing_bitmap = bitmap,
+            Err(e) => eprintln!(
+                "❌ Failed to load PNG resource: IDP_CAPTURE_PROCESSING ({})",
+                e
+            ),
         }
 
         // 初期化完了したオーバーレイインスタンスを返却
         // 一部リソース作成に失敗していても、利用可能な機能で動作継続
         overlay
     }
+
+    /// 96DPI基準の長さをシステムDPIに合わせたピクセル数に換算する
+    fn scaled(&self, length: i32) -> i32 {
+        layout::scaled(length, self.scale)
+    }
+
+    /// オーバーレイウィンドウの実際のサイズ（`WIN_SIZE` をシステムDPIで拡大）
+    fn window_size(&self) -> (i32, i32) {
+        (self.scaled(WIN_SIZE.0), self.scaled(WIN_SIZE.1))
+    }
 }
 
 /// CapturingOverLay用RAII自動リソース解放実装
@@ -350,11 +402,16 @@ impl Overlay for CapturingOverLay {
     fn get_description(&self) -> &str {
         "キャプチャモードオーバーレイ"
     }
+    /// 背景クリア用ブラシと待機中アイコンがあれば表示可能（ラベル用のフォント等がなくてもアイコンは描画できる）
+    fn is_usable(&self) -> bool {
+        !self.transparent_brush.is_null() && !self.wait_bitmap.is_null()
+    }
     fn get_window_proc(&self) -> OverlayWindowProc {
         OverlayWindowProc {
-            create: None,
+            create: Some(overlay_window_create),
             paint: Some(overlay_window_paint),
             destroy: None,
+            alpha: Some(overlay_window_alpha),
         }
     }
 
@@ -364,11 +421,12 @@ impl Overlay for CapturingOverLay {
 
     fn get_window_params(&self) -> OverlayWindowParams {
         // オーバーレイウィンドウを作成（WS_EX_TRANSPARENTを削除、マウスイベントを背後に通さないため）
+        let (width, height) = self.window_size();
         let mut params = OverlayWindowParams::default();
         params = OverlayWindowParams {
             dwex_style: WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_TRANSPARENT,
-            width: WIN_SIZE.0,
-            height: WIN_SIZE.1,
+            width,
+            height,
             ..params
         };
         params
@@ -379,20 +437,30 @@ impl Overlay for CapturingOverLay {
         unsafe {
             let app_state = AppState::get_app_state_mut();
 
-            let size = WIN_SIZE;
-            // let offset = size / 2;
-            let offset = ICON_DRAW_SIZE;
-            let screen_x = app_state.current_mouse_pos.x;
-            let screen_y = app_state.current_mouse_pos.y;
+            let offset = self.scaled(ICON_DRAW_SIZE);
+
+            // 経過時間表示（定期キャプチャ中）はマウスフックが動作していないため、
+            // マウスカーソルではなく選択エリアの左上に固定表示する
+            let anchor_area = app_state
+                .selected_area
+                .filter(|_| app_state.capture_overlay_shows_elapsed && !app_state.is_capture_mode());
+            let (screen_x, screen_y) = match anchor_area {
+                Some(area) => (area.left + offset, area.top + offset),
+                None => {
+                    let mouse_pos = app_state.current_mouse_pos();
+                    (mouse_pos.x, mouse_pos.y)
+                }
+            };
 
             if let Some(hwnd) = self.hwnd {
+                let rect = layout::window_rect((screen_x, screen_y), self.scale);
                 let _ = SetWindowPos(
                     *hwnd,
                     Some(HWND_TOPMOST),
-                    screen_x - offset,
-                    screen_y - offset,
-                    size.0,
-                    size.1,
+                    rect.left,
+                    rect.top,
+                    rect.width(),
+                    rect.height(),
                     SWP_NOACTIVATE,
                 );
             }
@@ -400,6 +468,33 @@ impl Overlay for CapturingOverLay {
     }
 }
 
+/// キャプチャオーバーレイウィンドウ作成時の処理
+///
+/// オーバーレイはマウスカーソル（＝選択エリアの内側）に追従するため、アイコンと進行状況ラベルを含む
+/// ウィンドウ全体（230x90）を表示アフィニティでキャプチャ対象外に設定します。
+/// キャプチャ直前の `hide_overlay` は、DWMの合成が非表示に追いつく前にBitBltした場合にラベルが写り込むため、
+/// Windows 10 2004 より前の環境（`WDA_EXCLUDEFROMCAPTURE` 非対応）向けの回避策としてのみ機能します。
+fn overlay_window_create(hwnd: HWND) {
+    unsafe {
+        if let Err(e) = SetWindowDisplayAffinity(hwnd, WINDOW_DISPLAY_AFFINITY(layout::DISPLAY_AFFINITY)) {
+            eprintln!(
+                "⚠️ SetWindowDisplayAffinity failed in CapturingOverLay: {}",
+                e
+            );
+        }
+    }
+}
+
+/// キャプチャオーバーレイ全体の透明度を返す（`UpdateLayeredWindow` の `SourceConstantAlpha`）
+///
+/// 表示方法の設定（表示 / 半透明 / 非表示）に従います。再描画のたびに参照するため、
+/// 設定の変更はキャプチャモードを開始し直さなくても次の描画から反映されます。
+fn overlay_window_alpha(_hwnd: HWND) -> u8 {
+    AppState::get_app_state_ref()
+        .capturing_overlay_visibility
+        .alpha()
+}
+
 /// キャプチャオーバーレイウィンドウの描画処理
 /// 
 /// キャプチャモード中のオーバーレイウィンドウに対するカスタム描画を実行します。
@@ -415,7 +510,7 @@ impl Overlay for CapturingOverLay {
 /// 2. **状態アイコン**: 
 ///    - 処理中：processing_bitmap（キャプチャ実行中）
 ///    - 待機中：wait_bitmap（ユーザー操作待ち）
-/// 3. **自動クリック状況**: 進行状況ラベル（有効時のみ）
+/// 3. **進行状況**: 経過時間ラベル（経過時間表示モード時）、または自動クリックの進行状況ラベル（実行中のみ）
 /// 
 /// # 描画技術詳細
 /// - **合成モード制御**: SourceCopy → SourceOver の切り替えで透明度管理
@@ -435,6 +530,9 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
         .expect("キャプチャーオーバーレイが存在しません。");
 
     unsafe {
+        // 以降の描画は96DPI基準の座標（WIN_SIZE・ICON_DRAW_SIZEなど）で行い、システムDPIに合わせて拡大する
+        GdipScaleWorldTransform(graphics, overlay.scale, overlay.scale, MatrixOrderPrepend);
+
         // === 1. 背景透明化処理 ===
         // LayeredWindowによる透明度制御とGDI+描画の協調動作
         // CompositingModeSourceCopy: 既存ピクセルを完全上書き（アルファ値無視）
@@ -459,7 +557,7 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
         let y = 0; // Y座標：上端に配置
 
         // アプリケーション状態に基づく条件分岐描画
-        if app_state.capture_overlay_is_processing {
+        if app_state.capture_overlay_is_processing() {
             // キャプチャ処理実行中：処理中アイコンを表示
             GdipDrawImageRectI(
                 graphics,
@@ -481,11 +579,20 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
             );
         };
 
-        // === 3. 自動クリック進行状況表示 ===  
-        // 自動クリック機能が動作中の場合のみ、進行状況ラベルを描画
-        if app_state.auto_clicker.is_running() {
+        // === 3. 進行状況表示 ===  
+        // 定期キャプチャなど無人実行中は経過時間とキャプチャ枚数を、
+        // 自動クリック機能が動作中の場合は進行状況ラベルを描画
+        if app_state.capture_overlay_shows_elapsed {
+            draw_elapsed_progress_label(graphics);
+        } else if app_state.auto_clicker.is_running() {
             draw_auto_click_processing_label(graphics);
         }
+
+        // === 4. デュアル選択表示 ===
+        // 2つ目の領域が選択済みの場合のみ、2領域を結合して保存することを示すバッジを描画
+        if app_state.selected_area_2.is_some() {
+            draw_dual_area_badge(graphics);
+        }
     }
 }
 
@@ -500,6 +607,7 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
 /// 
 /// # 表示内容
 /// - フォーマット：「自動クリック中 ...(現在回数/最大回数)」
+///   （最大実行時間を設定した場合は次の行に「残り mm:ss」、クリック位置を進める設定の場合は「→ (x, y)」）
 /// - 背景色：Burlywood (#DEB887) - 温かみのある通知色
 /// - 文字色：黒 (#000000) - 高コントラストで視認性確保
 /// - 配置：アイコン直下、中央揃え
@@ -515,8 +623,70 @@ fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
 /// - 文字：SourceOverモードでアンチエイリアス適用
 /// - 配置：StringFormat中央揃えで美しい視覚配置
 fn draw_auto_click_processing_label(graphics: *mut GpGraphics) {
+    let app_state = AppState::get_app_state_ref();
+
+    // 進行状況テキストの動的生成
+    // フォーマット例：「自動クリック中 ...(3/10)」（キャプチャのみモードは「自動キャプチャ中」、文言は表示言語に従う）
+    let label = strings::auto_click_progress_label(app_state.auto_clicker.get_mode());
+    let mut text = format!(
+        "{} ...({}/{})",
+        label,
+        app_state.auto_clicker.get_progress_count(),    // 現在の実行回数
+        app_state.auto_clicker.get_max_count(),         // 設定された最大回数
+    );
+
+    // セキュアデスクトップ・ロック画面・停止の確認・ユーザー操作のために一時停止している場合は、次の行に表示
+    if let Some(reason) = app_state.auto_clicker.pause_reason() {
+        text.push('\n');
+        text.push_str(strings::auto_click_paused_label(reason));
+    }
+
+    // 最大実行時間を設定した場合は、残り時間を次の行に表示（回数と先に達した方で停止）
+    if let Some(remaining) = app_state.auto_clicker.get_remaining_duration() {
+        text.push('\n');
+        text.push_str(&strings::auto_click_remaining_label(remaining));
+    }
+
+    // クリック位置を進める設定の場合は、直近のクリック位置を次の行に表示
+    if let Some(target) = app_state.auto_clicker.current_target() {
+        text.push('\n');
+        text.push_str(&strings::auto_click_target_label(target.x, target.y));
+    }
+
+    draw_progress_label(graphics, &text);
+}
+
+/// 経過時間表示モードの進行状況ラベル描画
+///
+/// 定期キャプチャなど長時間の無人実行中に、開始からの経過時間と
+/// 保存済みのキャプチャ枚数を「定期キャプチャ中 / 00:12:30 · 42枚」形式で表示します。
+/// 表示スタイルは自動クリックの進行状況ラベルと共通です。
+///
+/// # 引数
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+fn draw_elapsed_progress_label(graphics: *mut GpGraphics) {
+    let app_state = AppState::get_app_state_ref();
+    let scheduled_capturer = &app_state.scheduled_capturer;
+
+    let elapsed_secs = scheduled_capturer.get_elapsed().as_secs();
+    let text = strings::scheduled_capture_progress_label(
+        elapsed_secs / 3600,
+        elapsed_secs / 60 % 60,
+        elapsed_secs % 60,
+        scheduled_capturer.get_captured_count(),
+    );
+
+    draw_progress_label(graphics, &text);
+}
+
+/// アイコン直下のオレンジ背景ラベル描画（進行状況表示の共通処理）
+///
+/// # 引数
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+/// * `text` - ラベルに表示するテキスト（改行可）
+fn draw_progress_label(graphics: *mut GpGraphics, text: &str) {
     // ラベルの左端オフセット（視覚的調整用）
-    const LABEL_OFFSET_X: i32 = 20;
+    const LABEL_OFFSET_X: i32 = layout::LABEL_OFFSET_X;
 
     // AppStateと描画対象オーバーレイの取得
     let app_state = AppState::get_app_state_ref();
@@ -525,14 +695,6 @@ fn draw_auto_click_processing_label(graphics: *mut GpGraphics) {
         .as_ref()
         .expect("キャプチャーオーバーレイが存在しません。");
 
-    // 進行状況テキストの動的生成
-    // フォーマット例：「自動クリック中 ...(3/10)」
-    let text = format!(
-        "自動クリック中 ...({}/{})",
-        app_state.auto_clicker.get_progress_count(),    // 現在の実行回数
-        app_state.auto_clicker.get_max_count(),         // 設定された最大回数
-    );
-    
     // ラベル描画領域の計算
     let text_rect_y = ICON_DRAW_SIZE + 1;          // Y座標：アイコン直下+1px
     let text_rect_height = WIN_SIZE.1 - text_rect_y; // 高さ：残り全領域使用
@@ -576,6 +738,188 @@ fn draw_auto_click_processing_label(graphics: *mut GpGraphics) {
     }
 }
 
+/// デュアル選択（2領域キャプチャ）中を示すバッジ描画
+///
+/// 2つ目の領域（`selected_area_2`）が選択済みの場合に、アイコン右側へ
+/// オレンジ背景に黒文字で「2領域」と表示し、1回のキャプチャで2つの領域が
+/// 結合保存されることをユーザーに知らせます。
+///
+/// # 引数
+/// * `graphics` - GDI+グラフィックスコンテキストへのポインタ
+fn draw_dual_area_badge(graphics: *mut GpGraphics) {
+    // バッジの配置（アイコン右側、アイコンと同じ高さ）
+    const BADGE_X: i32 = ICON_DRAW_SIZE + 2;
+    const BADGE_WIDTH: i32 = 72;
+
+    let app_state = AppState::get_app_state_ref();
+    let overlay = app_state
+        .capturing_overlay
+        .as_ref()
+        .expect("キャプチャーオーバーレイが存在しません。");
+
+    unsafe {
+        // 背景（不透明なオレンジ矩形）
+        GdipSetCompositingMode(graphics, CompositingModeSourceCopy);
+        GdipFillRectangleI(
+            graphics,
+            overlay.back_orange_brush as *mut _,
+            BADGE_X,
+            0,
+            BADGE_WIDTH,
+            ICON_DRAW_SIZE,
+        );
+        GdipSetCompositingMode(graphics, CompositingModeSourceOver);
+
+        // 黒色のテキストを中央揃えで描画
+        GdipSetStringFormatAlign(overlay.string_format, StringAlignmentCenter);
+        GdipSetStringFormatLineAlign(overlay.string_format, StringAlignmentCenter);
+
+        let text_utf16: Vec<u16> = strings::dual_area_badge().encode_utf16().collect();
+        let layout_rect = RectF {
+            X: BADGE_X as f32,
+            Y: 0.0,
+            Width: BADGE_WIDTH as f32,
+            Height: ICON_DRAW_SIZE as f32,
+        };
+
+        GdipDrawString(
+            graphics,
+            PCWSTR(text_utf16.as_ptr()),
+            text_utf16.len() as i32,
+            overlay.font,
+            &layout_rect,
+            overlay.string_format,
+            overlay.back_ground_brush as *mut _,
+        );
+    }
+}
+
+/// ラベル描画用のフォントを作成する
+///
+/// 次の順にフォントファミリーを試し、最初に作成できたフォントを返します。
+/// 1. 設定のフォント（レジストリ `OverlayFontFamily`）
+/// 2. システムのメッセージフォント（`SystemParametersInfoW(SPI_GETNONCLIENTMETRICS)` の `lfMessageFont`）
+/// 3. `FALLBACK_FONT_FAMILIES`（Segoe UI → Yu Gothic UI → MS UI Gothic）
+/// 4. GDI+の汎用サンセリフ（`GdipGetGenericFontFamilySansSerif`）
+///
+/// サイズは `font_points` を96DPI基準のピクセル数に換算した値です（12pt = 16px）。
+/// 描画時のワールド変換でシステムDPIに合わせて拡大するため、ここではDPIを掛けません。
+/// 実際に選択したフォントと画面上のサイズ（150%表示で12pt = 24px）はログに出力します。
+///
+/// # 引数
+/// * `preferred_family` - 設定のフォント名（`None` の場合は省略）
+/// * `font_points` - 文字サイズ（ポイント、96DPI基準）
+/// * `scale` - システムDPIの拡大率（ログ出力用）
+///
+/// # 戻り値
+/// 作成したフォント。全て失敗した場合はnull（描画時にラベルは表示されません）。
+fn create_label_font(preferred_family: Option<&str>, font_points: u32, scale: f32) -> *mut GpFont {
+    let font_size = font_points as f32 * 96.0 / 72.0;
+
+    // 1. システムのメッセージフォント
+    let mut metrics = NONCLIENTMETRICSW {
+        cbSize: std::mem::size_of::<NONCLIENTMETRICSW>() as u32,
+        ..Default::default()
+    };
+    let system_font_name = unsafe {
+        SystemParametersInfoW(
+            SPI_GETNONCLIENTMETRICS,
+            metrics.cbSize,
+            Some(&mut metrics as *mut _ as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .ok()
+    .map(|()| {
+        let face_name = &metrics.lfMessageFont.lfFaceName;
+        let len = face_name
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(face_name.len());
+        String::from_utf16_lossy(&face_name[..len])
+    })
+    .filter(|name| !name.is_empty());
+
+    // 設定のフォント → システムフォント → フォールバックの順に名前で作成
+    let candidates = preferred_family
+        .into_iter()
+        .chain(system_font_name.as_deref())
+        .chain(FALLBACK_FONT_FAMILIES);
+    for family_name in candidates {
+        let family_name_wide: Vec<u16> = family_name
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+        unsafe {
+            let mut font_family: *mut GpFontFamily = std::ptr::null_mut();
+            let status = GdipCreateFontFamilyFromName(
+                PCWSTR(family_name_wide.as_ptr()),
+                std::ptr::null_mut(), // システム標準フォントコレクション使用
+                &mut font_family,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "⚠️ フォントファミリー「{}」を取得できませんでした (status: {:?})",
+                    family_name, status
+                );
+                continue;
+            }
+
+            let font = create_font_from_family(font_family, font_size);
+            // 作成したフォントファミリーはフォント作成後に即座に解放
+            GdipDeleteFontFamily(font_family);
+            if !font.is_null() {
+                app_log(&strings::overlay_font_selected(
+                    family_name,
+                    font_size * scale,
+                ));
+                return font;
+            }
+        }
+    }
+
+    // 4. 汎用サンセリフ（GDI+が管理するファミリーのため解放しない）
+    unsafe {
+        let mut font_family: *mut GpFontFamily = std::ptr::null_mut();
+        let status = GdipGetGenericFontFamilySansSerif(&mut font_family);
+        if status == Status(0) {
+            let font = create_font_from_family(font_family, font_size);
+            if !font.is_null() {
+                app_log(&strings::overlay_font_selected(
+                    strings::generic_sans_serif(),
+                    font_size * scale,
+                ));
+                return font;
+            }
+        }
+    }
+
+    app_log(strings::overlay_font_failed());
+    std::ptr::null_mut()
+}
+
+/// フォントファミリーからラベル用フォント（標準スタイル、ピクセル単位）を作成する
+///
+/// # 戻り値
+/// 作成したフォント。失敗した場合はnull。
+unsafe fn create_font_from_family(font_family: *mut GpFontFamily, font_size: f32) -> *mut GpFont {
+    let mut font: *mut GpFont = std::ptr::null_mut();
+    let status = unsafe {
+        GdipCreateFont(
+            font_family,
+            font_size,
+            Default::default(), // FontStyleRegular（標準）
+            UnitPixel,          // 96DPI基準のピクセル数（描画時のワールド変換でDPI拡大）
+            &mut font,
+        )
+    };
+    if status != Status(0) {
+        eprintln!("❌ GdipCreateFont failed with status: {:?}", status);
+        return std::ptr::null_mut();
+    }
+    font
+}
+
 /// 埋め込みリソースからPNG画像を読み込み、GDI+ビットマップを作成する
 ///
 /// 実行ファイルに`RT_RCDATA`として埋め込まれたPNGリソースを、
@@ -586,7 +930,7 @@ fn draw_auto_click_processing_label(graphics: *mut GpGraphics) {
 ///
 /// # 戻り値
 /// * `Ok(*mut GpBitmap)` - 成功した場合、GDI+ビットマップへのポインタ。
-/// * `Err(String)` - 失敗した場合、エラーメッセージ。
+/// * `Err(ResourceError)` - 失敗した場合、失敗したAPIに対応するエラー。
 ///
 /// # 処理フロー
 /// 1.  `FindResourceW`: 実行ファイル内のリソースを検索。
@@ -602,30 +946,30 @@ fn draw_auto_click_processing_label(graphics: *mut GpGraphics) {
 /// この関数は`unsafe`ブロックを含みますが、Win32 API呼び出しは適切に処理され、
 /// メモリ管理は`IStream`のRAIIパターンによって自動的に行われるため安全です。
 /// 呼び出し元は、返された`GpBitmap`ポインタを`GdipDisposeImage`で解放する責任があります。
-pub fn load_png_from_resource(resource_id: PCWSTR) -> Result<*mut GpBitmap, String> {
+pub fn load_png_from_resource(resource_id: PCWSTR) -> Result<*mut GpBitmap, ResourceError> {
     unsafe {
-        let hinstance = GetModuleHandleW(None).map_err(|e| e.to_string())?;
+        let hinstance = GetModuleHandleW(None).map_err(ResourceError::ModuleHandle)?;
 
         // 1. 実行ファイルからリソースを検索
         let resource_handle = FindResourceW(Some(hinstance), resource_id, RT_RCDATA);
         if resource_handle.0 == std::ptr::null_mut() {
-            return Err("リソースの検索に失敗しました (FindResourceW)".to_string());
+            return Err(ResourceError::NotFound);
         }
 
         // 2. リソースをメモリにロード
-        let loaded_resource = LoadResource(Some(hinstance), resource_handle)
-            .map_err(|e| format!("リソースのロードに失敗しました (LoadResource): {}", e))?;
+        let loaded_resource =
+            LoadResource(Some(hinstance), resource_handle).map_err(ResourceError::Load)?;
 
         // 3. リソースデータへのポインタを取得
         let resource_ptr = LockResource(loaded_resource);
         if resource_ptr.is_null() {
-            return Err("リソースポインタの取得に失敗しました (LockResource)".to_string());
+            return Err(ResourceError::Lock);
         }
 
         // 4. リソースデータのサイズを取得
         let resource_size = SizeofResource(Some(hinstance), resource_handle);
         if resource_size == 0 {
-            return Err("リソースサイズが0です (SizeofResource)".to_string());
+            return Err(ResourceError::Empty);
         }
 
         // 5. ポインタとサイズからRustのバイトスライスを作成
@@ -640,7 +984,7 @@ pub fn load_png_from_resource(resource_id: PCWSTR) -> Result<*mut GpBitmap, Stri
         let stream = match stream {
             Some(s) => s,
             None => {
-                return Err("メモリストリームの作成に失敗しました (SHCreateMemStream)".to_string());
+                return Err(ResourceError::StreamCreation);
             }
         };
 
@@ -649,15 +993,12 @@ pub fn load_png_from_resource(resource_id: PCWSTR) -> Result<*mut GpBitmap, Stri
         let status = GdipCreateBitmapFromStream(&stream, &mut bitmap);
 
         if status != Status(0) {
-            return Err(format!(
-                "ストリームからのビットマップ作成に失敗しました (GdipCreateBitmapFromStream): {:?}",
-                status
-            ));
+            return Err(ResourceError::BitmapCreation(status));
         }
 
         // ポインタがnullでないことを確認
         if bitmap.is_null() {
-            return Err("ビットマップは正常に作成されましたが、ポインタがnullです".to_string());
+            return Err(ResourceError::NullBitmap);
         }
 
         Ok(bitmap)
@@ -0,0 +1,467 @@
+/*
+============================================================================
+保存完了トーストオーバーレイモジュール (saved_toast_overlay.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャ画像の保存に成功した直後、キャプチャ領域の右下に「保存しました 0042.jpg」
+という小さな通知（トースト）を約1.5秒間表示し、フェードアウトさせるオーバーレイ。
+操作を妨げないよう、マウス入力・アクティブ化を受け付けない非インタラクティブな
+ウィンドウとして作成されます。
+
+【主要機能】
+1.  **トースト表示**: `show_saved_toast`
+    -   保存したファイル名を設定し、表示開始時刻を記録してオーバーレイを表示
+    -   メインダイアログに `TIMER_ID_SAVED_TOAST` のタイマーを設定
+2.  **フェードアウト制御**: `handle_saved_toast_timer`
+    -   メインダイアログの `WM_TIMER` から呼び出され、経過時間に応じて透明度を更新
+    -   表示時間終了でオーバーレイを非表示にし、タイマーを停止
+3.  **キャプチャからの除外**:
+    -   `SetWindowDisplayAffinity(WDA_EXCLUDEFROMCAPTURE)` で画面キャプチャの対象外に設定
+    -   表示位置は可能な限りキャプチャ領域（アンカー矩形）の外側（下側、入らなければ上側）を選択
+
+【技術仕様】
+-   **オーバーレイサイズ**: 240x48ピクセル
+-   **フェード方式**: `UpdateLayeredWindow` の `SourceConstantAlpha` をアニメーション
+    （`OverlayWindowProc.alpha` 経由で `paint_by_update_layered_window` に渡す）
+-   **タイマー駆動**: フック処理内でのスリープは行わず、ダイアログスレッドの `WM_TIMER` で駆動
+-   **フォント**: Yu Gothic UI 12pt
+
+【AI解析用：依存関係】
+-   `overlay.rs`: Overlayトレイトと `OverlayWindowProc.alpha`
+-   `app_state.rs`: `saved_toast_overlay` インスタンスの保持
+-   `constants.rs`: `TIMER_ID_SAVED_TOAST` タイマーID
+-   `screen_capture.rs`, `window_capture.rs`: 保存成功時に `show_saved_toast` を呼び出し
+-   `ui/dialog_handler.rs`: `WM_TIMER` 受信時に `handle_saved_toast_timer` を呼び出し
+ */
+
+// GDI+関連のライブラリ（外部機能）をインポート
+use windows::Win32::Graphics::GdiPlus::{
+    Color, CompositingModeSourceCopy, CompositingModeSourceOver, GdipCreateFont,
+    GdipCreateFontFamilyFromName, GdipCreateSolidFill, GdipCreateStringFormat, GdipDeleteBrush,
+    GdipDeleteFont, GdipDeleteFontFamily, GdipDeleteStringFormat, GdipDrawString,
+    GdipFillRectangleI, GdipSetCompositingMode, GdipSetStringFormatAlign,
+    GdipSetStringFormatLineAlign, GpFont, GpGraphics, GpSolidFill, GpStringFormat, RectF, Status,
+    StringAlignmentCenter,
+};
+// 必要なライブラリをインポート
+use windows::{
+    Win32::{
+        Foundation::{HWND, RECT},                // 基本的なデータ型
+        System::SystemInformation::GetTickCount, // 経過時間計測
+        UI::WindowsAndMessaging::*,              // ウィンドウとメッセージ処理
+    },
+    core::PCWSTR, // Windows API用の文字列操作
+};
+
+// アプリケーション状態管理構造体
+use crate::app_state::*;
+
+// タイマーID定数をインポート
+use crate::constants::*;
+
+// オーバーレイ共通機能モジュール
+use crate::overlay::*;
+
+// 表示文字列テーブル（トーストの多言語対応）
+use crate::strings;
+
+// オーバーレイウィンドウサイズ定数（幅240px × 高48px）
+const WIN_SIZE: (i32, i32) = (240, 48);
+
+// キャプチャ領域とトーストの間隔（ピクセル）
+const TOAST_MARGIN: i32 = 8;
+
+// 不透明のまま表示する時間（ミリ秒）
+const TOAST_HOLD_MS: u32 = 1000;
+
+// 表示開始からフェードアウト完了までの時間（ミリ秒）
+const TOAST_TOTAL_MS: u32 = 1500;
+
+// フェードアウト用タイマーの間隔（ミリ秒）
+const TOAST_TIMER_INTERVAL_MS: u32 = 30;
+
+/// 保存完了トーストオーバーレイ構造体
+///
+/// # 構造体フィールド詳細
+/// - `hwnd`: オーバーレイウィンドウハンドル（SafeHWNDでラップ）
+/// - `font`: テキスト描画用GDI+フォント（Yu Gothic UI 12pt）
+/// - `transparent_brush`: 背景透明化用ブラシ（Alpha=0）
+/// - `string_format`: 文字列描画制御（中央揃え設定）
+/// - `back_ground_brush`: トースト背景用の濃いグレーブラシ
+/// - `text_brush`: 文字描画用白ブラシ
+/// - `message`: 表示するメッセージ（例：「保存しました 0042.jpg」）
+/// - `shown_tick`: 表示開始時刻（`GetTickCount()` の値）
+/// - `alpha`: 現在のウィンドウ全体の透明度（`SourceConstantAlpha`）
+/// - `anchor`: 配置の基準となるキャプチャ領域（スクリーン座標）
+#[derive(Debug)]
+pub struct SavedToastOverlay {
+    hwnd: Option<SafeHWND>,
+    font: *mut GpFont,
+    transparent_brush: *mut GpSolidFill,
+    string_format: *mut GpStringFormat,
+    back_ground_brush: *mut GpSolidFill,
+    text_brush: *mut GpSolidFill,
+    message: String,
+    shown_tick: u32,
+    alpha: u8,
+    anchor: RECT,
+}
+
+/// 保存完了トーストオーバーレイ構造体実装
+impl SavedToastOverlay {
+    /// 新しい保存完了トーストオーバーレイインスタンスを作成する
+    ///
+    /// `CapturingOverLay::new()` と同様に、GDI+リソースの作成失敗は個別にログ出力し、
+    /// 失敗したリソースはnullポインタのまま残して動作を継続します。
+    pub fn new() -> Self {
+        // 構造体の初期状態（全ポインタをnullで初期化）
+        let mut overlay = SavedToastOverlay {
+            hwnd: None,
+            font: std::ptr::null_mut(),
+            transparent_brush: std::ptr::null_mut(),
+            string_format: std::ptr::null_mut(),
+            back_ground_brush: std::ptr::null_mut(),
+            text_brush: std::ptr::null_mut(),
+            message: String::new(),
+            shown_tick: 0,
+            alpha: 255,
+            anchor: RECT::default(),
+        };
+
+        // 1. ブラシ作成（透明・背景・文字）
+        unsafe {
+            let transparent_color = Color { Argb: 0x00000000 }; // Alpha=0で完全透明
+            let status =
+                GdipCreateSolidFill(transparent_color.Argb, &mut overlay.transparent_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for transparent_brush failed in SavedToastOverlay::new() with status {:?}",
+                    status
+                );
+            }
+
+            let back_color = Color { Argb: 0xE6303030 }; // やや透ける濃いグレー
+            let status = GdipCreateSolidFill(back_color.Argb, &mut overlay.back_ground_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for background failed in SavedToastOverlay::new() with status {:?}",
+                    status
+                );
+            }
+
+            let text_color = Color { Argb: 0xFFFFFFFF }; // 不透明な白
+            let status = GdipCreateSolidFill(text_color.Argb, &mut overlay.text_brush);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateSolidFill for text failed in SavedToastOverlay::new() with status {:?}",
+                    status
+                );
+            }
+        }
+
+        // 2. フォント作成（Yu Gothic UI 12pt）
+        let font_family_name: Vec<u16> = "Yu Gothic UI"
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+
+        unsafe {
+            let mut font_family: *mut _ = std::ptr::null_mut();
+            let status = GdipCreateFontFamilyFromName(
+                PCWSTR(font_family_name.as_ptr()),
+                std::ptr::null_mut(), // システム標準フォントコレクション使用
+                &mut font_family,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateFontFamilyFromName failed in SavedToastOverlay::new() with status: {:?}",
+                    status
+                );
+            }
+
+            let status = GdipCreateFont(
+                font_family,
+                12.0,               // フォントサイズ12pt
+                Default::default(), // FontStyleRegular（標準）
+                Default::default(), // UnitPoint（ポイント単位）
+                &mut overlay.font,
+            );
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateFont failed in SavedToastOverlay::new() with status: {:?}",
+                    status
+                );
+            }
+
+            GdipDeleteFontFamily(font_family);
+        }
+
+        // 3. 文字列描画フォーマット作成（中央揃え）
+        unsafe {
+            let status = GdipCreateStringFormat(0, 0, &mut overlay.string_format);
+            if status != Status(0) {
+                eprintln!(
+                    "❌ GdipCreateStringFormat failed in SavedToastOverlay::new() with status: {:?}",
+                    status
+                );
+            } else {
+                GdipSetStringFormatAlign(overlay.string_format, StringAlignmentCenter);
+                GdipSetStringFormatLineAlign(overlay.string_format, StringAlignmentCenter);
+            }
+        }
+
+        overlay
+    }
+}
+
+/// SavedToastOverlay用RAII自動リソース解放実装
+impl Drop for SavedToastOverlay {
+    fn drop(&mut self) {
+        // 1. オーバーレイウィンドウの破棄
+        self.destroy_overlay();
+
+        // 2. GDI+リソースの解放
+        unsafe {
+            GdipDeleteBrush(self.transparent_brush as *mut _);
+            GdipDeleteBrush(self.back_ground_brush as *mut _);
+            GdipDeleteBrush(self.text_brush as *mut _);
+            GdipDeleteFont(self.font);
+            GdipDeleteStringFormat(self.string_format);
+        }
+    }
+}
+
+/// Overlayトレイト実装
+impl Overlay for SavedToastOverlay {
+    fn set_hwnd(&mut self, hwnd: Option<SafeHWND>) {
+        self.hwnd = hwnd;
+    }
+    fn get_hwnd(&self) -> Option<SafeHWND> {
+        self.hwnd
+    }
+    fn get_overlay_name(&self) -> &str {
+        "SavedToast"
+    }
+    fn get_description(&self) -> &str {
+        "保存完了トーストオーバーレイ"
+    }
+    /// 文字を描画するフォント・ブラシ・書式が揃っている場合のみ表示可能（背景だけのトーストは出さない）
+    fn is_usable(&self) -> bool {
+        !self.font.is_null()
+            && !self.back_ground_brush.is_null()
+            && !self.text_brush.is_null()
+            && !self.string_format.is_null()
+    }
+    fn get_window_proc(&self) -> OverlayWindowProc {
+        OverlayWindowProc {
+            create: Some(overlay_window_create),
+            paint: Some(overlay_window_paint),
+            destroy: None,
+            alpha: Some(overlay_window_alpha),
+        }
+    }
+
+    fn get_class_params(&self) -> OverlayWindowClassParams {
+        OverlayWindowClassParams::default()
+    }
+
+    fn get_window_params(&self) -> OverlayWindowParams {
+        // クリックを背後に通し（WS_EX_TRANSPARENT）、表示時にフォーカスを奪わない（WS_EX_NOACTIVATE）
+        let params = OverlayWindowParams::default();
+        OverlayWindowParams {
+            dwex_style: WS_EX_LAYERED
+                | WS_EX_TOPMOST
+                | WS_EX_TOOLWINDOW
+                | WS_EX_TRANSPARENT
+                | WS_EX_NOACTIVATE,
+            width: WIN_SIZE.0,
+            height: WIN_SIZE.1,
+            ..params
+        }
+    }
+
+    // オーバーレイウィンドウの位置設定
+    //
+    // アンカー（キャプチャ領域）の右下に揃えて、領域の外側（下、入らなければ上）に配置する。
+    // どちらにも入らない場合（全画面選択など）は領域の内側右下に配置し、
+    // 表示アフィニティ（WDA_EXCLUDEFROMCAPTURE）によってキャプチャから除外する。
+    fn set_window_pos(&self) {
+        let app_state = AppState::get_app_state_ref();
+        let area = self.anchor;
+
+        let x = (area.right - WIN_SIZE.0).max(0);
+        let y = if area.bottom + TOAST_MARGIN + WIN_SIZE.1 <= app_state.screen_height {
+            area.bottom + TOAST_MARGIN
+        } else if area.top - TOAST_MARGIN - WIN_SIZE.1 >= 0 {
+            area.top - TOAST_MARGIN - WIN_SIZE.1
+        } else {
+            (area.bottom - TOAST_MARGIN - WIN_SIZE.1).max(0)
+        };
+
+        if let Some(hwnd) = self.hwnd {
+            unsafe {
+                let _ = SetWindowPos(
+                    *hwnd,
+                    Some(HWND_TOPMOST),
+                    x,
+                    y,
+                    WIN_SIZE.0,
+                    WIN_SIZE.1,
+                    SWP_NOACTIVATE,
+                );
+            }
+        }
+    }
+}
+
+/// トーストウィンドウ作成時の処理
+///
+/// 画面キャプチャ（BitBlt等）にトースト自身が写り込まないよう、表示アフィニティを設定します。
+/// Windows 10 2004 より前の環境では失敗するため、その場合は配置位置による回避のみとなります。
+fn overlay_window_create(hwnd: HWND) {
+    unsafe {
+        if let Err(e) = SetWindowDisplayAffinity(hwnd, WDA_EXCLUDEFROMCAPTURE) {
+            eprintln!(
+                "⚠️ SetWindowDisplayAffinity failed in SavedToastOverlay: {}",
+                e
+            );
+        }
+    }
+}
+
+/// トーストウィンドウ全体の透明度を返す（`UpdateLayeredWindow` の `SourceConstantAlpha`）
+fn overlay_window_alpha(_hwnd: HWND) -> u8 {
+    AppState::get_app_state_ref()
+        .saved_toast_overlay
+        .as_ref()
+        .map_or(255, |overlay| overlay.alpha)
+}
+
+/// トーストウィンドウの描画処理
+///
+/// 背景を透明クリアした後、濃いグレーの矩形を塗り、中央にメッセージを白文字で描画します。
+fn overlay_window_paint(_hwnd: HWND, graphics: *mut GpGraphics) {
+    let app_state = AppState::get_app_state_ref();
+    let overlay = app_state
+        .saved_toast_overlay
+        .as_ref()
+        .expect("保存完了トーストオーバーレイが存在しません。");
+
+    unsafe {
+        // 背景透明化（前回描画の痕跡を除去）
+        GdipSetCompositingMode(graphics, CompositingModeSourceCopy);
+        GdipFillRectangleI(
+            graphics,
+            overlay.transparent_brush as *mut _,
+            0,
+            0,
+            WIN_SIZE.0,
+            WIN_SIZE.1,
+        );
+
+        // トースト背景（アルファ付きの色をそのまま書き込む）
+        GdipFillRectangleI(
+            graphics,
+            overlay.back_ground_brush as *mut _,
+            0,
+            0,
+            WIN_SIZE.0,
+            WIN_SIZE.1,
+        );
+        GdipSetCompositingMode(graphics, CompositingModeSourceOver);
+
+        // メッセージ描画
+        let text_utf16: Vec<u16> = overlay.message.encode_utf16().collect();
+        let layout_rect = RectF {
+            X: 0.0,
+            Y: 0.0,
+            Width: WIN_SIZE.0 as f32,
+            Height: WIN_SIZE.1 as f32,
+        };
+
+        GdipDrawString(
+            graphics,
+            PCWSTR(text_utf16.as_ptr()),
+            text_utf16.len() as i32,
+            overlay.font,
+            &layout_rect,
+            overlay.string_format,
+            overlay.text_brush as *mut _,
+        );
+    }
+}
+
+/// 保存完了トーストを表示する
+///
+/// メッセージと表示開始時刻を設定してオーバーレイを不透明で表示し、
+/// メインダイアログにフェードアウト用のタイマーを設定します。
+/// 表示中に再度呼び出された場合は、メッセージを差し替えて表示時間を延長します。
+///
+/// # 引数
+/// * `file_name` - 保存したファイル名（例：`0042.jpg`）
+/// * `anchor` - キャプチャした領域（スクリーン座標）。トーストはこの右下に配置される
+pub fn show_saved_toast(file_name: &str, anchor: RECT) {
+    let app_state = AppState::get_app_state_mut();
+    let Some(dialog_hwnd) = app_state.dialog_hwnd else {
+        return;
+    };
+
+    if let Some(overlay) = app_state.saved_toast_overlay.as_mut() {
+        overlay.message = strings::saved_toast(file_name);
+        overlay.shown_tick = unsafe { GetTickCount() };
+        overlay.alpha = 255;
+        overlay.anchor = anchor;
+
+        if let Err(e) = overlay.show_overlay() {
+            eprintln!("❌ 保存完了トーストの表示に失敗: {}", e);
+            return;
+        }
+
+        // フックプロシージャ内で待機せず、ダイアログスレッドのWM_TIMERでフェードを駆動する
+        unsafe {
+            SetTimer(
+                Some(*dialog_hwnd),
+                TIMER_ID_SAVED_TOAST,
+                TOAST_TIMER_INTERVAL_MS,
+                None,
+            );
+        }
+    }
+}
+
+/// 保存完了トーストのタイマー処理（メインダイアログの `WM_TIMER` から呼び出し）
+///
+/// 表示開始からの経過時間に応じて透明度を更新し、表示時間を過ぎたら
+/// オーバーレイを非表示にしてタイマーを停止します。
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル（タイマーの所有者）
+pub fn handle_saved_toast_timer(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+    let Some(overlay) = app_state.saved_toast_overlay.as_mut() else {
+        unsafe {
+            let _ = KillTimer(Some(hwnd), TIMER_ID_SAVED_TOAST);
+        }
+        return;
+    };
+
+    let elapsed_ms = unsafe { GetTickCount() }.wrapping_sub(overlay.shown_tick);
+
+    if elapsed_ms >= TOAST_TOTAL_MS {
+        // 表示終了：非表示にしてタイマー停止
+        overlay.hide_overlay();
+        unsafe {
+            let _ = KillTimer(Some(hwnd), TIMER_ID_SAVED_TOAST);
+        }
+        return;
+    }
+
+    if elapsed_ms > TOAST_HOLD_MS {
+        // フェードアウト中：残り時間に比例して透明度を下げる
+        let remaining_ms = TOAST_TOTAL_MS - elapsed_ms;
+        let fade_ms = TOAST_TOTAL_MS - TOAST_HOLD_MS;
+        overlay.alpha = (255 * remaining_ms / fade_ms) as u8;
+        overlay.refresh_overlay();
+    }
+}
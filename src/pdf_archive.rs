@@ -0,0 +1,448 @@
+/*
+============================================================================
+PDF/A出力モジュール (pdf_archive.rs)
+============================================================================
+
+【ファイル概要】
+`PdfBuilder` のアーカイブ用モード（PDF/A-1b）で必要になる部品を提供するライブラリモジュールです。
+長期保存用のPDF/A-1bは、通常のPDFに加えて色の基準（出力インテントとICCプロファイル）、
+XMPメタデータ、文書IDを必要とし、ファイルの書式（ヘッダー・オブジェクト・ストリームの改行）にも制約があります。
+
+【主要機能】
+1.  **`srgb_icc_profile`**: 出力インテントに埋め込むsRGBのICCプロファイル（v2.1、マトリックス/TRC形式）を生成
+2.  **`xmp_metadata`**: PDF/A-1bの識別情報（`pdfaid:part` / `pdfaid:conformance`）を含むXMPメタデータを生成
+3.  **`ArchiveTimestamp`**: 文書情報（`D:YYYYMMDDHHmmSSZ`）とXMP（ISO 8601）で一致させる作成日時（UTC）
+4.  **`document_id`**: トレーラーの `/ID` に設定する16バイトの文書ID
+5.  **`write_archive_document`**: PDF/A-1の書式で `lopdf::Document` を書き出す
+
+【技術仕様】
+-   **ICCプロファイル**: 外部ファイルを同梱せず、sRGB（IEC 61966-2.1）の原色（D50に順応済み）とトーンカーブ（1024点）から生成。
+    PDF/A-1はICCプロファイルのバージョン2を要求するため、v2.1のヘッダーで作成する
+-   **書き出し**: `lopdf` の `save_to` は `obj` / `endobj` / `endstream` の前後に改行を入れず、
+    ヘッダー直後のバイナリコメントも書かないため（いずれもPDF/A-1の要件）、オブジェクトを直接書き出す。
+    相互参照表・トレーラーも同じ処理で作成する（オブジェクトストリーム・相互参照ストリームは使用しない）
+-   **日時**: 文書情報とXMPの作成日時・更新日時が一致している必要があるため、同じ `ArchiveTimestamp` から作成する
+
+【AI解析用：依存関係】
+-   `pdf_builder.rs`: `PdfConformance::PdfA1b` の場合に出力インテント・メタデータ・文書IDを追加し、`write_archive_document` で保存
+-   `lopdf`: 書き出すドキュメント・オブジェクトの表現
+*/
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use lopdf::{Dictionary, Document, Object, StringFormat};
+
+/// PDF/A-1bで出力するPDFのバージョン（PDF/A-1はPDF 1.4が基準）
+pub const ARCHIVE_PDF_VERSION: &str = "1.4";
+
+/// 出力インテントの条件名（`/OutputConditionIdentifier`）
+pub const SRGB_OUTPUT_CONDITION: &str = "sRGB IEC61966-2.1";
+
+/// 文書情報・XMPに記録する作成アプリケーション名
+pub const ARCHIVE_PRODUCER: &str = "ClickCapture";
+
+/// sRGBのトーンカーブの点数（`curv` タグ）
+const SRGB_CURVE_POINTS: usize = 1024;
+
+/// PDF/A-1bの文書情報・XMPに記録する日時（UTC、秒単位）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveTimestamp {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl ArchiveTimestamp {
+    /// 現在時刻（UTC）
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    /// `SystemTime` をUTCの日時に変換する（1970年より前は1970-01-01として扱う）
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let days = (secs / 86_400) as i64;
+        let secs_of_day = (secs % 86_400) as u32;
+
+        // 1970-01-01からの日数を年月日に変換（グレゴリオ暦、3月始まりの年で計算）
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+
+        Self {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day / 60 % 60,
+            second: secs_of_day % 60,
+        }
+    }
+
+    /// 文書情報の日付形式（`D:YYYYMMDDHHmmSSZ`）
+    pub fn pdf_date(&self) -> String {
+        format!(
+            "D:{:04}{:02}{:02}{:02}{:02}{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// XMPの日付形式（ISO 8601、`YYYY-MM-DDTHH:mm:SSZ`）
+    pub fn xmp_date(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// sRGBのICCプロファイル（v2.1、ディスプレイクラス、マトリックス/TRC形式）を生成する
+///
+/// 出力インテント（`/DestOutputProfile`）に埋め込み、`DeviceRGB` の画像の色の基準にします。
+pub fn srgb_icc_profile() -> Vec<u8> {
+    // 原色・白色点（D50に順応済みのXYZ）
+    const WHITE_POINT: [f64; 3] = [0.9642, 1.0, 0.8249];
+    const RED: [f64; 3] = [0.436_074_7, 0.222_504_5, 0.013_932_2];
+    const GREEN: [f64; 3] = [0.385_064_9, 0.716_878_6, 0.097_104_5];
+    const BLUE: [f64; 3] = [0.143_080_4, 0.060_616_9, 0.714_173_3];
+
+    let description = text_description_tag(SRGB_OUTPUT_CONDITION);
+    let copyright = text_tag("No copyright, use freely");
+    let white_point = xyz_tag(WHITE_POINT);
+    let red = xyz_tag(RED);
+    let green = xyz_tag(GREEN);
+    let blue = xyz_tag(BLUE);
+    let curve = srgb_curve_tag();
+
+    // タグのデータ（rTRC / gTRC / bTRC は同じトーンカーブのデータを共有する）
+    let tag_data: [&[u8]; 7] = [
+        &description,
+        &copyright,
+        &white_point,
+        &red,
+        &green,
+        &blue,
+        &curve,
+    ];
+    let tags: [(&[u8; 4], usize); 9] = [
+        (b"desc", 0),
+        (b"cprt", 1),
+        (b"wtpt", 2),
+        (b"rXYZ", 3),
+        (b"gXYZ", 4),
+        (b"bXYZ", 5),
+        (b"rTRC", 6),
+        (b"gTRC", 6),
+        (b"bTRC", 6),
+    ];
+
+    // タグデータの配置（ヘッダー128バイト + タグ数 + タグ表の後ろに、4バイト境界で並べる）
+    let mut offset = 128 + 4 + tags.len() * 12;
+    let mut data_offsets = Vec::with_capacity(tag_data.len());
+    for data in tag_data {
+        data_offsets.push(offset);
+        offset += data.len().next_multiple_of(4);
+    }
+    let profile_size = offset;
+
+    let mut profile = Vec::with_capacity(profile_size);
+    // ヘッダー
+    push_u32(&mut profile, profile_size as u32);
+    push_u32(&mut profile, 0); // CMMタイプ
+    push_u32(&mut profile, 0x0210_0000); // バージョン2.1
+    profile.extend_from_slice(b"mntr"); // ディスプレイ
+    profile.extend_from_slice(b"RGB ");
+    profile.extend_from_slice(b"XYZ ");
+    for value in [2024u16, 1, 1, 0, 0, 0] {
+        profile.extend_from_slice(&value.to_be_bytes()); // 作成日時
+    }
+    profile.extend_from_slice(b"acsp");
+    profile.extend_from_slice(&[0; 24]); // プラットフォーム・フラグ・製造元・モデル・属性
+    push_u32(&mut profile, 0); // レンダリングインテント（知覚的）
+    for value in WHITE_POINT {
+        push_s15_fixed16(&mut profile, value); // PCSの光源（D50）
+    }
+    profile.extend_from_slice(&[0; 48]); // 作成者・プロファイルID・予約領域
+    debug_assert_eq!(profile.len(), 128);
+
+    // タグ表
+    push_u32(&mut profile, tags.len() as u32);
+    for (signature, data_index) in tags {
+        profile.extend_from_slice(signature);
+        push_u32(&mut profile, data_offsets[data_index] as u32);
+        push_u32(&mut profile, tag_data[data_index].len() as u32);
+    }
+
+    // タグデータ
+    for data in tag_data {
+        profile.extend_from_slice(data);
+        profile.resize(profile.len().next_multiple_of(4), 0);
+    }
+    debug_assert_eq!(profile.len(), profile_size);
+    profile
+}
+
+/// PDF/A-1bのXMPメタデータ（カタログの `/Metadata` に設定するストリームの内容）を生成する
+///
+/// 作成日時・更新日時・作成アプリケーションは、文書情報（`/Info`）と同じ値を記録します。
+pub fn xmp_metadata(producer: &str, timestamp: &ArchiveTimestamp) -> Vec<u8> {
+    let date = timestamp.xmp_date();
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+         <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+         <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n\
+         <rdf:Description rdf:about=\"\" xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\">\n\
+         <pdfaid:part>1</pdfaid:part>\n\
+         <pdfaid:conformance>B</pdfaid:conformance>\n\
+         </rdf:Description>\n\
+         <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">\n\
+         <xmp:CreateDate>{date}</xmp:CreateDate>\n\
+         <xmp:ModifyDate>{date}</xmp:ModifyDate>\n\
+         <xmp:CreatorTool>{producer}</xmp:CreatorTool>\n\
+         </rdf:Description>\n\
+         <rdf:Description rdf:about=\"\" xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">\n\
+         <pdf:Producer>{producer}</pdf:Producer>\n\
+         </rdf:Description>\n\
+         </rdf:RDF>\n\
+         </x:xmpmeta>\n\
+         <?xpacket end=\"w\"?>",
+        date = date,
+        producer = escape_xml(producer),
+    )
+    .into_bytes()
+}
+
+/// トレーラーの `/ID` に設定する16バイトの文書IDを生成する
+///
+/// 作成日時とページ数などの `seed` から求めます（暗号学的な一意性は不要）。
+pub fn document_id(seed: &[u8]) -> Vec<u8> {
+    let mut id = Vec::with_capacity(16);
+    for salt in [0u8, 1] {
+        let mut hasher = DefaultHasher::new();
+        salt.hash(&mut hasher);
+        seed.hash(&mut hasher);
+        SystemTime::now().hash(&mut hasher);
+        id.extend_from_slice(&hasher.finish().to_be_bytes());
+    }
+    id
+}
+
+/**
+ * PDF/A-1の書式で `Document` を書き出す
+ *
+ * # 書式
+ * - ヘッダー（`%PDF-1.4`）の次の行に、0x80以上のバイトを4つ含むコメント
+ * - `N G obj` と `endobj` の前後、`stream` の後と `endstream` の前に改行
+ *   （ストリームの `/Length` は `endstream` 前の改行を含まない、実際のデータの長さ）
+ * - 相互参照表（1行20バイト）とトレーラー（`/Size` を更新）
+ */
+pub fn write_archive_document<W: Write>(doc: &Document, target: &mut W) -> io::Result<()> {
+    let mut out = Vec::new();
+    writeln!(out, "%PDF-{}", ARCHIVE_PDF_VERSION)?;
+    out.extend_from_slice(b"%\xE2\xE3\xCF\xD3\n");
+
+    let mut offsets: BTreeMap<u32, (usize, u16)> = BTreeMap::new();
+    for (&(id, generation), object) in &doc.objects {
+        offsets.insert(id, (out.len(), generation));
+        writeln!(out, "{} {} obj", id, generation)?;
+        match object {
+            Object::Stream(stream) => {
+                let mut dict = stream.dict.clone();
+                dict.set("Length", stream.content.len() as i64);
+                write_dictionary(&mut out, &dict);
+                out.extend_from_slice(b"\nstream\n");
+                out.extend_from_slice(&stream.content);
+                out.extend_from_slice(b"\nendstream");
+            }
+            other => write_object(&mut out, other),
+        }
+        out.extend_from_slice(b"\nendobj\n");
+    }
+
+    // 相互参照表（欠番は空きエントリ）
+    let size = doc.max_id + 1;
+    let xref_start = out.len();
+    write!(out, "xref\n0 {}\n", size)?;
+    out.extend_from_slice(b"0000000000 65535 f\r\n");
+    for id in 1..size {
+        match offsets.get(&id) {
+            Some((offset, generation)) => write!(out, "{:010} {:05} n\r\n", offset, generation)?,
+            None => out.extend_from_slice(b"0000000000 65535 f\r\n"),
+        }
+    }
+
+    let mut trailer = doc.trailer.clone();
+    trailer.set("Size", i64::from(size));
+    out.extend_from_slice(b"trailer\n");
+    write_dictionary(&mut out, &trailer);
+    write!(out, "\nstartxref\n{}\n%%EOF\n", xref_start)?;
+
+    target.write_all(&out)
+}
+
+/// オブジェクトを書き出す（ストリームは間接オブジェクトとして `write_archive_document` で扱う）
+fn write_object(out: &mut Vec<u8>, object: &Object) {
+    match object {
+        Object::Null => out.extend_from_slice(b"null"),
+        Object::Boolean(value) => out.extend_from_slice(if *value { b"true" } else { b"false" }),
+        Object::Integer(value) => out.extend_from_slice(value.to_string().as_bytes()),
+        Object::Real(value) => out.extend_from_slice(format_real(*value).as_bytes()),
+        Object::Name(name) => write_name(out, name),
+        Object::String(text, StringFormat::Literal) => {
+            out.push(b'(');
+            for &byte in text {
+                if matches!(byte, b'(' | b')' | b'\\') {
+                    out.push(b'\\');
+                    out.push(byte);
+                } else if byte == b'\r' {
+                    out.extend_from_slice(b"\\r");
+                } else {
+                    out.push(byte);
+                }
+            }
+            out.push(b')');
+        }
+        Object::String(bytes, StringFormat::Hexadecimal) => {
+            out.push(b'<');
+            for byte in bytes {
+                out.extend_from_slice(format!("{:02X}", byte).as_bytes());
+            }
+            out.push(b'>');
+        }
+        Object::Array(items) => {
+            out.push(b'[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(b' ');
+                }
+                write_object(out, item);
+            }
+            out.push(b']');
+        }
+        Object::Dictionary(dict) => write_dictionary(out, dict),
+        Object::Stream(stream) => write_dictionary(out, &stream.dict),
+        Object::Reference((id, generation)) => {
+            out.extend_from_slice(format!("{} {} R", id, generation).as_bytes())
+        }
+    }
+}
+
+/// 辞書を `<< /Key value ... >>` の形式で書き出す
+fn write_dictionary(out: &mut Vec<u8>, dict: &Dictionary) {
+    out.extend_from_slice(b"<<");
+    for (key, value) in dict.iter() {
+        write_name(out, key);
+        out.push(b' ');
+        write_object(out, value);
+    }
+    out.extend_from_slice(b">>");
+}
+
+/// 名前オブジェクトを書き出す（区切り文字・空白・非ASCIIは `#xx` でエスケープ）
+fn write_name(out: &mut Vec<u8>, name: &[u8]) {
+    out.push(b'/');
+    for &byte in name {
+        if b" \t\n\r\x0C()<>[]{}/%#".contains(&byte) || !(33..=126).contains(&byte) {
+            out.extend_from_slice(format!("#{:02X}", byte).as_bytes());
+        } else {
+            out.push(byte);
+        }
+    }
+}
+
+/// 実数を指数表記を使わずに書き出す（PDFは `1e-5` のような指数表記に対応していない）
+fn format_real(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// ICCプロファイルの符号付き固定小数点数（s15Fixed16Number）を追加する
+fn push_s15_fixed16(out: &mut Vec<u8>, value: f64) {
+    out.extend_from_slice(&((value * 65536.0).round() as i32).to_be_bytes());
+}
+
+/// ビッグエンディアンの32ビット整数を追加する
+fn push_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// `XYZ ` タグ（XYZType）
+fn xyz_tag(xyz: [f64; 3]) -> Vec<u8> {
+    let mut tag = b"XYZ \0\0\0\0".to_vec();
+    for value in xyz {
+        push_s15_fixed16(&mut tag, value);
+    }
+    tag
+}
+
+/// `text` タグ（textType、ASCIIの終端付き文字列）
+fn text_tag(text: &str) -> Vec<u8> {
+    let mut tag = b"text\0\0\0\0".to_vec();
+    tag.extend_from_slice(text.as_bytes());
+    tag.push(0);
+    tag
+}
+
+/// `desc` タグ（textDescriptionType、ASCIIの説明のみでUnicode・ScriptCodeは空）
+fn text_description_tag(text: &str) -> Vec<u8> {
+    let mut tag = b"desc\0\0\0\0".to_vec();
+    push_u32(&mut tag, text.len() as u32 + 1);
+    tag.extend_from_slice(text.as_bytes());
+    tag.push(0);
+    push_u32(&mut tag, 0); // Unicodeの言語コード
+    push_u32(&mut tag, 0); // Unicodeの文字数
+    tag.extend_from_slice(&[0; 3]); // ScriptCodeのコード・文字数
+    tag.extend_from_slice(&[0; 67]); // ScriptCodeの説明
+    tag
+}
+
+/// `curv` タグ（curveType、sRGBのトーンカーブを `SRGB_CURVE_POINTS` 点で近似）
+fn srgb_curve_tag() -> Vec<u8> {
+    let mut tag = b"curv\0\0\0\0".to_vec();
+    push_u32(&mut tag, SRGB_CURVE_POINTS as u32);
+    for index in 0..SRGB_CURVE_POINTS {
+        let encoded = index as f64 / (SRGB_CURVE_POINTS - 1) as f64;
+        let linear = if encoded <= 0.04045 {
+            encoded / 12.92
+        } else {
+            ((encoded + 0.055) / 1.055).powf(2.4)
+        };
+        let value = (linear * 65535.0).round() as u16;
+        tag.extend_from_slice(&value.to_be_bytes());
+    }
+    tag
+}
+
+/// XMLの特殊文字をエスケープする
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,1658 @@
+/*
+============================================================================
+PDF構築モジュール (pdf_builder.rs)
+============================================================================
+
+【ファイル概要】
+JPEG画像を再圧縮せずにPDFページとして埋め込む `PdfBuilder` と、
+フォルダ内のJPEG（およびWebP）をサイズ上限に従って分割しながらPDFへ変換する
+`export_jpeg_folder_to_pdf` を提供するライブラリモジュールです。
+`AppState` やダイアログには依存せず、進捗・警告は `PdfExportEvent` として呼び出し元のコールバックに通知します。
+
+【主要機能】
+1.  **`PdfBuilder`**:
+    -   `lopdf` を利用してPDFドキュメントを構築します。
+    -   JPEGデータを `DCTDecode` フィルタでそのまま埋め込み、画質の劣化を防ぎます。
+2.  **`export_jpeg_folder_to_pdf` / `export_jpeg_folders_to_pdf` / `export_image_files_to_pdf`**:
+    -   `export_image_files_to_pdf` はファイルを指定された順に変換します（キャプチャ履歴の並べ替え順など）。
+    -   複数フォルダを指定した場合は、フォルダごとにソートした画像を指定順に連結して1つのPDFストリームにします。
+    -   `jpg`/`jpeg`/`webp`/`bmp` ファイルをファイル名順に収集し、ページとして追加します。
+    -   WebP・BMPはPDFに直接埋め込めないため、品質95%のJPEGに変換してから埋め込みます。
+    -   AVIFはデコーダーを同梱していないため、警告を出してスキップします。
+    -   推定サイズが上限を超えた場合、現在のPDFを保存して新しいPDFを開始します（`0001.pdf` 形式の連番）。
+    -   同じ連番のPDFが既にある場合は `PdfOverwritePolicy` に従い、上書きするか `0001_2.pdf` のように枝番を付けて保存します。
+    -   読み込み・デコードに失敗した画像はスキップし、1ページも作成できなかった場合のみエラーを返します。
+    -   `capture_time_footer` 指定時は、EXIFの撮影日時を各ページ下端のフッターに表示します
+        （`add_jpeg_page_with_footer`、画像の下に余白を追加するため画像は隠れません）。
+    -   ページサイズは画像ごとの実効DPIから決めます。EXIFの撮影条件（選択領域・出力サイズ・撮影時の画面DPI）が
+        あれば縮小率を反映し、無い画像は `screen_dpi` の画面で等倍キャプチャしたものとして扱います
+        （PDFビューアの100%表示で、元の画面と同じ大きさになります）。
+3.  **`find_existing_pdf_outputs`**:
+    -   出力先フォルダに以前の変換の連番PDF（`0001.pdf` 形式）が残っているかを確認します（上書きの確認用）。
+4.  **`append_image_files_to_pdf` / `files_modified_after_pdf`**:
+    -   既存のPDFを `lopdf` で読み込み、ページツリーのルートに新しいページを追加して `Count` を更新します。
+    -   一時ファイル（`.pdf.tmp`）に保存してから置き換えます。サイズ上限による分割は行いません。
+    -   暗号化・タグ付き・出力インテント（PDF/A等）・オブジェクトストリームを含むPDFは、
+        このツール以外で作成・編集されたものとして追記を拒否します。
+    -   `files_modified_after_pdf` で、PDFより後に保存した画像だけを追記の対象にできます。
+5.  **`collect_capture_images` / `sort_capture_files`**:
+    -   変換対象の画像を連番の数値順に収集します（GIF変換・コンタクトシート作成と共通）。
+    -   9999を超えた5桁の連番（`10000.jpg`）も、4桁の連番の後に並べます。
+
+【技術仕様】
+-   **PDFライブラリ**: `lopdf` を使用して、低レベルなPDFオブジェクトを直接操作。
+-   **画像ライブラリ**: `image` を使用して、JPEGの寸法（幅・高さ）をヘッダーのみから取得（全体はデコードしない）。
+-   **先読み**: `PageReadAhead` が2つのスレッドで画像ファイルの読み込み・変換を先行させ、容量制限付きチャネルで
+    ファイルの順に受け渡す（ディスクI/OとPDFの組み立てを並行させ、出力は1スレッドで処理した場合と同じ）。
+-   **PDF/A-1b**: `PdfConformance::PdfA1b` 指定時は、PDF 1.4で作成し、sRGBの出力インテント・XMPメタデータ・
+    文書情報・文書IDを追加して `pdf_archive::write_archive_document` で保存します（フッターは省略）。
+-   **ログ出力**: 進捗・警告は引数の `notify` コールバックへ `PdfExportEvent` で通知し（表示言語に合わせた文言はバイナリ側の
+    `strings.rs` で作成）、詳細なデバッグ情報は標準出力へ出力。
+
+【AI解析用：依存関係】
+-   `capturer.rs`: `Capturer::export_pdf` から呼び出し
+-   `export_pdf.rs`（バイナリ側）: `AppState` の設定と、`PdfExportEvent` を `app_log` に出力するコールバックを渡して呼び出し
+-   `gif_builder.rs` / `contact_sheet_builder.rs`: `collect_capture_images` を利用
+-   `capture_metadata.rs`: 撮影日時フッターと実効DPI（ページサイズ）用のEXIF読み出し
+-   `pdf_archive.rs`: PDF/A-1b用のICCプロファイル・XMPメタデータ・PDF/A-1の書式での書き出し
+-   `lopdf`, `image`: PDF生成と画像解析のための外部クレート
+*/
+
+use crate::capture_metadata::{
+    effective_dpi_from_user_comment, read_exif_date_time_original, read_exif_user_comment,
+};
+use crate::pdf_archive::{
+    ARCHIVE_PDF_VERSION, ARCHIVE_PRODUCER, ArchiveTimestamp, SRGB_OUTPUT_CONDITION, document_id,
+    srgb_icc_profile, write_archive_document, xmp_metadata,
+};
+use image::codecs::jpeg::JpegEncoder;
+use image::io::Reader as ImageReader;
+use image::{DynamicImage, GenericImageView};
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use num_format::{Locale, ToFormattedString};
+use std::fs::{self, File};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Instant;
+
+/// WebP等をPDFへ埋め込む際にJPEGへ変換する品質（%）
+const TRANSCODE_JPEG_QUALITY: u8 = 95;
+
+/// PDF変換で画像ファイルを先読みするスレッド数（ディスクI/OとPDFの組み立てを並行させる）
+const PDF_READER_THREADS: usize = 2;
+
+/// 先読みスレッドごとに、受け取られる前に保持できる画像の数（メモリ使用量の上限）
+const PDF_READ_AHEAD_PER_THREAD: usize = 4;
+
+/// 出力先に同じ連番のPDF（以前の変換結果）が既にある場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PdfOverwritePolicy {
+    /// 既存のPDFを上書きする
+    #[default]
+    Overwrite,
+    /// 既存のPDFを残し、`0001_2.pdf` のように空いている枝番を付けて保存する
+    KeepExisting,
+}
+
+/// PDFの準拠規格（通常のPDF / 長期保存用のPDF/A-1b）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PdfConformance {
+    /// 通常のPDF（PDF 1.5）
+    #[default]
+    Standard,
+    /// アーカイブ用のPDF/A-1b（sRGBの出力インテント・XMPメタデータ・文書IDを追加）
+    ///
+    /// フォントを埋め込まない撮影日時フッターはPDF/A-1では使用できないため、表示しません。
+    PdfA1b,
+}
+
+/// 撮影条件の無い画像のページサイズに使う画面DPI（Windowsの表示スケール100%）
+pub const DEFAULT_SCREEN_DPI: u32 = 96;
+
+/// PDF変換の出力設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PdfExportOptions {
+    /// 1ファイルあたりのPDFサイズ上限（MB）
+    pub max_size_mb: u16,
+    /// 撮影時の画面DPI。EXIFに画面DPIの記録が無い画像のページサイズに使用
+    pub screen_dpi: u32,
+    /// 各ページの下にEXIFの撮影日時をフッターとして表示する（EXIFの無い画像はフッターなし）
+    pub capture_time_footer: bool,
+    /// 同じ連番のPDFが既にある場合に上書きするか、枝番を付けて残すか
+    pub overwrite_policy: PdfOverwritePolicy,
+    /// 通常のPDFか、アーカイブ用のPDF/A-1b（撮影日時フッターは省略）か
+    pub conformance: PdfConformance,
+}
+
+impl Default for PdfExportOptions {
+    /// 上限20MB、96DPI、フッターなし、上書き、通常のPDF
+    fn default() -> Self {
+        Self {
+            max_size_mb: 20,
+            screen_dpi: DEFAULT_SCREEN_DPI,
+            capture_time_footer: false,
+            overwrite_policy: PdfOverwritePolicy::Overwrite,
+            conformance: PdfConformance::Standard,
+        }
+    }
+}
+
+/// 画像をスキップした理由（PDF・GIF・コンタクトシートで共通）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSkipReason {
+    /// AVIF（デコーダーを同梱していないため読み込めない）
+    Avif,
+    /// ファイルを読み込めない
+    Read(String),
+    /// 画像をデコードできない
+    Decode(String),
+    /// WebP・BMPからJPEGへの変換に失敗した（元の形式、大文字）
+    Transcode { format: String, error: String },
+    /// PDFへのページの追加に失敗した
+    AddPage(String),
+    /// 先読みスレッドが終了した
+    ReadAheadStopped,
+}
+
+/// PDF変換・追記中の出来事（バイナリ側で表示言語に合わせてログに出力する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfExportEvent {
+    /// 対象の画像ファイルが見つからない（何もせずに終了する）
+    NoImages,
+    /// PDF/Aのため撮影日時フッターを省略する
+    ArchiveFooterOmitted,
+    /// 画像をページとして追加する（`index` は1から）
+    Processing {
+        file: String,
+        index: usize,
+        total: usize,
+    },
+    /// 画像をスキップした
+    Skipped {
+        file: String,
+        reason: ImageSkipReason,
+    },
+    /// サイズ上限に達したため、現在のPDFを保存して新しいPDFを開始する
+    SizeLimitReached { estimated_bytes: usize },
+    /// 既存のPDFを上書きする
+    Overwriting { path: PathBuf },
+    /// 既存のPDFを残し、枝番を付けた名前で保存する
+    KeepingExisting { path: PathBuf, versioned: PathBuf },
+    /// PDFを保存した
+    Saved { path: PathBuf, bytes: usize },
+    /// 変換が完了した（`skipped` が0の場合はすべての画像を変換した）
+    Completed { converted: usize, skipped: usize },
+    /// 既存のPDFに画像を追記する（`index` は1から）
+    Appending {
+        file: String,
+        index: usize,
+        total: usize,
+    },
+    /// 既存のPDFに追記した
+    Appended {
+        path: PathBuf,
+        added: usize,
+        total_pages: usize,
+        bytes: usize,
+    },
+    /// 追記できなかった画像がある
+    AppendSkipped { count: usize },
+}
+
+/// 撮影日時フッターの高さ（ポイント）。フッター付きページはこの分だけ画像の下に余白を追加します
+const FOOTER_HEIGHT_PT: f64 = 12.0;
+
+/// ページの大きさ（ポイント、1/72インチ）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageSize {
+    /// ページの幅（画像の幅と同じ）
+    pub width: f64,
+    /// 画像を配置する高さ
+    pub image_height: f64,
+    /// ページの高さ（フッター付きの場合は `image_height + FOOTER_HEIGHT_PT`）
+    pub height: f64,
+}
+
+/// 画像のピクセル数と実効DPIから、ページの大きさ（ポイント）を計算する
+///
+/// 実効DPIを基準にすることで、PDFビューアの100%表示で元の画面と同じ大きさになります
+/// （`ピクセル数 * 72 / dpi`、96 DPIでは 1px = 0.75pt）。
+///
+/// # 引数
+/// * `width` / `height` - 画像の大きさ（ピクセル）
+/// * `dpi` - 画像の実効DPI（0より大きい有限の値、呼び出し側で検証済み）
+/// * `with_footer` - 撮影日時フッターの分だけページの高さを広げるか
+pub fn page_size_pt(width: u32, height: u32, dpi: f64, with_footer: bool) -> PageSize {
+    let px_to_pt = |px: u32| -> f64 { (px as f64) * 72.0 / dpi };
+    let image_height = px_to_pt(height);
+    PageSize {
+        width: px_to_pt(width),
+        image_height,
+        height: if with_footer {
+            image_height + FOOTER_HEIGHT_PT
+        } else {
+            image_height
+        },
+    }
+}
+
+/// 撮影日時フッターの文字サイズ（ポイント）
+const FOOTER_FONT_SIZE_PT: f64 = 7.0;
+
+/// 撮影日時フッターの左端・下端からの余白（ポイント）
+const FOOTER_MARGIN_PT: f64 = 3.0;
+
+/// ページリソース内でのフッター用フォントの名前
+const FOOTER_FONT_NAME: &str = "F1";
+
+/// PDFドキュメントの構築を管理する構造体
+///
+/// `lopdf` を使用して、JPEG画像からPDFページを作成し、
+/// ドキュメント全体の構造（Pagesツリー、Catalogなど）を管理します。
+pub struct PdfBuilder {
+    /// `lopdf` のドキュメントオブジェクト。全てのPDFオブジェクト（ディクショナリ、ストリーム等）を保持します。
+    doc: Document,
+    /// 作成された各ページの `ObjectId` を保持するベクター。最終的に `Pages` ツリーの構築に使用されます。
+    pages: Vec<ObjectId>,
+    /// PDF内で画像リソース（XObject）にユニークな名前を付けるためのカウンター。
+    current_image_counter: u32,
+    /// 撮影日時フッター用のフォント（Helvetica）。最初のフッター付きページ追加時に作成します。
+    footer_font_id: Option<ObjectId>,
+    /// 準拠規格（PDF/A-1bの場合は `finalize` で出力インテント等を追加し、PDF/A-1の書式で保存します）
+    conformance: PdfConformance,
+    /// PDF/A-1b用のオブジェクト。`finalize` は保存・サイズ推定のたびに呼ばれるため、最初の1回のみ作成します。
+    archive_objects: Option<ArchiveObjects>,
+}
+
+/// PDF/A-1bでカタログ・トレーラーから参照するオブジェクト
+struct ArchiveObjects {
+    /// 出力インテント（sRGBのICCプロファイルを参照）
+    output_intent_id: ObjectId,
+    /// XMPメタデータのストリーム
+    metadata_id: ObjectId,
+    /// 文書情報（作成日時・更新日時・作成アプリケーション、XMPと同じ値）
+    info_id: ObjectId,
+    /// トレーラーの `/ID`
+    document_id: Vec<u8>,
+}
+
+impl Default for PdfBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfBuilder {
+    /// 新しい `PdfBuilder` インスタンスを作成します。
+    pub fn new() -> Self {
+        Self::with_conformance(PdfConformance::Standard)
+    }
+
+    /// 準拠規格を指定して `PdfBuilder` インスタンスを作成します。
+    pub fn with_conformance(conformance: PdfConformance) -> Self {
+        let version = match conformance {
+            PdfConformance::Standard => "1.5",
+            PdfConformance::PdfA1b => ARCHIVE_PDF_VERSION,
+        };
+        Self {
+            doc: Document::with_version(version),
+            pages: Vec::new(),
+            current_image_counter: 1,
+            footer_font_id: None,
+            conformance,
+            archive_objects: None,
+        }
+    }
+
+    /// JPEG画像を新しいページとしてPDFドキュメントに追加する
+    ///
+    /// JPEGデータを再圧縮せずに `DCTDecode` フィルタを用いてそのまま埋め込むことで、
+    /// 画質の劣化を防ぎます。ページサイズは `DEFAULT_SCREEN_DPI` を基準にします。
+    ///
+    /// # 引数
+    /// * `jpeg_bytes` - JPEGファイルの生データ。
+    /// * `width` - 画像の幅（ピクセル）。
+    /// * `height` - 画像の高さ（ピクセル）。
+    pub fn add_jpeg_page(
+        &mut self,
+        jpeg_bytes: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.add_jpeg_page_with_footer(jpeg_bytes, width, height, DEFAULT_SCREEN_DPI as f64, None)
+    }
+
+    /// JPEG画像を新しいページとして追加し、画像の下に1行のフッター（撮影日時など）を表示する
+    ///
+    /// フッターを指定した場合、ページの高さを `FOOTER_HEIGHT_PT` だけ広げ、画像を覆わずに
+    /// 下端へ文字列を描画します。`None` の場合は `add_jpeg_page` と同じページを作成します。
+    ///
+    /// # 引数
+    /// * `jpeg_bytes` - JPEGファイルの生データ。
+    /// * `width` - 画像の幅（ピクセル）。
+    /// * `height` - 画像の高さ（ピクセル）。
+    /// * `dpi` - 画像の実効DPI。ページの大きさ（ポイント）は `ピクセル数 * 72 / dpi` になります。
+    /// * `footer` - フッターに表示する文字列（ASCIIのみ、標準フォントHelveticaで描画）。
+    ///   PDF/A-1bではフォントを埋め込めないため無視します。
+    pub fn add_jpeg_page_with_footer(
+        &mut self,
+        jpeg_bytes: Vec<u8>,
+        width: u32,
+        height: u32,
+        dpi: f64,
+        footer: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // JPEGサイズの事前検証
+        if jpeg_bytes.is_empty() {
+            return Err("空のJPEGデータが渡されました".into());
+        }
+
+        if width == 0 || height == 0 {
+            return Err(format!("無効な画像サイズ: {}x{}", width, height).into());
+        }
+
+        if !(dpi.is_finite() && dpi > 0.0) {
+            return Err(format!("無効なDPI: {}", dpi).into());
+        }
+
+        // PDF/A-1はすべてのフォントの埋め込みを要求するため、標準フォントのフッターは使用しない
+        let footer = footer.filter(|_| self.conformance == PdfConformance::Standard);
+
+        // 画像XObject（PDF内で画像を表現するオブジェクト）を作成します。
+        let mut xobject = Dictionary::new();
+        xobject.set("Type", "XObject");
+        xobject.set("Subtype", "Image");
+        xobject.set("Width", Object::Integer(width as i64));
+        xobject.set("Height", Object::Integer(height as i64));
+        xobject.set("ColorSpace", "DeviceRGB");
+        xobject.set("BitsPerComponent", Object::Integer(8));
+        xobject.set("Filter", "DCTDecode");
+
+        // 元のJPEGデータをストリームとしてラップします。`DCTDecode`フィルタが指定されているため、
+        // PDFビューアはこれをJPEGとして直接デコードします。
+        let stream = Stream::new(xobject, jpeg_bytes);
+        let image_id = self.doc.add_object(stream);
+
+        // ページ内で画像を参照するためのユニークなリソース名を生成します。
+        let resource_name = format!("Image{}", self.current_image_counter);
+        self.current_image_counter += 1;
+
+        // ページサイズをポイント単位（1/72インチ）で計算します。
+        let PageSize {
+            width: page_width,
+            image_height,
+            height: page_height,
+        } = page_size_pt(width, height, dpi, footer.is_some());
+
+        // ページコンテンツストリーム（画像をページ全体に配置）
+        // フッター付きの場合は、画像をフッターの高さだけ上にずらしてページ下端に文字列を描画する
+        let contents = match footer {
+            None => format!(
+                "q\n{0} 0 0 {1} 0 0 cm\n/{2} Do\nQ\n",
+                page_width, image_height, resource_name
+            ),
+            Some(text) => format!(
+                "q\n{0} 0 0 {1} 0 {2} cm\n/{3} Do\nQ\n\
+                BT\n/{4} {5} Tf\n{6} {6} Td\n({7}) Tj\nET\n",
+                page_width,
+                image_height,
+                FOOTER_HEIGHT_PT,
+                resource_name,
+                FOOTER_FONT_NAME,
+                FOOTER_FONT_SIZE_PT,
+                FOOTER_MARGIN_PT,
+                escape_pdf_string(text)
+            ),
+        };
+
+        let contents_stream = Stream::new(Dictionary::new(), contents.into_bytes());
+        let contents_id = self.doc.add_object(contents_stream);
+
+        // ページが使用するリソース（この場合は画像XObject）を定義するリソースディクショナリを作成します。
+        let mut resources = Dictionary::new();
+        let mut xobj_map = Dictionary::new();
+        xobj_map.set(resource_name, image_id);
+        resources.set("XObject", xobj_map);
+        if footer.is_some() {
+            let font_id = self.footer_font_id();
+            let mut font_map = Dictionary::new();
+            font_map.set(FOOTER_FONT_NAME, font_id);
+            resources.set("Font", font_map);
+        }
+
+        // ページ辞書の作成
+        let mut page = Dictionary::new();
+        page.set("Type", "Page");
+        page.set(
+            "MediaBox",
+            vec![
+                Object::Integer(0),
+                Object::Integer(0),
+                Object::Real(page_width),
+                Object::Real(page_height),
+            ],
+        );
+        page.set("Resources", resources);
+        page.set("Contents", contents_id);
+
+        let page_id = self.doc.add_object(page);
+        self.pages.push(page_id);
+
+        Ok(())
+    }
+
+    /// フッター用の標準フォント（Helvetica、埋め込みなし）を取得する（未作成の場合は作成）
+    fn footer_font_id(&mut self) -> ObjectId {
+        if let Some(font_id) = self.footer_font_id {
+            return font_id;
+        }
+        let mut font = Dictionary::new();
+        font.set("Type", "Font");
+        font.set("Subtype", "Type1");
+        font.set("BaseFont", "Helvetica");
+        let font_id = self.doc.add_object(font);
+        self.footer_font_id = Some(font_id);
+        font_id
+    }
+
+    /// ドキュメントの最終処理を行い、保存可能な状態にする
+    ///
+    /// `Pages` ツリーと `Catalog` ディクショナリを構築し、ドキュメントのルートを設定します。
+    /// この処理は、ドキュメントを保存する直前、またはサイズを推定する前に呼び出す必要があります。
+    pub fn finalize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.pages.is_empty() {
+            return Ok(()); // 空文書は何もしない
+        }
+
+        let pages_kids: Vec<Object> = self.pages.iter().map(|id| Object::Reference(*id)).collect();
+        let mut pages_dict = Dictionary::new();
+        pages_dict.set("Type", "Pages");
+        pages_dict.set("Kids", Object::Array(pages_kids));
+        pages_dict.set("Count", Object::Integer(self.pages.len() as i64));
+
+        // 各ページのParent参照を設定
+        let pages_id = self.doc.add_object(pages_dict);
+        for &page_id in &self.pages {
+            if let Ok(page_obj) = self.doc.get_object_mut(page_id) {
+                if let Object::Dictionary(page_dict) = page_obj {
+                    page_dict.set("Parent", pages_id);
+                }
+            }
+        }
+
+        // カタログの作成
+        let mut catalog = Dictionary::new();
+        catalog.set("Type", "Catalog");
+        catalog.set("Pages", pages_id);
+        let catalog_id = self.doc.add_object(catalog);
+
+        // ドキュメントのルートオブジェクトとしてカタログを設定
+        self.doc.trailer.set("Root", catalog_id);
+
+        // PDF/A-1b: 出力インテント・XMPメタデータ・文書情報・文書IDを設定
+        if self.conformance == PdfConformance::PdfA1b {
+            let archive = self.archive_objects();
+            let (output_intent_id, metadata_id, info_id) = (
+                archive.output_intent_id,
+                archive.metadata_id,
+                archive.info_id,
+            );
+            let document_id =
+                Object::String(archive.document_id.clone(), StringFormat::Hexadecimal);
+            if let Ok(Object::Dictionary(catalog)) = self.doc.get_object_mut(catalog_id) {
+                catalog.set("Metadata", metadata_id);
+                catalog.set("OutputIntents", vec![Object::Reference(output_intent_id)]);
+            }
+            self.doc.trailer.set("Info", info_id);
+            self.doc
+                .trailer
+                .set("ID", vec![document_id.clone(), document_id]);
+        }
+
+        Ok(())
+    }
+
+    /// PDF/A-1b用のオブジェクトを取得する（未作成の場合は作成）
+    fn archive_objects(&mut self) -> &ArchiveObjects {
+        if self.archive_objects.is_none() {
+            let timestamp = ArchiveTimestamp::now();
+
+            // sRGBのICCプロファイル（3成分）を出力インテントとして埋め込む
+            let mut profile_dict = Dictionary::new();
+            profile_dict.set("N", Object::Integer(3));
+            let profile_id = self
+                .doc
+                .add_object(Stream::new(profile_dict, srgb_icc_profile()));
+
+            let mut output_intent = Dictionary::new();
+            output_intent.set("Type", "OutputIntent");
+            output_intent.set("S", "GTS_PDFA1");
+            output_intent.set(
+                "OutputConditionIdentifier",
+                Object::string_literal(SRGB_OUTPUT_CONDITION),
+            );
+            output_intent.set("Info", Object::string_literal(SRGB_OUTPUT_CONDITION));
+            output_intent.set("DestOutputProfile", profile_id);
+            let output_intent_id = self.doc.add_object(output_intent);
+
+            // XMPメタデータ（PDF/A-1ではメタデータのストリームを圧縮しない）
+            let mut metadata_dict = Dictionary::new();
+            metadata_dict.set("Type", "Metadata");
+            metadata_dict.set("Subtype", "XML");
+            let metadata_id = self.doc.add_object(Stream::new(
+                metadata_dict,
+                xmp_metadata(ARCHIVE_PRODUCER, &timestamp),
+            ));
+
+            // 文書情報（XMPと同じ作成日時・作成アプリケーション）
+            let mut info = Dictionary::new();
+            info.set("Producer", Object::string_literal(ARCHIVE_PRODUCER));
+            info.set("CreationDate", Object::string_literal(timestamp.pdf_date()));
+            info.set("ModDate", Object::string_literal(timestamp.pdf_date()));
+            let info_id = self.doc.add_object(info);
+
+            self.archive_objects = Some(ArchiveObjects {
+                output_intent_id,
+                metadata_id,
+                info_id,
+                document_id: document_id(timestamp.pdf_date().as_bytes()),
+            });
+        }
+        self.archive_objects
+            .as_ref()
+            .expect("PDF/A-1b用のオブジェクトは作成済み")
+    }
+
+    /// ドキュメントをバイト列に書き出す（PDF/A-1bの場合はPDF/A-1の書式で書き出す）
+    fn write_to(&mut self, buffer: &mut Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+        match self.conformance {
+            PdfConformance::Standard => self.doc.save_to(buffer)?,
+            PdfConformance::PdfA1b => write_archive_document(&self.doc, buffer)?,
+        }
+        Ok(())
+    }
+
+    /// 現在構築中のPDFの推定ファイルサイズをバイト単位で計算する
+    ///
+    /// 内部的にドキュメントをメモリ上のバッファに保存してみて、そのサイズを返します。
+    /// ファイル分割の判定に使用されます。
+    pub fn estimate_size(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        self.finalize()?;
+        let mut buffer = Vec::new();
+        self.write_to(&mut buffer)?;
+        Ok(buffer.len())
+    }
+
+    /// 構築したPDFドキュメントを指定されたパスに保存する
+    pub fn save_to_file(&mut self, path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+        self.finalize()?;
+        let mut buffer = Vec::new();
+        self.write_to(&mut buffer)?;
+        File::create(path)?.write_all(&buffer)?;
+        Ok(buffer.len())
+    }
+}
+
+/// フォルダ内のJPEG画像をPDFファイルに変換する
+///
+/// `source_dir` 内のJPEGファイルをファイル名順に読み込み、`max_size_mb` を上限として
+/// 1つまたは複数のPDFファイル（`0001.pdf` 形式）に分割して `output_dir` へ保存します。
+///
+/// # 引数
+/// * `source_dir` - 変換元のJPEGが格納されたフォルダ
+/// * `output_dir` - PDFの出力先フォルダ（存在しない場合は作成）
+/// * `max_size_mb` - 1ファイルあたりのPDFサイズ上限（MB）
+/// * `notify` - 進捗・警告の通知先
+pub fn export_jpeg_folder_to_pdf(
+    source_dir: &Path,
+    output_dir: &Path,
+    max_size_mb: u16,
+    notify: &dyn Fn(PdfExportEvent),
+) -> Result<(), Box<dyn std::error::Error>> {
+    export_jpeg_folders_to_pdf(
+        &[source_dir.to_path_buf()],
+        output_dir,
+        &PdfExportOptions {
+            max_size_mb,
+            ..PdfExportOptions::default()
+        },
+        notify,
+    )
+}
+
+/// 複数フォルダ内のJPEG画像を、指定された順に連結して1つのPDFストリームに変換する
+///
+/// 各フォルダ内の画像はファイル名順に並べ、`source_dirs` の順に連結します
+/// （セッションごとのサブフォルダーを1冊にまとめる用途）。
+/// サイズ上限による分割（`0001.pdf` 形式）はフォルダをまたいで適用されます。
+///
+/// # 引数
+/// * `source_dirs` - 変換元のJPEGが格納されたフォルダ（連結する順）
+/// * `output_dir` - PDFの出力先フォルダ（存在しない場合は作成）
+/// * `options` - サイズ上限・画面DPI・撮影日時フッター・既存PDFの扱い・準拠規格
+/// * `notify` - 進捗・警告の通知先（`PdfExportEvent`）
+pub fn export_jpeg_folders_to_pdf(
+    source_dirs: &[PathBuf],
+    output_dir: &Path,
+    options: &PdfExportOptions,
+    notify: &dyn Fn(PdfExportEvent),
+) -> Result<(), Box<dyn std::error::Error>> {
+    // フォルダの存在を確認（1つでも存在しない場合は変換を開始しない）
+    if let Some(missing_dir) = source_dirs.iter().find(|dir| !dir.exists()) {
+        return Err(format!(
+            "❌ 指定されたフォルダーが存在しません: {}",
+            missing_dir.display()
+        )
+        .into());
+    }
+
+    // 各フォルダ内の画像ファイル（.jpg, .jpeg, .webp, .avif, .bmp）をファイル名でソートし、フォルダ順に連結
+    let mut entries = Vec::new();
+    for source_dir in source_dirs {
+        let images = collect_capture_images(source_dir)?;
+        if source_dirs.len() > 1 {
+            println!(
+                "変換元フォルダー: {} ({}件)",
+                source_dir.display(),
+                images.len()
+            );
+        }
+        entries.extend(images);
+    }
+
+    export_image_files_to_pdf(&entries, output_dir, options, notify)
+}
+
+/// 指定された画像ファイルを、指定された順にPDFに変換する
+///
+/// フォルダの並び順ではなく、呼び出し側が決めた順（キャプチャ履歴で並べ替えた順など）で
+/// ページを追加します。サイズ上限による分割・スキップ・フッター・ページサイズの扱いは
+/// `export_jpeg_folders_to_pdf` と同じです。
+///
+/// # 引数
+/// * `files` - 変換する画像ファイル（ページの順）
+/// * `output_dir` - PDFの出力先フォルダ（存在しない場合は作成）
+/// * `options` - サイズ上限・画面DPI・撮影日時フッター・既存PDFの扱い・準拠規格
+/// * `notify` - 進捗・警告の通知先（`PdfExportEvent`）
+pub fn export_image_files_to_pdf(
+    files: &[PathBuf],
+    output_dir: &Path,
+    options: &PdfExportOptions,
+    notify: &dyn Fn(PdfExportEvent),
+) -> Result<(), Box<dyn std::error::Error>> {
+    let PdfExportOptions {
+        max_size_mb,
+        screen_dpi,
+        capture_time_footer,
+        overwrite_policy,
+        conformance,
+    } = *options;
+    if files.is_empty() {
+        notify(PdfExportEvent::NoImages);
+        return Ok(());
+    }
+
+    // 出力先フォルダが存在しない場合は作成
+    let output_folder_path = output_dir;
+    if !output_folder_path.exists() {
+        fs::create_dir_all(output_folder_path)?;
+    }
+
+    println!("処理対象ファイル数: {}", files.len());
+
+    // PDF/A-1では埋め込まれていないフォントを使えないため、撮影日時フッターは表示しない
+    if conformance == PdfConformance::PdfA1b && capture_time_footer {
+        notify(PdfExportEvent::ArchiveFooterOmitted);
+    }
+
+    let mut pdf_index = 1;
+    let mut current_builder = PdfBuilder::with_conformance(conformance);
+    let mut files_in_current_pdf = 0;
+    let total_files = files.len();
+
+    // デコード等に失敗してスキップした画像（1枚の破損で全体を中断しないため）
+    let mut skipped_files: Vec<String> = Vec::new();
+    let mut total_pages = 0;
+
+    // PDFの最大ファイルサイズ（MB単位）をバイトに変換
+    let max_pdf_size_bytes = (max_size_mb as u64) * 1024 * 1024;
+    println!(
+        "PDFサイズ上限: {} Byte",
+        max_pdf_size_bytes.to_formatted_string(&Locale::ja)
+    );
+
+    // 画像の読み込みを別スレッドで先読みし、PDFの組み立てと並行して行う（ページの順は変わらない）
+    let started = Instant::now();
+    let mut read_ahead = PageReadAhead::spawn(files);
+
+    for (index, path) in files.iter().enumerate() {
+        let filename = path
+            .file_name()
+            .expect("ファイル名の取得に失敗しました")
+            .to_string_lossy()
+            .to_string();
+
+        notify(PdfExportEvent::Processing {
+            file: filename.clone(),
+            index: index + 1,
+            total: total_files,
+        });
+
+        // 先読みスレッドが読み込んだ画像を受け取る（読み込み・変換に失敗した画像はログに記録してスキップ）
+        let LoadedPage {
+            jpeg_bytes,
+            width,
+            height,
+            transcoded_from,
+        } = match read_ahead.next_page() {
+            Ok(page) => page,
+            Err(reason) => {
+                notify(PdfExportEvent::Skipped {
+                    file: filename.clone(),
+                    reason,
+                });
+                skipped_files.push(filename);
+                continue;
+            }
+        };
+
+        let file_size_mb = jpeg_bytes.len() as f64 / 1024.0 / 1024.0;
+        if let Some(format) = transcoded_from {
+            println!(
+                "  {}をJPEGに変換: {} x {} px, {:.1}MB",
+                format, width, height, file_size_mb
+            );
+        } else {
+            let bytes_per_pixel = jpeg_bytes.len() as f64 / (width * height).max(1) as f64;
+
+            println!(
+                "  {} x {} px, {:.1}MB, {:.3}バイト/ピクセル",
+                width, height, file_size_mb, bytes_per_pixel
+            );
+
+            if jpeg_bytes.len() > 50 * 1024 * 1024 {
+                // 50MB以上の画像は警告
+                println!("⚠️ 警告: 大きな画像ファイル ({:.1}MB)", file_size_mb);
+            }
+
+            if bytes_per_pixel < 0.1 {
+                println!(
+                    "⚠️ 警告: 低品質JPEG ({:.3}バイト/ピクセル)",
+                    bytes_per_pixel
+                );
+            } else if bytes_per_pixel > 1.0 {
+                println!("✅ 高品質JPEG ({:.3}バイト/ピクセル)", bytes_per_pixel);
+            }
+        }
+
+        let (footer, dpi) = page_footer_and_dpi(&jpeg_bytes, capture_time_footer, screen_dpi);
+
+        // 読み込んだJPEGデータを現在の `PdfBuilder` にページとして追加
+        if let Err(e) = current_builder.add_jpeg_page_with_footer(
+            jpeg_bytes.clone(),
+            width,
+            height,
+            dpi,
+            footer.as_deref(),
+        ) {
+            notify(PdfExportEvent::Skipped {
+                file: filename.clone(),
+                reason: ImageSkipReason::AddPage(e.to_string()),
+            });
+            skipped_files.push(filename);
+            continue;
+        }
+
+        files_in_current_pdf += 1;
+        total_pages += 1;
+
+        // ファイルサイズをチェックして、必要であればPDFを分割する。
+        // 毎回チェックするとパフォーマンスが落ちるため、10ファイルごと、または最初の1ファイル以降にチェック。
+        if files_in_current_pdf % 10 == 0 || files_in_current_pdf > 1 {
+            let estimated_size = match current_builder.estimate_size() {
+                Ok(size) => size,
+                Err(e) => {
+                    eprintln!("❌ PDFサイズ推定エラー: {}", e);
+                    return Err(e);
+                }
+            };
+
+            println!(
+                "推定PDFサイズ: {} Byte",
+                estimated_size.to_formatted_string(&Locale::ja)
+            );
+
+            if estimated_size > max_pdf_size_bytes as usize && files_in_current_pdf > 1 {
+                notify(PdfExportEvent::SizeLimitReached {
+                    estimated_bytes: estimated_size,
+                });
+
+                // 現在のPDFを保存する。ただし、サイズオーバーの原因となった最後の画像は含めない。
+                // その画像は次の新しいPDFの最初のページになる。
+                current_builder.pages.pop();
+
+                if !current_builder.pages.is_empty() {
+                    let output_path =
+                        pdf_output_path(output_folder_path, pdf_index, overwrite_policy, notify);
+                    match current_builder.save_to_file(&output_path) {
+                        Ok(file_size) => {
+                            notify(PdfExportEvent::Saved {
+                                path: output_path,
+                                bytes: file_size,
+                            });
+                            pdf_index += 1;
+                        }
+                        Err(e) => {
+                            eprintln!("❌ PDF保存エラー: {}", e);
+                            return Err(e);
+                        }
+                    }
+                }
+
+                // 新しい `PdfBuilder` を作成し、先ほど除外した画像から新しいPDFを開始する
+                current_builder = PdfBuilder::with_conformance(conformance);
+                if let Err(e) = current_builder.add_jpeg_page_with_footer(
+                    jpeg_bytes,
+                    width,
+                    height,
+                    dpi,
+                    footer.as_deref(),
+                ) {
+                    eprintln!("❌ 新PDF開始エラー ({}): {}", filename, e);
+                    return Err(e);
+                }
+                files_in_current_pdf = 1;
+            }
+        }
+    }
+
+    // 1ページも作成できなかった場合のみエラーとする
+    if total_pages == 0 {
+        return Err(format!(
+            "❌ 有効な画像がありませんでした（スキップ: {}件）",
+            skipped_files.len()
+        )
+        .into());
+    }
+
+    // ループ終了後、残っているページがあれば最後のPDFファイルとして保存
+    if !current_builder.pages.is_empty() {
+        let output_path = pdf_output_path(output_folder_path, pdf_index, overwrite_policy, notify);
+        match current_builder.save_to_file(&output_path) {
+            Ok(file_size) => {
+                notify(PdfExportEvent::Saved {
+                    path: output_path,
+                    bytes: file_size,
+                });
+            }
+            Err(e) => {
+                eprintln!("❌ 最終PDF保存エラー: {}", e);
+                return Err(e);
+            }
+        }
+    }
+
+    println!(
+        "PDF変換の所要時間: {:.2}秒",
+        started.elapsed().as_secs_f64()
+    );
+    if !skipped_files.is_empty() {
+        println!("スキップしたファイル: {}", skipped_files.join(", "));
+    }
+    notify(PdfExportEvent::Completed {
+        converted: total_pages,
+        skipped: skipped_files.len(),
+    });
+    Ok(())
+}
+
+/// 既存のPDFの末尾に、画像ファイルをページとして追記する
+///
+/// PDFを読み込み、ページツリーのルート（`Pages`）に新しいページを追加して `Count` を更新します。
+/// 一時ファイルに書き出してから元のファイルと置き換えるため、保存に失敗しても元のPDFは残ります。
+/// このツール以外で作成・編集されたPDF（暗号化・タグ付き・PDF/A・オブジェクトストリームを含むもの）は、
+/// 追記すると文書の構造や準拠情報と一致しなくなるため、変更せずにエラーを返します。
+/// サイズ上限による分割は行いません。
+///
+/// # 引数
+/// * `pdf_path` - 追記先のPDF
+/// * `files` - 追記する画像ファイル（ページの順）
+/// * `screen_dpi` - 撮影時の画面DPI。EXIFに画面DPIの記録が無い画像のページサイズに使用
+/// * `capture_time_footer` - 各ページの下にEXIFの撮影日時をフッターとして表示する
+/// * `notify` - 進捗・警告の通知先（`PdfExportEvent`）
+///
+/// # 戻り値
+/// 追記したページ数
+pub fn append_image_files_to_pdf(
+    pdf_path: &Path,
+    files: &[PathBuf],
+    screen_dpi: u32,
+    capture_time_footer: bool,
+    notify: &dyn Fn(PdfExportEvent),
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let doc = Document::load(pdf_path).map_err(|e| {
+        format!(
+            "❌ PDFを読み込めませんでした ({}): {}",
+            pdf_path.display(),
+            e
+        )
+    })?;
+    let pages_root_id = appendable_pages_root(&doc).map_err(|reason| {
+        format!(
+            "❌ このPDFには追記できません ({}): {}",
+            pdf_path.display(),
+            reason
+        )
+    })?;
+    let existing_pages = doc.get_pages().len();
+    println!(
+        "PDF追記開始: {} (既存 {}ページ, 追記対象 {}件)",
+        pdf_path.display(),
+        existing_pages,
+        files.len()
+    );
+
+    // 読み込んだPDFに、新規作成時と同じ処理でページを追加する
+    let mut builder = PdfBuilder {
+        doc,
+        ..PdfBuilder::new()
+    };
+    let mut skipped_files: Vec<String> = Vec::new();
+    let mut read_ahead = PageReadAhead::spawn(files);
+
+    for (index, path) in files.iter().enumerate() {
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        notify(PdfExportEvent::Appending {
+            file: filename.clone(),
+            index: index + 1,
+            total: files.len(),
+        });
+
+        let LoadedPage {
+            jpeg_bytes,
+            width,
+            height,
+            ..
+        } = match read_ahead.next_page() {
+            Ok(page) => page,
+            Err(reason) => {
+                notify(PdfExportEvent::Skipped {
+                    file: filename.clone(),
+                    reason,
+                });
+                skipped_files.push(filename);
+                continue;
+            }
+        };
+
+        let (footer, dpi) = page_footer_and_dpi(&jpeg_bytes, capture_time_footer, screen_dpi);
+        if let Err(e) =
+            builder.add_jpeg_page_with_footer(jpeg_bytes, width, height, dpi, footer.as_deref())
+        {
+            notify(PdfExportEvent::Skipped {
+                file: filename.clone(),
+                reason: ImageSkipReason::AddPage(e.to_string()),
+            });
+            skipped_files.push(filename);
+        }
+    }
+
+    let new_pages = std::mem::take(&mut builder.pages);
+    if new_pages.is_empty() {
+        return Err(format!(
+            "❌ 追記できる画像がありませんでした（スキップ: {}件）",
+            skipped_files.len()
+        )
+        .into());
+    }
+
+    // 既存のページツリーのルートに新しいページを追加し、ページ数を更新
+    for &page_id in &new_pages {
+        if let Ok(Object::Dictionary(page_dict)) = builder.doc.get_object_mut(page_id) {
+            page_dict.set("Parent", pages_root_id);
+        }
+    }
+    let Ok(Object::Dictionary(pages_root)) = builder.doc.get_object_mut(pages_root_id) else {
+        return Err("❌ PDFのページツリーが見つかりません".into());
+    };
+    let count = pages_root.get(b"Count").and_then(Object::as_i64)?;
+    pages_root.set("Count", Object::Integer(count + new_pages.len() as i64));
+    pages_root
+        .get_mut(b"Kids")
+        .and_then(Object::as_array_mut)?
+        .extend(new_pages.iter().map(|&page_id| Object::Reference(page_id)));
+
+    // 一時ファイルに保存してから置き換える（途中で失敗しても元のPDFを壊さない）
+    let mut buffer = Vec::new();
+    builder.doc.save_to(&mut buffer)?;
+    let temp_path = pdf_path.with_extension("pdf.tmp");
+    if let Err(e) = File::create(&temp_path)
+        .and_then(|mut file| file.write_all(&buffer))
+        .and_then(|_| fs::rename(&temp_path, pdf_path))
+    {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    notify(PdfExportEvent::Appended {
+        path: pdf_path.to_path_buf(),
+        added: new_pages.len(),
+        total_pages: existing_pages + new_pages.len(),
+        bytes: buffer.len(),
+    });
+    if !skipped_files.is_empty() {
+        println!("スキップしたファイル: {}", skipped_files.join(", "));
+        notify(PdfExportEvent::AppendSkipped {
+            count: skipped_files.len(),
+        });
+    }
+    Ok(new_pages.len())
+}
+
+/// 追記できるPDFかを確認し、ページツリーのルート（`Pages`）のIDを返す
+///
+/// このツールが作成するPDFは暗号化・タグ・出力インテント・オブジェクトストリームを含まないため、
+/// いずれかを含むPDFは他のツールで作成・編集されたものとして、理由を返します。
+fn appendable_pages_root(doc: &Document) -> Result<ObjectId, &'static str> {
+    if doc.trailer.has(b"Encrypt") {
+        return Err("暗号化されています");
+    }
+    let catalog = doc
+        .catalog()
+        .map_err(|_| "カタログ（Root）が見つかりません")?;
+
+    // タグ付きPDF（論理構造ツリーがあり、追記したページが構造に含まれなくなる）
+    let is_marked = catalog
+        .get(b"MarkInfo")
+        .and_then(|object| doc.dereference(object))
+        .and_then(|(_, object)| object.as_dict())
+        .and_then(|mark_info| mark_info.get(b"Marked"))
+        .and_then(Object::as_bool)
+        .unwrap_or(false);
+    if is_marked || catalog.has(b"StructTreeRoot") {
+        return Err("タグ付きPDFです");
+    }
+
+    // PDF/A等の出力インテント（XMPメタデータの更新日時などと一致しなくなる）
+    if catalog.has(b"OutputIntents") {
+        return Err("PDF/Aなどの出力インテントを含んでいます");
+    }
+
+    let uses_object_streams = doc.objects.values().any(|object| {
+        matches!(object, Object::Stream(stream) if stream.dict.type_is(b"ObjStm") || stream.dict.type_is(b"XRef"))
+    });
+    if uses_object_streams {
+        return Err("他のツールで圧縮・保存されています（オブジェクトストリーム）");
+    }
+
+    // ページツリーのルート（`Kids` と `Count` を直接持つ `Pages`）
+    let pages_root_id = catalog
+        .get(b"Pages")
+        .and_then(Object::as_reference)
+        .map_err(|_| "ページツリーが見つかりません")?;
+    let pages_root = doc
+        .get_dictionary(pages_root_id)
+        .map_err(|_| "ページツリーが見つかりません")?;
+    let has_kids = pages_root.get(b"Kids").and_then(Object::as_array).is_ok();
+    let has_count = pages_root.get(b"Count").and_then(Object::as_i64).is_ok();
+    if !(pages_root.type_is(b"Pages") && has_kids && has_count) {
+        return Err("ページツリーの形式に対応していません");
+    }
+    Ok(pages_root_id)
+}
+
+/// PDFより後に更新された画像ファイルを、元の順のまま返す
+///
+/// PDFへの追記で、前回の変換・追記の後に撮影した画像だけを対象にするために使用します。
+/// 更新日時を取得できないファイルは含めません。
+///
+/// # 引数
+/// * `files` - 候補の画像ファイル（ページの順）
+/// * `pdf_path` - 比較するPDF
+pub fn files_modified_after_pdf(
+    files: &[PathBuf],
+    pdf_path: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    let pdf_modified = fs::metadata(pdf_path)?.modified()?;
+    Ok(files
+        .iter()
+        .filter(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified > pdf_modified)
+        })
+        .cloned()
+        .collect())
+}
+
+/// ページに表示する撮影日時フッターと、ページサイズの基準となる実効DPIをEXIFから求める
+///
+/// # 引数
+/// * `jpeg_bytes` - ページに埋め込むJPEGデータ
+/// * `capture_time_footer` - 撮影日時をフッターとして表示するか
+/// * `screen_dpi` - EXIFに撮影条件の記録が無い場合に使う画面DPI
+fn page_footer_and_dpi(
+    jpeg_bytes: &[u8],
+    capture_time_footer: bool,
+    screen_dpi: u32,
+) -> (Option<String>, f64) {
+    // 撮影日時フッター（EXIFの撮影日時を `YYYY-MM-DD HH:MM:SS` 形式で表示）
+    // WebP・BMPから変換したJPEGにはEXIFが無いため、元ファイルがJPEGの場合のみ表示される
+    let footer = if capture_time_footer {
+        read_exif_date_time_original(jpeg_bytes).map(|date_time| date_time.replacen(':', "-", 2))
+    } else {
+        None
+    };
+
+    // ページサイズの基準となる実効DPI（撮影条件から縮小率を反映、無い場合は画面DPIで等倍）
+    let screen_dpi = screen_dpi.max(1) as f64;
+    let dpi = read_exif_user_comment(jpeg_bytes)
+        .and_then(|comment| effective_dpi_from_user_comment(&comment, screen_dpi))
+        .unwrap_or(screen_dpi);
+    println!("  ページの実効DPI: {:.1}", dpi);
+
+    (footer, dpi)
+}
+
+/// 連番 `pdf_index` のPDFの保存先パスを決める
+///
+/// `0001.pdf` 形式のファイルが既にある場合、`PdfOverwritePolicy::KeepExisting` では
+/// `0001_2.pdf`、`0001_3.pdf` …のうち最初に空いている名前を返し、その旨を通知します。
+fn pdf_output_path(
+    output_dir: &Path,
+    pdf_index: u32,
+    overwrite_policy: PdfOverwritePolicy,
+    notify: &dyn Fn(PdfExportEvent),
+) -> PathBuf {
+    let path = output_dir.join(format!("{:04}.pdf", pdf_index));
+    if !path.exists() {
+        return path;
+    }
+
+    match overwrite_policy {
+        PdfOverwritePolicy::Overwrite => {
+            notify(PdfExportEvent::Overwriting { path: path.clone() });
+            path
+        }
+        PdfOverwritePolicy::KeepExisting => {
+            let versioned = (2..)
+                .map(|version| output_dir.join(format!("{:04}_{}.pdf", pdf_index, version)))
+                .find(|candidate| !candidate.exists())
+                .expect("空いている枝番が見つかりませんでした");
+            notify(PdfExportEvent::KeepingExisting {
+                path,
+                versioned: versioned.clone(),
+            });
+            versioned
+        }
+    }
+}
+
+/// 出力先フォルダに残っている、PDF変換の連番PDF（`0001.pdf` 形式）をファイル名順に返す
+///
+/// 枝番付きのファイル（`0001_2.pdf`）や手動で名前を付けたPDFは、変換で上書きされないため含めません。
+/// フォルダが存在しない場合は空のリストを返します。
+///
+/// # 引数
+/// * `output_dir` - PDFの出力先フォルダ
+pub fn find_existing_pdf_outputs(output_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !output_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut outputs: Vec<PathBuf> = fs::read_dir(output_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let is_pdf = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+            let is_numbered = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| {
+                    stem.len() >= 4 && stem.bytes().all(|byte| byte.is_ascii_digit())
+                });
+            is_pdf && is_numbered && path.is_file()
+        })
+        .collect();
+    sort_capture_files(&mut outputs);
+    Ok(outputs)
+}
+
+/// フォルダ内のキャプチャ画像（.jpg, .jpeg, .webp, .avif, .bmp）を収集し、連番順にソートして返す
+///
+/// PDF変換とGIF変換（`gif_builder.rs`）、コンタクトシート作成（`contact_sheet_builder.rs`）で共通の収集・並び順を使用します。
+///
+/// # 引数
+/// * `source_dir` - 画像が格納されたフォルダ
+pub fn collect_capture_images(source_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(source_dir)?
+        .filter_map(|r| r.ok())
+        .map(|e| e.path())
+        .filter(|path| is_capture_image(path))
+        .collect();
+
+    sort_capture_files(&mut entries);
+    Ok(entries)
+}
+
+/// キャプチャファイルを連番の数値順に並べ替える
+///
+/// 連番は4桁ゼロパディングですが、9999を超えると `10000.jpg` のように5桁になるため、
+/// 文字列順では `10000.jpg` が `1001.jpg` より前になってしまいます。
+/// ファイル名（拡張子を除く）が数字のみのファイルは数値順に並べ、それ以外のファイルはその後にファイル名順で並べます。
+pub fn sort_capture_files(entries: &mut [PathBuf]) {
+    entries.sort_by_cached_key(|path| {
+        let counter = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| !stem.is_empty() && stem.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|stem| stem.parse::<u64>().ok());
+        (counter.is_none(), counter, path.clone())
+    });
+}
+
+/// キャプチャ画像として扱う拡張子（.jpg, .jpeg, .webp, .avif, .bmp）のファイルかどうかを判定する
+///
+/// フォルダの収集（`collect_capture_images`）と、キャプチャ履歴からのPDF変換で共通の判定を使用します。
+pub fn is_capture_image(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        let s = ext.to_string_lossy().to_lowercase();
+        s == "jpg" || s == "jpeg" || s == "webp" || s == "avif" || s == "bmp"
+    })
+}
+
+/// PDFの文字列リテラル（`(...)`）用に、括弧とバックスラッシュをエスケープする
+fn escape_pdf_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// PDFのページにする画像（埋め込むJPEGのデータと寸法）
+struct LoadedPage {
+    jpeg_bytes: Vec<u8>,
+    width: u32,
+    height: u32,
+    /// WebP・BMPから変換した場合の元の形式（ログ表示用、大文字）
+    transcoded_from: Option<String>,
+}
+
+/// 画像ファイルの読み込みを別スレッドで先読みし、ページの順に受け渡す
+///
+/// `PDF_READER_THREADS` 個のスレッドが `files` を順番に分担して読み込み（`i` 番目はスレッド `i % N`）、
+/// スレッドごとの容量制限付きチャネル（`PDF_READ_AHEAD_PER_THREAD` 件）で送ります。
+/// 受け取り側はファイルの順にチャネルを切り替えて受け取るため、ページの順は1スレッドで読み込んだ場合と同じです。
+/// 途中で破棄した場合は送信が失敗し、先読みスレッドも終了します。
+struct PageReadAhead {
+    receivers: Vec<Receiver<Result<LoadedPage, ImageSkipReason>>>,
+    next_index: usize,
+}
+
+impl PageReadAhead {
+    /// 先読みスレッドを開始する
+    fn spawn(files: &[PathBuf]) -> Self {
+        let files: Arc<[PathBuf]> = files.into();
+        let reader_count = PDF_READER_THREADS.min(files.len()).max(1);
+        let receivers = (0..reader_count)
+            .map(|reader| {
+                let (sender, receiver) = mpsc::sync_channel(PDF_READ_AHEAD_PER_THREAD);
+                let files = Arc::clone(&files);
+                thread::spawn(move || {
+                    for path in files.iter().skip(reader).step_by(reader_count) {
+                        if sender.send(load_pdf_page(path)).is_err() {
+                            break;
+                        }
+                    }
+                });
+                receiver
+            })
+            .collect();
+        Self {
+            receivers,
+            next_index: 0,
+        }
+    }
+
+    /// 次のファイルの読み込み結果を受け取る（`files` の順に1回ずつ呼び出す）
+    fn next_page(&mut self) -> Result<LoadedPage, ImageSkipReason> {
+        let receiver = &self.receivers[self.next_index % self.receivers.len()];
+        self.next_index += 1;
+        receiver
+            .recv()
+            .unwrap_or(Err(ImageSkipReason::ReadAheadStopped))
+    }
+}
+
+/// 画像ファイルを読み込み、PDFに埋め込むJPEGと寸法を返す（先読みスレッドで実行）
+///
+/// JPEGはファイルの生データをそのまま埋め込むため、寸法はヘッダーのみから読み取ります（全体をデコードしない）。
+/// WebP・BMPは直接埋め込めないため、デコードしてJPEGに変換します。AVIFはデコーダーを同梱していないため対象外です。
+///
+/// # 戻り値
+/// * `Err(ImageSkipReason)` - スキップする理由
+fn load_pdf_page(path: &Path) -> Result<LoadedPage, ImageSkipReason> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    // AVIFはデコーダーを同梱していないため、PDFへ埋め込めない旨を明示してスキップする
+    if extension == "avif" {
+        return Err(ImageSkipReason::Avif);
+    }
+
+    // PDFはWebP・BMPを直接埋め込めないため、デコードした画像をJPEGに変換して埋め込む
+    if extension == "webp" || extension == "bmp" {
+        let img = ImageReader::open(path)
+            .map_err(|e| ImageSkipReason::Read(e.to_string()))?
+            .decode()
+            .map_err(|e| ImageSkipReason::Decode(e.to_string()))?;
+        let (width, height) = img.dimensions();
+        let jpeg_bytes = transcode_to_jpeg(&img).map_err(|e| ImageSkipReason::Transcode {
+            format: extension.to_uppercase(),
+            error: e.to_string(),
+        })?;
+        return Ok(LoadedPage {
+            jpeg_bytes,
+            width,
+            height,
+            transcoded_from: Some(extension.to_uppercase()),
+        });
+    }
+
+    // JPEGはファイルの生データをそのまま埋め込む（再圧縮なし）
+    let jpeg_bytes = fs::read(path).map_err(|e| ImageSkipReason::Read(e.to_string()))?;
+    let (width, height) = ImageReader::new(Cursor::new(&jpeg_bytes))
+        .with_guessed_format()
+        .map_err(image::ImageError::from)
+        .and_then(|reader| reader.into_dimensions())
+        .map_err(|e| ImageSkipReason::Decode(e.to_string()))?;
+    Ok(LoadedPage {
+        jpeg_bytes,
+        width,
+        height,
+        transcoded_from: None,
+    })
+}
+
+/// WebPなどPDFに直接埋め込めない形式の画像を、埋め込み用のJPEGに変換する
+///
+/// 変換時の劣化を抑えるため、品質は `TRANSCODE_JPEG_QUALITY`（95%）で固定します。
+fn transcode_to_jpeg(img: &DynamicImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut jpeg_bytes = Vec::new();
+    let mut encoder = JpegEncoder::new_with_quality(&mut jpeg_bytes, TRANSCODE_JPEG_QUALITY);
+    encoder.encode_image(&img.to_rgb8())?;
+    Ok(jpeg_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用の小さなJPEG（単色、8x4ピクセル）
+    fn sample_jpeg() -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::from_pixel(
+            8,
+            4,
+            image::Rgb([40, 120, 200]),
+        ));
+        transcode_to_jpeg(&image).unwrap()
+    }
+
+    /// 2ページのPDFを作成し、書き出したバイト列と読み込み直したドキュメントを返す
+    fn build_pdf(conformance: PdfConformance) -> (Vec<u8>, Document) {
+        let mut builder = PdfBuilder::with_conformance(conformance);
+        builder
+            .add_jpeg_page_with_footer(sample_jpeg(), 8, 4, 96.0, Some("2026-10-18 12:00"))
+            .unwrap();
+        builder.add_jpeg_page(sample_jpeg(), 8, 4).unwrap();
+        // サイズ推定の後に保存しても、PDF/A-1b用のオブジェクトは1組のみ
+        builder.estimate_size().unwrap();
+        builder.finalize().unwrap();
+        let mut bytes = Vec::new();
+        builder.write_to(&mut bytes).unwrap();
+        let doc = Document::load_mem(&bytes).unwrap();
+        (bytes, doc)
+    }
+
+    /// ドキュメント内で `/Type` が指定した名前の辞書・ストリームの数
+    fn count_type(doc: &Document, type_name: &str) -> usize {
+        doc.objects
+            .values()
+            .filter(|object| {
+                let dict = match object {
+                    Object::Dictionary(dict) => dict,
+                    Object::Stream(stream) => &stream.dict,
+                    _ => return false,
+                };
+                dict.get(b"Type")
+                    .and_then(Object::as_name_str)
+                    .is_ok_and(|name| name == type_name)
+            })
+            .count()
+    }
+
+    #[test]
+    fn page_size_pt_table() {
+        // (幅px, 高さpx, DPI, フッター, 期待する幅pt, 画像の高さpt, ページの高さpt)
+        let cases = [
+            (96, 96, 96.0, false, 72.0, 72.0, 72.0),
+            (1920, 1080, 96.0, false, 1440.0, 810.0, 810.0),
+            (1920, 1080, 96.0, true, 1440.0, 810.0, 822.0),
+            (1920, 1080, 144.0, false, 960.0, 540.0, 540.0),
+            (1, 1, 72.0, true, 1.0, 1.0, 13.0),
+            // 50%に縮小した画像（実効DPI 48）は原寸と同じ大きさのページになる
+            (960, 540, 48.0, false, 1440.0, 810.0, 810.0),
+        ];
+        for (width, height, dpi, footer, page_width, image_height, page_height) in cases {
+            assert_eq!(
+                page_size_pt(width, height, dpi, footer),
+                PageSize {
+                    width: page_width,
+                    image_height,
+                    height: page_height,
+                },
+                "{width}x{height} @ {dpi} DPI, footer={footer}"
+            );
+        }
+    }
+
+    #[test]
+    fn footer_page_media_box_includes_footer_height() {
+        // 1ページ目はフッター付き、2ページ目はフッターなし（いずれも 8x4px、96 DPI）
+        let (_, doc) = build_pdf(PdfConformance::Standard);
+        let media_boxes = doc
+            .get_pages()
+            .values()
+            .map(|&page_id| {
+                let page = doc.get_object(page_id).unwrap().as_dict().unwrap();
+                page.get(b"MediaBox")
+                    .unwrap()
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.as_float().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            media_boxes,
+            [
+                vec![0.0, 0.0, 6.0, 3.0 + FOOTER_HEIGHT_PT],
+                vec![0.0, 0.0, 6.0, 3.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn pdf_a1b_header_has_version_and_binary_comment() {
+        let (bytes, doc) = build_pdf(PdfConformance::PdfA1b);
+        assert!(bytes.starts_with(b"%PDF-1.4\n%"));
+        assert!(bytes[10..14].iter().all(|&b| b >= 0x80));
+        assert_eq!(doc.version, ARCHIVE_PDF_VERSION);
+        assert_eq!(doc.get_pages().len(), 2);
+    }
+
+    #[test]
+    fn pdf_a1b_catalog_has_srgb_output_intent() {
+        let (_, doc) = build_pdf(PdfConformance::PdfA1b);
+        let catalog = doc.catalog().unwrap();
+        let intents = catalog.get(b"OutputIntents").unwrap().as_array().unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(count_type(&doc, "OutputIntent"), 1);
+
+        let intent = doc
+            .get_dictionary(intents[0].as_reference().unwrap())
+            .unwrap();
+        assert_eq!(
+            intent.get(b"S").unwrap().as_name_str().unwrap(),
+            "GTS_PDFA1"
+        );
+        assert_eq!(
+            intent
+                .get(b"OutputConditionIdentifier")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            SRGB_OUTPUT_CONDITION.as_bytes()
+        );
+
+        // 埋め込んだICCプロファイル：3成分・v2・RGB/XYZ・署名・サイズが一致
+        let profile = intent
+            .get_deref(b"DestOutputProfile", &doc)
+            .unwrap()
+            .as_stream()
+            .unwrap();
+        assert_eq!(profile.dict.get(b"N").unwrap().as_i64().unwrap(), 3);
+        let icc = &profile.content;
+        assert_eq!(icc, &srgb_icc_profile());
+        assert_eq!(
+            u32::from_be_bytes(icc[0..4].try_into().unwrap()) as usize,
+            icc.len()
+        );
+        assert_eq!(icc[8], 2, "PDF/A-1 requires an ICC v2 profile");
+        assert_eq!(&icc[12..16], b"mntr");
+        assert_eq!(&icc[16..20], b"RGB ");
+        assert_eq!(&icc[20..24], b"XYZ ");
+        assert_eq!(&icc[36..40], b"acsp");
+    }
+
+    #[test]
+    fn pdf_a1b_catalog_has_uncompressed_xmp_matching_info() {
+        let (_, doc) = build_pdf(PdfConformance::PdfA1b);
+        let metadata = doc
+            .catalog()
+            .unwrap()
+            .get_deref(b"Metadata", &doc)
+            .unwrap()
+            .as_stream()
+            .unwrap();
+        assert_eq!(
+            metadata.dict.get(b"Type").unwrap().as_name_str().unwrap(),
+            "Metadata"
+        );
+        assert_eq!(
+            metadata
+                .dict
+                .get(b"Subtype")
+                .unwrap()
+                .as_name_str()
+                .unwrap(),
+            "XML"
+        );
+        assert!(
+            metadata.dict.get(b"Filter").is_err(),
+            "XMP must not be compressed"
+        );
+
+        let xmp = String::from_utf8(metadata.content.clone()).unwrap();
+        assert!(xmp.contains("<pdfaid:part>1</pdfaid:part>"));
+        assert!(xmp.contains("<pdfaid:conformance>B</pdfaid:conformance>"));
+        assert!(xmp.contains(&format!(
+            "<pdf:Producer>{}</pdf:Producer>",
+            ARCHIVE_PRODUCER
+        )));
+
+        // 文書情報の作成日時（D:YYYYMMDDHHmmSSZ）とXMPの作成日時（ISO 8601）が同じ時刻
+        let info = doc
+            .trailer
+            .get_deref(b"Info", &doc)
+            .unwrap()
+            .as_dict()
+            .unwrap();
+        let pdf_date = String::from_utf8(
+            info.get(b"CreationDate")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .to_vec(),
+        )
+        .unwrap();
+        let d = &pdf_date[2..];
+        let xmp_date = format!(
+            "{}-{}-{}T{}:{}:{}Z",
+            &d[0..4],
+            &d[4..6],
+            &d[6..8],
+            &d[8..10],
+            &d[10..12],
+            &d[12..14]
+        );
+        assert!(xmp.contains(&format!("<xmp:CreateDate>{}</xmp:CreateDate>", xmp_date)));
+        assert_eq!(
+            info.get(b"Producer").unwrap().as_str().unwrap(),
+            ARCHIVE_PRODUCER.as_bytes()
+        );
+    }
+
+    #[test]
+    fn pdf_a1b_trailer_has_document_id() {
+        let (_, doc) = build_pdf(PdfConformance::PdfA1b);
+        let ids = doc.trailer.get(b"ID").unwrap().as_array().unwrap();
+        assert_eq!(ids.len(), 2);
+        let first = ids[0].as_str().unwrap();
+        assert_eq!(first.len(), 16);
+        assert_eq!(first, ids[1].as_str().unwrap());
+    }
+
+    #[test]
+    fn pdf_a1b_omits_features_disallowed_by_a1b() {
+        let (bytes, doc) = build_pdf(PdfConformance::PdfA1b);
+        // 埋め込まない標準フォント（フッター）・暗号化・透明・オブジェクトストリームを使用しない
+        assert_eq!(count_type(&doc, "Font"), 0);
+        assert_eq!(count_type(&doc, "ObjStm"), 0);
+        assert_eq!(count_type(&doc, "XRef"), 0);
+        assert!(doc.trailer.get(b"Encrypt").is_err());
+        for needle in [&b"/SMask"[..], b"/JavaScript", b"/Transparency"] {
+            assert!(
+                !bytes.windows(needle.len()).any(|window| window == needle),
+                "{}",
+                String::from_utf8_lossy(needle)
+            );
+        }
+    }
+
+    #[test]
+    fn standard_pdf_has_no_archive_objects() {
+        let (bytes, doc) = build_pdf(PdfConformance::Standard);
+        assert!(bytes.starts_with(b"%PDF-1.5"));
+        let catalog = doc.catalog().unwrap();
+        assert!(catalog.get(b"OutputIntents").is_err());
+        assert!(catalog.get(b"Metadata").is_err());
+        assert_eq!(count_type(&doc, "OutputIntent"), 0);
+        // 通常のPDFは撮影日時のフッターを標準フォントで描画する
+        assert_eq!(count_type(&doc, "Font"), 1);
+    }
+}
@@ -3,6 +3,7 @@
 
 // ダイアログリソースID
 #define IDD_DIALOG1 101
+#define IDD_HISTORY_DIALOG 102
 
 // コントロールリソースID
 #define IDC_BROWSE_BUTTON 1001
@@ -18,6 +19,46 @@
 #define IDC_AUTO_CLICK_CHECKBOX 1013
 #define IDC_AUTO_CLICK_INTERVAL_COMBO 1014
 #define IDC_AUTO_CLICK_COUNT_EDIT 1015
+#define IDC_THUMBNAIL_STRIP 1016
+#define IDC_CAPTURE_TRIGGER_COMBO 1017
+#define IDC_WINDOW_CAPTURE_CHECKBOX 1018
+#define IDC_SOUND_CHECKBOX 1019
+#define IDC_HIGH_FIDELITY_TEXT_CHECKBOX 1020
+#define IDC_REAL_INPUT_ONLY_CHECKBOX 1021
+#define IDC_CAPTURE_FORMAT_COMBO 1022
+#define IDC_TARGET_WIDTH_EDIT 1023
+#define IDC_SCHEDULE_BUTTON 1024
+#define IDC_SCHEDULE_INTERVAL_COMBO 1025
+#define IDC_SCHEDULE_COUNT_EDIT 1026
+#define IDC_DUAL_AREA_STACKED_CHECKBOX 1027
+#define IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX 1028
+#define IDC_EXPORT_GIF_BUTTON 1029
+#define IDC_EXPORT_ZIP_BUTTON 1030
+#define IDC_RESAVE_BUTTON 1031
+#define IDC_CAPTURE_METADATA_CHECKBOX 1032
+#define IDC_LANGUAGE_COMBO 1033
+#define IDC_THEME_COMBO 1034
+#define IDC_EXPORT_CONTACT_SHEET_BUTTON 1035
+#define IDC_OVERLAY_VISIBILITY_COMBO 1036
+#define IDC_DIM_OPACITY_COMBO 1037
+#define IDC_BORDER_COLOR_COMBO 1038
+#define IDC_BORDER_WIDTH_COMBO 1039
+#define IDC_BORDER_HALO_CHECKBOX 1040
+#define IDC_FREEZE_SCREEN_CHECKBOX 1041
+#define IDC_COMPLETION_NOTIFY_CHECKBOX 1042
+#define IDC_HISTORY_BUTTON 1043
+#define IDC_HISTORY_LIST 1044
+#define IDC_HISTORY_DELETE_BUTTON 1045
+#define IDC_WORK_AREA_BUTTON 1046
+#define IDC_RENUMBER_BUTTON 1047
+#define IDC_RESET_COUNTER_BUTTON 1048
+#define IDC_PROGRESSIVE_JPEG_CHECKBOX 1049
+#define IDC_PDF_ARCHIVE_CHECKBOX 1050
+#define IDC_AUTO_PDF_EXPORT_CHECKBOX 1051
+#define IDC_SHARE_BUTTON 1052
+#define IDC_AUTO_CLICK_DURATION_COMBO 1053
+#define IDC_ELAPSED_STAMP_CHECKBOX 1054
+#define IDC_PAUSE_ON_USER_INPUT_CHECKBOX 1055
 
 // アイコンリソースID
 #define IDI_CAMERA_OFF 2001
@@ -34,4 +75,8 @@
 #define IDP_CAPTURE_PROCESSING 2009
 #define IDP_CAPTURE_WAITING 2010
 
+// 効果音用WAVリソースID
+#define IDW_SHUTTER_SOUND 2011
+#define IDW_COMPLETE_SOUND 2012
+
 #endif // RESOURCE_H
@@ -0,0 +1,178 @@
+/*
+============================================================================
+保存先フォルダー候補モジュール (save_folder.rs)
+============================================================================
+
+【ファイル概要】
+既定の保存先フォルダーを自動で決めるための、候補フォルダーの優先順位と選択を行うライブラリモジュールです。
+Known Folderのパス・`USERPROFILE` は呼び出し側（バイナリの `folder_manager.rs`）が取得して渡し、
+書き込みできるかの確認もクロージャで受け取るため、Win32 API・ファイル操作には依存しません。
+
+【主要機能】
+1.  **`folder_candidates`**: Known Folder・OneDrive・ローカルの画像フォルダーなどを優先順位順に並べる
+2.  **`choose_save_folder`**: 最初に書き込みできた候補に `\clickcapture` を付けて返す
+
+【技術仕様】
+-   **優先順位**: Known Folderのピクチャ → OneDriveの画像（日本語・英語） → ローカルの画像（英語・日本語）
+    → ドキュメント → デスクトップ → 共通のピクチャ・ドキュメント → `C:\`
+-   **パス区切り**: Windowsの `\` で連結（実行環境に関係なく同じ文字列を返す）
+
+【AI解析用：依存関係】
+-   `ui/folder_manager.rs`（バイナリ側）: `get_pictures_folder` で `SHGetKnownFolderPath` の結果と `is_folder_writable` を渡す
+*/
+
+/// 選択した候補フォルダーの下に作成する保存先のサブフォルダー名
+pub const SAVE_SUBFOLDER_NAME: &str = "clickcapture";
+
+/// すべての候補に書き込めなかった場合の保存先
+pub const FALLBACK_SAVE_FOLDER: &str = "C:\\";
+
+/// 保存先フォルダーの候補を優先順位順に並べる
+///
+/// 【優先順位戦略の根拠】
+/// 0. Known Folder: ユーザーが実際に設定しているピクチャフォルダー（移動済み・OneDriveバックアップ含む）
+/// 1. OneDrive統合: クラウド同期による自動バックアップ
+/// 2. ローカル画像フォルダー: 最も直感的なスクリーンショット保存場所
+/// 3. ドキュメント: 作業文書との関連性
+/// 4. デスクトップ: 一時的なアクセスの容易さ
+/// 5. 共通フォルダー: マルチユーザー環境での利用可能性
+/// 6. システムルート: 最終フォールバック
+///
+/// # 引数
+/// * `known_pictures` - `SHGetKnownFolderPath(FOLDERID_Pictures)` の結果（取得できない・空の場合は `None`）
+/// * `user_profile` - 環境変数 `USERPROFILE`（取得できない場合は `None`、ユーザーフォルダーの候補を省く）
+pub fn folder_candidates(known_pictures: Option<&str>, user_profile: Option<&str>) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    // 【優先順位0】Known Folderのピクチャフォルダー - ユーザーが移動した場合も実際の場所を指す
+    if let Some(pictures_folder) = known_pictures.filter(|path| !path.is_empty()) {
+        candidates.push(pictures_folder.to_string());
+    }
+
+    if let Some(user_profile) = user_profile {
+        // 【優先順位1】OneDriveの画像フォルダー - クラウド同期による保護
+        candidates.push(format!("{}\\OneDrive\\画像", user_profile)); // 日本語版Windows
+        candidates.push(format!("{}\\OneDrive\\Pictures", user_profile)); // 英語版Windows
+
+        // 【優先順位2】ローカルの画像フォルダー - 標準的なスクリーンショット保存場所
+        candidates.push(format!("{}\\Pictures", user_profile)); // 英語版Windows
+        candidates.push(format!("{}\\画像", user_profile)); // 日本語版Windows
+
+        // 【優先順位3】ドキュメントフォルダー - 作業関連ファイルとの整理
+        candidates.push(format!("{}\\Documents", user_profile));
+
+        // 【優先順位4】デスクトップ - 即座のアクセス性重視
+        candidates.push(format!("{}\\Desktop", user_profile));
+    }
+
+    // 【優先順位5】システム共通フォルダー - マルチユーザー環境対応
+    candidates.push("C:\\Users\\Public\\Pictures".to_string());
+    candidates.push("C:\\Users\\Public\\Documents".to_string());
+
+    // 【優先順位6】システムルートフォールバック - 確実な書き込み可能性
+    candidates.push(FALLBACK_SAVE_FOLDER.to_string());
+
+    candidates
+}
+
+/// 候補フォルダーを順に確認し、最初に書き込めたフォルダーの保存先（`\clickcapture` 付き）を返す
+///
+/// # 引数
+/// * `candidates` - 優先順位順の候補（`folder_candidates` の結果）
+/// * `is_writable` - 候補に書き込めるかを確認する関数（書き込めた候補以降は呼び出さない）
+///
+/// # 戻り値
+/// 書き込めた候補がない場合は `None`（呼び出し側で `FALLBACK_SAVE_FOLDER` を使用）。
+pub fn choose_save_folder<I>(
+    candidates: I,
+    mut is_writable: impl FnMut(&str) -> bool,
+) -> Option<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    candidates
+        .into_iter()
+        .find(|folder_path| is_writable(folder_path))
+        .map(|folder_path| format!("{}\\{}", folder_path, SAVE_SUBFOLDER_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILE: &str = "C:\\Users\\taro";
+
+    #[test]
+    fn candidates_are_in_priority_order() {
+        let candidates = folder_candidates(Some("D:\\Photos"), Some(PROFILE));
+        assert_eq!(
+            candidates,
+            [
+                "D:\\Photos",
+                "C:\\Users\\taro\\OneDrive\\画像",
+                "C:\\Users\\taro\\OneDrive\\Pictures",
+                "C:\\Users\\taro\\Pictures",
+                "C:\\Users\\taro\\画像",
+                "C:\\Users\\taro\\Documents",
+                "C:\\Users\\taro\\Desktop",
+                "C:\\Users\\Public\\Pictures",
+                "C:\\Users\\Public\\Documents",
+                "C:\\",
+            ]
+        );
+    }
+
+    #[test]
+    fn candidates_without_known_folder_or_profile() {
+        // (Known Folder, USERPROFILE, 候補数, 先頭の候補)
+        let cases = [
+            (None, Some(PROFILE), 9, "C:\\Users\\taro\\OneDrive\\画像"),
+            (
+                Some(""),
+                Some(PROFILE),
+                9,
+                "C:\\Users\\taro\\OneDrive\\画像",
+            ),
+            (Some("D:\\Photos"), None, 4, "D:\\Photos"),
+            (None, None, 3, "C:\\Users\\Public\\Pictures"),
+        ];
+        for (known_pictures, user_profile, len, first) in cases {
+            let candidates = folder_candidates(known_pictures, user_profile);
+            assert_eq!(candidates.len(), len, "{known_pictures:?} {user_profile:?}");
+            assert_eq!(candidates[0], first, "{known_pictures:?} {user_profile:?}");
+            assert_eq!(candidates.last().unwrap(), FALLBACK_SAVE_FOLDER);
+        }
+    }
+
+    #[test]
+    fn choose_save_folder_picks_first_writable() {
+        let candidates = folder_candidates(Some("D:\\Photos"), Some(PROFILE));
+        // (書き込める候補, 期待する保存先)
+        let cases = [
+            (
+                &["D:\\Photos", "C:\\"][..],
+                Some("D:\\Photos\\clickcapture"),
+            ),
+            (
+                &["C:\\Users\\taro\\Pictures", "C:\\Users\\taro\\Desktop"][..],
+                Some("C:\\Users\\taro\\Pictures\\clickcapture"),
+            ),
+            (&["C:\\"][..], Some("C:\\\\clickcapture")),
+            (&[][..], None),
+        ];
+        for (writable, expected) in cases {
+            let mut checked = Vec::new();
+            let chosen = choose_save_folder(candidates.clone(), |path| {
+                checked.push(path.to_string());
+                writable.contains(&path)
+            });
+            assert_eq!(chosen.as_deref(), expected, "{writable:?}");
+            // 書き込めた候補より後ろは確認しない
+            let expected_checks = candidates
+                .iter()
+                .position(|path| writable.contains(&path.as_str()))
+                .map_or(candidates.len(), |index| index + 1);
+            assert_eq!(checked, candidates[..expected_checks], "{writable:?}");
+        }
+    }
+}
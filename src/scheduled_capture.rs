@@ -0,0 +1,249 @@
+/*
+============================================================================
+定期キャプチャ機能モジュール (scheduled_capture.rs)
+============================================================================
+
+【機能概要】
+クリック操作とは無関係に、指定した間隔（分）ごとに選択済みエリアを自動でキャプチャします。
+ダッシュボードの監視など、無人で一定時間ごとに記録を残す用途を想定しています。
+タイマー処理はバックグラウンドスレッドで行い、キャプチャ自体はメインスレッドで実行します。
+
+【主要機能】
+1.  **`ScheduledCapturer` 構造体**: 定期キャプチャの状態（間隔、最大回数、最大継続時間、実行回数、経過時間、保存枚数）を管理します。
+2.  **バックグラウンド実行**: `std::thread` で間隔を計測し、UIの応答性を維持します。
+3.  **停止条件**: 最大回数（0で無制限）・最大継続時間のいずれかに達するか、停止ボタンで終了します。
+4.  **メインスレッドへの通知**: `PostMessageW` でメインダイアログに
+    `WM_SCHEDULED_CAPTURE`（キャプチャ要求）と `WM_SCHEDULED_CAPTURE_COMPLETE`（終了通知）を送信します。
+
+【技術仕様】
+-   **入力の注入なし**: `AutoClicker` と異なりクリックを合成せず、メッセージ経由で直接キャプチャを要求します。
+-   **スレッド同期**: `Arc<AtomicBool>` の停止フラグと `Arc<AtomicU32>` の実行回数を共有します。
+-   **停止応答性**: 長い間隔の待機中も100ミリ秒ごとに停止フラグを確認します。
+
+【処理フロー】
+1.  **[UI]** 「定期開始」ボタンクリック → `ScheduledCapturer::start()`
+2.  **`scheduled_capture_loop()`**:
+    -   開始直後に1回目の `WM_SCHEDULED_CAPTURE` を送信し、以降は指定間隔ごとに送信します。
+    -   停止条件に達したら `WM_SCHEDULED_CAPTURE_COMPLETE` を送信して終了します。
+3.  **[dialog_handler.rs]** `WM_SCHEDULED_CAPTURE` 受信時に `capture_screen_area_with_counter()` を実行します。
+4.  **[dialog_handler.rs]** `WM_SCHEDULED_CAPTURE_COMPLETE` 受信時にスレッドを回収し、UIを通常状態に戻します。
+
+【AI解析用：依存関係】
+- `ui/schedule_handler.rs`: 開始/停止ボタン、間隔コンボボックス、回数エディットボックス
+- `ui/dialog_handler.rs`: カスタムメッセージを受信してキャプチャ・後処理を行う
+- `app_state.rs`: `AppState` に `ScheduledCapturer` インスタンスを保持する
+*/
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use windows::Win32::{
+    Foundation::{LPARAM, WPARAM},
+    UI::WindowsAndMessaging::PostMessageW,
+};
+
+use crate::app_state::AppState;
+use crate::constants::{WM_SCHEDULED_CAPTURE, WM_SCHEDULED_CAPTURE_COMPLETE};
+use crate::strings;
+use crate::system_utils::app_log;
+
+/// 定期キャプチャの最大継続時間のデフォルト（24時間）
+const DEFAULT_MAX_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 定期キャプチャ機能の状態と制御を管理する
+#[derive(Debug)]
+pub struct ScheduledCapturer {
+    stop_flag: Arc<AtomicBool>, // バックグラウンドスレッドを停止させるためのフラグ
+    interval_minutes: u32,      // キャプチャ間隔（分）
+    max_count: u32,             // 最大キャプチャ回数（0で無制限）
+    max_duration: Duration,     // 最大継続時間（経過後は自動停止）
+    progress_count: Arc<AtomicU32>, // 現在のキャプチャ要求回数
+    captured_count: u32,        // 保存に成功したキャプチャ枚数（メインスレッドで更新）
+    started_at: Option<Instant>, // 開始時刻（経過時間表示用）
+    thread_handle: Option<thread::JoinHandle<()>>, // バックグラウンドスレッドのハンドル
+}
+
+impl ScheduledCapturer {
+    /// `ScheduledCapturer` の新しいインスタンスをデフォルト値で作成する
+    pub fn new() -> Self {
+        Self {
+            stop_flag: Arc::new(AtomicBool::new(true)),
+            interval_minutes: 5, // デフォルト5分
+            max_count: 0,        // デフォルト無制限（最大継続時間で停止）
+            max_duration: DEFAULT_MAX_DURATION,
+            progress_count: Arc::new(AtomicU32::new(0)),
+            captured_count: 0,
+            started_at: None,
+            thread_handle: None,
+        }
+    }
+
+    /// バックグラウンドスレッドが実行中かを確認する
+    pub fn is_running(&self) -> bool {
+        self.thread_handle.is_some()
+    }
+
+    /// キャプチャ間隔（分）を取得する
+    pub fn get_interval_minutes(&self) -> u32 {
+        self.interval_minutes
+    }
+
+    /// キャプチャ間隔（分）を設定する（次回の開始から適用）
+    pub fn set_interval_minutes(&mut self, interval_minutes: u32) {
+        self.interval_minutes = interval_minutes.max(1);
+    }
+
+    /// 最大キャプチャ回数を取得する（0で無制限）
+    pub fn get_max_count(&self) -> u32 {
+        self.max_count
+    }
+
+    /// 最大キャプチャ回数を設定する（0で無制限、次回の開始から適用）
+    pub fn set_max_count(&mut self, max_count: u32) {
+        self.max_count = max_count;
+    }
+
+    /// 現在のキャプチャ要求回数を取得する
+    pub fn get_progress_count(&self) -> u32 {
+        self.progress_count.load(Ordering::Relaxed)
+    }
+
+    /// 保存に成功したキャプチャ枚数を取得する
+    pub fn get_captured_count(&self) -> u32 {
+        self.captured_count
+    }
+
+    /// キャプチャの保存成功を記録する（メインスレッドから呼び出し）
+    pub fn record_captured_frame(&mut self) {
+        self.captured_count += 1;
+    }
+
+    /// 開始からの経過時間を取得する（未開始の場合は0）
+    pub fn get_elapsed(&self) -> Duration {
+        self.started_at
+            .map(|started_at| started_at.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// 定期キャプチャをバックグラウンドスレッドで開始する
+    pub fn start(&mut self) -> Result<(), String> {
+        if self.thread_handle.is_some() {
+            return Err("定期キャプチャは既に開始されています".to_string());
+        }
+
+        // スレッドを開始する前に停止フラグと実行回数をリセット
+        self.stop_flag.store(false, Ordering::Relaxed);
+        let stop_flag = Arc::clone(&self.stop_flag);
+
+        self.progress_count.store(0, Ordering::Relaxed);
+        let progress_count = Arc::clone(&self.progress_count);
+        self.captured_count = 0;
+        self.started_at = Some(Instant::now());
+
+        let interval = Duration::from_secs(self.interval_minutes as u64 * 60);
+        let max_count = self.max_count;
+        let max_duration = self.max_duration;
+
+        // バックグラウンドスレッドで間隔を計測
+        let handle = thread::spawn(move || {
+            scheduled_capture_loop(stop_flag, interval, max_count, max_duration, progress_count);
+        });
+
+        self.thread_handle = Some(handle);
+        app_log(&format!(
+            "⏰ 定期キャプチャを開始しました（{}分間隔, {}）",
+            self.interval_minutes,
+            if self.max_count == 0 {
+                "回数無制限".to_string()
+            } else {
+                format!("{}回", self.max_count)
+            }
+        ));
+
+        Ok(())
+    }
+
+    /// 実行中の定期キャプチャを停止し、スレッドを回収する
+    ///
+    /// 停止条件に達してスレッドが自然終了した後の回収にも使用します。
+    pub fn stop(&mut self) {
+        // 停止フラグをセット
+        self.stop_flag.store(true, Ordering::Relaxed);
+
+        // スレッドの終了を待機
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+            app_log(&format!(
+                "🛑 定期キャプチャを停止しました（{}回実行）",
+                self.get_progress_count()
+            ));
+        }
+    }
+}
+
+impl Drop for ScheduledCapturer {
+    /// `ScheduledCapturer` インスタンスが破棄される際に、実行中のスレッドを確実に停止させる
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 定期キャプチャの間隔を計測し、メインスレッドへキャプチャを要求するループ処理
+///
+/// # 引数
+/// * `stop_flag` - ループを外部から停止させるためのフラグ。
+/// * `interval` - キャプチャ間隔。
+/// * `max_count` - 最大キャプチャ回数（0で無制限）。
+/// * `max_duration` - 最大継続時間。
+/// * `progress_count` - キャプチャ要求回数をカウントするためのアトミックなカウンタ。
+fn scheduled_capture_loop(
+    stop_flag: Arc<AtomicBool>,
+    interval: Duration,
+    max_count: u32,
+    max_duration: Duration,
+    progress_count: Arc<AtomicU32>,
+) {
+    let started_at = Instant::now();
+    let mut count = 0;
+
+    while !stop_flag.load(Ordering::Relaxed) && started_at.elapsed() < max_duration {
+        // メインスレッドにキャプチャを要求（WPARAMに実行回数を設定）
+        count += 1;
+        progress_count.store(count, Ordering::Relaxed);
+        post_dialog_message(WM_SCHEDULED_CAPTURE, count as usize);
+
+        // 最大回数に達した場合は待機せずに終了
+        if max_count > 0 && count >= max_count {
+            break;
+        }
+
+        // 指定された間隔で待機する。
+        // 長い待機時間中の停止要求に即座に応答できるよう、100ミリ秒ごとに停止フラグを確認する。
+        let check_interval = Duration::from_millis(100);
+        let mut remaining = interval;
+        while remaining > Duration::ZERO && !stop_flag.load(Ordering::Relaxed) {
+            let sleep_time = remaining.min(check_interval);
+            thread::sleep(sleep_time);
+            remaining = remaining.saturating_sub(sleep_time);
+        }
+    }
+
+    // ループ終了後、メインスレッドに処理完了を非同期で通知する
+    // （停止ボタンによる停止時は、メインスレッド側で既に後処理済みのため通知しない）
+    if !stop_flag.load(Ordering::Relaxed) {
+        post_dialog_message(WM_SCHEDULED_CAPTURE_COMPLETE, 0);
+    }
+}
+
+/// メインダイアログのメッセージキューにカスタムメッセージを送信する
+fn post_dialog_message(message: u32, wparam: usize) {
+    if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+        unsafe {
+            if let Err(e) = PostMessageW(Some(*hwnd), message, WPARAM(wparam), LPARAM(0)) {
+                app_log(&strings::post_message_failed(&e));
+            }
+        }
+    }
+}
@@ -12,60 +12,116 @@ JPEG画像としての保存、連番ファイル名の生成、キャプチャ
 1.  **キャプチャモード制御 (`toggle_capture_mode`)**:
     -   キャプチャモードの開始と終了を切り替え、関連リソース（フック、オーバーレイ）を管理します。
 2.  **画面領域キャプチャと保存 (`capture_screen_area_with_counter`)**:
+    -   各段階（取得 / マスク / 縮小・結合 / エンコード / 保存）は `capture_pipeline.rs` の `CapturePipeline` に委譲し、
+        この関数は段階の呼び出しと、保存後のログ・連番・サムネイル・履歴の更新のみを行います。
     -   `BitBlt` APIを使用して指定領域のピクセルデータを高速に取得します。
-    -   取得したデータをユーザー設定のスケールと品質でJPEG画像としてエンコードし、保存します。
+    -   キャプチャバックエンド設定（`capture_backend`）に応じて、Desktop Duplication（DXGI）で取得し直します
+        （自動選択時はGDIの結果が真っ黒な場合のみ。DRM保護コンテンツやGPU描画ウィンドウ対策）。
+    -   マスク領域（`mask_areas`）を黒で塗りつぶします。
+    -   2つ目の領域（`selected_area_2`）が選択済みの場合は、同じ縮小率で縮小して左右または上下に結合します。
+    -   取得したデータをユーザー設定のスケール・品質・保存形式（JPEG / WebP / AVIF / BMP）でエンコードし、保存します。
+    -   変化の検出（`change_detection`、レジストリ `ChangeDetection`）が有効な場合、前回保存した画像から
+        変化がなければ保存せず（`CaptureOutcome::Unchanged`）、設定により変化した範囲のみ切り出して保存します。
+    -   撮影情報の記録（`record_capture_metadata`）が有効な場合、JPEGに撮影日時と撮影条件のEXIFを埋め込みます。
 3.  **連番ファイル名生成**:
-    -   保存するファイル名を `0001.jpg`, `0002.jpg` のように自動でインクリメントします。
+    -   保存するファイル名を `0001.jpg`, `0002.jpg` のように自動でインクリメントします（拡張子は保存形式に依存）。
+    -   保存直前にフォルダー内の既存ファイルから空き番号を求めて排他的に作成するため（`create_next_capture_file`）、
+        複数インスタンスが同じフォルダーに保存しても上書きしません。
+    -   起動引数 `--sink=stdout` / `--sink=pipe:名前` を指定した場合は、ファイルに保存せず長さ付きフレームで送信します
+        （連番・サムネイル・履歴は更新しません）。
+    -   一時的なロック（OneDrive同期など）で保存できない場合は待機して再試行し（`write_capture_file_with_retry`）、
+        それでも失敗した画像は `last_failed_capture` に保持して「再保存」ボタン（`retry_failed_capture_save`）で保存できます。
+    -   連番が9900に達すると警告し、9999を超えた後は5桁（`10000.jpg`）で保存を続けます（`advance_capture_file_counter`）。
+    -   保存の直前に空き容量を確認し（`ensure_disk_space`）、不足している場合は書き込まずに自動クリックを停止します。
+    -   保存先フォルダーのテンプレート（レジストリ `SaveFolderTemplate`、例: `D:\caps\{yyyy}\{mm}\{dd}`）が設定されている場合は、
+        保存時の日付で展開したフォルダーに保存します（`get_save_dir_path`、無効なパスの場合はピクチャフォルダー）。
 4.  **自動クリック連携**:
     -   自動クリックモードが有効な場合、最初のクリックをトリガーに `auto_clicker` を起動し、連続キャプチャを実行します。
+    -   「キャプチャのみ」モード（`AutoClickMode::CaptureOnly`）ではクリックを待たずに開始し、
+        `WM_AUTO_CAPTURE_REQUEST` を受けて `handle_auto_capture_request` でキャプチャします。
+    -   残り回数が多い実行中のESCは、`WM_ESC_STOP_CONFIRM` を受けて `handle_esc_stop_confirm` で停止を確認します
+        （確認中は自動クリックが一時停止、「はい」・Enter・もう一度ESCで停止、「いいえ」で再開）。
+    -   開始前に保存する画像の合計サイズを見積もり（`estimate_auto_click_disk_usage`）、確認ダイアログに
+        推定使用量と空き容量を表示します。空き容量を超える場合は開始しません。
 
 【技術仕様】
--   **画面取得**: `GetDC` + `BitBlt` による高速なピクセルデータ取得。
--   **画像処理**: `image` クレートによるJPEGエンコード。`StretchBlt` と `HALFTONE` モードによる高品質な画像縮小。
+-   **画面取得**: `GetDC` + `BitBlt` による高速なピクセルデータ取得。失敗時・真っ黒な場合は `capture_backend.rs` の `DesktopDuplicationBackend` を使用。
+-   **画像処理**: `image` クレートによるJPEGエンコード（高精細テキストモード時は `jpeg-encoder` による4:4:4サンプリング・フラットな輝度量子化テーブル）。原寸バッファを `image::imageops::resize`（Lanczos3）で縮小し、文字のにじみを抑える（`gdi-stretchblt` フィーチャー有効時は従来の `StretchBlt` + `HALFTONE`）。
 -   **ファイルI/O**: `std::fs` と `std::io::BufWriter` による効率的なファイル書き込み。
+-   **エラー処理**: `error.rs` の `CaptureError` で失敗の種類（エリア未選択 / GDI / I/O / エンコード）を返却。
 -   **オーバーレイ**: `capturing_overlay` を使用して、キャプチャ待機中や処理中の状態をユーザーにフィードバック。
+    画面取得の直前に非表示にし、`DwmFlush` と待ち時間（`OverlayHideSettleMs`、既定16ms）で写り込みを防止（`capture_pipeline.rs`）。
+-   **所要時間の計測**: `capture_timing.rs` の `CaptureTimer` で段階ごと（取得 / 変換 / 縮小 / エンコード / 書き込み）に計測し、
+    `TimingDebug` 有効時にログ出力。キャプチャモード終了時にセッションの最小・平均・最大を出力します。
 
 【処理フロー】
 1.  **[UI]** 「キャプチャ開始」ボタンクリック
 2.  **`toggle_capture_mode()`**:
     -   エリアが選択済みか、自動クリック設定が妥当かなどを検証します。
-    -   検証OKならモードを開始し、フックをインストールして `capturing_overlay` を表示します。
+    -   検証OKならモードを開始し、暖機運転（`warm_up_capture`、保存しない1回分のキャプチャ）の後、
+        フックをインストールして `capturing_overlay` を表示します。
 3.  **[マウスフック]** ユーザーが画面を左クリック
 4.  **`low_level_mouse_proc` (in `mouse.rs`)**:
     -   **自動クリック有効時**: `auto_clicker.start()` を呼び出します。`auto_clicker` は内部ループで `perform_mouse_click` を実行し、それが再度このマウスフックに捕捉され、結果的に `capture_screen_area_with_counter` が繰り返し呼ばれます。
     -   **自動クリック無効時**: `capture_screen_area_with_counter()` を一度だけ呼び出します。
 5.  **`capture_screen_area_with_counter()`**:
-    -   `BitBlt` で画面をキャプチャし、`StretchBlt` でリサイズします。
-    -   `image` クレートでJPEGにエンコードし、連番ファイル名で保存します。
-6.  **モード終了**:
+    -   `CapturePipeline` の `grab` → `postprocess` → `scale` → `encode` → `persist` を順に呼び出します。
+6.  **クイックキャプチャ**: `capture_window_under_cursor`
+    -   クイックキャプチャのホットキー（既定: Ctrl+Alt+Shift+W）でカーソル下のウィンドウのクライアント領域を、ドラッグなしでキャプチャします。
+        選択領域（`selected_area` / `selected_area_2`）は変更しないため、続けて通常のキャプチャ・再キャプチャができます。
+    -   再キャプチャのホットキー（既定: Ctrl+Alt+Shift+R、`repeat_last_capture`）で前回の `selected_area` を、キャプチャモードに入らずにその場で再キャプチャします。
+7.  **モード終了**:
     -   ESCキー押下、または「キャプチャ開始」ボタンの再クリックで `toggle_capture_mode()` が呼ばれ、フックとオーバーレイを解放します。
     -   自動クリック完了時も `WM_AUTO_CLICK_COMPLETE` を経由して `toggle_capture_mode()` が呼ばれます。
+    -   「キャプチャ終了時に自動でPDF変換」が有効で、このモード中に保存した画像があれば、
+        `WM_AUTO_PDF_EXPORT` で保存先フォルダーのPDF変換を要求します（`request_auto_pdf_export`）。
 
 ============================================================================
 */
 
 use windows::Win32::UI::WindowsAndMessaging::{
-    IDOK, MB_ICONQUESTION, MB_ICONWARNING, MB_OK, MB_OKCANCEL,
+    GA_ROOT, GetAncestor, GetClientRect, GetCursorPos, GetWindowTextW, IDOK, IDYES, MB_DEFBUTTON1,
+    MB_ICONERROR, MB_ICONQUESTION, MB_ICONWARNING, MB_OK, MB_OKCANCEL, MB_SETFOREGROUND,
+    MB_TOPMOST, MB_YESNO, PostMessageW, WindowFromPoint,
 };
 // 必要なライブラリ（外部機能）をインポート
-use windows::Win32::{
-    Graphics::Gdi::*, // グラフィック描画機能
+use windows::Win32::Foundation::{LPARAM, POINT, RECT, WPARAM}; // 基本的なデータ型
+use windows::Win32::Graphics::Gdi::ClientToScreen; // ウィンドウのクライアント座標の変換
+use windows::Win32::System::SystemInformation::GetLocalTime; // 保存先フォルダーのテンプレートの日付
+// 連番・保存サイズの計算（ライブラリ側と共通）
+use clickcapture::capture_naming::{
+    CaptureCounterMilestone, capture_counter_milestone, capture_file_name,
+    expand_save_folder_template,
 };
-// 画像処理ライブラリ（JPEGキャプチャ保存専用）
-use image::{ImageBuffer, Rgb};
+use clickcapture::capturer::{clamp_to_max_size, compute_scaled_size, estimate_encoded_size};
+use image::RgbImage;
 
-use std::fs;
+use std::{fs, time::Instant};
 
 use crate::{
     app_state::*,
-    hook::*,
-    overlay::Overlay,
+    area_select::cancel_area_select_mode,
+    auto_click::AutoClickMode,
+    capture_pipeline::{
+        CaptureDestination, CapturePipeline, FrameChange, RawFrame, WARM_UP_MAX_PIXELS,
+    },
+    capture_timing::{
+        CapturePhase, CaptureTimer, dump_capture_timing_stats, finish_capture_timing,
+    },
+    constants::WM_AUTO_PDF_EXPORT,
+    error::CaptureError,
+    mode_effects::apply_mode_effects,
+    overlay::saved_toast_overlay::show_saved_toast,
+    sound::{SoundEffect, play_sound},
+    strings,
     system_utils::*,
     ui::{
+        capture_history_handler::add_capture_history,
         dialog_handler::{bring_dialog_to_back, bring_dialog_to_front},
         folder_manager::*,
-        input_control_handlers::update_input_control_states,
+        thumbnail_strip_handler::add_capture_thumbnail_from_image,
     },
+    window_capture::capture_window_at_point_with_counter,
 };
 
 /**
@@ -77,56 +133,74 @@ use crate::{
  *
  * # 状態遷移
  * - **OFF -> ON**:
+ *   0. エリア選択モード中の場合は、2つのモードが同時に有効にならないよう、先に `cancel_area_select_mode` でエリア選択を終了します。
  *   1. 前提条件（エリア選択、自動クリック設定）を検証します。
  *   2. 検証に失敗した場合、エラーメッセージを表示して中断します。
- *   3. `AppState` の `is_capture_mode` を `true` に設定します。
- *   4. マウスとキーボードのフックをインストールし、`capturing_overlay` を表示します。
+ *   3. `AppState` のモードを `AppMode::Capturing` に遷移し、`warm_up_capture` で暖機運転を行います。
+ *   4. 遷移が返した副作用（フックのインストール、`capturing_overlay` の表示、UIの更新）を実行します。
+ *      オーバーレイを表示できない場合は、`exit_capture` の副作用で元に戻してモードを開始しません。
  *   5. メインダイアログを最小化します。
  *
  * - **ON -> OFF**:
- *   1. `AppState` の `is_capture_mode` を `false` に設定します。
- *   2. フックをアンインストールし、`capturing_overlay` を非表示にします。
- *   3. 実行中の自動クリック処理があれば停止させます。
+ *   1. `AppState` のモードを `AppMode::Idle` に戻します。
+ *   2. 遷移が返した副作用（フックのアンインストール、`capturing_overlay` の非表示、UIの更新）を実行します。
+ *   3. 実行中の自動クリック処理があれば停止させます（フックを先に外し、ワーカーのフック待ちを避ける）。
  *   4. メインダイアログを復元し、最前面に表示します。
  *
- * どちらの場合でも、最後に `update_input_control_states` を呼び出してUIの状態を更新します。
+ * フック・オーバーレイ・UIコントロールの処理は、`AppMode` の遷移が返す `ModeEffect` を
+ * `apply_mode_effects` で実行します。
  */
 pub fn toggle_capture_mode() {
     let app_state = AppState::get_app_state_mut();
-    let is_capture_mode = app_state.is_capture_mode;
+    let is_capture_mode = app_state.is_capture_mode();
 
     if is_capture_mode {
         // キャプチャモードを終了する
-        app_state.is_capture_mode = false;
-
-        // キーボードとマウスフック停止
-        uninstall_hooks();
-
-        // キャプチャモードオーバーレイを非表示
-        if let Some(overlay) = app_state.capturing_overlay.as_mut() {
-            overlay.hide_overlay();
+        // （マウスフックの停止、キャプチャモードオーバーレイの非表示、UIコントロールの更新）
+        if let Some(effects) = app_state.mode.exit_capture() {
+            let _ = apply_mode_effects(effects);
         }
 
         // メインダイアログを最前面に表示
         bring_dialog_to_front();
 
+        // このモード中のキャプチャ所要時間の集計を出力（TimingDebug有効時のみ）
+        dump_capture_timing_stats();
+
         // 実行中の自動クリック処理があれば停止させる
         if app_state.auto_clicker.is_running() {
             app_state.auto_clicker.stop();
         }
-        app_log("画面キャプチャモードを終了しました");
+        app_log(strings::capture_mode_ended());
+
+        // このモード中に保存した画像があれば、保存先フォルダーを自動でPDFに変換する
+        request_auto_pdf_export();
     } else {
+        // PDF変換（キャプチャモード終了時の自動変換を含む）の実行中は、新しいキャプチャを開始しない
+        if app_state.is_exporting_to_pdf() {
+            app_log(strings::capture_blocked_by_pdf_export());
+            return;
+        }
+
+        // エリア選択モード中の場合は、先にエリア選択を終了する
+        // （両モードのフック・オーバーレイが同時に動作し、一方の終了で他方のフックが外れるのを防ぐ）
+        if app_state.is_area_select_mode() {
+            app_log(strings::area_select_cancelled_for_capture());
+            cancel_area_select_mode();
+        }
+
         // キャプチャモードを開始する（開始前に前提条件をチェック）
-        let has_area = app_state.selected_area.is_some();
+        // ウィンドウ単体キャプチャではクリック位置のウィンドウが対象のため、エリア選択は不要
+        let has_area = app_state.selected_area.is_some() || app_state.window_capture_mode;
 
         if !has_area {
             // 【エラーハンドリング：エリア未選択時の親切な案内】
-            app_log("❌ 先にエリア選択を行ってください");
+            app_log(strings::area_not_selected_log());
 
             // ユーザーフレンドリーなエラーメッセージ表示
             show_message_box(
-                "先にエリア選択を行ってください。\n\n操作手順:\n1. エリア選択ボタンをクリック\n2. 画面上でドラッグして範囲を選択\n3. キャプチャ開始ボタンをクリック",
-                "エラー - エリア未選択",
+                strings::area_not_selected_message(),
+                strings::area_not_selected_title(),
                 MB_OK | MB_ICONWARNING,
             );
             return;
@@ -135,297 +209,703 @@ pub fn toggle_capture_mode() {
         // 回数の値が0の場合、自動クリック機能を無効化
         if app_state.auto_clicker.is_enabled() && app_state.auto_clicker.get_max_count() == 0 {
             show_message_box(
-                "回数の値が0、もしくは未設定です。1以上の値を設定してください。",
-                "自動クリックエラー",
+                strings::auto_click_count_zero_message(),
+                strings::auto_click_error_title(),
                 MB_OK | MB_ICONWARNING,
             );
             return;
         }
 
-        // 確認ダイアログを表示
+        // 確認ダイアログを表示（動作モードに応じて開始方法・動作の説明を切り替え）
         if app_state.auto_clicker.is_enabled() {
-            let result = show_message_box(
-                "自動クリックモードでキャプチャを開始します。\n\n\
-                【開始方法】\n\
-                キャプチャしたい場所（例：「次へ」ボタン）を1回クリックしてください。\n\n\
-                【動作】\n\
-                設定された回数・間隔で、同じ場所へのクリックとキャプチャを自動で繰り返します。\n\n\
-                【停止方法】\n\
-                いつでも ESC キーで中断できます。",
-                "自動クリックモードの開始確認",
-                MB_OKCANCEL | MB_ICONQUESTION,
-            );
+            let (message, title) = strings::auto_click_confirm(app_state.auto_clicker.get_mode());
+            let mut message = message.to_string();
+
+            // 推定使用量が保存先の空き容量を超える場合は、途中で保存できなくなるため開始しない
+            if let Some((estimated, free)) = estimate_auto_click_disk_usage() {
+                if estimated > free {
+                    app_log(&strings::auto_click_disk_insufficient_log(estimated, free));
+                    show_message_box(
+                        &strings::auto_click_disk_insufficient_message(estimated, free),
+                        strings::auto_click_error_title(),
+                        MB_OK | MB_ICONERROR,
+                    );
+                    return;
+                }
+                message.push_str("\n\n");
+                message.push_str(&strings::auto_click_disk_usage(estimated, free));
+            }
+
+            let result = show_message_box(&message, title, MB_OKCANCEL | MB_ICONQUESTION);
 
             if result.0 != IDOK.0 {
-                app_log("自動クリックモードがキャンセルされました。");
+                app_log(strings::auto_click_cancelled());
                 return;
             }
         }
 
         // 前提条件をクリアしたので、モードを開始
-        app_state.is_capture_mode = true;
-
-        // キーボードとマウスフック開始
-        install_hooks();
+        let effects = app_state.mode.enter_capture();
+        debug_assert!(
+            effects.is_some(),
+            "通常モード以外からキャプチャモードを開始しようとしました: {:?}",
+            app_state.mode
+        );
+        let Some(effects) = effects else {
+            return;
+        };
 
-        // キャプチャモードオーバーレイを表示
-        if let Some(overlay) = app_state.capturing_overlay.as_mut() {
-            if let Err(e) = overlay.show_overlay() {
-                eprintln!("❌ キャプチャモードオーバーレイの表示に失敗: {:?}", e);
-                // TODO: エラー時はモードを開始せずに終了するべき
+        // 変化の検出は、このモードで最初に保存した画像から比較する（最初の1枚は必ず保存）
+        app_state.previous_capture_frame = None;
+        // 経過時間スタンプは、このモードで最初に保存した画像から数える
+        app_state.last_capture_started_at = None;
+        // PDF自動変換の要否は、このモードで保存した枚数で判断する
+        app_state.capture_session_saved_count = 0;
+
+        // 最初のキャプチャだけ遅くならないよう、フック・オーバーレイの前に暖機運転を行う
+        warm_up_capture(app_state);
+
+        // キーボードとマウスフック開始、キャプチャモードオーバーレイを表示、UIコントロールの状態を更新
+        // 表示できない場合はモード中であることが分からなくなるため、フックを外してモードを開始しない
+        if let Err(e) = apply_mode_effects(effects) {
+            if let Some(effects) = app_state.mode.exit_capture() {
+                let _ = apply_mode_effects(effects);
             }
+            app_log(&strings::capture_mode_overlay_failed(&e));
+            return;
         }
 
         // メインダイアログを最背面に表示
         bring_dialog_to_back();
 
-        app_log("画面キャプチャモードを開始しました (エスケープキーでキャプチャ終了)");
+        app_log(strings::capture_mode_started());
+
+        // キャプチャのみモードはクリックを待たずに自動キャプチャを開始する
+        if app_state.auto_clicker.is_enabled()
+            && app_state.auto_clicker.get_mode() == AutoClickMode::CaptureOnly
+        {
+            let overlay_hwnd = app_state.capturing_overlay_hwnd();
+            let _ = app_state
+                .auto_clicker
+                .start(app_state.current_mouse_pos(), overlay_hwnd);
+        }
+    };
+}
+
+/**
+ * キャプチャモード終了時のPDF自動変換を要求する
+ *
+ * 「キャプチャ終了時に自動でPDF変換」（`auto_pdf_export`）が有効で、このモード中に1枚以上
+ * ファイルへ保存した場合に、メインダイアログへ `WM_AUTO_PDF_EXPORT` を送ります。
+ * ESCキーで終了した場合はキーボードフックの処理中のため、変換はメッセージで後から実行します
+ * （`ui/pdf_export_button_handler.rs` の `handle_auto_pdf_export`）。
+ */
+fn request_auto_pdf_export() {
+    let app_state = AppState::get_app_state_ref();
+    if !app_state.auto_pdf_export {
+        return;
+    }
+    if app_state.capture_session_saved_count == 0 {
+        app_log(strings::auto_pdf_export_skipped());
+        return;
+    }
+    if let Some(hwnd) = app_state.dialog_hwnd {
+        unsafe {
+            if let Err(e) = PostMessageW(Some(*hwnd), WM_AUTO_PDF_EXPORT, WPARAM(0), LPARAM(0)) {
+                app_log(&strings::post_message_failed(&e));
+            }
+        }
+    }
+}
+
+/**
+ * キャプチャモード開始時の暖機運転（保存しない1回分のキャプチャ）
+ *
+ * 起動後の最初のキャプチャは、DCの作成・メモリ確保・エンコーダーの初期化で時間がかかり、
+ * 自動クリックの最初のクリックでは対象アプリが先に進んでしまうことがあります。
+ * 選択領域を取得・縮小・エンコードして結果を破棄し、これらの初期化を先に済ませます（連番は更新しない）。
+ *
+ * レジストリ `CaptureWarmUp` が0の場合、選択領域が `WARM_UP_MAX_PIXELS` を超える場合
+ * （オーバーレイの表示が遅れるため）は行いません。失敗してもキャプチャモードはそのまま開始します。
+ */
+fn warm_up_capture(app_state: &AppState) {
+    if !app_state.capture_warm_up {
+        return;
+    }
+    // ウィンドウ単体キャプチャでエリア未選択の場合は対象がないため省略
+    let Ok(pipeline) = CapturePipeline::from_app_state(app_state) else {
+        return;
     };
-    // UIコントロールの状態を更新
-    update_input_control_states();
+
+    let (width, height) = pipeline.full_size();
+    if width as i64 * height as i64 > WARM_UP_MAX_PIXELS {
+        app_log(&strings::capture_warm_up_skipped(width, height));
+        return;
+    }
+
+    let started = Instant::now();
+    match pipeline.warm_up() {
+        Ok(()) => app_log(&strings::capture_warm_up_done(
+            started.elapsed().as_millis(),
+        )),
+        Err(e) => app_log(&strings::capture_warm_up_failed(&e)),
+    }
 }
 
 /**
- * 選択された画面領域をキャプチャし、連番ファイル名でJPEGとして保存する
+ * 選択された画面領域をキャプチャし、連番ファイル名で保存する（JPEG / WebP / AVIF / BMP）
  *
- * # パフォーマンス最適化
- * - `BitBlt` APIによる高速なピクセルデータコピー。
- * - `StretchBlt` APIと `HALFTONE` モードによる高品質な画像縮小。
- * - メモリDC（オフスクリーンバッファ）を使用し、GPUアクセラレーションを活用。
+ * 処理の各段階は `capture_pipeline.rs` の `CapturePipeline` が担当し、この関数は段階を順に呼び出します。
  *
  * 【戻り値】
- * * `Ok(())` - 成功した場合。
- * * `Err(Box<dyn std::error::Error>)` - 失敗した場合、エラー情報。
+ * * `Ok(CaptureOutcome::Saved)` - 保存（または出力先へ送信）した場合。
+ * * `Ok(CaptureOutcome::Unchanged)` - 変化の検出が有効で、前回保存した画像から変化がなかったため保存しなかった場合。
+ * * `Err(CaptureError)` - 失敗した場合、エラーの種類（エリア未選択 / GDI失敗 / I/O失敗 / エンコード失敗など）。
  *
  * 【処理フロー】
- * 1. `AppState` から選択領域 (`selected_area`) を取得します。
- * 2. `GetDC` で画面全体のデバイスコンテキストを取得し、`CreateCompatibleDC` でメモリDCを作成します。
- * 3. `BitBlt` を使用して、画面の指定領域をメモリ上のビットマップにコピーします。
- * 4. `StretchBlt` を使用して、ユーザー設定のスケールに合わせて画像をリサイズします。
- * 5. `GetDIBits` でリサイズされたビットマップからピクセルデータを抽出します。
- * 6. 抽出したBGR形式のピクセルデータをRGB形式に変換し、`image` クレートの `ImageBuffer` に格納します。
- * 7. `JpegEncoder` を使用して、ユーザー設定の品質でJPEGにエンコードし、連番ファイル名で保存します。
- * 8. 使用したGDIリソースを全て解放します。
+ * 1. `CapturePipeline::from_app_state` で選択領域・保存サイズ・保存形式などを取得します。
+ * 2. `grab`: `BitBlt` + `GetDIBits` で原寸の画像を取得します（必要に応じてDesktop Duplicationで取得し直す）。
+ * 3. `postprocess`: マスク領域を黒で塗りつぶします。
+ *    `detect_change`: 変化の検出が有効な場合、前回保存した画像から変化がなければここで終了します。
+ * 4. `scale`: 保存サイズへ縮小し、2つ目の領域が選択済みの場合は1枚に結合します。
+ *    変化した範囲のみ保存する設定の場合は、縮小後に変化した範囲を切り出します（`crop_to_change`）。
+ * 5. `encode`: 保存形式・品質でエンコードし、撮影情報のEXIFを埋め込みます。
+ * 6. `persist`: 連番ファイルを排他的に作成して書き込みます。
+ * 7. ログ・連番・サムネイル・履歴を更新し、シャッター音と保存完了トーストで通知します。
  */
+pub fn capture_screen_area_with_counter() -> Result<CaptureOutcome, CaptureError> {
+    capture_with_area_override(None)
+}
 
-pub fn capture_screen_area_with_counter() -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        app_log("⌛ スクリーンキャプチャ中です...");
-
-        let app_state = AppState::get_app_state_mut();
-
-        // 選択された領域を取得
-        let left;
-        let top;
-        let right;
-        let bottom;
-
-        match app_state.selected_area {
-            Some(selected_area) => {
-                left = selected_area.left;
-                top = selected_area.top;
-                right = selected_area.right;
-                bottom = selected_area.bottom;
-            }
-            None => {
-                return Err("❌ キャプチャエリアが選択されていません".into());
-            }
+/// 選択領域（`area_override` が `Some` の場合はその領域を1回だけ）をキャプチャして保存する
+///
+/// `area_override` を指定しても `AppState` の選択領域は変更しません（クイックキャプチャ用）。
+fn capture_with_area_override(area_override: Option<RECT>) -> Result<CaptureOutcome, CaptureError> {
+    app_log(strings::capturing());
+    let mut timer = CaptureTimer::start();
+    let app_state = AppState::get_app_state_ref();
+    let pipeline = match area_override {
+        Some(area) => CapturePipeline::for_area(app_state, area),
+        None => CapturePipeline::from_app_state(app_state)?,
+    };
+
+    // キャプチャ処理中はオーバーレイアイコンを「処理中」に切り替え（エラー時も待機中に戻す）
+    set_capture_overlay_processing_state(true);
+    let result = run_capture_pipeline(&pipeline, &mut timer);
+    set_capture_overlay_processing_state(false);
+    let Some(file_name) = result? else {
+        return Ok(CaptureOutcome::Unchanged);
+    };
+
+    // シャッター音（非同期再生、失敗してもキャプチャ結果には影響しない）
+    play_sound(SoundEffect::Shutter);
+
+    // 保存完了トーストを表示（フェードアウトはダイアログのWM_TIMERで駆動）
+    show_saved_toast(&file_name, pipeline.area());
+    Ok(CaptureOutcome::Saved)
+}
+
+/// `capture_screen_area_with_counter` の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureOutcome {
+    /// 保存（または出力先へ送信）した
+    Saved,
+    /// 前回保存した画像から変化がないため保存しなかった（変化の検出が有効な場合のみ）
+    Unchanged,
+}
+
+/// キャプチャパイプラインの各段階を実行し、保存結果をログ・連番・サムネイル・履歴に反映する
+///
+/// # 戻り値
+/// * `Ok(Some(file_name))` - 保存したファイル名（`0001.jpg` など）。出力先へ送信した場合は出力先のラベル（`pipe:名前` など）。
+/// * `Ok(None)` - 前回保存した画像から変化がないため保存しなかった。
+fn run_capture_pipeline(
+    pipeline: &CapturePipeline,
+    timer: &mut CaptureTimer,
+) -> Result<Option<String>, CaptureError> {
+    let mut frame = pipeline.grab(timer)?;
+    pipeline.postprocess(&mut frame);
+
+    // 前回保存した画像から変化がなければ保存しない（サムネイルのビットマップは破棄時に解放）
+    let previous = AppState::get_app_state_ref()
+        .previous_capture_frame
+        .as_ref();
+    let change = pipeline.detect_change(&frame.image, previous);
+    if change == FrameChange::Unchanged {
+        app_log(strings::capture_unchanged_skipped());
+        return Ok(None);
+    }
+    let reference = pipeline.detects_changes().then(|| frame.image.clone());
+    let image = scale_frame(pipeline, frame, change);
+    timer.lap(CapturePhase::Scale);
+
+    // 保存に失敗しても「再保存」で書き込めるよう、先にメモリ上でエンコードする
+    let encoded = pipeline.encode(&image)?;
+    timer.lap(CapturePhase::Encode);
+    let saved = pipeline.persist(encoded);
+    timer.lap(CapturePhase::Write);
+    let saved = saved?;
+
+    remember_saved_capture(pipeline, reference);
+    let file_name = log_saved_capture(pipeline, &saved, &image);
+    // 段階ごとの所要時間（TimingDebug有効時のみ出力）
+    finish_capture_timing(timer);
+    record_saved_capture(saved, &image);
+    Ok(Some(file_name))
+}
+
+/// 取得した画像を保存サイズへ縮小・結合し、変化した範囲のみ保存する場合は切り出す
+fn scale_frame(pipeline: &CapturePipeline, frame: RawFrame, change: FrameChange) -> RgbImage {
+    let raw_size = frame.image.dimensions();
+    let RawFrame { image, secondary } = frame;
+    let image = pipeline.scale(image, secondary);
+    let FrameChange::Crop(region) = change else {
+        return image;
+    };
+    let image = pipeline.crop_to_change(image, raw_size, region);
+    app_log(&strings::capture_cropped_to_change(
+        image.width(),
+        image.height(),
+    ));
+    image
+}
+
+/// 保存に成功したキャプチャを、次回の変化の検出と経過時間スタンプの基準にする
+///
+/// # 引数
+/// * `reference` - 次回の変化の検出で比較する原寸の画像（変化の検出が無効な場合は `None`）
+fn remember_saved_capture(pipeline: &CapturePipeline, reference: Option<RgbImage>) {
+    let app_state = AppState::get_app_state_mut();
+    // 次回の変化の検出は、保存に成功した画像と比較する
+    if reference.is_some() {
+        app_state.previous_capture_frame = reference;
+    }
+    // 次回の経過時間スタンプは、保存に成功したキャプチャの取得開始から数える
+    app_state.last_capture_started_at = Some(pipeline.started_at());
+}
+
+/// 保存結果をログに出力する
+///
+/// # 戻り値
+/// 保存したファイル名（`0001.jpg` など）。出力先へ送信した場合は出力先のラベル（`pipe:名前` など）。
+fn log_saved_capture(
+    pipeline: &CapturePipeline,
+    saved: &CaptureDestination,
+    image: &RgbImage,
+) -> String {
+    let counter = match saved {
+        CaptureDestination::File { counter, .. } => *counter,
+        CaptureDestination::Sink { label, bytes } => {
+            app_log(&strings::capture_sent_to_sink(
+                label,
+                *bytes,
+                image.width(),
+                image.height(),
+            ));
+            return label.clone();
         }
+    };
 
-        // キャプチャ処理開始時にオーバーレイアイコンを「処理中」に切り替え
-        set_capture_overlay_processing_state(true);
-
-        // デバイスコンテキストの準備
-        let screen_dc = GetDC(None);
-        let memory_dc = CreateCompatibleDC(Some(screen_dc));
-
-        // キャプチャ領域のサイズ計算
-        let width = (right - left).abs();
-        let height = (bottom - top).abs();
-
-        // ユーザー設定のスケール値に基づいて、リサイズ後のサイズを計算
-        let scale_factor = (app_state.capture_scale_factor as f32) / 100.0;
-        let scaled_width = ((width as f32) * scale_factor) as i32;
-        let scaled_height = ((height as f32) * scale_factor) as i32;
-
-        // 原寸サイズのビットマップを作成し、画面の指定領域をコピー
-        let hbitmap = CreateCompatibleBitmap(screen_dc, width, height);
-        let old_bitmap = SelectObject(memory_dc, hbitmap.into());
-
-        // キャプチャの瞬間だけオーバーレイを非表示にし、BitBltを実行後、再表示する
-        if let Some(overlay) = app_state.capturing_overlay.as_mut() {
-            overlay.hide_overlay(); // キャプチャアイコンを一時的に非表示
-
-            let _ = BitBlt(
-                memory_dc, // コピー先（メモリDC）
-                0,
-                0, // コピー先座標
-                width,
-                height,          // コピーサイズ
-                Some(screen_dc), // コピー元（画面DC）
-                left,
-                top,     // コピー元座標
-                SRCCOPY, // コピーモード（上書き）
-            );
+    let app_state = AppState::get_app_state_ref();
+    let file_name = capture_file_name(counter, pipeline.format().extension());
+    let scale_label = if app_state.capture_fit_to_width {
+        strings::fit_width_label(app_state.capture_target_width)
+    } else {
+        format!("{}%", app_state.capture_scale_factor)
+    };
+    app_log(&strings::image_saved(
+        &file_name,
+        image.width(),
+        image.height(),
+        &scale_label,
+        app_state.jpeg_quality,
+    ));
+    file_name
+}
 
-            if let Err(e) = overlay.show_overlay() {
-                return Err(format!("❌ キャプチャアイコンの再表示に失敗: {}", e).into());
-            }
+/// 連番ファイルとして保存した結果を、連番・サムネイル・キャプチャ履歴に反映する
+///
+/// 出力先へ送信した場合はファイルが存在しないため、何も更新しません。
+fn record_saved_capture(saved: CaptureDestination, image: &RgbImage) {
+    let CaptureDestination::File { counter, path } = saved else {
+        return;
+    };
+    // 成功時のみ連番カウンタを更新（他インスタンスの保存で番号が飛んだ場合も追従）
+    advance_capture_file_counter(counter);
+
+    // 保存した画像からサムネイルを作成してストリップに追加（ファイルは読み直さない、古いサムネイルは自動破棄）
+    add_capture_thumbnail_from_image(image, path.clone());
+
+    // キャプチャ履歴に追加（履歴ダイアログでの削除・並べ替え、PDFのページ順に使用）
+    add_capture_history(path);
+}
+
+/**
+ * 保存に成功した連番の次の番号を `capture_file_counter` に設定する
+ *
+ * 連番が `CAPTURE_COUNTER_WARNING_THRESHOLD` に達した時に残り枚数を警告し、
+ * 9999を超えた時に5桁の連番で保存を続けることをログに出力します。
+ * 5桁の連番もPDF・ZIP変換では数値順（`sort_capture_files`）に並ぶため、ページ順は崩れません。
+ * 他インスタンスの保存で番号が飛んだ場合も、境界を越えた時に1回だけ出力します。
+ * キャプチャモード終了時のPDF自動変換のため、このモードで保存した枚数も加算します。
+ *
+ * # 引数
+ * * `saved_counter` - 今回保存したファイルの連番
+ */
+pub fn advance_capture_file_counter(saved_counter: u32) {
+    let app_state = AppState::get_app_state_mut();
+    match capture_counter_milestone(app_state.capture_file_counter, saved_counter) {
+        Some(CaptureCounterMilestone::Widened) => {
+            app_log(&strings::capture_counter_widened(saved_counter));
+        }
+        Some(CaptureCounterMilestone::NearLimit { remaining }) => {
+            app_log(&strings::capture_counter_near_limit(
+                saved_counter,
+                remaining,
+            ));
         }
+        None => {}
+    }
 
-        // スケーリング用のデバイスコンテキストとビットマップを準備
-        let scaled_dc = CreateCompatibleDC(Some(screen_dc));
-        let hbitmap_scaled = CreateCompatibleBitmap(screen_dc, scaled_width, scaled_height);
-        let old_bitmap_scaled = SelectObject(scaled_dc, hbitmap_scaled.into());
-
-        // 高品質な縮小処理を行うためにHALFTONEモードを設定
-        let _ = SetStretchBltMode(scaled_dc, HALFTONE);
-        let _ = SetBrushOrgEx(scaled_dc, 0, 0, None);
-
-        // `StretchBlt` を使用して、原寸ビットマップを縮小ビットマップにコピー
-        let _ = StretchBlt(
-            scaled_dc,
-            0,
-            0,
-            scaled_width,
-            scaled_height,
-            Some(memory_dc),
-            0,
-            0,
-            width,
-            height,  // 縮小元サイズ
-            SRCCOPY, // 転送モード
-        );
+    app_state.capture_file_counter = saved_counter + 1;
+    app_state.capture_session_saved_count += 1;
+}
 
-        // ピクセルデータ抽出の準備
-        let bytes_per_pixel = 3; // RGB 24bit形式
-        let row_size = ((scaled_width * bytes_per_pixel + 3) / 4) * 4; // Windows 4バイト境界調整
-        let mut pixel_data = vec![0u8; (row_size * scaled_height) as usize];
-
-        // BITMAPINFO構造体の設定（GetDIBits API用）
-        let mut bitmap_info = BITMAPINFO {
-            bmiHeader: BITMAPINFOHEADER {
-                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                biWidth: scaled_width,
-                biHeight: -scaled_height, // 負値で上下反転防止（トップダウン形式）
-                biPlanes: 1,
-                biBitCount: 24,          // RGB 24bit カラー深度
-                biCompression: BI_RGB.0, // 無圧縮RGB
-                biSizeImage: 0,          // BI_RGB時は0で可
-                biXPelsPerMeter: 0,      // 解像度情報（未使用）
-                biYPelsPerMeter: 0,
-                biClrUsed: 0,      // フルカラー使用
-                biClrImportant: 0, // 全色重要
-            },
-            bmiColors: [RGBQUAD::default(); 1], // RGB形式では未使用
-        };
+/// 保存前に確保しておく空き容量（ファイルシステムのメタデータや他アプリの書き込み用）
+const DISK_SPACE_RESERVE_BYTES: u64 = 16 * 1024 * 1024;
 
-        // `GetDIBits` を使用して、縮小ビットマップからピクセルデータを抽出
-        let result = GetDIBits(
-            scaled_dc,                               // ソースDC
-            hbitmap_scaled,                          // ソースビットマップ
-            0,                                       // 開始スキャンライン
-            scaled_height as u32,                    // スキャンライン数
-            Some(pixel_data.as_mut_ptr() as *mut _), // 出力バッファ
-            &mut bitmap_info,                        // ビットマップ情報
-            DIB_RGB_COLORS,                          // カラーテーブル形式
+/**
+ * 自動クリックで保存する画像の合計サイズを見積もり、保存先の空き容量と合わせて返す
+ *
+ * 選択領域のサイズ・スケール・保存サイズ上限から1枚あたりの出力サイズを求め、
+ * 保存形式・品質ごとの経験値（`estimate_encoded_size`）で1枚のサイズを見積もって回数を掛けます。
+ *
+ * # 戻り値
+ * * `Some((推定使用量, 空き容量))` - バイト単位
+ * * `None` - ウィンドウ単体キャプチャ（ウィンドウサイズが事前に分からない）、
+ *   エリア未選択、または空き容量を取得できない場合
+ */
+fn estimate_auto_click_disk_usage() -> Option<(u64, u64)> {
+    let app_state = AppState::get_app_state_ref();
+    if app_state.window_capture_mode {
+        return None;
+    }
+    let area = app_state.selected_area?;
+    let free = get_disk_free_space(std::path::Path::new(&get_save_dir_path()))?;
+
+    let width = (area.right - area.left).abs();
+    let height = (area.bottom - area.top).abs();
+    let (scaled_width, scaled_height) = compute_scaled_size(
+        width,
+        height,
+        app_state.capture_scale_factor,
+        app_state
+            .capture_fit_to_width
+            .then_some(app_state.capture_target_width),
+    );
+    let (scaled_width, scaled_height) = clamp_to_max_size(
+        scaled_width,
+        scaled_height,
+        Some(app_state.capture_max_width),
+        Some(app_state.capture_max_height),
+    );
+    let estimate = |width: i32, height: i32| {
+        estimate_encoded_size(
+            width.max(1) as u32,
+            height.max(1) as u32,
+            app_state.capture_format,
+            app_state.jpeg_quality,
+            app_state.jpeg_high_fidelity_text,
+        )
+    };
+
+    // デュアル選択時は、2つ目の領域も同じ縮小率で縮小した分を加える
+    let mut per_file = estimate(scaled_width, scaled_height);
+    if let Some(area_2) = app_state.selected_area_2 {
+        let ratio = scaled_width as f64 / width.max(1) as f64;
+        per_file += estimate(
+            ((area_2.right - area_2.left).abs() as f64 * ratio).round() as i32,
+            ((area_2.bottom - area_2.top).abs() as f64 * ratio).round() as i32,
         );
+    }
 
-        // Windows GDIリソースを解放
-        let _ = SelectObject(memory_dc, old_bitmap); // 元のビットマップを復元
-        let _ = SelectObject(scaled_dc, old_bitmap_scaled); // 元のビットマップを復元
-        let _ = DeleteObject(hbitmap.into()); // 原寸ビットマップ削除
-        let _ = DeleteObject(hbitmap_scaled.into()); // 縮小ビットマップ削除
-        let _ = DeleteDC(memory_dc); // メモリDC削除
-        let _ = DeleteDC(scaled_dc); // スケーリングDC削除
-        let _ = ReleaseDC(None, screen_dc); // 画面DC解放
-
-        // ピクセルデータ取得成功確認
-        if result == 0 {
-            // エラー時にもアイコンを待機中に戻す
-            set_capture_overlay_processing_state(false);
-            return Err("ビットマップデータの取得に失敗".into());
-        }
+    let estimated = per_file * app_state.auto_clicker.get_max_count() as u64;
+    Some((estimated, free))
+}
 
-        // `image` クレート用の `ImageBuffer` を作成し、ピクセルデータを変換
-        let mut img_buffer =
-            ImageBuffer::<Rgb<u8>, Vec<u8>>::new(scaled_width as u32, scaled_height as u32);
+/**
+ * 保存先に指定サイズのファイルを書き込める空き容量があるかを確認する
+ *
+ * 書き込み途中で容量が尽きて壊れたファイルが残るのを防ぐため、保存の直前に呼び出します。
+ * 空き容量を取得できない場合（ネットワークドライブなど）は確認せずに保存を試みます。
+ *
+ * # 引数
+ * * `save_dir` - 保存先フォルダー
+ * * `data_len` - 書き込むバイト数
+ *
+ * # 戻り値
+ * * `Err(CaptureError::DiskFull)` - 空き容量が `data_len` と予備分の合計に満たない場合
+ */
+pub fn ensure_disk_space(save_dir: &std::path::Path, data_len: u64) -> Result<(), CaptureError> {
+    let Some(available) = get_disk_free_space(save_dir) else {
+        return Ok(());
+    };
+    let required = data_len + DISK_SPACE_RESERVE_BYTES;
+    if available < required {
+        return Err(CaptureError::DiskFull {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
 
-        // Windows GDIのBGR形式から、標準的なRGB形式にピクセル単位で変換
-        for y in 0..scaled_height {
-            for x in 0..scaled_width {
-                let src_idx = (y * row_size + x * bytes_per_pixel) as usize;
+/// 保存に失敗したキャプチャ（エンコード済み）
+///
+/// 画面は既に変わっているため再キャプチャせず、「再保存」ボタンでこのデータを書き込みます。
+pub struct FailedCapture {
+    /// エンコード済みの画像データ
+    pub encoded: Vec<u8>,
+    /// 保存時の拡張子（保存形式を変更しても、エンコードした形式で保存する）
+    pub extension: &'static str,
+}
 
-                // 配列境界チェック（安全性確保）
-                if src_idx + 2 < pixel_data.len() {
-                    // Windows GDI はBGR順なのでRGB順に変換
-                    let b = pixel_data[src_idx]; // Blue
-                    let g = pixel_data[src_idx + 1]; // Green  
-                    let r = pixel_data[src_idx + 2]; // Red
+impl std::fmt::Debug for FailedCapture {
+    // 画像データそのものは出力せず、サイズのみを表示する
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FailedCapture")
+            .field("encoded_len", &self.encoded.len())
+            .field("extension", &self.extension)
+            .finish()
+    }
+}
 
-                    img_buffer.put_pixel(x as u32, y as u32, Rgb([r, g, b]));
-                }
-            }
-        }
+/// 保存先ディレクトリを取得する（ユーザー指定フォルダー優先、未指定時は自動検出フォルダー）
+///
+/// 保存先フォルダーのテンプレート（`SaveFolderTemplate`）が設定されている場合は、現在の日付で展開したフォルダーを返します。
+/// 展開したパスがWindowsで使用できない場合はログを出力し、自動検出フォルダー（ピクチャ）を返します。
+/// フォルダーはここでは作成しません（保存時に `create_dir_all` で作成）。
+pub fn get_save_dir_path() -> String {
+    let app_state = AppState::get_app_state_ref();
+    if let Some(template) = app_state.save_folder_template.as_deref() {
+        let now = unsafe { GetLocalTime() };
+        return expand_save_folder_template(template, now.wYear, now.wMonth, now.wDay)
+            .unwrap_or_else(|| {
+                app_log(&strings::save_folder_template_invalid(template));
+                get_pictures_folder()
+            });
+    }
 
-        // 保存先ディレクトリを決定
-        let save_dir_path: String = {
-            if let Some(selected_path) = app_state.selected_folder_path.as_ref() {
-                selected_path.clone() // ユーザー指定フォルダー優先
-            } else {
-                get_pictures_folder() // 自動検出フォルダー（OneDrive対応）
-            }
-        };
+    match app_state.selected_folder_path.as_ref() {
+        Some(selected_path) => selected_path.clone(), // ユーザー指定フォルダー優先
+        None => get_pictures_folder(),                // 自動検出フォルダー（OneDrive対応）
+    }
+}
 
-        // フォルダが存在しない場合は作成
-        let save_dir = std::path::Path::new(&save_dir_path);
-        if !save_dir.exists() {
-            fs::create_dir_all(save_dir)?; // 親ディレクトリも含めて再帰作成
-        }
+/**
+ * 保存に失敗したキャプチャ（`last_failed_capture`）を再度保存する
+ *
+ * 「再保存」ボタンから呼び出されます。成功した場合は保持していたデータを破棄し、
+ * 連番カウンタを更新します。失敗した場合はデータを保持したままエラーを返します。
+ */
+pub fn retry_failed_capture_save() -> Result<(), CaptureError> {
+    let app_state = AppState::get_app_state_mut();
+    let Some(failed) = app_state.last_failed_capture.as_ref() else {
+        return Ok(());
+    };
 
-        // 連番ファイル名を生成（4桁ゼロパディング）
-        let current_counter = app_state.capture_file_counter;
-        let file_path = save_dir.join(format!("{:04}.jpg", current_counter));
-
-        // JPEGとして保存
-        use image::codecs::jpeg::JpegEncoder;
-        use std::fs::File;
-        use std::io::BufWriter;
-
-        let save_result = (|| -> Result<(), Box<dyn std::error::Error>> {
-            let output_file = File::create(&file_path)?;
-            let mut writer = BufWriter::new(output_file);
-            let encoder = JpegEncoder::new_with_quality(&mut writer, app_state.jpeg_quality);
-            img_buffer.write_with_encoder(encoder)?;
-            Ok(())
-        })();
-
-        match save_result {
-            Ok(()) => {
-                // 成功通知とデバッグ情報出力
-                app_log(&format!(
-                    "✅ 画像保存完了: {:04}.jpg ({}x{}) (scale: {}%, quality: {}%)",
-                    current_counter,
-                    scaled_width,
-                    scaled_height,
-                    app_state.capture_scale_factor,
-                    app_state.jpeg_quality
-                ));
-
-                // 成功時のみ連番カウンタをインクリメント
-                app_state.capture_file_counter += 1;
-
-                // 処理成功時にアイコンを待機中に戻す
-                set_capture_overlay_processing_state(false);
-
-                Ok(()) // 全処理成功
-            }
-            Err(e) => {
-                // ファイル保存エラー時にもアイコンを待機中に戻す
-                set_capture_overlay_processing_state(false);
-                Err(e)
-            }
+    let save_dir_path = get_save_dir_path();
+    let save_dir = std::path::Path::new(&save_dir_path);
+    if !save_dir.exists() {
+        fs::create_dir_all(save_dir)?;
+    }
+    ensure_disk_space(save_dir, failed.encoded.len() as u64)?;
+
+    let (counter, file_path) = write_capture_file_with_retry(
+        save_dir,
+        failed.extension,
+        app_state.capture_file_counter,
+        &failed.encoded,
+    )?;
+
+    app_log(&strings::image_resaved(counter, failed.extension));
+    add_capture_history(file_path);
+    advance_capture_file_counter(counter);
+    app_state.last_failed_capture = None;
+    play_sound(SoundEffect::Shutter);
+    Ok(())
+}
+
+/**
+ * 自動クリックの「キャプチャのみ」モードのキャプチャ要求（`WM_AUTO_CAPTURE_REQUEST`）を処理する
+ *
+ * ワーカースレッドから送信された要求を受け、メインスレッドでキャプチャを実行します。
+ * ESCキーなどでキャプチャモードが終了した後に届いた要求は無視します。
+ * ウィンドウ単体キャプチャ有効時は、現在のマウス位置のウィンドウを保存します。
+ */
+pub fn handle_auto_capture_request() {
+    let app_state = AppState::get_app_state_ref();
+    if !app_state.is_capture_mode() {
+        return;
+    }
+
+    if app_state.window_capture_mode {
+        let _ = capture_window_at_point_with_counter(app_state.current_mouse_pos());
+    } else if let Err(e) = capture_screen_area_with_counter() {
+        report_capture_error(&e);
+    }
+}
+
+/**
+ * ESCキーによる自動クリックの停止の確認（`WM_ESC_STOP_CONFIRM`）を処理する
+ *
+ * キーボードフックが残り回数の多い実行中のESCで確認中にした後に送信します。
+ * 確認中は自動クリックが一時停止しているため、回答を待つ間にクリック・キャプチャは進みません。
+ * 「はい」（既定のボタンのためEnterでも可、表示中のESCはフックが「はい」を送る）でキャプチャモードを終了し、
+ * 「いいえ」で確認中を解除して再開します。
+ * 回答までに最大実行時間に達するなどしてキャプチャモードが終了していた場合は何もしません。
+ */
+pub fn handle_esc_stop_confirm() {
+    let auto_clicker = &AppState::get_app_state_ref().auto_clicker;
+    // 表示前に2回目のESCで終了済みの場合は、確認中が解除されている
+    if !auto_clicker.is_stop_confirming() {
+        return;
+    }
+
+    let message = strings::esc_stop_confirm_message(
+        auto_clicker.get_progress_count(),
+        auto_clicker.get_max_count(),
+    );
+    // メインダイアログは最小化されているため、最前面に表示する
+    let result = show_message_box(
+        &message,
+        strings::esc_stop_confirm_title(),
+        MB_YESNO | MB_ICONQUESTION | MB_DEFBUTTON1 | MB_SETFOREGROUND | MB_TOPMOST,
+    );
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.auto_clicker.set_stop_confirming(false);
+    if result == IDYES && app_state.is_capture_mode() {
+        toggle_capture_mode();
+    }
+}
+
+/**
+ * カーソル下のウィンドウのクライアント領域をキャプチャする（クイックキャプチャ、既定: Ctrl+Alt+Shift+W）
+ *
+ * ドラッグによるエリア選択を行わずに、ダイアログなどをウィンドウ単位で素早くキャプチャします。
+ * `WM_HOTKEY`（`global_hotkey::handle_hotkey`）から呼び出され、キャプチャモード外でも動作します。
+ *
+ * # 処理フロー
+ * 1. `GetCursorPos` + `WindowFromPoint` でカーソル下のウィンドウを取得し、
+ *    `GetAncestor(GA_ROOT)` でトップレベルウィンドウに変換します（自アプリのダイアログは対象外）。
+ * 2. `GetClientRect` のクライアント領域を `ClientToScreen` でスクリーン座標に変換します。
+ * 3. その領域を1回限りの対象として、通常のキャプチャ処理（縮小・マスク・エンコード・保存）を実行します。
+ *    選択領域（`selected_area` / `selected_area_2`）は変更しません。
+ */
+pub fn capture_window_under_cursor() {
+    let app_state = AppState::get_app_state_ref();
+
+    let mut cursor_pos = POINT::default();
+    if let Err(e) = unsafe { GetCursorPos(&mut cursor_pos) } {
+        app_log(&strings::cursor_pos_failed(&e));
+        return;
+    }
+
+    let target_hwnd = unsafe { GetAncestor(WindowFromPoint(cursor_pos), GA_ROOT) };
+    if target_hwnd.is_invalid() {
+        app_log(strings::no_window_at_cursor());
+        return;
+    }
+    if app_state
+        .dialog_hwnd
+        .is_some_and(|hwnd| *hwnd == target_hwnd)
+    {
+        app_log(strings::quick_capture_own_dialog());
+        return;
+    }
+
+    // クライアント領域をスクリーン座標に変換
+    let mut client_rect = RECT::default();
+    let mut origin = POINT::default();
+    unsafe {
+        if let Err(e) = GetClientRect(target_hwnd, &mut client_rect) {
+            app_log(&strings::window_rect_failed(&e));
+            return;
         }
+        let _ = ClientToScreen(target_hwnd, &mut origin);
+    }
+    let area = RECT {
+        left: origin.x + client_rect.left,
+        top: origin.y + client_rect.top,
+        right: origin.x + client_rect.right,
+        bottom: origin.y + client_rect.bottom,
+    };
+    if area.right <= area.left || area.bottom <= area.top {
+        app_log(strings::empty_client_area());
+        return;
+    }
+
+    // ウィンドウタイトルをログに表示
+    let mut title_buffer = [0u16; 256];
+    let title_len = unsafe { GetWindowTextW(target_hwnd, &mut title_buffer) };
+    let title = String::from_utf16_lossy(&title_buffer[..title_len.max(0) as usize]);
+    app_log(&strings::quick_capture_target(
+        &title,
+        area.left,
+        area.top,
+        area.right - area.left,
+        area.bottom - area.top,
+    ));
+
+    if let Err(e) = capture_with_area_override(Some(area)) {
+        report_capture_error(&e);
+    }
+}
+
+/**
+ * 前回の選択領域をそのまま再キャプチャする（既定: Ctrl+Alt+Shift+R）
+ *
+ * `WM_HOTKEY`（`global_hotkey::handle_hotkey`）から、キャプチャモード・エリア選択モード以外のときのみ呼び出されます。
+ * キャプチャモードに入らず、オーバーレイも表示せずに、保持している `selected_area`
+ * （2つ目の領域が選択済みの場合は結合）を `capture_screen_area_with_counter` で保存します。
+ * 保存形式・スケール・画質は現在の設定に従い、連番カウンタも通常のキャプチャと同様に進みます。
+ * 一度もエリアが選択されていない場合はログを出力するだけで何もしません。
+ */
+pub fn repeat_last_capture() {
+    let Some(area) = AppState::get_app_state_ref().selected_area else {
+        app_log(strings::repeat_capture_no_area());
+        return;
+    };
+    app_log(&strings::repeat_capture_target(
+        area.left,
+        area.top,
+        area.right - area.left,
+        area.bottom - area.top,
+    ));
+
+    if let Err(e) = capture_screen_area_with_counter() {
+        report_capture_error(&e);
+    }
+}
+
+/**
+ * キャプチャエラーをログに出力し、回復しないエラーであれば自動クリックを停止する
+ *
+ * 保存先への書き込み失敗など、次の実行でも回復しないエラー（`should_stop_auto_click`）は
+ * 自動クリックを停止します。GDIの一時的な失敗などは警告のみで継続します。
+ * 停止はフラグのみを立て、スレッドの回収は `WM_AUTO_CLICK_COMPLETE` 受信時に行います。
+ *
+ * # 引数
+ * * `e` - `capture_screen_area_with_counter` が返したエラー
+ */
+pub fn report_capture_error(e: &CaptureError) {
+    let app_state = AppState::get_app_state_ref();
+    if e.should_stop_auto_click() && app_state.auto_clicker.is_running() {
+        app_log(&strings::capture_error_stopping_auto_click(e));
+        app_state.auto_clicker.request_stop();
+    } else {
+        app_log(&format!("⚠️ {}", e));
     }
 }
 
@@ -445,12 +925,9 @@ pub fn capture_screen_area_with_counter() -> Result<(), Box<dyn std::error::Erro
 pub fn set_capture_overlay_processing_state(is_processing: bool) {
     let app_state = AppState::get_app_state_mut();
 
-    // 状態フラグを更新
-    app_state.capture_overlay_is_processing = is_processing;
-
-    // オーバーレイ更新
-    if let Some(overlay) = app_state.capturing_overlay.as_mut() {
-        overlay.refresh_overlay();
+    // 処理中表示を更新してオーバーレイを再描画（キャプチャモード外では変更しない）
+    if let Some(effects) = app_state.mode.set_capture_processing(is_processing) {
+        let _ = apply_mode_effects(effects);
     }
 
     if is_processing {
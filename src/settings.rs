@@ -0,0 +1,267 @@
+/*
+============================================================================
+設定の永続化モジュール (settings.rs)
+============================================================================
+
+【ファイル概要】
+アプリを再起動しても引き継ぐ設定値を、レジストリ（`HKCU\Software\ClickCapture`）に読み書きするモジュール。
+保存先フォルダー（`LastFolder`、REG_SZ）は `ui/folder_manager.rs` が扱い、
+ここでは数値の設定（REG_DWORD）・文字列の設定（REG_SZ）の読み書きと、値の名前を一元管理します。
+
+【主要機能】
+1.  **数値設定の読み込み**: `load_setting_dword`
+    -   値がない・型が異なる場合は `None`（呼び出し側で既定値を使用）
+2.  **文字列設定の読み込み**: `load_setting_string`（空文字列も `None`）
+3.  **数値設定の保存**: `save_setting_dword`
+    -   キーがない場合は作成し、失敗した場合はログを出力して続行（設定は `AppState` に保持したまま）
+4.  **文字列設定の保存**: `save_setting_string`（`save_setting_dword` と同じく、失敗した場合はログのみ）
+
+【技術仕様】
+-   **保存先**: `HKEY_CURRENT_USER\Software\ClickCapture`
+-   **API**: `RegGetValueW`（`RRF_RT_REG_DWORD` / `RRF_RT_REG_SZ`）、`RegSetKeyValueW`（`REG_DWORD` / `REG_SZ`）
+-   **読み込みのタイミング**: `AppState::default()` で各設定の初期値として読み込む
+
+【AI解析用：依存関係】
+-   `app_state.rs`: 起動時に設定値を読み込んで初期値にする
+-   `strings.rs`: 保存に失敗した場合のログ文字列
+-   `ui/folder_manager.rs`: `load_setting_string` / `save_setting_string`（前回の保存先フォルダー・最近使ったフォルダー）
+-   `overlay/capturing_overlay.rs`: `OverlayFontFamily` / `OverlayFontSize`（ラベルのフォント、画面に設定項目なし）
+-   `ui/overlay_settings_handler.rs`, `ui/completion_notify_checkbox_handler.rs`, `ui/pdf_archive_checkbox_handler.rs`,
+    `ui/auto_pdf_export_checkbox_handler.rs`, `ui/elapsed_stamp_checkbox_handler.rs`,
+    `ui/pause_on_user_input_checkbox_handler.rs`, `ui/capture_trigger_combo_handler.rs`:
+    設定変更時に `save_setting_dword` を呼び出す
+-   `auto_click.rs`: `AutoClickStepX` / `AutoClickStepY` / `AutoClickStepCount`（クリック位置の進行、画面に設定項目なし）、
+    `AutoClickTargetCheck`（対象ウィンドウの確認、同上）、`EscConfirmThreshold`（ESCキーでの停止の確認、同上）、
+    `UserInputQuietMs`（ユーザー操作中の一時停止の静止時間、同上）
+-   `screen_capture.rs`: `CaptureWarmUp`（キャプチャモード開始時の暖機運転、画面に設定項目なし）
+-   `hook/mouse.rs`: `CaptureDebounceMs`（手動クリックの連続キャプチャ抑止の間隔、同上）
+-   `capture_pipeline.rs`: `JpegComment`（JPEGに埋め込むコメント、画面に設定項目なし）
+-   `screen_capture.rs`: `SaveFolderTemplate`（日付で展開する保存先フォルダー、画面に設定項目なし）
+-   `global_hotkey.rs`: `QuickWindowCaptureHotkey` / `RepeatLastCaptureHotkey`（クイックキャプチャ・再キャプチャのホットキー、画面に設定項目なし）
+-   `capture_timing.rs`: `TimingDebug`（画面に設定項目がなく、レジストリを直接編集して有効にする）
+-   `export_pdf.rs`: `PdfAutoVersion`（同上。既存のPDFを確認なしで残す）
+ */
+
+use windows::{
+    Win32::{
+        Foundation::ERROR_SUCCESS,
+        System::Registry::{
+            HKEY_CURRENT_USER, REG_DWORD, REG_SZ, RRF_RT_REG_DWORD, RRF_RT_REG_SZ, RegGetValueW,
+            RegSetKeyValueW,
+        },
+    },
+    core::{PCWSTR, w},
+};
+
+use crate::{strings, system_utils::app_log};
+
+/// アプリケーション設定を保存するレジストリキー（HKEY_CURRENT_USER配下）
+pub const SETTINGS_REGISTRY_KEY: PCWSTR = w!("Software\\ClickCapture");
+
+/// キャプチャオーバーレイの表示方法（表示 / 半透明 / 非表示）
+pub const OVERLAY_VISIBILITY_VALUE_NAME: PCWSTR = w!("OverlayVisibility");
+
+/// キャプチャの直前にオーバーレイを隠してから画面を取得するまでの待ち時間（ミリ秒、画面に設定項目のない設定）
+pub const OVERLAY_HIDE_SETTLE_VALUE_NAME: PCWSTR = w!("OverlayHideSettleMs");
+
+/// エリア選択の暗転の不透明度（%）
+pub const DIM_OPACITY_VALUE_NAME: PCWSTR = w!("DimOpacity");
+
+/// エリア選択の境界線の色（ARGB）
+pub const BORDER_COLOR_VALUE_NAME: PCWSTR = w!("BorderColor");
+
+/// エリア選択の境界線の太さ（ピクセル）
+pub const BORDER_WIDTH_VALUE_NAME: PCWSTR = w!("BorderWidth");
+
+/// エリア選択の境界線の白い縁取り（0: なし / 1: あり）
+pub const BORDER_HALO_VALUE_NAME: PCWSTR = w!("BorderHalo");
+
+/// エリア選択の開始時に画面を固定する（0: しない / 1: する）
+pub const FREEZE_SCREEN_VALUE_NAME: PCWSTR = w!("FreezeScreen");
+
+/// 自動クリック・PDF変換の完了を通知する（0: しない / 1: する）
+pub const COMPLETION_NOTIFY_VALUE_NAME: PCWSTR = w!("CompletionNotify");
+
+/// キャプチャの段階ごとの所要時間をログに出力する（0: しない / 1: する、画面に設定項目のない調査用の設定）
+pub const TIMING_DEBUG_VALUE_NAME: PCWSTR = w!("TimingDebug");
+
+/// 前回保存した画像からの変化の検出（0: 毎回保存 / 1: 変化がなければ保存しない /
+/// 2: 変化した範囲のみ保存、画面に設定項目のない設定）
+pub const CHANGE_DETECTION_VALUE_NAME: PCWSTR = w!("ChangeDetection");
+
+/// 自動クリックで1回ごとにクリック位置を進める量（ピクセル、符号付きの値をDWORDとして保存。
+/// 0,0 は同じ位置でクリック、画面に設定項目のない設定）
+pub const AUTO_CLICK_STEP_X_VALUE_NAME: PCWSTR = w!("AutoClickStepX");
+pub const AUTO_CLICK_STEP_Y_VALUE_NAME: PCWSTR = w!("AutoClickStepY");
+
+/// 自動クリックでクリック位置を進める回数の上限（0: 無制限、以降は最後の位置でクリック）
+pub const AUTO_CLICK_STEP_COUNT_VALUE_NAME: PCWSTR = w!("AutoClickStepCount");
+
+/// 自動クリックの前に、開始時にクリック位置にあったウィンドウが残っているかを確認する
+/// （0: 確認しない、実行中に意図的にウィンドウを切り替える手順向け / それ以外: 確認する、既定）
+pub const AUTO_CLICK_TARGET_CHECK_VALUE_NAME: PCWSTR = w!("AutoClickTargetCheck");
+
+/// ESCキーで自動クリックを停止する前に確認する残り回数（0: 確認せずに停止する / 既定: 50）
+pub const ESC_CONFIRM_THRESHOLD_VALUE_NAME: PCWSTR = w!("EscConfirmThreshold");
+
+/// ユーザーがマウスを操作している間は自動クリックを一時停止する（0: しない（既定） / 1: する）
+pub const PAUSE_ON_USER_INPUT_VALUE_NAME: PCWSTR = w!("PauseOnUserInput");
+
+/// ユーザー操作中の一時停止で、最後の操作から次のクリックまで空ける静止時間（ミリ秒、既定: 2000）
+pub const USER_INPUT_QUIET_MS_VALUE_NAME: PCWSTR = w!("UserInputQuietMs");
+
+/// キャプチャモード中にキャプチャを実行するマウスボタン（`CaptureTrigger` の値、0: 左（既定） / 1: 中 / 2: 左+中 / 3: 右）
+pub const CAPTURE_TRIGGER_VALUE_NAME: PCWSTR = w!("CaptureTrigger");
+
+/// 手動クリックによるキャプチャの連続実行を抑止する間隔（ミリ秒、既定: 300、画面に設定項目のない設定）
+pub const CAPTURE_DEBOUNCE_VALUE_NAME: PCWSTR = w!("CaptureDebounceMs");
+
+/// キャプチャモードの開始時に、保存しない1回分のキャプチャで初回の遅延を解消する
+/// （0: しない / 1: する（既定）、画面に設定項目のない設定）
+pub const CAPTURE_WARM_UP_VALUE_NAME: PCWSTR = w!("CaptureWarmUp");
+
+/// キャプチャオーバーレイのラベルのフォント名（REG_SZ、画面に設定項目のない設定。
+/// 見つからない場合はシステムのメッセージフォントなどにフォールバック）
+pub const OVERLAY_FONT_FAMILY_VALUE_NAME: PCWSTR = w!("OverlayFontFamily");
+
+/// キャプチャオーバーレイのラベルの文字サイズ（ポイント、96DPI基準。システムDPIに応じて拡大）
+pub const OVERLAY_FONT_SIZE_VALUE_NAME: PCWSTR = w!("OverlayFontSize");
+
+/// PDF変換の出力先に以前の連番PDFがある場合に、確認せず枝番を付けて保存する
+/// （0: 上書きするか確認する / 1: 確認せずに `0001_2.pdf` 形式で保存、画面に設定項目のない設定）
+pub const PDF_AUTO_VERSION_VALUE_NAME: PCWSTR = w!("PdfAutoVersion");
+
+/// JPEGにコメント（COMセグメント）として埋め込む文字列（REG_SZ、画面に設定項目のない設定。
+/// `{counter}` は連番、`{timestamp}` は撮影日時に置き換える。空・未設定の場合は埋め込まない）
+pub const JPEG_COMMENT_VALUE_NAME: PCWSTR = w!("JpegComment");
+
+/// 保存先フォルダーのテンプレート（REG_SZ、画面に設定項目のない設定。例: `D:\caps\{yyyy}\{mm}\{dd}`）
+/// 設定されている場合は選択した保存先フォルダーの代わりに、保存時の日付で展開したフォルダーに保存する
+pub const SAVE_FOLDER_TEMPLATE_VALUE_NAME: PCWSTR = w!("SaveFolderTemplate");
+
+/// PDF変換をアーカイブ用のPDF/A-1b形式で出力する（0: 通常のPDF / 1: PDF/A-1b）
+pub const PDF_ARCHIVE_VALUE_NAME: PCWSTR = w!("PdfArchive");
+
+/// キャプチャモード終了時に、保存先フォルダーを自動でPDFに変換する（0: 変換しない / 1: 変換する）
+pub const AUTO_PDF_EXPORT_VALUE_NAME: PCWSTR = w!("AutoPdfExport");
+
+/// キャプチャ画像の左上に撮影時刻と前回からの経過時間を焼き込む（0: 焼き込まない / 1: 焼き込む）
+pub const ELAPSED_STAMP_VALUE_NAME: PCWSTR = w!("ElapsedStamp");
+
+/// カーソル下ウィンドウのクイックキャプチャのホットキー（REG_SZ、画面に設定項目のない設定。例: `Ctrl+Alt+F9`）
+/// 未設定・解析できない場合は既定の Ctrl+Alt+Shift+W を使う
+pub const QUICK_WINDOW_CAPTURE_HOTKEY_VALUE_NAME: PCWSTR = w!("QuickWindowCaptureHotkey");
+
+/// 前回の領域の再キャプチャのホットキー（REG_SZ、画面に設定項目のない設定）
+/// 未設定・解析できない場合は既定の Ctrl+Alt+Shift+R を使う
+pub const REPEAT_LAST_CAPTURE_HOTKEY_VALUE_NAME: PCWSTR = w!("RepeatLastCaptureHotkey");
+
+/// 数値の設定をレジストリから読み込む
+///
+/// # 引数
+/// * `value_name` - レジストリ値の名前
+///
+/// # 戻り値
+/// 保存されている値。値がない・REG_DWORDでない場合は `None`。
+pub fn load_setting_dword(value_name: PCWSTR) -> Option<u32> {
+    let mut value: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            SETTINGS_REGISTRY_KEY,
+            value_name,
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut value as *mut u32 as *mut _),
+            Some(&mut size),
+        )
+    };
+    (result == ERROR_SUCCESS).then_some(value)
+}
+
+/// 文字列の設定をレジストリから読み込む
+///
+/// # 引数
+/// * `value_name` - レジストリ値の名前
+///
+/// # 戻り値
+/// 保存されている文字列。値がない・空・REG_SZでない場合は `None`。
+pub fn load_setting_string(value_name: PCWSTR) -> Option<String> {
+    unsafe {
+        // 1回目でサイズ（バイト数、終端のNULを含む）を取得し、2回目で読み込む
+        let mut size: u32 = 0;
+        let result = RegGetValueW(
+            HKEY_CURRENT_USER,
+            SETTINGS_REGISTRY_KEY,
+            value_name,
+            RRF_RT_REG_SZ,
+            None,
+            None,
+            Some(&mut size),
+        );
+        if result != ERROR_SUCCESS || size == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+        let result = RegGetValueW(
+            HKEY_CURRENT_USER,
+            SETTINGS_REGISTRY_KEY,
+            value_name,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr() as *mut _),
+            Some(&mut size),
+        );
+        if result != ERROR_SUCCESS {
+            return None;
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len])).filter(|value| !value.is_empty())
+    }
+}
+
+/// 数値の設定をレジストリに保存する（次回起動時の初期値）
+///
+/// # 引数
+/// * `value_name` - レジストリ値の名前
+/// * `value` - 保存する値
+pub fn save_setting_dword(value_name: PCWSTR, value: u32) {
+    let result = unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            SETTINGS_REGISTRY_KEY,
+            value_name,
+            REG_DWORD.0,
+            Some(&value as *const u32 as *const _),
+            std::mem::size_of::<u32>() as u32,
+        )
+    };
+    if result != ERROR_SUCCESS {
+        app_log(&strings::setting_save_failed(&result));
+    }
+}
+
+/// 文字列の設定をレジストリに保存する（次回起動時の初期値）
+///
+/// # 引数
+/// * `value_name` - レジストリ値の名前
+/// * `value` - 保存する文字列
+pub fn save_setting_string(value_name: PCWSTR, value: &str) {
+    let value_wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+    let result = unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            SETTINGS_REGISTRY_KEY,
+            value_name,
+            REG_SZ.0,
+            Some(value_wide.as_ptr() as *const _),
+            (value_wide.len() * std::mem::size_of::<u16>()) as u32,
+        )
+    };
+    if result != ERROR_SUCCESS {
+        app_log(&strings::setting_save_failed(&result));
+    }
+}
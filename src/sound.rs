@@ -0,0 +1,129 @@
+/*
+============================================================================
+効果音再生モジュール (sound.rs)
+============================================================================
+
+【ファイル概要】
+画面を見ずに作業できるよう、画像保存時のシャッター音と自動クリック完了時の
+チャイムを再生するモジュールです。「サウンドを再生」チェックボックスが
+有効な場合のみ再生します。
+
+【主要機能】
+1.  **効果音再生 (`play_sound`)**:
+    -   実行ファイルに `RT_RCDATA` として埋め込まれたWAVを `PlaySoundW(SND_MEMORY | SND_ASYNC)` で非同期再生
+    -   リソースの取得や再生に失敗した場合は `MessageBeep` にフォールバック
+
+【技術仕様】
+-   **非同期再生**: `SND_ASYNC` によりフック処理・キャプチャ処理をブロックしない
+-   **メモリ再生**: `LockResource` で得たポインタはプロセス終了まで有効なため、非同期再生中も安全
+-   **エラー耐性**: 再生の失敗はログ出力のみとし、キャプチャ結果には一切影響させない
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `sound_enabled` フラグの参照
+-   `constants.rs`: `IDW_SHUTTER_SOUND`, `IDW_COMPLETE_SOUND` リソースID
+-   `screen_capture.rs`, `window_capture.rs`: 保存成功時にシャッター音を再生
+-   `ui/dialog_handler.rs`: `WM_AUTO_CLICK_COMPLETE` 受信時に完了チャイムを再生
+ */
+
+use windows::{
+    Win32::{
+        Media::{
+            Audio::{PlaySoundW, SND_ASYNC, SND_MEMORY, SND_NODEFAULT},
+            KernelStreaming::RT_RCDATA,
+        },
+        System::{
+            Diagnostics::Debug::MessageBeep,
+            LibraryLoader::{FindResourceW, GetModuleHandleW, LoadResource, LockResource},
+        },
+        UI::WindowsAndMessaging::{MB_ICONASTERISK, MB_OK, MESSAGEBOX_STYLE},
+    },
+    core::PCWSTR,
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// 再生する効果音の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    /// 画像保存時のシャッター音
+    Shutter,
+    /// 自動クリック完了時のチャイム
+    Complete,
+}
+
+impl SoundEffect {
+    /// 埋め込みWAVリソースのID
+    fn resource_id(self) -> i32 {
+        match self {
+            SoundEffect::Shutter => IDW_SHUTTER_SOUND,
+            SoundEffect::Complete => IDW_COMPLETE_SOUND,
+        }
+    }
+
+    /// WAVが再生できない場合に使用するシステム音の種類
+    fn fallback_beep(self) -> MESSAGEBOX_STYLE {
+        match self {
+            SoundEffect::Shutter => MB_OK,
+            SoundEffect::Complete => MB_ICONASTERISK,
+        }
+    }
+}
+
+/// 効果音を非同期で再生する
+///
+/// 「サウンドを再生」が無効な場合は何もしません。埋め込みWAVの再生に失敗した場合は
+/// `MessageBeep` で代替し、それにも失敗した場合はログ出力のみ行います。
+///
+/// # 引数
+/// * `effect` - 再生する効果音の種類
+pub fn play_sound(effect: SoundEffect) {
+    if !AppState::get_app_state_ref().sound_enabled {
+        return;
+    }
+
+    if play_embedded_wav(effect.resource_id()) {
+        return;
+    }
+
+    if let Err(e) = unsafe { MessageBeep(effect.fallback_beep()) } {
+        eprintln!("⚠️ 効果音の再生に失敗しました ({:?}): {}", effect, e);
+    }
+}
+
+/// 埋め込みWAVリソースを `SND_MEMORY | SND_ASYNC` で再生する
+///
+/// # 戻り値
+/// 再生を開始できた場合は `true`
+fn play_embedded_wav(resource_id: i32) -> bool {
+    unsafe {
+        let Ok(hinstance) = GetModuleHandleW(None) else {
+            return false;
+        };
+
+        let resource_handle = FindResourceW(
+            Some(hinstance),
+            PCWSTR(resource_id as usize as *const u16),
+            RT_RCDATA,
+        );
+        if resource_handle.0.is_null() {
+            return false;
+        }
+
+        let Ok(loaded_resource) = LoadResource(Some(hinstance), resource_handle) else {
+            return false;
+        };
+
+        // リソースのメモリはプロセス終了まで有効なため、非同期再生に直接渡せる
+        let resource_ptr = LockResource(loaded_resource);
+        if resource_ptr.is_null() {
+            return false;
+        }
+
+        PlaySoundW(
+            PCWSTR(resource_ptr as *const u16),
+            None,
+            SND_MEMORY | SND_ASYNC | SND_NODEFAULT,
+        )
+        .as_bool()
+    }
+}
@@ -0,0 +1,3033 @@
+/*
+============================================================================
+表示文字列テーブルモジュール (strings.rs)
+============================================================================
+
+【ファイル概要】
+ログ・メッセージボックス・オーバーレイのラベルなど、ユーザーに表示するRust側の文字列を
+日本語・英語で切り替えるための文字列テーブル。
+表示言語はOSのUI言語（`GetUserDefaultUILanguage`）から自動判定し、
+メインダイアログの言語コンボボックスで上書きできます。
+
+【主要機能】
+1.  **表示言語の管理**: `current_language` / `set_language`
+    -   未設定の場合は初回参照時にOSのUI言語から判定（日本語以外は英語）
+2.  **言語設定**: `LanguageSetting`（自動 / 日本語 / English）
+    -   コンボボックスの項目データとの相互変換（`from_u8`）と表示ラベル（`label`）
+3.  **文字列テーブル**: モジュールごとにまとめた表示文字列関数
+    -   固定文字列は `&'static str`、引数を埋め込む文字列は `String` を返します
+
+【技術仕様】
+-   **スレッド安全性**: 表示言語は `AtomicU8` で保持するため、自動クリックやPDF変換の
+    ワーカースレッドからも参照できます（`AppState` を経由しない）
+-   **対象外**: ダイアログリソース（`dialog.rc`）のキャプション、コンソール専用のデバッグ出力
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `language_setting` フィールドの保持
+-   `ui/language_combo_handler.rs`: 言語コンボボックス（`set_language` の呼び出し元）
+-   `ui/tooltip_handler.rs`: ダイアログコントロールのツールチップ（`control_tooltip`）
+-   `ui/keyboard_navigation_handler.rs`: Escキーで閉じる際の確認メッセージ
+-   `system_utils.rs`, `screen_capture.rs`, `export_pdf.rs`, `auto_click.rs`, `overlay/` 配下の各オーバーレイ: 表示文字列の利用
+-   `export_pdf.rs` / `export_gif.rs` / `export_zip.rs` / `export_contact_sheet.rs`: ライブラリの変換処理の進捗
+    （`PdfExportEvent` などの列挙型）を表示言語に合わせたログに変換
+ */
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use windows::Win32::Globalization::GetUserDefaultUILanguage;
+
+use clickcapture::hotkey::Hotkey;
+use clickcapture::pdf_builder::ImageSkipReason;
+
+use crate::{
+    auto_click::{AutoClickMode, AutoClickPause},
+    constants::*,
+};
+
+/// 表示言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// 日本語
+    Japanese = 0,
+    /// 英語
+    English = 1,
+}
+
+/// 言語コンボボックスの設定値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LanguageSetting {
+    /// OSのUI言語から自動判定（デフォルト）
+    #[default]
+    Auto = 0,
+    /// 日本語に固定
+    Japanese = 1,
+    /// 英語に固定
+    English = 2,
+}
+
+impl LanguageSetting {
+    /// コンボボックス項目データから復元（不明な値は自動判定扱い）
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => LanguageSetting::Japanese,
+            2 => LanguageSetting::English,
+            _ => LanguageSetting::Auto,
+        }
+    }
+
+    /// コンボボックス表示用ラベル（言語名は各言語の表記で表示）
+    pub fn label(&self) -> &'static str {
+        match self {
+            LanguageSetting::Auto => localize("自動", "Auto"),
+            LanguageSetting::Japanese => "日本語",
+            LanguageSetting::English => "English",
+        }
+    }
+
+    /// 設定値から実際の表示言語を決定する
+    pub fn resolve(&self) -> Language {
+        match self {
+            LanguageSetting::Auto => detect_ui_language(),
+            LanguageSetting::Japanese => Language::Japanese,
+            LanguageSetting::English => Language::English,
+        }
+    }
+}
+
+// 表示言語が未設定であることを示す値（初回参照時にOSのUI言語から判定）
+const LANGUAGE_UNSET: u8 = u8::MAX;
+
+// 現在の表示言語（`Language` の識別値）
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(LANGUAGE_UNSET);
+
+// 日本語のプライマリ言語ID（LANGIDの下位10ビット）
+const LANG_JAPANESE: u16 = 0x11;
+
+/// 現在の表示言語を取得する
+///
+/// 言語コンボボックスで未設定の場合は、OSのUI言語から判定した言語を返します。
+pub fn current_language() -> Language {
+    match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+        0 => Language::Japanese,
+        1 => Language::English,
+        _ => {
+            let language = detect_ui_language();
+            CURRENT_LANGUAGE.store(language as u8, Ordering::Relaxed);
+            language
+        }
+    }
+}
+
+/// 表示言語を設定する（以降のログ・メッセージ・オーバーレイ描画から適用）
+pub fn set_language(language: Language) {
+    CURRENT_LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// OSのUI言語から表示言語を判定する（日本語以外は英語）
+fn detect_ui_language() -> Language {
+    let lang_id = unsafe { GetUserDefaultUILanguage() };
+    // PRIMARYLANGID：下位10ビットがプライマリ言語
+    if lang_id & 0x3ff == LANG_JAPANESE {
+        Language::Japanese
+    } else {
+        Language::English
+    }
+}
+
+/// 現在の表示言語に応じて日本語・英語の固定文字列を選択する
+fn localize(ja: &'static str, en: &'static str) -> &'static str {
+    match current_language() {
+        Language::Japanese => ja,
+        Language::English => en,
+    }
+}
+
+// ===== 言語設定 =====
+
+/// 言語設定変更のログ
+pub fn language_changed(label: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("🌐 表示言語を変更しました: {}", label),
+        Language::English => format!("🌐 Display language changed: {}", label),
+    }
+}
+
+// ===== 画面キャプチャ (screen_capture.rs) =====
+
+/// キャプチャモード終了のログ
+pub fn capture_mode_ended() -> &'static str {
+    localize(
+        "画面キャプチャモードを終了しました",
+        "Screen capture mode ended",
+    )
+}
+
+/// エスケープキーでエリア選択モードを終了した場合のログ
+pub fn area_select_cancelled_by_escape() -> &'static str {
+    localize(
+        "エリア選択モードを終了しました (エスケープキー)",
+        "Area selection mode ended (Escape key)",
+    )
+}
+
+/// キャプチャモードの開始のためにエリア選択モードを終了した場合のログ
+pub fn area_select_cancelled_for_capture() -> &'static str {
+    localize(
+        "エリア選択モードを終了してからキャプチャモードを開始します",
+        "Ending area selection mode before starting capture mode",
+    )
+}
+
+/// キャプチャモード中にエリア選択を開始しようとした場合のログ
+pub fn area_select_blocked_by_capture_log() -> &'static str {
+    localize(
+        "⚠️ キャプチャモード中のため、エリア選択を開始しませんでした",
+        "⚠️ Area selection not started because capture mode is active",
+    )
+}
+
+/// キャプチャモード中にエリア選択を開始しようとした場合のメッセージボックス本文
+pub fn area_select_blocked_by_capture_message() -> &'static str {
+    localize(
+        "キャプチャモード中はエリア選択を開始できません。\nEscキーでキャプチャモードを終了してから、もう一度実行してください。",
+        "Area selection cannot start while capture mode is active.\nPress Esc to end capture mode, then try again.",
+    )
+}
+
+/// キャプチャモード中にエリア選択を開始しようとした場合のメッセージボックスタイトル
+pub fn area_select_blocked_by_capture_title() -> &'static str {
+    localize("エリア選択エラー", "Area selection error")
+}
+
+/// キャプチャモード開始のログ
+pub fn capture_mode_started() -> &'static str {
+    localize(
+        "画面キャプチャモードを開始しました (エスケープキーでキャプチャ終了)",
+        "Screen capture mode started (press Esc to stop)",
+    )
+}
+
+/// キャプチャモードオーバーレイを表示できず、キャプチャモードを開始しなかった場合のログ
+pub fn capture_mode_overlay_failed(error: &dyn std::fmt::Debug) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "❌ キャプチャモードオーバーレイを表示できないため、キャプチャモードを開始しませんでした: {:?}",
+            error
+        ),
+        Language::English => format!(
+            "❌ Capture mode was not started because the capture overlay could not be shown: {:?}",
+            error
+        ),
+    }
+}
+
+/// エリア未選択エラーのログ
+pub fn area_not_selected_log() -> &'static str {
+    localize(
+        "❌ 先にエリア選択を行ってください",
+        "❌ Please select an area first",
+    )
+}
+
+/// エリア未選択エラーのメッセージボックス本文
+pub fn area_not_selected_message() -> &'static str {
+    localize(
+        "先にエリア選択を行ってください。\n\n操作手順:\n1. エリア選択ボタンをクリック\n2. 画面上でドラッグして範囲を選択\n3. キャプチャ開始ボタンをクリック",
+        "Please select an area first.\n\nSteps:\n1. Click the area select button\n2. Drag on the screen to select a region\n3. Click the capture start button",
+    )
+}
+
+/// 起動時の初期化失敗を通知するメッセージボックスのタイトル
+pub fn startup_failures_title() -> &'static str {
+    localize("警告 - 初期化の失敗", "Warning - Initialization failed")
+}
+
+/// 起動時の初期化失敗を通知するメッセージボックスの本文（失敗した項目を箇条書き）
+pub fn startup_failures_message(failures: &[String]) -> String {
+    let header = localize(
+        "一部の初期化に失敗したため、機能を制限して動作します。キャプチャと保存はそのまま使用できます。",
+        "Some components failed to initialize, so ClickCapture will run with reduced features. Capturing and saving still work.",
+    );
+    let items: Vec<String> = failures.iter().map(|f| format!("・{}", f)).collect();
+    format!("{}\n\n{}", header, items.join("\n"))
+}
+
+/// GDI+の初期化に失敗した場合の説明（起動時の通知・ログ用）
+pub fn gdiplus_startup_failed(status: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "GDI+ の初期化に失敗したため、オーバーレイ表示が無効になります (Status {})",
+            status
+        ),
+        Language::English => format!(
+            "GDI+ failed to initialize, so overlays are disabled (Status {})",
+            status
+        ),
+    }
+}
+
+/// オーバーレイの描画リソースの作成に失敗した場合の説明（起動時の通知用）
+pub fn overlay_resources_failed(description: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "{}: 描画リソースを作成できなかったため表示されません",
+            description
+        ),
+        Language::English => format!(
+            "{}: drawing resources could not be created, so it will not be shown",
+            description
+        ),
+    }
+}
+
+/// 描画リソースのないオーバーレイの表示を省略したことのログ
+pub fn overlay_unusable(description: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ {} は描画リソースがないため表示しません（縮退動作）",
+            description
+        ),
+        Language::English => format!(
+            "⚠️ {} is not shown because its drawing resources are missing (degraded mode)",
+            description
+        ),
+    }
+}
+
+/// エリア未選択エラーのメッセージボックスタイトル
+pub fn area_not_selected_title() -> &'static str {
+    localize("エラー - エリア未選択", "Error - No area selected")
+}
+
+/// 自動クリック回数未設定エラーのメッセージボックス本文
+pub fn auto_click_count_zero_message() -> &'static str {
+    localize(
+        "回数の値が0、もしくは未設定です。1以上の値を設定してください。",
+        "The count is 0 or not set. Please enter a value of 1 or more.",
+    )
+}
+
+/// 自動クリックエラーのメッセージボックスタイトル
+pub fn auto_click_error_title() -> &'static str {
+    localize("自動クリックエラー", "Auto-click error")
+}
+
+/// 自動クリック・自動キャプチャ開始確認のメッセージボックス（本文, タイトル）
+pub fn auto_click_confirm(mode: AutoClickMode) -> (&'static str, &'static str) {
+    match (mode, current_language()) {
+        (AutoClickMode::ClickAndCapture, Language::Japanese) => (
+            "自動クリックモードでキャプチャを開始します。\n\n\
+            【開始方法】\n\
+            キャプチャしたい場所（例：「次へ」ボタン）を1回クリックしてください。\n\n\
+            【動作】\n\
+            設定された回数・間隔で、同じ場所へのクリックとキャプチャを自動で繰り返します。\n\n\
+            【停止方法】\n\
+            いつでも ESC キーで中断できます。",
+            "自動クリックモードの開始確認",
+        ),
+        (AutoClickMode::ClickAndCapture, Language::English) => (
+            "Capture will start in auto-click mode.\n\n\
+            [How to start]\n\
+            Click once on the place you want to capture (e.g. a \"Next\" button).\n\n\
+            [What happens]\n\
+            The same place is clicked and captured repeatedly at the configured count and interval.\n\n\
+            [How to stop]\n\
+            Press Esc at any time.",
+            "Start auto-click mode",
+        ),
+        (AutoClickMode::CaptureOnly, Language::Japanese) => (
+            "自動キャプチャモード（クリックなし）でキャプチャを開始します。\n\n\
+            【開始方法】\n\
+            OKを押すと、すぐに開始します。\n\n\
+            【動作】\n\
+            設定された回数・間隔で、選択エリアのキャプチャのみを自動で繰り返します。\n\
+            クリックは行いません（進捗バーの監視などに使用できます）。\n\n\
+            【停止方法】\n\
+            いつでも ESC キーで中断できます。",
+            "自動キャプチャモードの開始確認",
+        ),
+        (AutoClickMode::CaptureOnly, Language::English) => (
+            "Capture will start in auto-capture mode (no clicks).\n\n\
+            [How to start]\n\
+            Capture starts as soon as you press OK.\n\n\
+            [What happens]\n\
+            The selected area is captured repeatedly at the configured count and interval.\n\
+            No clicks are sent (useful for watching a progress bar, for example).\n\n\
+            [How to stop]\n\
+            Press Esc at any time.",
+            "Start auto-capture mode",
+        ),
+    }
+}
+
+/// 自動クリックモードのキャンセルのログ
+pub fn auto_click_cancelled() -> &'static str {
+    localize(
+        "自動クリックモードがキャンセルされました。",
+        "Auto-click mode was cancelled.",
+    )
+}
+
+/// 自動クリック開始確認に追記する、推定使用量と保存先の空き容量
+pub fn auto_click_disk_usage(estimated: u64, free: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "推定使用量 {} / 空き {}",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+        Language::English => format!(
+            "Estimated usage {} / Free {}",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+    }
+}
+
+/// 推定使用量が保存先の空き容量を超えるため自動クリックを開始しない場合のメッセージボックス本文
+pub fn auto_click_disk_insufficient_message(estimated: u64, free: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "保存先の空き容量が不足しているため、自動クリックを開始できません。\n\n\
+            推定使用量 {} / 空き {}\n\n\
+            回数を減らすか、スケール・品質を下げるか、別の保存先を選択してください。",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+        Language::English => format!(
+            "Auto-click cannot start because the save folder does not have enough free space.\n\n\
+            Estimated usage {} / Free {}\n\n\
+            Reduce the count, lower the scale or quality, or choose another save folder.",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+    }
+}
+
+/// 推定使用量が保存先の空き容量を超えるため自動クリックを開始しない場合のログ
+pub fn auto_click_disk_insufficient_log(estimated: u64, free: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "💾 空き容量不足のため自動クリックを中止しました（推定使用量 {} / 空き {}）",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+        Language::English => format!(
+            "💾 Auto-click aborted due to insufficient free space (estimated {} / free {})",
+            byte_size_label(estimated),
+            byte_size_label(free)
+        ),
+    }
+}
+
+/// バイト数の表示（`1.8GB`、`512MB`、`300KB` の形式。1GB以上は小数第1位まで）
+pub fn byte_size_label(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+    match bytes {
+        GB.. => format!("{:.1}GB", bytes as f64 / GB as f64),
+        MB.. => format!("{}MB", bytes / MB),
+        _ => format!("{}KB", bytes.div_ceil(KB)),
+    }
+}
+
+/// ファイルサイズの表示用（MB、小数第1位まで表示する）
+fn megabytes(bytes: u64) -> f64 {
+    bytes as f64 / 1024.0 / 1024.0
+}
+
+/// キャプチャ実行中のログ
+pub fn capturing() -> &'static str {
+    localize(
+        "⌛ スクリーンキャプチャ中です...",
+        "⌛ Capturing the screen...",
+    )
+}
+
+/// 保存サイズ上限適用のログ
+///
+/// # 引数
+/// * `size` - 上限適用前の保存サイズ、`capped` - 適用後の保存サイズ、`limit` - 保存サイズ上限
+pub fn size_cap_applied(size: (i32, i32), capped: (i32, i32), limit: (u32, u32)) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "📐 保存サイズ上限を適用: {}x{} → {}x{} (上限 {}x{})",
+            size.0, size.1, capped.0, capped.1, limit.0, limit.1
+        ),
+        Language::English => format!(
+            "📐 Size limit applied: {}x{} → {}x{} (limit {}x{})",
+            size.0, size.1, capped.0, capped.1, limit.0, limit.1
+        ),
+    }
+}
+
+/// 2つ目の領域の取得失敗のログ
+pub fn second_area_failed() -> &'static str {
+    localize(
+        "⚠️ 2つ目の領域の取得に失敗したため、1つ目の領域のみ保存します",
+        "⚠️ Could not capture the second area; saving the first area only",
+    )
+}
+
+/// 前回保存した画像から変化がないため保存しなかったことのログ
+pub fn capture_unchanged_skipped() -> &'static str {
+    localize(
+        "⏭️ 前回保存した画像から変化がないため、保存しませんでした",
+        "⏭️ Nothing changed since the last saved image; skipped saving",
+    )
+}
+
+/// 変化した範囲のみ切り出したことのログ
+pub fn capture_cropped_to_change(width: u32, height: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!("✂️ 変化した範囲のみ保存します ({}x{})", width, height),
+        Language::English => format!("✂️ Saving only the changed region ({}x{})", width, height),
+    }
+}
+
+/// キャプチャモード開始時の暖機運転が完了したことのログ
+pub fn capture_warm_up_done(elapsed_ms: u128) -> String {
+    match current_language() {
+        Language::Japanese => format!("🔥 キャプチャの暖機運転が完了しました ({}ms)", elapsed_ms),
+        Language::English => format!("🔥 Capture warm-up finished ({}ms)", elapsed_ms),
+    }
+}
+
+/// 選択領域が大きいため暖機運転を省略したことのログ
+pub fn capture_warm_up_skipped(width: i32, height: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⏭️ 選択領域が大きいため、キャプチャの暖機運転を省略しました ({}x{})",
+            width, height
+        ),
+        Language::English => format!(
+            "⏭️ Skipped the capture warm-up because the selection is large ({}x{})",
+            width, height
+        ),
+    }
+}
+
+/// 暖機運転に失敗したことのログ（キャプチャモードはそのまま開始する）
+pub fn capture_warm_up_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("⚠️ キャプチャの暖機運転に失敗しました: {}", error),
+        Language::English => format!("⚠️ Capture warm-up failed: {}", error),
+    }
+}
+
+/// 起動引数の出力先の指定が正しくない場合のログ
+pub fn capture_sink_invalid(value: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 出力先の指定が正しくありません: --sink={}（stdout または pipe:名前）。ファイルに保存します",
+            value
+        ),
+        Language::English => format!(
+            "⚠️ Invalid sink: --sink={} (expected stdout or pipe:NAME). Saving to files instead",
+            value
+        ),
+    }
+}
+
+/// 出力先を使用できない場合のログ
+pub fn capture_sink_unavailable(label: &str, error: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ 出力先 {} を使用できません: {}", label, error),
+        Language::English => format!("❌ Sink {} is not available: {}", label, error),
+    }
+}
+
+/// 出力先へ画像を送信したことのログ
+pub fn capture_sent_to_sink(label: &str, bytes: usize, width: u32, height: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "📤 {} へ送信しました: {} バイト ({}x{})",
+            label, bytes, width, height
+        ),
+        Language::English => format!(
+            "📤 Sent to {}: {} bytes ({}x{})",
+            label, bytes, width, height
+        ),
+    }
+}
+
+/// 保存失敗した画像を保持したことのログ
+pub fn failed_capture_kept() -> &'static str {
+    localize(
+        "💾 保存できなかった画像を保持しました。「再保存」ボタンで再度保存できます",
+        "💾 The unsaved image was kept. Use the Resave button to save it again",
+    )
+}
+
+/// 幅指定縮小時のスケール表示（例：「幅1280px」）
+pub fn fit_width_label(width: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!("幅{}px", width),
+        Language::English => format!("width {}px", width),
+    }
+}
+
+/// 画像保存完了のログ
+pub fn image_saved(
+    file_name: &str,
+    width: u32,
+    height: u32,
+    scale_label: &str,
+    quality: u8,
+) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "✅ 画像保存完了: {} ({}x{}) (scale: {}, quality: {}%)",
+            file_name, width, height, scale_label, quality
+        ),
+        Language::English => format!(
+            "✅ Image saved: {} ({}x{}) (scale: {}, quality: {}%)",
+            file_name, width, height, scale_label, quality
+        ),
+    }
+}
+
+/// 再保存完了のログ
+pub fn image_resaved(counter: u32, extension: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("✅ 画像を再保存しました: {:04}.{}", counter, extension),
+        Language::English => format!("✅ Image resaved: {:04}.{}", counter, extension),
+    }
+}
+
+/// カーソル位置の取得失敗のログ
+pub fn cursor_pos_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ カーソル位置の取得に失敗しました: {}", error),
+        Language::English => format!("❌ Failed to get the cursor position: {}", error),
+    }
+}
+
+/// カーソル位置にウィンドウがない場合のログ
+pub fn no_window_at_cursor() -> &'static str {
+    localize(
+        "❌ カーソル位置にキャプチャ対象のウィンドウがありません",
+        "❌ There is no window to capture under the cursor",
+    )
+}
+
+/// クイックキャプチャで自アプリのダイアログを指定した場合のログ
+pub fn quick_capture_own_dialog() -> &'static str {
+    localize(
+        "⚠️ クイックキャプチャ: 自アプリのダイアログはキャプチャできません",
+        "⚠️ Quick capture: this app's own dialog cannot be captured",
+    )
+}
+
+/// ウィンドウ領域の取得失敗のログ
+pub fn window_rect_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ウィンドウ領域の取得に失敗しました: {}", error),
+        Language::English => format!("❌ Failed to get the window area: {}", error),
+    }
+}
+
+/// クライアント領域が空のウィンドウのログ
+pub fn empty_client_area() -> &'static str {
+    localize(
+        "❌ クライアント領域が空のウィンドウはキャプチャできません",
+        "❌ Windows with an empty client area cannot be captured",
+    )
+}
+
+/// ウィンドウキャプチャの画像を保存できなかった場合のログ (window_capture.rs)
+pub fn window_capture_save_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ウィンドウ画像の保存に失敗: {}", error),
+        Language::English => format!("❌ Failed to save the window image: {}", error),
+    }
+}
+
+/// 前回の領域の再キャプチャで、領域が一度も選択されていない場合のログ
+pub fn repeat_capture_no_area() -> &'static str {
+    localize(
+        "⚠️ 再キャプチャ: まだエリアが選択されていません",
+        "⚠️ Repeat capture: no area has been selected yet",
+    )
+}
+
+/// 前回の領域の再キャプチャ対象のログ
+pub fn repeat_capture_target(x: i32, y: i32, width: i32, height: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🔁 前回の領域を再キャプチャ: ({},{} {}x{})",
+            x, y, width, height
+        ),
+        Language::English => format!(
+            "🔁 Repeating the last capture: ({},{} {}x{})",
+            x, y, width, height
+        ),
+    }
+}
+
+/// 作業領域（タスクバーを除く範囲）をキャプチャエリアに設定した場合のログ
+pub fn work_area_selected(x: i32, y: i32, width: i32, height: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🖥️ 作業領域（タスクバーを除く）を選択しました: ({},{} {}x{})",
+            x, y, width, height
+        ),
+        Language::English => format!(
+            "🖥️ Selected the work area (excluding the taskbar): ({},{} {}x{})",
+            x, y, width, height
+        ),
+    }
+}
+
+/// 作業領域の取得に失敗した場合のログ
+pub fn work_area_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ 作業領域の取得に失敗しました: {}", error),
+        Language::English => format!("❌ Failed to get the work area: {}", error),
+    }
+}
+
+/// クイックキャプチャ対象ウィンドウのログ
+pub fn quick_capture_target(title: &str, x: i32, y: i32, width: i32, height: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🪟 クイックキャプチャ: 「{}」 ({},{} {}x{})",
+            if title.is_empty() { "(無題)" } else { title },
+            x,
+            y,
+            width,
+            height
+        ),
+        Language::English => format!(
+            "🪟 Quick capture: \"{}\" ({},{} {}x{})",
+            if title.is_empty() {
+                "(untitled)"
+            } else {
+                title
+            },
+            x,
+            y,
+            width,
+            height
+        ),
+    }
+}
+
+/// 回復しないキャプチャエラーで自動クリックを停止する場合のログ
+pub fn capture_error_stopping_auto_click(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("🛑 {}（自動クリックを停止します）", error),
+        Language::English => format!("🛑 {} (stopping auto-click)", error),
+    }
+}
+
+/// Desktop Duplicationで取得した場合のログ
+pub fn desktop_duplication_used() -> &'static str {
+    localize(
+        "🖥️ Desktop Duplicationで画面を取得しました",
+        "🖥️ Captured the screen with Desktop Duplication",
+    )
+}
+
+/// Desktop Duplicationを使用できずGDIの結果を使用する場合のログ
+pub fn desktop_duplication_fallback(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ Desktop Duplicationを使用できないため、GDIの結果を使用します: {}",
+            error
+        ),
+        Language::English => format!(
+            "⚠️ Desktop Duplication is unavailable; using the GDI result: {}",
+            error
+        ),
+    }
+}
+
+// ===== キャプチャ所要時間 (capture_timing.rs) =====
+
+/// 1回のキャプチャの段階ごとの所要時間のログ（取得 / 変換 / 縮小 / エンコード / 書き込み、ミリ秒）
+pub fn capture_timing_breakdown(total_ms: f64, phase_ms: [f64; 5]) -> String {
+    let [grab, convert, scale, encode, write] = phase_ms;
+    match current_language() {
+        Language::Japanese => format!(
+            "⏱️ キャプチャ {:.1}ms（取得 {:.1} / 変換 {:.1} / 縮小 {:.1} / エンコード {:.1} / 書き込み {:.1}）",
+            total_ms, grab, convert, scale, encode, write
+        ),
+        Language::English => format!(
+            "⏱️ Capture {:.1}ms (grab {:.1} / convert {:.1} / scale {:.1} / encode {:.1} / write {:.1})",
+            total_ms, grab, convert, scale, encode, write
+        ),
+    }
+}
+
+/// キャプチャモード・定期キャプチャ終了時の所要時間の集計のログ（ミリ秒）
+pub fn capture_timing_summary(
+    count: u32,
+    min_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+    phase_avg_ms: [f64; 5],
+) -> String {
+    let [grab, convert, scale, encode, write] = phase_avg_ms;
+    match current_language() {
+        Language::Japanese => format!(
+            "⏱️ キャプチャ所要時間 {}回: 最小 {:.1}ms / 平均 {:.1}ms / 最大 {:.1}ms（平均内訳: 取得 {:.1} / 変換 {:.1} / 縮小 {:.1} / エンコード {:.1} / 書き込み {:.1}）",
+            count, min_ms, avg_ms, max_ms, grab, convert, scale, encode, write
+        ),
+        Language::English => format!(
+            "⏱️ Capture time over {} captures: min {:.1}ms / avg {:.1}ms / max {:.1}ms (avg breakdown: grab {:.1} / convert {:.1} / scale {:.1} / encode {:.1} / write {:.1})",
+            count, min_ms, avg_ms, max_ms, grab, convert, scale, encode, write
+        ),
+    }
+}
+
+// ===== 連番ファイルの確保 (system_utils.rs) =====
+
+/// 空き連番が見つからない場合のエラー
+pub fn no_free_file_counter(start_counter: u32, attempts: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "空き連番が見つかりません（{:04}から{}回試行）",
+            start_counter, attempts
+        ),
+        Language::English => format!(
+            "No free file number found (tried {} numbers from {:04})",
+            attempts, start_counter
+        ),
+    }
+}
+
+/// 連番が4桁の上限（9999）に近づいた場合の警告ログ
+pub fn capture_counter_near_limit(counter: u32, remaining: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 連番が {:04} に達しました。あと{}枚で4桁を超え、10000以降は5桁のファイル名で保存します",
+            counter, remaining
+        ),
+        Language::English => format!(
+            "⚠️ File number reached {:04}. After {} more images it exceeds 4 digits and files from 10000 on use 5-digit names",
+            counter, remaining
+        ),
+    }
+}
+
+/// 連番が9999を超えて5桁になった場合のログ
+pub fn capture_counter_widened(counter: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🔢 連番が9999を超えたため、5桁のファイル名（{}）で保存を続けます。PDF・ZIP変換では番号順に並べます",
+            counter
+        ),
+        Language::English => format!(
+            "🔢 File number exceeded 9999; continuing with 5-digit names ({}). PDF and ZIP export keep numeric order",
+            counter
+        ),
+    }
+}
+
+/// 次の連番を変更した場合のログ
+pub fn capture_counter_set(counter: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!("🔢 次の連番を {:04} に設定しました", counter),
+        Language::English => format!("🔢 Next file number set to {:04}", counter),
+    }
+}
+
+// ===== 連番のリセット (ui/reset_counter_button_handler.rs) =====
+
+/// 連番リセットの確認・警告メッセージボックスのタイトル
+pub fn reset_counter_title() -> &'static str {
+    localize("連番リセット", "Reset file number")
+}
+
+/// 連番リセットの確認メッセージボックス本文
+pub fn reset_counter_confirm() -> &'static str {
+    localize(
+        "次のキャプチャを0001から保存します。よろしいですか？",
+        "The next capture will be saved as 0001. Continue?",
+    )
+}
+
+/// 保存フォルダーに連番ファイルが残っているため連番をリセットしない場合のメッセージボックス本文
+pub fn reset_counter_files_remain_message(max_existing: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "保存フォルダーに連番ファイル（最大 {:04}）が残っているため、連番をリセットできません。\n\n\
+            既存のファイルを上書きしないよう、保存は残っているファイルの続きの番号から行われます。\n\
+            0001から保存するには、ファイルを別のフォルダーに移動してから実行してください。",
+            max_existing
+        ),
+        Language::English => format!(
+            "The file number cannot be reset because numbered files (up to {:04}) remain in the save folder.\n\n\
+            To avoid overwriting them, captures continue from the next free number.\n\
+            Move the files to another folder first to start again from 0001.",
+            max_existing
+        ),
+    }
+}
+
+/// 保存フォルダーに連番ファイルが残っているため連番をリセットしない場合のログ
+pub fn reset_counter_files_remain_log(max_existing: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 保存フォルダーに連番ファイル（最大 {:04}）が残っているため、連番をリセットしませんでした",
+            max_existing
+        ),
+        Language::English => format!(
+            "⚠️ File number not reset: numbered files (up to {:04}) remain in the save folder",
+            max_existing
+        ),
+    }
+}
+
+/// 保存フォルダーを確認できず連番をリセットしない場合のログ
+pub fn reset_counter_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "❌ 保存フォルダーを確認できないため、連番をリセットしませんでした: {}",
+            error
+        ),
+        Language::English => format!(
+            "❌ File number not reset: could not read the save folder: {}",
+            error
+        ),
+    }
+}
+
+// ===== 連番の振り直し (ui/renumber_button_handler.rs) =====
+
+/// 連番の振り直しの確認メッセージボックス本文
+pub fn renumber_confirm(total: usize, renamed: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "保存フォルダーの連番ファイル {}件を、並び順を変えずに0001から振り直します。\n\
+            {}件のファイル名が変更されます。\n\n\
+            ファイルを開いているアプリがある場合は、閉じてから実行してください。\n\
+            実行しますか？",
+            total, renamed
+        ),
+        Language::English => format!(
+            "Renumber the {} numbered files in the save folder from 0001, keeping their order.\n\
+            {} files will be renamed.\n\n\
+            Close any app that has these files open before continuing.\n\
+            Continue?",
+            total, renamed
+        ),
+    }
+}
+
+/// 連番の振り直しの確認メッセージボックスのタイトル
+pub fn renumber_confirm_title() -> &'static str {
+    localize("連番整理の確認", "Renumber files")
+}
+
+/// 連番の振り直しの完了ログ
+pub fn renumber_completed(renamed: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("✅ 連番を振り直しました（{}件の名前を変更）", renamed),
+        Language::English => format!("✅ Files renumbered ({} renamed)", renamed),
+    }
+}
+
+/// 欠番がなく振り直しが不要な場合のログ
+pub fn renumber_not_needed(total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("ℹ️ 連番に欠番はありません（{}件）", total),
+        Language::English => format!("ℹ️ No gaps in the file numbers ({} files)", total),
+    }
+}
+
+/// 連番の振り直しに失敗した場合のログ（変更済みのファイルは元の名前に戻す）
+pub fn renumber_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "❌ 連番の振り直しに失敗しました（ファイル名は元に戻しました）: {}",
+            error
+        ),
+        Language::English => format!(
+            "❌ Failed to renumber files (names were restored): {}",
+            error
+        ),
+    }
+}
+
+/// 振り直し先のファイル名が既に存在する場合のエラー
+pub fn renumber_target_exists(path: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("変更後のファイルが既に存在します: {}", path),
+        Language::English => format!("The renamed file already exists: {}", path),
+    }
+}
+
+// ===== 設定の保存 (settings.rs) =====
+
+/// 設定をレジストリに保存できなかった場合のログ
+pub fn setting_save_failed(error: &dyn std::fmt::Debug) -> String {
+    match current_language() {
+        Language::Japanese => format!("⚠️ 設定の保存に失敗しました: {:?}", error),
+        Language::English => format!("⚠️ Failed to save the setting: {:?}", error),
+    }
+}
+
+// ===== 設定値の検証 (app_state.rs) =====
+
+/// スケール設定の名前（補正ログ用）
+pub fn capture_scale_setting_name() -> &'static str {
+    localize("画像サイズ調整", "Image scale")
+}
+
+/// JPEG品質設定の名前（補正ログ用）
+pub fn jpeg_quality_setting_name() -> &'static str {
+    localize("JPEG品質", "JPEG quality")
+}
+
+/// 範囲外の設定値を補正した場合のログ
+pub fn setting_clamped(name: &str, value: u8, clamped: u8) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ {}の設定値 {}% は範囲外のため、{}% に補正しました",
+            name, value, clamped
+        ),
+        Language::English => format!(
+            "⚠️ {} setting {}% is out of range; corrected to {}%",
+            name, value, clamped
+        ),
+    }
+}
+
+/// ホットキーの設定文字列を解析できなかった（既定値を使う）
+pub fn hotkey_setting_invalid(text: &str, default: Hotkey) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ ホットキーの設定値 \"{}\" を解析できないため、既定の {} を使います（Ctrl・Alt・Winのいずれかと A〜Z・0〜9・F1〜F24 の組み合わせ）",
+            text, default
+        ),
+        Language::English => format!(
+            "⚠️ Could not parse the hotkey setting \"{}\"; using the default {} (combine Ctrl, Alt or Win with A-Z, 0-9 or F1-F24)",
+            text, default
+        ),
+    }
+}
+
+// ===== グローバルホットキー (global_hotkey.rs) =====
+
+/// ホットキーで実行する操作の名前と、変更に使うレジストリ値の名前
+fn hotkey_action(id: i32) -> (&'static str, &'static str) {
+    match id {
+        HOTKEY_ID_REPEAT_LAST_CAPTURE => (
+            localize("再キャプチャ", "repeat capture"),
+            "RepeatLastCaptureHotkey",
+        ),
+        _ => (
+            localize("クイックキャプチャ", "quick capture"),
+            "QuickWindowCaptureHotkey",
+        ),
+    }
+}
+
+/// ホットキーを登録した
+///
+/// # 引数
+/// * `id` - ホットキーのID（`HOTKEY_ID_*`）
+pub fn hotkey_registered(id: i32, hotkey: Hotkey) -> String {
+    let (action, _) = hotkey_action(id);
+    match current_language() {
+        Language::Japanese => format!("⌨️ {}のホットキー {} を登録しました", action, hotkey),
+        Language::English => format!("⌨️ Registered the {} hotkey {}", action, hotkey),
+    }
+}
+
+/// ホットキーを登録できなかった（他のアプリが使用中など）
+///
+/// # 引数
+/// * `id` - ホットキーのID（`HOTKEY_ID_*`）
+pub fn hotkey_register_failed(id: i32, hotkey: Hotkey, error: &dyn std::fmt::Display) -> String {
+    let (action, value_name) = hotkey_action(id);
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ {}のホットキー {} を登録できませんでした（他のアプリが使用中の可能性があります。レジストリ {} で変更できます）: {}",
+            action, hotkey, value_name, error
+        ),
+        Language::English => format!(
+            "⚠️ Could not register the {} hotkey {} (another app may be using it; change it with the {} registry value): {}",
+            action, hotkey, value_name, error
+        ),
+    }
+}
+
+// ===== PDF変換 (export_pdf.rs) =====
+
+/// PDF変換で保存フォルダーが未選択の場合のログ
+pub fn pdf_no_folder() -> &'static str {
+    localize(
+        "⚠️ PDF変換エラー: 保存フォルダーが選択されていません",
+        "⚠️ PDF export error: no save folder is selected",
+    )
+}
+
+/// 出力先に以前の変換の連番PDFがある場合の確認メッセージボックスのタイトル
+pub fn pdf_overwrite_title() -> &'static str {
+    localize("既存のPDF", "Existing PDFs")
+}
+
+/// 出力先に以前の変換の連番PDFがある場合の確認メッセージボックス本文
+pub fn pdf_overwrite_confirm(count: usize, first_name: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "出力先に以前のPDF変換のファイルが{}件あります（{} など）。\n\n\
+            はい: 既存のPDFを上書きします\n\
+            いいえ: 既存のPDFを残し、0001_2.pdf のように枝番を付けて保存します\n\
+            キャンセル: PDF変換を中止します",
+            count, first_name
+        ),
+        Language::English => format!(
+            "The output folder already has {} PDFs from a previous export ({}, ...).\n\n\
+            Yes: overwrite the existing PDFs\n\
+            No: keep them and save with a suffix such as 0001_2.pdf\n\
+            Cancel: stop the PDF export",
+            count, first_name
+        ),
+    }
+}
+
+/// 既存の連番PDFを上書きする場合のログ
+pub fn pdf_overwrite_existing(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("♻️ 出力先の既存のPDF（{}件）を上書きします", count),
+        Language::English => format!(
+            "♻️ Overwriting {} existing PDFs in the output folder",
+            count
+        ),
+    }
+}
+
+/// 既存の連番PDFを残して枝番を付けて保存する場合のログ
+pub fn pdf_keep_existing(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "📄 出力先の既存のPDF（{}件）は残し、同じ番号のPDFは枝番を付けて保存します（例: 0001_2.pdf）",
+            count
+        ),
+        Language::English => format!(
+            "📄 Keeping {} existing PDFs; PDFs with the same number get a suffix (e.g. 0001_2.pdf)",
+            count
+        ),
+    }
+}
+
+/// 出力先の既存のPDFを確認できなかった場合のログ（上書きしないよう枝番を付けて保存する）
+pub fn pdf_existing_check_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 出力先の既存のPDFを確認できませんでした。上書きしないよう、同じ番号のPDFは枝番を付けて保存します: {}",
+            error
+        ),
+        Language::English => format!(
+            "⚠️ Could not check the output folder for existing PDFs; PDFs with the same number get a suffix to avoid overwriting: {}",
+            error
+        ),
+    }
+}
+
+/// 既存のPDFへの追記を確認するメッセージボックスのタイトル
+pub fn pdf_append_title() -> &'static str {
+    localize("既存PDFに追記", "Append to existing PDF")
+}
+
+/// 既存のPDFへの追記を確認するメッセージボックス本文
+pub fn pdf_append_confirm(pdf_name: &str, count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "出力先の {} より後に保存した画像が{}件あります。\n\n\
+            はい: {} の末尾にページとして追記します\n\
+            いいえ: 追記せず、すべての画像から新しく変換します\n\
+            キャンセル: PDF変換を中止します",
+            pdf_name, count, pdf_name
+        ),
+        Language::English => format!(
+            "{} images were saved after {} in the output folder.\n\n\
+            Yes: append them as pages to the end of {}\n\
+            No: export all images into new PDFs instead\n\
+            Cancel: stop the PDF export",
+            count, pdf_name, pdf_name
+        ),
+    }
+}
+
+/// 既存のPDFに追記する場合のログ
+pub fn pdf_append_start(pdf_name: &str, count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("📎 {} に画像{}件を追記します", pdf_name, count),
+        Language::English => format!("📎 Appending {} images to {}", count, pdf_name),
+    }
+}
+
+/// キャプチャ履歴の並び順でPDFに変換する場合のログ
+pub fn pdf_using_capture_history(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("📑 キャプチャ履歴の並び順（{}件）でPDFに変換します", count),
+        Language::English => format!(
+            "📑 Exporting {} images to PDF in capture history order",
+            count
+        ),
+    }
+}
+
+/// PDF変換の確認ダイアログの本文
+///
+/// # 引数
+/// * `source` - 変換元（フォルダー、またはキャプチャ履歴の件数）
+/// * `output_dir` - 出力先フォルダー
+pub fn pdf_export_confirm(source: &str, output_dir: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "PDF変換を開始してもよろしいでしょうか？\n\n変換元: {}\n出力先: {}\n\n\
+            はい: このフォルダーで変換を開始します\n\
+            いいえ: 変換元・出力先フォルダーを選び直してから変換します（複数フォルダーの連結も可）",
+            source, output_dir
+        ),
+        Language::English => format!(
+            "Start the PDF export?\n\nSource: {}\nOutput: {}\n\n\
+            Yes: Export from this folder\n\
+            No: Choose the source and output folders first (several folders can be joined)",
+            source, output_dir
+        ),
+    }
+}
+
+/// PDF変換の確認ダイアログのタイトル
+pub fn pdf_export_confirm_title() -> &'static str {
+    localize("PDF変換確認", "Confirm PDF export")
+}
+
+/// PDF変換の確認ダイアログで、キャプチャ履歴の並び順で変換する場合の変換元の表示
+pub fn pdf_history_source_label(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("キャプチャ履歴 {}件（履歴の並び順）", count),
+        Language::English => format!("Capture history, {} images (history order)", count),
+    }
+}
+
+/// PDF変換の確認ダイアログで、保存フォルダーが未選択の場合の表示
+pub fn pdf_folder_not_selected() -> &'static str {
+    localize("(未選択)", "(not selected)")
+}
+
+/// PDF変換で対象の画像ファイルが見つからなかった場合のログ
+pub fn pdf_no_images() -> &'static str {
+    localize(
+        "⚠️ PDF変換: 対象の画像ファイルが見つかりませんでした。",
+        "⚠️ PDF export: no image files were found.",
+    )
+}
+
+/// PDF/Aのため撮影日時フッターを省略する場合のログ
+pub fn pdf_archive_footer_omitted() -> &'static str {
+    localize(
+        "ℹ️ PDF/A: 撮影日時フッターはPDF/Aで使用できないため省略します。",
+        "ℹ️ PDF/A: the capture-time footer is left out because PDF/A does not allow it.",
+    )
+}
+
+/// PDF変換で処理中の画像のログ
+pub fn pdf_processing_image(file: &str, index: usize, total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏳ 処理中の画像: {} ({}/{})", file, index, total),
+        Language::English => format!("⏳ Processing image: {} ({}/{})", file, index, total),
+    }
+}
+
+/// PDF変換・追記で画像をスキップした場合のログ
+pub fn pdf_image_skipped(file: &str, reason: &ImageSkipReason) -> String {
+    image_skipped(
+        file,
+        reason,
+        ("PDFに埋め込めない", "cannot be embedded in a PDF"),
+    )
+}
+
+/// PDFのサイズ上限に達し、新しいPDFを開始する場合のログ
+pub fn pdf_size_limit_reached(estimated_bytes: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "➡️ PDFサイズ制限到達 ({:.1}MB)。現在のPDFを保存して新しいPDFを開始します。",
+            megabytes(estimated_bytes as u64)
+        ),
+        Language::English => format!(
+            "➡️ PDF size limit reached ({:.1}MB). Saving the current PDF and starting a new one.",
+            megabytes(estimated_bytes as u64)
+        ),
+    }
+}
+
+/// 同じ連番の既存のPDFを上書きする場合のログ
+pub fn pdf_overwriting(path: &Path) -> String {
+    match current_language() {
+        Language::Japanese => format!("♻️ 既存のPDFを上書きします: {}", path.display()),
+        Language::English => format!("♻️ Overwriting the existing PDF: {}", path.display()),
+    }
+}
+
+/// 同じ連番の既存のPDFを残し、枝番を付けて保存する場合のログ
+pub fn pdf_keeping_existing(path: &Path, versioned: &Path) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "📄 {} は既に存在するため、{} として保存します",
+            path.display(),
+            versioned.display()
+        ),
+        Language::English => format!(
+            "📄 {} already exists; saving as {}",
+            path.display(),
+            versioned.display()
+        ),
+    }
+}
+
+/// PDFを1ファイル保存した場合のログ
+pub fn pdf_saved(path: &Path, bytes: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "✅ PDF完了: {} ({:.1}MB)",
+            path.display(),
+            megabytes(bytes as u64)
+        ),
+        Language::English => format!(
+            "✅ PDF saved: {} ({:.1}MB)",
+            path.display(),
+            megabytes(bytes as u64)
+        ),
+    }
+}
+
+/// PDF変換が完了した場合のログ（スキップした画像がある場合はその件数も表示）
+pub fn pdf_export_completed(converted: usize, skipped: usize) -> String {
+    match (current_language(), skipped) {
+        (Language::Japanese, 0) => format!(
+            "✅ 全画像からのPDF変換処理が完了しました。処理ファイル数: {}",
+            converted
+        ),
+        (Language::English, 0) => format!(
+            "✅ PDF export finished for all images. Files processed: {}",
+            converted
+        ),
+        (Language::Japanese, _) => format!(
+            "⚠️ PDF変換処理が完了しました。変換: {}件, スキップ: {}件",
+            converted, skipped
+        ),
+        (Language::English, _) => format!(
+            "⚠️ PDF export finished. Converted: {}, skipped: {}",
+            converted, skipped
+        ),
+    }
+}
+
+/// 既存のPDFに追記中の画像のログ
+pub fn pdf_appending_image(file: &str, index: usize, total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏳ 追記中の画像: {} ({}/{})", file, index, total),
+        Language::English => format!("⏳ Appending image: {} ({}/{})", file, index, total),
+    }
+}
+
+/// 既存のPDFに追記した場合のログ
+pub fn pdf_appended(path: &Path, added: usize, total_pages: usize, bytes: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "✅ PDFに追記しました: {} (+{}ページ, 計{}ページ, {:.1}MB)",
+            path.display(),
+            added,
+            total_pages,
+            megabytes(bytes as u64)
+        ),
+        Language::English => format!(
+            "✅ Appended to the PDF: {} (+{} pages, {} pages in total, {:.1}MB)",
+            path.display(),
+            added,
+            total_pages,
+            megabytes(bytes as u64)
+        ),
+    }
+}
+
+/// 既存のPDFに追記できなかった画像がある場合のログ
+pub fn pdf_append_skipped(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⚠️ 追記できなかった画像: {}件", count),
+        Language::English => format!("⚠️ Images that could not be appended: {}", count),
+    }
+}
+
+/// PDF変換に失敗した場合のログ
+pub fn pdf_export_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("PDF変換エラー: {}", error),
+        Language::English => format!("PDF export error: {}", error),
+    }
+}
+
+/// PDF変換に失敗した場合のメッセージボックス本文
+pub fn pdf_export_failed_message(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("PDF変換中にエラーが発生しました：\n\n{}", error),
+        Language::English => format!("An error occurred during the PDF export:\n\n{}", error),
+    }
+}
+
+/// PDF変換に失敗した場合のメッセージボックスのタイトル
+pub fn pdf_export_failed_title() -> &'static str {
+    localize("PDF変換エラー", "PDF export error")
+}
+
+/// 画像をスキップした場合のログ（PDF変換・GIF変換・一覧画像で共通）
+///
+/// # 引数
+/// * `avif` - AVIFをスキップする理由（日本語, 英語）。変換先によって異なる
+fn image_skipped(file: &str, reason: &ImageSkipReason, avif: (&str, &str)) -> String {
+    match (current_language(), reason) {
+        (Language::Japanese, ImageSkipReason::Avif) => {
+            format!("⚠️ AVIFは{}ためスキップ ({})", avif.0, file)
+        }
+        (Language::English, ImageSkipReason::Avif) => {
+            format!("⚠️ Skipped {}: AVIF {}", file, avif.1)
+        }
+        (Language::Japanese, ImageSkipReason::Read(e)) => {
+            format!("⚠️ 画像の読み込みに失敗したためスキップ ({}): {}", file, e)
+        }
+        (Language::English, ImageSkipReason::Read(e)) => {
+            format!("⚠️ Skipped {}: could not read the file: {}", file, e)
+        }
+        (Language::Japanese, ImageSkipReason::Decode(e)) => {
+            format!("⚠️ 画像のデコードに失敗したためスキップ ({}): {}", file, e)
+        }
+        (Language::English, ImageSkipReason::Decode(e)) => {
+            format!("⚠️ Skipped {}: could not decode the image: {}", file, e)
+        }
+        (Language::Japanese, ImageSkipReason::Transcode { format, error }) => format!(
+            "⚠️ {}からJPEGへの変換エラーのためスキップ ({}): {}",
+            format, file, error
+        ),
+        (Language::English, ImageSkipReason::Transcode { format, error }) => format!(
+            "⚠️ Skipped {}: could not convert {} to JPEG: {}",
+            file, format, error
+        ),
+        (Language::Japanese, ImageSkipReason::AddPage(e)) => {
+            format!("⚠️ PDF追加エラーのためスキップ ({}): {}", file, e)
+        }
+        (Language::English, ImageSkipReason::AddPage(e)) => {
+            format!(
+                "⚠️ Skipped {}: could not add the page to the PDF: {}",
+                file, e
+            )
+        }
+        (Language::Japanese, ImageSkipReason::ReadAheadStopped) => {
+            format!("⚠️ 画像の先読みスレッドが終了したためスキップ ({})", file)
+        }
+        (Language::English, ImageSkipReason::ReadAheadStopped) => {
+            format!("⚠️ Skipped {}: the image read-ahead thread stopped", file)
+        }
+    }
+}
+
+// ===== GIF変換 (export_gif.rs) =====
+
+/// GIF変換で保存フォルダーが未選択の場合のログ
+pub fn gif_no_folder() -> &'static str {
+    localize(
+        "⚠️ GIF変換エラー: 保存フォルダーが選択されていません",
+        "⚠️ GIF export error: no save folder is selected",
+    )
+}
+
+/// GIF変換中の画像のログ
+pub fn gif_converting_image(file: &str, index: usize, total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏳ GIF変換中: {} ({}/{})", file, index, total),
+        Language::English => format!("⏳ Converting to GIF: {} ({}/{})", file, index, total),
+    }
+}
+
+/// GIF変換で画像をスキップした場合のログ
+pub fn gif_image_skipped(file: &str, reason: &ImageSkipReason) -> String {
+    image_skipped(
+        file,
+        reason,
+        ("GIFに変換できない", "cannot be converted to GIF"),
+    )
+}
+
+/// GIFの推定サイズが大きい場合の警告のログ
+pub fn gif_large_output(estimated_bytes: u64, width: u32, height: u32, frames: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ GIFの推定サイズが約{}MBです（{}x{}, {}フレーム）。最大幅を小さくすると軽くなります",
+            estimated_bytes / 1024 / 1024,
+            width,
+            height,
+            frames
+        ),
+        Language::English => format!(
+            "⚠️ The GIF is estimated at about {}MB ({}x{}, {} frames). A smaller maximum width makes it lighter",
+            estimated_bytes / 1024 / 1024,
+            width,
+            height,
+            frames
+        ),
+    }
+}
+
+/// GIF変換が完了した場合のログ
+pub fn gif_export_completed(path: &Path, frames: usize, bytes: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🎞️ GIF変換完了: {} ({}フレーム, {:.1}MB)",
+            path.display(),
+            frames,
+            megabytes(bytes)
+        ),
+        Language::English => format!(
+            "🎞️ GIF export finished: {} ({} frames, {:.1}MB)",
+            path.display(),
+            frames,
+            megabytes(bytes)
+        ),
+    }
+}
+
+/// GIF変換に失敗した場合のログ
+pub fn gif_export_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ GIF変換エラー: {}", error),
+        Language::English => format!("❌ GIF export error: {}", error),
+    }
+}
+
+// ===== ZIP圧縮 (export_zip.rs) =====
+
+/// ZIP圧縮で保存フォルダーが未選択の場合のログ
+pub fn zip_no_folder() -> &'static str {
+    localize(
+        "⚠️ ZIP圧縮エラー: 保存フォルダーが選択されていません",
+        "⚠️ ZIP export error: no save folder is selected",
+    )
+}
+
+/// ZIP圧縮中の画像のログ
+pub fn zip_archiving_image(file: &str, index: usize, total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏳ ZIP圧縮中: {} ({}/{})", file, index, total),
+        Language::English => format!("⏳ Adding to ZIP: {} ({}/{})", file, index, total),
+    }
+}
+
+/// 書き込み中または読み込めないため、ZIPに追加しなかった場合のログ
+pub fn zip_file_skipped(file: &str, error: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 書き込み中または読み込めないためスキップ ({}): {}",
+            file, error
+        ),
+        Language::English => format!(
+            "⚠️ Skipped {}: the file is being written or cannot be read: {}",
+            file, error
+        ),
+    }
+}
+
+/// ZIP作成が完了した場合のログ
+pub fn zip_export_completed(path: &Path, archived: usize, skipped: usize, bytes: u64) -> String {
+    match (current_language(), skipped) {
+        (Language::Japanese, 0) => format!(
+            "📦 ZIP作成完了: {} ({}ファイル, {:.1}MB)",
+            path.display(),
+            archived,
+            megabytes(bytes)
+        ),
+        (Language::English, 0) => format!(
+            "📦 ZIP created: {} ({} files, {:.1}MB)",
+            path.display(),
+            archived,
+            megabytes(bytes)
+        ),
+        (Language::Japanese, _) => format!(
+            "📦 ZIP作成完了: {} ({}ファイル, スキップ{}件, {:.1}MB)",
+            path.display(),
+            archived,
+            skipped,
+            megabytes(bytes)
+        ),
+        (Language::English, _) => format!(
+            "📦 ZIP created: {} ({} files, {} skipped, {:.1}MB)",
+            path.display(),
+            archived,
+            skipped,
+            megabytes(bytes)
+        ),
+    }
+}
+
+/// ZIP圧縮を中止した場合のログ
+pub fn zip_export_cancelled() -> &'static str {
+    localize(
+        "⏹️ ZIP圧縮を中止しました（作成途中のZIPは削除しました）",
+        "⏹️ ZIP export cancelled (the partial ZIP was deleted)",
+    )
+}
+
+/// ZIP圧縮に失敗した場合のログ
+pub fn zip_export_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ZIP圧縮エラー: {}", error),
+        Language::English => format!("❌ ZIP export error: {}", error),
+    }
+}
+
+// ===== 一覧画像 (export_contact_sheet.rs / ui/contact_sheet_button_handler.rs) =====
+
+/// 一覧画像の作成を確認するメッセージボックス本文
+///
+/// # 引数
+/// * `source_dir` - 変換元フォルダー（未選択の場合は `None`）
+pub fn contact_sheet_confirm(
+    source_dir: Option<&str>,
+    columns: u32,
+    thumbnail_width: u32,
+) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "フォルダー内の画像をサムネイルの一覧画像にまとめてもよろしいでしょうか？\n\n\
+            変換元: {}\n配置: {}列（サムネイル幅 {}px）\n出力先: contactsheet.png（変換元フォルダー内）",
+            source_dir.unwrap_or("(未選択)"),
+            columns,
+            thumbnail_width
+        ),
+        Language::English => format!(
+            "Combine the folder's images into a contact sheet of thumbnails?\n\n\
+            Source: {}\nLayout: {} columns (thumbnail width {}px)\nOutput: contactsheet.png (in the source folder)",
+            source_dir.unwrap_or("(not selected)"),
+            columns,
+            thumbnail_width
+        ),
+    }
+}
+
+/// 一覧画像の作成を確認するメッセージボックスのタイトル
+pub fn contact_sheet_confirm_title() -> &'static str {
+    localize("一覧画像作成確認", "Confirm contact sheet")
+}
+
+/// 一覧画像の作成をキャンセルした場合のログ
+pub fn contact_sheet_cancelled() -> &'static str {
+    localize(
+        "一覧画像の作成がキャンセルされました。",
+        "Contact sheet creation was cancelled.",
+    )
+}
+
+/// 一覧画像の作成を開始する場合のログ
+pub fn contact_sheet_started() -> &'static str {
+    localize(
+        "一覧画像の作成を開始します...",
+        "Creating the contact sheet...",
+    )
+}
+
+/// 一覧画像の作成で保存フォルダーが未選択の場合のログ
+pub fn contact_sheet_no_folder() -> &'static str {
+    localize(
+        "⚠️ 一覧画像作成エラー: 保存フォルダーが選択されていません",
+        "⚠️ Contact sheet error: no save folder is selected",
+    )
+}
+
+/// 一覧画像の作成が完了した場合のログ
+pub fn contact_sheet_export_completed(path: &Path, image_count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🗂️ 一覧画像作成完了: {} ({}枚)",
+            path.display(),
+            image_count
+        ),
+        Language::English => format!(
+            "🗂️ Contact sheet created: {} ({} images)",
+            path.display(),
+            image_count
+        ),
+    }
+}
+
+/// 一覧画像の作成に失敗した場合のログ
+pub fn contact_sheet_export_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ 一覧画像作成エラー: {}", error),
+        Language::English => format!("❌ Contact sheet error: {}", error),
+    }
+}
+
+/// 一覧画像の作成が完了した場合のメッセージボックス本文
+pub fn contact_sheet_completed_message() -> &'static str {
+    localize(
+        "一覧画像の作成が正常に完了しました。",
+        "The contact sheet was created successfully.",
+    )
+}
+
+/// 一覧画像の作成が完了した場合のメッセージボックスのタイトル
+pub fn contact_sheet_completed_title() -> &'static str {
+    localize("一覧画像作成完了", "Contact sheet created")
+}
+
+/// 一覧画像の作成に失敗した場合のメッセージボックス本文
+pub fn contact_sheet_failed_message() -> &'static str {
+    localize(
+        "一覧画像の作成中にエラーが発生しました。\n詳細はログを確認してください。",
+        "An error occurred while creating the contact sheet.\nSee the log for details.",
+    )
+}
+
+/// 一覧画像の作成に失敗した場合のメッセージボックスのタイトル
+pub fn contact_sheet_failed_title() -> &'static str {
+    localize("一覧画像作成エラー", "Contact sheet error")
+}
+
+/// 一覧画像のサムネイルを作成中の画像のログ
+pub fn contact_sheet_thumbnailing(file: &str, index: usize, total: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏳ 一覧画像作成中: {} ({}/{})", file, index, total),
+        Language::English => format!("⏳ Creating contact sheet: {} ({}/{})", file, index, total),
+    }
+}
+
+/// 一覧画像で画像をスキップした場合のログ
+pub fn contact_sheet_image_skipped(file: &str, reason: &ImageSkipReason) -> String {
+    image_skipped(
+        file,
+        reason,
+        (
+            "一覧画像に配置できない",
+            "cannot be placed on a contact sheet",
+        ),
+    )
+}
+
+/// 一覧画像を保存する場合のログ
+pub fn contact_sheet_saving(width: u32, height: u32, columns: u32, rows: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "💾 一覧画像を保存中: {}x{}px ({}列 x {}行)",
+            width, height, columns, rows
+        ),
+        Language::English => format!(
+            "💾 Saving the contact sheet: {}x{}px ({} columns x {} rows)",
+            width, height, columns, rows
+        ),
+    }
+}
+
+// ===== キャプチャ履歴 (ui/capture_history_handler.rs) =====
+
+/// 履歴から選択したファイルを削除する前の確認メッセージ
+pub fn capture_history_delete_confirm(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "選択した{}件のファイルを削除しますか？\n削除したファイルは元に戻せません。",
+            count
+        ),
+        Language::English => format!(
+            "Delete the {} selected files?\nDeleted files cannot be restored.",
+            count
+        ),
+    }
+}
+
+/// 履歴から選択したファイルを削除する前の確認ダイアログのタイトル
+pub fn capture_history_delete_confirm_title() -> &'static str {
+    localize("削除の確認", "Confirm delete")
+}
+
+/// 履歴から選択したファイルを削除した場合のログ
+pub fn capture_history_deleted(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!("🗑️ キャプチャ履歴から{}件のファイルを削除しました", count),
+        Language::English => format!("🗑️ Deleted {} files from the capture history", count),
+    }
+}
+
+/// 履歴のファイルを削除できなかった場合のログ
+pub fn capture_history_delete_failed(path: &str, error: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ファイルを削除できませんでした: {} ({})", path, error),
+        Language::English => format!("❌ Could not delete the file: {} ({})", path, error),
+    }
+}
+
+/// 履歴の項目をドラッグで並べ替えた場合のログ
+///
+/// # 引数
+/// * `count` - 移動した項目数
+/// * `position` - 移動先の先頭位置（0始まり）
+pub fn capture_history_reordered(count: usize, position: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "↕️ キャプチャ履歴の{}件を{}番目に移動しました",
+            count,
+            position + 1
+        ),
+        Language::English => format!(
+            "↕️ Moved {} capture history entries to position {}",
+            count,
+            position + 1
+        ),
+    }
+}
+
+/// キャプチャ履歴ダイアログを作成できなかった場合のログ
+pub fn capture_history_dialog_failed() -> &'static str {
+    localize(
+        "❌ キャプチャ履歴ダイアログの作成に失敗しました",
+        "❌ Failed to create the capture history dialog",
+    )
+}
+
+// ===== 自動クリック (auto_click.rs) =====
+
+/// 自動クリックの二重起動エラー
+pub fn auto_click_already_running() -> &'static str {
+    localize(
+        "連続クリックは既に開始されています",
+        "Auto-click is already running",
+    )
+}
+
+/// 自動クリック・自動キャプチャ開始のログ
+pub fn auto_click_started(mode: AutoClickMode, interval_ms: u64, max_count: u32) -> String {
+    match (mode, current_language()) {
+        (AutoClickMode::ClickAndCapture, Language::Japanese) => format!(
+            "🖱️ 連続クリックを開始しました（{}ms間隔, {}回クリック）",
+            interval_ms, max_count
+        ),
+        (AutoClickMode::ClickAndCapture, Language::English) => format!(
+            "🖱️ Auto-click started ({}ms interval, {} clicks)",
+            interval_ms, max_count
+        ),
+        (AutoClickMode::CaptureOnly, Language::Japanese) => format!(
+            "⏱️ 自動キャプチャを開始しました（{}ms間隔, {}回、クリックなし）",
+            interval_ms, max_count
+        ),
+        (AutoClickMode::CaptureOnly, Language::English) => format!(
+            "⏱️ Auto-capture started ({}ms interval, {} captures, no clicks)",
+            interval_ms, max_count
+        ),
+    }
+}
+
+/// クリック位置を進める設定で自動クリックを開始した場合のログ
+pub fn auto_click_position_step(dx: i32, dy: i32, steps: u32) -> String {
+    match (steps, current_language()) {
+        (0, Language::Japanese) => {
+            format!("↘️ クリック位置を1回ごとに ({}, {}) 進めます", dx, dy)
+        }
+        (0, Language::English) => {
+            format!("↘️ Moving the click point by ({}, {}) each time", dx, dy)
+        }
+        (steps, Language::Japanese) => format!(
+            "↘️ クリック位置を1回ごとに ({}, {}) 進めます（{}回まで）",
+            dx, dy, steps
+        ),
+        (steps, Language::English) => format!(
+            "↘️ Moving the click point by ({}, {}) each time (up to {} times)",
+            dx, dy, steps
+        ),
+    }
+}
+
+/// 進めたクリック位置が画面外に出たため自動クリックを終了する場合のログ
+pub fn auto_click_target_off_screen(x: i32, y: i32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🛑 クリック位置({}, {})が画面外のため、自動クリックを終了します",
+            x, y
+        ),
+        Language::English => format!(
+            "🛑 Click point ({}, {}) is off-screen; stopping auto-click",
+            x, y
+        ),
+    }
+}
+
+/// キャプチャ中オーバーレイに表示する現在のクリック位置（クリック位置を進める設定の場合）
+pub fn auto_click_target_label(x: i32, y: i32) -> String {
+    format!("→ ({}, {})", x, y)
+}
+
+/// 自動クリック間隔が描画待ち時間より短いため切り上げた場合のログ
+pub fn auto_click_interval_clamped(requested_ms: u64, minimum_ms: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 自動クリック間隔 {}ms はクリック後の描画待ち時間より短いため、{}msに切り上げました",
+            requested_ms, minimum_ms
+        ),
+        Language::English => format!(
+            "⚠️ Auto-click interval {}ms is shorter than the post-click render delay; using {}ms",
+            requested_ms, minimum_ms
+        ),
+    }
+}
+
+/// 自動クリックの最大実行時間を設定して開始した場合のログ
+pub fn auto_click_max_duration(minutes: u64) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⏳ 最長{}分で停止します（回数と先に達した方で停止）",
+            minutes
+        ),
+        Language::English => format!(
+            "⏳ Stops after {} minutes at most (or at the click count, whichever comes first)",
+            minutes
+        ),
+    }
+}
+
+/// 自動クリックの最大実行時間の変更のログ（0はなし）
+pub fn auto_click_max_duration_changed(minutes: u64) -> String {
+    match (minutes, current_language()) {
+        (0, Language::Japanese) => "⏳ 自動クリックの最長時間: なし".to_string(),
+        (0, Language::English) => "⏳ Auto-click time limit: off".to_string(),
+        (minutes, Language::Japanese) => format!("⏳ 自動クリックの最長時間: {}分", minutes),
+        (minutes, Language::English) => format!("⏳ Auto-click time limit: {} minutes", minutes),
+    }
+}
+
+/// 自動クリックが最大実行時間に達して停止した場合のログ
+pub fn auto_click_duration_reached(count: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⏳ 最長時間に達したため、自動クリックを停止します（{}回実行）",
+            count
+        ),
+        Language::English => format!(
+            "⏳ The time limit was reached; stopping auto-click ({} runs)",
+            count
+        ),
+    }
+}
+
+/// キャプチャ中オーバーレイの自動クリックの残り時間（例：「残り 12:34」）
+pub fn auto_click_remaining_label(remaining: Duration) -> String {
+    match current_language() {
+        Language::Japanese => format!("残り {}", elapsed_label(remaining)),
+        Language::English => format!("{} left", elapsed_label(remaining)),
+    }
+}
+
+/// 開始時にクリック位置にあったウィンドウが閉じられた・移動した場合のログ（自動クリックを終了する）
+pub fn auto_click_target_window_lost() -> &'static str {
+    localize(
+        "🛑 対象ウィンドウが見つからないため停止しました",
+        "🛑 Stopped because the target window is gone",
+    )
+}
+
+/// セキュアデスクトップ・ロック画面・停止の確認・ユーザー操作のために自動クリックを一時停止した場合のログ
+pub fn auto_click_paused(reason: AutoClickPause, count: u32) -> String {
+    match (current_language(), reason) {
+        (Language::Japanese, AutoClickPause::SecureDesktop) => format!(
+            "⏸️ UACの確認画面などのセキュアデスクトップは取得できないため、自動クリックを一時停止します（{}回実行、通常の画面に戻ると再開）",
+            count
+        ),
+        (Language::Japanese, AutoClickPause::SessionLocked) => format!(
+            "⏸️ 画面がロックされたため、自動クリックを一時停止します（{}回実行、ロック解除後に再開）",
+            count
+        ),
+        (Language::English, AutoClickPause::SecureDesktop) => format!(
+            "⏸️ The secure desktop (such as a UAC prompt) cannot be captured; pausing auto-click ({} runs, resumes when the normal desktop returns)",
+            count
+        ),
+        (Language::English, AutoClickPause::SessionLocked) => format!(
+            "⏸️ The session was locked; pausing auto-click ({} runs, resumes after unlocking)",
+            count
+        ),
+        (Language::Japanese, AutoClickPause::ConfirmingStop) => format!(
+            "⏸️ 停止の確認中のため、自動クリックを一時停止します（{}回実行）",
+            count
+        ),
+        (Language::English, AutoClickPause::ConfirmingStop) => format!(
+            "⏸️ Waiting for the stop confirmation; pausing auto-click ({} runs)",
+            count
+        ),
+        (Language::Japanese, AutoClickPause::UserActive) => format!(
+            "⏸️ マウスの操作を検出したため、自動クリックを一時停止します（{}回実行、操作が止まると再開）",
+            count
+        ),
+        (Language::English, AutoClickPause::UserActive) => format!(
+            "⏸️ Mouse activity detected; pausing auto-click ({} runs, resumes when the mouse is idle)",
+            count
+        ),
+    }
+}
+
+/// 一時停止していた自動クリックを再開した場合のログ
+pub fn auto_click_resumed(reason: AutoClickPause) -> &'static str {
+    match reason {
+        AutoClickPause::ConfirmingStop => localize(
+            "▶️ 停止を取り消したため、自動クリックを再開します",
+            "▶️ Stop cancelled; resuming auto-click",
+        ),
+        AutoClickPause::UserActive => localize(
+            "▶️ マウスの操作が止まったため、自動クリックを再開します",
+            "▶️ The mouse is idle; resuming auto-click",
+        ),
+        AutoClickPause::SecureDesktop | AutoClickPause::SessionLocked => localize(
+            "▶️ 通常の画面に戻ったため、自動クリックを再開します",
+            "▶️ The normal desktop is back; resuming auto-click",
+        ),
+    }
+}
+
+/// 自動クリックの一時停止中に、進行状況ラベルに表示する文字列
+pub fn auto_click_paused_label(reason: AutoClickPause) -> &'static str {
+    match reason {
+        AutoClickPause::ConfirmingStop => {
+            localize("一時停止中（停止の確認中）", "Paused (confirming stop)")
+        }
+        AutoClickPause::UserActive => {
+            localize("一時停止中（操作を検出）", "Paused (mouse activity)")
+        }
+        AutoClickPause::SecureDesktop | AutoClickPause::SessionLocked => localize(
+            "一時停止中（セキュアデスクトップ・ロック中）",
+            "Paused (secure desktop or locked)",
+        ),
+    }
+}
+
+/// ユーザー操作中の一時停止の設定変更のログ
+pub fn pause_on_user_input_changed(enabled: bool, quiet_ms: u32) -> String {
+    match (current_language(), enabled) {
+        (Language::Japanese, true) => format!(
+            "🖐️ マウスを操作している間は自動クリックを一時停止します（操作が止まってから{:.1}秒後に再開）",
+            quiet_ms as f64 / 1000.0
+        ),
+        (Language::Japanese, false) => "🖐️ マウスを操作しても自動クリックを続けます".to_string(),
+        (Language::English, true) => format!(
+            "🖐️ Auto-click will pause while the mouse is in use (resumes {:.1}s after it stops)",
+            quiet_ms as f64 / 1000.0
+        ),
+        (Language::English, false) => {
+            "🖐️ Auto-click will keep running while the mouse is in use".to_string()
+        }
+    }
+}
+
+/// ESCキーで長い自動クリックを停止する前の確認メッセージ（「はい」・Enter・もう一度ESCで停止）
+pub fn esc_stop_confirm_message(progress: u32, max_count: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "自動クリックを停止しますか？\n\n{}/{}回実行済み（残り{}回）です。停止すると途中から再開できません。\n\n「はい」・Enter・もう一度ESCで停止、「いいえ」で続行します。",
+            progress,
+            max_count,
+            max_count.saturating_sub(progress)
+        ),
+        Language::English => format!(
+            "Stop auto-click?\n\n{}/{} runs done ({} remaining). A stopped run cannot be resumed.\n\nYes, Enter or Esc again stops; No continues.",
+            progress,
+            max_count,
+            max_count.saturating_sub(progress)
+        ),
+    }
+}
+
+/// ESCキーによる停止の確認ダイアログのタイトル
+pub fn esc_stop_confirm_title() -> &'static str {
+    localize("自動クリックの停止", "Stop auto-click")
+}
+
+/// セッションのロック通知の登録に失敗した場合のログ（ロック中も自動クリックを続ける）
+pub fn session_notification_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 画面ロックの通知を登録できませんでした（ロック中の自動クリックは入力デスクトップの確認のみで一時停止）: {}",
+            error
+        ),
+        Language::English => format!(
+            "⚠️ Could not register for session lock notifications (auto-click pauses on the input desktop check only): {}",
+            error
+        ),
+    }
+}
+
+/// 自動クリックのスレッド停止のログ
+pub fn auto_click_stopped() -> &'static str {
+    localize(
+        "🛑 自動連続クリック処理（スレッド）を停止しました",
+        "🛑 Auto-click (worker thread) stopped",
+    )
+}
+
+/// 自動クリック完了通知（バルーン）のタイトル
+pub fn auto_click_complete_notification_title() -> &'static str {
+    localize("自動クリック完了", "Auto-click complete")
+}
+
+/// 自動クリック完了通知（バルーン）の本文
+pub fn auto_click_complete_notification(count: u32, elapsed: Duration) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "{}回の自動クリックが終了しました（経過時間 {}）",
+            count,
+            elapsed_label(elapsed)
+        ),
+        Language::English => format!(
+            "Auto-click finished after {} runs (elapsed {})",
+            count,
+            elapsed_label(elapsed)
+        ),
+    }
+}
+
+/// PDF変換完了通知（バルーン）のタイトル
+pub fn pdf_export_complete_notification_title() -> &'static str {
+    localize("PDF変換完了", "PDF export complete")
+}
+
+/// PDF変換完了通知（バルーン）の本文
+pub fn pdf_export_complete_notification(elapsed: Duration) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "PDF変換が完了しました（経過時間 {}）",
+            elapsed_label(elapsed)
+        ),
+        Language::English => format!("PDF export finished (elapsed {})", elapsed_label(elapsed)),
+    }
+}
+
+/// 完了通知のバルーンを表示できず、システム音で代替する場合のログ (notification.rs)
+pub fn notification_balloon_fallback() -> &'static str {
+    localize(
+        "⚠️ 通知領域にバルーンを表示できないため、システム音で通知します",
+        "⚠️ Cannot show a notification balloon; using the system sound instead",
+    )
+}
+
+/// PDF/Aチェックボックスの変更のログ
+pub fn pdf_archive_changed(enabled: bool) -> &'static str {
+    localize(
+        if enabled {
+            "🗄️ PDF変換をアーカイブ用のPDF/A-1b形式で出力します（撮影日時のフッターは付きません）"
+        } else {
+            "📄 PDF変換を通常のPDF形式で出力します"
+        },
+        if enabled {
+            "🗄️ PDF export will produce PDF/A-1b for archiving (no capture-time footer)"
+        } else {
+            "📄 PDF export will produce regular PDFs"
+        },
+    )
+}
+
+/// PDF自動変換チェックボックスの変更のログ
+pub fn auto_pdf_export_changed(enabled: bool) -> &'static str {
+    localize(
+        if enabled {
+            "📑 キャプチャモードの終了時に、保存先フォルダーを自動でPDFに変換します"
+        } else {
+            "📑 キャプチャモード終了時の自動PDF変換をオフにしました"
+        },
+        if enabled {
+            "📑 The save folder will be exported to PDF automatically when capture mode ends"
+        } else {
+            "📑 Automatic PDF export at the end of capture mode is off"
+        },
+    )
+}
+
+/// 経過時間スタンプチェックボックスの変更のログ
+pub fn elapsed_stamp_changed(enabled: bool) -> &'static str {
+    localize(
+        if enabled {
+            "⏱️ キャプチャ画像に撮影時刻と前回からの経過時間を表示します"
+        } else {
+            "⏱️ キャプチャ画像への経過時間の表示をオフにしました"
+        },
+        if enabled {
+            "⏱️ Captures will show the capture time and the time since the previous capture"
+        } else {
+            "⏱️ Elapsed-time stamps on captures are off"
+        },
+    )
+}
+
+/// キャプチャモード終了時の自動PDF変換の開始のログ
+pub fn auto_pdf_export_start(saved_count: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "📑 このキャプチャで{}枚保存したため、保存先フォルダーを自動でPDFに変換します...",
+            saved_count
+        ),
+        Language::English => format!(
+            "📑 {} capture(s) saved in this session; exporting the save folder to PDF...",
+            saved_count
+        ),
+    }
+}
+
+/// 保存した画像がないため自動PDF変換を行わなかった場合のログ
+pub fn auto_pdf_export_skipped() -> &'static str {
+    localize(
+        "📑 このキャプチャで保存した画像がないため、自動PDF変換は行いません",
+        "📑 No captures were saved in this session, so the automatic PDF export was skipped",
+    )
+}
+
+/// キャプチャモード終了時の自動PDF変換が完了した場合のメッセージボックス本文
+pub fn auto_pdf_export_completed() -> &'static str {
+    localize(
+        "キャプチャ終了時の自動PDF変換が正常に完了しました。",
+        "The automatic PDF export at the end of capture mode finished successfully.",
+    )
+}
+
+/// PDF変換中のためキャプチャモードを開始できない場合のログ
+pub fn capture_blocked_by_pdf_export() -> &'static str {
+    localize(
+        "⏳ PDF変換中のため、キャプチャモードを開始できません",
+        "⏳ Capture mode cannot start while a PDF export is running",
+    )
+}
+
+/// 共有UIを使用できない環境の場合のログ（「共有」ボタンは無効のまま）
+pub fn share_ui_unsupported() -> &'static str {
+    localize(
+        "ℹ️ この環境ではWindowsの共有機能を使用できないため、「共有」ボタンは無効です",
+        "ℹ️ Windows sharing is not available on this system; the Share button is disabled",
+    )
+}
+
+/// 共有UIを表示した場合のログ
+pub fn share_ui_opened(file_name: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("📤 {} を共有します（共有先を選択してください）", file_name),
+        Language::English => format!("📤 Sharing {} (choose where to send it)", file_name),
+    }
+}
+
+/// 共有UIを表示できなかった場合のログ（エクスプローラーで代替）
+pub fn share_ui_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 共有画面を表示できませんでした。エクスプローラーでファイルを表示します: {}",
+            error
+        ),
+        Language::English => format!(
+            "⚠️ Could not open the share window; showing the file in Explorer instead: {}",
+            error
+        ),
+    }
+}
+
+/// 共有するファイルが削除されていた場合のログ
+pub fn share_file_missing(file_name: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ 共有するファイルが見つかりません: {}", file_name),
+        Language::English => format!("❌ The file to share was not found: {}", file_name),
+    }
+}
+
+/// 共有の代替としてエクスプローラーでフォルダーを開けなかった場合のログ
+pub fn share_folder_open_failed(path: &Path) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ フォルダーを開けませんでした: {}", path.display()),
+        Language::English => format!("❌ Could not open the folder: {}", path.display()),
+    }
+}
+
+/// ドロップしたフォルダーを保存先に設定した場合のログ
+pub fn folder_dropped(folder: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("📁 保存先フォルダーを変更しました: {}", folder),
+        Language::English => format!("📁 Save folder changed: {}", folder),
+    }
+}
+
+/// 複数の項目がドロップされ、保存先に使用しなかった項目がある場合のログ
+pub fn folder_drop_ignored(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "ℹ️ 複数の項目がドロップされたため、最初のフォルダーのみ使用します（{}件は無視）",
+            count
+        ),
+        Language::English => format!(
+            "ℹ️ Several items were dropped; using the first folder only ({} ignored)",
+            count
+        ),
+    }
+}
+
+/// ドロップした項目からフォルダーを決められなかった場合のログ
+pub fn folder_drop_no_folder() -> &'static str {
+    localize(
+        "⚠️ ドロップした項目に保存先にできるフォルダーがありません",
+        "⚠️ None of the dropped items can be used as the save folder",
+    )
+}
+
+/// ドロップしたフォルダーに書き込めない場合のログ
+pub fn folder_drop_not_writable(folder: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "❌ フォルダーに書き込めないため、保存先を変更しませんでした: {}",
+            folder
+        ),
+        Language::English => format!(
+            "❌ The folder is not writable; the save folder was not changed: {}",
+            folder
+        ),
+    }
+}
+
+/// キャプチャ中・変換中などでドロップを受け付けない場合のログ
+pub fn folder_drop_unavailable() -> &'static str {
+    localize(
+        "⚠️ 現在は保存先フォルダーを変更できません（キャプチャ・変換の終了後にドロップしてください）",
+        "⚠️ The save folder cannot be changed right now (drop it again after capturing or exporting ends)",
+    )
+}
+
+/// 最近使ったフォルダーのメニューで保存先を変更した場合のログ
+pub fn recent_folder_selected(folder: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("📁 最近使ったフォルダーを保存先にしました: {}", folder),
+        Language::English => format!("📁 Save folder changed to a recent folder: {}", folder),
+    }
+}
+
+/// 最近使ったフォルダーのメニューで選んだフォルダーが見つからない場合のログ（一覧から削除する）
+pub fn recent_folder_missing(folder: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ フォルダーが見つからないため、最近使ったフォルダーから削除します: {}",
+            folder
+        ),
+        Language::English => format!(
+            "⚠️ The folder no longer exists; removing it from recent folders: {}",
+            folder
+        ),
+    }
+}
+
+/// 最近使ったフォルダーから見つからないフォルダーを削除した場合のログ
+pub fn recent_folders_pruned(count: usize) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🧹 見つからないフォルダーを最近使ったフォルダーから削除しました（{}件）",
+            count
+        ),
+        Language::English => format!("🧹 Removed {} missing folder(s) from recent folders", count),
+    }
+}
+
+/// 最近使ったフォルダーのメニュー：記録がない場合の項目
+pub fn recent_folders_empty() -> &'static str {
+    localize(
+        "（最近使ったフォルダーはありません）",
+        "(No recent folders)",
+    )
+}
+
+/// 最近使ったフォルダーのメニュー：見つからないフォルダーを一覧から削除する項目
+pub fn recent_folders_prune() -> &'static str {
+    localize(
+        "見つからないフォルダーを一覧から削除",
+        "Remove missing folders from the list",
+    )
+}
+
+/// 保存先フォルダーのテンプレートを展開したパスが使用できない場合のログ（ピクチャフォルダーに保存）
+pub fn save_folder_template_invalid(template: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 保存先フォルダーのテンプレートが無効なため、ピクチャフォルダーに保存します: {}",
+            template
+        ),
+        Language::English => format!(
+            "⚠️ The save folder template is invalid; saving to the Pictures folder instead: {}",
+            template
+        ),
+    }
+}
+
+/// サムネイルのファイルをドラッグ＆ドロップした場合のログ
+pub fn thumbnail_dragged(file_path: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("📎 ドラッグ＆ドロップしました: {}", file_path),
+        Language::English => format!("📎 Dropped: {}", file_path),
+    }
+}
+
+/// サムネイルのファイルをドラッグ＆ドロップできなかった場合のログ
+pub fn thumbnail_drag_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ドラッグ＆ドロップに失敗しました: {}", error),
+        Language::English => format!("❌ Drag and drop failed: {}", error),
+    }
+}
+
+/// ドラッグするサムネイルのファイルが削除されていた場合のログ
+pub fn thumbnail_drag_file_missing(file_path: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ドラッグするファイルが見つかりません: {}", file_path),
+        Language::English => format!("❌ The file to drag was not found: {}", file_path),
+    }
+}
+
+/// サムネイルのファイルを既定のアプリで開けなかった場合のログ
+pub fn thumbnail_open_failed(file_path: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ ファイルを開けませんでした: {}", file_path),
+        Language::English => format!("❌ Could not open the file: {}", file_path),
+    }
+}
+
+/// 完了通知チェックボックスの変更のログ
+pub fn completion_notify_changed(enabled: bool) -> &'static str {
+    localize(
+        if enabled {
+            "🔔 自動クリック・PDF変換の完了を通知領域で知らせます"
+        } else {
+            "🔕 完了の通知をオフにしました"
+        },
+        if enabled {
+            "🔔 Auto-click and PDF export completion will be shown in the notification area"
+        } else {
+            "🔕 Completion notifications turned off"
+        },
+    )
+}
+
+/// 経過時間の表示（1時間未満は `m:ss`、以上は `h:mm:ss`）
+fn elapsed_label(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// 最大クリック数到達のメッセージボックス本文
+pub fn auto_click_limit_reached(limit: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "⚠️ 連続クリックが最大クリック数({})に達しました。連続クリックを停止します。",
+            limit
+        ),
+        Language::English => format!(
+            "⚠️ Auto-click reached the maximum number of clicks ({}). Auto-click will stop.",
+            limit
+        ),
+    }
+}
+
+/// 自動クリック警告のメッセージボックスタイトル
+pub fn auto_click_warning_title() -> &'static str {
+    localize("自動クリック警告", "Auto-click warning")
+}
+
+/// 自動クリック実行のログ
+pub fn auto_click_executed(x: i32, y: i32, count: u32, max_count: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🖱️ 自動クリック実行: マウス位置({}, {}) {}/{}回目",
+            x, y, count, max_count
+        ),
+        Language::English => format!(
+            "🖱️ Auto-click: mouse position ({}, {}) {}/{}",
+            x, y, count, max_count
+        ),
+    }
+}
+
+/// 自動キャプチャ実行のログ
+pub fn auto_capture_executed(count: u32, max_count: u32) -> String {
+    match current_language() {
+        Language::Japanese => format!("⏱️ 自動キャプチャ実行: {}/{}回目", count, max_count),
+        Language::English => format!("⏱️ Auto-capture: {}/{}", count, max_count),
+    }
+}
+
+/// クリック実行エラーのログ
+pub fn click_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ クリック実行エラー: {}", error),
+        Language::English => format!("❌ Click failed: {}", error),
+    }
+}
+
+/// キャプチャ要求の送信エラーのログ
+pub fn capture_request_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ キャプチャ要求の送信エラー: {}", error),
+        Language::English => format!("❌ Failed to send the capture request: {}", error),
+    }
+}
+
+/// ダイアログへのメッセージ送信エラーのログ
+pub fn post_message_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("❌ メッセージ送信エラー: {}", error),
+        Language::English => format!("❌ Failed to post a message: {}", error),
+    }
+}
+
+// ===== オーバーレイ (overlay/*.rs) =====
+
+/// 自動クリック進行状況ラベル（「自動クリック中」「自動キャプチャ中」）
+pub fn auto_click_progress_label(mode: AutoClickMode) -> &'static str {
+    match mode {
+        AutoClickMode::ClickAndCapture => localize("自動クリック中", "Auto-clicking"),
+        AutoClickMode::CaptureOnly => localize("自動キャプチャ中", "Auto-capturing"),
+    }
+}
+
+/// 定期キャプチャの経過時間ラベル（例：「定期キャプチャ中 / 00:12:30 · 42枚」）
+pub fn scheduled_capture_progress_label(
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+    captured: u32,
+) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "定期キャプチャ中\n{:02}:{:02}:{:02} · {}枚",
+            hours, minutes, seconds, captured
+        ),
+        Language::English => format!(
+            "Scheduled capture\n{:02}:{:02}:{:02} · {} shots",
+            hours, minutes, seconds, captured
+        ),
+    }
+}
+
+/// 2領域選択時のバッジ
+pub fn dual_area_badge() -> &'static str {
+    localize("2領域", "2 areas")
+}
+
+/// 保存完了トーストのメッセージ
+pub fn saved_toast(file_name: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("保存しました {}", file_name),
+        Language::English => format!("Saved {}", file_name),
+    }
+}
+
+/// 選択範囲の強調方法：外側を暗く
+pub fn selection_style_dim_outside() -> &'static str {
+    localize("選択範囲の外側を暗く", "Dim outside the selection")
+}
+
+/// 選択範囲の強調方法：内側を暗く
+pub fn selection_style_dim_inside() -> &'static str {
+    localize("選択範囲の内側を暗く", "Dim inside the selection")
+}
+
+/// 選択範囲の強調方法：境界線のみ
+pub fn selection_style_border_only() -> &'static str {
+    localize("境界線のみ", "Border only")
+}
+
+/// 選択範囲の強調方法の切り替え（Tabキー）のログ
+pub fn selection_style_changed(label: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("🔦 選択範囲の表示: {}", label),
+        Language::English => format!("🔦 Selection display: {}", label),
+    }
+}
+
+/// 三分割グリッドの表示切り替えのログ
+pub fn selection_grid_toggled(enabled: bool) -> &'static str {
+    if enabled {
+        localize(
+            "📐 三分割グリッドを表示します",
+            "📐 Rule-of-thirds grid shown",
+        )
+    } else {
+        localize(
+            "📐 三分割グリッドを非表示にします",
+            "📐 Rule-of-thirds grid hidden",
+        )
+    }
+}
+
+/// キャプチャオーバーレイの表示方法：表示
+pub fn overlay_visibility_visible() -> &'static str {
+    localize("表示", "Show")
+}
+
+/// キャプチャオーバーレイの表示方法：半透明
+pub fn overlay_visibility_semi_transparent() -> &'static str {
+    localize("半透明", "Translucent")
+}
+
+/// キャプチャオーバーレイの表示方法：非表示
+pub fn overlay_visibility_hidden() -> &'static str {
+    localize("非表示", "Hide")
+}
+
+/// キャプチャオーバーレイの表示方法変更のログ
+pub fn overlay_visibility_changed(label: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("👁️ キャプチャ中の表示を変更しました: {}", label),
+        Language::English => format!("👁️ Capture indicator changed: {}", label),
+    }
+}
+
+/// エリア選択の暗転の濃さ変更のログ
+pub fn dim_opacity_changed(percent: u8) -> String {
+    match current_language() {
+        Language::Japanese => format!("🌓 エリア選択の暗転の濃さを変更しました: {}%", percent),
+        Language::English => format!("🌓 Area selection dimming changed: {}%", percent),
+    }
+}
+
+/// エリア選択の境界線の色：赤
+pub fn border_color_red() -> &'static str {
+    localize("赤", "Red")
+}
+
+/// エリア選択の境界線の色：緑
+pub fn border_color_green() -> &'static str {
+    localize("緑", "Green")
+}
+
+/// エリア選択の境界線の色：青
+pub fn border_color_blue() -> &'static str {
+    localize("青", "Blue")
+}
+
+/// エリア選択の境界線の色：黄
+pub fn border_color_yellow() -> &'static str {
+    localize("黄", "Yellow")
+}
+
+/// エリア選択の境界線の色：黒
+pub fn border_color_black() -> &'static str {
+    localize("黒", "Black")
+}
+
+/// エリア選択の境界線のスタイル変更のログ
+pub fn border_style_changed(color_label: &str, width: u8, halo: bool) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🖍️ エリア選択の境界線を変更しました: {} {}px{}",
+            color_label,
+            width,
+            if halo {
+                "（白い縁取りあり）"
+            } else {
+                ""
+            }
+        ),
+        Language::English => format!(
+            "🖍️ Area selection border changed: {} {}px{}",
+            color_label,
+            width,
+            if halo { " (white outline)" } else { "" }
+        ),
+    }
+}
+
+/// 画面固定チェックボックスの変更のログ
+pub fn freeze_screen_changed(enabled: bool) -> &'static str {
+    localize(
+        if enabled {
+            "🧊 エリア選択中は画面を固定します（次のエリア選択から反映）"
+        } else {
+            "🧊 エリア選択中の画面の固定を解除しました"
+        },
+        if enabled {
+            "🧊 The screen will be frozen while selecting an area (from the next selection)"
+        } else {
+            "🧊 The screen will no longer be frozen while selecting an area"
+        },
+    )
+}
+
+/// エリア選択の開始時に画面を固定できた場合のログ
+pub fn freeze_screen_succeeded() -> &'static str {
+    localize(
+        "🧊 画面を固定しました（キャプチャは実際の画面から取得します）",
+        "🧊 Screen frozen (captures are still taken from the live screen)",
+    )
+}
+
+/// エリア選択の開始時に画面を固定できなかった場合のログ
+pub fn freeze_screen_failed() -> &'static str {
+    localize(
+        "⚠️ 画面の固定に失敗しました。通常の表示で選択を続けます",
+        "⚠️ Failed to freeze the screen. Continuing the selection on the live screen",
+    )
+}
+
+/// オーバーレイのフォント選択のログ
+pub fn overlay_font_selected(family_name: &str, size_px: f32) -> String {
+    match current_language() {
+        Language::Japanese => format!(
+            "🔤 オーバーレイのフォント: {} ({:.0}px)",
+            family_name, size_px
+        ),
+        Language::English => format!("🔤 Overlay font: {} ({:.0}px)", family_name, size_px),
+    }
+}
+
+/// 汎用サンセリフフォントの表示名
+pub fn generic_sans_serif() -> &'static str {
+    localize("汎用サンセリフ", "generic sans-serif")
+}
+
+/// オーバーレイのフォント作成失敗のログ
+pub fn overlay_font_failed() -> &'static str {
+    localize(
+        "❌ オーバーレイのフォントを作成できませんでした（ラベルは表示されません）",
+        "❌ Could not create the overlay font (labels will not be shown)",
+    )
+}
+
+// ===== 配色テーマ (theme.rs) =====
+
+/// テーマ設定：自動
+pub fn theme_auto() -> &'static str {
+    localize("自動", "Auto")
+}
+
+/// テーマ設定：ライト
+pub fn theme_light() -> &'static str {
+    localize("ライト", "Light")
+}
+
+/// テーマ設定：ダーク
+pub fn theme_dark() -> &'static str {
+    localize("ダーク", "Dark")
+}
+
+/// OSのアプリモードの表示名
+pub fn system_theme_label(is_dark: bool) -> &'static str {
+    if is_dark { theme_dark() } else { theme_light() }
+}
+
+/// 配色テーマ変更のログ
+pub fn theme_changed(label: &str) -> String {
+    match current_language() {
+        Language::Japanese => format!("🎨 配色テーマを変更しました: {}", label),
+        Language::English => format!("🎨 Color theme changed: {}", label),
+    }
+}
+
+// ===== キャプチャトリガー (hook/mouse.rs) =====
+
+/// キャプチャトリガー：左クリック
+pub fn capture_trigger_left() -> &'static str {
+    localize("左", "Left")
+}
+
+/// キャプチャトリガー：中クリック
+pub fn capture_trigger_middle() -> &'static str {
+    localize("中", "Middle")
+}
+
+/// キャプチャトリガー：左クリックと中クリック
+pub fn capture_trigger_left_and_middle() -> &'static str {
+    localize("左+中", "Left+Middle")
+}
+
+/// キャプチャトリガー：右クリック
+pub fn capture_trigger_right() -> &'static str {
+    localize("右", "Right")
+}
+
+// ===== キーボード操作 =====
+
+/// 処理の実行中にEscキーで閉じる場合の確認メッセージ
+pub fn close_while_busy_message() -> &'static str {
+    localize(
+        "キャプチャ・定期キャプチャ・変換処理などを実行中です。\n中止してアプリケーションを終了しますか？",
+        "A capture, scheduled capture or export is in progress.\nStop it and exit the application?",
+    )
+}
+
+/// 処理の実行中にEscキーで閉じる場合の確認ダイアログのタイトル
+pub fn close_while_busy_title() -> &'static str {
+    localize("終了確認", "Confirm exit")
+}
+
+/// Enterキーでキャプチャモードを開始するログ
+pub fn enter_key_capture_start() -> &'static str {
+    localize(
+        "⌨️ Enterキーでキャプチャモードを開始します",
+        "⌨️ Enter: starting capture mode",
+    )
+}
+
+/// Alt+A（エリア選択）のログ
+pub fn accelerator_area_select() -> &'static str {
+    localize("⌨️ Alt+A: エリア選択", "⌨️ Alt+A: Select area")
+}
+
+/// Alt+C（キャプチャ開始）のログ
+pub fn accelerator_capture_start() -> &'static str {
+    localize("⌨️ Alt+C: キャプチャ開始", "⌨️ Alt+C: Start capture")
+}
+
+/// Alt+P（PDF変換）のログ
+pub fn accelerator_pdf_export() -> &'static str {
+    localize("⌨️ Alt+P: PDF変換", "⌨️ Alt+P: Export PDF")
+}
+
+// ===== ツールチップ =====
+
+/// ツールチップコントロールを作成できなかった場合のログ
+pub fn tooltip_create_failed(error: &dyn std::fmt::Display) -> String {
+    match current_language() {
+        Language::Japanese => format!("⚠️ ツールチップの作成に失敗しました: {}", error),
+        Language::English => format!("⚠️ Failed to create the tooltips: {}", error),
+    }
+}
+
+/// ダイアログコントロールのツールチップ文字列（対象外のコントロールは `None`）
+///
+/// # 引数
+/// * `control_id` - コントロールID（`IDC_*`）
+/// * `quick_capture_hotkey` - クイックキャプチャのホットキー（設定で変更できるため表示に埋め込む）
+/// * `repeat_capture_hotkey` - 再キャプチャのホットキー（同上）
+pub fn control_tooltip(
+    control_id: i32,
+    quick_capture_hotkey: Hotkey,
+    repeat_capture_hotkey: Hotkey,
+) -> Option<String> {
+    if control_id == IDC_CAPTURE_START_BUTTON {
+        return Some(match current_language() {
+            Language::Japanese => format!(
+                "キャプチャモードを開始します（クリックするたびに選択範囲を保存）\n\
+                Alt+C / Enter: 開始 / ESC: キャプチャモードを終了\n\
+                {}: 前回の領域をすぐに再キャプチャ",
+                repeat_capture_hotkey
+            ),
+            Language::English => format!(
+                "Start capture mode (the selected area is saved on every click)\n\
+                Alt+C / Enter: start / ESC: exit capture mode\n\
+                {}: capture the last area again now",
+                repeat_capture_hotkey
+            ),
+        });
+    }
+    if control_id == IDC_WINDOW_CAPTURE_CHECKBOX {
+        return Some(match current_language() {
+            Language::Japanese => format!(
+                "クリックしたウィンドウだけをPNGで保存します\n\
+                {}: カーソル下のウィンドウをすぐにキャプチャ",
+                quick_capture_hotkey
+            ),
+            Language::English => format!(
+                "Save only the clicked window as PNG\n\
+                {}: capture the window under the cursor now",
+                quick_capture_hotkey
+            ),
+        });
+    }
+    let text = match control_id {
+        IDC_PATH_EDIT => localize(
+            "キャプチャ画像の保存先フォルダー（エクスプローラーからフォルダーをドロップして変更できます）",
+            "Folder where captured images are saved (drop a folder from Explorer to change it)",
+        ),
+        IDC_BROWSE_BUTTON => localize(
+            "保存先フォルダーを選択します\n右クリック: 最近使ったフォルダーから選択",
+            "Choose the folder to save captures in\nRight-click: choose from recent folders",
+        ),
+        IDC_AREA_SELECT_BUTTON => localize(
+            "キャプチャする範囲をドラッグで選択します（Alt+A）\n\
+            Shift+ドラッグ: 2つ目の領域 / Ctrl+ドラッグ: マスク領域\n\
+            Tab: 強調方法の切り替え / G: 三分割グリッド / ESC: 選択を終了",
+            "Drag to select the area to capture (Alt+A)\n\
+            Shift+drag: second area / Ctrl+drag: mask area\n\
+            Tab: switch highlight style / G: thirds grid / ESC: finish selecting",
+        ),
+        IDC_EXPORT_PDF_BUTTON => localize(
+            "保存フォルダーの画像をファイル名順にPDFにまとめます（Alt+P）",
+            "Combine the folder's images into a PDF in file name order (Alt+P)",
+        ),
+        IDC_CLOSE_BUTTON => localize(
+            "アプリケーションを終了します（ESC）",
+            "Exit the application (ESC)",
+        ),
+        IDC_SCALE_COMBO => localize(
+            "保存する画像の大きさ（画面サイズに対する縮小率）",
+            "Size of the saved image (scale relative to the screen)",
+        ),
+        IDC_TARGET_WIDTH_EDIT => localize(
+            "画像サイズ調整で「幅指定」を選んだときの出力幅（px）",
+            "Output width in pixels when the size is set to a fixed width",
+        ),
+        IDC_QUALITY_COMBO => localize(
+            "JPEGの画質（高いほど鮮明・ファイルサイズ大）",
+            "JPEG quality (higher is sharper but larger)",
+        ),
+        IDC_PDF_SIZE_COMBO => localize(
+            "PDF1ファイルあたりのサイズ上限（超えると分割）",
+            "Maximum size per PDF file (split when exceeded)",
+        ),
+        IDC_LOG_EDIT => localize("直近の処理状況", "Latest status message"),
+        IDC_AUTO_CLICK_CHECKBOX => localize(
+            "キャプチャモード開始時に、指定間隔・回数で自動的にクリックします\n\
+            ESC: 自動クリックを中止",
+            "Click automatically at the given interval and count in capture mode\n\
+            ESC: stop auto click",
+        ),
+        IDC_AUTO_CLICK_INTERVAL_COMBO => localize(
+            "自動クリックの間隔（250ms〜5秒）",
+            "Auto click interval (250 ms to 5 seconds)",
+        ),
+        IDC_AUTO_CLICK_COUNT_EDIT => localize("自動クリックの回数", "Number of auto clicks"),
+        IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX => localize(
+            "クリックせずにキャプチャだけを繰り返します",
+            "Repeat captures without clicking",
+        ),
+        IDC_AUTO_CLICK_DURATION_COMBO => localize(
+            "自動クリックを続ける最長の時間（回数と先に達した方で停止）",
+            "Longest time to keep auto-clicking (stops at the count or the time, whichever comes first)",
+        ),
+        IDC_CAPTURE_FORMAT_COMBO => localize("保存する画像の形式", "Image format to save"),
+        IDC_EXPORT_GIF_BUTTON => localize(
+            "保存フォルダーの画像をアニメーションGIFにします",
+            "Turn the folder's images into an animated GIF",
+        ),
+        IDC_EXPORT_ZIP_BUTTON => localize(
+            "保存フォルダーの画像をZIPにまとめます（実行中は中止）",
+            "Pack the folder's images into a ZIP (click again to cancel)",
+        ),
+        IDC_EXPORT_CONTACT_SHEET_BUTTON => localize(
+            "保存フォルダーの画像をサムネイルの一覧画像（contactsheet.png）にまとめます",
+            "Tile the folder's images into a contact sheet (contactsheet.png)",
+        ),
+        IDC_SHARE_BUTTON => localize(
+            "最後に保存したキャプチャを、Windowsの共有機能でTeams・メールなどに送ります",
+            "Send the last saved capture to Teams, Mail, etc. with Windows sharing",
+        ),
+        IDC_RESAVE_BUTTON => localize(
+            "保存に失敗したキャプチャをもう一度保存します",
+            "Save the capture that failed to save again",
+        ),
+        IDC_CAPTURE_TRIGGER_COMBO => localize(
+            "キャプチャを実行するマウスボタン（左以外を選ぶと、左クリックは対象アプリでそのまま使えます）",
+            "Mouse button that triggers a capture (pick one other than left to keep left-clicking normally in the target app)",
+        ),
+        IDC_REAL_INPUT_ONLY_CHECKBOX => localize(
+            "他のツールが注入したクリック・キー入力ではキャプチャしません",
+            "Ignore clicks and keys injected by other tools",
+        ),
+        IDC_HIGH_FIDELITY_TEXT_CHECKBOX => localize(
+            "文字のにじみを抑えた高画質で保存します（ファイルサイズ大）",
+            "Save with less blur around text (larger files)",
+        ),
+        IDC_SOUND_CHECKBOX => localize(
+            "シャッター音・完了音を再生します",
+            "Play shutter and completion sounds",
+        ),
+        IDC_SCHEDULE_INTERVAL_COMBO => {
+            localize("定期キャプチャの間隔", "Scheduled capture interval")
+        }
+        IDC_SCHEDULE_COUNT_EDIT => localize(
+            "定期キャプチャの回数（0で無制限）",
+            "Number of scheduled captures (0 for unlimited)",
+        ),
+        IDC_SCHEDULE_BUTTON => localize(
+            "選択範囲を一定間隔で自動キャプチャします（実行中は停止）",
+            "Capture the selected area at a fixed interval (click again to stop)",
+        ),
+        IDC_DUAL_AREA_STACKED_CHECKBOX => localize(
+            "2つの領域を上下に結合して保存します（オフで左右）",
+            "Join two areas vertically (side by side when off)",
+        ),
+        IDC_THUMBNAIL_STRIP => localize(
+            "直近のキャプチャ（クリックでファイルを開く、ドラッグでTeams・フォルダーなどにドロップ）",
+            "Recent captures (click to open the file, drag to drop it into Teams, a folder, etc.)",
+        ),
+        IDC_CAPTURE_METADATA_CHECKBOX => localize(
+            "JPEGに撮影日時を記録し、PDFのページ下に表示します",
+            "Record the capture time in JPEG and print it under each PDF page",
+        ),
+        IDC_THEME_COMBO => localize("ボタンの配色", "Button color theme"),
+        IDC_LANGUAGE_COMBO => localize("表示言語", "Display language"),
+        IDC_OVERLAY_VISIBILITY_COMBO => localize(
+            "キャプチャ中にカーソルに追従するアイコンの表示（保存画像には写りません）",
+            "Cursor-following capture indicator (never appears in saved images)",
+        ),
+        IDC_DIM_OPACITY_COMBO => localize(
+            "エリア選択中に画面を暗くする濃さ",
+            "How strongly the screen is dimmed while selecting an area",
+        ),
+        IDC_BORDER_COLOR_COMBO => localize(
+            "エリア選択の境界線の色（画面と同系色で見えにくい場合に変更）",
+            "Area selection border color (change it if the border blends into the screen)",
+        ),
+        IDC_BORDER_WIDTH_COMBO => localize(
+            "エリア選択の境界線の太さ",
+            "Area selection border thickness",
+        ),
+        IDC_BORDER_HALO_CHECKBOX => localize(
+            "境界線の両側に白い縁取りを付け、どんな背景でも見えるようにします",
+            "Outline the border in white so it stays visible on any background",
+        ),
+        IDC_COMPLETION_NOTIFY_CHECKBOX => localize(
+            "自動クリック・PDF変換が終わったら、通知領域のバルーンで回数と経過時間を知らせます",
+            "Show a notification-area balloon with the count and elapsed time when auto-click or PDF export finishes",
+        ),
+        IDC_HISTORY_BUTTON => localize(
+            "今回保存したキャプチャの一覧を表示します（選択して削除、ドラッグで並べ替え）\n履歴がある場合、PDF変換はこの並び順で行います",
+            "Show this session's captures (select to delete, drag to reorder)\nPDF export follows this order while the history is not empty",
+        ),
+        IDC_PDF_ARCHIVE_CHECKBOX => localize(
+            "PDF変換を長期保存向けのPDF/A-1b形式で出力します（sRGBの色情報・メタデータを埋め込み）\n撮影日時のフッターは付きません",
+            "Export PDFs as PDF/A-1b for long-term archiving (embeds sRGB color and metadata)\nThe capture-time footer is left out",
+        ),
+        IDC_PAUSE_ON_USER_INPUT_CHECKBOX => localize(
+            "ユーザー操作中は一時停止：自動クリックの実行中にマウスを動かすと、操作が止まってから\n\
+            静止時間（既定2秒）が経つまで次のクリックを遅らせます（回数は飛ばさず、その分だけ延びます）",
+            "Pause while you use the mouse: moving the mouse during auto-click delays the next click\n\
+            until the mouse has been idle for a quiet period (2s by default; no runs are skipped)",
+        ),
+        IDC_ELAPSED_STAMP_CHECKBOX => localize(
+            "キャプチャ画像の左上に撮影時刻と前回のキャプチャからの経過時間（例: 14:03:27.512 +12.4s）を焼き込みます\n\
+            キャプチャモード・定期キャプチャの最初の1枚は経過時間の代わりに「first」と表示します",
+            "Burn the capture time and the time since the previous capture (e.g. 14:03:27.512 +12.4s) into the top-left of each image\n\
+            The first capture of a capture mode or scheduled run shows \"first\" instead",
+        ),
+        IDC_AUTO_PDF_EXPORT_CHECKBOX => localize(
+            "キャプチャモードを終了した時に、保存先フォルダーの画像を自動でPDFに変換します\n保存した画像がない場合は変換しません（サイズの上限で分割）",
+            "Export the save folder to PDF automatically when capture mode ends\nSkipped if nothing was saved (split by the size limit)",
+        ),
+        IDC_PROGRESSIVE_JPEG_CHECKBOX => localize(
+            "JPEGをプログレッシブ形式で保存します（Webで読み込み中も全体が段階的に表示されます）\n高精細テキストモードは常にプログレッシブです",
+            "Save JPEGs as progressive (shown gradually while loading on the web)\nHigh-fidelity text mode is always progressive",
+        ),
+        IDC_RESET_COUNTER_BUTTON => localize(
+            "次のキャプチャを0001から保存します（保存フォルダーに連番ファイルが残っている場合はリセットしません）",
+            "Save the next capture as 0001 (not reset while numbered files remain in the save folder)",
+        ),
+        IDC_RENUMBER_BUTTON => localize(
+            "保存フォルダーの連番ファイルを、並び順を変えずに0001から欠番なく振り直します（確認あり）",
+            "Renumber the save folder's numbered files from 0001 without gaps, keeping their order (asks first)",
+        ),
+        IDC_WORK_AREA_BUTTON => localize(
+            "プライマリモニターのタスクバーを除いた範囲をキャプチャエリアに設定します（ドラッグ不要）",
+            "Set the capture area to the primary monitor without the taskbar (no dragging needed)",
+        ),
+        IDC_FREEZE_SCREEN_CHECKBOX => localize(
+            "エリア選択の開始時の画面を静止画として表示します（動画など動き続ける画面の範囲選択用）\nキャプチャは選択した範囲の実際の画面から取得します",
+            "Show a still image of the screen while selecting an area (for videos and animations)\nCaptures are still taken from the live screen",
+        ),
+        _ => return None,
+    };
+    Some(text.to_string())
+}
@@ -12,33 +12,65 @@ WindowsシステムAPIとの連携を担う、アプリケーション全体で
     -   実行ファイルに埋め込まれたアイコンリソースを読み込み、メインダイアログのタイトルバーとタスクバーに設定します。
 2.  **統合ログ表示 (`app_log`)**:
     -   メッセージをコンソール（デバッグ用）とUI上のログ表示ボックスの両方に同期して出力します。
+    -   ダイアログの作成前はコンソールのみに出力し、ワーカースレッドからは `WM_APP_LOG` でメインスレッドに転送します。
 3.  **メッセージボックス表示 (`show_message_box`)**:
     -   Windows標準のメッセージボックスを簡単に表示するためのラッパー関数。UTF-8からUTF-16への文字列変換を内部で処理します。
+4.  **連番ファイルの排他的作成 (`create_next_capture_file`)**:
+    -   保存先の既存ファイルから次の空き連番を求め、`create_new` で作成します。複数インスタンスが同じフォルダーに保存しても上書きしません。
+5.  **再試行付き保存 (`write_capture_file_with_retry`)**:
+    -   OneDrive同期などによる一時的なロックで保存に失敗した場合、100/300/900ms待って再試行します。
+6.  **システムDPIの取得 (`get_system_dpi`)**:
+    -   オーバーレイのラベルフォントの拡大と、撮影情報・PDFのページサイズ計算に使用します。
+7.  **空き容量の取得 (`get_disk_free_space`)**:
+    -   保存先ボリュームの空き容量を `GetDiskFreeSpaceExW` で取得します（自動クリック前の見積もり・保存前の確認）。
+8.  **連番の振り直し (`collect_numbered_capture_files` / `rename_capture_files`)**:
+    -   削除で欠番ができた連番ファイルを、PDF変換と同じ並び順のまま `0001` から振り直します。
+    -   名前の衝突を避けるため一時的な名前を経由し、失敗した場合は元の名前に戻します。
 
 【技術仕様】
 -   **API連携**: `LoadIconW`, `SendMessageW`, `MessageBoxW` などの基本的なWin32 APIを使用。
 -   **状態アクセス**: `AppState` からダイアログハンドル (`dialog_hwnd`) を取得してUIを操作。
+    ワーカースレッドからも呼ばれる `app_log` / `show_message_box` は `AppState::shared_dialog_hwnd` を使用。
 -   **文字列処理**: `encode_utf16` を使用して、Rustの `&str` をWindows APIが要求するUTF-16形式のワイド文字列に変換。
 
 【AI解析用：依存関係】
 - `app_state.rs`: ダイアログハンドルを取得するために使用。
 - `constants.rs`: `IDI_APP_ICON` などのリソースID定義。
 - `main.rs`: `WM_INITDIALOG` 内で `set_application_icon` を呼び出す。
+- `screen_capture.rs`, `window_capture.rs`: 保存時に `create_next_capture_file` で連番ファイルを作成する。
+- `ui/renumber_button_handler.rs`: 「連番整理」ボタンで連番を振り直す。
+- `clickcapture::capture_naming`: 連番ファイル名の作成・解析、次の連番の計算。
+- `clickcapture::pdf_builder`: 連番ファイルの判定（`is_capture_image`）と並び順（`sort_capture_files`）。
 - プロジェクト内のほぼ全てのモジュール: ログ出力のために `app_log` を、ユーザーへの通知のために `show_message_box` を呼び出す。
  */
 
 use crate::{
     app_state::*,
-    constants::{IDC_LOG_EDIT, IDI_APP_ICON},
+    constants::{IDC_LOG_EDIT, IDI_APP_ICON, WM_APP_LOG},
+    strings,
+};
+use clickcapture::capture_naming::{
+    capture_file_name, capture_file_number, first_free_capture_counter,
+};
+use clickcapture::pdf_builder::{is_capture_image, sort_capture_files};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
 };
 use windows::{
     Win32::{
         Foundation::{HINSTANCE, LPARAM, WPARAM},
-        Graphics::Gdi::{InvalidateRect, UpdateWindow},
+        Graphics::Gdi::{
+            GetDC, GetDeviceCaps, InvalidateRect, LOGPIXELSY, ReleaseDC, UpdateWindow,
+        },
+        Storage::FileSystem::GetDiskFreeSpaceExW,
         System::LibraryLoader::GetModuleHandleW,
         UI::WindowsAndMessaging::{
             GetDlgItem, ICON_BIG, ICON_SMALL, LoadIconW, MESSAGEBOX_RESULT, MESSAGEBOX_STYLE,
-            MessageBoxW, SendMessageW, SetWindowTextW, WM_SETICON,
+            MessageBoxW, PostMessageW, SendMessageW, SetWindowTextW, WM_SETICON,
         },
     },
     core::PCWSTR,
@@ -106,6 +138,10 @@ pub fn set_application_icon() {
  * メッセージを標準出力（コンソール）と
  * ダイアログのログ表示テキストボックス（IDC_LOG_EDIT）の両方に同時出力します。
  *
+ * - ダイアログの作成前（`init_app_state` の前）・破棄後は、コンソールのみに出力します。
+ * - ワーカースレッドから呼び出された場合は、テキストボックスを直接操作せず、
+ *   `WM_APP_LOG` でメインスレッドに転送します（`handle_app_log_message` で表示）。
+ *
  * # 使用例
  * ```rust
  * app_log("キャプチャを開始しました");
@@ -117,26 +153,68 @@ pub fn app_log(message: &str) {
     println!("{}", message);
 
     // 出力2: UIテキストボックスへの表示（ユーザー向け）
+    let Some(dialog_hwnd) = AppState::shared_dialog_hwnd() else {
+        // ダイアログが存在しない場合はコンソール出力のみ
+        return;
+    };
+
+    if AppState::is_dialog_thread() {
+        set_log_edit_text(message);
+        return;
+    }
+
+    // ワーカースレッドからはヒープに確保した文字列をメインスレッドに転送（受信側で解放）
+    let message_ptr = Box::into_raw(Box::new(message.to_string()));
     unsafe {
-        let app_state = AppState::get_app_state_ref();
+        if PostMessageW(
+            Some(*dialog_hwnd),
+            WM_APP_LOG,
+            WPARAM(0),
+            LPARAM(message_ptr as isize),
+        )
+        .is_err()
+        {
+            // 送信できなかった場合は受信側で解放されないため、ここで解放
+            drop(Box::from_raw(message_ptr));
+        }
+    }
+}
 
-        if let Some(dialog_hwnd) = app_state.dialog_hwnd {
-            // ログ表示用テキストボックスコントロールを取得
-            if let Ok(log_edit) = GetDlgItem(Some(*dialog_hwnd), IDC_LOG_EDIT) {
-                // UTF-8からUTF-16へ変換し、null終端を追加
-                let message_wide: Vec<u16> =
-                    message.encode_utf16().chain(std::iter::once(0)).collect();
+/**
+ * ワーカースレッドから転送されたログ（`WM_APP_LOG`）をテキストボックスに表示する
+ *
+ * # 引数
+ * * `lparam` - `app_log` が `Box::into_raw` で確保した `String` へのポインタ（ここで解放します）
+ */
+pub fn handle_app_log_message(lparam: LPARAM) {
+    let message_ptr = lparam.0 as *mut String;
+    if message_ptr.is_null() {
+        return;
+    }
+    let message = unsafe { Box::from_raw(message_ptr) };
+    set_log_edit_text(&message);
+}
 
-                // テキストボックスにメッセージを設定（最新メッセージで上書き）
-                let _ = SetWindowTextW(log_edit, PCWSTR(message_wide.as_ptr()));
+/// ログ表示テキストボックスにメッセージを設定する（メインスレッドからのみ呼び出す）
+fn set_log_edit_text(message: &str) {
+    let Some(dialog_hwnd) =
+        AppState::try_get_app_state_ref().and_then(|app_state| app_state.dialog_hwnd)
+    else {
+        return;
+    };
 
-                // 強制的な再描画を実行してUI更新を確実にする
-                let _ = InvalidateRect(Some(log_edit), None, true); // コントロールを無効化
-                let _ = UpdateWindow(log_edit); // 即座に再描画を実行
-            }
-        } else {
-            // ダイアログハンドルが無効な場合はデフォルト
-            eprintln!("❌ メッセージボックス表示エラー: ダイアログハンドルが無効です。");
+    unsafe {
+        // ログ表示用テキストボックスコントロールを取得
+        if let Ok(log_edit) = GetDlgItem(Some(*dialog_hwnd), IDC_LOG_EDIT) {
+            // UTF-8からUTF-16へ変換し、null終端を追加
+            let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+
+            // テキストボックスにメッセージを設定（最新メッセージで上書き）
+            let _ = SetWindowTextW(log_edit, PCWSTR(message_wide.as_ptr()));
+
+            // 強制的な再描画を実行してUI更新を確実にする
+            let _ = InvalidateRect(Some(log_edit), None, true); // コントロールを無効化
+            let _ = UpdateWindow(log_edit); // 即座に再描画を実行
         }
     }
 }
@@ -146,6 +224,7 @@ pub fn app_log(message: &str) {
  *
  * この関数は、`MessageBoxW` APIのラッパーとして機能し、
  * Rustの `&str` をAPIが要求するUTF-16形式に自動的に変換します。
+ * ダイアログの作成前・破棄後は、メッセージをコンソールに出力して `MESSAGEBOX_RESULT(0)` を返します。
  *
  * # 引数
  * * `message_text` - メッセージボックスに表示する本文。
@@ -161,9 +240,14 @@ pub fn show_message_box(
     style: MESSAGEBOX_STYLE,
 ) -> MESSAGEBOX_RESULT {
     unsafe {
-        let app_state = AppState::get_app_state_ref();
-
-        if let Some(hwnd) = app_state.dialog_hwnd {
+        // 初期化前・破棄後もパニックしないよう、AppStateを参照せずダイアログHWNDのみを使用する
+        if let Some(hwnd) = AppState::shared_dialog_hwnd() {
+            // モーダルのメッセージボックスはUIスレッドでのみ表示する（ワーカースレッドはメッセージで依頼）
+            debug_assert!(
+                AppState::is_dialog_thread(),
+                "メッセージボックスをUIスレッド以外から表示しようとしました: {}",
+                title_text
+            );
             // UTF-8からUTF-16へ変換し、null終端を追加
             let message_wide: Vec<u16> = message_text
                 .encode_utf16()
@@ -179,9 +263,279 @@ pub fn show_message_box(
 
             MessageBoxW(Some(*hwnd), message, title, style)
         } else {
-            // ダイアログハンドルが無効な場合はデフォルト
-            eprintln!("❌ メッセージボックス表示エラー: ダイアログハンドルが無効です。");
+            // ダイアログが存在しない場合はコンソールに出力
+            eprintln!("[{}] {}", title_text, message_text);
             MESSAGEBOX_RESULT(0)
         }
     }
 }
+
+/// 連番ファイルの予約時に試行する最大回数（他インスタンスとの競合が続く場合の無限ループ防止）
+const MAX_CAPTURE_FILE_RESERVE_ATTEMPTS: u32 = 1000;
+
+/**
+ * 保存先フォルダーで次に空いている連番を探し、その番号のファイルを排他的に作成する
+ *
+ * 同じフォルダーに対して複数のインスタンス（ネットワーク共有フォルダーを別PCから使用する場合など）が
+ * 保存しても、互いのファイルを上書きしないようにするための関数です。
+ *
+ * # 処理内容
+ * 1. フォルダー内の数字のみのファイル名（`0001.jpg`, `0002.png` など、拡張子は問わない）の最大値を調べ、
+ *    `start_counter` とその次の番号の大きい方から探索を開始します。
+ * 2. `OpenOptions::create_new`（Win32の `CREATE_NEW`）でファイルを作成します。
+ *    作成とチェックが不可分に行われるため、スキャン後に他インスタンスが同じ番号を使った場合は
+ *    `AlreadyExists` となり、次の番号で再試行します。
+ *
+ * # 引数
+ * * `save_dir` - 保存先フォルダー（存在している必要があります）。
+ * * `extension` - ファイルの拡張子（`jpg`, `png` など）。
+ * * `start_counter` - このインスタンスが次に使用する予定の連番（`capture_file_counter`）。
+ *
+ * # 戻り値
+ * * `Ok((counter, file_path, file))` - 予約した連番、ファイルパス、書き込み用に開いたファイル。
+ * * `Err(e)` - フォルダーの読み取りやファイル作成に失敗した場合。
+ */
+pub fn create_next_capture_file(
+    save_dir: &Path,
+    extension: &str,
+    start_counter: u32,
+) -> std::io::Result<(u32, PathBuf, File)> {
+    let max_existing = max_capture_file_number(save_dir)?;
+
+    let mut counter = first_free_capture_counter(start_counter, max_existing);
+    for _ in 0..MAX_CAPTURE_FILE_RESERVE_ATTEMPTS {
+        let file_path = save_dir.join(capture_file_name(counter, extension));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&file_path)
+        {
+            Ok(file) => return Ok((counter, file_path, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                println!(
+                    "連番 {:04} は他のプロセスが使用済みのため次の番号を試行します",
+                    counter
+                );
+                counter += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(std::io::Error::new(
+        ErrorKind::AlreadyExists,
+        strings::no_free_file_counter(start_counter, MAX_CAPTURE_FILE_RESERVE_ATTEMPTS),
+    ))
+}
+
+/// 保存先フォルダー内の連番ファイル（拡張子は問わない）の最大の番号を返す（連番ファイルがない場合は `None`）
+pub fn max_capture_file_number(save_dir: &Path) -> std::io::Result<Option<u32>> {
+    Ok(fs::read_dir(save_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| capture_file_number(&entry.path()))
+        .max())
+}
+
+/// 連番の振り直しで一時的に使用するファイル名の接頭辞（数字のみではないため連番の検索対象外）
+const RENUMBER_TEMP_PREFIX: &str = "renumber_tmp_";
+
+/**
+ * 保存先フォルダーの連番ファイル（`0001.jpg` など）を連番順に収集する
+ *
+ * PDF変換と同じ並び順（`sort_capture_files`）で返します。
+ * 対象はPDF変換の対象画像（JPEG / WebP / AVIF / BMP）とウィンドウ単体キャプチャのPNGで、
+ * ファイル名が数字のみのものに限ります（別名で保存したファイルは振り直さない）。
+ */
+pub fn collect_numbered_capture_files(save_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(save_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && capture_file_number(path).is_some())
+        .filter(|path| {
+            is_capture_image(path)
+                || path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+        })
+        .collect();
+    sort_capture_files(&mut files);
+    Ok(files)
+}
+
+/**
+ * 連番の振り直しを実行する
+ *
+ * 変更後の名前が他のファイルの変更前の名前と重なる場合があるため（`0003.jpg` → `0002.jpg` と
+ * `0002.png` → `0001.png` の入れ替わりなど）、一度すべて一時的な名前に変更してから、変更後の名前に変更します。
+ * 途中で失敗した場合（ファイルが開かれているなど）は、変更済みのファイルを元の名前に戻してエラーを返します。
+ *
+ * # 引数
+ * * `renames` - `plan_capture_file_renumbering` で求めた（変更前, 変更後）のパス
+ */
+pub fn rename_capture_files(renames: &[(PathBuf, PathBuf)]) -> std::io::Result<()> {
+    let to_temp: Vec<(PathBuf, PathBuf)> = renames
+        .iter()
+        .enumerate()
+        .map(|(index, (from, to))| {
+            let extension = to.extension().unwrap_or_default().to_string_lossy();
+            let temp =
+                from.with_file_name(format!("{}{}.{}", RENUMBER_TEMP_PREFIX, index, extension));
+            (from.clone(), temp)
+        })
+        .collect();
+    let from_temp: Vec<(PathBuf, PathBuf)> = to_temp
+        .iter()
+        .zip(renames)
+        .map(|((_, temp), (_, to))| (temp.clone(), to.clone()))
+        .collect();
+
+    rename_files_or_rollback(&to_temp)?;
+    if let Err(e) = rename_files_or_rollback(&from_temp) {
+        for (from, temp) in to_temp.iter().rev() {
+            let _ = fs::rename(temp, from);
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// ファイル名を順に変更し、失敗した場合は変更済みのファイルを元に戻す
+///
+/// `fs::rename` は変更後の名前のファイルが既にあると上書きするため、その場合はエラーにします。
+fn rename_files_or_rollback(renames: &[(PathBuf, PathBuf)]) -> std::io::Result<()> {
+    for (done, (from, to)) in renames.iter().enumerate() {
+        let result = if to.exists() {
+            Err(std::io::Error::new(
+                ErrorKind::AlreadyExists,
+                strings::renumber_target_exists(&to.display().to_string()),
+            ))
+        } else {
+            fs::rename(from, to)
+        };
+        if let Err(e) = result {
+            for (from, to) in renames[..done].iter().rev() {
+                let _ = fs::rename(to, from);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// 保存失敗時の再試行待機時間（OneDrive同期クライアントなどによる一時的なロック対策）
+const CAPTURE_WRITE_RETRY_DELAYS_MS: [u64; 3] = [100, 300, 900];
+
+/// 他プロセスによる一時的なロックを示すWin32エラーコード（`ERROR_SHARING_VIOLATION`, `ERROR_LOCK_VIOLATION`）
+const TRANSIENT_WIN32_ERRORS: [i32; 2] = [32, 33];
+
+/**
+ * エンコード済みの画像データを連番ファイルとして保存する（一時的なエラーは待機して再試行）
+ *
+ * `create_next_capture_file` で作成したファイルに書き込みます。共有違反・アクセス拒否など
+ * 一時的と考えられるエラーの場合は、100ms / 300ms / 900ms 待って再試行します。
+ * 書き込み途中で失敗したファイルは削除し、再試行時は空き番号を探し直します。
+ *
+ * # 引数
+ * * `save_dir` - 保存先フォルダー（存在している必要があります）。
+ * * `extension` - ファイルの拡張子（`jpg`, `png` など）。
+ * * `start_counter` - このインスタンスが次に使用する予定の連番（`capture_file_counter`）。
+ * * `data` - 書き込むエンコード済みの画像データ。
+ *
+ * # 戻り値
+ * * `Ok((counter, file_path))` - 保存した連番とファイルパス。
+ * * `Err(e)` - 再試行しても保存できなかった場合、または一時的でないエラーの場合（最後のエラー）。
+ */
+pub fn write_capture_file_with_retry(
+    save_dir: &Path,
+    extension: &str,
+    start_counter: u32,
+    data: &[u8],
+) -> std::io::Result<(u32, PathBuf)> {
+    let mut retry_delays = CAPTURE_WRITE_RETRY_DELAYS_MS.iter();
+    loop {
+        let result = create_next_capture_file(save_dir, extension, start_counter).and_then(
+            |(counter, file_path, mut file)| match file.write_all(data) {
+                Ok(()) => Ok((counter, file_path)),
+                Err(e) => {
+                    // 書きかけのファイルを残さない
+                    drop(file);
+                    let _ = fs::remove_file(&file_path);
+                    Err(e)
+                }
+            },
+        );
+
+        match result {
+            Ok(saved) => return Ok(saved),
+            Err(e) if is_transient_write_error(&e) => match retry_delays.next() {
+                Some(&delay_ms) => {
+                    println!("保存に失敗したため{}ms後に再試行します: {}", delay_ms, e);
+                    thread::sleep(Duration::from_millis(delay_ms));
+                }
+                None => return Err(e),
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 待機して再試行すれば成功する可能性がある（他プロセスによる一時的なロックなど）エラーかを判定する
+fn is_transient_write_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::PermissionDenied | ErrorKind::Interrupted | ErrorKind::TimedOut
+    ) || e
+        .raw_os_error()
+        .is_some_and(|code| TRANSIENT_WIN32_ERRORS.contains(&code))
+}
+
+/// システムDPI（`LOGPIXELSY`）を取得する
+///
+/// プロセスはDPI対応（`SetProcessDPIAware`）のため、表示スケールに応じた値（100%で96、150%で144）になります。
+pub fn get_system_dpi() -> i32 {
+    unsafe {
+        let screen_dc = GetDC(None);
+        let dpi = GetDeviceCaps(Some(screen_dc), LOGPIXELSY);
+        ReleaseDC(None, screen_dc);
+        if dpi > 0 { dpi } else { 96 }
+    }
+}
+
+/// 指定フォルダーがあるボリュームの空き容量（バイト）を取得する
+///
+/// ユーザーごとのクォータを考慮した、呼び出し元が使用できる空き容量を返します。
+/// フォルダーがまだ作成されていない場合は、存在する親フォルダーのボリュームで調べます。
+/// ネットワークドライブに接続できないなどで取得できない場合は `None` を返します。
+pub fn get_disk_free_space(dir: &Path) -> Option<u64> {
+    let existing_dir = dir.ancestors().find(|path| path.exists())?;
+    let dir_wide: Vec<u16> = existing_dir
+        .as_os_str()
+        .to_string_lossy()
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut free_bytes: u64 = 0;
+    unsafe {
+        GetDiskFreeSpaceExW(PCWSTR(dir_wide.as_ptr()), Some(&mut free_bytes), None, None).ok()?;
+    }
+    Some(free_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use windows::Win32::UI::WindowsAndMessaging::MB_OK;
+
+    /// `init_app_state` の前（ダイアログの作成前）でも、ログとメッセージボックスはパニックせずに
+    /// コンソールへ出力する（起動時の設定読み込みやキャプチャ出力先の検証から呼ばれるため）
+    #[test]
+    fn logging_before_init_app_state_falls_back_to_console() {
+        assert!(AppState::shared_dialog_hwnd().is_none());
+        assert!(AppState::try_get_app_state_ref().is_none());
+
+        app_log("init_app_state より前のログ");
+        let result = show_message_box("init_app_state より前のメッセージ", "テスト", MB_OK);
+
+        assert_eq!(result.0, 0);
+    }
+}
@@ -0,0 +1,204 @@
+/*
+============================================================================
+配色テーマモジュール (theme.rs)
+============================================================================
+
+【ファイル概要】
+オーナードローボタンとエリア選択オーバーレイの配色を、Windowsのアプリモード
+（ライト / ダーク）に合わせて切り替えるモジュール。
+テーマはOSの設定（`AppsUseLightTheme` レジストリ値）から自動判定し、
+メインダイアログのテーマコンボボックスでライト / ダークに固定できます。
+
+【主要機能】
+1.  **テーマ設定**: `ThemeSetting`（自動 / ライト / ダーク）
+    -   コンボボックスの項目データとの相互変換（`from_u8`）と表示ラベル（`label`）
+2.  **OSのアプリモード判定**: `detect_system_dark_mode`
+    -   `HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize` の
+        `AppsUseLightTheme` が0の場合にダークモードと判定
+3.  **OS設定変更への追従**: `handle_theme_setting_change`
+    -   `WM_SETTINGCHANGE`（`ImmersiveColorSet`）受信時に判定し直し、`redraw_themed_controls` でボタンを再描画
+4.  **配色の取得**: `is_dark_mode` / `button_palette`
+
+【技術仕様】
+-   **ボタン配色**: `ButtonPalette`（背景・押下時背景・境界線、ホバー・無効状態・押下時の影）
+    -   ライト: #F0F0F0 / #E0E0E0 / #ACACAC（従来の配色）、ホバー #E5F1FB、無効 #F7F7F7 / #D5D5D5
+    -   ダーク: #2B2B2B / #3C3C3C / #5A5A5A、ホバー #454545、無効 #262626 / #3A3A3A
+-   **エリア選択の暗転**: ダーク時は半透明黒の代わりに半透明白で覆う
+    （暗い壁紙・ダークテーマのアプリでは、黒で暗くするより明るく覆う方が選択範囲が見やすい）
+-   **判定失敗時**: レジストリ値がない環境（Windows 10 1809より前など）はライトとして扱う
+-   **設定の保持**: 他の設定と同様に `AppState` に保持（アプリ終了まで有効）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `theme_setting`, `system_dark_mode` フィールドの保持
+-   `ui/icon_button.rs`: `button_palette` でボタンの背景・境界線色を取得
+-   `overlay/area_select_overlay.rs`: `is_dark_mode` で暗転ブラシを選択
+-   `ui/theme_combo_handler.rs`: テーマコンボボックス
+-   `ui/dialog_handler.rs`: `WM_SETTINGCHANGE` の受信
+ */
+
+use windows::{
+    Win32::{
+        Foundation::{COLORREF, ERROR_SUCCESS, HWND, LPARAM},
+        Graphics::Gdi::{RDW_ALLCHILDREN, RDW_ERASE, RDW_INVALIDATE, RedrawWindow},
+        System::Registry::{HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RegGetValueW},
+    },
+    core::{PCWSTR, w},
+};
+
+use crate::{app_state::AppState, strings, system_utils::app_log};
+
+/// テーマコンボボックスの設定値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeSetting {
+    /// OSのアプリモードに従う（デフォルト）
+    #[default]
+    Auto = 0,
+    /// ライトに固定
+    Light = 1,
+    /// ダークに固定
+    Dark = 2,
+}
+
+impl ThemeSetting {
+    /// コンボボックス項目データから復元（不明な値は自動扱い）
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ThemeSetting::Light,
+            2 => ThemeSetting::Dark,
+            _ => ThemeSetting::Auto,
+        }
+    }
+
+    /// コンボボックス表示用ラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeSetting::Auto => strings::theme_auto(),
+            ThemeSetting::Light => strings::theme_light(),
+            ThemeSetting::Dark => strings::theme_dark(),
+        }
+    }
+}
+
+/// オーナードローボタンの配色
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonPalette {
+    /// 通常状態の背景色
+    pub background: COLORREF,
+    /// 押下状態（モード実行中）の背景色
+    pub pressed_background: COLORREF,
+    /// 境界線の色
+    pub border: COLORREF,
+    /// マウスカーソルが乗っている状態の背景色
+    pub hover_background: COLORREF,
+    /// 無効状態の背景色（通常より平坦にして操作できないことを示す）
+    pub disabled_background: COLORREF,
+    /// 無効状態の境界線の色
+    pub disabled_border: COLORREF,
+    /// 押下中の内側の影（境界線の内側、左上に描画）
+    pub pressed_shadow: COLORREF,
+}
+
+/// ライトテーマのボタン配色（従来の配色）
+const LIGHT_BUTTON_PALETTE: ButtonPalette = ButtonPalette {
+    background: COLORREF(0xF0F0F0),
+    pressed_background: COLORREF(0xE0E0E0),
+    border: COLORREF(0xACACAC),
+    hover_background: COLORREF(0xFBF1E5),
+    disabled_background: COLORREF(0xF7F7F7),
+    disabled_border: COLORREF(0xD5D5D5),
+    pressed_shadow: COLORREF(0x9A9A9A),
+};
+
+/// ダークテーマのボタン配色
+const DARK_BUTTON_PALETTE: ButtonPalette = ButtonPalette {
+    background: COLORREF(0x2B2B2B),
+    pressed_background: COLORREF(0x3C3C3C),
+    border: COLORREF(0x5A5A5A),
+    hover_background: COLORREF(0x454545),
+    disabled_background: COLORREF(0x262626),
+    disabled_border: COLORREF(0x3A3A3A),
+    pressed_shadow: COLORREF(0x1A1A1A),
+};
+
+/// 現在の配色がダークかどうか（テーマ設定が自動の場合はOSのアプリモードに従う）
+pub fn is_dark_mode() -> bool {
+    let app_state = AppState::get_app_state_ref();
+    match app_state.theme_setting {
+        ThemeSetting::Auto => app_state.system_dark_mode,
+        ThemeSetting::Light => false,
+        ThemeSetting::Dark => true,
+    }
+}
+
+/// 現在のテーマのボタン配色を取得する
+pub fn button_palette() -> &'static ButtonPalette {
+    if is_dark_mode() {
+        &DARK_BUTTON_PALETTE
+    } else {
+        &LIGHT_BUTTON_PALETTE
+    }
+}
+
+/// OSのアプリモードがダークかどうかをレジストリから判定する
+///
+/// # 戻り値
+/// `AppsUseLightTheme` が0の場合は `true`。値を取得できない場合は `false`（ライト）。
+pub fn detect_system_dark_mode() -> bool {
+    let mut value: u32 = 1;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+            w!("AppsUseLightTheme"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut value as *mut u32 as *mut _),
+            Some(&mut size),
+        )
+    };
+    result == ERROR_SUCCESS && value == 0
+}
+
+/// OS設定変更の通知（`WM_SETTINGCHANGE`）を処理する
+///
+/// アプリモードの切り替え時に送られる `ImmersiveColorSet` の場合のみ判定し直し、
+/// 配色が変わった場合はダイアログ（オーナードローボタン）を再描画します。
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル
+/// * `lparam` - 変更された設定の名前（`PCWSTR`、nullの場合あり）
+pub fn handle_theme_setting_change(hwnd: HWND, lparam: LPARAM) {
+    // ダイアログ初期化前・破棄後にも届くため、AppStateがない場合は何もしない
+    if lparam.0 == 0 || AppState::try_get_app_state_ref().is_none() {
+        return;
+    }
+    let setting_name = unsafe { PCWSTR(lparam.0 as *const u16).to_string() }.unwrap_or_default();
+    if setting_name != "ImmersiveColorSet" {
+        return;
+    }
+
+    let was_dark = is_dark_mode();
+    AppState::get_app_state_mut().system_dark_mode = detect_system_dark_mode();
+    if is_dark_mode() != was_dark {
+        app_log(&strings::theme_changed(strings::system_theme_label(
+            is_dark_mode(),
+        )));
+        redraw_themed_controls(hwnd);
+    }
+}
+
+/// 配色の変更をダイアログと子コントロール（オーナードローボタン）に反映する
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル
+pub fn redraw_themed_controls(hwnd: HWND) {
+    unsafe {
+        let _ = RedrawWindow(
+            Some(hwnd),
+            None,
+            None,
+            RDW_INVALIDATE | RDW_ERASE | RDW_ALLCHILDREN,
+        );
+    }
+}
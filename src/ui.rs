@@ -15,6 +15,7 @@ UIの各関心事（初期化、イベント処理、状態更新、描画など
 
 -   **`draw_icon_button`**:
     オーナードローボタン（アイコン付きボタン）のカスタム描画処理を担当します。
+    無効・押下・ホバー・フォーカスの各状態を描き分けます（ホバーはサブクラス化で追跡）。
 
 -   **`initialize_controls`**:
     ダイアログ起動時（`WM_INITDIALOG`）に、各UIコントロール（コンボボックス、エディットボックス等）を初期化します。
@@ -49,4 +50,36 @@ pub mod quality_combo_handler;
 pub mod dialog_handler;
 pub mod icon_button;
 pub mod folder_manager;
-
+pub mod thumbnail_strip_handler;
+pub mod capture_trigger_combo_handler;
+pub mod window_capture_checkbox_handler;
+pub mod sound_checkbox_handler;
+pub mod high_fidelity_text_checkbox_handler;
+pub mod progressive_jpeg_checkbox_handler;
+pub mod real_input_only_checkbox_handler;
+pub mod capture_format_combo_handler;
+pub mod target_width_edit_handler;
+pub mod schedule_handler;
+pub mod dual_area_layout_checkbox_handler;
+pub mod auto_click_mode_checkbox_handler;
+pub mod gif_export_button_handler;
+pub mod zip_export_button_handler;
+pub mod resave_button_handler;
+pub mod capture_metadata_checkbox_handler;
+pub mod language_combo_handler;
+pub mod theme_combo_handler;
+pub mod contact_sheet_button_handler;
+pub mod tooltip_handler;
+pub mod keyboard_navigation_handler;
+pub mod overlay_settings_handler;
+pub mod completion_notify_checkbox_handler;
+pub mod capture_history_handler;
+pub mod work_area_button_handler;
+pub mod renumber_button_handler;
+pub mod reset_counter_button_handler;
+pub mod pdf_archive_checkbox_handler;
+pub mod auto_pdf_export_checkbox_handler;
+pub mod share_button_handler;
+pub mod auto_click_duration_combo_handler;
+pub mod elapsed_stamp_checkbox_handler;
+pub mod pause_on_user_input_checkbox_handler;
@@ -134,6 +134,16 @@ pub fn initialize_auto_click_checkbox(hwnd: HWND) {
         if let Ok(count_edit) = GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_COUNT_EDIT) {
             let _ = EnableWindow(count_edit, is_checked);
         }
+
+        // 動作モード（キャプチャのみ）チェックボックスの有効/無効制御
+        if let Ok(mode_checkbox) = GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX) {
+            let _ = EnableWindow(mode_checkbox, is_checked);
+        }
+
+        // 最大実行時間コンボボックスの有効/無効制御
+        if let Ok(duration_combo) = GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_DURATION_COMBO) {
+            let _ = EnableWindow(duration_combo, is_checked);
+        }
     }
 }
 
@@ -220,6 +230,14 @@ pub fn handle_auto_click_checkbox_change(hwnd: HWND) {
 ///    - 自動クリックの実行回数を設定（例：5回、10回、無制限等）
 ///    - 自動クリック有効時のみ設定変更可能
 ///
+/// 3. **動作モードチェックボックス** (`IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX`)
+///    - クリックを合成せずにキャプチャのみ繰り返すかを設定
+///    - 自動クリック有効時のみ設定変更可能
+///
+/// 4. **最大実行時間コンボボックス** (`IDC_AUTO_CLICK_DURATION_COMBO`)
+///    - 回数の上限に加えて、自動クリックを停止するまでの時間を設定（なし / 5〜60分）
+///    - 自動クリック有効時のみ設定変更可能
+///
 /// # UI状態の論理
 /// - **自動クリック有効**: 関連コントロールが通常表示、ユーザー操作可能
 /// - **自動クリック無効**: 関連コントロールがグレーアウト、操作不可
@@ -254,5 +272,23 @@ pub fn update_auto_click_controls_state(hwnd: HWND) {
             GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_COUNT_EDIT).unwrap(),
             is_enabled,
         );
+
+        // 動作モード（キャプチャのみ）チェックボックスの有効/無効制御
+        let _ = EnableWindow(
+            GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX).unwrap(),
+            is_enabled,
+        );
+
+        // 最大実行時間コンボボックスの有効/無効制御
+        let _ = EnableWindow(
+            GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_DURATION_COMBO).unwrap(),
+            is_enabled,
+        );
+
+        // ユーザー操作中の一時停止チェックボックスの有効/無効制御
+        let _ = EnableWindow(
+            GetDlgItem(Some(hwnd), IDC_PAUSE_ON_USER_INPUT_CHECKBOX).unwrap(),
+            is_enabled,
+        );
     }
 }
@@ -0,0 +1,127 @@
+/*
+============================================================================
+自動クリック最大実行時間コンボボックスハンドラモジュール (auto_click_duration_combo_handler.rs)
+============================================================================
+
+【ファイル概要】
+自動連続クリックを「最大N分間」で自動停止する時間を設定するコンボボックスを管理するモジュール。
+回数の上限（`IDC_AUTO_CLICK_COUNT_EDIT`）に加えて時間でも止められるようにし、
+回数と時間のどちらか先に達した方で自動クリックを終了します。
+
+【主要機能】
+1.  **コンボボックス初期化**: `initialize_auto_click_duration_combo`
+    -   なし / 5分 / 15分 / 30分 / 60分 を提供（デフォルト：なし）
+2.  **選択変更処理**: `handle_auto_click_duration_combo_change`
+    -   選択した時間を `AutoClicker::set_max_duration` に反映（次回の開始から適用）
+
+【技術仕様】
+-   **データ管理**: 各項目に時間（分、0はなし）を `CB_SETITEMDATA` で関連付け
+-   **停止処理**: `auto_click.rs` の `auto_click_loop` が開始からの経過時間を確認し、
+    達した時点でループを抜けて `WM_AUTO_CLICK_COMPLETE` を送信（回数で終了した場合と同じ後処理）
+-   **残り時間の表示**: 設定時はキャプチャ中オーバーレイの進行状況ラベルに残り時間を表示
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `auto_clicker` の最大実行時間の設定
+-   `auto_click.rs`: `set_max_duration` / `get_max_duration`
+-   `auto_click_checkbox_handler.rs`: 自動クリックの有効/無効に合わせてコンボボックスを有効/無効化
+-   メインダイアログ: CBN_SELCHANGE通知メッセージの受信
+ */
+
+use std::time::Duration;
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{app_state::AppState, constants::*, strings, system_utils::app_log};
+
+/// コンボボックスに表示する最大実行時間の選択肢（分、0はなし）
+const AUTO_CLICK_DURATION_OPTIONS_MINUTES: [u64; 5] = [0, 5, 15, 30, 60];
+
+/// 最大実行時間コンボボックスを初期化する
+///
+/// 選択肢を追加し、`AutoClicker` の現在の設定（デフォルト：なし）を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - ダイアログウィンドウハンドル
+pub fn initialize_auto_click_duration_combo(hwnd: HWND) {
+    let Ok(combo_hwnd) = (unsafe { GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_DURATION_COMBO) }) else {
+        return;
+    };
+    let current_minutes = AppState::get_app_state_ref()
+        .auto_clicker
+        .get_max_duration()
+        .map_or(0, |max_duration| max_duration.as_secs() / 60);
+
+    let mut selected_index = 0;
+    for minutes in AUTO_CLICK_DURATION_OPTIONS_MINUTES {
+        let text = if minutes == 0 {
+            "なし\0".to_string()
+        } else {
+            format!("{}分\0", minutes)
+        };
+        let wide_text: Vec<u16> = text.encode_utf16().collect();
+        let index = unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_ADDSTRING,
+                Some(WPARAM(0)),
+                Some(LPARAM(wide_text.as_ptr() as isize)),
+            )
+        }
+        .0 as usize;
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETITEMDATA,
+                Some(WPARAM(index)),
+                Some(LPARAM(minutes as isize)),
+            );
+        }
+        if minutes == current_minutes {
+            selected_index = index;
+        }
+    }
+
+    unsafe {
+        SendMessageW(
+            combo_hwnd,
+            CB_SETCURSEL,
+            Some(WPARAM(selected_index)),
+            Some(LPARAM(0)),
+        );
+    }
+}
+
+/// 最大実行時間コンボボックスの選択変更を処理する
+///
+/// 選択された時間を `AppState` の `auto_clicker` に設定します（「なし」は時間で停止しない）。
+///
+/// # 引数
+/// * `hwnd` - ダイアログウィンドウハンドル
+pub fn handle_auto_click_duration_combo_change(hwnd: HWND) {
+    let Ok(combo_hwnd) = (unsafe { GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_DURATION_COMBO) }) else {
+        return;
+    };
+    let selected_index =
+        unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 };
+    if selected_index < 0 {
+        return;
+    }
+    let minutes = unsafe {
+        SendMessageW(
+            combo_hwnd,
+            CB_GETITEMDATA,
+            Some(WPARAM(selected_index as usize)),
+            Some(LPARAM(0)),
+        )
+    }
+    .0 as u64;
+
+    let max_duration = (minutes > 0).then(|| Duration::from_secs(minutes * 60));
+    AppState::get_app_state_mut()
+        .auto_clicker
+        .set_max_duration(max_duration);
+    app_log(&strings::auto_click_max_duration_changed(minutes));
+}
@@ -5,13 +5,13 @@
 
 【ファイル概要】
 ClickCaptureアプリケーションの自動連続クリック機能において、クリック実行間隔を
-設定するコンボボックスを管理するモジュール。ユーザーが1秒〜5秒の範囲で
+設定するコンボボックスを管理するモジュール。ユーザーが250ミリ秒〜5秒の範囲で
 自動キャプチャの実行間隔を直感的に選択できるUIを提供し、
 高度な自動化ワークフローの精密な制御を可能にします。
 
 【主要機能】
 1.  **間隔コンボボックス初期化**: `initialize_auto_click_interval_combo`
-    -   1秒〜5秒の間隔設定を1秒刻みで提供（1秒、2秒、3秒、4秒、5秒）
+    -   1秒未満の間隔（250ms、500ms、750ms）と、1秒〜5秒の間隔（1秒刻み）を提供
     -   デフォルト値として実用的な1秒間隔を設定
     -   Win32コンボボックスAPIによるネイティブUI制御
 
@@ -20,14 +20,16 @@ ClickCaptureアプリケーションの自動連続クリック機能におい
     -   リアルタイムでの設定更新によるシームレスな操作体験
 
 【技術仕様】
--   **間隔範囲**: 1秒〜5秒（1秒刻み）
+-   **間隔範囲**: 250ms〜5秒
+    - 250ms〜750ms: ページ送りの高速スクレイピング向け（描画の速い画面のみ）
     - 1秒: 高速連続キャプチャ、動的コンテンツ監視に最適
     - 2秒: バランス重視、一般的なスクリーンキャプチャ作業
     - 3秒: 安定重視、システム負荷を抑えた長時間動作
     - 4秒: 低負荷動作、バックグラウンド監視用途
     - 5秒: 最低負荷、定期的なスナップショット取得
 -   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)
--   **データ管理**: 各項目に間隔値（`u64`秒）を関連付け
+-   **データ管理**: 各項目に間隔値（ミリ秒）を関連付け
+-   **下限**: `auto_click::POST_CLICK_RENDER_DELAY_MS`（クリック後の描画待ち時間）より短い間隔は `AutoClicker::set_interval` が切り上げる
 -   **状態同期**: AutoClicker経由でアプリケーション全体の間隔設定共有
 
 【自動化用途別推奨設定】
@@ -37,7 +39,7 @@ ClickCaptureアプリケーションの自動連続クリック機能におい
 -   **プレゼンテーション記録**: 2-3秒間隔、適度な詳細度での記録
 
 【実装詳細】
--   コンボボックス項目の表示テキスト（"N秒"）と内部データ（秒数値）の分離管理
+-   コンボボックス項目の表示テキスト（"Nms" / "N秒"）と内部データ（ミリ秒）の分離管理
 -   UTF-16エンコーディングによるWin32 API互換性確保
 -   エラーハンドリング付きの安全なWin32 API呼び出し
 -   AutoClickerモジュールとの密接な連携
@@ -59,18 +61,29 @@ use windows::Win32::{
 
 use crate::{app_state::AppState, constants::*};
 
-/// 自動クリック間隔コンボボックスを初期化（1秒〜5秒、1秒刻み）
+/// コンボボックスに表示する間隔の選択肢（ミリ秒）
+const AUTO_CLICK_INTERVAL_OPTIONS_MS: [u64; 8] = [250, 500, 750, 1000, 2000, 3000, 4000, 5000];
+
+/// デフォルトで選択する間隔（ミリ秒）
+const DEFAULT_AUTO_CLICK_INTERVAL_MS: u64 = 1000;
+
+/// 自動クリック間隔コンボボックスを初期化（250ms〜5秒）
 ///
-/// 自動連続クリックの実行間隔を設定するコンボボックスに、1秒から5秒までの選択肢を1秒刻みで追加します。
+/// 自動連続クリックの実行間隔を設定するコンボボックスに、1秒未満の選択肢（250ms / 500ms / 750ms）と
+/// 1秒から5秒までの選択肢を1秒刻みで追加します。
 /// デフォルト値として1秒を選択状態にします。
 ///
 /// # 引数
 /// * `hwnd` - ダイアログウィンドウハンドル。
 pub fn initialize_auto_click_interval_combo(hwnd: HWND) {
     if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_AUTO_CLICK_INTERVAL_COMBO) } {
-        // 1秒から5秒まで1秒刻みで項目を追加
-        for interval_sec in 1..=5u64 {
-            let text = format!("{}秒\0", interval_sec);
+        // 1秒未満はミリ秒、1秒以上は秒で表示
+        for interval_ms in AUTO_CLICK_INTERVAL_OPTIONS_MS {
+            let text = if interval_ms < 1000 {
+                format!("{}ms\0", interval_ms)
+            } else {
+                format!("{}秒\0", interval_ms / 1000)
+            };
             let wide_text: Vec<u16> = text.encode_utf16().collect();
             let index = unsafe {
                 SendMessageW(
@@ -86,14 +99,23 @@ pub fn initialize_auto_click_interval_combo(hwnd: HWND) {
                     combo_hwnd,
                     CB_SETITEMDATA,
                     Some(WPARAM(index)),
-                    Some(LPARAM((interval_sec * 1000) as isize)),
+                    Some(LPARAM(interval_ms as isize)),
                 );
             }
         }
 
         // デフォルト値（1秒）を選択
+        let default_index = AUTO_CLICK_INTERVAL_OPTIONS_MS
+            .iter()
+            .position(|&interval_ms| interval_ms == DEFAULT_AUTO_CLICK_INTERVAL_MS)
+            .unwrap_or(0);
         unsafe {
-            SendMessageW(combo_hwnd, CB_SETCURSEL, Some(WPARAM(0)), Some(LPARAM(0)));
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
         }
     }
 }
@@ -124,11 +146,14 @@ pub fn handle_auto_click_interval_combo_change(hwnd: HWND) {
             }
             .0 as u64;
 
-            // AppStateに保存
+            // AppStateに保存（描画待ち時間より短い値は set_interval が下限に切り上げる）
             let app_state = AppState::get_app_state_mut();
             app_state.auto_clicker.set_interval(interval_value);
 
-            println!("自動クリック間隔設定変更: {}ms", interval_value);
+            println!(
+                "自動クリック間隔設定変更: {}ms",
+                app_state.auto_clicker.get_interval()
+            );
         }
     }
 }
@@ -0,0 +1,85 @@
+/*
+============================================================================
+自動クリック動作モードチェックボックスハンドラモジュール (auto_click_mode_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+自動クリック設定の「キャプチャのみ」チェックボックスを管理するモジュール。
+ON のとき、自動クリックはマウスクリックを合成せずに、設定された間隔・回数で
+選択エリアのキャプチャのみを繰り返します（進捗バーの監視など）。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_auto_click_mode_checkbox`
+    -   `AutoClicker` の動作モードをチェック状態に反映（デフォルト：OFF＝クリック＋キャプチャ）
+2.  **チェック状態変更処理**: `handle_auto_click_mode_checkbox_change`
+    -   チェック状態を `AutoClickMode` に変換して `AutoClicker` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **有効/無効**: 間隔・回数と同様に、自動クリックチェックボックスがONのときのみ有効
+    （`auto_click_checkbox_handler.rs` の `update_auto_click_controls_state`）
+
+【AI解析用：依存関係】
+-   `auto_click.rs`: `AutoClickMode` と `AutoClicker::set_mode`
+-   `constants.rs`: `IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX` コントロールID定義
+-   `screen_capture.rs`: 開始確認ダイアログの文言切り替えと、キャプチャのみモードの即時開始
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, auto_click::AutoClickMode, constants::*};
+
+/// 自動クリック動作モードチェックボックスを初期化する
+///
+/// `AutoClicker` の現在の動作モードをチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_auto_click_mode_checkbox(hwnd: HWND) {
+    let is_checked =
+        AppState::get_app_state_ref().auto_clicker.get_mode() == AutoClickMode::CaptureOnly;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 自動クリック動作モードチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AutoClicker` の動作モードとして保存します。
+/// 変更は次回のキャプチャモード開始から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_auto_click_mode_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.auto_clicker.set_mode(if is_checked {
+        AutoClickMode::CaptureOnly
+    } else {
+        AutoClickMode::ClickAndCapture
+    });
+
+    println!(
+        "自動クリック動作モード設定変更: {}",
+        if is_checked {
+            "キャプチャのみ"
+        } else {
+            "クリック＋キャプチャ"
+        }
+    );
+}
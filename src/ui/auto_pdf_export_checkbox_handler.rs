@@ -0,0 +1,79 @@
+/*
+============================================================================
+PDF自動変換チェックボックスハンドラモジュール (auto_pdf_export_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャモードの終了時に、保存先フォルダーを自動でPDFに変換するかどうかを切り替える
+「キャプチャ終了時に自動でPDF変換」チェックボックスを管理するモジュール。
+フォルダーを監視する感覚で、キャプチャを終えるたびに「PDF変換」ボタンを押す手間を省きます。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_auto_pdf_export_checkbox`
+    -   `AppState.auto_pdf_export` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_auto_pdf_export_checkbox_change`
+    -   チェック状態を `AppState.auto_pdf_export` に反映し、レジストリに保存
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **永続化**: `settings::save_setting_dword`（`AutoPdfExport`）で次回起動時の初期値にする
+-   **変換の条件**: キャプチャモード中に1枚以上ファイルへ保存した場合のみ（`capture_session_saved_count`）。
+    変換は `WM_AUTO_PDF_EXPORT` で要求され、`pdf_export_button_handler.rs` が確認なしで実行する
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `auto_pdf_export` フィールドの保持
+-   `screen_capture.rs`: キャプチャモード終了時の変換要求（`request_auto_pdf_export`）
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    settings::{AUTO_PDF_EXPORT_VALUE_NAME, save_setting_dword},
+    strings,
+    system_utils::app_log,
+};
+
+/// PDF自動変換チェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_auto_pdf_export_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().auto_pdf_export;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_AUTO_PDF_EXPORT_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// PDF自動変換チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.auto_pdf_export` に保存します。
+/// 変更は次回のキャプチャモード終了時から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_auto_pdf_export_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_AUTO_PDF_EXPORT_CHECKBOX) } == BST_CHECKED.0;
+
+    AppState::get_app_state_mut().auto_pdf_export = is_checked;
+    save_setting_dword(AUTO_PDF_EXPORT_VALUE_NAME, is_checked as u32);
+    app_log(strings::auto_pdf_export_changed(is_checked));
+}
@@ -0,0 +1,130 @@
+/*
+============================================================================
+保存形式コンボボックスハンドラモジュール (capture_format_combo_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャ画像の保存形式（JPEG / WebP(可逆) / WebP / AVIF / BMP）を選択するコンボボックスを
+管理するモジュール。単色の多いUI画面のキャプチャでは、可逆WebPにすることで
+JPEG（品質95%）より大幅にファイルサイズを削減できます。
+BMP(無圧縮)はコマ単位の作業用で、エンコードを行わず画質劣化ゼロで保存します（PDF変換時はJPEGに変換）。
+
+【主要機能】
+1.  **保存形式コンボボックス初期化**: `initialize_capture_format_combo`
+    -   `CaptureFormat::all()` の項目を提供（デフォルト：JPEG）
+    -   AVIFは `avif` フィーチャー有効時のみ表示
+2.  **保存形式変更イベント処理**: `handle_capture_format_combo_change`
+    -   選択された形式を `AppState.capture_format` に即座に反映
+
+【技術仕様】
+-   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)
+-   **データ管理**: 各項目に `CaptureFormat` の識別値（`u8`）を関連付け
+-   **エンコード**: `capturer.rs` の `encode_capture`（非可逆形式の品質はJPEG品質設定を流用）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `capture_format` フィールドの保持
+-   `constants.rs`: `IDC_CAPTURE_FORMAT_COMBO` コントロールID定義
+-   `capturer.rs`: `CaptureFormat` 定義
+-   メインダイアログ: CBN_SELCHANGE通知メッセージの受信
+ */
+
+use clickcapture::CaptureFormat;
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// 保存形式コンボボックスを初期化する
+///
+/// 選択可能な保存形式を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_capture_format_combo(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_CAPTURE_FORMAT_COMBO) } {
+        let current_format = AppState::get_app_state_ref().capture_format;
+        let mut default_index = 0;
+
+        for &format in CaptureFormat::all() {
+            // Win32 APIに渡すためNull終端文字を付加
+            let text = format!("{}\0", format.label());
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+
+            // CB_ADDSTRING：コンボボックスに表示テキストを追加
+            let index = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(wide_text.as_ptr() as isize)),
+                )
+            }
+            .0 as usize;
+
+            // CB_SETITEMDATA：表示テキストと保存形式の識別値を関連付け
+            unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_SETITEMDATA,
+                    Some(WPARAM(index)),
+                    Some(LPARAM(format as isize)),
+                );
+            }
+
+            if format == current_format {
+                default_index = index;
+            }
+        }
+
+        // 現在の設定値を選択状態に設定
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
+        }
+    }
+}
+
+/// 保存形式コンボボックスの選択変更イベントを処理する
+///
+/// 選択された項目の識別値から `CaptureFormat` を復元し、`AppState` に保存します。
+/// 変更は次回のキャプチャから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_capture_format_combo_change(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_CAPTURE_FORMAT_COMBO) } {
+        // CB_GETCURSEL：現在選択されている項目のインデックス取得
+        let selected_index =
+            unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 }
+                as i32;
+
+        if selected_index >= 0 {
+            // CB_GETITEMDATA：選択項目に関連付けられた保存形式の識別値を取得
+            let format_value = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_GETITEMDATA,
+                    Some(WPARAM(selected_index as usize)),
+                    Some(LPARAM(0)),
+                )
+            }
+            .0 as u8;
+
+            let format = CaptureFormat::from_u8(format_value);
+            let app_state = AppState::get_app_state_mut();
+            app_state.capture_format = format;
+
+            println!(
+                "保存形式設定変更: {} (.{})",
+                format.label(),
+                format.extension()
+            );
+        }
+    }
+}
@@ -0,0 +1,527 @@
+/*
+============================================================================
+キャプチャ履歴ハンドラモジュール (capture_history_handler.rs)
+============================================================================
+
+【ファイル概要】
+今回のセッションで保存したキャプチャのファイル名を一覧表示する「キャプチャ履歴」ダイアログを
+管理するモジュール。PDF変換の前に、失敗したコマの削除やページの入れ替えを行えるようにします。
+履歴が空でない場合、PDF変換はフォルダーのファイル名順ではなく履歴の並び順で行われます
+（PDF変換の確認で変換元フォルダーを選び直した場合は、そのフォルダーが優先されます）。
+
+【主要機能】
+1.  **履歴の記録 (`add_capture_history`)**:
+    -   保存成功時にファイルパスを末尾に追加し、`MAX_CAPTURE_HISTORY` 件を超えた古い履歴を破棄
+    -   「共有」ボタンの対象として `last_saved_file` も更新
+2.  **履歴ダイアログ (`handle_capture_history_button`)**:
+    -   「履歴」ボタンから `IDD_HISTORY_DIALOG` をモーダル表示
+    -   リストビュー（レポート表示、複数選択可）にファイル名とフォルダーを履歴の順で表示
+3.  **削除 (`delete_selected_history_entries`)**:
+    -   選択した項目のファイルを削除し、履歴からも取り除く（「削除」ボタン・Deleteキー、確認あり）
+4.  **並べ替え（ドラッグ＆ドロップ）**:
+    -   `LVN_BEGINDRAG` でマウスをキャプチャし、`WM_LBUTTONUP` のドロップ位置へ選択項目をまとめて移動
+
+【技術仕様】
+-   **リストビュー**: `SysListView32`（`LVS_REPORT | LVS_SHOWSELALWAYS`）＋ `LVS_EX_FULLROWSELECT`
+-   **ドロップ位置**: `LVM_HITTEST` で項目を特定。下方向へのドラッグは項目の後ろ、上方向は前に挿入し、
+    項目のない余白へのドロップは末尾に移動
+-   **保持期間**: 履歴はセッション中のみ保持し、レジストリには保存しない
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `capture_history`（履歴）と `is_dragging_history_items`（ドラッグ中フラグ）の保持
+-   `screen_capture.rs`, `window_capture.rs`: 保存成功時・再保存時に `add_capture_history` を呼び出す
+-   `export_pdf.rs`: 履歴が空でない場合に履歴の並び順でPDFに変換
+-   `dialog_handler.rs`: `IDC_HISTORY_BUTTON` のBN_CLICKEDで `handle_capture_history_button` を呼び出す
+ */
+
+use std::{fs, io::ErrorKind, ops::Range, path::PathBuf};
+
+use windows::{
+    Win32::{
+        Foundation::{HWND, LPARAM, POINT, RECT, WPARAM},
+        Graphics::Gdi::ScreenToClient,
+        UI::{
+            Controls::{
+                ICC_LISTVIEW_CLASSES, INITCOMMONCONTROLSEX, InitCommonControlsEx,
+                LIST_VIEW_ITEM_STATE_FLAGS, LVCF_TEXT, LVCF_WIDTH, LVCOLUMNW, LVHITTESTINFO,
+                LVIF_TEXT, LVIS_FOCUSED, LVIS_SELECTED, LVITEMW, LVM_DELETEALLITEMS,
+                LVM_ENSUREVISIBLE, LVM_GETNEXTITEM, LVM_HITTEST, LVM_INSERTCOLUMNW,
+                LVM_INSERTITEMW, LVM_SETCOLUMNWIDTH, LVM_SETEXTENDEDLISTVIEWSTYLE,
+                LVM_SETITEMSTATE, LVM_SETITEMTEXTW, LVN_BEGINDRAG, LVN_KEYDOWN, LVNI_SELECTED,
+                LVS_EX_FULLROWSELECT, LVSCW_AUTOSIZE_USEHEADER, NMHDR, NMLVKEYDOWN,
+            },
+            Input::KeyboardAndMouse::{ReleaseCapture, SetCapture, VK_DELETE},
+            WindowsAndMessaging::{
+                DialogBoxParamW, EndDialog, GetClientRect, GetCursorPos, GetDlgItem, IDC_SIZENS,
+                IDCANCEL, IDOK, IDYES, LoadCursorW, MB_ICONWARNING, MB_YESNO, MessageBoxW,
+                SendMessageW, SetCursor, WM_CAPTURECHANGED, WM_COMMAND, WM_INITDIALOG,
+                WM_LBUTTONUP, WM_MOUSEMOVE, WM_NOTIFY,
+            },
+        },
+    },
+    core::{PCWSTR, PWSTR, w},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::{IDC_HISTORY_DELETE_BUTTON, IDC_HISTORY_LIST, IDD_HISTORY_DIALOG},
+    strings,
+    system_utils::app_log,
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// セッション中に保持するキャプチャ履歴の最大件数（これを超えると古いものから破棄）
+pub const MAX_CAPTURE_HISTORY: usize = 2000;
+
+/// ファイル名列の幅（ピクセル）
+const FILE_NAME_COLUMN_WIDTH: i32 = 110;
+
+/// キャプチャの保存成功時に、ファイルパスを履歴の末尾に追加する
+///
+/// 件数が `MAX_CAPTURE_HISTORY` を超えた場合は古い履歴から破棄します（ファイルは削除しない）。
+/// 「共有」ボタンの対象（`last_saved_file`）も更新し、最初の保存時にボタンを有効にします。
+pub fn add_capture_history(file_path: PathBuf) {
+    let app_state = AppState::get_app_state_mut();
+    let is_first_save = app_state
+        .last_saved_file
+        .replace(file_path.clone())
+        .is_none();
+
+    let history = &mut app_state.capture_history;
+    history.push(file_path);
+    if history.len() > MAX_CAPTURE_HISTORY {
+        let overflow = history.len() - MAX_CAPTURE_HISTORY;
+        history.drain(..overflow);
+    }
+
+    if is_first_save {
+        update_input_control_states();
+    }
+}
+
+/// 「履歴」ボタンのクリックイベントを処理する
+///
+/// キャプチャ履歴ダイアログをモーダル表示します。ダイアログを閉じるまで制御は戻りません。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_capture_history_button(hwnd: HWND) {
+    unsafe {
+        // リストビュークラスはcomctl32に含まれるため、ダイアログ作成前に登録しておく
+        let icc = INITCOMMONCONTROLSEX {
+            dwSize: std::mem::size_of::<INITCOMMONCONTROLSEX>() as u32,
+            dwICC: ICC_LISTVIEW_CLASSES,
+        };
+        let _ = InitCommonControlsEx(&icc);
+
+        let dialog_id = PCWSTR(IDD_HISTORY_DIALOG as *const u16);
+        let result = DialogBoxParamW(
+            None,
+            dialog_id,
+            Some(hwnd),
+            Some(capture_history_dialog_proc),
+            LPARAM(0),
+        );
+        if result == -1 {
+            app_log(strings::capture_history_dialog_failed());
+        }
+    }
+}
+
+/// キャプチャ履歴ダイアログのダイアログプロシージャ
+unsafe extern "system" fn capture_history_dialog_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> isize {
+    match message {
+        WM_INITDIALOG => {
+            initialize_history_list(hwnd);
+            1
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            match id {
+                IDC_HISTORY_DELETE_BUTTON => {
+                    delete_selected_history_entries(hwnd);
+                    1
+                }
+                id if id == IDOK.0 || id == IDCANCEL.0 => {
+                    let _ = unsafe { EndDialog(hwnd, 0) };
+                    1
+                }
+                _ => 0,
+            }
+        }
+        WM_NOTIFY => handle_history_list_notify(hwnd, lparam),
+        WM_MOUSEMOVE if AppState::get_app_state_ref().is_dragging_history_items => {
+            // ドラッグ中は上下移動のカーソルで並べ替え中であることを示す
+            unsafe {
+                let cursor = LoadCursorW(None, IDC_SIZENS).unwrap_or_default();
+                SetCursor(Some(cursor));
+            }
+            1
+        }
+        WM_LBUTTONUP if AppState::get_app_state_ref().is_dragging_history_items => {
+            finish_history_drag(hwnd);
+            1
+        }
+        WM_CAPTURECHANGED => {
+            // 他のウィンドウにマウスキャプチャを奪われた場合はドラッグを中止
+            AppState::get_app_state_mut().is_dragging_history_items = false;
+            0
+        }
+        _ => 0,
+    }
+}
+
+/// リストビューの列を作成し、履歴を表示する
+fn initialize_history_list(hwnd: HWND) {
+    unsafe {
+        let Ok(list) = GetDlgItem(Some(hwnd), IDC_HISTORY_LIST) else {
+            return;
+        };
+        SendMessageW(
+            list,
+            LVM_SETEXTENDEDLISTVIEWSTYLE,
+            Some(WPARAM(LVS_EX_FULLROWSELECT as usize)),
+            Some(LPARAM(LVS_EX_FULLROWSELECT as isize)),
+        );
+
+        let columns = [
+            (w!("ファイル名"), FILE_NAME_COLUMN_WIDTH),
+            (w!("フォルダー"), FILE_NAME_COLUMN_WIDTH),
+        ];
+        for (index, (title, width)) in columns.into_iter().enumerate() {
+            let column = LVCOLUMNW {
+                mask: LVCF_TEXT | LVCF_WIDTH,
+                cx: width,
+                pszText: PWSTR(title.as_ptr() as *mut u16),
+                ..Default::default()
+            };
+            SendMessageW(
+                list,
+                LVM_INSERTCOLUMNW,
+                Some(WPARAM(index)),
+                Some(LPARAM(&column as *const _ as isize)),
+            );
+        }
+        // フォルダー列は残りの幅いっぱいに広げる
+        SendMessageW(
+            list,
+            LVM_SETCOLUMNWIDTH,
+            Some(WPARAM(1)),
+            Some(LPARAM(LVSCW_AUTOSIZE_USEHEADER as isize)),
+        );
+
+        populate_history_list(list);
+    }
+}
+
+/// リストビューの項目を、現在の履歴の内容で作り直す
+fn populate_history_list(list: HWND) {
+    let history = &AppState::get_app_state_ref().capture_history;
+    unsafe {
+        SendMessageW(list, LVM_DELETEALLITEMS, None, None);
+
+        for (index, path) in history.iter().enumerate() {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let folder = path
+                .parent()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default();
+
+            let mut file_name_wide: Vec<u16> =
+                file_name.encode_utf16().chain(std::iter::once(0)).collect();
+            let item = LVITEMW {
+                mask: LVIF_TEXT,
+                iItem: index as i32,
+                pszText: PWSTR(file_name_wide.as_mut_ptr()),
+                ..Default::default()
+            };
+            let inserted = SendMessageW(
+                list,
+                LVM_INSERTITEMW,
+                Some(WPARAM(0)),
+                Some(LPARAM(&item as *const _ as isize)),
+            )
+            .0;
+            if inserted < 0 {
+                continue;
+            }
+
+            let mut folder_wide: Vec<u16> =
+                folder.encode_utf16().chain(std::iter::once(0)).collect();
+            let sub_item = LVITEMW {
+                iSubItem: 1,
+                pszText: PWSTR(folder_wide.as_mut_ptr()),
+                ..Default::default()
+            };
+            SendMessageW(
+                list,
+                LVM_SETITEMTEXTW,
+                Some(WPARAM(inserted as usize)),
+                Some(LPARAM(&sub_item as *const _ as isize)),
+            );
+        }
+    }
+}
+
+/// リストビューの通知（WM_NOTIFY）を処理する
+///
+/// * `LVN_BEGINDRAG` - マウスをキャプチャしてドラッグによる並べ替えを開始
+/// * `LVN_KEYDOWN` - Deleteキーで選択項目を削除
+fn handle_history_list_notify(hwnd: HWND, lparam: LPARAM) -> isize {
+    unsafe {
+        let header = lparam.0 as *const NMHDR;
+        if header.is_null() || (*header).idFrom != IDC_HISTORY_LIST as usize {
+            return 0;
+        }
+
+        match (*header).code {
+            LVN_BEGINDRAG => {
+                AppState::get_app_state_mut().is_dragging_history_items = true;
+                SetCapture(hwnd);
+                1
+            }
+            LVN_KEYDOWN => {
+                let key_down = &*(lparam.0 as *const NMLVKEYDOWN);
+                if key_down.wVKey == VK_DELETE.0 {
+                    delete_selected_history_entries(hwnd);
+                }
+                0
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// ドラッグを終了し、ドロップ位置へ選択項目を移動する
+fn finish_history_drag(hwnd: HWND) {
+    unsafe {
+        let _ = ReleaseCapture();
+        AppState::get_app_state_mut().is_dragging_history_items = false;
+
+        let Ok(list) = GetDlgItem(Some(hwnd), IDC_HISTORY_LIST) else {
+            return;
+        };
+
+        // ドロップ位置をリストビューのクライアント座標に変換
+        let mut cursor_pos = POINT::default();
+        if GetCursorPos(&mut cursor_pos).is_err() {
+            return;
+        }
+        let _ = ScreenToClient(list, &mut cursor_pos);
+
+        let mut hit_test = LVHITTESTINFO {
+            pt: cursor_pos,
+            ..Default::default()
+        };
+        let hit_index = SendMessageW(
+            list,
+            LVM_HITTEST,
+            Some(WPARAM(0)),
+            Some(LPARAM(&mut hit_test as *mut _ as isize)),
+        )
+        .0;
+
+        let history = &mut AppState::get_app_state_mut().capture_history;
+        let drop_index = if hit_index >= 0 {
+            hit_index as usize
+        } else {
+            // 項目のない余白（最後の項目より下）へのドロップは末尾に移動、リスト外は中止
+            let mut client_rect = RECT::default();
+            if GetClientRect(list, &mut client_rect).is_err()
+                || cursor_pos.x < client_rect.left
+                || cursor_pos.x >= client_rect.right
+                || cursor_pos.y < client_rect.top
+                || cursor_pos.y >= client_rect.bottom
+            {
+                return;
+            }
+            history.len()
+        };
+
+        let selected = selected_history_indices(list);
+        if selected.is_empty() || selected.binary_search(&drop_index).is_ok() {
+            return;
+        }
+
+        let moved = move_history_entries(history, &selected, drop_index);
+        app_log(&strings::capture_history_reordered(
+            moved.len(),
+            moved.start,
+        ));
+        populate_history_list(list);
+        select_history_items(list, moved);
+    }
+}
+
+/// 選択された履歴項目を、ドロップ先の項目の位置へまとめて移動する
+///
+/// 下方向へのドラッグはドロップ先の後ろ、上方向へのドラッグは前に挿入します。
+/// 選択項目どうしの順序は維持します。
+///
+/// # 引数
+/// * `history` - キャプチャ履歴
+/// * `selected` - 移動する項目のインデックス（昇順）
+/// * `drop_index` - ドロップ先の項目のインデックス（`history.len()` で末尾）
+///
+/// # 戻り値
+/// 移動後の項目のインデックス範囲
+fn move_history_entries(
+    history: &mut Vec<PathBuf>,
+    selected: &[usize],
+    drop_index: usize,
+) -> Range<usize> {
+    let insert_before = if selected.last().is_some_and(|&last| drop_index > last) {
+        drop_index + 1
+    } else {
+        drop_index
+    };
+    // 挿入位置は、挿入先より前にある「移動しない項目」の数
+    let insert_position = (0..insert_before.min(history.len()))
+        .filter(|index| selected.binary_search(index).is_err())
+        .count();
+
+    let mut moved = Vec::with_capacity(selected.len());
+    let mut remaining = Vec::with_capacity(history.len());
+    for (index, path) in history.drain(..).enumerate() {
+        if selected.binary_search(&index).is_ok() {
+            moved.push(path);
+        } else {
+            remaining.push(path);
+        }
+    }
+
+    let moved_count = moved.len();
+    remaining.splice(insert_position..insert_position, moved);
+    *history = remaining;
+    insert_position..insert_position + moved_count
+}
+
+/// 選択中の項目のファイルを削除し、履歴からも取り除く
+///
+/// 削除前に確認ダイアログを表示します。既に存在しないファイルは履歴からのみ取り除き、
+/// 削除に失敗したファイル（他のアプリで開いている場合など）は履歴に残します。
+fn delete_selected_history_entries(hwnd: HWND) {
+    unsafe {
+        let Ok(list) = GetDlgItem(Some(hwnd), IDC_HISTORY_LIST) else {
+            return;
+        };
+        let selected = selected_history_indices(list);
+        if selected.is_empty() {
+            return;
+        }
+
+        let message_wide: Vec<u16> = strings::capture_history_delete_confirm(selected.len())
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+        let title_wide: Vec<u16> = strings::capture_history_delete_confirm_title()
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+        let result = MessageBoxW(
+            Some(hwnd),
+            PCWSTR(message_wide.as_ptr()),
+            PCWSTR(title_wide.as_ptr()),
+            MB_YESNO | MB_ICONWARNING,
+        );
+        if result != IDYES {
+            return;
+        }
+
+        let history = &mut AppState::get_app_state_mut().capture_history;
+        let mut deleted_count = 0;
+        let mut index = 0;
+        history.retain(|path| {
+            let is_selected = selected.binary_search(&index).is_ok();
+            index += 1;
+            if !is_selected {
+                return true;
+            }
+            match fs::remove_file(path) {
+                Ok(()) => {
+                    deleted_count += 1;
+                    false
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => false,
+                Err(e) => {
+                    app_log(&strings::capture_history_delete_failed(
+                        &path.display().to_string(),
+                        &e.to_string(),
+                    ));
+                    true
+                }
+            }
+        });
+
+        app_log(&strings::capture_history_deleted(deleted_count));
+        populate_history_list(list);
+    }
+}
+
+/// 選択中の項目のインデックスを昇順で取得する
+fn selected_history_indices(list: HWND) -> Vec<usize> {
+    let mut indices = Vec::new();
+    let mut index: isize = -1;
+    loop {
+        index = unsafe {
+            SendMessageW(
+                list,
+                LVM_GETNEXTITEM,
+                Some(WPARAM(index as usize)),
+                Some(LPARAM(LVNI_SELECTED as isize)),
+            )
+        }
+        .0;
+        if index < 0 {
+            break;
+        }
+        indices.push(index as usize);
+    }
+    indices
+}
+
+/// 指定範囲の項目だけを選択状態にし、先頭の項目が見えるようにスクロールする
+fn select_history_items(list: HWND, range: Range<usize>) {
+    unsafe {
+        // いったん全項目（wParam = -1）の選択とフォーカスを解除
+        let clear = LVITEMW {
+            stateMask: LIST_VIEW_ITEM_STATE_FLAGS(LVIS_SELECTED.0 | LVIS_FOCUSED.0),
+            ..Default::default()
+        };
+        SendMessageW(
+            list,
+            LVM_SETITEMSTATE,
+            Some(WPARAM(usize::MAX)),
+            Some(LPARAM(&clear as *const _ as isize)),
+        );
+
+        let select = LVITEMW {
+            state: LVIS_SELECTED,
+            stateMask: LVIS_SELECTED,
+            ..Default::default()
+        };
+        for index in range.clone() {
+            SendMessageW(
+                list,
+                LVM_SETITEMSTATE,
+                Some(WPARAM(index)),
+                Some(LPARAM(&select as *const _ as isize)),
+            );
+        }
+
+        SendMessageW(
+            list,
+            LVM_ENSUREVISIBLE,
+            Some(WPARAM(range.start)),
+            Some(LPARAM(0)),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+/*
+============================================================================
+撮影情報記録チェックボックスハンドラモジュール (capture_metadata_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+保存するJPEGに撮影日時と撮影条件（選択領域・スケール・品質）をEXIFとして埋め込み、
+PDF変換時に各ページの下へ撮影日時を表示するかどうかを切り替える
+「撮影情報を記録」チェックボックスを管理するモジュール。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_capture_metadata_checkbox`
+    -   `AppState.record_capture_metadata` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_capture_metadata_checkbox_change`
+    -   チェック状態を `AppState.record_capture_metadata` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **OFF時**: 保存されるJPEGは従来と同一のバイト列（EXIFを追加しない）、PDFにもフッターを表示しない
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `record_capture_metadata` フィールドの保持
+-   `constants.rs`: `IDC_CAPTURE_METADATA_CHECKBOX` コントロールID定義
+-   `screen_capture.rs`: EXIFの埋め込み、`export_pdf.rs`: 撮影日時フッターの有無
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// 撮影情報記録チェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_capture_metadata_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().record_capture_metadata;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_CAPTURE_METADATA_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 撮影情報記録チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.record_capture_metadata` に保存します。
+/// 変更は次回のキャプチャ保存・PDF変換から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_capture_metadata_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_CAPTURE_METADATA_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.record_capture_metadata = is_checked;
+
+    println!(
+        "撮影情報記録設定変更: {}",
+        if is_checked { "有効" } else { "無効" }
+    );
+}
@@ -0,0 +1,139 @@
+/*
+============================================================================
+キャプチャトリガーコンボボックスハンドラモジュール (capture_trigger_combo_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャモード中にどのマウスボタンでキャプチャを実行するかを選択する
+コンボボックスを管理するモジュール。左手をキーボードに置いたまま中ボタン・右ボタンで
+キャプチャし、左クリックは対象アプリの通常操作に使う、といった運用を可能にします。
+
+【主要機能】
+1.  **トリガーコンボボックス初期化**: `initialize_capture_trigger_combo`
+    -   「左」「中」「左+中」「右」の4項目を提供（デフォルト：左）
+2.  **トリガー変更イベント処理**: `handle_capture_trigger_combo_change`
+    -   選択されたトリガーを `AppState.capture_trigger` に即座に反映し、レジストリに保存
+
+【技術仕様】
+-   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)
+-   **データ管理**: 各項目に `CaptureTrigger` の識別値（`u8`）を関連付け
+-   **判定処理**: `hook/mouse.rs` が `CaptureTrigger` を参照してボタンごとに処理を分岐
+    （トリガーのボタンの押下・解放は消費し、それ以外のボタンは対象アプリへ透過）
+-   **永続化**: `settings::save_setting_dword`（`CaptureTrigger`）で次回起動時の初期値にする
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `capture_trigger` フィールドの保持
+-   `constants.rs`: `IDC_CAPTURE_TRIGGER_COMBO` コントロールID定義
+-   `hook/mouse.rs`: `CaptureTrigger` 定義とトリガー判定
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: CBN_SELCHANGE通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    hook::mouse::CaptureTrigger,
+    settings::{CAPTURE_TRIGGER_VALUE_NAME, save_setting_dword},
+};
+
+/// キャプチャトリガーコンボボックスを初期化する
+///
+/// 「左」「中」「左+中」「右」の選択肢を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_capture_trigger_combo(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_CAPTURE_TRIGGER_COMBO) } {
+        let triggers = [
+            CaptureTrigger::Left,
+            CaptureTrigger::Middle,
+            CaptureTrigger::LeftAndMiddle,
+            CaptureTrigger::Right,
+        ];
+
+        let current_trigger = AppState::get_app_state_ref().capture_trigger;
+        let mut default_index = 0;
+
+        for trigger in triggers {
+            // Win32 APIに渡すためNull終端文字を付加
+            let text = format!("{}\0", trigger.label());
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+
+            // CB_ADDSTRING：コンボボックスに表示テキストを追加
+            let index = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(wide_text.as_ptr() as isize)),
+                )
+            }
+            .0 as usize;
+
+            // CB_SETITEMDATA：表示テキストとトリガー識別値を関連付け
+            unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_SETITEMDATA,
+                    Some(WPARAM(index)),
+                    Some(LPARAM(trigger as isize)),
+                );
+            }
+
+            if trigger == current_trigger {
+                default_index = index;
+            }
+        }
+
+        // 現在の設定値を選択状態に設定
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
+        }
+    }
+}
+
+/// キャプチャトリガーコンボボックスの選択変更イベントを処理する
+///
+/// 選択された項目の識別値から `CaptureTrigger` を復元し、`AppState` とレジストリに保存します。
+/// 変更は次回のキャプチャモード中のクリックから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_capture_trigger_combo_change(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_CAPTURE_TRIGGER_COMBO) } {
+        // CB_GETCURSEL：現在選択されている項目のインデックス取得
+        let selected_index =
+            unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 }
+                as i32;
+
+        if selected_index >= 0 {
+            // CB_GETITEMDATA：選択項目に関連付けられたトリガー識別値を取得
+            let trigger_value = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_GETITEMDATA,
+                    Some(WPARAM(selected_index as usize)),
+                    Some(LPARAM(0)),
+                )
+            }
+            .0 as u8;
+
+            let trigger = CaptureTrigger::from_u8(trigger_value);
+            let app_state = AppState::get_app_state_mut();
+            app_state.capture_trigger = trigger;
+            save_setting_dword(CAPTURE_TRIGGER_VALUE_NAME, trigger as u32);
+
+            println!("キャプチャトリガー設定変更: {}", trigger.label());
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/*
+============================================================================
+完了通知チェックボックスハンドラモジュール (completion_notify_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+自動クリックやPDF変換の完了を、通知領域のバルーンで知らせるかどうかを
+切り替える「完了を通知」チェックボックスを管理するモジュール。
+無人で長時間実行し、その場を離れている間に処理が終わっても気付けるようにします。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_completion_notify_checkbox`
+    -   `AppState.completion_notify_enabled` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_completion_notify_checkbox_change`
+    -   チェック状態を `AppState.completion_notify_enabled` に反映し、レジストリに保存
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **永続化**: `settings::save_setting_dword`（`CompletionNotify`）で次回起動時の初期値にする
+-   **常に有効**: 自動クリックの実行中にも切り替えられるよう、モード中も無効化しない
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `completion_notify_enabled` フィールドの保持
+-   `notification.rs`: `completion_notify_enabled` に基づく通知の可否の判定
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    settings::{COMPLETION_NOTIFY_VALUE_NAME, save_setting_dword},
+    strings,
+    system_utils::app_log,
+};
+
+/// 完了通知チェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_completion_notify_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().completion_notify_enabled;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_COMPLETION_NOTIFY_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 完了通知チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.completion_notify_enabled` に保存します。
+/// 変更は次回の自動クリック・PDF変換の完了時から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_completion_notify_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_COMPLETION_NOTIFY_CHECKBOX) } == BST_CHECKED.0;
+
+    AppState::get_app_state_mut().completion_notify_enabled = is_checked;
+    save_setting_dword(COMPLETION_NOTIFY_VALUE_NAME, is_checked as u32);
+    app_log(strings::completion_notify_changed(is_checked));
+}
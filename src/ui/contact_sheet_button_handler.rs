@@ -0,0 +1,94 @@
+/*
+============================================================================
+一覧画像ボタンハンドラモジュール (contact_sheet_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+「一覧PNG」ボタンのクリックと、ワーカースレッドからの完了通知を処理するモジュール。
+フォルダ内のキャプチャ画像をサムネイルの格子（コンタクトシート）にまとめます。
+作成処理はワーカースレッドで実行され、進捗はログ表示エリアに表示されます。
+
+【主要機能】
+1.  **ボタンクリック処理**: `handle_contact_sheet_button`
+    -   変換元フォルダ・列数・出力ファイル名を確認ダイアログで提示
+    -   `is_exporting_contact_sheet` を設定してUIを無効化し、`start_contact_sheet_export` でワーカースレッドを起動
+2.  **完了通知処理**: `handle_contact_sheet_export_complete`
+    -   `WM_CONTACT_SHEET_EXPORT_COMPLETE` 受信時にフラグを解除してUIを再度有効化し、結果を通知
+
+【AI解析用：依存関係】
+-   `export_contact_sheet.rs`: `start_contact_sheet_export`（ワーカースレッドの起動）
+-   `input_control_handlers.rs`: `update_input_control_states`（作成中のUI無効化）
+-   `sound.rs`: 完了チャイム
+-   メインダイアログ: BN_CLICKED通知と `WM_CONTACT_SHEET_EXPORT_COMPLETE` の受信
+ */
+
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::{
+    app_state::AppState,
+    export_contact_sheet::start_contact_sheet_export,
+    export_gif::get_gif_source_dir,
+    sound::{SoundEffect, play_sound},
+    strings,
+    system_utils::{app_log, show_message_box},
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 一覧画像ボタンのクリックイベントを処理する
+///
+/// 確認ダイアログで同意が得られた場合、作成中フラグを設定してUIを無効化し、
+/// ワーカースレッドで一覧画像の作成を開始します。完了は `handle_contact_sheet_export_complete` で処理します。
+pub fn handle_contact_sheet_button() -> isize {
+    let source_dir = get_gif_source_dir();
+    let app_state = AppState::get_app_state_mut();
+    let message = strings::contact_sheet_confirm(
+        source_dir.as_deref(),
+        app_state.contact_sheet_columns,
+        app_state.contact_sheet_thumbnail_width,
+    );
+    let result = show_message_box(
+        &message,
+        strings::contact_sheet_confirm_title(),
+        MB_OKCANCEL | MB_ICONQUESTION,
+    );
+
+    if result.0 != IDOK.0 {
+        app_log(strings::contact_sheet_cancelled());
+        return 1;
+    }
+
+    app_log(strings::contact_sheet_started());
+    app_state.is_exporting_contact_sheet = true;
+    update_input_control_states();
+
+    if !start_contact_sheet_export() {
+        app_state.is_exporting_contact_sheet = false;
+        update_input_control_states();
+    }
+    1
+}
+
+/// 一覧画像作成の完了通知（`WM_CONTACT_SHEET_EXPORT_COMPLETE`）を処理する
+///
+/// # 引数
+/// * `success` - 作成に成功した場合は `true`（詳細はワーカースレッドがログに出力済み）
+pub fn handle_contact_sheet_export_complete(success: bool) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.is_exporting_contact_sheet = false;
+    update_input_control_states();
+
+    if success {
+        play_sound(SoundEffect::Complete);
+        show_message_box(
+            strings::contact_sheet_completed_message(),
+            strings::contact_sheet_completed_title(),
+            MB_OK | MB_ICONINFORMATION,
+        );
+    } else {
+        show_message_box(
+            strings::contact_sheet_failed_message(),
+            strings::contact_sheet_failed_title(),
+            MB_OK | MB_ICONERROR,
+        );
+    }
+}
@@ -18,6 +18,9 @@
 2.  **ダイアログの復元と最前面表示 (`bring_dialog_to_front`)**:
     -   `ShowWindow` API (SW_RESTORE) で最小化状態から復元します。
     -   `SetWindowPos` API (HWND_TOP) でウィンドウをZオーダーの最前面に移動させ、ユーザーがすぐに操作できるようにします。
+3.  **起動時の初期化失敗の通知 (`report_startup_failures`)**:
+    -   GDI+の初期化やオーバーレイの描画リソースの作成に失敗した場合、`WM_INITDIALOG` の最後に1回だけ
+        メッセージボックスで通知します（オーバーレイ表示は無効、キャプチャ・保存は継続）。
 
 【技術仕様】
 -   `AppState` からグローバルなダイアログハンドルを取得して操作します。
@@ -31,6 +34,9 @@
 use windows::Win32::{
     Foundation::{HWND, LPARAM, WPARAM}, // 基本的なデータ型
     Graphics::Gdi::UpdateWindow,
+    System::RemoteDesktop::{
+        NOTIFY_FOR_THIS_SESSION, WTSRegisterSessionNotification, WTSUnRegisterSessionNotification,
+    },
     UI::WindowsAndMessaging::*,
 };
 
@@ -38,23 +44,45 @@ use crate::{
     app_state::AppState,
     area_select::*,
     constants::*,
+    global_hotkey::{handle_hotkey, register_global_hotkeys, unregister_global_hotkeys},
+    notification::{handle_notify_icon_message, notify_completion, remove_notify_icon},
+    overlay::saved_toast_overlay::handle_saved_toast_timer,
     screen_capture::*,
-    system_utils::{app_log, set_application_icon},
+    sound::{SoundEffect, play_sound},
+    strings,
+    system_utils::{app_log, handle_app_log_message, set_application_icon, show_message_box},
+    theme::handle_theme_setting_change,
     ui::{
         auto_click_checkbox_handler::*,
         auto_click_count_edit_handler::handle_auto_click_count_edit_change,
-        auto_click_interval_combo_handler::*, folder_manager::*,
+        auto_click_duration_combo_handler::*, auto_click_interval_combo_handler::*,
+        auto_click_mode_checkbox_handler::*, auto_pdf_export_checkbox_handler::*,
+        capture_format_combo_handler::*, capture_history_handler::handle_capture_history_button,
+        capture_metadata_checkbox_handler::*, capture_trigger_combo_handler::*,
+        completion_notify_checkbox_handler::*, contact_sheet_button_handler::*,
+        dual_area_layout_checkbox_handler::*, elapsed_stamp_checkbox_handler::*, folder_manager::*,
+        gif_export_button_handler::*, high_fidelity_text_checkbox_handler::*,
         icon_button::draw_icon_button_handler, input_control_handlers::initialize_icon_button,
-        path_edit_handler::init_path_edit_control,
-        pdf_export_button_handler::handle_pdf_export_button, pdf_size_combo_handler::*,
-        quality_combo_handler::*, scale_combo_handler::*,
+        keyboard_navigation_handler::*, language_combo_handler::*, overlay_settings_handler::*,
+        path_edit_handler::init_path_edit_control, pause_on_user_input_checkbox_handler::*,
+        pdf_archive_checkbox_handler::*, pdf_export_button_handler::*, pdf_size_combo_handler::*,
+        progressive_jpeg_checkbox_handler::*, quality_combo_handler::*,
+        real_input_only_checkbox_handler::*, renumber_button_handler::handle_renumber_button,
+        resave_button_handler::handle_resave_button,
+        reset_counter_button_handler::handle_reset_counter_button, scale_combo_handler::*,
+        schedule_handler::*, share_button_handler::*, sound_checkbox_handler::*,
+        target_width_edit_handler::*, theme_combo_handler::*, thumbnail_strip_handler::*,
+        tooltip_handler::*, window_capture_checkbox_handler::*,
+        work_area_button_handler::handle_work_area_button, zip_export_button_handler::*,
     },
 };
+use clickcapture::auto_click_runner::MAX_CAPTURE_COUNT;
 
 // ===== Windows標準のコントロール通知コード =====
 const CBN_SELCHANGE: u16 = 1; // コンボボックスの選択が変更された
 const BN_CLICKED: u16 = 0; // ボタンがクリックされた
 const EN_KILLFOCUS: u16 = 0x0200; // エディットボックスがフォーカスを失った
+const STN_CLICKED: u16 = 0; // スタティックコントロールがクリックされた（SS_NOTIFY）
 
 /*
 ============================================================================
@@ -67,7 +95,12 @@ Windowsメッセージループの中核。ダイアログで発生する全て
 【処理メッセージ】
 - WM_INITDIALOG: ダイアログの初回表示時に一度だけ呼ばれ、UIコントロールの初期化を行う。
 - WM_COMMAND: ボタンクリックやコンボボックスの選択変更など、ユーザー操作を処理する。
+  Enter（IDOK）・Esc（IDCANCEL）もここで受け取る。
+- WM_SYSCHAR / WM_SYSCOMMAND（SC_KEYMENU）: アクセラレータ（Alt+A / Alt+C / Alt+P）
 - WM_DRAWITEM: オーナードローボタン描画（アイコン表示）
+- WM_TIMER: 保存完了トーストのフェードアウト
+- WM_SCHEDULED_CAPTURE / WM_SCHEDULED_CAPTURE_COMPLETE: 定期キャプチャの実行要求と完了通知
+- WM_APP_LOG: ワーカースレッドからのログ出力（ログ表示ボックスの更新はメインスレッドのみで行う）
 - WM_CLOSE: 終了処理（リソースクリーンアップ）
 
 【リソース管理責任】
@@ -103,6 +136,9 @@ pub unsafe extern "system" fn dialog_proc(
             // スケールコンボボックスを初期化
             initialize_scale_combo(hwnd);
 
+            // 出力幅エディットボックスを初期化
+            initialize_target_width_edit(hwnd);
+
             // JPEG品質コンボボックスを初期化
             initialize_quality_combo(hwnd);
 
@@ -115,8 +151,95 @@ pub unsafe extern "system" fn dialog_proc(
             // 自動クリック間隔コンボボックスを初期化
             initialize_auto_click_interval_combo(hwnd);
 
+            // 自動クリック最大実行時間コンボボックスを初期化
+            initialize_auto_click_duration_combo(hwnd);
+
+            // 自動クリック動作モードチェックボックスを初期化
+            initialize_auto_click_mode_checkbox(hwnd);
+
+            // ユーザー操作中の一時停止チェックボックスを初期化
+            initialize_pause_on_user_input_checkbox(hwnd);
+
+            // キャプチャトリガーコンボボックスを初期化
+            initialize_capture_trigger_combo(hwnd);
+
+            // 保存形式コンボボックスを初期化
+            initialize_capture_format_combo(hwnd);
+
+            // ウィンドウ単体キャプチャチェックボックスを初期化
+            initialize_window_capture_checkbox(hwnd);
+
+            // サウンドチェックボックスを初期化
+            initialize_sound_checkbox(hwnd);
+            initialize_completion_notify_checkbox(hwnd);
+
+            // 高精細テキストモードチェックボックスを初期化
+            initialize_high_fidelity_text_checkbox(hwnd);
+
+            // プログレッシブJPEGチェックボックスを初期化（フィーチャー無効時は非表示）
+            initialize_progressive_jpeg_checkbox(hwnd);
+
+            // PDF/Aチェックボックスを初期化
+            initialize_pdf_archive_checkbox(hwnd);
+
+            // PDF自動変換チェックボックスを初期化
+            initialize_auto_pdf_export_checkbox(hwnd);
+
+            // 経過時間スタンプチェックボックスを初期化
+            initialize_elapsed_stamp_checkbox(hwnd);
+
+            // 実入力限定チェックボックスを初期化
+            initialize_real_input_only_checkbox(hwnd);
+
+            // 撮影情報記録チェックボックスを初期化
+            initialize_capture_metadata_checkbox(hwnd);
+
+            // 言語コンボボックスを初期化
+            initialize_language_combo(hwnd);
+
+            // テーマコンボボックスを初期化
+            initialize_theme_combo(hwnd);
+
+            // オーバーレイの表示方法・暗転の濃さコンボボックス、境界線のスタイルを初期化
+            initialize_overlay_visibility_combo(hwnd);
+            initialize_dim_opacity_combo(hwnd);
+            initialize_border_style_controls(hwnd);
+            initialize_freeze_screen_checkbox(hwnd);
+
+            // 2領域結合方向チェックボックスを初期化
+            initialize_dual_area_layout_checkbox(hwnd);
+
+            // 定期キャプチャ関連コントロールを初期化
+            initialize_schedule_controls(hwnd);
+
+            // 共有UIを使用できるかを判定し、「共有」ボタンの有効状態に反映
+            initialize_share_button();
+
+            // サムネイルストリップからのファイルのドラッグを有効化
+            initialize_thumbnail_strip(hwnd);
+
+            // エクスプローラーからのフォルダーのドロップ（保存先の変更）を受け付ける
+            initialize_folder_drop(hwnd);
+
+            // セッションのロック・ロック解除の通知を受け取る（ロック中は自動クリックを一時停止）
+            let registered =
+                unsafe { WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) };
+            if let Err(e) = registered {
+                app_log(&strings::session_notification_failed(&e));
+            }
+
+            // 各コントロールのツールチップを作成
+            initialize_tooltips(hwnd);
+
+            // クイックキャプチャ・再キャプチャのホットキーを登録
+            // （既定: Ctrl+Alt+Shift+W / Ctrl+Alt+Shift+R、他のアプリが使用中の場合はログのみ）
+            register_global_hotkeys(hwnd);
+
             app_log("システム準備完了");
 
+            // GDI+・オーバーレイの初期化に失敗していれば、機能の制限をまとめて1回だけ通知
+            report_startup_failures();
+
             return 1;
         }
         WM_COMMAND => {
@@ -124,6 +247,18 @@ pub unsafe extern "system" fn dialog_proc(
             let notify_code = (wparam.0 >> 16) as u16; // 上位16ビット：通知コード
 
             match id {
+                id if id == IDOK.0 => {
+                    // 1 - Enterキー（既定のボタン：通常モードではキャプチャ開始）
+                    handle_dialog_enter(hwnd);
+                    return 1;
+                }
+                id if id == IDCANCEL.0 => {
+                    // 2 - Escキー（閉じるボタンと同じ、処理の実行中は確認）
+                    if confirm_dialog_escape() {
+                        shutdown_application(hwnd);
+                    }
+                    return 1;
+                }
                 IDC_BROWSE_BUTTON => {
                     // 1001
                     // ディレクトリ選択ダイアログを表示
@@ -210,12 +345,352 @@ pub unsafe extern "system" fn dialog_proc(
                     }
                     return 1;
                 }
+                IDC_TARGET_WIDTH_EDIT => {
+                    // 1023 - 出力幅エディットボックス
+                    if notify_code == EN_KILLFOCUS {
+                        app_log("出力幅エディットボックスの内容が変更されました");
+                        handle_target_width_edit_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_CAPTURE_TRIGGER_COMBO => {
+                    // 1017 - キャプチャトリガーコンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("キャプチャトリガーコンボボックスの選択が変更されました");
+                        handle_capture_trigger_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_CAPTURE_FORMAT_COMBO => {
+                    // 1022 - 保存形式コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("保存形式コンボボックスの選択が変更されました");
+                        handle_capture_format_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_WINDOW_CAPTURE_CHECKBOX => {
+                    // 1018 - ウィンドウ単体キャプチャチェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("ウィンドウ単体キャプチャチェックボックスの状態が変更されました");
+                        handle_window_capture_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_CAPTURE_METADATA_CHECKBOX => {
+                    // 1032 - 撮影情報記録チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("撮影情報記録チェックボックスの状態が変更されました");
+                        handle_capture_metadata_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_LANGUAGE_COMBO => {
+                    // 1033 - 言語コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("言語コンボボックスの選択が変更されました");
+                        handle_language_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_THEME_COMBO => {
+                    // 1034 - テーマコンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("テーマコンボボックスの選択が変更されました");
+                        handle_theme_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_EXPORT_CONTACT_SHEET_BUTTON => {
+                    // 1035 - 一覧画像ボタン
+                    if notify_code == BN_CLICKED {
+                        app_log("一覧画像ボタンがクリックされました");
+                        handle_contact_sheet_button();
+                    }
+                    return 1;
+                }
+                IDC_OVERLAY_VISIBILITY_COMBO => {
+                    // 1036 - 表示方法コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("表示方法コンボボックスの選択が変更されました");
+                        handle_overlay_visibility_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_DIM_OPACITY_COMBO => {
+                    // 1037 - 暗転の濃さコンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("暗転の濃さコンボボックスの選択が変更されました");
+                        handle_dim_opacity_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_BORDER_COLOR_COMBO => {
+                    // 1038 - 境界線の色コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("境界線の色コンボボックスの選択が変更されました");
+                        handle_border_color_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_BORDER_WIDTH_COMBO => {
+                    // 1039 - 境界線の太さコンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("境界線の太さコンボボックスの選択が変更されました");
+                        handle_border_width_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_BORDER_HALO_CHECKBOX => {
+                    // 1040 - 縁取りチェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("縁取りチェックボックスの状態が変更されました");
+                        handle_border_halo_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_FREEZE_SCREEN_CHECKBOX => {
+                    // 1041 - 画面固定チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("画面固定チェックボックスの状態が変更されました");
+                        handle_freeze_screen_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_COMPLETION_NOTIFY_CHECKBOX => {
+                    // 1042 - 完了通知チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("完了通知チェックボックスの状態が変更されました");
+                        handle_completion_notify_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_HISTORY_BUTTON => {
+                    // 1043 - 履歴ボタン
+                    if notify_code == BN_CLICKED {
+                        app_log("履歴ボタンがクリックされました");
+                        handle_capture_history_button(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_WORK_AREA_BUTTON => {
+                    // 1046 - 作業領域ボタン（タスクバーを除く範囲をキャプチャエリアに設定）
+                    if notify_code == BN_CLICKED {
+                        app_log("作業領域ボタンがクリックされました");
+                        handle_work_area_button();
+                    }
+                    return 1;
+                }
+                IDC_RENUMBER_BUTTON => {
+                    // 1047 - 連番整理ボタン（欠番を詰めて0001から振り直し）
+                    if notify_code == BN_CLICKED {
+                        app_log("連番整理ボタンがクリックされました");
+                        handle_renumber_button();
+                    }
+                    return 1;
+                }
+                IDC_RESET_COUNTER_BUTTON => {
+                    // 1048 - 連番リセットボタン（次のキャプチャを0001から保存）
+                    if notify_code == BN_CLICKED {
+                        app_log("連番リセットボタンがクリックされました");
+                        handle_reset_counter_button();
+                    }
+                    return 1;
+                }
+                IDC_SOUND_CHECKBOX => {
+                    // 1019 - サウンド再生チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("サウンド再生チェックボックスの状態が変更されました");
+                        handle_sound_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_HIGH_FIDELITY_TEXT_CHECKBOX => {
+                    // 1020 - 高精細テキストモードチェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("高精細テキストモードチェックボックスの状態が変更されました");
+                        handle_high_fidelity_text_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_PROGRESSIVE_JPEG_CHECKBOX => {
+                    // 1049 - プログレッシブJPEGチェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("プログレッシブJPEGチェックボックスの状態が変更されました");
+                        handle_progressive_jpeg_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_PDF_ARCHIVE_CHECKBOX => {
+                    // 1050 - PDF/Aチェックボックス
+                    if notify_code == BN_CLICKED {
+                        handle_pdf_archive_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_AUTO_PDF_EXPORT_CHECKBOX => {
+                    // 1051 - PDF自動変換チェックボックス
+                    if notify_code == BN_CLICKED {
+                        handle_auto_pdf_export_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_AUTO_CLICK_DURATION_COMBO => {
+                    // 1053 - 自動クリック最大実行時間コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        handle_auto_click_duration_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_ELAPSED_STAMP_CHECKBOX => {
+                    // 1054 - 経過時間スタンプチェックボックス
+                    if notify_code == BN_CLICKED {
+                        handle_elapsed_stamp_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_PAUSE_ON_USER_INPUT_CHECKBOX => {
+                    // 1055 - ユーザー操作中の一時停止チェックボックス
+                    if notify_code == BN_CLICKED {
+                        handle_pause_on_user_input_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_REAL_INPUT_ONLY_CHECKBOX => {
+                    // 1021 - 実入力限定チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("実入力限定チェックボックスの状態が変更されました");
+                        handle_real_input_only_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_DUAL_AREA_STACKED_CHECKBOX => {
+                    // 1027 - 2領域結合方向チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("2領域結合方向チェックボックスの状態が変更されました");
+                        handle_dual_area_layout_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX => {
+                    // 1028 - 自動クリック動作モード（キャプチャのみ）チェックボックス
+                    if notify_code == BN_CLICKED {
+                        app_log("自動クリック動作モードチェックボックスの状態が変更されました");
+                        handle_auto_click_mode_checkbox_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_EXPORT_GIF_BUTTON => {
+                    // 1029 - GIF変換ボタン
+                    if notify_code == BN_CLICKED {
+                        app_log("GIF変換ボタンがクリックされました");
+                        handle_gif_export_button();
+                    }
+                    return 1;
+                }
+                IDC_EXPORT_ZIP_BUTTON => {
+                    // 1030 - ZIP圧縮ボタン（実行中は中止ボタン）
+                    if notify_code == BN_CLICKED {
+                        app_log("ZIP圧縮ボタンがクリックされました");
+                        handle_zip_export_button(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_SHARE_BUTTON => {
+                    // 1052 - 共有ボタン（最後に保存したキャプチャ）
+                    if notify_code == BN_CLICKED {
+                        app_log("共有ボタンがクリックされました");
+                        handle_share_button(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_RESAVE_BUTTON => {
+                    // 1031 - 再保存ボタン（保存に失敗したキャプチャ）
+                    if notify_code == BN_CLICKED {
+                        app_log("再保存ボタンがクリックされました");
+                        handle_resave_button();
+                    }
+                    return 1;
+                }
+                IDC_SCHEDULE_BUTTON => {
+                    // 1024 - 定期キャプチャ開始/停止ボタン
+                    if notify_code == BN_CLICKED {
+                        app_log("定期キャプチャボタンがクリックされました");
+                        handle_schedule_button(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_SCHEDULE_INTERVAL_COMBO => {
+                    // 1025 - 定期キャプチャ間隔コンボボックス
+                    if notify_code == CBN_SELCHANGE {
+                        app_log("定期キャプチャ間隔コンボボックスの選択が変更されました");
+                        handle_schedule_interval_combo_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_SCHEDULE_COUNT_EDIT => {
+                    // 1026 - 定期キャプチャ回数エディットボックス
+                    if notify_code == EN_KILLFOCUS {
+                        app_log("定期キャプチャ回数エディットボックスの内容が変更されました");
+                        handle_schedule_count_edit_change(hwnd);
+                    }
+                    return 1;
+                }
+                IDC_THUMBNAIL_STRIP => {
+                    // 1016 - サムネイルストリップ
+                    // クリックされたサムネイルのファイルを開く（ドラッグはサブクラスで処理）
+                    if notify_code == STN_CLICKED {
+                        handle_thumbnail_strip_click(hwnd);
+                    }
+                    return 1;
+                }
                 _ => {}
             }
         }
         WM_DRAWITEM => {
-            // オーナードローボタンの描画処理
-            draw_icon_button_handler(hwnd, wparam, _lparam);
+            if wparam.0 as i32 == IDC_THUMBNAIL_STRIP {
+                // サムネイルストリップの描画処理
+                draw_thumbnail_strip(_lparam);
+            } else {
+                // オーナードローボタンの描画処理
+                draw_icon_button_handler(hwnd, wparam, _lparam);
+            }
+            return 1;
+        }
+
+        WM_DROPFILES => {
+            // エクスプローラーからドロップされたフォルダーを保存先に設定
+            handle_dropped_folder(hwnd, wparam);
+            return 1;
+        }
+
+        WM_CONTEXTMENU => {
+            // 「参照」ボタンの右クリックで最近使ったフォルダーのメニューを表示
+            let on_browse_button = unsafe { GetDlgItem(Some(hwnd), IDC_BROWSE_BUTTON) }
+                .is_ok_and(|button| button == HWND(wparam.0 as *mut _));
+            if on_browse_button {
+                show_recent_folders_menu(hwnd, _lparam);
+                return 1;
+            }
+        }
+
+        WM_WTSSESSION_CHANGE => {
+            // ロック中は自動クリックを一時停止し、ロック解除後に再開する（スレッドが状態を確認する）
+            match wparam.0 as u32 {
+                WTS_SESSION_LOCK => AppState::get_app_state_ref()
+                    .auto_clicker
+                    .set_session_locked(true),
+                WTS_SESSION_UNLOCK => AppState::get_app_state_ref()
+                    .auto_clicker
+                    .set_session_locked(false),
+                _ => {}
+            }
+            return 1;
+        }
+
+        WM_HOTKEY => {
+            // RegisterHotKeyで登録したホットキー（wparamは登録時のID）
+            handle_hotkey(wparam.0 as i32);
             return 1;
         }
 
@@ -226,20 +701,121 @@ pub unsafe extern "system" fn dialog_proc(
         }
         WM_DESTROY => {
             // ウィンドウが破棄される直前に呼ばれる。
+            // 登録したホットキーとツールチップ、完了通知のアイコンを解除してから、
             // `WM_INITDIALOG` で確保した `AppState` のメモリをここで解放する。
+            unregister_global_hotkeys(hwnd);
+            destroy_tooltips();
+            remove_notify_icon();
+            let _ = unsafe { WTSUnRegisterSessionNotification(hwnd) };
             AppState::cleanup_app_state(hwnd);
             return 1;
         }
+        WM_TIMER => {
+            if wparam.0 == TIMER_ID_SAVED_TOAST {
+                // 保存完了トーストのフェードアウト処理
+                handle_saved_toast_timer(hwnd);
+                return 1;
+            }
+            if wparam.0 == TIMER_ID_CAPTURE_OVERLAY_ELAPSED {
+                // 定期キャプチャ中の経過時間オーバーレイの再描画
+                handle_elapsed_overlay_timer(hwnd);
+                return 1;
+            }
+        }
         WM_AUTO_CLICK_COMPLETE => {
-            // 自動クリック処理スレッドからの完了通知
+            // 自動クリック処理スレッドからの完了通知（WPARAM：1=安全上限の回数に到達して停止）
+            if wparam.0 != 0 {
+                show_message_box(
+                    &strings::auto_click_limit_reached(MAX_CAPTURE_COUNT),
+                    strings::auto_click_warning_title(),
+                    MB_OK | MB_ICONWARNING,
+                );
+            }
             app_log("✅ 自動連続クリック処理が完了しました。");
+            // 完了チャイム（失敗してもキャプチャ処理には影響しない）
+            play_sound(SoundEffect::Complete);
             let app_state = AppState::get_app_state_ref();
+            // 完了通知（無人実行中でも気付けるよう、回数と経過時間をバルーンで表示）
+            notify_completion(
+                strings::auto_click_complete_notification_title(),
+                &strings::auto_click_complete_notification(
+                    app_state.auto_clicker.get_progress_count(),
+                    app_state.auto_clicker.get_elapsed(),
+                ),
+            );
             // キャプチャモード中であれば、モードを終了する
-            if app_state.is_capture_mode {
+            if app_state.is_capture_mode() {
                 toggle_capture_mode();
             }
             return 1;
         }
+        WM_AUTO_PDF_EXPORT => {
+            // キャプチャモード終了時のPDF自動変換の要求（このモード中に保存した画像がある場合のみ）
+            handle_auto_pdf_export();
+            return 1;
+        }
+        WM_NOTIFY_ICON => {
+            // 完了通知の通知領域アイコンからのイベント（バルーンのクリック・タイムアウトなど）
+            handle_notify_icon_message(_lparam);
+            return 1;
+        }
+        WM_ESC_STOP_CONFIRM => {
+            // キーボードフックからの、ESCキーによる自動クリックの停止の確認の要求
+            handle_esc_stop_confirm();
+            return 1;
+        }
+        WM_AUTO_CAPTURE_REQUEST => {
+            // 自動クリック（キャプチャのみモード）スレッドからのキャプチャ要求
+            handle_auto_capture_request();
+            return 1;
+        }
+        WM_GIF_EXPORT_COMPLETE => {
+            // GIF変換スレッドからの完了通知（WPARAM：1=成功, 0=失敗）
+            handle_gif_export_complete(wparam.0 != 0);
+            return 1;
+        }
+        WM_CONTACT_SHEET_EXPORT_COMPLETE => {
+            // 一覧画像作成スレッドからの完了通知（WPARAM：1=成功, 0=失敗）
+            handle_contact_sheet_export_complete(wparam.0 != 0);
+            return 1;
+        }
+        WM_ZIP_EXPORT_COMPLETE => {
+            // ZIP圧縮スレッドからの終了通知（WPARAM：1=成功, 0=失敗・中止）
+            handle_zip_export_complete(hwnd, wparam.0 != 0);
+            return 1;
+        }
+        // ダイアログにフォーカスがある場合のアクセラレータ（WPARAM：文字コード）
+        WM_SYSCHAR if handle_dialog_accelerator(hwnd, wparam.0 as u32) => {
+            return 1;
+        }
+        // 子コントロールにフォーカスがある場合のアクセラレータ（SC_KEYMENU、LPARAM：文字コード）
+        WM_SYSCOMMAND
+            if (wparam.0 as u32 & 0xFFF0) == SC_KEYMENU
+                && handle_dialog_accelerator(hwnd, _lparam.0 as u32) =>
+        {
+            return 1;
+        }
+        WM_SETTINGCHANGE => {
+            // OS設定の変更通知（LPARAM：設定名）。アプリモード（ライト/ダーク）の切り替えに追従する
+            handle_theme_setting_change(hwnd, _lparam);
+            return 0; // 既定の処理も行わせる
+        }
+        WM_APP_LOG => {
+            // ワーカースレッドからのログ出力（LPARAM：Box<String>のポインタ、ハンドラで解放）
+            handle_app_log_message(_lparam);
+            return 1;
+        }
+        WM_SCHEDULED_CAPTURE => {
+            // 定期キャプチャスレッドからのキャプチャ要求（WPARAM：実行回数）
+            handle_scheduled_capture_tick(wparam.0 as u32);
+            return 1;
+        }
+        WM_SCHEDULED_CAPTURE_COMPLETE => {
+            // 定期キャプチャスレッドからの完了通知（最大回数・最大継続時間に到達）
+            app_log("✅ 定期キャプチャが完了しました。");
+            finish_scheduled_capture(hwnd);
+            return 1;
+        }
         _ => (),
     }
     0 // FALSE
@@ -297,17 +873,42 @@ pub fn bring_dialog_to_front() {
     }
 }
 
+/// 起動時の初期化失敗（GDI+・オーバーレイの描画リソース）をログに出力し、1つのメッセージボックスで通知する
+///
+/// コンソールのないリリースビルドでは `eprintln!` が見えず、オーバーレイが表示されない理由が
+/// 分からないため、失敗があった場合のみ表示します。キャプチャ・保存はそのまま継続できます。
+fn report_startup_failures() {
+    let failures = AppState::get_app_state_ref().startup_failures();
+    if failures.is_empty() {
+        return;
+    }
+
+    for failure in &failures {
+        app_log(&format!("⚠️ {}", failure));
+    }
+    show_message_box(
+        &strings::startup_failures_message(&failures),
+        strings::startup_failures_title(),
+        MB_OK | MB_ICONWARNING,
+    );
+}
+
 /// アプリケーション終了時のクリーンアップ処理を行い、ダイアログを閉じてアプリケーションを終了させる
 fn shutdown_application(hwnd: HWND) {
     app_log("ダイアログを終了しています...");
 
     // 各モードが有効な場合は、安全に終了させる
-    let app_state = AppState::get_app_state_ref();
+    let app_state = AppState::get_app_state_mut();
+
+    // 定期キャプチャ実行中ならスレッドを停止
+    if app_state.scheduled_capturer.is_running() {
+        app_state.scheduled_capturer.stop();
+    }
 
-    if app_state.is_capture_mode {
+    if app_state.is_capture_mode() {
         // キャプチャモード中なら終了
         toggle_capture_mode();
-    } else if app_state.is_area_select_mode {
+    } else if app_state.is_area_select_mode() {
         // エリア選択モード中なら終了
         cancel_area_select_mode();
     }
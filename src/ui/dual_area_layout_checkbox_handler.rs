@@ -0,0 +1,81 @@
+/*
+============================================================================
+2領域結合方向チェックボックスハンドラモジュール (dual_area_layout_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+デュアル選択（Shift+ドラッグで2つ目の領域を選択）時に、2つの領域を
+上下に並べて結合するか（ON）、左右に並べて結合するか（OFF）を切り替える
+「2領域を上下に結合」チェックボックスを管理するモジュール。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_dual_area_layout_checkbox`
+    -   `AppState.dual_area_layout` の値をチェック状態に反映（デフォルト：OFF＝左右）
+2.  **チェック状態変更処理**: `handle_dual_area_layout_checkbox_change`
+    -   チェック状態を `AppState.dual_area_layout` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **結合処理**: `capturer.rs` の `compose_dual_images`（領域間に `DUAL_AREA_GAP` の余白）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `dual_area_layout` フィールドの保持
+-   `constants.rs`: `IDC_DUAL_AREA_STACKED_CHECKBOX` コントロールID定義
+-   `screen_capture.rs`: `selected_area_2` 選択時の結合処理
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use clickcapture::capturer::DualAreaLayout;
+
+use crate::{app_state::AppState, constants::*};
+
+/// 2領域結合方向チェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_dual_area_layout_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().dual_area_layout == DualAreaLayout::Stacked;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_DUAL_AREA_STACKED_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 2領域結合方向チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.dual_area_layout` に保存します。
+/// 変更は次回のキャプチャから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_dual_area_layout_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_DUAL_AREA_STACKED_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.dual_area_layout = if is_checked {
+        DualAreaLayout::Stacked
+    } else {
+        DualAreaLayout::SideBySide
+    };
+
+    println!(
+        "2領域結合方向設定変更: {}",
+        if is_checked { "上下" } else { "左右" }
+    );
+}
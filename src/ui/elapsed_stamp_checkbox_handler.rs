@@ -0,0 +1,80 @@
+/*
+============================================================================
+経過時間スタンプチェックボックスハンドラモジュール (elapsed_stamp_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャ画像の左上に、撮影時刻と前回のキャプチャからの経過時間を焼き込むかどうかを切り替える
+「経過時間を画像に表示」チェックボックスを管理するモジュール。
+ユーザビリティテストなどで、各画面の操作にかかった時間を画像だけで確認できるようにします。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_elapsed_stamp_checkbox`
+    -   `AppState.burn_in_elapsed_stamp` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_elapsed_stamp_checkbox_change`
+    -   チェック状態を `AppState.burn_in_elapsed_stamp` に反映し、レジストリに保存
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **永続化**: `settings::save_setting_dword`（`ElapsedStamp`）で次回起動時の初期値にする
+-   **描画**: `capture_pipeline.rs` の `grab` が、`GetDIBits` の前にGDIでメモリDCへ描画する
+    （画像のピクセルの一部になるため、EXIF・コメントと異なりどの保存形式・ビューアーでも表示される）
+-   **経過時間**: 前回保存したキャプチャの取得開始からの時間。キャプチャモード・定期キャプチャの開始時にリセット
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `burn_in_elapsed_stamp`・`last_capture_started_at` の保持
+-   `capture_pipeline.rs`: スタンプの文字列の作成（`format_elapsed_stamp`）と描画（`draw_elapsed_stamp`）
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    settings::{ELAPSED_STAMP_VALUE_NAME, save_setting_dword},
+    strings,
+    system_utils::app_log,
+};
+
+/// 経過時間スタンプチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_elapsed_stamp_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().burn_in_elapsed_stamp;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_ELAPSED_STAMP_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 経過時間スタンプチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.burn_in_elapsed_stamp` に保存します。
+/// 変更は次回のキャプチャから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_elapsed_stamp_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_ELAPSED_STAMP_CHECKBOX) } == BST_CHECKED.0;
+
+    AppState::get_app_state_mut().burn_in_elapsed_stamp = is_checked;
+    save_setting_dword(ELAPSED_STAMP_VALUE_NAME, is_checked as u32);
+    app_log(strings::elapsed_stamp_changed(is_checked));
+}
@@ -10,49 +10,105 @@ Windows Shell APIと連携し、最適な保存先の自動検出と、ユーザ
 【主要機能】
 1.  **フォルダー選択ダイアログ (`show_folder_dialog`)**:
     -   `SHBrowseForFolderW` APIを利用して、ネイティブのフォルダー選択ダイアログを表示します。
+    -   ダイアログ本体は `browse_for_folder` として分離し、PDF変換の変換元/出力先選択でも再利用します。
 2.  **最適保存先の自動決定 (`get_pictures_folder`)**:
-    -   OneDrive上のピクチャフォルダ、ローカルのピクチャフォルダなどを優先順位に従って探索し、書き込み可能な最適なフォルダを自動で決定します。
+    -   `SHGetKnownFolderPath(FOLDERID_Pictures)` が返す実際のピクチャフォルダを最優先に、
+        OneDrive上のピクチャフォルダ、ローカルのピクチャフォルダなどを優先順位に従って探索し、書き込み可能な最適なフォルダを自動で決定します。
 3.  **書き込み権限の検証 (`is_folder_writable`)**:
     -   実際に一時ファイルを作成・削除することで、フォルダへの書き込み権限を確実にテストします。
+4.  **フォルダーのドロップ (`initialize_folder_drop` / `handle_dropped_folder`)**:
+    -   エクスプローラーからダイアログにドロップしたフォルダー（ファイルの場合はその親フォルダー）を保存先に設定します。
+5.  **最近使ったフォルダー (`show_recent_folders_menu`)**:
+    -   「参照」ボタンの右クリック（またはShift+F10）で、最近選択した保存先（最大8件）をメニューに表示し、
+        選んだフォルダーを保存先に設定します。存在しないフォルダーは無効表示にし、メニューから一覧を整理できます。
 
 【設計原則】
 -   **フォールバック戦略**: 複数の候補から安全な保存先を選択する堅牢な設計。
 -   **実用的な検証**: ACL（アクセス制御リスト）の確認ではなく、実際のファイル書き込みテストによる確実な権限検証。
--   **国際化対応**: 日本語版・英語版Windowsの両方で「ピクチャ」フォルダを正しく認識。
+-   **国際化対応**: Known Folderの実パスを使用するため、ピクチャフォルダを別ドライブへ移動した環境や
+    日本語・英語以外の言語のWindowsでも「ピクチャ」フォルダを正しく認識。
 
 【技術仕様】
--   **API連携**: Windows Shell API (`SHBrowseForFolderW`, `SHGetPathFromIDListW`) との統合。
--   **COM初期化**: Shell APIの呼び出し前に `CoInitialize` を行い、適切に処理。
+-   **API連携**: Windows Shell API (`SHBrowseForFolderW`, `SHGetPathFromIDListW`, `SHGetKnownFolderPath`) との統合。
+-   **COM初期化**: Shell APIの呼び出し前に `CoInitialize` を行い、成功した場合のみ `CoUninitialize` で対にする
+    （`RPC_E_CHANGED_MODE` の場合は解除せず、STAを必要としない従来形式のダイアログを使用）。
+-   **前回フォルダーの記憶**: 選択した保存先を `HKCU\Software\ClickCapture` の `LastFolder` に保存し、
+    次回起動時の既定の保存先とします。フォルダー選択ダイアログは `BFFM_SETSELECTIONW` で現在のフォルダーから開きます。
+-   **最近使ったフォルダー**: 保存先を設定するたびに `RecentFolders`（REG_SZ、`|` 区切り、新しい順）の先頭に追加します。
+    `|` はパスに使用できない文字のため区切りに使用し、大文字・小文字と末尾の `\` の違いは同じフォルダーとして扱います。
+    メニューは `TrackPopupMenu`（`TPM_RETURNCMD`）で表示し、フォルダー名の `&` はアクセスキーにならないよう `&&` にします。
+-   **ドロップ**: `DragAcceptFiles` で `WM_DROPFILES` を受け付け、`DragQueryFileW` でパスを取得（`DragFinish` で解放）。
+    管理者として実行した場合に備え、`ChangeWindowMessageFilterEx` でドロップ関連のメッセージを許可します。
 -   **Unicode文字列処理**: `OsString::from_wide` を使用して、Windows APIが返すUTF-16文字列を安全に扱います。
 
 【AI解析用：依存関係】
 - `app_state.rs`: ユーザーが選択したフォルダパスを `AppState` に保存。
 - `main.rs`: UI上の「参照」ボタンがクリックされた際に `show_folder_dialog` を呼び出す。
+- `dialog_handler.rs`: 「参照」ボタン上の `WM_CONTEXTMENU` で `show_recent_folders_menu` を呼び出す。
+- `clickcapture::save_folder`（ライブラリ）: 候補フォルダーの優先順位と選択（Win32 APIなし、単体テストあり）。
+- `settings.rs`: `LastFolder` / `RecentFolders` の読み書き（`load_setting_string` / `save_setting_string`）。
 - `initialize_controls.rs`: アプリケーション起動時に `get_pictures_folder` を呼び出してデフォルトの保存先を設定する。
 
 ============================================================================
 */
 
-use crate::{app_state::*, system_utils::app_log};
+use clickcapture::save_folder::{FALLBACK_SAVE_FOLDER, choose_save_folder, folder_candidates};
+
+use crate::{
+    app_state::*,
+    constants::{IDC_BROWSE_BUTTON, IDC_PATH_EDIT},
+    settings::{load_setting_string, save_setting_string},
+    strings,
+    system_utils::app_log,
+};
 use std::{
     ffi::OsString,
     fs::{self, File},
     os::windows::ffi::OsStringExt,
-    path::Path,
+    path::{Path, PathBuf},
     ptr,
 };
 use windows::{
     Win32::{
-        Foundation::{HWND, LPARAM},
-        System::Com::{CoInitialize, CoTaskMemFree},
+        Foundation::{HWND, LPARAM, RECT, RPC_E_CHANGED_MODE, WPARAM},
+        System::Com::{CoInitialize, CoTaskMemFree, CoUninitialize},
         UI::{
-            Shell::{BROWSEINFOW, SHBrowseForFolderW, SHGetPathFromIDListW},
-            WindowsAndMessaging::{GetDlgItem, SetWindowTextW},
+            Input::KeyboardAndMouse::IsWindowEnabled,
+            Shell::{
+                BFFM_INITIALIZED, BFFM_SETSELECTIONW, BROWSEINFOW, DragAcceptFiles, DragFinish,
+                DragQueryFileW, FOLDERID_Pictures, HDROP, KF_FLAG_DEFAULT, SHBrowseForFolderW,
+                SHGetKnownFolderPath, SHGetPathFromIDListW,
+            },
+            WindowsAndMessaging::{
+                AppendMenuW, ChangeWindowMessageFilterEx, CreatePopupMenu, DestroyMenu, GetDlgItem,
+                GetWindowRect, MF_CHECKED, MF_GRAYED, MF_SEPARATOR, MF_STRING, MSGFLT_ALLOW,
+                SendMessageW, SetWindowTextW, TPM_LEFTALIGN, TPM_RETURNCMD, TPM_RIGHTBUTTON,
+                TPM_TOPALIGN, TrackPopupMenu, WM_COPYDATA, WM_DROPFILES,
+            },
         },
     },
-    core::PCWSTR,
+    core::{PCWSTR, w},
 };
 
+/// ドロップ時にプロセス間でデータを渡すメッセージ（windowsクレートに定数がないため定義、UIPIで許可する）
+const WM_COPYGLOBALDATA: u32 = 0x0049;
+
+/// 前回選択した保存先フォルダーを保存するレジストリ値の名前
+const LAST_FOLDER_VALUE_NAME: PCWSTR = w!("LastFolder");
+
+/// 最近使った保存先フォルダーを保存するレジストリ値の名前（`|` 区切り、新しい順）
+const RECENT_FOLDERS_VALUE_NAME: PCWSTR = w!("RecentFolders");
+
+/// 最近使ったフォルダーの区切り文字（Windowsのパスに使用できない文字）
+const RECENT_FOLDERS_SEPARATOR: char = '|';
+
+/// 最近使ったフォルダーとして記憶する最大件数
+const MAX_RECENT_FOLDERS: usize = 8;
+
+/// 最近使ったフォルダーメニューの「見つからないフォルダーを一覧から削除」のコマンドID
+/// （フォルダーの項目は1から始まる番号を使用する）
+const PRUNE_RECENT_FOLDERS_COMMAND: usize = 100;
+
 /**
  * フォルダー選択ダイアログを表示し、ユーザーが選択したパスを `AppState` に保存する
  *
@@ -63,41 +119,344 @@ use windows::{
  * * `parent_hwnd` - ダイアログの親ウィンドウハンドル。ダイアログがモーダルで表示されます。
  *
  * # 処理フロー
- * 1. COMライブラリを初期化します（Shell APIの前提条件）。
+ * 1. COMライブラリを初期化します（Shell APIの前提条件、`browse_for_folder` 内で終了時に解除）。
  * 2. `BROWSEINFOW` 構造体を設定し、`SHBrowseForFolderW` を呼び出してダイアログを表示します。
+ *    現在の保存先フォルダーを初期選択状態にします（`browse_callback_proc`）。
  * 3. ユーザーがフォルダーを選択した場合（キャンセルされなかった場合）:
  *    a. 返されたPIDL（ポインタ）を `SHGetPathFromIDListW` でファイルシステムパスに変換します。
- *    b. 変換したパスを `AppState` とUIのエディットボックスに設定します。
+ *    b. 変換したパスを `AppState` とUIのエディットボックスに設定し、レジストリに保存します（`save_last_folder`）。
  *    c. `CoTaskMemFree` を使用してPIDLが確保したメモリを解放します。
- *
- * # 安全性
- * この関数は `unsafe` ブロックを含みますが、Win32 API呼び出しとポインタ操作は
- * ドキュメントに従って安全に処理され、リソースは適切に解放されます。
  */
 pub fn show_folder_dialog(parent_hwnd: HWND) {
+    // 現在の保存先フォルダーを初期選択にしてダイアログを開く
+    let current_folder = AppState::get_app_state_ref().selected_folder_path.clone();
+    if let Some(path_string) = browse_for_folder(
+        parent_hwnd,
+        "保存先フォルダーを選択してください",
+        current_folder.as_deref(),
+    ) {
+        set_save_folder(parent_hwnd, &path_string);
+    }
+}
+
+/// 保存先フォルダーを `AppState` とUIのエディットボックスに設定し、レジストリに保存する
+///
+/// フォルダー選択ダイアログ・フォルダーのドロップ・最近使ったフォルダーで共通して使用します。
+fn set_save_folder(parent_hwnd: HWND, path_string: &str) {
+    // AppStateとUIを更新
+    let app_state = AppState::get_app_state_mut();
+    app_state.selected_folder_path = Some(path_string.to_string());
+    // 次回起動時も同じフォルダーを使用できるよう保存
+    save_last_folder(path_string);
+    add_recent_folder(path_string);
+
+    unsafe {
+        if let Ok(path_edit) = GetDlgItem(Some(parent_hwnd), IDC_PATH_EDIT) {
+            let path_wide: Vec<u16> = path_string
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect();
+            let _ = SetWindowTextW(path_edit, PCWSTR(path_wide.as_ptr()));
+        }
+    }
+}
+
+/// ダイアログへのファイル・フォルダーのドロップ（`WM_DROPFILES`）を受け付ける
+///
+/// ダイアログ初期化時に1回だけ呼び出します。管理者として実行した場合も
+/// エクスプローラー（通常の権限）からドロップできるよう、ドロップ関連のメッセージを許可します。
+pub fn initialize_folder_drop(hwnd: HWND) {
+    unsafe {
+        for message in [WM_DROPFILES, WM_COPYDATA, WM_COPYGLOBALDATA] {
+            let _ = ChangeWindowMessageFilterEx(hwnd, message, MSGFLT_ALLOW, None);
+        }
+        DragAcceptFiles(hwnd, true);
+    }
+}
+
+/**
+ * ダイアログにドロップされたフォルダーを保存先に設定する（`WM_DROPFILES`）
+ *
+ * ドロップされた項目のうち最初のフォルダーを使用します。フォルダーがない場合は、
+ * 最初のファイルが入っているフォルダーを使用します。使用しなかった項目はログに件数を出力します。
+ * 「参照」ボタンと同じく、通常モード（ボタンが有効な間）のみ受け付け、書き込めないフォルダーは設定しません。
+ *
+ * # 引数
+ * * `parent_hwnd` - メインダイアログのウィンドウハンドル
+ * * `wparam` - `WM_DROPFILES` のWPARAM（ドロップ情報のハンドル `HDROP`、ここで解放する）
+ */
+pub fn handle_dropped_folder(parent_hwnd: HWND, wparam: WPARAM) {
+    let hdrop = HDROP(wparam.0 as *mut _);
+    let dropped_paths = unsafe {
+        let count = DragQueryFileW(hdrop, u32::MAX, None);
+        let paths: Vec<PathBuf> = (0..count)
+            .filter_map(|index| {
+                let len = DragQueryFileW(hdrop, index, None) as usize;
+                let mut buffer = vec![0u16; len + 1];
+                (DragQueryFileW(hdrop, index, Some(&mut buffer)) != 0)
+                    .then(|| PathBuf::from(OsString::from_wide(&buffer[..len])))
+            })
+            .collect();
+        DragFinish(hdrop);
+        paths
+    };
+
+    if !is_browse_button_enabled(parent_hwnd) {
+        app_log(strings::folder_drop_unavailable());
+        return;
+    }
+
+    let folder = dropped_paths
+        .iter()
+        .find(|path| path.is_dir())
+        .cloned()
+        .or_else(|| {
+            dropped_paths
+                .iter()
+                .find(|path| path.is_file())
+                .and_then(|path| path.parent())
+                .map(Path::to_path_buf)
+        });
+    let Some(folder) = folder else {
+        app_log(strings::folder_drop_no_folder());
+        return;
+    };
+    if dropped_paths.len() > 1 {
+        app_log(&strings::folder_drop_ignored(dropped_paths.len() - 1));
+    }
+
+    let folder = folder.to_string_lossy().into_owned();
+    if !is_folder_writable(&folder) {
+        app_log(&strings::folder_drop_not_writable(&folder));
+        return;
+    }
+    set_save_folder(parent_hwnd, &folder);
+    app_log(&strings::folder_dropped(&folder));
+}
+
+/// 「参照」ボタンが有効か（通常モードで保存先を変更できる状態か）を確認する
+fn is_browse_button_enabled(parent_hwnd: HWND) -> bool {
+    unsafe {
+        GetDlgItem(Some(parent_hwnd), IDC_BROWSE_BUTTON)
+            .is_ok_and(|button| IsWindowEnabled(button).as_bool())
+    }
+}
+
+/**
+ * 最近使った保存先フォルダーのメニューを表示し、選択されたフォルダーを保存先に設定する
+ *
+ * 「参照」ボタン上の `WM_CONTEXTMENU`（右クリック・Shift+F10）で呼び出します。
+ * 現在の保存先にはチェックを付け、存在しないフォルダーは選択できない状態で表示します。
+ * 存在しないフォルダーがある場合は、一覧から削除する項目をメニューの最後に追加します。
+ * 「参照」ボタンと同じく、通常モード（ボタンが有効な間）のみ表示します。
+ *
+ * # 引数
+ * * `parent_hwnd` - メインダイアログのウィンドウハンドル
+ * * `lparam` - `WM_CONTEXTMENU` のLPARAM（スクリーン座標、キーボード操作の場合は-1）
+ */
+pub fn show_recent_folders_menu(parent_hwnd: HWND, lparam: LPARAM) {
+    if !is_browse_button_enabled(parent_hwnd) {
+        return;
+    }
+    let folders = load_recent_folders();
+    let current_folder = AppState::get_app_state_ref().selected_folder_path.clone();
+
+    let command = unsafe {
+        let Ok(menu) = CreatePopupMenu() else {
+            return;
+        };
+        if folders.is_empty() {
+            let text = to_wide(strings::recent_folders_empty());
+            let _ = AppendMenuW(menu, MF_STRING | MF_GRAYED, 0, PCWSTR(text.as_ptr()));
+        }
+        for (index, folder) in folders.iter().enumerate() {
+            let mut flags = MF_STRING;
+            if !Path::new(folder).is_dir() {
+                flags |= MF_GRAYED;
+            }
+            if current_folder
+                .as_deref()
+                .is_some_and(|current| same_folder(current, folder))
+            {
+                flags |= MF_CHECKED;
+            }
+            // 「&」はアクセスキーの指定になるため、そのまま表示されるよう「&&」にする
+            let text = to_wide(&folder.replace('&', "&&"));
+            let _ = AppendMenuW(menu, flags, index + 1, PCWSTR(text.as_ptr()));
+        }
+        if folders.iter().any(|folder| !Path::new(folder).is_dir()) {
+            let text = to_wide(strings::recent_folders_prune());
+            let _ = AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null());
+            let _ = AppendMenuW(
+                menu,
+                MF_STRING,
+                PRUNE_RECENT_FOLDERS_COMMAND,
+                PCWSTR(text.as_ptr()),
+            );
+        }
+
+        // キーボード操作（LPARAMが-1）の場合は「参照」ボタンの左下に表示する
+        let (x, y) = if lparam.0 as i32 == -1 {
+            let mut rect = RECT::default();
+            if let Ok(button) = GetDlgItem(Some(parent_hwnd), IDC_BROWSE_BUTTON) {
+                let _ = GetWindowRect(button, &mut rect);
+            }
+            (rect.left, rect.bottom)
+        } else {
+            (
+                (lparam.0 & 0xFFFF) as i16 as i32,
+                ((lparam.0 >> 16) & 0xFFFF) as i16 as i32,
+            )
+        };
+        let command = TrackPopupMenu(
+            menu,
+            TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_LEFTALIGN | TPM_TOPALIGN,
+            x,
+            y,
+            None,
+            parent_hwnd,
+            None,
+        )
+        .0 as usize;
+        let _ = DestroyMenu(menu);
+        command
+    };
+
+    if command == PRUNE_RECENT_FOLDERS_COMMAND {
+        prune_recent_folders(&folders);
+        return;
+    }
+    let Some(folder) = command.checked_sub(1).and_then(|index| folders.get(index)) else {
+        return; // キャンセル
+    };
+    // メニューの表示中に削除・移動された場合は一覧から外す
+    if !Path::new(folder).is_dir() {
+        app_log(&strings::recent_folder_missing(folder));
+        prune_recent_folders(&folders);
+        return;
+    }
+    if !is_folder_writable(folder) {
+        app_log(&strings::folder_drop_not_writable(folder));
+        return;
+    }
+    set_save_folder(parent_hwnd, folder);
+    app_log(&strings::recent_folder_selected(folder));
+}
+
+/// 最近使った保存先フォルダーをレジストリから読み込む（新しい順）
+fn load_recent_folders() -> Vec<String> {
+    load_setting_string(RECENT_FOLDERS_VALUE_NAME)
+        .map(|value| {
+            value
+                .split(RECENT_FOLDERS_SEPARATOR)
+                .filter(|folder| !folder.is_empty())
+                .take(MAX_RECENT_FOLDERS)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 最近使った保存先フォルダーをレジストリに保存する
+fn save_recent_folders(folders: &[String]) {
+    let value = folders.join(&RECENT_FOLDERS_SEPARATOR.to_string());
+    save_setting_string(RECENT_FOLDERS_VALUE_NAME, &value);
+}
+
+/// 最近使った保存先フォルダーの先頭に追加する（同じフォルダーは先頭に移動し、最大件数を超えた分は削除）
+fn add_recent_folder(folder: &str) {
+    let mut folders = load_recent_folders();
+    folders.retain(|recent| !same_folder(recent, folder));
+    folders.insert(0, folder.to_string());
+    folders.truncate(MAX_RECENT_FOLDERS);
+    save_recent_folders(&folders);
+}
+
+/// 最近使った保存先フォルダーから、存在しないフォルダーを削除する
+fn prune_recent_folders(folders: &[String]) {
+    let existing: Vec<String> = folders
+        .iter()
+        .filter(|folder| Path::new(folder).is_dir())
+        .cloned()
+        .collect();
+    save_recent_folders(&existing);
+    app_log(&strings::recent_folders_pruned(
+        folders.len() - existing.len(),
+    ));
+}
+
+/// 2つのパスが同じフォルダーを指すかを比較する（大文字・小文字と末尾の区切り文字を区別しない）
+fn same_folder(a: &str, b: &str) -> bool {
+    a.trim_end_matches(['\\', '/'])
+        .eq_ignore_ascii_case(b.trim_end_matches(['\\', '/']))
+}
+
+/// 文字列をNUL終端のUTF-16に変換する（Win32 APIに渡す用）
+fn to_wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/**
+ * フォルダー選択ダイアログを表示し、選択されたパスを返す
+ *
+ * `show_folder_dialog`（保存先）とPDF変換時の変換元/出力先選択で共通して使用します。
+ * `AppState` やUIは更新せず、選択結果のみを返します。
+ *
+ * # 引数
+ * * `parent_hwnd` - ダイアログの親ウィンドウハンドル。
+ * * `title` - ダイアログ上部に表示する説明文。
+ * * `initial_dir` - ダイアログを開いたときに選択状態にするフォルダー（`None` の場合はデスクトップ）。
+ *
+ * # 戻り値
+ * * `Some(String)` - ユーザーが選択したフォルダーのパス。
+ * * `None` - キャンセルされた場合、またはパス変換に失敗した場合。
+ */
+pub fn browse_for_folder(
+    parent_hwnd: HWND,
+    title: &str,
+    initial_dir: Option<&str>,
+) -> Option<String> {
     unsafe {
         // COM環境を初期化（Shell APIの前提条件）
-        let _ = CoInitialize(None);
+        // S_OK / S_FALSE（初期化済み）のどちらも参照カウントが増えるため、終了時にCoUninitializeで対にする。
+        // RPC_E_CHANGED_MODE（既にMTAで初期化済み）の場合はカウントが増えないため解除しない。
+        let com_result = CoInitialize(None);
+        let com_initialized = com_result.is_ok();
+        if com_result == RPC_E_CHANGED_MODE {
+            app_log(
+                "⚠️ COMがMTAで初期化済みのため、従来形式のフォルダー選択ダイアログを使用します",
+            );
+        } else if !com_initialized {
+            app_log(&format!("⚠️ COMの初期化に失敗しました: {}", com_result));
+        }
 
         // BROWSEINFOW構造体の設定 - フォルダー選択ダイアログのパラメータ
-        let title_wide: Vec<u16> = "保存先フォルダーを選択してください"
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
+        let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+        // 初期選択フォルダー（コールバックにポインタを渡すため、ダイアログを閉じるまで保持する）
+        let initial_dir_wide: Option<Vec<u16>> = initial_dir
+            .filter(|dir| Path::new(dir).is_dir())
+            .map(|dir| dir.encode_utf16().chain(std::iter::once(0)).collect());
         let mut browse_info = BROWSEINFOW {
             hwndOwner: parent_hwnd,
             pidlRoot: ptr::null_mut(), // ルートはデスクトップ
             pszDisplayName: windows::core::PWSTR::null(), // 選択されたフォルダ名を受け取るバッファ（今回は不要）
             lpszTitle: PCWSTR(title_wide.as_ptr()),
-            ulFlags: 0x00000040, // BIF_NEWDIALOGSTYLE: モダンなUIのダイアログを使用
-            lpfn: None,          // コールバック関数は使用しない
-            lParam: LPARAM(0),
+            // BIF_NEWDIALOGSTYLE: モダンなUIのダイアログを使用（STAでのCOM初期化が必要）
+            ulFlags: if com_initialized { 0x00000040 } else { 0 },
+            lpfn: Some(browse_callback_proc), // 初期化時に初期選択フォルダーを設定
+            lParam: LPARAM(
+                initial_dir_wide
+                    .as_ref()
+                    .map_or(0, |dir| dir.as_ptr() as isize),
+            ),
             iImage: 0,
         };
 
         // フォルダー選択ダイアログを表示し、ユーザーの選択を待つ
         let pidl = SHBrowseForFolderW(&mut browse_info);
 
+        let mut selected_path = None;
+
         // pidl有効性チェック - ユーザーがフォルダーを選択した場合のみ処理継続
         if !pidl.is_null() {
             // MAX_PATH サイズの Unicode文字列バッファ準備
@@ -108,25 +467,54 @@ pub fn show_folder_dialog(parent_hwnd: HWND) {
                 // UTF-16からRust文字列への変換処理
                 let len = path.iter().position(|&c| c == 0).unwrap_or(path.len());
                 let path_os_string = OsString::from_wide(&path[..len]);
-                let path_string = path_os_string.to_string_lossy().to_string();
-
-                // AppStateとUIを更新
-                let app_state = AppState::get_app_state_mut();
-                app_state.selected_folder_path = Some(path_string.clone());
-
-                if let Ok(path_edit) = GetDlgItem(Some(parent_hwnd), 1002) {
-                    let _ = SetWindowTextW(path_edit, PCWSTR(path.as_ptr()));
-                }
+                selected_path = Some(path_os_string.to_string_lossy().to_string());
             }
 
             // Shell APIが確保したメモリを解放
             CoTaskMemFree(Some(pidl as *const _ as *const _));
         }
 
-        // CoInitializeに対するCoUninitializeは、このスレッドが終了する際に自動的に行われる思想だが、明示的に呼ぶのがより安全。今回は省略。
+        // CoInitializeが成功した場合のみ、対になるCoUninitializeで参照カウントを戻す
+        if com_initialized {
+            CoUninitialize();
+        }
+        selected_path
     }
 }
 
+/// フォルダー選択ダイアログのコールバック
+///
+/// ダイアログの初期化完了（`BFFM_INITIALIZED`）時に、`lpdata` で渡されたフォルダーを
+/// `BFFM_SETSELECTIONW` で選択状態にします（`lpdata` が0の場合は何もしない）。
+unsafe extern "system" fn browse_callback_proc(
+    hwnd: HWND,
+    message: u32,
+    _lparam: LPARAM,
+    lpdata: LPARAM,
+) -> i32 {
+    if message == BFFM_INITIALIZED && lpdata.0 != 0 {
+        unsafe {
+            // WPARAM=TRUE：lParamはPIDLではなくパス文字列
+            SendMessageW(hwnd, BFFM_SETSELECTIONW, Some(WPARAM(1)), Some(lpdata));
+        }
+    }
+    0
+}
+
+/// 前回選択した保存先フォルダーをレジストリから読み込む
+///
+/// # 戻り値
+/// 保存されていて、かつ現在も存在するフォルダーのパス。それ以外は `None`。
+pub fn load_last_folder() -> Option<String> {
+    let folder = load_setting_string(LAST_FOLDER_VALUE_NAME)?;
+    Path::new(&folder).is_dir().then_some(folder)
+}
+
+/// 選択した保存先フォルダーをレジストリに保存する（次回起動時の既定値・ダイアログの初期選択）
+pub fn save_last_folder(folder: &str) {
+    save_setting_string(LAST_FOLDER_VALUE_NAME, folder);
+}
+
 /**
  * 保存先フォルダーを決定する関数
  *
@@ -147,80 +535,52 @@ pub fn show_folder_dialog(parent_hwnd: HWND) {
 pub fn get_pictures_folder() -> String {
     let folder_candidates = get_folder_candidates();
 
-    for folder_path in folder_candidates {
-        if is_folder_writable(&folder_path) {
-            app_log(&format!("選択されたフォルダー: {}", folder_path));
-            return format!("{}\\clickcapture", folder_path); // 最初に権限があるフォルダーで確定
+    let chosen = choose_save_folder(folder_candidates, |folder_path| {
+        let writable = is_folder_writable(folder_path);
+        if writable {
+            app_log(&format!("選択されたフォルダー: {}", folder_path)); // 最初に権限があるフォルダーで確定
         } else {
             app_log(&format!("書き込み権限なし: {}", folder_path));
         }
-    }
+        writable
+    });
 
     // 全ての候補で書き込みに失敗した場合の最終フォールバック
-    let fallback = "C:\\".to_string();
-    app_log(&format!("フォールバック使用: {}", fallback));
-    fallback
+    chosen.unwrap_or_else(|| {
+        app_log(&format!("フォールバック使用: {}", FALLBACK_SAVE_FOLDER));
+        FALLBACK_SAVE_FOLDER.to_string()
+    })
 }
 
-/**
- * フォルダー候補を優先順位順で取得する内部関数
- *
- * 【機能説明】
- * Windowsユーザー環境における一般的なフォルダー使用パターンに基づいて、
- * スクリーンショット保存に適したフォルダー候補を優先順位付きで生成します。
- *
- * 【優先順位戦略の根拠】
- * 1. OneDrive統合: クラウド同期による自動バックアップ
- * 2. ローカル画像フォルダー: 最も直感的なスクリーンショット保存場所
- * 3. ドキュメント: 作業文書との関連性
- * 4. デスクトップ: 一時的なアクセスの容易さ
- * 5. 共通フォルダー: マルチユーザー環境での利用可能性
- * 6. システムルート: 最終フォールバック
- *
- * 【国際化対応】
- * 日本語版Windows（"画像"フォルダー）と英語版Windows（"Pictures"フォルダー）の
- * 両方に対応し、言語設定に関係なく適切なフォルダーを検出できます。
- *
- * 【戻り値】
- * Vec<String>: 優先順位順に並んだフォルダーパス候補のリスト
- *
- * 【エラーハンドリング】
- * USERPROFILE環境変数が取得できない場合でも、システム共通フォルダーと
- * フォールバックにより継続動作を保証します。
- *
- * 【AIおよび第三者解析用の技術ノート】
- * この関数は Windows環境の多様性（OneDrive有無、言語設定、ユーザー権限）
- * を考慮した包括的なアプローチを採用しています。環境変数への依存度を
- * 最小化し、フォールバック戦略により堅牢性を確保しています。
- */
+/// フォルダー候補を優先順位順で取得する内部関数
+///
+/// Known Folderのピクチャフォルダーと `USERPROFILE` を取得し、優先順位の決定は
+/// `clickcapture::save_folder::folder_candidates` に任せます（優先順位の根拠は同関数を参照）。
+/// Known Folderの実パスを最優先するため、言語設定やフォルダーの移動に関係なく適切なフォルダーを検出できます。
 fn get_folder_candidates() -> Vec<String> {
-    let mut candidates = Vec::new();
-
-    // USERPROFILE環境変数からユーザーホームディレクトリを取得
-    if let Ok(user_profile) = std::env::var("USERPROFILE") {
-        // 【優先順位1】OneDriveの画像フォルダー - クラウド同期による保護
-        candidates.push(format!("{}\\OneDrive\\画像", user_profile)); // 日本語版Windows
-        candidates.push(format!("{}\\OneDrive\\Pictures", user_profile)); // 英語版Windows
-
-        // 【優先順位2】ローカルの画像フォルダー - 標準的なスクリーンショット保存場所
-        candidates.push(format!("{}\\Pictures", user_profile)); // 英語版Windows
-        candidates.push(format!("{}\\画像", user_profile)); // 日本語版Windows
-
-        // 【優先順位3】ドキュメントフォルダー - 作業関連ファイルとの整理
-        candidates.push(format!("{}\\Documents", user_profile));
+    let known_pictures = get_known_pictures_folder();
+    let user_profile = std::env::var("USERPROFILE").ok();
+    folder_candidates(known_pictures.as_deref(), user_profile.as_deref())
+}
 
-        // 【優先順位4】デスクトップ - 即座のアクセス性重視
-        candidates.push(format!("{}\\Desktop", user_profile));
+/// Known Folder API（`FOLDERID_Pictures`）から現在のピクチャフォルダーの実パスを取得する
+///
+/// # 戻り値
+/// 取得できた場合はフォルダーパス、APIが失敗した場合は `None`（手組みの候補にフォールバック）。
+fn get_known_pictures_folder() -> Option<String> {
+    unsafe {
+        let path_ptr = match SHGetKnownFolderPath(&FOLDERID_Pictures, KF_FLAG_DEFAULT, None) {
+            Ok(path_ptr) => path_ptr,
+            Err(e) => {
+                app_log(&format!("⚠️ ピクチャフォルダーの取得に失敗: {}", e));
+                return None;
+            }
+        };
+        // 返されたバッファは呼び出し側でCoTaskMemFreeにより解放する
+        let path = path_ptr.to_string().ok();
+        CoTaskMemFree(Some(path_ptr.0 as *const _));
+        path.filter(|path| !path.is_empty())
     }
-
-    // 【優先順位5】システム共通フォルダー - マルチユーザー環境対応
-    candidates.push("C:\\Users\\Public\\Pictures".to_string());
-    candidates.push("C:\\Users\\Public\\Documents".to_string());
-
-    // 【優先順位6】システムルートフォールバック - 確実な書き込み可能性
-    candidates.push("C:\\".to_string());
-
-    candidates
 }
 
 /**
@@ -0,0 +1,88 @@
+/*
+============================================================================
+GIF変換ボタンハンドラモジュール (gif_export_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+「GIFに変換」ボタンのクリックと、ワーカースレッドからの完了通知を処理するモジュール。
+変換処理はワーカースレッドで実行されるため、PDF変換と異なりダイアログは応答し続け、
+進捗はログ表示エリアに表示されます。
+
+【主要機能】
+1.  **ボタンクリック処理**: `handle_gif_export_button`
+    -   変換元フォルダ・出力ファイル名を確認ダイアログで提示
+    -   `is_exporting_to_gif` を設定してUIを無効化し、`start_gif_export` でワーカースレッドを起動
+2.  **完了通知処理**: `handle_gif_export_complete`
+    -   `WM_GIF_EXPORT_COMPLETE` 受信時にフラグを解除してUIを再度有効化し、結果を通知
+
+【AI解析用：依存関係】
+-   `export_gif.rs`: `start_gif_export`（ワーカースレッドの起動）
+-   `input_control_handlers.rs`: `update_input_control_states`（変換中のUI無効化）
+-   `sound.rs`: 完了チャイム
+-   メインダイアログ: BN_CLICKED通知と `WM_GIF_EXPORT_COMPLETE` の受信
+ */
+
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::{
+    app_state::AppState,
+    export_gif::{get_gif_source_dir, start_gif_export},
+    sound::{SoundEffect, play_sound},
+    system_utils::{app_log, show_message_box},
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// GIF変換ボタンのクリックイベントを処理する
+///
+/// 確認ダイアログで同意が得られた場合、変換中フラグを設定してUIを無効化し、
+/// ワーカースレッドでGIF変換を開始します。完了は `handle_gif_export_complete` で処理します。
+pub fn handle_gif_export_button() -> isize {
+    let source_dir = get_gif_source_dir().unwrap_or_else(|| "(未選択)".to_string());
+    let message = format!(
+        "フォルダー内の画像をアニメーションGIFに変換してもよろしいでしょうか？\n\n\
+        変換元: {}\n出力先: animation.gif（変換元フォルダー内）",
+        source_dir
+    );
+    let result = show_message_box(&message, "GIF変換確認", MB_OKCANCEL | MB_ICONQUESTION);
+
+    if result.0 != IDOK.0 {
+        app_log("GIF変換がキャンセルされました。");
+        return 1;
+    }
+
+    app_log("GIF変換を開始します...");
+    let app_state = AppState::get_app_state_mut();
+    app_state.is_exporting_to_gif = true;
+    update_input_control_states();
+
+    if !start_gif_export() {
+        app_state.is_exporting_to_gif = false;
+        update_input_control_states();
+    }
+    1
+}
+
+/// GIF変換の完了通知（`WM_GIF_EXPORT_COMPLETE`）を処理する
+///
+/// # 引数
+/// * `success` - 変換に成功した場合は `true`（詳細はワーカースレッドがログに出力済み）
+pub fn handle_gif_export_complete(success: bool) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.is_exporting_to_gif = false;
+    update_input_control_states();
+
+    if success {
+        play_sound(SoundEffect::Complete);
+        show_message_box(
+            "GIF変換が正常に完了しました。",
+            "GIF変換完了",
+            MB_OK | MB_ICONINFORMATION,
+        );
+    } else {
+        show_message_box(
+            "GIF変換中にエラーが発生しました。\n詳細はログを確認してください。",
+            "GIF変換エラー",
+            MB_OK | MB_ICONERROR,
+        );
+    }
+}
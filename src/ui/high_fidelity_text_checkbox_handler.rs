@@ -0,0 +1,84 @@
+/*
+============================================================================
+高精細テキストモードチェックボックスハンドラモジュール (high_fidelity_text_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+JPEG保存時の色差サンプリングを切り替える「高精細テキストモード」チェックボックスを
+管理するモジュール。品質70〜80%の標準設定（4:2:0）では赤や青の細い文字が滲むため、
+有効時は4:4:4（色差間引きなし）かつプログレッシブ形式で保存します。
+また、標準の量子化テーブルでは品質を95から100に上げても文字のリンギングがほとんど減らないため、
+有効時は輝度にフラットな量子化テーブルを使い、品質を上げた分だけ文字が鮮明になるようにします。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_high_fidelity_text_checkbox`
+    -   `AppState.jpeg_high_fidelity_text` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_high_fidelity_text_checkbox_change`
+    -   チェック状態を `AppState.jpeg_high_fidelity_text` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **エンコード**: `screen_capture.rs`（有効時は `jpeg-encoder` クレートの `SamplingFactor::R_4_4_4`、
+    輝度は `QuantizationTableType::Flat`）
+-   **ファイルサイズ**: 標準設定より大きくなる（同じ品質で概ね1.5〜2倍）
+-   **PDF変換**: JPEGをそのまま埋め込むため、本設定の影響を受けない
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `jpeg_high_fidelity_text` フィールドの保持
+-   `constants.rs`: `IDC_HIGH_FIDELITY_TEXT_CHECKBOX` コントロールID定義
+-   `screen_capture.rs`: JPEGエンコーダーの切り替え
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// 高精細テキストモードチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_high_fidelity_text_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().jpeg_high_fidelity_text;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_HIGH_FIDELITY_TEXT_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 高精細テキストモードチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.jpeg_high_fidelity_text` に保存します。
+/// 変更は次回のキャプチャから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_high_fidelity_text_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_HIGH_FIDELITY_TEXT_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.jpeg_high_fidelity_text = is_checked;
+
+    println!(
+        "高精細テキストモード設定変更: {}",
+        if is_checked {
+            "有効（4:4:4・フラット量子化・プログレッシブ）"
+        } else {
+            "無効（4:2:0）"
+        }
+    );
+}
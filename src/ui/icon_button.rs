@@ -2,14 +2,24 @@
 ============================================================================
 アイコンボタン描画機能群
 ============================================================================
+
+オーナードローボタンの描画と状態表示（無効・押下・ホバー・キーボードフォーカス）。
+ホバー状態はボタンをサブクラス化（SetWindowSubclass）し、WM_MOUSEMOVE / WM_MOUSELEAVE で追跡する。
+アイコンは初回描画時にリソースから読み込んで `AppState.icon_cache` に保持し、AppState破棄時に一度だけ解放する。
+描画はメモリDC（ダブルバッファ）で行い、完成した画像を一度に転送して背景塗りつぶしのちらつきを防ぐ。
  */
 
 // 必要なライブラリ（外部機能）をインポート
+use std::collections::HashMap;
+
 use windows::{
     Win32::{
-        Foundation::{COLORREF, HINSTANCE, HWND, LPARAM, RECT, WPARAM}, Graphics::Gdi::*, System:: 
+        Foundation::{COLORREF, HINSTANCE, HWND, LPARAM, LRESULT, RECT, WPARAM}, Graphics::Gdi::*, System:: 
             LibraryLoader::GetModuleHandleW, UI::{
-            Controls::DRAWITEMSTRUCT, WindowsAndMessaging::*, // メモリストリーム作成
+            Controls::{DRAWITEMSTRUCT, ODS_DISABLED, ODS_FOCUS, ODS_NOFOCUSRECT, ODS_SELECTED, WM_MOUSELEAVE},
+            Input::KeyboardAndMouse::{TME_LEAVE, TRACKMOUSEEVENT, TrackMouseEvent},
+            Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass},
+            WindowsAndMessaging::*, // メモリストリーム作成
         } // リソースタイプ定義
     },
     core::PCWSTR, // Windows API用の文字列操作
@@ -21,6 +31,54 @@ use crate::app_state::*;
 // 定数群インポート
 use crate::constants::*;
 
+// 配色テーマ（ライト / ダーク）
+use crate::theme::button_palette;
+
+// 無効状態のアイコンの不透明度（0-255、約40%）
+const DISABLED_ICON_ALPHA: u8 = 102;
+
+// ホバー追跡用サブクラスの識別子
+const ICON_BUTTON_SUBCLASS_ID: usize = 1;
+
+// ボタンに描画するアイコンのサイズ（ピクセル）
+const ICON_SIZE: i32 = 32;
+
+/// アイコンボタン用のアイコンキャッシュ
+///
+/// リソースIDごとに読み込んだHICONを保持し、WM_DRAWITEMのたびにLoadImage / DestroyIconを繰り返さないようにする。
+/// 保持しているアイコンはAppState破棄時（Drop）にまとめて解放する。
+#[derive(Debug, Default)]
+pub struct IconCache {
+    icons: HashMap<i32, HICON>,
+}
+
+impl IconCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// キャッシュ済みのアイコンを取得する（未読み込みの場合はリソースから読み込んで保持）
+    pub fn get_or_load(&mut self, resource_id: i32) -> Option<HICON> {
+        if let Some(&hicon) = self.icons.get(&resource_id) {
+            return Some(hicon);
+        }
+        let hicon = load_icon_from_resource(resource_id)?;
+        self.icons.insert(resource_id, hicon);
+        Some(hicon)
+    }
+}
+
+impl Drop for IconCache {
+    fn drop(&mut self) {
+        // 保持している全アイコンを解放（USERハンドルリーク防止）
+        for (_, hicon) in self.icons.drain() {
+            unsafe {
+                let _ = DestroyIcon(hicon);
+            }
+        }
+    }
+}
+
 
 // アイコンボタン描画制御ハンドラ
 pub fn draw_icon_button_handler(_hwnd: HWND, _wparam: WPARAM, lparam: LPARAM) {
@@ -37,12 +95,12 @@ pub fn draw_icon_button_handler(_hwnd: HWND, _wparam: WPARAM, lparam: LPARAM) {
         match draw_struct.CtlID {
             id if id == IDC_CAPTURE_START_BUTTON as u32 => {
                 // キャプチャ開始ボタンの描画
-                let is_capture_mode = app_state.is_capture_mode;
+                let is_capture_mode = app_state.is_capture_mode();
                 draw_icon_button(draw_struct, is_capture_mode, IDI_CAMERA_ON, IDI_CAMERA_OFF);
             }
             id if id == IDC_AREA_SELECT_BUTTON as u32 => {
                 // エリア選択ボタンの描画
-                let is_area_select_mode = app_state.is_area_select_mode;
+                let is_area_select_mode = app_state.is_area_select_mode();
                 draw_icon_button(
                     draw_struct,
                     is_area_select_mode,
@@ -68,6 +126,15 @@ pub fn draw_icon_button_handler(_hwnd: HWND, _wparam: WPARAM, lparam: LPARAM) {
 }
 
 // アイコンボタンを描画する共通関数
+//
+// 状態の描画（DRAWITEMSTRUCT.itemState とホバー状態から判定）
+// - 無効（ODS_DISABLED）：平坦な背景・淡い境界線で、アイコンを約40%の不透明度で描画
+// - 押下中（ODS_SELECTED）：押下時の背景と内側の影（くぼんだ境界線）、アイコンを1px右下にずらす
+// - ホバー中：ホバー時の背景で控えめに強調
+// - `is_active`（モード実行中）：押下時の背景とON状態のアイコン
+//
+// ちらつき防止のため、ボタンと同じ大きさのメモリDCに描画してからBitBltで一度に転送する。
+// メモリDCを作成できない場合はボタンのDCに直接描画する。
 pub fn draw_icon_button(
     draw_struct: &DRAWITEMSTRUCT,
     is_active: bool,
@@ -75,46 +142,153 @@ pub fn draw_icon_button(
     inactive_icon_id: i32,
 ) {
     unsafe {
-        let hdc = draw_struct.hDC;
-        let rect = draw_struct.rcItem;
+        let target_dc = draw_struct.hDC;
+        let target_rect = draw_struct.rcItem;
+        let width = target_rect.right - target_rect.left;
+        let height = target_rect.bottom - target_rect.top;
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        let mem_dc = CreateCompatibleDC(Some(target_dc));
+        let bitmap = CreateCompatibleBitmap(target_dc, width, height);
+        if mem_dc.is_invalid() || bitmap.is_invalid() {
+            if !bitmap.is_invalid() {
+                let _ = DeleteObject(bitmap.into());
+            }
+            if !mem_dc.is_invalid() {
+                let _ = DeleteDC(mem_dc);
+            }
+            paint_icon_button(
+                target_dc,
+                target_rect,
+                draw_struct,
+                is_active,
+                active_icon_id,
+                inactive_icon_id,
+            );
+            return;
+        }
+        let old_bitmap = SelectObject(mem_dc, bitmap.into());
+
+        let local_rect = RECT {
+            left: 0,
+            top: 0,
+            right: width,
+            bottom: height,
+        };
+        paint_icon_button(
+            mem_dc,
+            local_rect,
+            draw_struct,
+            is_active,
+            active_icon_id,
+            inactive_icon_id,
+        );
+        let _ = BitBlt(
+            target_dc,
+            target_rect.left,
+            target_rect.top,
+            width,
+            height,
+            Some(mem_dc),
+            0,
+            0,
+            SRCCOPY,
+        );
+
+        SelectObject(mem_dc, old_bitmap);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+    }
+}
+
+// アイコンボタンの背景・アイコン・境界線・フォーカス枠を指定のDCに描画する
+fn paint_icon_button(
+    hdc: HDC,
+    rect: RECT,
+    draw_struct: &DRAWITEMSTRUCT,
+    is_active: bool,
+    active_icon_id: i32,
+    inactive_icon_id: i32,
+) {
+    unsafe {
+        let item_state = draw_struct.itemState.0;
+        let is_disabled = item_state & ODS_DISABLED.0 != 0;
+        let is_pressed = item_state & ODS_SELECTED.0 != 0;
+        let is_hovered = !is_disabled && is_icon_button_hovered(draw_struct.CtlID as i32);
 
-        // 1. ボタン背景を描画
-        let bg_color = if is_active {
-            COLORREF(0xE0E0E0) // 押下状態
+        // 1. ボタン背景を描画（配色は現在のテーマに従う）
+        let palette = button_palette();
+        let bg_color = if is_disabled {
+            palette.disabled_background // 無効状態
+        } else if is_active || is_pressed {
+            palette.pressed_background // 押下状態
+        } else if is_hovered {
+            palette.hover_background // ホバー状態
         } else {
-            COLORREF(0xF0F0F0) // 通常状態
+            palette.background // 通常状態
         };
 
         let bg_brush = CreateSolidBrush(bg_color);
         FillRect(hdc, &rect, bg_brush);
         let _ = DeleteObject(bg_brush.into());
 
-        // 2. アイコンを直接描画（変換処理不要）
+        // 2. アイコンを描画
         let icon_id = if is_active {
             active_icon_id
         } else {
             inactive_icon_id
         };
 
-        if let Some(hicon) = load_icon_from_resource(icon_id) {
-            let icon_size = 32;
-            let x = rect.left + (rect.right - rect.left - icon_size) / 2;
-            let y = rect.top + (rect.bottom - rect.top - icon_size) / 2;
+        // アイコンはキャッシュから取得（破棄はAppState破棄時のIconCacheに任せる）
+        if let Some(hicon) = AppState::get_app_state_mut().icon_cache.get_or_load(icon_id) {
+            let icon_size = ICON_SIZE;
+            let press_offset = if is_pressed { 1 } else { 0 };
+            let x = rect.left + (rect.right - rect.left - icon_size) / 2 + press_offset;
+            let y = rect.top + (rect.bottom - rect.top - icon_size) / 2 + press_offset;
 
-            // アイコンを直接描画（これだけ！）
-            let _ = DrawIconEx(hdc, x, y, hicon, icon_size, icon_size, 0, None, DI_NORMAL);
-
-            // アイコンリソースを解放
-            let _ = DestroyIcon(hicon);
+            if is_disabled {
+                // 無効状態は背景に半透明で重ねる
+                draw_icon_translucent(hdc, x, y, hicon, icon_size, bg_color, DISABLED_ICON_ALPHA);
+            } else {
+                let _ = DrawIconEx(hdc, x, y, hicon, icon_size, icon_size, 0, None, DI_NORMAL);
+            }
         }
 
         // 3. 境界線を描画
-        draw_button_border(hdc, &rect);
+        if is_disabled {
+            draw_frame(hdc, &rect, palette.disabled_border);
+        } else {
+            draw_button_border(hdc, &rect);
+            if is_pressed {
+                // 押下中は境界線の内側の左上に影を付け、くぼんで見せる
+                let pen = CreatePen(PS_SOLID, 1, palette.pressed_shadow);
+                let old_pen = SelectObject(hdc, pen.into());
+                let _ = MoveToEx(hdc, rect.left + 1, rect.bottom - 2, None);
+                let _ = LineTo(hdc, rect.left + 1, rect.top + 1);
+                let _ = LineTo(hdc, rect.right - 1, rect.top + 1);
+                SelectObject(hdc, old_pen);
+                let _ = DeleteObject(pen.into());
+            }
+        }
+
+        // 4. キーボードフォーカスがある場合はフォーカス枠を描画（Tabキーでの移動先を示す）
+        //    ODS_NOFOCUSRECT：マウス操作中などキーボードキューが非表示の場合は描画しない
+        if item_state & ODS_FOCUS.0 != 0 && item_state & ODS_NOFOCUSRECT.0 == 0 {
+            let focus_rect = RECT {
+                left: rect.left + 3,
+                top: rect.top + 3,
+                right: rect.right - 3,
+                bottom: rect.bottom - 3,
+            };
+            let _ = DrawFocusRect(hdc, &focus_rect);
+        }
     }
 }
 
-// リソースからビットマップをHBITMAPとして読み込む関数
-// アイコンリソースからHBITMAPとして読み込む関数
+// アイコンリソースからHICONとして読み込む関数
+// 返したHICONは呼び出し側でDestroyIconする（ボタン描画では `IconCache` 経由で使用する）
 pub fn load_icon_from_resource(resource_id: i32) -> Option<HICON> {
     unsafe {
         let hmodule = GetModuleHandleW(None).ok()?;
@@ -123,8 +297,8 @@ pub fn load_icon_from_resource(resource_id: i32) -> Option<HICON> {
             Some(HINSTANCE(hmodule.0)),
             PCWSTR(resource_id as usize as *const u16),
             IMAGE_ICON, // アイコンとして直接読み込み
-            32,
-            32,
+            ICON_SIZE,
+            ICON_SIZE,
             LR_DEFAULTCOLOR,
         )
         .ok()
@@ -132,10 +306,15 @@ pub fn load_icon_from_resource(resource_id: i32) -> Option<HICON> {
     }
 }
 
-// 境界線描画（共通処理）
+// 境界線描画（共通処理、配色は現在のテーマに従う）
 pub fn draw_button_border(hdc: HDC, rect: &RECT) {
+    draw_frame(hdc, rect, button_palette().border);
+}
+
+// 指定色で矩形の枠線を描画する
+fn draw_frame(hdc: HDC, rect: &RECT, color: COLORREF) {
     unsafe {
-        let pen = CreatePen(PS_SOLID, 1, COLORREF(0xacacac));
+        let pen = CreatePen(PS_SOLID, 1, color);
         let old_pen = SelectObject(hdc, pen.into());
         let old_brush = SelectObject(hdc, GetStockObject(NULL_BRUSH));
 
@@ -145,4 +324,123 @@ pub fn draw_button_border(hdc: HDC, rect: &RECT) {
         SelectObject(hdc, old_brush);
         let _ = DeleteObject(pen.into());
     }
-}
\ No newline at end of file
+}
+// アイコンを指定の不透明度で描画する（無効状態の表示用）
+//
+// 背景色で塗りつぶしたメモリDCにアイコンを描き、AlphaBlendの定数アルファで重ねる。
+// 描画先も同じ背景色で塗られているため、結果は「背景 + 不透明度 alpha のアイコン」になる。
+fn draw_icon_translucent(
+    hdc: HDC,
+    x: i32,
+    y: i32,
+    hicon: HICON,
+    icon_size: i32,
+    bg_color: COLORREF,
+    alpha: u8,
+) {
+    unsafe {
+        let mem_dc = CreateCompatibleDC(Some(hdc));
+        let bitmap = CreateCompatibleBitmap(hdc, icon_size, icon_size);
+        let old_bitmap = SelectObject(mem_dc, bitmap.into());
+
+        let bg_brush = CreateSolidBrush(bg_color);
+        let icon_rect = RECT {
+            left: 0,
+            top: 0,
+            right: icon_size,
+            bottom: icon_size,
+        };
+        FillRect(mem_dc, &icon_rect, bg_brush);
+        let _ = DeleteObject(bg_brush.into());
+        let _ = DrawIconEx(
+            mem_dc, 0, 0, hicon, icon_size, icon_size, 0, None, DI_NORMAL,
+        );
+
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER as u8,
+            BlendFlags: 0,
+            SourceConstantAlpha: alpha,
+            AlphaFormat: 0, // 定数アルファのみ（ピクセルごとのアルファは使用しない）
+        };
+        let _ = AlphaBlend(
+            hdc, x, y, icon_size, icon_size, mem_dc, 0, 0, icon_size, icon_size, blend,
+        );
+
+        SelectObject(mem_dc, old_bitmap);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+    }
+}
+
+// アイコンボタンをサブクラス化し、マウスの出入り（ホバー状態）を追跡する
+pub fn subclass_icon_button(button: HWND) {
+    unsafe {
+        let _ = SetWindowSubclass(
+            button,
+            Some(icon_button_subclass_proc),
+            ICON_BUTTON_SUBCLASS_ID,
+            0,
+        );
+    }
+}
+
+// 指定したボタンにマウスカーソルが乗っているか
+fn is_icon_button_hovered(control_id: i32) -> bool {
+    AppState::try_get_app_state_ref()
+        .is_some_and(|app_state| app_state.hovered_icon_button == Some(control_id))
+}
+
+// アイコンボタンのサブクラスプロシージャ（ホバー状態の追跡）
+//
+// WM_MOUSEMOVE：初回のみTrackMouseEventでWM_MOUSELEAVEを要求し、ホバー状態にして再描画
+// WM_MOUSELEAVE：ホバー状態を解除して再描画
+// WM_ERASEBKGND：背景消去を省略（WM_DRAWITEMがボタン全体を描画するため、消去によるちらつきを防ぐ）
+// WM_NCDESTROY：サブクラスを解除
+unsafe extern "system" fn icon_button_subclass_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _subclass_id: usize,
+    _ref_data: usize,
+) -> LRESULT {
+    unsafe {
+        match message {
+            WM_ERASEBKGND => {
+                return LRESULT(1);
+            }
+            WM_MOUSEMOVE => {
+                let control_id = GetDlgCtrlID(hwnd);
+                if AppState::try_get_app_state_ref().is_some()
+                    && AppState::get_app_state_ref().hovered_icon_button != Some(control_id)
+                {
+                    AppState::get_app_state_mut().hovered_icon_button = Some(control_id);
+                    let mut track = TRACKMOUSEEVENT {
+                        cbSize: std::mem::size_of::<TRACKMOUSEEVENT>() as u32,
+                        dwFlags: TME_LEAVE,
+                        hwndTrack: hwnd,
+                        dwHoverTime: 0,
+                    };
+                    let _ = TrackMouseEvent(&mut track);
+                    let _ = InvalidateRect(Some(hwnd), None, false);
+                }
+            }
+            WM_MOUSELEAVE if AppState::try_get_app_state_ref().is_some() => {
+                let app_state = AppState::get_app_state_mut();
+                if app_state.hovered_icon_button == Some(GetDlgCtrlID(hwnd)) {
+                    app_state.hovered_icon_button = None;
+                }
+                let _ = InvalidateRect(Some(hwnd), None, false);
+            }
+            WM_NCDESTROY => {
+                let _ = RemoveWindowSubclass(
+                    hwnd,
+                    Some(icon_button_subclass_proc),
+                    ICON_BUTTON_SUBCLASS_ID,
+                );
+            }
+            _ => {}
+        }
+        DefSubclassProc(hwnd, message, wparam, lparam)
+    }
+}
@@ -18,7 +18,7 @@ UIコントロールイベントハンドラ (input_control_handlers.rs)
 3.  **自動クリック関連のUI処理**:
     -   自動クリックの有効/無効チェックボックス、間隔、回数の設定を `AppState` に同期させます。
 4.  **`update_input_control_states`**:
-    -   `AppState` から現在のモードフラグ（`is_area_select_mode`, `is_capture_mode`など）を読み取ります。
+    -   `AppState` の現在のモード（`AppMode`）と変換中フラグ（`is_exporting_to_gif` など）を読み取ります。
     -   モードに応じて、各UIコントロールが有効であるべきか無効であるべきかを決定します。
     -   `EnableWindow` API を使用して、各コントロールの状態を実際に変更します。
     -   ユーザーが状況に応じて適切な操作のみを行えるようにUIを動的に制御します。
@@ -43,8 +43,13 @@ use windows::Win32::{
 };
 
 use crate::{
-    app_state::AppState, constants::*,
-    ui::auto_click_checkbox_handler::update_auto_click_controls_state,
+    app_state::{AppMode, AppState},
+    constants::*,
+    ui::{
+        auto_click_checkbox_handler::update_auto_click_controls_state,
+        icon_button::subclass_icon_button,
+        target_width_edit_handler::update_target_width_edit_state,
+    },
 };
 
 /// オーナードローボタンの初期化
@@ -60,6 +65,7 @@ use crate::{
 /// 1. `LoadCursorW` で `IDC_HAND`（手のひら）カーソルを読み込みます。
 /// 2. `GetDlgItem` で各ボタンのハンドルを取得します。
 /// 3. `SetClassLongPtrW` を使用して、各ボタンのウィンドウクラスに `GCLP_HCURSOR` を設定します。
+/// 4. `subclass_icon_button` で各ボタンをサブクラス化し、ホバー表示のためにマウスの出入りを追跡します。
 pub fn initialize_icon_button(hwnd: HWND) {
     unsafe {
         // 手のひらカーソルを読み込み
@@ -69,22 +75,27 @@ pub fn initialize_icon_button(hwnd: HWND) {
         if let Ok(button) = GetDlgItem(Some(hwnd), IDC_CAPTURE_START_BUTTON) {
             let _ = InvalidateRect(Some(button), None, true);
             let _ = SetClassLongPtrW(button, GET_CLASS_LONG_INDEX(-12), hand_cursor.0 as isize);
+            subclass_icon_button(button);
         }
         if let Ok(button) = GetDlgItem(Some(hwnd), IDC_AREA_SELECT_BUTTON) {
             let _ = InvalidateRect(Some(button), None, true);
             let _ = SetClassLongPtrW(button, GET_CLASS_LONG_INDEX(-12), hand_cursor.0 as isize);
+            subclass_icon_button(button);
         }
         if let Ok(button) = GetDlgItem(Some(hwnd), IDC_BROWSE_BUTTON) {
             let _ = InvalidateRect(Some(button), None, true);
             let _ = SetClassLongPtrW(button, GET_CLASS_LONG_INDEX(-12), hand_cursor.0 as isize);
+            subclass_icon_button(button);
         }
         if let Ok(button) = GetDlgItem(Some(hwnd), IDC_CLOSE_BUTTON) {
             let _ = InvalidateRect(Some(button), None, true);
             let _ = SetClassLongPtrW(button, GET_CLASS_LONG_INDEX(-12), hand_cursor.0 as isize);
+            subclass_icon_button(button);
         }
         if let Ok(button) = GetDlgItem(Some(hwnd), IDC_EXPORT_PDF_BUTTON) {
             let _ = InvalidateRect(Some(button), None, true);
             let _ = SetClassLongPtrW(button, GET_CLASS_LONG_INDEX(-12), hand_cursor.0 as isize);
+            subclass_icon_button(button);
         }
     }
 }
@@ -96,6 +107,8 @@ pub fn initialize_icon_button(hwnd: HWND) {
 /// - **エリア選択モード**: 「エリア選択」ボタン（キャンセルとして機能）と「閉じる」ボタンのみ有効になります。
 /// - **キャプチャモード**: 「キャプチャ開始」ボタン（キャンセルとして機能）と「閉じる」ボタンのみ有効になります。
 /// - **PDF変換中**: 全てのコントロールが無効になり、処理に集中させます。
+/// - **GIF変換中・一覧画像作成中**: 「閉じる」ボタンのみ有効になります（変換はワーカースレッドで継続）。
+/// - **定期キャプチャ中**: 「定期停止」ボタンと「閉じる」ボタンのみ有効になります。
 ///
 /// # 呼び出しタイミング
 /// モードが変更されるたびに呼び出され、UIの状態をアプリケーションの内部状態と同期させます。
@@ -118,18 +131,31 @@ pub fn update_input_control_states() {
         close_enable,
         auto_click_enable,
         property_combobox_enable,
-    ) = if app_state.is_area_select_mode {
-        // エリア選択モード中：「エリア選択」ボタン（キャンセル用）と「閉じる」ボタンのみ有効
-        (true, false, false, false, true, false, false)
-    } else if app_state.is_capture_mode {
-        // キャプチャモード中：「キャプチャ開始」ボタン（キャンセル用）と「閉じる」ボタンのみ有効
-        (false, true, false, false, true, false, false)
-    } else if app_state.is_exporting_to_pdf {
-        // PDF変換中：全てのコントロールを無効化
-        (false, false, false, false, false, false, false)
-    } else {
-        // 通常モード：エリア選択済みならキャプチャ表示、他は全て表示
-        (true, true, true, true, true, true, true)
+    ) = match app_state.mode {
+        AppMode::AreaSelecting { .. } => {
+            // エリア選択モード中：「エリア選択」ボタン（キャンセル用）と「閉じる」ボタンのみ有効
+            (true, false, false, false, true, false, false)
+        }
+        AppMode::Capturing { .. } => {
+            // キャプチャモード中：「キャプチャ開始」ボタン（キャンセル用）と「閉じる」ボタンのみ有効
+            (false, true, false, false, true, false, false)
+        }
+        AppMode::ExportingPdf => {
+            // PDF変換中：全てのコントロールを無効化
+            (false, false, false, false, false, false, false)
+        }
+        AppMode::Idle if app_state.is_exporting_to_gif || app_state.is_exporting_contact_sheet => {
+            // GIF変換中・一覧画像作成中（ワーカースレッド）：「閉じる」ボタンのみ有効
+            (false, false, false, false, true, false, false)
+        }
+        AppMode::Idle if app_state.scheduled_capturer.is_running() => {
+            // 定期キャプチャ中：「定期停止」ボタンと「閉じる」ボタンのみ有効
+            (false, false, false, false, true, false, false)
+        }
+        AppMode::Idle => {
+            // 通常モード：エリア選択済みならキャプチャ表示、他は全て表示
+            (true, true, true, true, true, true, true)
+        }
     };
 
     // ボタン表示制御関数
@@ -150,13 +176,91 @@ pub fn update_input_control_states() {
     set_input_control_status(hwnd, IDC_CAPTURE_START_BUTTON, capture_enable);
     set_input_control_status(hwnd, IDC_BROWSE_BUTTON, browse_enable);
     set_input_control_status(hwnd, IDC_EXPORT_PDF_BUTTON, export_pdf_enable);
+    set_input_control_status(hwnd, IDC_EXPORT_GIF_BUTTON, export_pdf_enable);
+    set_input_control_status(hwnd, IDC_EXPORT_CONTACT_SHEET_BUTTON, export_pdf_enable);
+    // 履歴の削除・並べ替えはPDF変換のページ順に影響するため、変換ボタンと同じ条件で有効にする
+    set_input_control_status(hwnd, IDC_HISTORY_BUTTON, export_pdf_enable);
+    // 作業領域ボタンは通常モードのみ有効（エリア選択中・キャプチャ中は選択領域を変更しない）
+    set_input_control_status(hwnd, IDC_WORK_AREA_BUTTON, export_pdf_enable);
+    // 連番整理ボタンは通常モードのみ有効（ZIP圧縮中は対象ファイルを読み込んでいるため無効）
+    set_input_control_status(
+        hwnd,
+        IDC_RENUMBER_BUTTON,
+        export_pdf_enable && !app_state.is_exporting_to_zip,
+    );
+    // 連番リセットボタンは通常モードのみ有効
+    set_input_control_status(hwnd, IDC_RESET_COUNTER_BUTTON, export_pdf_enable);
+    // 再保存ボタンは保存に失敗したキャプチャを保持している場合のみ有効
+    set_input_control_status(
+        hwnd,
+        IDC_RESAVE_BUTTON,
+        export_pdf_enable && app_state.last_failed_capture.is_some(),
+    );
+    // 共有ボタンは共有UIを使用でき、保存済みのキャプチャがある場合のみ有効
+    set_input_control_status(
+        hwnd,
+        IDC_SHARE_BUTTON,
+        export_pdf_enable && app_state.share_ui_supported && app_state.last_saved_file.is_some(),
+    );
+    // ZIP圧縮ボタンは実行中（中止用）も有効（圧縮中もキャプチャ操作は継続できる）
+    set_input_control_status(
+        hwnd,
+        IDC_EXPORT_ZIP_BUTTON,
+        export_pdf_enable || app_state.is_exporting_to_zip,
+    );
     set_input_control_status(hwnd, IDC_CLOSE_BUTTON, close_enable);
     set_input_control_status(hwnd, IDC_AUTO_CLICK_CHECKBOX, auto_click_enable);
 
+    // 定期キャプチャボタンは実行中（停止用）と通常モードで有効
+    let schedule_button_enable = app_state.mode == AppMode::Idle
+        && !app_state.is_exporting_to_gif
+        && !app_state.is_exporting_contact_sheet;
+    set_input_control_status(hwnd, IDC_SCHEDULE_BUTTON, schedule_button_enable);
+
     // プロパティコンボボックス群の有効/無効制御
     set_input_control_status(hwnd, IDC_SCALE_COMBO, property_combobox_enable);
     set_input_control_status(hwnd, IDC_QUALITY_COMBO, property_combobox_enable);
     set_input_control_status(hwnd, IDC_PDF_SIZE_COMBO, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_PDF_ARCHIVE_CHECKBOX, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_AUTO_PDF_EXPORT_CHECKBOX, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_ELAPSED_STAMP_CHECKBOX, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_CAPTURE_TRIGGER_COMBO, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_CAPTURE_FORMAT_COMBO, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_WINDOW_CAPTURE_CHECKBOX, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_SOUND_CHECKBOX, property_combobox_enable);
+    set_input_control_status(
+        hwnd,
+        IDC_CAPTURE_METADATA_CHECKBOX,
+        property_combobox_enable,
+    );
+    set_input_control_status(hwnd, IDC_LANGUAGE_COMBO, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_THEME_COMBO, property_combobox_enable);
+    // 完了通知（IDC_COMPLETION_NOTIFY_CHECKBOX）は自動クリックの実行中にも切り替えられるよう、常に有効のままにする
+    // 画面の固定はエリア選択の開始時に反映されるため、モード中は変更できないようにする
+    set_input_control_status(hwnd, IDC_FREEZE_SCREEN_CHECKBOX, property_combobox_enable);
+    // 表示方法・暗転の濃さ・境界線のスタイル（IDC_OVERLAY_VISIBILITY_COMBO / IDC_DIM_OPACITY_COMBO /
+    // IDC_BORDER_COLOR_COMBO / IDC_BORDER_WIDTH_COMBO / IDC_BORDER_HALO_CHECKBOX）は
+    // モード中にも変更して即時反映できるよう、常に有効のままにする
+    set_input_control_status(
+        hwnd,
+        IDC_HIGH_FIDELITY_TEXT_CHECKBOX,
+        property_combobox_enable,
+    );
+    set_input_control_status(hwnd, IDC_REAL_INPUT_ONLY_CHECKBOX, property_combobox_enable);
+    set_input_control_status(
+        hwnd,
+        IDC_DUAL_AREA_STACKED_CHECKBOX,
+        property_combobox_enable,
+    );
+    set_input_control_status(hwnd, IDC_SCHEDULE_INTERVAL_COMBO, property_combobox_enable);
+    set_input_control_status(hwnd, IDC_SCHEDULE_COUNT_EDIT, property_combobox_enable);
+
+    // 出力幅エディットボックスは幅指定モード時のみ有効化
+    if property_combobox_enable {
+        update_target_width_edit_state(hwnd);
+    } else {
+        set_input_control_status(hwnd, IDC_TARGET_WIDTH_EDIT, false);
+    }
 
     // 自動クリックの設定が有効な場合、関連コントロールを有効化
     if auto_click_enable {
@@ -164,6 +268,9 @@ pub fn update_input_control_states() {
     } else {
         set_input_control_status(hwnd, IDC_AUTO_CLICK_INTERVAL_COMBO, false);
         set_input_control_status(hwnd, IDC_AUTO_CLICK_COUNT_EDIT, false);
+        set_input_control_status(hwnd, IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX, false);
+        set_input_control_status(hwnd, IDC_AUTO_CLICK_DURATION_COMBO, false);
+        set_input_control_status(hwnd, IDC_PAUSE_ON_USER_INPUT_CHECKBOX, false);
     }
 
     // デバッグログ出力
@@ -0,0 +1,154 @@
+/*
+============================================================================
+ダイアログのキーボード操作モジュール (keyboard_navigation_handler.rs)
+============================================================================
+
+【ファイル概要】
+メインダイアログをマウスなしで操作するための、Enter / Esc キーとアクセラレータ（Alt+文字）の処理モジュール。
+モーダルダイアログ（`DialogBoxParamW`）のメッセージループは `IsDialogMessageW` 相当の処理を行うため、
+Enter は `IDOK`、Esc は `IDCANCEL` の `WM_COMMAND` として `dialog_proc` に届きます。
+
+【主要機能】
+1.  **Enterキー**: `handle_dialog_enter`
+    -   通常モードでは「キャプチャ開始」ボタンと同じ動作（既定のボタン）
+2.  **Escキー**: `confirm_dialog_escape`
+    -   「閉じる」ボタンと同じ動作。定期キャプチャ・自動クリック・変換処理の実行中は確認ダイアログを表示
+    -   キャプチャモード・エリア選択モードのEscはグローバルキーボードフックが消費するため、ここには届かない
+3.  **アクセラレータ**: `handle_dialog_accelerator`
+    -   Alt+A: エリア選択 / Alt+C: キャプチャ開始 / Alt+P: PDF変換
+    -   無効化されているボタンのアクセラレータは無視
+4.  **診断ダンプ（隠しホットキー）**: Alt+D
+    -   `AppState::dump` の内容（動作モード・選択領域・連番・画質・自動クリック設定・画面解像度・GDI+の初期化結果）を1行ずつログに出力
+    -   ダイアログ上のボタンに対応しないため、ツールチップには表示しない
+
+【技術仕様】
+-   **アクセラレータの受信**: ダイアログにフォーカスがある場合は `WM_SYSCHAR`、子コントロールにフォーカスがある場合は
+    `DefWindowProc` がトップレベルウィンドウに送る `WM_SYSCOMMAND`（`SC_KEYMENU`、lparam: 文字コード）で受け取る
+-   **無効状態の判定**: `IsWindowEnabled` でボタンの状態を確認（`update_input_control_states` の結果に従う）
+
+【AI解析用：依存関係】
+-   `dialog_handler.rs`: `IDOK` / `IDCANCEL` / `WM_SYSCHAR` / `WM_SYSCOMMAND` の受信
+-   `area_select.rs`, `screen_capture.rs`, `pdf_export_button_handler.rs`: 各ボタンの処理
+-   `strings.rs`: 終了確認ダイアログ・キー操作ログの文字列
+-   `app_state.rs`: `AppState::dump`（診断ダンプの整形）
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::{Input::KeyboardAndMouse::IsWindowEnabled, WindowsAndMessaging::*},
+};
+
+use crate::{
+    app_state::{AppMode, AppState},
+    area_select::start_area_select_mode,
+    constants::*,
+    screen_capture::toggle_capture_mode,
+    strings,
+    system_utils::{app_log, show_message_box},
+    ui::pdf_export_button_handler::handle_pdf_export_button,
+};
+
+/// Enterキー（`IDOK`）を処理する
+///
+/// 通常モードで「キャプチャ開始」ボタンが有効な場合に、キャプチャモードを開始します。
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル
+pub fn handle_dialog_enter(hwnd: HWND) {
+    let app_state = AppState::get_app_state_ref();
+    if app_state.is_capture_mode() || app_state.is_area_select_mode() {
+        return;
+    }
+    if is_control_enabled(hwnd, IDC_CAPTURE_START_BUTTON) {
+        app_log(strings::enter_key_capture_start());
+        toggle_capture_mode();
+    }
+}
+
+/// Escキー（`IDCANCEL`）でダイアログを閉じてよいかを確認する
+///
+/// # 戻り値
+/// 閉じてよい場合は `true`。処理の実行中に確認ダイアログでキャンセルされた場合は `false`。
+pub fn confirm_dialog_escape() -> bool {
+    if !is_any_mode_active() {
+        return true;
+    }
+    let result = show_message_box(
+        strings::close_while_busy_message(),
+        strings::close_while_busy_title(),
+        MB_OKCANCEL | MB_ICONQUESTION,
+    );
+    result.0 == IDOK.0
+}
+
+/// アクセラレータ（Alt+文字）を処理する
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル
+/// * `char_code` - 押された文字の文字コード
+///
+/// # 戻り値
+/// アクセラレータとして処理した場合は `true`（既定の処理を行わせない）。
+pub fn handle_dialog_accelerator(hwnd: HWND, char_code: u32) -> bool {
+    let Some(key) = char::from_u32(char_code).map(|c| c.to_ascii_lowercase()) else {
+        return false;
+    };
+    let control_id = match key {
+        'd' => {
+            dump_diagnostics();
+            return true;
+        }
+        'a' => IDC_AREA_SELECT_BUTTON,
+        'c' => IDC_CAPTURE_START_BUTTON,
+        'p' => IDC_EXPORT_PDF_BUTTON,
+        _ => return false,
+    };
+    // 無効化されているボタンのアクセラレータは消費だけして何もしない
+    if !is_control_enabled(hwnd, control_id) {
+        return true;
+    }
+
+    match control_id {
+        IDC_AREA_SELECT_BUTTON => {
+            app_log(strings::accelerator_area_select());
+            start_area_select_mode();
+        }
+        IDC_CAPTURE_START_BUTTON => {
+            app_log(strings::accelerator_capture_start());
+            toggle_capture_mode();
+        }
+        _ => {
+            app_log(strings::accelerator_pdf_export());
+            handle_pdf_export_button();
+        }
+    }
+    true
+}
+
+/// 診断ダンプ：現在の `AppState` の主要な状態をログに出力する（Alt+D）
+fn dump_diagnostics() {
+    app_log("🩺 ===== 診断ダンプ (Alt+D) =====");
+    for line in AppState::get_app_state_ref().dump().lines() {
+        app_log(&format!("🩺 {}", line));
+    }
+}
+
+/// 終了前に確認が必要な処理（定期キャプチャ・自動クリック・変換処理）が実行中かを判定する
+fn is_any_mode_active() -> bool {
+    let app_state = AppState::get_app_state_ref();
+    app_state.mode != AppMode::Idle
+        || app_state.scheduled_capturer.is_running()
+        || app_state.auto_clicker.is_running()
+        || app_state.is_exporting_to_gif
+        || app_state.is_exporting_to_zip
+        || app_state.is_exporting_contact_sheet
+}
+
+/// ダイアログ上のコントロールが有効かを判定する
+fn is_control_enabled(hwnd: HWND, control_id: i32) -> bool {
+    unsafe {
+        GetDlgItem(Some(hwnd), control_id)
+            .map(|control| IsWindowEnabled(control).as_bool())
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,138 @@
+/*
+============================================================================
+言語コンボボックスハンドラモジュール (language_combo_handler.rs)
+============================================================================
+
+【ファイル概要】
+ログ・メッセージボックス・オーバーレイのラベルの表示言語を選択するコンボボックスを
+管理するモジュール。既定ではOSのUI言語から自動判定し、日本語以外の環境では英語で表示します。
+
+【主要機能】
+1.  **言語コンボボックス初期化**: `initialize_language_combo`
+    -   「自動」「日本語」「English」の3項目を提供（デフォルト：自動）
+2.  **言語変更イベント処理**: `handle_language_combo_change`
+    -   選択された設定を `AppState.language_setting` に保存し、`strings::set_language` で表示言語を切り替え
+    -   ツールチップの文字列も新しい表示言語で更新（`refresh_tooltip_texts`）
+
+【技術仕様】
+-   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)
+-   **データ管理**: 各項目に `LanguageSetting` の識別値（`u8`）を関連付け
+-   **適用範囲**: Rust側の表示文字列のみ（ダイアログリソースのキャプションは対象外）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `language_setting` フィールドの保持
+-   `constants.rs`: `IDC_LANGUAGE_COMBO` コントロールID定義
+-   `strings.rs`: `LanguageSetting` 定義と表示言語の保持
+-   `tooltip_handler.rs`: 言語変更時のツールチップ文字列の更新
+-   メインダイアログ: CBN_SELCHANGE通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    strings::{self, LanguageSetting},
+    system_utils::app_log,
+    ui::tooltip_handler::refresh_tooltip_texts,
+};
+
+/// 言語コンボボックスを初期化する
+///
+/// 「自動」「日本語」「English」の選択肢を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_language_combo(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_LANGUAGE_COMBO) } {
+        let settings = [
+            LanguageSetting::Auto,
+            LanguageSetting::Japanese,
+            LanguageSetting::English,
+        ];
+
+        let current_setting = AppState::get_app_state_ref().language_setting;
+        let mut default_index = 0;
+
+        for setting in settings {
+            // Win32 APIに渡すためNull終端文字を付加
+            let text = format!("{}\0", setting.label());
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+
+            // CB_ADDSTRING：コンボボックスに表示テキストを追加
+            let index = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(wide_text.as_ptr() as isize)),
+                )
+            }
+            .0 as usize;
+
+            // CB_SETITEMDATA：表示テキストと言語設定の識別値を関連付け
+            unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_SETITEMDATA,
+                    Some(WPARAM(index)),
+                    Some(LPARAM(setting as isize)),
+                );
+            }
+
+            if setting == current_setting {
+                default_index = index;
+            }
+        }
+
+        // 現在の設定値を選択状態に設定
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
+        }
+    }
+}
+
+/// 言語コンボボックスの選択変更イベントを処理する
+///
+/// 選択された項目の識別値から `LanguageSetting` を復元して `AppState` に保存し、
+/// 表示言語を切り替えます。変更は以降のログ・メッセージ・オーバーレイ描画から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_language_combo_change(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_LANGUAGE_COMBO) } {
+        // CB_GETCURSEL：現在選択されている項目のインデックス取得
+        let selected_index =
+            unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 }
+                as i32;
+
+        if selected_index >= 0 {
+            // CB_GETITEMDATA：選択項目に関連付けられた言語設定の識別値を取得
+            let setting_value = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_GETITEMDATA,
+                    Some(WPARAM(selected_index as usize)),
+                    Some(LPARAM(0)),
+                )
+            }
+            .0 as u8;
+
+            let setting = LanguageSetting::from_u8(setting_value);
+            let app_state = AppState::get_app_state_mut();
+            app_state.language_setting = setting;
+            strings::set_language(setting.resolve());
+            refresh_tooltip_texts(hwnd);
+
+            app_log(&strings::language_changed(setting.label()));
+        }
+    }
+}
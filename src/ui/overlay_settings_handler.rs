@@ -0,0 +1,375 @@
+/*
+============================================================================
+オーバーレイ表示設定ハンドラモジュール (overlay_settings_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャオーバーレイ（カーソルに追従するカメラアイコン）の表示方法と、
+エリア選択オーバーレイの暗転の濃さ・境界線のスタイルを選択するコントロールを管理するモジュール。
+画面上に何も表示せずにキャプチャしたい場合や、暗転が濃すぎて選択しづらい場合、
+赤い画面で境界線が見えにくい場合に使用します。
+
+【主要機能】
+1.  **表示方法コンボボックス**: `initialize_overlay_visibility_combo` / `handle_overlay_visibility_combo_change`
+    -   「表示」「半透明」「非表示」の3項目（デフォルト：表示）
+2.  **暗転の濃さコンボボックス**: `initialize_dim_opacity_combo` / `handle_dim_opacity_combo_change`
+    -   50%〜100%（10%刻み、デフォルト：60%）
+3.  **境界線のスタイル**: `initialize_border_style_controls` / `handle_border_color_combo_change` /
+    `handle_border_width_combo_change` / `handle_border_halo_checkbox_change`
+    -   色（赤/緑/青/黄/黒、デフォルト：赤）、太さ（1〜4px、デフォルト：2px）、白い縁取り（デフォルト：なし）
+    -   レジストリに選択肢にない色（ARGB）が保存されている場合は、`#RRGGBB` の項目を追加して選択
+4.  **画面固定チェックボックス**: `initialize_freeze_screen_checkbox` / `handle_freeze_screen_checkbox_change`
+    -   エリア選択の開始時の画面を静止画として表示する（デフォルト：しない）
+    -   開始時に画面を保存するため、エリア選択中は変更できない（次のエリア選択から反映）
+
+【技術仕様】
+-   **即時反映**: 変更時に表示中のオーバーレイを `refresh_overlay` で再描画するため、
+    キャプチャモード・エリア選択モードを開始し直す必要はない（モード中も操作できるよう無効化しない）
+    ただし画面の固定はエリア選択の開始時にのみ反映されるため、モード中は無効化する
+-   **永続化**: `settings::save_setting_dword` でレジストリに保存し、次回起動時の初期値にする
+-   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)、`CheckDlgButton`
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `capturing_overlay_visibility`, `area_select_dim_opacity`, `area_select_border_*`,
+    `freeze_screen_on_area_select` フィールドの保持
+-   `overlay/capturing_overlay.rs`: `CapturingOverlayVisibility`（表示方法と透明度）
+-   `overlay/area_select_overlay.rs`: `set_dim_opacity`（暗転ブラシの色の差し替え）、`update_style`（境界線の色と太さ）
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: CBN_SELCHANGE / BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    overlay::{
+        Overlay, area_select_overlay::BORDER_WIDTH_RANGE,
+        capturing_overlay::CapturingOverlayVisibility,
+    },
+    settings::{
+        BORDER_COLOR_VALUE_NAME, BORDER_HALO_VALUE_NAME, BORDER_WIDTH_VALUE_NAME,
+        DIM_OPACITY_VALUE_NAME, FREEZE_SCREEN_VALUE_NAME, OVERLAY_VISIBILITY_VALUE_NAME,
+        save_setting_dword,
+    },
+    strings,
+    system_utils::app_log,
+};
+
+/// 暗転の濃さの選択肢（%）
+const DIM_OPACITY_OPTIONS: [u8; 6] = [50, 60, 70, 80, 90, 100];
+
+/// 選択肢の表示名（現在の表示言語の文字列を返す）
+type OptionLabel = fn() -> &'static str;
+
+/// 境界線の色の選択肢（ARGBと表示名）
+const BORDER_COLOR_OPTIONS: [(u32, OptionLabel); 5] = [
+    (0xFFFF0000, strings::border_color_red),
+    (0xFF00C000, strings::border_color_green),
+    (0xFF0000FF, strings::border_color_blue),
+    (0xFFFFD700, strings::border_color_yellow),
+    (0xFF000000, strings::border_color_black),
+];
+
+/// 表示方法コンボボックスを初期化する
+///
+/// 「表示」「半透明」「非表示」の選択肢を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_overlay_visibility_combo(hwnd: HWND) {
+    let settings = [
+        CapturingOverlayVisibility::Visible,
+        CapturingOverlayVisibility::SemiTransparent,
+        CapturingOverlayVisibility::Hidden,
+    ];
+    let current_setting = AppState::get_app_state_ref().capturing_overlay_visibility;
+    let items: Vec<(String, isize)> = settings
+        .iter()
+        .map(|setting| (setting.label().to_string(), *setting as isize))
+        .collect();
+    let default_index = settings
+        .iter()
+        .position(|&setting| setting == current_setting)
+        .unwrap_or(0);
+    fill_combo(hwnd, IDC_OVERLAY_VISIBILITY_COMBO, &items, default_index);
+}
+
+/// 表示方法コンボボックスの選択変更イベントを処理する
+///
+/// 選択された表示方法を `AppState` とレジストリに保存し、表示中のキャプチャオーバーレイを再描画します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_overlay_visibility_combo_change(hwnd: HWND) {
+    let Some(value) = get_selected_item_data(hwnd, IDC_OVERLAY_VISIBILITY_COMBO) else {
+        return;
+    };
+    let setting = CapturingOverlayVisibility::from_u8(value as u8);
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.capturing_overlay_visibility = setting;
+    save_setting_dword(OVERLAY_VISIBILITY_VALUE_NAME, setting as u32);
+
+    // 表示中（キャプチャモード中・定期キャプチャ中）であれば次の描画から新しい透明度で表示
+    if let Some(overlay) = app_state.capturing_overlay.as_ref() {
+        overlay.refresh_overlay();
+    }
+
+    app_log(&strings::overlay_visibility_changed(setting.label()));
+}
+
+/// 暗転の濃さコンボボックスを初期化する
+///
+/// 50%〜100%（10%刻み）の選択肢を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_dim_opacity_combo(hwnd: HWND) {
+    let current_opacity = AppState::get_app_state_ref().area_select_dim_opacity;
+    let items: Vec<(String, isize)> = DIM_OPACITY_OPTIONS
+        .iter()
+        .map(|&percent| (format!("{}%", percent), percent as isize))
+        .collect();
+    // 保存値が選択肢にない場合は、それ以下で最も近い選択肢を選ぶ
+    let default_index = DIM_OPACITY_OPTIONS
+        .iter()
+        .rposition(|&percent| percent <= current_opacity)
+        .unwrap_or(0);
+    fill_combo(hwnd, IDC_DIM_OPACITY_COMBO, &items, default_index);
+}
+
+/// 暗転の濃さコンボボックスの選択変更イベントを処理する
+///
+/// 選択された不透明度を `AppState` とレジストリに保存し、エリア選択オーバーレイの暗転ブラシに反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_dim_opacity_combo_change(hwnd: HWND) {
+    let Some(value) = get_selected_item_data(hwnd, IDC_DIM_OPACITY_COMBO) else {
+        return;
+    };
+    let percent = (value as u8).clamp(50, 100);
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.area_select_dim_opacity = percent;
+    save_setting_dword(DIM_OPACITY_VALUE_NAME, percent as u32);
+
+    // エリア選択中であれば次の描画から新しい濃さで表示
+    if let Some(overlay) = app_state.area_select_overlay.as_ref() {
+        overlay.set_dim_opacity(percent);
+        overlay.refresh_overlay();
+    }
+
+    app_log(&strings::dim_opacity_changed(percent));
+}
+
+/// 境界線のスタイル（色・太さ・縁取り）のコントロールを初期化する
+///
+/// 色と太さのコンボボックスに選択肢を追加し、`AppState` の現在値を選択状態・チェック状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_border_style_controls(hwnd: HWND) {
+    let app_state = AppState::get_app_state_ref();
+
+    // 色：保存値が選択肢にない場合（レジストリを直接編集した場合など）は `#RRGGBB` の項目を追加する
+    let current_argb = app_state.area_select_border_argb;
+    let mut color_items: Vec<(String, isize)> = BORDER_COLOR_OPTIONS
+        .iter()
+        .map(|(argb, label)| (label().to_string(), *argb as isize))
+        .collect();
+    let color_index = match BORDER_COLOR_OPTIONS
+        .iter()
+        .position(|(argb, _)| *argb == current_argb)
+    {
+        Some(index) => index,
+        None => {
+            color_items.push((border_color_label(current_argb), current_argb as isize));
+            color_items.len() - 1
+        }
+    };
+    fill_combo(hwnd, IDC_BORDER_COLOR_COMBO, &color_items, color_index);
+
+    // 太さ：1〜4px
+    let width_items: Vec<(String, isize)> = BORDER_WIDTH_RANGE
+        .map(|width| (format!("{}px", width), width as isize))
+        .collect();
+    let width_index = (app_state.area_select_border_width - BORDER_WIDTH_RANGE.start()) as usize;
+    fill_combo(hwnd, IDC_BORDER_WIDTH_COMBO, &width_items, width_index);
+
+    // 縁取り
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_BORDER_HALO_CHECKBOX,
+            if app_state.area_select_border_halo {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 境界線の色コンボボックスの選択変更イベントを処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_border_color_combo_change(hwnd: HWND) {
+    let Some(value) = get_selected_item_data(hwnd, IDC_BORDER_COLOR_COMBO) else {
+        return;
+    };
+    let argb = value as u32;
+    AppState::get_app_state_mut().area_select_border_argb = argb;
+    save_setting_dword(BORDER_COLOR_VALUE_NAME, argb);
+    apply_border_style();
+}
+
+/// 境界線の太さコンボボックスの選択変更イベントを処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_border_width_combo_change(hwnd: HWND) {
+    let Some(value) = get_selected_item_data(hwnd, IDC_BORDER_WIDTH_COMBO) else {
+        return;
+    };
+    let width = (value as u8).clamp(*BORDER_WIDTH_RANGE.start(), *BORDER_WIDTH_RANGE.end());
+    AppState::get_app_state_mut().area_select_border_width = width;
+    save_setting_dword(BORDER_WIDTH_VALUE_NAME, width as u32);
+    apply_border_style();
+}
+
+/// 縁取りチェックボックスの状態変更イベントを処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_border_halo_checkbox_change(hwnd: HWND) {
+    let is_checked = unsafe { IsDlgButtonChecked(hwnd, IDC_BORDER_HALO_CHECKBOX) } == BST_CHECKED.0;
+    AppState::get_app_state_mut().area_select_border_halo = is_checked;
+    save_setting_dword(BORDER_HALO_VALUE_NAME, is_checked as u32);
+    apply_border_style();
+}
+
+/// 画面固定チェックボックスを初期化する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_freeze_screen_checkbox(hwnd: HWND) {
+    let is_enabled = AppState::get_app_state_ref().freeze_screen_on_area_select;
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_FREEZE_SCREEN_CHECKBOX,
+            if is_enabled {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 画面固定チェックボックスの状態変更イベントを処理する
+///
+/// 次のエリア選択の開始時から反映されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_freeze_screen_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_FREEZE_SCREEN_CHECKBOX) } == BST_CHECKED.0;
+    AppState::get_app_state_mut().freeze_screen_on_area_select = is_checked;
+    save_setting_dword(FREEZE_SCREEN_VALUE_NAME, is_checked as u32);
+    app_log(strings::freeze_screen_changed(is_checked));
+}
+
+/// 現在の境界線のスタイルをエリア選択オーバーレイに反映し、変更をログに出力する
+fn apply_border_style() {
+    let app_state = AppState::get_app_state_ref();
+    let (argb, width, halo) = (
+        app_state.area_select_border_argb,
+        app_state.area_select_border_width,
+        app_state.area_select_border_halo,
+    );
+
+    // エリア選択中であれば次の描画から新しいスタイルで表示
+    if let Some(overlay) = app_state.area_select_overlay.as_ref() {
+        overlay.update_style(argb, width);
+        overlay.refresh_overlay();
+    }
+
+    app_log(&strings::border_style_changed(
+        &border_color_label(argb),
+        width,
+        halo,
+    ));
+}
+
+/// 境界線の色の表示名（選択肢にない色は `#RRGGBB`）
+fn border_color_label(argb: u32) -> String {
+    BORDER_COLOR_OPTIONS
+        .iter()
+        .find(|(option, _)| *option == argb)
+        .map(|(_, label)| label().to_string())
+        .unwrap_or_else(|| format!("#{:06X}", argb & 0x00FFFFFF))
+}
+
+/// コンボボックスに項目（表示テキストと項目データ）を追加し、指定の項目を選択する
+fn fill_combo(hwnd: HWND, control_id: i32, items: &[(String, isize)], selected_index: usize) {
+    let Ok(combo_hwnd) = (unsafe { GetDlgItem(Some(hwnd), control_id) }) else {
+        return;
+    };
+    for (label, data) in items {
+        // Win32 APIに渡すためNull終端文字を付加
+        let wide_text: Vec<u16> = label.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe {
+            let index = SendMessageW(
+                combo_hwnd,
+                CB_ADDSTRING,
+                Some(WPARAM(0)),
+                Some(LPARAM(wide_text.as_ptr() as isize)),
+            )
+            .0 as usize;
+            SendMessageW(
+                combo_hwnd,
+                CB_SETITEMDATA,
+                Some(WPARAM(index)),
+                Some(LPARAM(*data)),
+            );
+        }
+    }
+    unsafe {
+        SendMessageW(
+            combo_hwnd,
+            CB_SETCURSEL,
+            Some(WPARAM(selected_index)),
+            Some(LPARAM(0)),
+        );
+    }
+}
+
+/// コンボボックスで選択されている項目のデータを取得する（未選択の場合は `None`）
+fn get_selected_item_data(hwnd: HWND, control_id: i32) -> Option<isize> {
+    let combo_hwnd = unsafe { GetDlgItem(Some(hwnd), control_id) }.ok()?;
+    unsafe {
+        let selected_index =
+            SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 as i32;
+        if selected_index < 0 {
+            return None;
+        }
+        Some(
+            SendMessageW(
+                combo_hwnd,
+                CB_GETITEMDATA,
+                Some(WPARAM(selected_index as usize)),
+                Some(LPARAM(0)),
+            )
+            .0,
+        )
+    }
+}
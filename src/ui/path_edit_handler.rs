@@ -10,7 +10,11 @@ use windows::Win32::{
 };
 use windows::core::PCWSTR;
 
-use crate::{app_state::AppState, constants::*, ui::folder_manager::get_pictures_folder};
+use crate::{
+    app_state::AppState,
+    constants::*,
+    ui::folder_manager::{get_pictures_folder, load_last_folder},
+};
 
 /// 保存先パスのエディットボックスを初期化
 ///
@@ -21,13 +25,14 @@ use crate::{app_state::AppState, constants::*, ui::folder_manager::get_pictures_
 /// * `hwnd` - メインダイアログのウィンドウハンドル。
 ///
 /// # 処理内容
-/// 1. `folder_manager::get_pictures_folder` を呼び出し、最適な保存先（例: OneDrive/ピクチャ, ローカルのピクチャ）を自動検出します。
+/// 1. 前回選択した保存先（`folder_manager::load_last_folder`）が現在も存在すればそれを使用し、
+///    なければ `folder_manager::get_pictures_folder` を呼び出して最適な保存先（例: OneDrive/ピクチャ, ローカルのピクチャ）を自動検出します。
 /// 2. 検出したパスを `AppState` の `selected_folder_path` に保存します。
 /// 3. `SetWindowTextW` を使用して、UIのエディットボックス（`IDC_PATH_EDIT`）にパスを表示します。
 pub fn init_path_edit_control(hwnd: HWND) {
     unsafe {
         let app_state = AppState::get_app_state_mut();
-        let default_folder = get_pictures_folder();
+        let default_folder = load_last_folder().unwrap_or_else(get_pictures_folder);
         app_state.selected_folder_path = Some(default_folder.clone());
 
         // パステキストボックスに初期値を設定
@@ -0,0 +1,87 @@
+/*
+============================================================================
+ユーザー操作中の一時停止チェックボックスハンドラモジュール (pause_on_user_input_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+自動クリック設定の「操作中は一時停止」（ユーザー操作中は一時停止）チェックボックスを管理するモジュール。
+ON のとき、自動クリックの実行中にユーザーがマウスを動かすと、操作が止まってから静止時間が経つまで
+次のクリックを遅らせます。チャットの返信などで途中にマウスを使っても、合成クリックが操作中の
+場所に割り込んだり、無関係な操作を撮影したりしないようにします。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_pause_on_user_input_checkbox`
+    -   `AutoClicker` の設定をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_pause_on_user_input_checkbox_change`
+    -   チェック状態を `AutoClicker::set_pause_on_user_input` に反映し、レジストリに保存
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **永続化**: `settings::save_setting_dword`（`PauseOnUserInput`）で次回起動時の初期値にする
+-   **操作の検出**: `hook/mouse.rs` が自動クリック由来でないマウス移動で `AutoClicker::note_user_input` を呼び出す
+-   **静止時間**: 既定2秒。画面に設定項目はなく、レジストリ `UserInputQuietMs`（ミリ秒）で変更する
+-   **有効/無効**: 間隔・回数と同様に、自動クリックチェックボックスがONのときのみ有効
+    （`auto_click_checkbox_handler.rs` の `update_auto_click_controls_state`）
+
+【AI解析用：依存関係】
+-   `auto_click.rs`: `AutoClicker::set_pause_on_user_input` と、`auto_click_loop` での一時停止
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    settings::{PAUSE_ON_USER_INPUT_VALUE_NAME, save_setting_dword},
+    strings,
+    system_utils::app_log,
+};
+
+/// ユーザー操作中の一時停止チェックボックスを初期化する
+///
+/// `AutoClicker` の現在の設定をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_pause_on_user_input_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref()
+        .auto_clicker
+        .is_pause_on_user_input();
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_PAUSE_ON_USER_INPUT_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// ユーザー操作中の一時停止チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AutoClicker` に保存します。
+/// 変更は次回の自動クリックの開始から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_pause_on_user_input_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_PAUSE_ON_USER_INPUT_CHECKBOX) } == BST_CHECKED.0;
+
+    let auto_clicker = &mut AppState::get_app_state_mut().auto_clicker;
+    auto_clicker.set_pause_on_user_input(is_checked);
+    save_setting_dword(PAUSE_ON_USER_INPUT_VALUE_NAME, is_checked as u32);
+    app_log(&strings::pause_on_user_input_changed(
+        is_checked,
+        auto_clicker.get_user_input_quiet_ms(),
+    ));
+}
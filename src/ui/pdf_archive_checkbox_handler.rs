@@ -0,0 +1,78 @@
+/*
+============================================================================
+PDF/Aチェックボックスハンドラモジュール (pdf_archive_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+PDF変換の出力を、長期保存向けのPDF/A-1b形式にするかどうかを切り替える
+「アーカイブ用 (PDF/A)」チェックボックスを管理するモジュール。
+文書管理システムなど、PDF/Aでの提出を求められる保存先に変換結果をそのまま登録できるようにします。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_pdf_archive_checkbox`
+    -   `AppState.pdf_archive` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_pdf_archive_checkbox_change`
+    -   チェック状態を `AppState.pdf_archive` に反映し、レジストリに保存
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **永続化**: `settings::save_setting_dword`（`PdfArchive`）で次回起動時の初期値にする
+-   **出力内容**: PDF 1.4、sRGBの出力インテント・XMPメタデータ・文書IDを追加（`pdf_archive.rs`）。
+    埋め込みフォントを使わない撮影日時フッターはPDF/A-1で使用できないため省略される
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `pdf_archive` フィールドの保持
+-   `export_pdf.rs`: `pdf_archive` に基づく `PdfConformance` の選択
+-   `settings.rs`: 設定値のレジストリへの保存
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    settings::{PDF_ARCHIVE_VALUE_NAME, save_setting_dword},
+    strings,
+    system_utils::app_log,
+};
+
+/// PDF/Aチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_pdf_archive_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().pdf_archive;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_PDF_ARCHIVE_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// PDF/Aチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.pdf_archive` に保存します。
+/// 変更は次回のPDF変換から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_pdf_archive_checkbox_change(hwnd: HWND) {
+    let is_checked = unsafe { IsDlgButtonChecked(hwnd, IDC_PDF_ARCHIVE_CHECKBOX) } == BST_CHECKED.0;
+
+    AppState::get_app_state_mut().pdf_archive = is_checked;
+    save_setting_dword(PDF_ARCHIVE_VALUE_NAME, is_checked as u32);
+    app_log(strings::pdf_archive_changed(is_checked));
+}
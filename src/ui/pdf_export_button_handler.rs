@@ -4,13 +4,21 @@ PDF変換ボタンハンドラモジュール
 ============================================================================
 */
 
+use std::time::Instant;
+
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use crate::{
-    app_state::AppState,
-    export_pdf::export_selected_folder_to_pdf,
+    app_state::{AppMode, AppState},
+    export_pdf::{
+        PdfExportAction, capture_history_pdf_files, choose_pdf_export_action,
+        export_save_folder_to_pdf, run_pdf_export,
+    },
+    mode_effects::apply_mode_effects,
+    notification::notify_completion,
+    strings,
     system_utils::{app_log, show_message_box},
-    ui::input_control_handlers::update_input_control_states,
+    ui::folder_manager::browse_for_folder,
 };
 
 /// PDF変換ボタンのクリックイベントを処理する
@@ -19,61 +27,255 @@ use crate::{
 /// 処理中は、他のUI操作を無効化し、マウスカーソルを砂時計に変更して処理中であることを示します。
 ///
 /// # 処理フロー
-/// 1. `show_message_box` でユーザーに実行の意思を確認します。
-/// 2. ユーザーが「OK」をクリックした場合:
-///    a. `AppState` の `is_exporting_to_pdf` フラグを `true` に設定し、UIコントロールを無効化します。
+/// 1. `show_message_box` で変換元/出力先フォルダーを提示し、ユーザーに実行の意思を確認します。
+///    「いいえ」を選んだ場合は `select_pdf_export_folders` で変換元/出力先を選び直します。
+/// 2. ユーザーが「はい」または「いいえ」をクリックした場合は、`choose_pdf_export_action` で
+///    最新のPDFより後の画像を既存PDFに追記するか、新しく変換するか（以前の変換のPDFがあれば
+///    上書きするか枝番を付けて残すか）を確認し、続行する場合:
+///    a. `AppState` のモードを `AppMode::ExportingPdf` に遷移し、UIコントロールを無効化します。
 ///    b. マウスカーソルを砂時計（`IDC_WAIT`）に変更します。
-///    c. `export_selected_folder_to_pdf` を呼び出して変換処理を実行します。
-///    d. 処理完了後、カーソルを元に戻し、`is_exporting_to_pdf` フラグを `false` にしてUIを再度有効化します。
-///    e. 処理結果（成功または失敗）をメッセージボックスでユーザーに通知します。
+///    c. `run_pdf_export` を呼び出して変換（または追記）処理を実行します。
+///    d. 処理完了後、カーソルを元に戻し、モードを `AppMode::Idle` に戻してUIを再度有効化します。
+///    e. 処理結果（成功または失敗）をメッセージボックスでユーザーに通知します。成功時は「完了を通知」が有効であれば、経過時間を通知領域のバルーンでも知らせます。
 /// 3. ユーザーが「キャンセル」をクリックした場合は、ログを出力して処理を中断します。
 pub fn handle_pdf_export_button() -> isize {
-    unsafe {
-        // 確認ダイアログを表示
-        let result = show_message_box(
-            "PDF変換を開始してもよろしいでしょうか？\n\n選択されたフォルダー内のJPEG画像を\nPDFファイルに変換します。",
-            "PDF変換確認",
-            MB_OKCANCEL | MB_ICONQUESTION,
-        );
+    // 確認ダイアログを表示（変換元/出力先を提示し、必要なら選び直す）
+    let (source_dir, output_dir) = get_pdf_export_folders();
+    let mut result = show_message_box(
+        &strings::pdf_export_confirm(&source_dir, &output_dir),
+        strings::pdf_export_confirm_title(),
+        MB_YESNOCANCEL | MB_ICONQUESTION,
+    );
 
-        if result.0 == IDOK.0 {
-            app_log("PDF変換を開始します...");
-
-            // カーソルを砂時計に変更
-            let wait_cursor = LoadCursorW(None, IDC_WAIT).unwrap_or_default();
-            let original_cursor = SetCursor(Some(wait_cursor));
-
-            // PDF変換実行（RAIIパターンでカーソー復元を保証）
-            let conversion_result = {
-                let app_state = AppState::get_app_state_mut();
-
-                app_state.is_exporting_to_pdf = true;
-                update_input_control_states();
-                let result = export_selected_folder_to_pdf();
-                app_state.is_exporting_to_pdf = false;
-                update_input_control_states();
-                SetCursor(Some(original_cursor));
-                result
-            };
-
-            // 結果処理
-            match conversion_result {
-                Err(e) => {
-                    app_log(&format!("PDF変換エラー: {}", e));
-                    let error_message = format!("PDF変換中にエラーが発生しました：\n\n{}", e);
-                    show_message_box(&error_message, "PDF変換エラー", MB_OK | MB_ICONERROR);
-                }
-                Ok(_) => {
-                    show_message_box(
-                        "PDF変換が正常に完了しました。",
-                        "PDF変換完了",
-                        MB_OK | MB_ICONINFORMATION,
-                    );
-                }
-            }
+    if result.0 == IDNO.0 {
+        result = if select_pdf_export_folders() {
+            IDYES
         } else {
-            app_log("PDF変換がキャンセルされました。");
+            IDCANCEL
+        };
+    }
+
+    // 既存PDFへの追記か、新しく変換するか（以前の変換のPDFを上書きするか枝番を付けて残すか）を確認する
+    let action = if result.0 == IDYES.0 {
+        choose_pdf_export_action()
+    } else {
+        None
+    };
+
+    if let Some(action) = action {
+        let is_append = matches!(action, PdfExportAction::Append { .. });
+        app_log("PDF変換を開始します...");
+
+        let started_at = Instant::now();
+        let conversion_result = run_in_pdf_export_mode(|| run_pdf_export(action));
+
+        // 結果処理
+        match conversion_result {
+            Err(e) => {
+                app_log(&strings::pdf_export_failed(&e));
+                show_message_box(
+                    &strings::pdf_export_failed_message(&e),
+                    strings::pdf_export_failed_title(),
+                    MB_OK | MB_ICONERROR,
+                );
+            }
+            Ok(_) => {
+                // メッセージボックスは閉じるまで待つため、先にバルーンで完了を通知する
+                notify_completion(
+                    strings::pdf_export_complete_notification_title(),
+                    &strings::pdf_export_complete_notification(started_at.elapsed()),
+                );
+                show_message_box(
+                    if is_append {
+                        "既存のPDFへの追記が正常に完了しました。"
+                    } else {
+                        "PDF変換が正常に完了しました。"
+                    },
+                    "PDF変換完了",
+                    MB_OK | MB_ICONINFORMATION,
+                );
+            }
         }
+    } else {
+        app_log("PDF変換がキャンセルされました。");
     }
     1
 }
+
+/// キャプチャモード終了時のPDF自動変換（`WM_AUTO_PDF_EXPORT`）を処理する
+///
+/// `screen_capture.rs` の `request_auto_pdf_export` から、このモード中に保存した画像がある場合に送られます。
+/// 確認ダイアログは表示せず、`export_save_folder_to_pdf` で保存先フォルダーを変換します。
+/// 変換中は「PDF変換」ボタンと同じく `AppMode::ExportingPdf` に遷移するため、新しいキャプチャは開始できません。
+/// 結果はログとメッセージボックス（成功時は「完了を通知」が有効であればバルーンも）で知らせます。
+pub fn handle_auto_pdf_export() {
+    // メッセージの処理までにキャプチャ・エリア選択が再開された場合は変換しない
+    if AppState::get_app_state_ref().mode != AppMode::Idle {
+        return;
+    }
+    app_log(&strings::auto_pdf_export_start(
+        AppState::get_app_state_ref().capture_session_saved_count,
+    ));
+
+    let started_at = Instant::now();
+    match run_in_pdf_export_mode(export_save_folder_to_pdf) {
+        Err(e) => {
+            app_log(&strings::pdf_export_failed(&e));
+            show_message_box(
+                &strings::pdf_export_failed_message(&e),
+                strings::pdf_export_failed_title(),
+                MB_OK | MB_ICONERROR,
+            );
+        }
+        Ok(()) => {
+            notify_completion(
+                strings::pdf_export_complete_notification_title(),
+                &strings::pdf_export_complete_notification(started_at.elapsed()),
+            );
+            show_message_box(
+                strings::auto_pdf_export_completed(),
+                strings::pdf_export_complete_notification_title(),
+                MB_OK | MB_ICONINFORMATION,
+            );
+        }
+    }
+}
+
+/// `AppMode::ExportingPdf` に遷移し、UIを無効化・カーソルを砂時計にしてPDF変換を実行する
+///
+/// 変換が終わるとカーソルを元に戻し、`AppMode::Idle` に戻してUIを再度有効化します。
+fn run_in_pdf_export_mode(
+    export: impl FnOnce() -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    unsafe {
+        // カーソルを砂時計に変更
+        let wait_cursor = LoadCursorW(None, IDC_WAIT).unwrap_or_default();
+        let original_cursor = SetCursor(Some(wait_cursor));
+
+        let app_state = AppState::get_app_state_mut();
+        let effects = app_state.mode.enter_pdf_export();
+        debug_assert!(effects.is_some(), "通常モード以外からPDF変換を開始しました");
+        if let Some(effects) = effects {
+            let _ = apply_mode_effects(effects);
+        }
+        let result = export();
+        if let Some(effects) = app_state.mode.exit_pdf_export() {
+            let _ = apply_mode_effects(effects);
+        }
+        SetCursor(Some(original_cursor));
+        result
+    }
+}
+
+/// 現在のPDF変換元/出力先フォルダーを表示用の文字列として取得する
+///
+/// `pdf_source_dir` / `pdf_output_dir` が未設定の場合は `selected_folder_path` を返します。
+/// 追加の変換元フォルダーがある場合は、連結する順に改行して列挙します。
+/// キャプチャ履歴の並び順で変換する場合は、変換元として履歴の件数を返します。
+fn get_pdf_export_folders() -> (String, String) {
+    let app_state = AppState::get_app_state_ref();
+    let default_dir = app_state
+        .selected_folder_path
+        .clone()
+        .unwrap_or_else(|| strings::pdf_folder_not_selected().to_string());
+
+    if let Some(history_files) = capture_history_pdf_files() {
+        let source = strings::pdf_history_source_label(history_files.len());
+        let output_dir = app_state.pdf_output_dir.clone().unwrap_or(default_dir);
+        return (source, output_dir);
+    }
+
+    let source_dir = std::iter::once(
+        app_state
+            .pdf_source_dir
+            .clone()
+            .unwrap_or_else(|| default_dir.clone()),
+    )
+    .chain(app_state.pdf_additional_source_dirs.iter().cloned())
+    .collect::<Vec<_>>()
+    .join("\n　　　→ ");
+    let output_dir = app_state.pdf_output_dir.clone().unwrap_or(default_dir);
+
+    (source_dir, output_dir)
+}
+
+/// PDF変換元/出力先フォルダーをフォルダー選択ダイアログで選び直す
+///
+/// 変換元 → 出力先の順にダイアログを表示し、選択されたパスを `AppState` の
+/// `pdf_source_dir` / `pdf_output_dir` に保存します。
+/// 変換元の選択後に「フォルダーを追加しますか？」と確認し、「はい」の間は追加の変換元を
+/// 選択できます（選択した順に連結され、`pdf_additional_source_dirs` に保存）。
+///
+/// # 戻り値
+/// * `true` - 両方のフォルダーが選択された場合（変換を続行）。
+/// * `false` - いずれかのダイアログがキャンセルされた場合（変換を中止、設定は変更しない）。
+fn select_pdf_export_folders() -> bool {
+    let app_state = AppState::get_app_state_mut();
+    let Some(dialog_hwnd) = app_state.dialog_hwnd else {
+        return false;
+    };
+
+    // 現在の変換元（未設定の場合は保存先）・出力先からダイアログを開く
+    let current_source_dir = app_state
+        .pdf_source_dir
+        .clone()
+        .or_else(|| app_state.selected_folder_path.clone());
+    let current_output_dir = app_state.pdf_output_dir.clone();
+
+    let Some(source_dir) = browse_for_folder(
+        *dialog_hwnd,
+        "PDFに変換するJPEG画像のフォルダーを選択してください",
+        current_source_dir.as_deref(),
+    ) else {
+        return false;
+    };
+
+    // 追加の変換元フォルダー（選択した順にPDFへ連結）
+    let mut additional_dirs: Vec<String> = Vec::new();
+    loop {
+        let message = format!(
+            "続けて別のフォルダーを追加しますか？\n（選択した順にページを連結し、1つのPDFにまとめます）\n\n現在の変換元:\n{}",
+            std::iter::once(&source_dir)
+                .chain(additional_dirs.iter())
+                .enumerate()
+                .map(|(i, dir)| format!("{}. {}", i + 1, dir))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        let result = show_message_box(
+            &message,
+            "PDF変換フォルダー追加",
+            MB_YESNO | MB_ICONQUESTION,
+        );
+        if result.0 != IDYES.0 {
+            break;
+        }
+        match browse_for_folder(
+            *dialog_hwnd,
+            "PDFに連結するJPEG画像のフォルダーを選択してください",
+            Some(additional_dirs.last().unwrap_or(&source_dir).as_str()),
+        ) {
+            Some(dir) => additional_dirs.push(dir),
+            None => break,
+        }
+    }
+
+    let Some(output_dir) = browse_for_folder(
+        *dialog_hwnd,
+        "PDFの出力先フォルダーを選択してください",
+        Some(current_output_dir.as_deref().unwrap_or(&source_dir)),
+    ) else {
+        return false;
+    };
+
+    app_log(&format!(
+        "PDF変換フォルダーを変更しました: 変換元 = {}, 追加 = {}件, 出力先 = {}",
+        source_dir,
+        additional_dirs.len(),
+        output_dir
+    ));
+    app_state.pdf_source_dir = Some(source_dir);
+    app_state.pdf_additional_source_dirs = additional_dirs;
+    app_state.pdf_output_dir = Some(output_dir);
+    true
+}
@@ -0,0 +1,96 @@
+/*
+============================================================================
+プログレッシブJPEGチェックボックスハンドラモジュール (progressive_jpeg_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+Web掲載向けに、通常のJPEG（4:2:0・標準の量子化テーブル）をプログレッシブ形式で保存する
+「プログレッシブJPEG」チェックボックスを管理するモジュール。
+プログレッシブJPEGは読み込み中に粗い全体像から段階的に表示されるため、
+ベースライン形式（上から順に表示）より読み込み中の見え方が良くなります。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_progressive_jpeg_checkbox`
+    -   `AppState.jpeg_progressive` の値をチェック状態に反映（デフォルト：OFF）
+    -   `progressive-jpeg` フィーチャー無効時はチェックボックスを非表示にする
+2.  **チェック状態変更処理**: `handle_progressive_jpeg_checkbox_change`
+    -   チェック状態を `AppState.jpeg_progressive` に即座に反映
+
+【技術仕様】
+-   **エンコード**: 有効時は `jpeg-encoder` クレート（`set_progressive(true)`）、
+    無効時は従来どおり `image` クレートの `JpegEncoder`（ベースライン）
+-   **高精細テキストモード**: 高精細テキストモードは常にプログレッシブのため、本設定の影響を受けない
+-   **対象**: 保存形式がJPEGの場合のみ（WebP・AVIF・BMPでは無視）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `jpeg_progressive` フィールドの保持
+-   `constants.rs`: `IDC_PROGRESSIVE_JPEG_CHECKBOX` コントロールID定義
+-   `capturer.rs`: `encode_jpeg` でのエンコーダーの切り替え
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::{
+        Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+        WindowsAndMessaging::{GetDlgItem, SW_HIDE, ShowWindow},
+    },
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// プログレッシブJPEGチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+/// `progressive-jpeg` フィーチャーなしでビルドした場合は、選択できないためチェックボックスを隠します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_progressive_jpeg_checkbox(hwnd: HWND) {
+    if !cfg!(feature = "progressive-jpeg") {
+        unsafe {
+            if let Ok(checkbox) = GetDlgItem(Some(hwnd), IDC_PROGRESSIVE_JPEG_CHECKBOX) {
+                let _ = ShowWindow(checkbox, SW_HIDE);
+            }
+        }
+        return;
+    }
+
+    let is_checked = AppState::get_app_state_ref().jpeg_progressive;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_PROGRESSIVE_JPEG_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// プログレッシブJPEGチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.jpeg_progressive` に保存します。
+/// 変更は次回のキャプチャから適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_progressive_jpeg_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_PROGRESSIVE_JPEG_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.jpeg_progressive = is_checked;
+
+    println!(
+        "プログレッシブJPEG設定変更: {}",
+        if is_checked {
+            "有効（jpeg-encoder・プログレッシブ）"
+        } else {
+            "無効（ベースライン）"
+        }
+    );
+}
@@ -158,7 +158,7 @@ pub fn initialize_quality_combo(hwnd: HWND) {
 /// 1. **コントロール取得**: `GetDlgItem`で品質コンボボックスのハンドル取得
 /// 2. **選択取得**: `CB_GETCURSEL`で現在選択されている項目のインデックス取得
 /// 3. **データ取得**: `CB_GETITEMDATA`で選択項目に関連付けられた品質値取得
-/// 4. **状態更新**: 取得した品質値をAppStateの`jpeg_quality`フィールドに保存
+/// 4. **状態更新**: 取得した品質値をAppStateの`jpeg_quality`フィールドに保存（範囲外の値は70%〜100%に補正）
 /// 5. **ログ出力**: 設定変更をデバッグコンソールに記録
 ///
 /// # データフロー
@@ -208,11 +208,12 @@ pub fn handle_quality_combo_change(hwnd: HWND) {
             // アプリケーション状態に品質設定を即座に反映
             // get_app_state_mut()：グローバル状態への書き込み可能参照取得
             let app_state = AppState::get_app_state_mut();
-            app_state.jpeg_quality = quality_value;
+            // 範囲外の値（70%未満・100%超）は有効範囲に補正
+            app_state.set_jpeg_quality(quality_value);
 
             // 設定変更をデバッグコンソールに記録
             // 開発時のトラブルシューティングやユーザーフィードバック確認用
-            println!("JPEG品質設定変更: {}%", quality_value);
+            println!("JPEG品質設定変更: {}%", app_state.jpeg_quality);
         }
     }
 }
\ No newline at end of file
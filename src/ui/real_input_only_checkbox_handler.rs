@@ -0,0 +1,84 @@
+/*
+============================================================================
+実入力限定チェックボックスハンドラモジュール (real_input_only_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+他のツールが `SendInput` 等で注入したクリック・キー入力を、キャプチャトリガーや
+ESCキーによるモード終了として扱うかどうかを切り替える「実入力のみ」チェックボックスを
+管理するモジュール。自動クリックの合成クリックは設定に関係なく常にトリガーとなります。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_real_input_only_checkbox`
+    -   `AppState.injected_input_policy` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_real_input_only_checkbox_change`
+    -   チェック状態を `AppState.injected_input_policy` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **判定処理**: `hook.rs` の `is_trigger_input_allowed`（`LLMHF_INJECTED` / `LLKHF_INJECTED`、`dwExtraInfo` タグ）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `injected_input_policy` フィールドの保持
+-   `constants.rs`: `IDC_REAL_INPUT_ONLY_CHECKBOX` コントロールID定義
+-   `hook/mouse.rs`, `hook/keyboard.rs`: ポリシーに基づくトリガー判定
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, constants::*, hook::InjectedInputPolicy};
+
+/// 実入力限定チェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_real_input_only_checkbox(hwnd: HWND) {
+    let is_checked =
+        AppState::get_app_state_ref().injected_input_policy == InjectedInputPolicy::RealInputOnly;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_REAL_INPUT_ONLY_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// 実入力限定チェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.injected_input_policy` に保存します。
+/// 変更は次のクリック・キー入力から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_real_input_only_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_REAL_INPUT_ONLY_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.injected_input_policy = if is_checked {
+        InjectedInputPolicy::RealInputOnly
+    } else {
+        InjectedInputPolicy::AllowInjected
+    };
+
+    println!(
+        "注入入力ポリシー変更: {}",
+        if is_checked {
+            "実入力のみ（注入入力を無視）"
+        } else {
+            "注入入力も許可"
+        }
+    );
+}
@@ -0,0 +1,109 @@
+/*
+============================================================================
+連番整理ボタンハンドラモジュール (renumber_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャ中に失敗したコマを削除するなどして連番に欠番ができた保存フォルダーを、
+「連番整理」ボタンで `0001` から欠番なく振り直すモジュール。
+並び順はPDF変換と同じ（連番の数値順）のまま変えず、次に保存する連番も振り直し後の続きにします。
+
+【主要機能】
+1.  **連番の振り直し**: `handle_renumber_button`
+    -   保存フォルダーの連番ファイルを収集し、名前が変わるファイルの件数を確認ダイアログで表示
+    -   確認後に一時的な名前を経由して名前を変更（`rename_capture_files`、失敗時は元に戻す）
+    -   `capture_file_counter` を「ファイル数 + 1」に設定
+2.  **パスの追従**: `apply_renamed_paths`
+    -   キャプチャ履歴とサムネイルストリップのファイルパスを変更後の名前に更新
+
+【技術仕様】
+-   **対象**: 数字のみのファイル名の画像（JPEG / WebP / AVIF / BMP / PNG）。別名のファイルは変更しない
+-   **有効/無効**: 通常モード時のみ有効（ZIP圧縮中はファイルを読み込んでいるため無効）
+
+【AI解析用：依存関係】
+-   `system_utils.rs`: 連番ファイルの収集・名前の変更
+-   `clickcapture::capture_naming`: 変更内容の計算（`plan_capture_file_renumbering`）
+-   `screen_capture.rs`: 保存先フォルダーの取得（`get_save_dir_path`）
+-   `app_state.rs`: `capture_file_counter`、`capture_history`、`capture_thumbnails` の更新
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use std::{collections::HashMap, path::PathBuf};
+
+use windows::Win32::UI::WindowsAndMessaging::{IDYES, MB_ICONQUESTION, MB_YESNO};
+
+use clickcapture::capture_naming::plan_capture_file_renumbering;
+
+use crate::{
+    app_state::AppState,
+    screen_capture::get_save_dir_path,
+    strings,
+    system_utils::{
+        app_log, collect_numbered_capture_files, rename_capture_files, show_message_box,
+    },
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 連番整理ボタンのクリックイベントを処理する
+///
+/// 名前を変更するファイルがある場合は確認ダイアログを表示し、「はい」の場合のみ実行します。
+/// 欠番がない場合も、最後のファイルを削除していれば次の連番を詰めます。
+pub fn handle_renumber_button() {
+    let save_dir_path = get_save_dir_path();
+    let files = match collect_numbered_capture_files(std::path::Path::new(&save_dir_path)) {
+        Ok(files) => files,
+        Err(e) => {
+            app_log(&strings::renumber_failed(&e));
+            return;
+        }
+    };
+    let renames = plan_capture_file_renumbering(&files);
+
+    if renames.is_empty() {
+        app_log(&strings::renumber_not_needed(files.len()));
+    } else {
+        let result = show_message_box(
+            &strings::renumber_confirm(files.len(), renames.len()),
+            strings::renumber_confirm_title(),
+            MB_YESNO | MB_ICONQUESTION,
+        );
+        if result.0 != IDYES.0 {
+            return;
+        }
+
+        if let Err(e) = rename_capture_files(&renames) {
+            app_log(&strings::renumber_failed(&e));
+            return;
+        }
+        apply_renamed_paths(&renames);
+        app_log(&strings::renumber_completed(renames.len()));
+    }
+
+    // 次の保存は振り直し後の最後の番号の続きから
+    let app_state = AppState::get_app_state_mut();
+    let next_counter = files.len() as u32 + 1;
+    if app_state.capture_file_counter != next_counter {
+        app_state.capture_file_counter = next_counter;
+        app_log(&strings::capture_counter_set(next_counter));
+    }
+
+    update_input_control_states();
+}
+
+/// キャプチャ履歴とサムネイルストリップのファイルパスを、変更後の名前に更新する
+fn apply_renamed_paths(renames: &[(PathBuf, PathBuf)]) {
+    let renamed: HashMap<&PathBuf, &PathBuf> =
+        renames.iter().map(|(from, to)| (from, to)).collect();
+    let app_state = AppState::get_app_state_mut();
+
+    for path in app_state.capture_history.iter_mut() {
+        if let Some(&to) = renamed.get(&*path) {
+            *path = to.clone();
+        }
+    }
+    for thumbnail in app_state.capture_thumbnails.iter_mut() {
+        if let Some(&to) = renamed.get(&thumbnail.file_path) {
+            thumbnail.file_path = to.clone();
+        }
+    }
+}
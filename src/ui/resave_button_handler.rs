@@ -0,0 +1,40 @@
+/*
+============================================================================
+再保存ボタンハンドラモジュール (resave_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+保存先フォルダーの一時的なロック（OneDrive同期など）で再試行しても保存できなかった
+キャプチャを、「再保存」ボタンで再度保存するモジュール。
+画面は既に変わっているため再キャプチャはせず、保持しているエンコード済みデータを書き込みます。
+
+【主要機能】
+1.  **再保存処理**: `handle_resave_button`
+    -   `retry_failed_capture_save` で保持中のデータを保存
+    -   成功・失敗をログに出力し、ボタンの有効状態を更新
+
+【技術仕様】
+-   **有効/無効**: `last_failed_capture` を保持している通常モード時のみ有効
+    （`input_control_handlers.rs` の `update_input_control_states`）
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`: `retry_failed_capture_save`（保存処理本体）
+-   `app_state.rs`: `last_failed_capture` の保持
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use crate::{
+    screen_capture::retry_failed_capture_save, system_utils::app_log,
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 再保存ボタンのクリックイベントを処理する
+///
+/// 保存に失敗したキャプチャを再度保存します。失敗した場合はデータを保持したままにするため、
+/// 保存先の状態が回復した後に再度実行できます。
+pub fn handle_resave_button() {
+    if let Err(e) = retry_failed_capture_save() {
+        app_log(&format!("❌ 再保存に失敗しました: {}", e));
+    }
+    update_input_control_states();
+}
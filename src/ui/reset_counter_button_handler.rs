@@ -0,0 +1,83 @@
+/*
+============================================================================
+連番リセットボタンハンドラモジュール (reset_counter_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+撮り終えたキャプチャを保存フォルダーから移動した後に、アプリを再起動せずに
+次のキャプチャを `0001` から保存できるよう、「連番リセット」ボタンで
+`capture_file_counter` を1に戻すモジュール。
+
+【主要機能】
+1.  **連番のリセット**: `handle_reset_counter_button`
+    -   保存フォルダーに連番ファイルが残っている場合は、リセットせずに理由を表示
+    -   残っていない場合は確認ダイアログの後に `capture_file_counter` を1に設定し、ログに出力
+
+【技術仕様】
+-   **上書き防止**: 保存時は既存ファイルの最大の番号の次から保存する（`create_next_capture_file`）ため、
+    連番ファイルが残っているとリセットしても `0001` にはならない。既存ファイルを上書きする手段は設けない
+-   **永続化なし**: 連番は起動時に1から始まり、レジストリには保存していないため、リセットも保存しない
+-   **有効/無効**: 通常モード時のみ有効
+
+【AI解析用：依存関係】
+-   `system_utils.rs`: `max_capture_file_number`（保存フォルダーの連番ファイルの確認）
+-   `screen_capture.rs`: 保存先フォルダーの取得（`get_save_dir_path`）
+-   `app_state.rs`: `capture_file_counter` の更新
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use std::path::Path;
+
+use windows::Win32::UI::WindowsAndMessaging::{
+    IDYES, MB_ICONQUESTION, MB_ICONWARNING, MB_OK, MB_YESNO,
+};
+
+use crate::{
+    app_state::AppState,
+    screen_capture::get_save_dir_path,
+    strings,
+    system_utils::{app_log, max_capture_file_number, show_message_box},
+};
+
+/// 連番リセットボタンのクリックイベントを処理する
+///
+/// 保存フォルダーに連番ファイルが残っている場合は中止し、ファイルを移動してから
+/// 実行するよう案内します。フォルダーがまだ作成されていない場合は空として扱います。
+pub fn handle_reset_counter_button() {
+    let save_dir_path = get_save_dir_path();
+    let save_dir = Path::new(&save_dir_path);
+
+    let max_existing = if save_dir.exists() {
+        match max_capture_file_number(save_dir) {
+            Ok(max_existing) => max_existing,
+            Err(e) => {
+                app_log(&strings::reset_counter_failed(&e));
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    if let Some(max_existing) = max_existing {
+        app_log(&strings::reset_counter_files_remain_log(max_existing));
+        show_message_box(
+            &strings::reset_counter_files_remain_message(max_existing),
+            strings::reset_counter_title(),
+            MB_OK | MB_ICONWARNING,
+        );
+        return;
+    }
+
+    let result = show_message_box(
+        strings::reset_counter_confirm(),
+        strings::reset_counter_title(),
+        MB_YESNO | MB_ICONQUESTION,
+    );
+    if result.0 != IDYES.0 {
+        return;
+    }
+
+    AppState::get_app_state_mut().capture_file_counter = 1;
+    app_log(&strings::capture_counter_set(1));
+}
@@ -9,11 +9,15 @@ use windows::Win32::{
     UI::WindowsAndMessaging::*,
 };
 
-use crate::{app_state::AppState, constants::*};
+use crate::{app_state::AppState, constants::*, ui::target_width_edit_handler::*};
 
-/// スケールコンボボックスを初期化（100%〜55%、5%刻み）
+/// 「幅指定」項目に関連付けるデータ値（スケール値としては使用されない0を割り当て）
+const SCALE_ITEM_FIT_TO_WIDTH: u8 = 0;
+
+/// スケールコンボボックスを初期化（100%〜25%、5%刻み＋幅指定）
 ///
-/// キャプチャ画像の縮小率を設定するコンボボックスに、55%から100%までの選択肢を5%刻みで追加します。
+/// キャプチャ画像の縮小率を設定するコンボボックスに、25%から100%までの選択肢を5%刻みで追加し、
+/// 末尾に出力幅を直接指定する「幅指定」項目を追加します。
 /// デフォルト値として、画質とファイルサイズのバランスが良い65%を選択状態にします。
 ///
 /// # 引数
@@ -24,11 +28,18 @@ use crate::{app_state::AppState, constants::*};
 /// - `CB_SETCURSEL` でデフォルトの項目を選択します。`AppState` の `capture_scale_factor` のデフォルト値と一致させます。
 pub fn initialize_scale_combo(hwnd: HWND) {
     if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_SCALE_COMBO) } {
-        // 55%から100%まで5%刻みで項目を追加
-        let scales: Vec<u8> = (55..=100).step_by(5).collect();
+        // 25%から100%まで5%刻みで項目を追加し、末尾に「幅指定」を追加
+        let scales: Vec<u8> = (25..=100).step_by(5).collect();
+        let items = scales
+            .iter()
+            .rev()
+            .map(|&scale| (format!("{}%\0", scale), scale))
+            .chain(std::iter::once((
+                "幅指定\0".to_string(),
+                SCALE_ITEM_FIT_TO_WIDTH,
+            )));
 
-        for &scale in scales.iter().rev() {
-            let text = format!("{}%\0", scale);
+        for (text, scale) in items {
             let wide_text: Vec<u16> = text.encode_utf16().collect();
             let index = unsafe {
                 SendMessageW(
@@ -72,7 +83,8 @@ pub fn initialize_scale_combo(hwnd: HWND) {
 /// # 処理内容
 /// 1. `CB_GETCURSEL` で選択された項目のインデックスを取得します。
 /// 2. `CB_GETITEMDATA` でその項目に関連付けられたスケール値（`u8`）を取得します。
-/// 3. 取得した値を `AppState` の `capture_scale_factor` フィールドに保存します。
+/// 3. 取得した値を `AppState` の `capture_scale_factor` フィールドに保存します（範囲外の値は25%〜100%に補正）。
+///    「幅指定」項目の場合は `capture_fit_to_width` を有効にし、出力幅エディットボックスを有効化します。
 pub fn handle_scale_combo_change(hwnd: HWND) {
     if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_SCALE_COMBO) } {
         // 現在選択されているインデックスを取得
@@ -94,9 +106,20 @@ pub fn handle_scale_combo_change(hwnd: HWND) {
 
             // AppStateに保存
             let app_state = AppState::get_app_state_mut();
-            app_state.capture_scale_factor = scale_value as u8;
+            if scale_value == SCALE_ITEM_FIT_TO_WIDTH {
+                app_state.capture_fit_to_width = true;
+                println!(
+                    "スケール設定変更: 幅指定 {}px",
+                    app_state.capture_target_width
+                );
+            } else {
+                app_state.capture_fit_to_width = false;
+                app_state.set_capture_scale_factor(scale_value);
+                println!("スケール設定変更: {}%", app_state.capture_scale_factor);
+            }
 
-            println!("スケール設定変更: {}%", scale_value);
+            // 幅指定モードの切り替えに合わせて出力幅エディットボックスの有効状態を更新
+            update_target_width_edit_state(hwnd);
         }
     }
 }
@@ -0,0 +1,321 @@
+/*
+============================================================================
+定期キャプチャUIハンドラモジュール (schedule_handler.rs)
+============================================================================
+
+【ファイル概要】
+定期キャプチャ（N分ごとに選択エリアを自動キャプチャ）の開始/停止ボタン、
+間隔コンボボックス、最大回数エディットボックスを管理するモジュール。
+バックグラウンドスレッドから届くキャプチャ要求・終了通知の処理もここで行います。
+
+【主要機能】
+1.  **コントロール初期化**: `initialize_schedule_controls`
+    -   間隔（1/2/5/10/15/30/60分、デフォルト5分）と最大回数（デフォルト0：無制限）を表示
+2.  **設定変更処理**: `handle_schedule_interval_combo_change`, `handle_schedule_count_edit_change`
+3.  **開始/停止**: `handle_schedule_button`（ボタン表示を「定期開始」⇔「定期停止」で切り替え）
+4.  **スレッドからの通知処理**: `handle_scheduled_capture_tick`, `finish_scheduled_capture`
+5.  **経過時間オーバーレイ**: 実行中は `capturing_overlay` に経過時間とキャプチャ枚数を表示し、
+    `handle_elapsed_overlay_timer` で1秒ごとに再描画
+
+【技術仕様】
+-   **キャプチャ実行**: メインスレッドで `capture_screen_area_with_counter()` を呼び出し（入力の注入なし）
+-   **競合回避**: エリア選択中・キャプチャモード中・PDF変換中に届いた要求はスキップ
+-   **停止条件**: 最大回数（0で無制限）、最大継続時間（24時間）、停止ボタン
+-   **経過時間表示**: `AppState.capture_overlay_shows_elapsed` を立て、`TIMER_ID_CAPTURE_OVERLAY_ELAPSED` で再描画
+
+【AI解析用：依存関係】
+-   `scheduled_capture.rs`: `ScheduledCapturer` のスレッド制御
+-   `app_state.rs`: `scheduled_capturer` フィールド、`selected_area`
+-   `constants.rs`: `IDC_SCHEDULE_*` コントロールID、`WM_SCHEDULED_CAPTURE*` メッセージ定義
+-   `screen_capture.rs`: 実際のキャプチャ処理
+-   `overlay/capturing_overlay.rs`: 経過時間ラベルの描画
+-   `dialog_handler.rs`: BN_CLICKED / CBN_SELCHANGE / EN_KILLFOCUS とカスタムメッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+use windows::core::w;
+
+use crate::{
+    app_state::{AppMode, AppState},
+    capture_timing::dump_capture_timing_stats,
+    constants::*,
+    overlay::Overlay,
+    screen_capture::{CaptureOutcome, capture_screen_area_with_counter},
+    sound::{SoundEffect, play_sound},
+    system_utils::app_log,
+    ui::{
+        dialog_handler::{bring_dialog_to_back, bring_dialog_to_front},
+        input_control_handlers::update_input_control_states,
+    },
+};
+
+/// 選択可能なキャプチャ間隔（分）
+const SCHEDULE_INTERVALS_MINUTES: [u32; 7] = [1, 2, 5, 10, 15, 30, 60];
+
+/// 経過時間オーバーレイの再描画間隔（ミリ秒）
+const ELAPSED_OVERLAY_INTERVAL_MS: u32 = 1000;
+
+/// 定期キャプチャ関連コントロールを初期化する
+///
+/// 間隔コンボボックスに選択肢を追加し、`ScheduledCapturer` の現在値を
+/// コンボボックスと回数エディットボックスに反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_schedule_controls(hwnd: HWND) {
+    let scheduled_capturer = &AppState::get_app_state_ref().scheduled_capturer;
+
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_SCHEDULE_INTERVAL_COMBO) } {
+        let mut default_index = 0;
+
+        for &minutes in SCHEDULE_INTERVALS_MINUTES.iter() {
+            let text = format!("{}分\0", minutes);
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+
+            // CB_ADDSTRING：コンボボックスに表示テキストを追加
+            let index = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(wide_text.as_ptr() as isize)),
+                )
+            }
+            .0 as usize;
+
+            // CB_SETITEMDATA：表示テキストと間隔（分）を関連付け
+            unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_SETITEMDATA,
+                    Some(WPARAM(index)),
+                    Some(LPARAM(minutes as isize)),
+                );
+            }
+
+            if minutes == scheduled_capturer.get_interval_minutes() {
+                default_index = index;
+            }
+        }
+
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
+        }
+    }
+
+    unsafe {
+        let _ = SetDlgItemInt(
+            hwnd,
+            IDC_SCHEDULE_COUNT_EDIT,
+            scheduled_capturer.get_max_count(),
+            false,
+        );
+    }
+}
+
+/// 定期キャプチャ間隔コンボボックスの選択変更を処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_schedule_interval_combo_change(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_SCHEDULE_INTERVAL_COMBO) } {
+        // CB_GETCURSEL：現在選択されている項目のインデックス取得
+        let selected_index =
+            unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 }
+                as i32;
+
+        if selected_index >= 0 {
+            // CB_GETITEMDATA：選択項目に関連付けられた間隔（分）を取得
+            let minutes = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_GETITEMDATA,
+                    Some(WPARAM(selected_index as usize)),
+                    Some(LPARAM(0)),
+                )
+            }
+            .0 as u32;
+
+            let app_state = AppState::get_app_state_mut();
+            app_state.scheduled_capturer.set_interval_minutes(minutes);
+            println!("定期キャプチャ間隔設定変更: {}分", minutes);
+        }
+    }
+}
+
+/// 定期キャプチャ回数エディットボックスの変更を処理する
+///
+/// フォーカスが外れた（`EN_KILLFOCUS`）際に入力値を数値に変換し、最大キャプチャ回数として保存します。
+/// 空欄や数値以外の場合は現在値を表示し直します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_schedule_count_edit_change(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+
+    if let Ok(edit_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_SCHEDULE_COUNT_EDIT) } {
+        let mut buffer: [u16; 16] = [0; 16];
+        let text_length = unsafe { GetWindowTextW(edit_hwnd, &mut buffer) };
+        let text = String::from_utf16_lossy(&buffer[..text_length as usize]);
+
+        if let Ok(count) = text.trim().parse::<u32>() {
+            app_state.scheduled_capturer.set_max_count(count);
+            println!("定期キャプチャ回数設定変更: {}", count);
+        }
+    }
+
+    unsafe {
+        let _ = SetDlgItemInt(
+            hwnd,
+            IDC_SCHEDULE_COUNT_EDIT,
+            app_state.scheduled_capturer.get_max_count(),
+            false,
+        );
+    }
+}
+
+/// 定期キャプチャの開始/停止ボタンのクリックを処理する
+///
+/// 実行中であれば停止し、停止中であれば選択エリアを確認したうえで開始します。
+/// 開始時はダイアログを最小化し、キャプチャ対象の画面を覆わないようにします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_schedule_button(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+
+    if app_state.scheduled_capturer.is_running() {
+        finish_scheduled_capture(hwnd);
+        return;
+    }
+
+    if app_state.selected_area.is_none() {
+        app_log("❌ 先にエリア選択を行ってください");
+        return;
+    }
+
+    if let Err(e) = app_state.scheduled_capturer.start() {
+        app_log(&format!("❌ 定期キャプチャの開始に失敗: {}", e));
+        return;
+    }
+    // 変化の検出は、この定期キャプチャで最初に保存した画像から比較する
+    app_state.previous_capture_frame = None;
+    // 経過時間スタンプも、この定期キャプチャで最初に保存した画像から数える
+    app_state.last_capture_started_at = None;
+
+    unsafe {
+        let _ = SetDlgItemTextW(hwnd, IDC_SCHEDULE_BUTTON, w!("定期停止"));
+    }
+    show_elapsed_overlay(hwnd);
+    update_input_control_states();
+    bring_dialog_to_back();
+}
+
+/// 定期キャプチャスレッドからのキャプチャ要求（`WM_SCHEDULED_CAPTURE`）を処理する
+///
+/// # 引数
+/// * `count` - 開始からのキャプチャ要求回数
+pub fn handle_scheduled_capture_tick(count: u32) {
+    let app_state = AppState::get_app_state_ref();
+
+    // 他の操作中はキャプチャ結果が乱れるためスキップ
+    if app_state.mode != AppMode::Idle {
+        app_log(&format!(
+            "⏭️ 定期キャプチャ {}回目: 他の操作中のためスキップしました",
+            count
+        ));
+        return;
+    }
+
+    app_log(&format!("⏰ 定期キャプチャ {}回目", count));
+    match capture_screen_area_with_counter() {
+        Ok(CaptureOutcome::Saved) => AppState::get_app_state_mut()
+            .scheduled_capturer
+            .record_captured_frame(),
+        Ok(CaptureOutcome::Unchanged) => {}
+        Err(e) => app_log(&format!("❌ 定期キャプチャに失敗: {}", e)),
+    }
+}
+
+/// 定期キャプチャを終了し、UIを通常状態に戻す
+///
+/// 停止ボタン押下時と、停止条件到達（`WM_SCHEDULED_CAPTURE_COMPLETE`）時に呼び出されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn finish_scheduled_capture(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+    if !app_state.scheduled_capturer.is_running() {
+        return;
+    }
+
+    app_state.scheduled_capturer.stop();
+    hide_elapsed_overlay(hwnd);
+    play_sound(SoundEffect::Complete);
+    dump_capture_timing_stats();
+
+    unsafe {
+        let _ = SetDlgItemTextW(hwnd, IDC_SCHEDULE_BUTTON, w!("定期開始"));
+    }
+    update_input_control_states();
+    bring_dialog_to_front();
+}
+
+/// 経過時間オーバーレイの再描画タイマー（`TIMER_ID_CAPTURE_OVERLAY_ELAPSED`）を処理する
+///
+/// # 引数
+/// * `hwnd` - メインダイアログのハンドル（タイマーの所有者）
+pub fn handle_elapsed_overlay_timer(hwnd: HWND) {
+    let app_state = AppState::get_app_state_ref();
+
+    match app_state.capturing_overlay.as_ref() {
+        Some(overlay) if app_state.capture_overlay_shows_elapsed => overlay.refresh_overlay(),
+        _ => unsafe {
+            let _ = KillTimer(Some(hwnd), TIMER_ID_CAPTURE_OVERLAY_ELAPSED);
+        },
+    }
+}
+
+/// キャプチャオーバーレイを経過時間表示モードで表示し、再描画タイマーを開始する
+fn show_elapsed_overlay(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.capture_overlay_shows_elapsed = true;
+
+    let show_result = match app_state.capturing_overlay.as_mut() {
+        Some(overlay) => overlay.show_overlay(),
+        None => Ok(()),
+    };
+    if let Err(e) = show_result {
+        eprintln!("❌ 経過時間オーバーレイの表示に失敗: {:?}", e);
+    }
+
+    unsafe {
+        SetTimer(
+            Some(hwnd),
+            TIMER_ID_CAPTURE_OVERLAY_ELAPSED,
+            ELAPSED_OVERLAY_INTERVAL_MS,
+            None,
+        );
+    }
+}
+
+/// 経過時間表示モードを終了し、キャプチャオーバーレイを非表示にする
+fn hide_elapsed_overlay(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.capture_overlay_shows_elapsed = false;
+
+    unsafe {
+        let _ = KillTimer(Some(hwnd), TIMER_ID_CAPTURE_OVERLAY_ELAPSED);
+    }
+    if let Some(overlay) = app_state.capturing_overlay.as_ref() {
+        overlay.hide_overlay();
+    }
+}
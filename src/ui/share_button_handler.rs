@@ -0,0 +1,148 @@
+/*
+============================================================================
+共有ボタンハンドラモジュール (share_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+最後に保存したキャプチャを、Windowsの共有UI（共有ウィンドウ）でTeams・メールなどに送る
+「共有」ボタンを管理するモジュール。保存先フォルダーを開いてファイルをドラッグする手間を省きます。
+
+【主要機能】
+1.  **共有UIの対応判定**: `initialize_share_button`
+    -   ダイアログ初期化時に共有UIを使用できるかを1回だけ判定し、`AppState.share_ui_supported` に保持
+    -   使用できない環境（Windows Serverなど）ではボタンを常に無効にし、クリック時の失敗を防ぐ
+2.  **共有処理**: `handle_share_button`
+    -   `last_saved_file` を `StorageFile` として `DataRequested` イベントで渡し、共有UIを表示
+    -   共有UIを表示できなかった場合は、エクスプローラーでファイルを選択した状態で表示（送る・ドラッグ用）
+
+【技術仕様】
+-   **WinRT相互運用**: `IDataTransferManagerInterop`（`GetForWindow` / `ShowShareUIForWindow`）で
+    デスクトップアプリのウィンドウに `DataTransferManager` を関連付ける
+-   **対応判定**: `DataTransferManager::IsSupported`（Windows 10 2004以降）。判定APIがない古いWindowsでは、
+    相互運用インターフェースを取得できるかで判定する
+-   **ハンドラーの登録**: 共有のたびに前回の `DataRequested` ハンドラーを外してから登録する
+    （`share_request_token`）。ファイルの読み取り専用の `IStorageItem` として渡す
+-   **有効/無効**: 共有UIを使用でき、保存済みのキャプチャがある通常モード時のみ有効
+    （`input_control_handlers.rs` の `update_input_control_states`）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `last_saved_file`・`share_ui_supported`・`share_request_token` の保持
+-   `capture_history_handler.rs`: 保存成功時に `last_saved_file` を更新（`add_capture_history`）
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use std::path::Path;
+
+use windows::{
+    ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager},
+    Foundation::TypedEventHandler,
+    Storage::{IStorageItem, StorageFile},
+    Win32::{
+        Foundation::HWND,
+        UI::{
+            Shell::{IDataTransferManagerInterop, ShellExecuteW},
+            WindowsAndMessaging::SW_SHOWNORMAL,
+        },
+    },
+    core::{AgileReference, HSTRING, Interface, PCWSTR, factory, w},
+};
+use windows_collections::IIterable;
+
+use crate::{
+    app_state::AppState, strings, system_utils::app_log,
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 共有UIを使用できるかを判定し、「共有」ボタンの有効状態に反映する
+///
+/// ダイアログ初期化時に1回だけ呼び出します（判定結果は起動中に変わらないため）。
+pub fn initialize_share_button() {
+    let supported = DataTransferManager::IsSupported()
+        .unwrap_or_else(|_| factory::<DataTransferManager, IDataTransferManagerInterop>().is_ok());
+
+    AppState::get_app_state_mut().share_ui_supported = supported;
+    if !supported {
+        app_log(strings::share_ui_unsupported());
+    }
+    update_input_control_states();
+}
+
+/// 「共有」ボタンのクリックイベントを処理する
+///
+/// 最後に保存したキャプチャを共有UIで送ります。ファイルが削除されている場合はログのみ出力します。
+/// 共有UIを表示できなかった場合は、エクスプローラーでファイルを選択した状態で表示します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_share_button(hwnd: HWND) {
+    let Some(file_path) = AppState::get_app_state_ref().last_saved_file.clone() else {
+        return;
+    };
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if !file_path.exists() {
+        app_log(&strings::share_file_missing(&file_name));
+        return;
+    }
+
+    match show_share_ui(hwnd, &file_path, &file_name) {
+        Ok(()) => app_log(&strings::share_ui_opened(&file_name)),
+        Err(e) => {
+            app_log(&strings::share_ui_failed(&e));
+            reveal_in_explorer(hwnd, &file_path);
+        }
+    }
+}
+
+/// ファイルを `DataRequested` イベントで渡すよう登録し、共有UIを表示する
+fn show_share_ui(hwnd: HWND, file_path: &Path, file_name: &str) -> windows::core::Result<()> {
+    // 共有先の選択後ではなく表示前にファイルを開き、見つからない場合はここで失敗させる
+    let file = StorageFile::GetFileFromPathAsync(&HSTRING::from(file_path))?.join()?;
+    // イベントは別スレッドで届くことがあるため、スレッド間で渡せる参照にしておく
+    let item = AgileReference::new(&file.cast::<IStorageItem>()?)?;
+    let title = HSTRING::from(file_name);
+
+    let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+    // 前回の共有で登録したハンドラーを外す（同じウィンドウには同じマネージャーが返される）
+    let app_state = AppState::get_app_state_mut();
+    if let Some(token) = app_state.share_request_token.take() {
+        let _ = manager.RemoveDataRequested(token);
+    }
+
+    let handler =
+        TypedEventHandler::<DataTransferManager, DataRequestedEventArgs>::new(move |_, args| {
+            let data = args.ok()?.Request()?.Data()?;
+            data.Properties()?.SetTitle(&title)?;
+            let items: IIterable<IStorageItem> = vec![Some(item.resolve()?)].into();
+            data.SetStorageItemsReadOnly(&items)
+        });
+    app_state.share_request_token = Some(manager.DataRequested(&handler)?);
+
+    unsafe { interop.ShowShareUIForWindow(hwnd) }
+}
+
+/// エクスプローラーでファイルを選択した状態でフォルダーを開く（共有UIを表示できない場合の代替）
+fn reveal_in_explorer(hwnd: HWND, file_path: &Path) {
+    let parameters: Vec<u16> = format!("/select,\"{}\"", file_path.display())
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        // ShellExecuteWの戻り値は32以下がエラー
+        let result = ShellExecuteW(
+            Some(hwnd),
+            w!("open"),
+            w!("explorer.exe"),
+            PCWSTR(parameters.as_ptr()),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        );
+        if result.0 as isize <= 32 {
+            app_log(&strings::share_folder_open_failed(file_path));
+        }
+    }
+}
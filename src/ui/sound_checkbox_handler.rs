@@ -0,0 +1,73 @@
+/*
+============================================================================
+サウンドチェックボックスハンドラモジュール (sound_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+画像保存時のシャッター音と自動クリック完了時のチャイムを再生するかどうかを
+切り替える「サウンドを再生」チェックボックスを管理するモジュール。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_sound_checkbox`
+    -   `AppState.sound_enabled` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_sound_checkbox_change`
+    -   チェック状態を `AppState.sound_enabled` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **効果音再生**: `sound.rs`（PlaySoundW + SND_ASYNC、MessageBeepフォールバック）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `sound_enabled` フィールドの保持
+-   `constants.rs`: `IDC_SOUND_CHECKBOX` コントロールID定義
+-   `sound.rs`: `sound_enabled` に基づく再生可否の判定
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// サウンドチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_sound_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().sound_enabled;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_SOUND_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// サウンドチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.sound_enabled` に保存します。
+/// 変更は次回の保存・自動クリック完了時から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_sound_checkbox_change(hwnd: HWND) {
+    let is_checked = unsafe { IsDlgButtonChecked(hwnd, IDC_SOUND_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.sound_enabled = is_checked;
+
+    println!(
+        "サウンド設定変更: {}",
+        if is_checked { "有効" } else { "無効" }
+    );
+}
@@ -0,0 +1,108 @@
+/*
+============================================================================
+出力幅エディットボックスハンドラモジュール (target_width_edit_handler.rs)
+============================================================================
+
+【ファイル概要】
+スケールコンボボックスで「幅指定」を選択したときに使用する出力画像幅（ピクセル）の
+エディットボックスを管理するモジュール。4K/5Kなどの高DPI画面でドキュメントを
+キャプチャする際、画面解像度に関係なく一定の幅で保存できます。
+
+【主要機能】
+1.  **エディットボックス初期化**: `initialize_target_width_edit`
+    -   `AppState.capture_target_width` の値を表示（デフォルト：1200px）
+2.  **入力値変更処理**: `handle_target_width_edit_change`
+    -   フォーカス喪失時（EN_KILLFOCUS）に入力値を検証して `AppState` に反映
+3.  **有効状態の同期**: `update_target_width_edit_state`
+    -   幅指定モード時のみエディットボックスを有効化
+
+【技術仕様】
+-   **入力範囲**: `TARGET_WIDTH_MIN`〜`TARGET_WIDTH_MAX`（範囲外は丸めて表示を更新）
+-   **縮小計算**: `capturer.rs` の `compute_scaled_size`（縦横比維持・拡大なし）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `capture_fit_to_width` / `capture_target_width` フィールドの保持
+-   `constants.rs`: `IDC_TARGET_WIDTH_EDIT` コントロールID定義
+-   `scale_combo_handler.rs`: 「幅指定」選択時に有効状態を更新
+-   メインダイアログ: EN_KILLFOCUS通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::{
+        Input::KeyboardAndMouse::EnableWindow,
+        WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+    },
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// 出力幅の最小値（ピクセル）
+const TARGET_WIDTH_MIN: u32 = 100;
+/// 出力幅の最大値（ピクセル）
+const TARGET_WIDTH_MAX: u32 = 9999;
+
+/// 出力幅エディットボックスを初期化する
+///
+/// `AppState` の現在値を表示し、幅指定モードに応じて有効状態を設定します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_target_width_edit(hwnd: HWND) {
+    let target_width = AppState::get_app_state_ref().capture_target_width;
+
+    unsafe {
+        let _ = SetDlgItemInt(hwnd, IDC_TARGET_WIDTH_EDIT, target_width, false);
+    }
+
+    update_target_width_edit_state(hwnd);
+}
+
+/// 出力幅エディットボックスの変更を処理する
+///
+/// フォーカスが外れた（`EN_KILLFOCUS`）際に入力値を数値に変換し、
+/// 許容範囲に丸めて `AppState.capture_target_width` に保存します。
+/// 空欄や数値以外の場合は現在値を表示し直します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_target_width_edit_change(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+
+    if let Ok(edit_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_TARGET_WIDTH_EDIT) } {
+        let mut buffer: [u16; 16] = [0; 16];
+        let text_length = unsafe { GetWindowTextW(edit_hwnd, &mut buffer) };
+        let text = String::from_utf16_lossy(&buffer[..text_length as usize]);
+
+        if let Ok(width) = text.trim().parse::<u32>() {
+            app_state.capture_target_width = width.clamp(TARGET_WIDTH_MIN, TARGET_WIDTH_MAX);
+            println!("出力幅設定変更: {}px", app_state.capture_target_width);
+        }
+    }
+
+    // 丸め後（または不正入力時は元）の値を表示に反映
+    unsafe {
+        let _ = SetDlgItemInt(
+            hwnd,
+            IDC_TARGET_WIDTH_EDIT,
+            app_state.capture_target_width,
+            false,
+        );
+    }
+}
+
+/// 出力幅エディットボックスの有効/無効状態を更新する
+///
+/// スケールコンボボックスで「幅指定」が選択されている場合のみ有効化します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn update_target_width_edit_state(hwnd: HWND) {
+    let is_enabled = AppState::get_app_state_ref().capture_fit_to_width;
+
+    if let Ok(edit_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_TARGET_WIDTH_EDIT) } {
+        unsafe {
+            let _ = EnableWindow(edit_hwnd, is_enabled);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+============================================================================
+テーマコンボボックスハンドラモジュール (theme_combo_handler.rs)
+============================================================================
+
+【ファイル概要】
+オーナードローボタン・サムネイルストリップ・エリア選択オーバーレイの配色テーマを選択する
+コンボボックスを管理するモジュール。既定ではWindowsのアプリモード（ライト / ダーク）に従います。
+
+【主要機能】
+1.  **テーマコンボボックス初期化**: `initialize_theme_combo`
+    -   「自動」「ライト」「ダーク」の3項目を提供（デフォルト：自動）
+2.  **テーマ変更イベント処理**: `handle_theme_combo_change`
+    -   選択された設定を `AppState.theme_setting` に保存し、ボタンを再描画
+
+【技術仕様】
+-   **UI制御**: Win32 ComboBox API (`CB_ADDSTRING`, `CB_SETITEMDATA`, `CB_GETCURSEL`)
+-   **データ管理**: 各項目に `ThemeSetting` の識別値（`u8`）を関連付け
+-   **適用範囲**: エリア選択オーバーレイは次回の描画から新しい配色で表示
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `theme_setting` フィールドの保持
+-   `constants.rs`: `IDC_THEME_COMBO` コントロールID定義
+-   `theme.rs`: `ThemeSetting` 定義と配色、`redraw_themed_controls`
+-   メインダイアログ: CBN_SELCHANGE通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::*, // ウィンドウとメッセージ処理
+};
+
+use crate::{
+    app_state::AppState,
+    constants::*,
+    strings,
+    system_utils::app_log,
+    theme::{ThemeSetting, redraw_themed_controls},
+};
+
+/// テーマコンボボックスを初期化する
+///
+/// 「自動」「ライト」「ダーク」の選択肢を追加し、`AppState` の現在値を選択状態にします。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_theme_combo(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_THEME_COMBO) } {
+        let settings = [ThemeSetting::Auto, ThemeSetting::Light, ThemeSetting::Dark];
+
+        let current_setting = AppState::get_app_state_ref().theme_setting;
+        let mut default_index = 0;
+
+        for setting in settings {
+            // Win32 APIに渡すためNull終端文字を付加
+            let text = format!("{}\0", setting.label());
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+
+            // CB_ADDSTRING：コンボボックスに表示テキストを追加
+            let index = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(wide_text.as_ptr() as isize)),
+                )
+            }
+            .0 as usize;
+
+            // CB_SETITEMDATA：表示テキストとテーマ設定の識別値を関連付け
+            unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_SETITEMDATA,
+                    Some(WPARAM(index)),
+                    Some(LPARAM(setting as isize)),
+                );
+            }
+
+            if setting == current_setting {
+                default_index = index;
+            }
+        }
+
+        // 現在の設定値を選択状態に設定
+        unsafe {
+            SendMessageW(
+                combo_hwnd,
+                CB_SETCURSEL,
+                Some(WPARAM(default_index)),
+                Some(LPARAM(0)),
+            );
+        }
+    }
+}
+
+/// テーマコンボボックスの選択変更イベントを処理する
+///
+/// 選択された項目の識別値から `ThemeSetting` を復元して `AppState` に保存し、
+/// ボタン・サムネイルストリップを新しい配色で再描画します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_theme_combo_change(hwnd: HWND) {
+    if let Ok(combo_hwnd) = unsafe { GetDlgItem(Some(hwnd), IDC_THEME_COMBO) } {
+        // CB_GETCURSEL：現在選択されている項目のインデックス取得
+        let selected_index =
+            unsafe { SendMessageW(combo_hwnd, CB_GETCURSEL, Some(WPARAM(0)), Some(LPARAM(0))).0 }
+                as i32;
+
+        if selected_index >= 0 {
+            // CB_GETITEMDATA：選択項目に関連付けられたテーマ設定の識別値を取得
+            let setting_value = unsafe {
+                SendMessageW(
+                    combo_hwnd,
+                    CB_GETITEMDATA,
+                    Some(WPARAM(selected_index as usize)),
+                    Some(LPARAM(0)),
+                )
+            }
+            .0 as u8;
+
+            let setting = ThemeSetting::from_u8(setting_value);
+            let app_state = AppState::get_app_state_mut();
+            app_state.theme_setting = setting;
+            redraw_themed_controls(hwnd);
+
+            app_log(&strings::theme_changed(setting.label()));
+        }
+    }
+}
@@ -0,0 +1,559 @@
+/*
+============================================================================
+サムネイルストリップハンドラモジュール (thumbnail_strip_handler.rs)
+============================================================================
+
+【ファイル概要】
+今回のセッションでキャプチャした直近の画像を、ダイアログ下部の横長ストリップに
+サムネイルとして並べて表示するモジュール。失敗したコマ（ブレ、表示途中など）を
+一目で見つけられるようにし、クリックで該当ファイルを既定のアプリで開く。
+
+【主要機能】
+1.  **サムネイル保持 (`ThumbnailStrip`)**:
+    -   直近 `MAX_THUMBNAIL_COUNT` 件の `HBITMAP` を保持するリングバッファ。
+    -   上限を超えた古いビットマップは `DeleteObject` で即座に破棄（GDIハンドルリーク防止）。
+2.  **サムネイル生成 (`create_thumbnail_bitmap` / `create_thumbnail_bitmap_from_image`)**:
+    -   キャプチャ処理中のメモリDC、または保存した画像（`RgbImage`）から、縦横比を保って小さなビットマップを作成。
+    -   画面領域のキャプチャは保存した画像から作成するため（`add_capture_thumbnail_from_image`）、
+        マスク・結合・切り出しを含めて保存した内容と一致し、ファイルを読み直す必要もない。
+3.  **オーナードロー描画 (`draw_thumbnail_strip`)**:
+    -   `SS_OWNERDRAW` スタティックコントロールを等幅スロットに分割し、古い順に左から描画。
+4.  **クリック処理 (`handle_thumbnail_strip_click`)**:
+    -   `STN_CLICKED` 通知時のカーソル位置からスロットを特定し、`ShellExecuteW` でファイルを開く。
+5.  **ドラッグ＆ドロップ (`initialize_thumbnail_strip`)**:
+    -   ストリップをサブクラス化し、`WM_LBUTTONDOWN` で `DragDetect` がドラッグと判定した場合は
+        サムネイルのファイルを `SHDoDragDrop` でドラッグする（Teams・エクスプローラーなどにドロップ可能）。
+
+【技術仕様】
+-   **描画**: `StretchBlt` + `HALFTONE` による縮小描画
+-   **コントロール**: `IDC_THUMBNAIL_STRIP`（`SS_OWNERDRAW | SS_NOTIFY`）
+-   **リソース管理**: `Drop` 実装により AppState 解放時に全ビットマップを破棄
+-   **ドラッグ**: シェルのデータオブジェクト（`BHID_DataObject`、CF_HDROPを含む）と既定のドロップソース。
+    OLEは `main.rs` でダイアログのスレッドに初期化（`OleInitialize`）。フックが有効なモードではドラッグしない
+
+【AI解析用：依存関係】
+-   `screen_capture.rs`: 保存成功時に `add_capture_thumbnail_from_image` を呼び出す。
+-   `window_capture.rs`: 保存成功時に `add_capture_thumbnail` を呼び出す。
+-   `dialog_handler.rs`: `WM_DRAWITEM` / `WM_COMMAND` から描画・クリック処理を呼び出す。
+-   `app_state.rs`: `ThumbnailStrip` を `capture_thumbnails` として保持。
+ */
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+use clickcapture::dib::dib_row_size;
+use image::{RgbImage, imageops};
+
+use windows::{
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+        Graphics::Gdi::*,
+        System::{
+            Com::IDataObject,
+            Ole::{DROPEFFECT, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_NONE, IDropSource},
+        },
+        UI::{
+            Controls::DRAWITEMSTRUCT,
+            Input::KeyboardAndMouse::DragDetect,
+            Shell::{
+                BHID_DataObject, DefSubclassProc, IShellItem, RemoveWindowSubclass,
+                SHCreateItemFromParsingName, SHDoDragDrop, SetWindowSubclass, ShellExecuteW,
+            },
+            WindowsAndMessaging::{
+                GetClientRect, GetCursorPos, GetDlgItem, SW_SHOWNORMAL, WM_LBUTTONDOWN,
+                WM_NCDESTROY,
+            },
+        },
+    },
+    core::{HSTRING, PCWSTR, w},
+};
+
+use crate::{
+    app_state::{AppMode, AppState},
+    constants::*,
+    strings,
+    system_utils::app_log,
+    theme::button_palette,
+    ui::icon_button::*,
+};
+
+// ストリップに保持するサムネイルの最大件数（これを超えると古いものから破棄）
+pub const MAX_THUMBNAIL_COUNT: usize = 8;
+
+// サムネイルビットマップの最大サイズ（ピクセル、縦横比は維持）
+const THUMBNAIL_MAX_WIDTH: i32 = 96;
+const THUMBNAIL_MAX_HEIGHT: i32 = 64;
+
+// スロット間の余白（ピクセル）
+const THUMBNAIL_PADDING: i32 = 2;
+
+// サムネイルストリップのサブクラスID（SetWindowSubclass / RemoveWindowSubclass）
+const THUMBNAIL_STRIP_SUBCLASS_ID: usize = 1;
+
+/// 1枚分のサムネイル情報
+#[derive(Debug)]
+pub struct CaptureThumbnail {
+    /// 縮小済みビットマップ（ThumbnailStripが所有し、破棄時にDeleteObjectする）
+    pub hbitmap: HBITMAP,
+    /// ビットマップの幅（ピクセル）
+    pub width: i32,
+    /// ビットマップの高さ（ピクセル）
+    pub height: i32,
+    /// 保存先ファイルのパス（クリック時に開く）
+    pub file_path: PathBuf,
+}
+
+/// 直近キャプチャのサムネイルを保持するリングバッファ
+///
+/// 先頭が最も古く、末尾が最新のキャプチャ。
+/// 件数が `MAX_THUMBNAIL_COUNT` に達した状態で追加すると、先頭のビットマップを破棄する。
+#[derive(Debug, Default)]
+pub struct ThumbnailStrip {
+    items: VecDeque<CaptureThumbnail>,
+}
+
+impl ThumbnailStrip {
+    pub fn new() -> Self {
+        Self {
+            items: VecDeque::with_capacity(MAX_THUMBNAIL_COUNT),
+        }
+    }
+
+    /// サムネイルを末尾に追加し、上限を超えた古いビットマップを破棄する
+    pub fn push(&mut self, thumbnail: CaptureThumbnail) {
+        while self.items.len() >= MAX_THUMBNAIL_COUNT {
+            if let Some(old) = self.items.pop_front() {
+                unsafe {
+                    let _ = DeleteObject(old.hbitmap.into());
+                }
+            }
+        }
+        self.items.push_back(thumbnail);
+    }
+
+    /// 指定スロット（0 = 最も古い）のサムネイルを取得する
+    pub fn get(&self, index: usize) -> Option<&CaptureThumbnail> {
+        self.items.get(index)
+    }
+
+    /// 保持しているサムネイルを古い順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = &CaptureThumbnail> {
+        self.items.iter()
+    }
+
+    /// 保持しているサムネイルを古い順に変更可能な参照で列挙する（ファイル名の変更の反映用）
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut CaptureThumbnail> {
+        self.items.iter_mut()
+    }
+}
+
+impl Drop for ThumbnailStrip {
+    fn drop(&mut self) {
+        // 保持している全ビットマップを解放（GDIハンドルリーク防止）
+        for item in self.items.drain(..) {
+            unsafe {
+                let _ = DeleteObject(item.hbitmap.into());
+            }
+        }
+    }
+}
+
+/// 縮小済みのキャプチャ画像からサムネイル用ビットマップを作成する
+///
+/// # 引数
+/// * `screen_dc` - 互換ビットマップ作成用の画面DC
+/// * `source_dc` - キャプチャ画像が選択されているメモリDC
+/// * `source_width`, `source_height` - キャプチャ画像のサイズ
+///
+/// # 戻り値
+/// * `Some((HBITMAP, 幅, 高さ))` - 作成成功。ビットマップの破棄は呼び出し側の責任。
+/// * `None` - サイズが不正、またはビットマップ作成に失敗した場合。
+pub fn create_thumbnail_bitmap(
+    screen_dc: HDC,
+    source_dc: HDC,
+    source_width: i32,
+    source_height: i32,
+) -> Option<(HBITMAP, i32, i32)> {
+    if source_width <= 0 || source_height <= 0 {
+        return None;
+    }
+
+    let (width, height) = thumbnail_size(source_width, source_height);
+
+    unsafe {
+        let thumb_dc = CreateCompatibleDC(Some(screen_dc));
+        let hbitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        if hbitmap.is_invalid() {
+            let _ = DeleteDC(thumb_dc);
+            return None;
+        }
+        let old_bitmap = SelectObject(thumb_dc, hbitmap.into());
+
+        let _ = SetStretchBltMode(thumb_dc, HALFTONE);
+        let _ = SetBrushOrgEx(thumb_dc, 0, 0, None);
+        let _ = StretchBlt(
+            thumb_dc,
+            0,
+            0,
+            width,
+            height,
+            Some(source_dc),
+            0,
+            0,
+            source_width,
+            source_height,
+            SRCCOPY,
+        );
+
+        let _ = SelectObject(thumb_dc, old_bitmap);
+        let _ = DeleteDC(thumb_dc);
+
+        Some((hbitmap, width, height))
+    }
+}
+
+/// 縦横比を維持したまま、サムネイルの最大サイズに収めたサイズを求める
+fn thumbnail_size(source_width: i32, source_height: i32) -> (i32, i32) {
+    let ratio = f32::min(
+        THUMBNAIL_MAX_WIDTH as f32 / source_width as f32,
+        THUMBNAIL_MAX_HEIGHT as f32 / source_height as f32,
+    );
+    (
+        ((source_width as f32 * ratio) as i32).max(1),
+        ((source_height as f32 * ratio) as i32).max(1),
+    )
+}
+
+/// 保存した画像からサムネイル用ビットマップ（DIBセクション）を作成する
+///
+/// エンコード前の画像（縮小・結合・切り出し・マスク適用後）から作成するため、
+/// 保存したファイルを読み直さずに、保存した内容と同じサムネイルを表示できます。
+///
+/// # 戻り値
+/// * `Some((HBITMAP, 幅, 高さ))` - 作成成功。ビットマップの破棄は呼び出し側の責任。
+/// * `None` - 画像が空、またはビットマップ作成に失敗した場合。
+pub fn create_thumbnail_bitmap_from_image(image: &RgbImage) -> Option<(HBITMAP, i32, i32)> {
+    if image.width() == 0 || image.height() == 0 {
+        return None;
+    }
+    let (width, height) = thumbnail_size(image.width() as i32, image.height() as i32);
+    let thumbnail = imageops::thumbnail(image, width as u32, height as u32);
+
+    let bitmap_info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height, // トップダウン形式
+            biPlanes: 1,
+            biBitCount: 24,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        bmiColors: [RGBQUAD::default(); 1],
+    };
+
+    unsafe {
+        let mut bits = std::ptr::null_mut();
+        let hbitmap =
+            CreateDIBSection(None, &bitmap_info, DIB_RGB_COLORS, &mut bits, None, 0).ok()?;
+        if bits.is_null() {
+            let _ = DeleteObject(hbitmap.into());
+            return None;
+        }
+
+        // RGB → BGR（24bpp、各行は4バイト境界）
+        let row_size = dib_row_size(width as u32);
+        let pixels = std::slice::from_raw_parts_mut(bits as *mut u8, row_size * height as usize);
+        for (y, row) in thumbnail.rows().enumerate() {
+            for (x, pixel) in row.enumerate() {
+                let index = y * row_size + x * 3;
+                pixels[index] = pixel[2];
+                pixels[index + 1] = pixel[1];
+                pixels[index + 2] = pixel[0];
+            }
+        }
+
+        Some((hbitmap, width, height))
+    }
+}
+
+/// 保存した画像からサムネイルを作成してストリップに追加する（ビットマップの所有権はストリップに移る）
+pub fn add_capture_thumbnail_from_image(image: &RgbImage, file_path: PathBuf) {
+    if let Some((hbitmap, width, height)) = create_thumbnail_bitmap_from_image(image) {
+        add_capture_thumbnail(CaptureThumbnail {
+            hbitmap,
+            width,
+            height,
+            file_path,
+        });
+    }
+}
+
+/// キャプチャ保存成功時にサムネイルを追加し、ストリップを再描画する
+pub fn add_capture_thumbnail(thumbnail: CaptureThumbnail) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.capture_thumbnails.push(thumbnail);
+
+    unsafe {
+        if let Some(dialog_hwnd) = app_state.dialog_hwnd
+            && let Ok(strip) = GetDlgItem(Some(*dialog_hwnd), IDC_THUMBNAIL_STRIP)
+        {
+            let _ = InvalidateRect(Some(strip), None, true);
+        }
+    }
+}
+
+/// 各スロットの矩形を計算する（コントロールを `MAX_THUMBNAIL_COUNT` 等分）
+fn thumbnail_slot_rect(client_rect: &RECT, index: usize) -> RECT {
+    let slot_width = (client_rect.right - client_rect.left) / MAX_THUMBNAIL_COUNT as i32;
+    let left = client_rect.left + slot_width * index as i32;
+    RECT {
+        left: left + THUMBNAIL_PADDING,
+        top: client_rect.top + THUMBNAIL_PADDING,
+        right: left + slot_width - THUMBNAIL_PADDING,
+        bottom: client_rect.bottom - THUMBNAIL_PADDING,
+    }
+}
+
+/// サムネイルストリップのオーナードロー描画（WM_DRAWITEM）
+pub fn draw_thumbnail_strip(lparam: LPARAM) {
+    unsafe {
+        let draw_item = lparam.0 as *const DRAWITEMSTRUCT;
+        if draw_item.is_null() {
+            return;
+        }
+        let draw_struct = &*draw_item;
+        let hdc = draw_struct.hDC;
+        let rect = draw_struct.rcItem;
+
+        // 1. 背景を描画（配色は現在のテーマに従う）
+        let bg_brush = CreateSolidBrush(button_palette().background);
+        FillRect(hdc, &rect, bg_brush);
+        let _ = DeleteObject(bg_brush.into());
+
+        // 2. サムネイルを古い順に左から描画（スロット内で縦横比を維持して中央配置）
+        let app_state = AppState::get_app_state_ref();
+        let memory_dc = CreateCompatibleDC(Some(hdc));
+        let _ = SetStretchBltMode(hdc, HALFTONE);
+        let _ = SetBrushOrgEx(hdc, 0, 0, None);
+
+        for (index, thumbnail) in app_state.capture_thumbnails.iter().enumerate() {
+            let slot = thumbnail_slot_rect(&rect, index);
+            let slot_width = slot.right - slot.left;
+            let slot_height = slot.bottom - slot.top;
+            if slot_width <= 0 || slot_height <= 0 {
+                continue;
+            }
+
+            let ratio = f32::min(
+                slot_width as f32 / thumbnail.width as f32,
+                slot_height as f32 / thumbnail.height as f32,
+            );
+            let draw_width = ((thumbnail.width as f32 * ratio) as i32).max(1);
+            let draw_height = ((thumbnail.height as f32 * ratio) as i32).max(1);
+            let x = slot.left + (slot_width - draw_width) / 2;
+            let y = slot.top + (slot_height - draw_height) / 2;
+
+            let old_bitmap = SelectObject(memory_dc, thumbnail.hbitmap.into());
+            let _ = StretchBlt(
+                hdc,
+                x,
+                y,
+                draw_width,
+                draw_height,
+                Some(memory_dc),
+                0,
+                0,
+                thumbnail.width,
+                thumbnail.height,
+                SRCCOPY,
+            );
+            let _ = SelectObject(memory_dc, old_bitmap);
+        }
+
+        let _ = DeleteDC(memory_dc);
+
+        // 3. 境界線を描画
+        draw_button_border(hdc, &rect);
+    }
+}
+
+/// クライアント座標の位置にあるスロットの番号（0 = 最も古い）を求める
+fn thumbnail_index_at(strip: HWND, point: POINT) -> Option<usize> {
+    let mut client_rect = RECT::default();
+    unsafe { GetClientRect(strip, &mut client_rect) }.ok()?;
+    let slot_width = (client_rect.right - client_rect.left) / MAX_THUMBNAIL_COUNT as i32;
+    if slot_width <= 0 || point.x < 0 {
+        return None;
+    }
+    Some((point.x / slot_width) as usize)
+}
+
+/// サムネイルストリップをサブクラス化し、サムネイルからのファイルのドラッグを受け付ける
+///
+/// ダイアログ初期化時に1回だけ呼び出します。
+pub fn initialize_thumbnail_strip(hwnd: HWND) {
+    unsafe {
+        if let Ok(strip) = GetDlgItem(Some(hwnd), IDC_THUMBNAIL_STRIP) {
+            let _ = SetWindowSubclass(
+                strip,
+                Some(thumbnail_strip_subclass_proc),
+                THUMBNAIL_STRIP_SUBCLASS_ID,
+                0,
+            );
+        }
+    }
+}
+
+// サムネイルストリップのサブクラスプロシージャ（ファイルのドラッグ）
+//
+// WM_LBUTTONDOWN：DragDetectでドラッグ操作かを判定し、ドラッグならファイルをドラッグ＆ドロップする
+//                （既定の処理を行わないため、STN_CLICKEDによるファイルのオープンは発生しない）。
+//                ドラッグでなければ既定の処理に渡し、従来どおりクリックでファイルを開く
+// WM_NCDESTROY：サブクラスを解除
+unsafe extern "system" fn thumbnail_strip_subclass_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _subclass_id: usize,
+    _ref_data: usize,
+) -> LRESULT {
+    unsafe {
+        match message {
+            WM_LBUTTONDOWN => {
+                let point = POINT {
+                    x: (lparam.0 & 0xFFFF) as i16 as i32,
+                    y: ((lparam.0 >> 16) & 0xFFFF) as i16 as i32,
+                };
+                if begin_thumbnail_drag(hwnd, point) {
+                    return LRESULT(0);
+                }
+            }
+            WM_NCDESTROY => {
+                let _ = RemoveWindowSubclass(
+                    hwnd,
+                    Some(thumbnail_strip_subclass_proc),
+                    THUMBNAIL_STRIP_SUBCLASS_ID,
+                );
+            }
+            _ => {}
+        }
+        DefSubclassProc(hwnd, message, wparam, lparam)
+    }
+}
+
+/// 押下位置のサムネイルのファイルを、ドラッグ操作であればドラッグ＆ドロップする
+///
+/// Teamsのチャット欄やエクスプローラーのフォルダーにドロップできるよう、シェルのデータオブジェクト
+/// （CF_HDROPを含む）を `SHDoDragDrop` に渡します（ドロップ先にはコピーまたはリンクを許可）。
+/// `SHDoDragDrop` はドロップまで戻らないため、フックが有効なモード（キャプチャ・エリア選択）では開始しません。
+///
+/// # 戻り値
+/// * `true` - ドラッグ＆ドロップを行った（押下の既定の処理は行わない）
+/// * `false` - ドラッグではない、または対象のファイルがない（クリックとして処理する）
+fn begin_thumbnail_drag(strip: HWND, point: POINT) -> bool {
+    let app_state = AppState::get_app_state_ref();
+    if app_state.mode != AppMode::Idle {
+        return false;
+    }
+    let Some(file_path) = thumbnail_index_at(strip, point)
+        .and_then(|index| app_state.capture_thumbnails.get(index))
+        .map(|thumbnail| thumbnail.file_path.clone())
+    else {
+        return false;
+    };
+
+    // ボタンを離すか、一定以上マウスを動かすまで待つ（動かさずに離した場合はクリック）
+    let mut screen_point = point;
+    unsafe {
+        let _ = ClientToScreen(strip, &mut screen_point);
+        if !DragDetect(strip, screen_point).as_bool() {
+            return false;
+        }
+    }
+
+    if !file_path.exists() {
+        app_log(&strings::thumbnail_drag_file_missing(
+            &file_path.display().to_string(),
+        ));
+        return true;
+    }
+    match drag_file(strip, &file_path) {
+        Ok(effect) if effect != DROPEFFECT_NONE => {
+            app_log(&strings::thumbnail_dragged(
+                &file_path.display().to_string(),
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => app_log(&strings::thumbnail_drag_failed(&e)),
+    }
+    true
+}
+
+/// ファイルをシェルのデータオブジェクトにしてドラッグ＆ドロップする
+///
+/// # 戻り値
+/// ドロップ先が行った操作（キャンセルされた場合は `DROPEFFECT_NONE`）
+fn drag_file(hwnd: HWND, file_path: &Path) -> windows::core::Result<DROPEFFECT> {
+    unsafe {
+        let item: IShellItem = SHCreateItemFromParsingName(&HSTRING::from(file_path), None)?;
+        let data_object: IDataObject = item.BindToHandler(None, &BHID_DataObject)?;
+        SHDoDragDrop(
+            Some(hwnd),
+            &data_object,
+            None::<&IDropSource>,
+            DROPEFFECT_COPY | DROPEFFECT_LINK,
+        )
+    }
+}
+
+/// サムネイルストリップのクリック処理（STN_CLICKED）
+///
+/// クリック位置からスロットを特定し、対応するキャプチャファイルを既定のアプリで開く。
+/// 空のスロットをクリックした場合は何もしない。
+pub fn handle_thumbnail_strip_click(hwnd: HWND) {
+    unsafe {
+        let Ok(strip) = GetDlgItem(Some(hwnd), IDC_THUMBNAIL_STRIP) else {
+            return;
+        };
+
+        // クリック位置をコントロールのクライアント座標に変換
+        let mut cursor_pos = POINT::default();
+        if GetCursorPos(&mut cursor_pos).is_err() {
+            return;
+        }
+        let _ = ScreenToClient(strip, &mut cursor_pos);
+
+        let Some(index) = thumbnail_index_at(strip, cursor_pos) else {
+            return;
+        };
+
+        let app_state = AppState::get_app_state_ref();
+        if let Some(thumbnail) = app_state.capture_thumbnails.get(index) {
+            let path_wide: Vec<u16> = thumbnail
+                .file_path
+                .to_string_lossy()
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect();
+
+            // ShellExecuteWの戻り値は32以下がエラー
+            let result = ShellExecuteW(
+                Some(hwnd),
+                w!("open"),
+                PCWSTR(path_wide.as_ptr()),
+                PCWSTR::null(),
+                PCWSTR::null(),
+                SW_SHOWNORMAL,
+            );
+            if result.0 as isize <= 32 {
+                app_log(&strings::thumbnail_open_failed(
+                    &thumbnail.file_path.display().to_string(),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+/*
+============================================================================
+ツールチップ管理モジュール (tooltip_handler.rs)
+============================================================================
+
+【ファイル概要】
+メインダイアログの各コントロール（ボタン・コンボボックス・チェックボックス・エディット）に、
+操作説明とキーボードショートカット（ESC / Tab / クイックキャプチャのホットキーなど）のツールチップを表示するモジュール。
+表示文字列は `strings::control_tooltip` から取得し、表示言語の切り替えにも追従します。
+
+【主要機能】
+1.  **ツールチップの作成**: `initialize_tooltips`
+    -   `TOOLTIPS_CLASSW`（`TTS_ALWAYSTIP`）のウィンドウを作成し、`TOOLTIP_CONTROL_IDS` の各コントロールを登録
+2.  **表示文字列の更新**: `refresh_tooltip_texts`
+    -   言語コンボボックスの変更時に `TTM_UPDATETIPTEXTW` で全ツールの文字列を差し替え
+3.  **ツールチップの破棄**: `destroy_tooltips`
+    -   `WM_DESTROY` で `DestroyWindow` し、`AppState.tooltip_hwnd` をクリア
+
+【技術仕様】
+-   **無効状態のコントロール**: 無効なウィンドウはマウスメッセージを受け取らず親ダイアログに届くため、
+    コントロール自身（`TTF_IDISHWND`）に加えて、ダイアログ上の同じ矩形（`uId` = コントロールID）も登録する
+    （エリア選択中・変換中に無効化されたボタンでも説明を表示）
+-   **複数行表示**: `TTM_SETMAXTIPWIDTH` を設定し、文字列中の改行で折り返す
+-   **表示時間**: ショートカットを読めるよう `TTDT_AUTOPOP` を延長
+
+【AI解析用：依存関係】
+-   `strings.rs`: `control_tooltip`（コントロールIDごとの説明文）、作成失敗のログ
+-   `app_state.rs`: `tooltip_hwnd` の保持、ツールチップに表示するホットキーの設定
+-   `dialog_handler.rs`: `WM_INITDIALOG` / `WM_DESTROY` から呼び出し
+-   `language_combo_handler.rs`: 言語変更時に `refresh_tooltip_texts` を呼び出し
+ */
+
+use windows::{
+    Win32::{
+        Foundation::{HWND, LPARAM, POINT, RECT, WPARAM},
+        Graphics::Gdi::MapWindowPoints,
+        System::LibraryLoader::GetModuleHandleW,
+        UI::{Controls::*, WindowsAndMessaging::*},
+    },
+    core::PWSTR,
+};
+
+use crate::{
+    app_state::{AppState, SafeHWND},
+    constants::*,
+    strings,
+    system_utils::app_log,
+};
+
+/// ツールチップを表示するコントロール
+const TOOLTIP_CONTROL_IDS: &[i32] = &[
+    IDC_PATH_EDIT,
+    IDC_BROWSE_BUTTON,
+    IDC_AREA_SELECT_BUTTON,
+    IDC_CAPTURE_START_BUTTON,
+    IDC_EXPORT_PDF_BUTTON,
+    IDC_CLOSE_BUTTON,
+    IDC_EXPORT_ZIP_BUTTON,
+    IDC_HIGH_FIDELITY_TEXT_CHECKBOX,
+    IDC_SOUND_CHECKBOX,
+    IDC_SCALE_COMBO,
+    IDC_TARGET_WIDTH_EDIT,
+    IDC_QUALITY_COMBO,
+    IDC_PDF_SIZE_COMBO,
+    IDC_AUTO_CLICK_CHECKBOX,
+    IDC_AUTO_CLICK_INTERVAL_COMBO,
+    IDC_AUTO_CLICK_COUNT_EDIT,
+    IDC_AUTO_CLICK_CAPTURE_ONLY_CHECKBOX,
+    IDC_AUTO_CLICK_DURATION_COMBO,
+    IDC_CAPTURE_FORMAT_COMBO,
+    IDC_EXPORT_GIF_BUTTON,
+    IDC_WINDOW_CAPTURE_CHECKBOX,
+    IDC_CAPTURE_TRIGGER_COMBO,
+    IDC_REAL_INPUT_ONLY_CHECKBOX,
+    IDC_SCHEDULE_INTERVAL_COMBO,
+    IDC_SCHEDULE_COUNT_EDIT,
+    IDC_SCHEDULE_BUTTON,
+    IDC_DUAL_AREA_STACKED_CHECKBOX,
+    IDC_THUMBNAIL_STRIP,
+    IDC_CAPTURE_METADATA_CHECKBOX,
+    IDC_THEME_COMBO,
+    IDC_LANGUAGE_COMBO,
+    IDC_LOG_EDIT,
+    IDC_EXPORT_CONTACT_SHEET_BUTTON,
+    IDC_RESAVE_BUTTON,
+    IDC_OVERLAY_VISIBILITY_COMBO,
+    IDC_DIM_OPACITY_COMBO,
+    IDC_BORDER_COLOR_COMBO,
+    IDC_BORDER_WIDTH_COMBO,
+    IDC_BORDER_HALO_CHECKBOX,
+    IDC_FREEZE_SCREEN_CHECKBOX,
+    IDC_COMPLETION_NOTIFY_CHECKBOX,
+    IDC_HISTORY_BUTTON,
+    IDC_WORK_AREA_BUTTON,
+    IDC_RENUMBER_BUTTON,
+    IDC_RESET_COUNTER_BUTTON,
+    IDC_PROGRESSIVE_JPEG_CHECKBOX,
+    IDC_PDF_ARCHIVE_CHECKBOX,
+    IDC_AUTO_PDF_EXPORT_CHECKBOX,
+    IDC_SHARE_BUTTON,
+    IDC_ELAPSED_STAMP_CHECKBOX,
+    IDC_PAUSE_ON_USER_INPUT_CHECKBOX,
+];
+
+/// ツールチップの最大幅（ピクセル、これを超える行と改行位置で折り返す）
+const TOOLTIP_MAX_WIDTH: isize = 360;
+
+/// ツールチップの表示時間（ミリ秒）
+const TOOLTIP_AUTOPOP_MS: isize = 15_000;
+
+/// ツールチップウィンドウを作成し、ダイアログの各コントロールを登録する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_tooltips(hwnd: HWND) {
+    unsafe {
+        // ツールチップクラスはcomctl32に含まれるため、事前に登録しておく
+        let icc = INITCOMMONCONTROLSEX {
+            dwSize: std::mem::size_of::<INITCOMMONCONTROLSEX>() as u32,
+            dwICC: ICC_BAR_CLASSES,
+        };
+        let _ = InitCommonControlsEx(&icc);
+
+        let hinstance = GetModuleHandleW(None).unwrap_or_default();
+        let tooltip_hwnd = match CreateWindowExW(
+            WS_EX_TOPMOST,
+            TOOLTIPS_CLASSW,
+            None,
+            WS_POPUP | WINDOW_STYLE(TTS_ALWAYSTIP | TTS_NOPREFIX),
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            Some(hwnd),
+            None,
+            Some(hinstance.into()),
+            None,
+        ) {
+            Ok(tooltip_hwnd) => tooltip_hwnd,
+            Err(e) => {
+                app_log(&strings::tooltip_create_failed(&e));
+                return;
+            }
+        };
+
+        SendMessageW(
+            tooltip_hwnd,
+            TTM_SETMAXTIPWIDTH,
+            Some(WPARAM(0)),
+            Some(LPARAM(TOOLTIP_MAX_WIDTH)),
+        );
+        SendMessageW(
+            tooltip_hwnd,
+            TTM_SETDELAYTIME,
+            Some(WPARAM(TTDT_AUTOPOP as usize)),
+            Some(LPARAM(TOOLTIP_AUTOPOP_MS)),
+        );
+
+        let app_state = AppState::get_app_state_ref();
+        let (quick_capture_hotkey, repeat_capture_hotkey) = (
+            app_state.quick_window_capture_hotkey,
+            app_state.repeat_last_capture_hotkey,
+        );
+        for &control_id in TOOLTIP_CONTROL_IDS {
+            let (Ok(control_hwnd), Some(text)) = (
+                GetDlgItem(Some(hwnd), control_id),
+                strings::control_tooltip(control_id, quick_capture_hotkey, repeat_capture_hotkey),
+            ) else {
+                continue;
+            };
+            let mut text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+
+            // 有効なコントロール上：コントロール自身をサブクラス化して表示
+            let mut control_tool = control_tool_info(hwnd, control_hwnd);
+            control_tool.lpszText = PWSTR(text_wide.as_mut_ptr());
+            send_tool_message(tooltip_hwnd, TTM_ADDTOOLW, &control_tool);
+
+            // 無効なコントロール上：マウスメッセージを受け取る親ダイアログ上の矩形で表示
+            let mut disabled_tool = disabled_tool_info(hwnd, control_hwnd, control_id);
+            disabled_tool.lpszText = PWSTR(text_wide.as_mut_ptr());
+            send_tool_message(tooltip_hwnd, TTM_ADDTOOLW, &disabled_tool);
+        }
+
+        AppState::get_app_state_mut().tooltip_hwnd = Some(SafeHWND(tooltip_hwnd));
+    }
+}
+
+/// 現在の表示言語でツールチップの文字列を更新する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn refresh_tooltip_texts(hwnd: HWND) {
+    let app_state = AppState::get_app_state_ref();
+    let Some(tooltip_hwnd) = app_state.tooltip_hwnd else {
+        return;
+    };
+    let (quick_capture_hotkey, repeat_capture_hotkey) = (
+        app_state.quick_window_capture_hotkey,
+        app_state.repeat_last_capture_hotkey,
+    );
+    unsafe {
+        for &control_id in TOOLTIP_CONTROL_IDS {
+            let (Ok(control_hwnd), Some(text)) = (
+                GetDlgItem(Some(hwnd), control_id),
+                strings::control_tooltip(control_id, quick_capture_hotkey, repeat_capture_hotkey),
+            ) else {
+                continue;
+            };
+            let mut text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+
+            let mut control_tool = control_tool_info(hwnd, control_hwnd);
+            control_tool.lpszText = PWSTR(text_wide.as_mut_ptr());
+            send_tool_message(*tooltip_hwnd, TTM_UPDATETIPTEXTW, &control_tool);
+
+            let mut disabled_tool = disabled_tool_info(hwnd, control_hwnd, control_id);
+            disabled_tool.lpszText = PWSTR(text_wide.as_mut_ptr());
+            send_tool_message(*tooltip_hwnd, TTM_UPDATETIPTEXTW, &disabled_tool);
+        }
+    }
+}
+
+/// ツールチップウィンドウを破棄する（`WM_DESTROY` から呼び出し）
+pub fn destroy_tooltips() {
+    let app_state = AppState::get_app_state_mut();
+    if let Some(tooltip_hwnd) = app_state.tooltip_hwnd.take() {
+        unsafe {
+            let _ = DestroyWindow(*tooltip_hwnd);
+        }
+    }
+}
+
+/// 有効なコントロール用のツール情報（コントロールのウィンドウハンドルで識別）
+fn control_tool_info(hwnd: HWND, control_hwnd: HWND) -> TTTOOLINFOW {
+    TTTOOLINFOW {
+        cbSize: std::mem::size_of::<TTTOOLINFOW>() as u32,
+        uFlags: TTF_IDISHWND | TTF_SUBCLASS,
+        hwnd,
+        uId: control_hwnd.0 as usize,
+        ..Default::default()
+    }
+}
+
+/// 無効なコントロール用のツール情報（親ダイアログ上のコントロール矩形、コントロールIDで識別）
+fn disabled_tool_info(hwnd: HWND, control_hwnd: HWND, control_id: i32) -> TTTOOLINFOW {
+    let mut rect = RECT::default();
+    unsafe {
+        if GetWindowRect(control_hwnd, &mut rect).is_ok() {
+            // スクリーン座標からダイアログのクライアント座標に変換
+            let mut points = [
+                POINT {
+                    x: rect.left,
+                    y: rect.top,
+                },
+                POINT {
+                    x: rect.right,
+                    y: rect.bottom,
+                },
+            ];
+            MapWindowPoints(None, Some(hwnd), &mut points);
+            rect = RECT {
+                left: points[0].x,
+                top: points[0].y,
+                right: points[1].x,
+                bottom: points[1].y,
+            };
+        }
+    }
+    TTTOOLINFOW {
+        cbSize: std::mem::size_of::<TTTOOLINFOW>() as u32,
+        uFlags: TTF_SUBCLASS,
+        hwnd,
+        uId: control_id as usize,
+        rect,
+        ..Default::default()
+    }
+}
+
+/// ツール情報を指定してツールチップにメッセージを送信する
+fn send_tool_message(tooltip_hwnd: HWND, message: u32, tool_info: &TTTOOLINFOW) {
+    unsafe {
+        SendMessageW(
+            tooltip_hwnd,
+            message,
+            Some(WPARAM(0)),
+            Some(LPARAM(tool_info as *const TTTOOLINFOW as isize)),
+        );
+    }
+}
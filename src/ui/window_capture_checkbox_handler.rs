@@ -0,0 +1,79 @@
+/*
+============================================================================
+ウィンドウ単体キャプチャチェックボックスハンドラモジュール (window_capture_checkbox_handler.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャモード中のクリックで、選択エリアの代わりにクリック位置のウィンドウ1つを
+キャプチャするかどうかを切り替えるチェックボックスを管理するモジュール。
+角丸・半透明のウィンドウを、背景を含まないアルファ付きPNGとして保存できます。
+
+【主要機能】
+1.  **チェックボックス初期化**: `initialize_window_capture_checkbox`
+    -   `AppState.window_capture_mode` の値をチェック状態に反映（デフォルト：OFF）
+2.  **チェック状態変更処理**: `handle_window_capture_checkbox_change`
+    -   チェック状態を `AppState.window_capture_mode` に即座に反映
+
+【技術仕様】
+-   **チェックボックス制御**: Win32 CheckDlgButton / IsDlgButtonChecked
+-   **キャプチャ処理**: `window_capture.rs`（PrintWindow + PW_RENDERFULLCONTENT）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `window_capture_mode` フィールドの保持
+-   `constants.rs`: `IDC_WINDOW_CAPTURE_CHECKBOX` コントロールID定義
+-   `hook/mouse.rs`: `window_capture_mode` によるキャプチャ処理の分岐
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Controls::{BST_CHECKED, BST_UNCHECKED, CheckDlgButton, IsDlgButtonChecked},
+};
+
+use crate::{app_state::AppState, constants::*};
+
+/// ウィンドウ単体キャプチャチェックボックスを初期化する
+///
+/// `AppState` の現在値をチェックボックスの表示状態に反映します。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn initialize_window_capture_checkbox(hwnd: HWND) {
+    let is_checked = AppState::get_app_state_ref().window_capture_mode;
+
+    unsafe {
+        let _ = CheckDlgButton(
+            hwnd,
+            IDC_WINDOW_CAPTURE_CHECKBOX,
+            if is_checked {
+                BST_CHECKED
+            } else {
+                BST_UNCHECKED
+            },
+        );
+    }
+}
+
+/// ウィンドウ単体キャプチャチェックボックスの状態変更イベントを処理する
+///
+/// チェック状態を `AppState.window_capture_mode` に保存します。
+/// 変更は次回のキャプチャモード開始時から適用されます。
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_window_capture_checkbox_change(hwnd: HWND) {
+    let is_checked =
+        unsafe { IsDlgButtonChecked(hwnd, IDC_WINDOW_CAPTURE_CHECKBOX) } == BST_CHECKED.0;
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.window_capture_mode = is_checked;
+
+    println!(
+        "ウィンドウ単体キャプチャ設定変更: {}",
+        if is_checked {
+            "有効（PNG）"
+        } else {
+            "無効（JPEG）"
+        }
+    );
+}
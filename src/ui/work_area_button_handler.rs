@@ -0,0 +1,70 @@
+/*
+============================================================================
+作業領域ボタンハンドラモジュール (work_area_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+プライマリモニターの作業領域（タスクバーを除いた範囲）を、ドラッグなしで
+キャプチャエリア（`selected_area`）に設定する「作業領域」ボタンを管理するモジュール。
+「タスクバー以外の画面全体」をキャプチャしたい場合に、エリア選択の代わりに使用します。
+
+【主要機能】
+1.  **作業領域の選択**: `handle_work_area_button`
+    -   `SystemParametersInfoW(SPI_GETWORKAREA)` でプライマリモニターの作業領域を取得
+    -   `selected_area` に設定し、2つ目の領域（`selected_area_2`）は解除
+    -   設定後はドラッグで選択した場合と同じく、キャプチャ開始・再キャプチャ（既定: Ctrl+Alt+Shift+R）で使用
+
+【技術仕様】
+-   **座標系**: 作業領域は仮想スクリーン座標で返るため、そのまま `BitBlt` の取得元として使用できる
+-   **有効/無効**: 通常モード時のみ有効（エリア選択中・キャプチャ中は選択領域を変更しない）
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `selected_area` / `selected_area_2` の保持
+-   `input_control_handlers.rs`: 選択後のボタン状態の更新
+-   メインダイアログ: BN_CLICKED通知メッセージの受信
+ */
+
+use windows::Win32::{
+    Foundation::RECT,
+    UI::WindowsAndMessaging::{
+        SPI_GETWORKAREA, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SystemParametersInfoW,
+    },
+};
+
+use crate::{
+    app_state::AppState, strings, system_utils::app_log,
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// 作業領域ボタンのクリックイベントを処理する
+///
+/// プライマリモニターの作業領域（タスクバーを除く）をキャプチャエリアに設定します。
+/// 取得に失敗した場合は、現在の選択領域を変更せずにログを出力します。
+pub fn handle_work_area_button() {
+    let mut work_area = RECT::default();
+    if let Err(e) = unsafe {
+        SystemParametersInfoW(
+            SPI_GETWORKAREA,
+            0,
+            Some(&mut work_area as *mut _ as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    } {
+        app_log(&strings::work_area_failed(&e));
+        return;
+    }
+
+    let app_state = AppState::get_app_state_mut();
+    app_state.selected_area = Some(work_area);
+    if app_state.selected_area_2.take().is_some() {
+        app_log("🧹 2つ目の領域を解除しました");
+    }
+    app_log(&strings::work_area_selected(
+        work_area.left,
+        work_area.top,
+        work_area.right - work_area.left,
+        work_area.bottom - work_area.top,
+    ));
+
+    update_input_control_states();
+}
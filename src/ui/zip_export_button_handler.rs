@@ -0,0 +1,132 @@
+/*
+============================================================================
+ZIP圧縮ボタンハンドラモジュール (zip_export_button_handler.rs)
+============================================================================
+
+【ファイル概要】
+「ZIPに圧縮」ボタンのクリックと、ワーカースレッドからの終了通知を処理するモジュール。
+圧縮はワーカースレッドで実行され、その間もキャプチャ操作は継続できます。
+実行中はボタン表示を「ZIP中止」に切り替え、クリックでキャンセルを要求します。
+
+【主要機能】
+1.  **ボタンクリック処理**: `handle_zip_export_button`
+    -   停止中: 確認ダイアログを表示し、`start_zip_export` でワーカースレッドを起動
+    -   実行中: `request_zip_export_cancel` で中止を要求
+2.  **終了通知処理**: `handle_zip_export_complete`
+    -   `WM_ZIP_EXPORT_COMPLETE` 受信時にボタン表示を戻し、成功時は「フォルダーを開く」を提案
+    -   「はい」でエクスプローラーを起動し、作成したZIPを選択状態で表示
+
+【AI解析用：依存関係】
+-   `export_zip.rs`: `start_zip_export` / `request_zip_export_cancel`
+-   `input_control_handlers.rs`: `update_input_control_states`（実行中もボタンを有効に保つ）
+-   メインダイアログ: BN_CLICKED通知と `WM_ZIP_EXPORT_COMPLETE` の受信
+ */
+
+use windows::{
+    Win32::{
+        Foundation::HWND,
+        UI::{Shell::ShellExecuteW, WindowsAndMessaging::*},
+    },
+    core::{PCWSTR, w},
+};
+
+use crate::{
+    app_state::AppState,
+    constants::IDC_EXPORT_ZIP_BUTTON,
+    export_zip::{request_zip_export_cancel, start_zip_export},
+    sound::{SoundEffect, play_sound},
+    system_utils::{app_log, show_message_box},
+    ui::input_control_handlers::update_input_control_states,
+};
+
+/// ZIP圧縮ボタンのクリックイベントを処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+pub fn handle_zip_export_button(hwnd: HWND) {
+    let app_state = AppState::get_app_state_mut();
+
+    // 実行中は中止ボタンとして機能
+    if app_state.is_exporting_to_zip {
+        app_log("ZIP圧縮の中止を要求しました...");
+        request_zip_export_cancel();
+        return;
+    }
+
+    let folder = app_state
+        .selected_folder_path
+        .clone()
+        .unwrap_or_else(|| "(未選択)".to_string());
+    let message = format!(
+        "保存フォルダー内の画像をZIPファイルにまとめてもよろしいでしょうか？\n\n\
+        対象: {}\n\n圧縮中もキャプチャは続けられます。",
+        folder
+    );
+    let result = show_message_box(&message, "ZIP圧縮確認", MB_OKCANCEL | MB_ICONQUESTION);
+    if result.0 != IDOK.0 {
+        app_log("ZIP圧縮がキャンセルされました。");
+        return;
+    }
+
+    app_log("ZIP圧縮を開始します...");
+    if !start_zip_export() {
+        return;
+    }
+
+    app_state.is_exporting_to_zip = true;
+    unsafe {
+        let _ = SetDlgItemTextW(hwnd, IDC_EXPORT_ZIP_BUTTON, w!("ZIP中止"));
+    }
+    update_input_control_states();
+}
+
+/// ZIP圧縮の終了通知（`WM_ZIP_EXPORT_COMPLETE`）を処理する
+///
+/// # 引数
+/// * `hwnd` - 親ダイアログウィンドウのハンドル
+/// * `success` - 圧縮に成功した場合は `true`（失敗・中止の詳細はワーカースレッドがログに出力済み）
+pub fn handle_zip_export_complete(hwnd: HWND, success: bool) {
+    let app_state = AppState::get_app_state_mut();
+    app_state.is_exporting_to_zip = false;
+    unsafe {
+        let _ = SetDlgItemTextW(hwnd, IDC_EXPORT_ZIP_BUTTON, w!("ZIPに圧縮"));
+    }
+    update_input_control_states();
+
+    let Some(output_path) = app_state.zip_export_output_path.clone().filter(|_| success) else {
+        return;
+    };
+
+    play_sound(SoundEffect::Complete);
+    let message = format!(
+        "ZIPファイルを作成しました。\n\n{}\n\nフォルダーを開きますか？",
+        output_path.display()
+    );
+    let result = show_message_box(&message, "ZIP圧縮完了", MB_YESNO | MB_ICONINFORMATION);
+    if result.0 != IDYES.0 {
+        return;
+    }
+
+    // エクスプローラーでZIPファイルを選択した状態でフォルダーを開く
+    let parameters: Vec<u16> = format!("/select,\"{}\"", output_path.display())
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        // ShellExecuteWの戻り値は32以下がエラー
+        let result = ShellExecuteW(
+            Some(hwnd),
+            w!("open"),
+            w!("explorer.exe"),
+            PCWSTR(parameters.as_ptr()),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        );
+        if result.0 as isize <= 32 {
+            app_log(&format!(
+                "❌ フォルダーを開けませんでした: {}",
+                output_path.display()
+            ));
+        }
+    }
+}
@@ -0,0 +1,285 @@
+/*
+============================================================================
+ウィンドウ単体キャプチャモジュール (window_capture.rs)
+============================================================================
+
+【ファイル概要】
+キャプチャモード中にクリックした位置のトップレベルウィンドウ1つを、
+`PrintWindow(PW_RENDERFULLCONTENT)` で32bpp DIBに描画させてキャプチャし、
+アルファチャンネル付きのPNGとして保存するモジュールです。
+画面の `BitBlt` + 24bit `GetDIBits` では角丸や半透明部分が黒く潰れたり、
+背後のウィンドウが写り込んだりするため、その代替経路として使用します。
+
+【主要機能】
+1.  **ウィンドウ単体キャプチャ (`capture_window_at_point_with_counter`)**:
+    -   `WindowFromPoint` + `GetAncestor(GA_ROOT)` でクリック位置のトップレベルウィンドウを特定
+    -   `PrintWindow(PW_RENDERFULLCONTENT)` でDirectComposition/DirectX描画を含めて取得
+    -   BGRA（乗算済みアルファ）から RGBA（ストレートアルファ）へ変換し、PNGで保存
+2.  **連番・サムネイル・トースト連携**:
+    -   画面キャプチャと同じ `capture_file_counter` を使用（`0001.png` 形式、`create_next_capture_file` で空き番号を排他的に作成）
+    -   保存成功時にサムネイルストリップへの追加と保存完了トーストの表示を行う
+    -   保存前に空き容量を確認し、不足している場合は書き込まずに自動クリックを停止する
+
+【技術仕様】
+-   **取得方式**: `CreateDIBSection`（32bpp、トップダウン）+ `PrintWindow`
+-   **アルファ処理**: 全画素のアルファが0の場合はアルファ非対応の描画とみなし不透明として扱う
+-   **スケール**: `capture_scale_factor`（または幅指定の `capture_target_width`）に従い `image::imageops::resize` で縮小（アルファ保持）
+-   **制約**: PDF変換はJPEGのみが対象のため、PNGは変換対象外
+
+【AI解析用：依存関係】
+-   `app_state.rs`: `window_capture_mode`、保存先、連番、スケール設定
+-   `hook/mouse.rs`: キャプチャトリガー時に本モジュールへ分岐
+-   `screen_capture.rs`: キャプチャオーバーレイの処理中表示切り替え、空き容量の確認とエラー報告
+-   `ui/thumbnail_strip_handler.rs`: サムネイル作成とストリップへの追加
+-   `overlay/saved_toast_overlay.rs`: 保存完了トースト
+-   `strings.rs`: 保存に失敗した場合のログ文字列
+ */
+
+use std::fs;
+use std::io::BufWriter;
+
+use clickcapture::capture_naming::capture_file_name;
+use clickcapture::capturer::{DOWNSCALE_FILTER, clamp_to_max_size, compute_scaled_size};
+use clickcapture::dib::premultiplied_bgra_to_rgba_image;
+use image::ImageFormat;
+use windows::Win32::{
+    Foundation::{POINT, RECT},
+    Graphics::Gdi::*,
+    Storage::Xps::{PRINT_WINDOW_FLAGS, PrintWindow},
+    UI::WindowsAndMessaging::*,
+};
+
+use crate::{
+    app_state::*,
+    error::CaptureError,
+    overlay::saved_toast_overlay::show_saved_toast,
+    screen_capture::{
+        advance_capture_file_counter, ensure_disk_space, get_save_dir_path, report_capture_error,
+        set_capture_overlay_processing_state,
+    },
+    sound::{SoundEffect, play_sound},
+    strings,
+    system_utils::{app_log, create_next_capture_file},
+    ui::{capture_history_handler::add_capture_history, thumbnail_strip_handler::*},
+};
+
+/**
+ * クリック位置のトップレベルウィンドウをキャプチャし、連番ファイル名でPNGとして保存する
+ *
+ * # 引数
+ * * `point` - クリックされたスクリーン座標
+ *
+ * # 戻り値
+ * * `Ok(())` - 成功した場合。
+ * * `Err(Box<dyn std::error::Error>)` - ウィンドウが見つからない、`PrintWindow` 失敗、保存失敗など。
+ *
+ * # 処理フロー
+ * 1. `WindowFromPoint` と `GetAncestor(GA_ROOT)` で対象ウィンドウを特定し、`GetWindowRect` でサイズを取得します。
+ * 2. 32bppトップダウンのDIBセクションを作成し、`PrintWindow(PW_RENDERFULLCONTENT)` で描画させます。
+ * 3. 乗算済みBGRAをストレートRGBAへ変換し、必要に応じて縮小します。
+ * 4. `0001.png` 形式の連番ファイル名で保存し、サムネイルとトーストを更新します。
+ */
+pub fn capture_window_at_point_with_counter(
+    point: POINT,
+) -> Result<(), Box<dyn std::error::Error>> {
+    app_log("⌛ ウィンドウキャプチャ中です...");
+
+    let app_state = AppState::get_app_state_mut();
+
+    // クリック位置のトップレベルウィンドウを取得
+    let target_hwnd = unsafe { GetAncestor(WindowFromPoint(point), GA_ROOT) };
+    if target_hwnd.is_invalid() {
+        app_log("❌ クリック位置にキャプチャ対象のウィンドウがありません");
+        return Err("キャプチャ対象のウィンドウが見つかりません".into());
+    }
+
+    let mut window_rect = RECT::default();
+    unsafe { GetWindowRect(target_hwnd, &mut window_rect)? };
+    let width = window_rect.right - window_rect.left;
+    let height = window_rect.bottom - window_rect.top;
+    if width <= 0 || height <= 0 {
+        return Err("キャプチャ対象のウィンドウサイズが不正です".into());
+    }
+
+    // キャプチャ処理開始時にオーバーレイアイコンを「処理中」に切り替え
+    set_capture_overlay_processing_state(true);
+
+    // 32bpp DIBセクションへPrintWindowで描画し、BGRAピクセルを取得
+    let mut bgra_pixels = vec![0u8; (width * height * 4) as usize];
+    let thumbnail;
+    let print_result;
+    unsafe {
+        let screen_dc = GetDC(None);
+        let memory_dc = CreateCompatibleDC(Some(screen_dc));
+
+        let bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height, // トップダウンDIB
+                biPlanes: 1,
+                biBitCount: 32, // BGRA 32bit（アルファチャンネル付き）
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut bits = std::ptr::null_mut();
+        let dib = match CreateDIBSection(
+            Some(screen_dc),
+            &bitmap_info,
+            DIB_RGB_COLORS,
+            &mut bits,
+            None,
+            0,
+        ) {
+            Ok(dib) => dib,
+            Err(e) => {
+                let _ = DeleteDC(memory_dc);
+                let _ = ReleaseDC(None, screen_dc);
+                set_capture_overlay_processing_state(false);
+                return Err(format!("DIBセクションの作成に失敗: {}", e).into());
+            }
+        };
+        let old_bitmap = SelectObject(memory_dc, dib.into());
+
+        // PW_RENDERFULLCONTENT: DirectComposition/DirectXで描画されるウィンドウも取得する
+        print_result = PrintWindow(
+            target_hwnd,
+            memory_dc,
+            PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT),
+        );
+
+        if print_result.as_bool() {
+            // DIBのビットへアクセスする前にGDIのバッチ処理を確定させる
+            let _ = GdiFlush();
+            std::ptr::copy_nonoverlapping(
+                bits as *const u8,
+                bgra_pixels.as_mut_ptr(),
+                bgra_pixels.len(),
+            );
+            thumbnail = create_thumbnail_bitmap(screen_dc, memory_dc, width, height);
+        } else {
+            thumbnail = None;
+        }
+
+        // Windows GDIリソースを解放
+        let _ = SelectObject(memory_dc, old_bitmap);
+        let _ = DeleteObject(dib.into());
+        let _ = DeleteDC(memory_dc);
+        let _ = ReleaseDC(None, screen_dc);
+    }
+
+    if !print_result.as_bool() {
+        set_capture_overlay_processing_state(false);
+        app_log("❌ PrintWindowによるウィンドウの描画に失敗しました");
+        return Err("PrintWindowによるウィンドウの描画に失敗".into());
+    }
+
+    // 乗算済みBGRAからストレートアルファのRGBAへ変換
+    let mut img_buffer =
+        premultiplied_bgra_to_rgba_image(&bgra_pixels, width as u32, height as u32);
+
+    // ユーザー設定のスケール値（または幅指定）に基づいて縮小（アルファを保持するためimageクレートで処理）
+    let (scaled_width, scaled_height) = compute_scaled_size(
+        width,
+        height,
+        app_state.capture_scale_factor,
+        app_state
+            .capture_fit_to_width
+            .then_some(app_state.capture_target_width),
+    );
+    let (scaled_width, scaled_height) = clamp_to_max_size(
+        scaled_width,
+        scaled_height,
+        Some(app_state.capture_max_width),
+        Some(app_state.capture_max_height),
+    );
+    if (scaled_width, scaled_height) != (width, height) {
+        img_buffer = image::imageops::resize(
+            &img_buffer,
+            scaled_width as u32,
+            scaled_height as u32,
+            DOWNSCALE_FILTER,
+        );
+    }
+
+    // 保存先ディレクトリを決定（テンプレート設定時は日付で展開したフォルダー）
+    let save_dir_path = get_save_dir_path();
+
+    let save_dir = std::path::Path::new(&save_dir_path);
+
+    let save_result = (|| -> Result<(u32, std::path::PathBuf), Box<dyn std::error::Error>> {
+        if !save_dir.exists() {
+            fs::create_dir_all(save_dir)?; // 親ディレクトリも含めて再帰作成
+        }
+        // PNGのサイズはエンコードするまで分からないため、無圧縮RGBAのサイズを上限として空き容量を確認
+        ensure_disk_space(
+            save_dir,
+            img_buffer.width() as u64 * img_buffer.height() as u64 * 4,
+        )?;
+        // 連番ファイル（4桁ゼロパディング）を排他的に作成（他インスタンスのファイルを上書きしない）
+        let (counter, file_path, output_file) =
+            create_next_capture_file(save_dir, "png", app_state.capture_file_counter)?;
+        if let Err(e) = img_buffer.write_to(&mut BufWriter::new(output_file), ImageFormat::Png) {
+            // 書きかけのファイルを残さない
+            let _ = fs::remove_file(&file_path);
+            return Err(e.into());
+        }
+        Ok((counter, file_path))
+    })();
+
+    // 処理終了時にアイコンを待機中に戻す
+    set_capture_overlay_processing_state(false);
+
+    match save_result {
+        Ok((current_counter, file_path)) => {
+            let file_name = capture_file_name(current_counter, "png");
+
+            app_log(&format!(
+                "✅ ウィンドウ画像保存完了: {} ({}x{})",
+                file_name,
+                img_buffer.width(),
+                img_buffer.height()
+            ));
+
+            // 成功時のみ連番カウンタを更新（他インスタンスの保存で番号が飛んだ場合も追従）
+            advance_capture_file_counter(current_counter);
+
+            // サムネイルストリップに追加（古いサムネイルは自動破棄）
+            if let Some((hbitmap, thumb_width, thumb_height)) = thumbnail {
+                add_capture_thumbnail(CaptureThumbnail {
+                    hbitmap,
+                    width: thumb_width,
+                    height: thumb_height,
+                    file_path: file_path.clone(),
+                });
+            }
+
+            // キャプチャ履歴に追加（PNGのためPDF変換の対象外だが、削除・並べ替えの一覧には表示する）
+            add_capture_history(file_path);
+
+            // シャッター音（非同期再生、失敗してもキャプチャ結果には影響しない）
+            play_sound(SoundEffect::Shutter);
+
+            // 保存完了トーストをキャプチャしたウィンドウの右下に表示
+            show_saved_toast(&file_name, window_rect);
+
+            Ok(())
+        }
+        Err(e) => {
+            if let Some((thumbnail_bitmap, _, _)) = thumbnail {
+                unsafe {
+                    let _ = DeleteObject(thumbnail_bitmap.into());
+                }
+            }
+            match e.downcast_ref::<CaptureError>() {
+                // 空き容量不足は次の保存でも回復しないため、自動クリックを停止する
+                Some(capture_error) => report_capture_error(capture_error),
+                None => app_log(&strings::window_capture_save_failed(&e)),
+            }
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,205 @@
+/*
+============================================================================
+ZIPアーカイブ構築モジュール (zip_builder.rs)
+============================================================================
+
+【ファイル概要】
+保存フォルダ内のキャプチャ画像（JPEG / PNG / WebP / AVIF）を1つのZIPファイルにまとめる
+ライブラリモジュールです。大量の画像をそのまま共有する手間を省くことを想定しています。
+`AppState` やダイアログには依存せず、進捗・警告の通知先（`ZipExportEvent`）とキャンセル判定は呼び出し元から受け取ります。
+
+【主要機能】
+1.  **`export_image_folder_to_zip`**:
+    -   画像を連番順（5桁の連番も数値順）に収集し、1ファイルずつストリーミングでZIPに追加します。
+    -   画像は既に圧縮済みのため、再圧縮せず無圧縮（Stored）エントリとして格納します。
+    -   ファイルごとにキャンセルフラグを確認し、キャンセル時は作成途中のZIPを削除します。
+2.  **書き込み中ファイルの保護**:
+    -   書き込み共有を許可せずに開く（`FILE_SHARE_READ` のみ）ことで、キャプチャ保存中の
+        ファイルを検出します。共有違反の場合は少し待って再試行し、それでも開けない場合はスキップします。
+    -   開いている間は他プロセスから書き込めないため、途中までのデータがZIPに入ることはありません。
+
+【技術仕様】
+-   **ZIPライブラリ**: `zip` クレート（`CompressionMethod::Stored`）
+-   **再試行**: `OPEN_RETRY_COUNT` 回 × `OPEN_RETRY_INTERVAL` 間隔（共有違反 `ERROR_SHARING_VIOLATION` のみ）
+-   **ログ出力**: 進捗・警告は引数の `notify` コールバックへ `ZipExportEvent` で通知（文言はバイナリ側の `strings.rs`）
+
+【AI解析用：依存関係】
+-   `export_zip.rs`（バイナリ側）: 保存フォルダ・出力パス・キャンセルフラグと、`ZipExportEvent` を `app_log` に出力するコールバックを渡してワーカースレッドで呼び出し
+-   `zip`: ZIPアーカイブの書き込み
+-   `pdf_builder.rs`: `sort_capture_files`（PDF変換と共通の並び順）
+*/
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+#[cfg(windows)]
+use std::{fs::OpenOptions, os::windows::fs::OpenOptionsExt};
+use zip::CompressionMethod;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::pdf_builder::sort_capture_files;
+
+/// ZIPに格納する画像の拡張子（小文字）
+const ZIP_IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "webp", "avif"];
+
+/// 書き込み中のファイルを開く際の再試行回数
+const OPEN_RETRY_COUNT: u32 = 5;
+
+/// 書き込み中のファイルを開く際の再試行間隔
+const OPEN_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 読み取りのみ共有を許可する共有モード（Win32 `FILE_SHARE_READ`）
+#[cfg(windows)]
+const FILE_SHARE_READ: u32 = 0x0000_0001;
+
+/// 他プロセスが書き込み用に開いているファイルを開こうとした際のエラーコード（Win32 `ERROR_SHARING_VIOLATION`）
+const ERROR_SHARING_VIOLATION: i32 = 32;
+
+/// ZIP変換の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipExportResult {
+    /// 全ファイルを処理した（格納数、スキップ数）
+    Completed { archived: usize, skipped: usize },
+    /// キャンセルフラグにより中断した（作成途中のZIPは削除済み）
+    Cancelled,
+}
+
+/// ZIP圧縮中の出来事（バイナリ側で表示言語に合わせてログに出力する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZipExportEvent {
+    /// 画像をZIPに追加する（`index` は1から）
+    Archiving {
+        file: String,
+        index: usize,
+        total: usize,
+    },
+    /// 書き込み中または読み込めないため、画像をスキップした
+    Skipped { file: String, error: String },
+}
+
+/// フォルダ内のキャプチャ画像をZIPファイルにまとめる
+///
+/// # 引数
+/// * `source_dir` - 画像が格納されたフォルダ
+/// * `output_path` - 出力するZIPファイルのパス
+/// * `cancel_flag` - `true` になった時点で処理を中断する
+/// * `notify` - 進捗・警告の通知先（`ZipExportEvent`）
+pub fn export_image_folder_to_zip(
+    source_dir: &Path,
+    output_path: &Path,
+    cancel_flag: &AtomicBool,
+    notify: &dyn Fn(ZipExportEvent),
+) -> Result<ZipExportResult, Box<dyn std::error::Error>> {
+    if !source_dir.exists() {
+        return Err(format!(
+            "❌ 指定されたフォルダーが存在しません: {}",
+            source_dir.display()
+        )
+        .into());
+    }
+
+    let entries = collect_zip_targets(source_dir)?;
+    if entries.is_empty() {
+        return Err("対象の画像ファイルが見つかりませんでした".into());
+    }
+
+    let total_files = entries.len();
+    let mut writer = ZipWriter::new(BufWriter::new(File::create(output_path)?));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    let mut archived = 0;
+    let mut skipped = 0;
+
+    for (index, path) in entries.iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            drop(writer);
+            let _ = fs::remove_file(output_path);
+            return Ok(ZipExportResult::Cancelled);
+        }
+
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        notify(ZipExportEvent::Archiving {
+            file: filename.clone(),
+            index: index + 1,
+            total: total_files,
+        });
+
+        let mut source_file = match open_completed_file(path) {
+            Ok(file) => file,
+            Err(e) => {
+                notify(ZipExportEvent::Skipped {
+                    file: filename,
+                    error: e.to_string(),
+                });
+                skipped += 1;
+                continue;
+            }
+        };
+
+        writer.start_file(filename.as_str(), options)?;
+        io::copy(&mut source_file, &mut writer)?;
+        archived += 1;
+    }
+
+    writer.finish()?;
+    Ok(ZipExportResult::Completed { archived, skipped })
+}
+
+/// フォルダ内のZIP対象画像を収集し、連番順（`sort_capture_files`）にソートして返す
+fn collect_zip_targets(source_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(source_dir)?
+        .filter_map(|r| r.ok())
+        .map(|e| e.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| {
+                let s = ext.to_string_lossy().to_lowercase();
+                ZIP_IMAGE_EXTENSIONS.contains(&s.as_str())
+            })
+        })
+        .collect();
+
+    sort_capture_files(&mut entries);
+    Ok(entries)
+}
+
+/// 書き込みが完了しているファイルを、書き込み共有を許可せずに開く
+///
+/// キャプチャ保存中のファイルは書き込み用に開かれているため共有違反となります。
+/// その場合は `OPEN_RETRY_INTERVAL` 待って最大 `OPEN_RETRY_COUNT` 回再試行します。
+fn open_completed_file(path: &Path) -> io::Result<File> {
+    let mut last_error = None;
+    for _ in 0..OPEN_RETRY_COUNT {
+        match open_without_write_sharing(path) {
+            Ok(file) => return Ok(file),
+            Err(e) if e.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => {
+                last_error = Some(e);
+                thread::sleep(OPEN_RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::other("ファイルを開けませんでした")))
+}
+
+/// 他プロセスの書き込みを共有せずに、読み取り用にファイルを開く
+#[cfg(windows)]
+fn open_without_write_sharing(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .share_mode(FILE_SHARE_READ)
+        .open(path)
+}
+
+/// 読み取り用にファイルを開く（共有モードのないWindows以外では通常どおり開く）
+#[cfg(not(windows))]
+fn open_without_write_sharing(path: &Path) -> io::Result<File> {
+    File::open(path)
+}